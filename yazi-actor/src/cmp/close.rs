@@ -17,10 +17,12 @@ impl Actor for Close {
 	fn act(cx: &mut Ctx, opt: Self::Options) -> Result<Data> {
 		let cmp = &mut cx.core.cmp;
 		if let Some(item) = cmp.selected().filter(|_| opt.submit).cloned() {
-			return act!(complete, cx.core.input, CompleteOpt { item, _ticket: cmp.ticket });
+			let parent = cmp.parent.clone();
+			return act!(complete, cx.core.input, CompleteOpt { item, parent, _ticket: cmp.ticket });
 		}
 
 		cmp.caches.clear();
+		cmp.aliases.clear();
 		succ!(render!(mem::replace(&mut cmp.visible, false)));
 	}
 }
@@ -1,9 +1,10 @@
-use std::{ffi::OsStr, mem, ops::ControlFlow};
+use std::{ffi::OsStr, mem, path::MAIN_SEPARATOR};
 
 use anyhow::Result;
+use yazi_fs::FRECENCY;
 use yazi_macro::{render, succ};
 use yazi_parser::cmp::{CmpItem, ShowOpt};
-use yazi_shared::{event::Data, osstr_contains, osstr_starts_with};
+use yazi_shared::{event::Data, fuzzy, osstr_contains, osstr_starts_with, url::Url};
 
 use crate::{Actor, Ctx};
 
@@ -30,11 +31,18 @@ impl Actor for Show {
 		};
 
 		cmp.ticket = opt.ticket;
-		cmp.cands = Self::match_candidates(opt.word.as_os_str(), cache);
+		cmp.cands = Self::match_candidates(opt.word.as_os_str(), cache, |item| {
+			FRECENCY.score(&opt.cache_name.join(&item.name))
+		});
 		if cmp.cands.is_empty() {
 			succ!(render!(mem::replace(&mut cmp.visible, false)));
 		}
 
+		cmp.parent = opt.alias.map(|alias| {
+			cmp.aliases.insert(alias, opt.cache_name.clone());
+			Self::parent(&opt.cache_name)
+		});
+
 		cmp.offset = 0;
 		cmp.cursor = 0;
 		cmp.visible = true;
@@ -43,28 +51,82 @@ impl Actor for Show {
 }
 
 impl Show {
-	fn match_candidates(word: &OsStr, cache: &[CmpItem]) -> Vec<CmpItem> {
+	/// Ranks the prefix matches first, then the containing ones, then the fuzzy ones.
+	fn match_candidates(
+		word: &OsStr,
+		cache: &[CmpItem],
+		frecency: impl Fn(&CmpItem) -> f64,
+	) -> Vec<CmpItem> {
 		let smart = !word.as_encoded_bytes().iter().any(|c| c.is_ascii_uppercase());
+		let pattern = word.to_string_lossy();
 
-		let flow = cache.iter().try_fold((Vec::new(), Vec::new()), |(mut exact, mut fuzzy), item| {
-			if osstr_starts_with(&item.name, word, smart) {
-				exact.push(item);
-				if exact.len() >= LIMIT {
-					return ControlFlow::Break((exact, fuzzy));
-				}
-			} else if fuzzy.len() < LIMIT - exact.len() && osstr_contains(&item.name, word) {
-				// Here we don't break the control flow, since we want more exact matching.
-				fuzzy.push(item)
-			}
-			ControlFlow::Continue((exact, fuzzy))
-		});
+		let mut ranked: Vec<_> = cache
+			.iter()
+			.filter_map(|item| {
+				let rank = if osstr_starts_with(&item.name, word, smart) {
+					(0, 0)
+				} else if osstr_contains(&item.name, word, smart) {
+					(1, 0)
+				} else if smart {
+					(2, fuzzy(&pattern, &item.name.to_string_lossy())?)
+				} else {
+					return None;
+				};
+				Some((rank, item))
+			})
+			.collect();
+
+		// Only the frecency of the few best is looked up
+		ranked.sort_by_key(|&((rank, score), _)| (rank, u32::MAX - score));
+		ranked.truncate(LIMIT.max(ranked.partition_point(|&((rank, _), _)| rank == 0)));
+
+		let mut ranked: Vec<_> =
+			ranked.into_iter().map(|((rank, score), item)| (rank, frecency(item), score, item)).collect();
+		ranked.sort_by(|a, b| a.0.cmp(&b.0).then(b.1.total_cmp(&a.1)).then(b.2.cmp(&a.2)));
+		ranked.into_iter().take(LIMIT).map(|(.., item)| item.clone()).collect()
+	}
 
-		let (exact, fuzzy) = match flow {
-			ControlFlow::Continue(v) => v,
-			ControlFlow::Break(v) => v,
+	fn parent(dir: &Url) -> String {
+		let mut s = dir.as_path().map(|p| p.to_string_lossy().into_owned()).unwrap_or_default();
+		if !s.ends_with(MAIN_SEPARATOR) {
+			s.push(MAIN_SEPARATOR);
+		}
+		s
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn names(word: &str, cache: &[&str], frecent: &[&str]) -> Vec<String> {
+		let cache: Vec<_> = cache.iter().map(|&s| CmpItem { name: s.into(), is_dir: true }).collect();
+		let frecency = |item: &CmpItem| {
+			if frecent.contains(&&*item.name.to_string_lossy()) { 1.0 } else { 0.0 }
 		};
 
-		let it = fuzzy.into_iter().take(LIMIT - exact.len());
-		exact.into_iter().chain(it).cloned().collect()
+		let cands = Show::match_candidates(OsStr::new(word), &cache, frecency);
+		cands.into_iter().map(|c| c.name.to_string_lossy().into_owned()).collect()
+	}
+
+	#[test]
+	fn test_match_candidates() {
+		let cache = ["Documents", "docs", "my-docs", "download", "dot_config", "Music"];
+		assert_eq!(names("do", &cache, &[]), [
+			"Documents",
+			"docs",
+			"download",
+			"dot_config",
+			"my-docs"
+		]);
+		assert_eq!(names("Do", &cache, &[]), ["Documents"]);
+		assert_eq!(names("do", &cache, &["download"]), [
+			"download",
+			"Documents",
+			"docs",
+			"dot_config",
+			"my-docs"
+		]);
+		assert_eq!(names("dcs", &cache, &[]), ["docs", "my-docs", "Documents"]);
 	}
 }
@@ -1,11 +1,11 @@
-use std::{ffi::OsString, mem, path::MAIN_SEPARATOR_STR};
+use std::{ffi::OsString, mem, path::{Component, MAIN_SEPARATOR_STR, PathBuf}};
 
 use anyhow::Result;
-use yazi_fs::{CWD, path::expand_url, provider};
+use yazi_fs::{CWD, FRECENCY, path::expand_url, provider};
 use yazi_macro::{act, render, succ};
 use yazi_parser::cmp::{CmpItem, ShowOpt, TriggerOpt};
 use yazi_proxy::CmpProxy;
-use yazi_shared::{OsStrSplit, event::Data, natsort, url::{Url, UrnBuf}};
+use yazi_shared::{OsStrSplit, event::Data, natsort, osstr_starts_with, url::{Url, UrnBuf}};
 
 use crate::{Actor, Ctx};
 
@@ -25,18 +25,31 @@ impl Actor for Trigger {
 			cmp.ticket = t;
 		}
 
-		let Some((parent, word)) = Self::split_url(&opt.word) else {
+		let Some((typed, word)) = Self::split_url(&opt.word) else {
 			return act!(cmp:close, cx, false);
 		};
 
+		let (parent, alias) = match cmp.aliases.get(&typed) {
+			Some(u) => (u.clone(), Some(typed)),
+			None => (typed, None),
+		};
+
+		let ticket = cmp.ticket;
 		if cmp.caches.contains_key(&parent) {
-			let ticket = cmp.ticket;
-			return act!(cmp:show, cx, ShowOpt { cache_name: parent, word, ticket, ..Default::default() });
+			let opt = ShowOpt { cache_name: parent, alias, word, ticket, ..Default::default() };
+			return act!(cmp:show, cx, opt);
 		}
 
-		let ticket = cmp.ticket;
 		tokio::spawn(async move {
-			let mut dir = provider::read_dir(&parent).await?;
+			let (parent, alias, mut dir) = match provider::read_dir(&parent).await {
+				Ok(dir) => (parent, alias, dir),
+				Err(e) => {
+					let Some(resolved) = Self::resolve(&parent).await else { return Err(e.into()) };
+					let dir = provider::read_dir(&resolved).await?;
+					(resolved, Some(parent), dir)
+				}
+			};
+
 			let mut cache = vec![];
 
 			// "/" is both a directory separator and the root directory per se
@@ -55,7 +68,7 @@ impl Actor for Trigger {
 				cache.sort_unstable_by(|a, b| {
 					natsort(a.name.as_encoded_bytes(), b.name.as_encoded_bytes(), false)
 				});
-				CmpProxy::show(ShowOpt { cache, cache_name: parent, word, ticket });
+				CmpProxy::show(ShowOpt { cache, cache_name: parent, alias, word, ticket });
 			}
 
 			Ok::<_, anyhow::Error>(())
@@ -84,6 +97,46 @@ impl Trigger {
 			None => (CWD.load().as_ref().clone(), path.into()),
 		})
 	}
+
+	/// Resolves the components of `dir` that don't exist to the directories they prefix.
+	async fn resolve(dir: &Url) -> Option<Url> {
+		let mut resolved = PathBuf::new();
+		for c in dir.as_path()?.components() {
+			let Component::Normal(typed) = c else {
+				resolved.push(c);
+				continue;
+			};
+
+			if provider::metadata(Url::from(resolved.join(typed))).await.is_ok_and(|m| m.is_dir()) {
+				resolved.push(typed);
+				continue;
+			}
+
+			let smart = !typed.as_encoded_bytes().iter().any(|c| c.is_ascii_uppercase());
+			let mut it = provider::read_dir(Url::from(&resolved)).await.ok()?;
+			let mut best: Option<(f64, OsString)> = None;
+
+			while let Ok(Some(ent)) = it.next_entry().await {
+				let (name, url) = (ent.file_name(), ent.url());
+				if !osstr_starts_with(&name, typed, smart)
+					|| !provider::metadata(&url).await.is_ok_and(|m| m.is_dir())
+				{
+					continue;
+				}
+
+				let score = FRECENCY.score(&url);
+				let better = best.as_ref().is_none_or(|(s, n)| {
+					let first = natsort(name.as_encoded_bytes(), n.as_encoded_bytes(), false).is_lt();
+					score > *s || (score == *s && first)
+				});
+				if better {
+					best = Some((score, name));
+				}
+			}
+			resolved.push(best?.1);
+		}
+		Some(Url::from(resolved))
+	}
 }
 
 #[cfg(test)]
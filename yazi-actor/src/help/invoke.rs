@@ -0,0 +1,46 @@
+use anyhow::Result;
+use yazi_config::{keymap::ChordCow, popup::InputCfg};
+use yazi_core::help::HelpEntry;
+use yazi_macro::{act, emit, succ};
+use yazi_parser::help::InvokeOpt;
+use yazi_proxy::{AppProxy, InputProxy};
+use yazi_shared::event::{CmdCow, Data};
+
+use crate::{Actor, Ctx};
+
+pub struct Invoke;
+
+impl Actor for Invoke {
+	type Options = InvokeOpt;
+
+	const NAME: &str = "invoke";
+
+	fn act(cx: &mut Ctx, opt: Self::Options) -> Result<Data> {
+		let Some(entry) = cx.help.hovered() else { succ!() };
+		let (chord, run) = (entry.chord, entry.run.clone());
+		act!(help:toggle, cx, cx.help.layer)?;
+
+		if opt.prompt {
+			tokio::spawn(async move {
+				let mut input = InputProxy::show(InputCfg::palette(&run));
+				if let Some(Ok(s)) = input.recv().await {
+					Self::run(&s);
+				}
+			});
+		} else if let Some(chord) = chord {
+			emit!(Seq(ChordCow::from(chord).into_seq()));
+		} else {
+			Self::run(&run);
+		}
+		succ!();
+	}
+}
+
+impl Invoke {
+	fn run(s: &str) {
+		match HelpEntry::parse(s) {
+			Ok(cmds) => emit!(Seq(cmds.into_iter().rev().map(CmdCow::from).collect())),
+			Err(e) => AppProxy::notify_error("Palette", e),
+		}
+	}
+}
@@ -1 +1 @@
-yazi_macro::mod_flat!(arrow escape filter toggle);
+yazi_macro::mod_flat!(arrow escape filter invoke palette toggle);
@@ -0,0 +1,30 @@
+use anyhow::Result;
+use yazi_macro::{render, succ};
+use yazi_parser::VoidOpt;
+use yazi_shared::{Layer, event::Data};
+
+use crate::{Actor, Ctx};
+
+pub struct Palette;
+
+impl Actor for Palette {
+	type Options = VoidOpt;
+
+	const NAME: &str = "palette";
+
+	fn act(cx: &mut Ctx, _: Self::Options) -> Result<Data> {
+		let help = &mut cx.help;
+
+		help.visible = true;
+		help.layer = Layer::Mgr;
+		help.palette = true;
+		help.reload();
+
+		help.in_filter = Some(Default::default());
+		help.filter_apply();
+
+		help.offset = 0;
+		help.cursor = 0;
+		succ!(render!());
+	}
+}
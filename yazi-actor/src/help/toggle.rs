@@ -17,8 +17,9 @@ impl Actor for Toggle {
 
 		help.visible = !help.visible;
 		help.layer = opt.layer;
+		help.palette = false;
+		help.reload();
 
-		help.keyword = String::new();
 		help.in_filter = None;
 		help.filter_apply();
 
@@ -2,6 +2,6 @@
 
 extern crate self as yazi_actor;
 
-yazi_macro::mod_pub!(cmp confirm core help input lives mgr pick spot tasks which);
+yazi_macro::mod_pub!(cmp confirm core help input lives mgr notify pick spot tasks which);
 
 yazi_macro::mod_flat!(actor context);
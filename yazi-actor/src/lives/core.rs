@@ -13,6 +13,8 @@ pub(super) struct Core {
 	c_tasks:  Option<Value>,
 	c_yanked: Option<Value>,
 	c_layer:  Option<Value>,
+
+	c_selections: Option<Value>,
 }
 
 impl Deref for Core {
@@ -32,6 +34,8 @@ impl Core {
 			c_tasks:  None,
 			c_yanked: None,
 			c_layer:  None,
+
+			c_selections: None,
 		})
 	}
 }
@@ -56,6 +60,7 @@ impl UserData for Core {
 				b"tabs" => reuse!(tabs, super::Tabs::make(&me.mgr.tabs)),
 				b"tasks" => reuse!(tasks, super::Tasks::make(&me.tasks)),
 				b"yanked" => reuse!(yanked, super::Yanked::make(&me.mgr.yanked)),
+				b"selections" => reuse!(selections, super::Selections::make(&me.mgr.selections)),
 				b"layer" => {
 					reuse!(layer, Ok::<_, mlua::Error>(yazi_plugin::bindings::Layer::from(me.layer())))
 				}
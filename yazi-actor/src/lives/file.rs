@@ -81,7 +81,7 @@ impl UserData for File {
 			})?
 		});
 		methods.add_method("prefix", |lua, me, ()| {
-			if !me.url.has_trail() {
+			if !me.url.has_trail() || me.folder.tree.is_some() {
 				return Ok(None);
 			}
 			let Some(path) = me.url.as_path() else {
@@ -92,6 +92,11 @@ impl UserData for File {
 			comp.next_back();
 			Some(lua.create_string(comp.as_path().as_os_str().as_encoded_bytes())).transpose()
 		});
+		methods.add_method("tree", |_, me, ()| {
+			let Some(tree) = &me.folder.tree else { return Ok((None, None)) };
+			let expanded = me.is_dir().then(|| tree.is_expanded(me.urn()));
+			Ok((Some(me.urn().count() - 1), expanded))
+		});
 		methods.add_method("style", |lua, me, ()| {
 			lua.named_registry_value::<AnyUserData>("cx")?.borrow_scoped(|core: &yazi_core::Core| {
 				let mime = core.mgr.mimetype.by_file(me).unwrap_or_default();
@@ -122,6 +127,23 @@ impl UserData for File {
 			})
 		});
 		methods.add_method("is_selected", |_, me, ()| Ok(me.tab.selected.contains(&me.url)));
+		methods.add_method("tags", |lua, me, ()| {
+			let tags = yazi_fs::TAGS.get(&me.url);
+			let t = lua.create_table_with_capacity(tags.len(), 0)?;
+			for (name, color) in tags {
+				t.raw_push(lua.create_table_from([("name", Some(name)), ("color", color)])?)?;
+			}
+			Ok(t)
+		});
+		methods.add_method("git", |_, me, ()| Ok(yazi_fs::GIT.status(&me.url).map(|s| s.as_str())));
+		methods.add_method("compared", |lua, me, ()| {
+			lua.named_registry_value::<AnyUserData>("cx")?.borrow_scoped(|core: &yazi_core::Core| {
+				let c = core.mgr.compare.as_ref();
+				c.and_then(|c| c.get(&me.folder.url, me.urn()))
+					.map(|d| lua.create_string(d.as_str()))
+					.transpose()
+			})?
+		});
 		methods.add_method("found", |lua, me, ()| {
 			lua.named_registry_value::<AnyUserData>("cx")?.borrow_scoped(|core: &yazi_core::Core| {
 				let Some(finder) = &core.active().finder else {
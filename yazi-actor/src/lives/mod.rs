@@ -1,3 +1,3 @@
 #![allow(clippy::module_inception)]
 
-yazi_macro::mod_flat!(core file files filter finder folder lives mode preference preview ptr selected tab tabs tasks yanked);
+yazi_macro::mod_flat!(core file files filter finder folder lives mode preference preview ptr selected selections tab tabs tasks yanked);
@@ -28,6 +28,7 @@ impl Preview {
 impl UserData for Preview {
 	fn add_fields<F: UserDataFields<Self>>(fields: &mut F) {
 		fields.add_field_method_get("skip", |_, me| Ok(me.skip));
+		fields.add_field_method_get("info", |_, me| Ok(me.info));
 		cached_field!(fields, folder, |_, me| {
 			me.tab
 				.hovered_folder()
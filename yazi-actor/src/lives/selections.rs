@@ -0,0 +1,52 @@
+use std::ops::Deref;
+
+use mlua::{AnyUserData, MetaMethod, MultiValue, ObjectLike, UserData, UserDataMethods};
+use yazi_binding::{Iter, get_metatable};
+
+use super::{Lives, PtrCell};
+
+pub(super) struct Selections {
+	inner: PtrCell<yazi_core::mgr::Selections>,
+	names: AnyUserData,
+}
+
+impl Deref for Selections {
+	type Target = yazi_core::mgr::Selections;
+
+	fn deref(&self) -> &Self::Target { &self.inner }
+}
+
+impl Selections {
+	#[inline]
+	pub(super) fn make(inner: &yazi_core::mgr::Selections) -> mlua::Result<AnyUserData> {
+		let inner = PtrCell::from(inner);
+
+		Lives::scoped_userdata(Self {
+			inner,
+			names: Lives::scoped_userdata(Iter::new(
+				inner.as_static().keys().map(String::as_str),
+				Some(inner.len()),
+			))?,
+		})
+	}
+}
+
+impl UserData for Selections {
+	fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+		methods.add_meta_method(MetaMethod::Len, |_, me, ()| Ok(me.len()));
+
+		methods.add_meta_method(MetaMethod::Index, |_, me, name: mlua::String| {
+			let Some(urls) = me.get(&*name.to_str()?) else { return Ok(None) };
+			Lives::scoped_userdata(Iter::new(
+				urls.iter().cloned().map(yazi_binding::Url::new),
+				Some(urls.len()),
+			))
+			.map(Some)
+		});
+
+		methods.add_meta_function(MetaMethod::Pairs, |lua, ud: AnyUserData| {
+			let names = ud.borrow::<Self>()?.names.clone();
+			get_metatable(lua, &names)?.call_function::<MultiValue>(MetaMethod::Pairs.name(), names)
+		});
+	}
+}
@@ -24,6 +24,7 @@ impl Tabs {
 impl UserData for Tabs {
 	fn add_fields<F: UserDataFields<Self>>(fields: &mut F) {
 		fields.add_field_method_get("idx", |_, me| Ok(me.cursor + 1));
+		fields.add_field_method_get("other", |_, me| me.other().map(Tab::make).transpose());
 	}
 
 	fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
@@ -24,8 +24,8 @@ impl Yanked {
 		Lives::scoped_userdata(Self {
 			inner,
 			iter: Lives::scoped_userdata(Iter::new(
-				inner.as_static().iter().cloned().map(yazi_binding::Url::new),
-				Some(inner.len()),
+				inner.as_static().expand().map(yazi_binding::Url::new),
+				Some(inner.count()),
 			))?,
 		})
 	}
@@ -37,7 +37,7 @@ impl UserData for Yanked {
 	}
 
 	fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
-		methods.add_meta_method(MetaMethod::Len, |_, me, ()| Ok(me.len()));
+		methods.add_meta_method(MetaMethod::Len, |_, me, ()| Ok(me.count()));
 
 		methods.add_meta_function(MetaMethod::Pairs, |lua, ud: AnyUserData| {
 			let me = ud.borrow::<Self>()?;
@@ -0,0 +1,138 @@
+use std::{io, time::Duration};
+
+use anyhow::Result;
+use futures::future::join_all;
+use yazi_config::popup::PickCfg;
+use yazi_fs::{BOOKMARKS, provider};
+use yazi_macro::succ;
+use yazi_parser::{app::{NotifyLevel, NotifyOpt}, mgr::{BookmarkOpt, BookmarkOptAction}};
+use yazi_proxy::{AppProxy, MgrProxy, PickProxy};
+use yazi_scheduler::readable_size;
+use yazi_shared::{event::Data, natsort, url::Url};
+
+use crate::{Actor, Ctx};
+
+pub struct Bookmark;
+
+impl Actor for Bookmark {
+	type Options = BookmarkOpt;
+
+	const NAME: &str = "bookmark";
+
+	fn act(cx: &mut Ctx, opt: Self::Options) -> Result<Data> {
+		match opt.action {
+			BookmarkOptAction::Set => {
+				let url = match cx.hovered().filter(|_| !opt.cwd) {
+					Some(h) => &h.url,
+					None => cx.cwd(),
+				};
+				Self::set(opt.key.as_deref(), url);
+			}
+			BookmarkOptAction::Jump => {
+				let key = opt.key.map(|k| k.into_owned());
+				tokio::spawn(async move {
+					if let Some(url) = Self::target(key.as_deref()).await {
+						Self::jump(url).await;
+					}
+				});
+			}
+			BookmarkOptAction::Delete => {
+				let key = opt.key.unwrap_or_default();
+				match BOOKMARKS.remove(&key) {
+					Ok(Some(_)) => {}
+					Ok(None) => AppProxy::notify_warn("Bookmark", format!("No bookmark `{key}`")),
+					Err(e) => AppProxy::notify_warn("Bookmark", format!("Could not save: {e}")),
+				}
+			}
+		}
+		succ!();
+	}
+}
+
+impl Bookmark {
+	fn set(key: Option<&str>, url: &Url) {
+		let vacant = key.is_none();
+		let Some(key) = key.map(ToOwned::to_owned).or_else(|| BOOKMARKS.vacant().map(String::from))
+		else {
+			return AppProxy::notify_warn("Bookmark", "All the letters from `a` to `z` are taken");
+		};
+
+		if let Err(e) = BOOKMARKS.set(&key, url) {
+			return AppProxy::notify_warn("Bookmark", format!("Could not save: {e}"));
+		}
+
+		// Tell the letter it's been given, since it wasn't chosen
+		if vacant {
+			AppProxy::notify(NotifyOpt {
+				title:   "Bookmark".to_owned(),
+				content: format!("Bookmarked `{}` as `{key}`", url.display()),
+				level:   NotifyLevel::Info,
+				timeout: Duration::from_secs(3),
+				action:  None,
+			});
+		}
+	}
+
+	async fn target(key: Option<&str>) -> Option<Url> {
+		if let Some(key) = key {
+			let url = BOOKMARKS.get(key);
+			if url.is_none() {
+				AppProxy::notify_warn("Bookmark", format!("No bookmark `{key}`"));
+			}
+			return url;
+		}
+
+		let mut marks = BOOKMARKS.list();
+		if marks.is_empty() {
+			AppProxy::notify_warn("Bookmark", "No bookmarks yet");
+			return None;
+		}
+
+		let items = join_all(marks.iter().map(|(k, u)| Self::describe(k, u))).await;
+		let choice = PickProxy::show(PickCfg::bookmark(items)).await.ok()?;
+		Some(marks.swap_remove(choice).1)
+	}
+
+	// Directories are entered, and files are revealed in their parent
+	async fn jump(url: Url) {
+		match provider::metadata(&url).await {
+			Ok(m) if m.is_dir() => MgrProxy::cd(&url),
+			Ok(_) => MgrProxy::reveal(&url),
+			Err(e) if e.kind() == io::ErrorKind::NotFound => AppProxy::notify_warn(
+				"Bookmark",
+				format!("`{}` no longer exists", url.display()),
+			),
+			// Schemes that can't be checked here are left for `cd` to deal with
+			Err(_) => MgrProxy::cd(&url),
+		}
+	}
+
+	/// The key and the target, with a glimpse of the target.
+	async fn describe(key: &str, url: &Url) -> String {
+		let preview = match provider::metadata(url).await {
+			Ok(m) if m.is_dir() => Self::entries(url).await,
+			Ok(m) => readable_size(m.len()),
+			Err(e) if e.kind() == io::ErrorKind::NotFound => "missing".to_owned(),
+			Err(_) => "unavailable".to_owned(),
+		};
+		format!("{key}  {}  ({preview})", url.display())
+	}
+
+	async fn entries(dir: &Url) -> String {
+		const SHOWN: usize = 3;
+
+		let Ok(mut it) = provider::read_dir(dir).await else { return "unavailable".to_owned() };
+		let mut names = vec![];
+		while let Ok(Some(ent)) = it.next_entry().await {
+			names.push(ent.file_name());
+		}
+
+		names.sort_unstable_by(|a, b| natsort(a.as_encoded_bytes(), b.as_encoded_bytes(), false));
+		let shown: Vec<_> = names.iter().take(SHOWN).map(|n| n.to_string_lossy()).collect();
+		match names.len() {
+			0 => "empty".to_owned(),
+			n if n <= SHOWN => shown.join(", "),
+			n => format!("{}, and {} more", shown.join(", "), n - SHOWN),
+		}
+	}
+}
@@ -1,4 +1,4 @@
-use std::{borrow::Cow, collections::HashMap, ffi::{OsStr, OsString}, hash::Hash, io::{Read, Write}, ops::Deref, path::Path};
+use std::{borrow::Cow, cmp::Reverse, collections::{HashMap, HashSet}, ffi::{OsStr, OsString}, hash::Hash, io::{Read, Write}, ops::Deref, path::Path};
 
 use anyhow::{Result, anyhow};
 use crossterm::{execute, style::Print};
@@ -6,10 +6,11 @@ use scopeguard::defer;
 use tokio::io::AsyncWriteExt;
 use yazi_config::{YAZI, opener::OpenerRule};
 use yazi_dds::Pubsub;
-use yazi_fs::{File, FilesOp, max_common_root, maybe_exists, path::skip_url, paths_to_same_file, provider::{self, local::{Gate, Local}}};
+use yazi_fs::{AUDIT, AuditOp, File, FilesOp, JournalOp, Rename, RenameConflict, Renamer, max_common_root, maybe_exists, path::skip_url, paths_to_same_file, provider::{self, local::{Gate, Local}}};
 use yazi_macro::{err, succ};
-use yazi_parser::VoidOpt;
+use yazi_parser::mgr::BulkRenameOpt;
 use yazi_proxy::{AppProxy, HIDER, TasksProxy, WATCHER};
+use yazi_scheduler::{SUMMARY, SummaryKind};
 use yazi_shared::{OsStrJoin, event::Data, terminal_clear, url::{Component, Url}};
 use yazi_term::tty::TTY;
 
@@ -18,11 +19,25 @@ use crate::{Actor, Ctx};
 pub struct BulkRename;
 
 impl Actor for BulkRename {
-	type Options = VoidOpt;
+	type Options = BulkRenameOpt;
 
 	const NAME: &str = "bulk_rename";
 
-	fn act(cx: &mut Ctx, _: Self::Options) -> Result<Data> {
+	fn act(cx: &mut Ctx, opt: Self::Options) -> Result<Data> {
+		if let Some(expr) = opt.expr {
+			let renamer: Renamer = match expr.parse() {
+				Ok(r) => r,
+				Err(e) => succ!(AppProxy::notify_warn("Bulk rename", format!("{e:#}"))),
+			};
+
+			let selected: Vec<_> = cx.tab().selected_or_hovered().cloned().collect();
+			if selected.is_empty() {
+				succ!(AppProxy::notify_warn("Bulk rename", "No files selected"));
+			}
+			tokio::spawn(Self::review(renamer.plan(&selected)));
+			succ!();
+		}
+
 		let Some(opener) = Self::opener() else {
 			succ!(AppProxy::notify_warn("Bulk rename", "No text opener found"));
 		};
@@ -115,9 +130,12 @@ impl BulkRename {
 
 			if maybe_exists(&new).await && !paths_to_same_file(&old, &new).await {
 				failed.push((o, n, anyhow!("Destination already exists")));
-			} else if let Err(e) = provider::rename(&old, &new).await {
+			} else if let Err(e) =
+				AUDIT.done(AuditOp::Rename, Some(&old), &new, provider::rename(&old, &new).await)
+			{
 				failed.push((o, n, e.into()));
 			} else if let Ok(f) = File::new(new).await {
+				SUMMARY.record(SummaryKind::Renamed, 0);
 				succeeded.insert(old, f);
 			} else {
 				failed.push((o, n, anyhow!("Failed to retrieve file info")));
@@ -127,6 +145,9 @@ impl BulkRename {
 		if !succeeded.is_empty() {
 			let it = succeeded.iter().map(|(o, n)| (o, &n.url));
 			err!(Pubsub::pub_after_bulk(it));
+			for (o, n) in &succeeded {
+				JournalOp::Rename { from: o.clone(), to: n.url_owned() }.emit();
+			}
 			FilesOp::rename(succeeded);
 		}
 		drop(permit);
@@ -137,6 +158,159 @@ impl BulkRename {
 		Ok(())
 	}
 
+	/// Previews the renames planned, and applies them all at once if confirmed.
+	pub(super) async fn review(mut plans: Vec<Rename>) -> Result<()> {
+		plans.retain(|p| p.from != p.to || p.conflict.is_some());
+		if plans.is_empty() {
+			return Ok(());
+		}
+
+		let from: HashSet<_> = plans.iter().map(|p| p.from.clone()).collect();
+		for p in &mut plans {
+			if p.conflict.is_none()
+				&& !from.contains(&p.to)
+				&& maybe_exists(&p.to).await
+				&& !paths_to_same_file(&p.from, &p.to).await
+			{
+				p.conflict = Some(RenameConflict::Exists);
+			}
+		}
+
+		let _permit = HIDER.acquire().await.unwrap();
+		defer!(AppProxy::resume());
+		AppProxy::stop().await;
+
+		terminal_clear(TTY.writer())?;
+		let conflicts = plans.iter().filter(|p| p.conflict.is_some()).count();
+		{
+			let mut w = TTY.lockout();
+			for p in &plans {
+				let reason = match p.conflict {
+					None => "",
+					Some(RenameConflict::Invalid) => "  [invalid name]",
+					Some(RenameConflict::Duplicate) => "  [duplicate]",
+					Some(RenameConflict::Exists) => "  [already exists]",
+				};
+				writeln!(w, "{} -> {}{reason}", p.from.display(), p.to.name().display())?;
+			}
+
+			if conflicts > 0 {
+				write!(w, "\n{conflicts} conflict(s), nothing is renamed. Press <Enter> to exit...")?;
+			} else {
+				write!(w, "\nContinue to rename? (y/N): ")?;
+			}
+			w.flush()?;
+		}
+
+		let mut buf = [0; 10];
+		_ = TTY.reader().read(&mut buf)?;
+		if conflicts > 0 || (buf[0] != b'y' && buf[0] != b'Y') {
+			return Ok(());
+		}
+
+		let permit = WATCHER.acquire().await.unwrap();
+		let result = Self::apply(&plans).await;
+		drop(permit);
+
+		match result {
+			Ok(succeeded) => {
+				// Journaled with the parent directories under their new names, and before what's
+				// in them, so undoing renames what's in them back first
+				plans.sort_by_key(|p| p.from.components().count());
+				let moved = Self::moved(&plans);
+				for p in &plans {
+					let (from, to) = (Self::relocate(&p.from, &moved), Self::relocate(&p.to, &moved));
+					JournalOp::Rename { from, to }.emit();
+				}
+
+				let it = succeeded.iter().map(|(o, n)| (o, &n.url));
+				err!(Pubsub::pub_after_bulk(it));
+				FilesOp::rename(succeeded);
+				Ok(())
+			}
+			Err(e) => {
+				let mut stdout = TTY.lockout();
+				terminal_clear(&mut *stdout)?;
+				writeln!(stdout, "Failed to rename: {e:#}\n\nPress ENTER to exit")?;
+
+				stdout.flush()?;
+				TTY.reader().read_exact(&mut [0])?;
+				Ok(())
+			}
+		}
+	}
+
+	/// Renames all the files or none of them, through a temporary name each.
+	async fn apply(plans: &[Rename]) -> Result<HashMap<Url, File>> {
+		let temp: Vec<_> = plans
+			.iter()
+			.enumerate()
+			.map(|(i, p)| {
+				let mut u = p.from.clone();
+				u.set_name(format!(".yazi-rename-{}-{i}", std::process::id()));
+				u
+			})
+			.collect();
+
+		let depth: Vec<_> = plans.iter().map(|p| p.from.components().count()).collect();
+		let mut order: Vec<_> = (0..plans.len()).collect();
+		order.sort_by_key(|&i| Reverse(depth[i]));
+
+		let mut steps = Vec::with_capacity(plans.len() * 2);
+		for level in order.chunk_by(|&a, &b| depth[a] == depth[b]) {
+			steps.extend(level.iter().map(|&i| (&plans[i].from, &temp[i])));
+			steps.extend(level.iter().map(|&i| (&temp[i], &plans[i].to)));
+		}
+
+		let mut done: Vec<(&Url, &Url)> = Vec::with_capacity(plans.len() * 2);
+		for (from, to) in steps {
+			let result = if maybe_exists(to).await && !paths_to_same_file(from, to).await {
+				Err(anyhow!("`{}` already exists", to.display()))
+			} else {
+				provider::rename(from, to).await.map_err(|e| anyhow!("`{}`: {e}", from.display()))
+			};
+
+			if let Err(e) = result {
+				for &(from, to) in done.iter().rev() {
+					provider::rename(to, from).await.ok();
+				}
+				for p in plans {
+					AUDIT.done(AuditOp::Rename, Some(&p.from), &p.to, Err::<(), _>(&e)).ok();
+				}
+				return Err(e.context("Nothing has been renamed"));
+			}
+			done.push((from, to));
+		}
+
+		let moved = Self::moved(plans);
+		let mut succeeded = HashMap::with_capacity(plans.len());
+		for p in plans {
+			AUDIT.done(AuditOp::Rename, Some(&p.from), &p.to, anyhow::Ok(())).ok();
+			if let Ok(f) = File::new(Self::relocate(&p.to, &moved)).await {
+				SUMMARY.record(SummaryKind::Renamed, 0);
+				succeeded.insert(p.from.clone(), f);
+			}
+		}
+		Ok(succeeded)
+	}
+
+	#[inline]
+	fn moved(plans: &[Rename]) -> HashMap<&Url, &Url> {
+		plans.iter().map(|p| (&p.from, &p.to)).collect()
+	}
+
+	/// Where `url` is after the renames, as its parent directories may have been renamed too.
+	fn relocate(url: &Url, moved: &HashMap<&Url, &Url>) -> Url {
+		let mut parent = url.parent_url();
+		while let Some(p) = parent {
+			if let Some(&to) = moved.get(&p) {
+				return Self::relocate(to, moved).join(skip_url(url, p.components().count()));
+			}
+			parent = p.parent_url();
+		}
+		url.clone()
+	}
+
 	fn opener() -> Option<&'static OpenerRule> {
 		YAZI.opener.block(YAZI.open.all(Url::from(Path::new("bulk-rename.txt")), "text/plain"))
 	}
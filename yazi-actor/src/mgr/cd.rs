@@ -5,7 +5,7 @@ use tokio::pin;
 use tokio_stream::{StreamExt, wrappers::UnboundedReceiverStream};
 use yazi_config::popup::InputCfg;
 use yazi_dds::Pubsub;
-use yazi_fs::{File, FilesOp, path::expand_url};
+use yazi_fs::{FRECENCY, File, FilesOp, path::expand_url};
 use yazi_macro::{act, err, render, succ};
 use yazi_parser::mgr::CdOpt;
 use yazi_proxy::{CmpProxy, InputProxy, MgrProxy};
@@ -46,6 +46,10 @@ impl Actor for Cd {
 			}
 		}
 
+		if opt.target.is_regular() {
+			FRECENCY.visit(&opt.target);
+		}
+
 		// Current
 		let rep = tab.history.remove_or(&opt.target);
 		let rep = mem::replace(&mut tab.current, rep);
@@ -56,6 +60,8 @@ impl Actor for Cd {
 			tab.parent = Some(tab.history.remove_or(&parent));
 		}
 
+		tab.restore_view();
+
 		err!(Pubsub::pub_after_cd(tab.id, tab.cwd()));
 		act!(mgr:hidden, cx)?;
 		act!(mgr:sort, cx)?;
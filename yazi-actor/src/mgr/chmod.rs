@@ -0,0 +1,107 @@
+use std::{fs::Metadata, io};
+
+use anyhow::Result;
+use yazi_config::popup::InputCfg;
+use yazi_fs::{File, FilesOp, provider, walk};
+use yazi_macro::{act, succ};
+use yazi_parser::mgr::ChmodOpt;
+use yazi_proxy::{AppProxy, InputProxy};
+use yazi_shared::{event::Data, url::Url};
+
+use crate::{Actor, Ctx};
+
+pub struct Chmod;
+
+impl Actor for Chmod {
+	type Options = ChmodOpt;
+
+	const NAME: &str = "chmod";
+
+	fn act(cx: &mut Ctx, mut opt: Self::Options) -> Result<Data> {
+		act!(mgr:escape_visual, cx)?;
+
+		let urls: Vec<_> = cx.tab().selected_or_hovered().cloned().collect();
+		if urls.is_empty() {
+			succ!();
+		}
+
+		#[cfg(unix)]
+		let current = cx.hovered().map(|h| format!("{:03o}", h.cha.mode & 0o7777));
+		#[cfg(not(unix))]
+		let current = None::<String>;
+
+		let input = opt.mode.is_none().then(|| {
+			InputProxy::show(InputCfg::chmod(urls.len()).with_value(current.unwrap_or_default()))
+		});
+
+		tokio::spawn(async move {
+			if let Some(mut rx) = input {
+				match rx.recv().await {
+					Some(Ok(s)) => opt.mode = Some(s.into()),
+					_ => return,
+				}
+			}
+
+			let mode = opt.mode.unwrap_or_default();
+			if mode.trim().is_empty() {
+				return;
+			}
+
+			match mode.parse::<yazi_fs::Chmod>() {
+				Ok(mode) => {
+					Self::each("Change mode", urls, opt.recursive, async |url, meta| {
+						mode.set(url, meta).await
+					})
+					.await
+				}
+				Err(e) => AppProxy::notify_warn("Change mode", format!("Invalid mode `{mode}`: {e}")),
+			}
+		});
+		succ!();
+	}
+}
+
+impl Chmod {
+	/// Calls `f` on each of the files, then warns about the ones it failed on.
+	pub(super) async fn each(
+		title: &str,
+		urls: Vec<Url>,
+		recursive: bool,
+		f: impl AsyncFn(&Url, &Metadata) -> io::Result<()>,
+	) {
+		let (mut total, mut failed, mut last) = (0, 0, None);
+		for url in &urls {
+			let files = if recursive {
+				walk(url).await
+			} else {
+				provider::symlink_metadata(url).await.map(|m| vec![(url.clone(), m)])
+			};
+
+			let files = match files {
+				Ok(files) => files,
+				Err(e) => {
+					(total, failed, last) = (total + 1, failed + 1, Some(e));
+					continue;
+				}
+			};
+
+			for (url, meta) in files {
+				total += 1;
+				if let Err(e) = f(&url, &meta).await {
+					(failed, last) = (failed + 1, Some(e));
+				}
+			}
+		}
+
+		if let Some(e) = last {
+			AppProxy::notify_warn(title, format!("Failed on {failed} of {total} files: {e}"));
+		}
+
+		// Directories that aren't watched, such as the ones on MTP devices
+		for url in urls {
+			if let (Some(parent), Ok(file)) = (url.parent_url(), File::new(url).await) {
+				FilesOp::Upserting(parent, [(file.urn_owned(), file)].into()).emit();
+			}
+		}
+	}
+}
@@ -0,0 +1,57 @@
+use anyhow::Result;
+use yazi_config::popup::InputCfg;
+use yazi_macro::{act, succ};
+use yazi_parser::mgr::ChownOpt;
+use yazi_proxy::{AppProxy, InputProxy};
+use yazi_shared::event::Data;
+
+use crate::{Actor, Ctx, mgr::Chmod};
+
+pub struct Chown;
+
+impl Actor for Chown {
+	type Options = ChownOpt;
+
+	const NAME: &str = "chown";
+
+	fn act(cx: &mut Ctx, mut opt: Self::Options) -> Result<Data> {
+		act!(mgr:escape_visual, cx)?;
+
+		let urls: Vec<_> = cx.tab().selected_or_hovered().cloned().collect();
+		if urls.is_empty() {
+			succ!();
+		}
+
+		#[cfg(unix)]
+		let current = cx.hovered().map(|h| yazi_fs::Chown::display(h.cha.uid, h.cha.gid));
+		#[cfg(not(unix))]
+		let current = None::<String>;
+
+		let input = opt.owner.is_none().then(|| {
+			InputProxy::show(InputCfg::chown(urls.len()).with_value(current.unwrap_or_default()))
+		});
+
+		tokio::spawn(async move {
+			if let Some(mut rx) = input {
+				match rx.recv().await {
+					Some(Ok(s)) => opt.owner = Some(s.into()),
+					_ => return,
+				}
+			}
+
+			let owner = opt.owner.unwrap_or_default();
+			if owner.trim().is_empty() {
+				return;
+			}
+
+			match owner.parse::<yazi_fs::Chown>() {
+				Ok(owner) => {
+					Chmod::each("Change owner", urls, opt.recursive, async |url, _| owner.set(url).await)
+						.await
+				}
+				Err(e) => AppProxy::notify_warn("Change owner", format!("Invalid owner `{owner}`: {e}")),
+			}
+		});
+		succ!();
+	}
+}
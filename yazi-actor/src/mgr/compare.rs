@@ -0,0 +1,138 @@
+use anyhow::Result;
+use yazi_fs::{Comparison, Filter};
+use yazi_macro::{act, render, succ};
+use yazi_parser::mgr::{CompareOpt, CompareOptAction};
+use yazi_proxy::AppProxy;
+use yazi_shared::{event::Data, url::CovUrl};
+
+use crate::{Actor, Ctx};
+
+pub struct Compare;
+
+impl Actor for Compare {
+	type Options = CompareOpt;
+
+	const NAME: &str = "compare";
+
+	fn act(cx: &mut Ctx, opt: Self::Options) -> Result<Data> {
+		match opt.action {
+			CompareOptAction::Start => Self::start(cx, opt),
+			CompareOptAction::Stop => Self::stop(cx),
+			CompareOptAction::Filter => Self::filter(cx),
+			CompareOptAction::Sync => Self::sync(cx),
+			CompareOptAction::Resolve => Self::resolve(cx, opt),
+		}
+	}
+}
+
+impl Compare {
+	fn start(cx: &mut Ctx, opt: CompareOpt) -> Result<Data> {
+		let Some(right) = opt.url.or_else(|| cx.tabs().other().map(|t| t.cwd().clone())) else {
+			succ!(AppProxy::notify_warn(
+				"Compare",
+				"Nothing to compare with, turn on the dual-panel layout or give a directory"
+			));
+		};
+
+		let left = cx.cwd().clone();
+		if left == right {
+			succ!(AppProxy::notify_warn("Compare", "Cannot compare a directory with itself"));
+		}
+
+		tokio::spawn(async move {
+			match Comparison::new(left, right, opt.by).await {
+				Ok(c) => c.emit(),
+				Err(e) => AppProxy::notify_warn("Compare", format!("Failed to compare: {e}")),
+			}
+		});
+		succ!();
+	}
+
+	fn stop(cx: &mut Ctx) -> Result<Data> {
+		let Some(c) = &mut cx.mgr.compare else { succ!() };
+		if c.filter {
+			c.filter = false;
+			Self::refilter(cx)?;
+		}
+
+		cx.mgr.compare = None;
+		succ!(render!());
+	}
+
+	fn filter(cx: &mut Ctx) -> Result<Data> {
+		let Some(c) = &mut cx.mgr.compare else { succ!() };
+		c.filter = !c.filter;
+
+		Self::refilter(cx)?;
+		succ!(render!());
+	}
+
+	fn sync(cx: &mut Ctx) -> Result<Data> {
+		let Some(c) = &cx.mgr.compare else { succ!() };
+		let cwd = cx.cwd();
+		let dest = if *cwd == c.left {
+			c.right.clone()
+		} else if *cwd == c.right {
+			c.left.clone()
+		} else {
+			succ!(AppProxy::notify_warn("Compare", "Not in one of the compared directories"));
+		};
+
+		// Files only on the other side can't be synced from here, as they aren't
+		let src: Vec<CovUrl> = cx
+			.tab()
+			.selected_or_hovered()
+			.filter(|&u| u.parent_url().as_ref() == Some(cwd) && c.get(cwd, u.urn()).is_some())
+			.cloned()
+			.map(CovUrl)
+			.collect();
+		if src.is_empty() {
+			succ!();
+		}
+
+		// Each is resolved by its own task once copied, as some might fail
+		cx.tasks.file_sync(&src, &dest);
+		succ!();
+	}
+
+	fn resolve(cx: &mut Ctx, opt: CompareOpt) -> Result<Data> {
+		let Some(url) = opt.url else { succ!() };
+		let Some(c) = &mut cx.mgr.compare else { succ!() };
+
+		let parent = url.parent_url();
+		if parent.as_ref() != Some(&c.left) && parent.as_ref() != Some(&c.right) {
+			succ!();
+		}
+
+		c.resolve([url.urn()]);
+		if c.filter {
+			Self::refilter(cx)?;
+		}
+		succ!(render!());
+	}
+
+	/// Shows only the differences in the tabs in one of the compared directories.
+	pub(super) fn refilter(cx: &mut Ctx) -> Result<()> {
+		let mgr = &mut cx.mgr;
+		let Some(c) = &mgr.compare else { return Ok(()) };
+
+		for tab in mgr.tabs.iter_mut() {
+			let url = &tab.current.url;
+			if *url != c.left && *url != c.right {
+				continue;
+			}
+
+			let filter =
+				if c.filter { Some(Filter::exact("differences", c.differences(url))?) } else { None };
+			let hovered = tab.hovered().map(|f| f.urn_owned());
+			if tab.current.files.set_filter(filter) {
+				tab.current.repos(hovered.as_deref());
+			}
+		}
+
+		act!(mgr:hover, cx)?;
+		act!(mgr:peek, cx)?;
+		act!(mgr:watch, cx)?;
+		Ok(())
+	}
+}
@@ -1,6 +1,6 @@
 use anyhow::Result;
 use yazi_config::popup::{ConfirmCfg, InputCfg};
-use yazi_fs::{File, FilesOp, maybe_exists, ok_or_not_found, provider, realname};
+use yazi_fs::{AUDIT, AuditOp, File, FilesOp, JournalOp, maybe_exists, ok_or_not_found, provider, realname};
 use yazi_macro::succ;
 use yazi_parser::mgr::CreateOpt;
 use yazi_proxy::{ConfirmProxy, InputProxy, MgrProxy, WATCHER};
@@ -33,28 +33,21 @@ impl Actor for Create {
 				return;
 			}
 
-			_ = Self::r#do(new, opt.dir || name.ends_with('/') || name.ends_with('\\')).await;
+			let dir = opt.dir || name.ends_with('/') || name.ends_with('\\');
+			if Self::r#do(new.clone(), dir).await.is_ok() {
+				JournalOp::Create { target: new, dir }.emit();
+			}
 		});
 		succ!();
 	}
 }
 
 impl Create {
-	async fn r#do(new: Url, dir: bool) -> Result<()> {
+	pub(crate) async fn r#do(new: Url, dir: bool) -> Result<()> {
 		let Some(parent) = new.parent_url() else { return Ok(()) };
 		let _permit = WATCHER.acquire().await.unwrap();
 
-		if dir {
-			provider::create_dir_all(&new).await?;
-		} else if let Some(real) = realname(&new).await {
-			ok_or_not_found(provider::remove_file(&new).await)?;
-			FilesOp::Deleting(parent.clone(), [UrnBuf::from(real)].into()).emit();
-			provider::create(&new).await?;
-		} else {
-			provider::create_dir_all(&parent).await.ok();
-			ok_or_not_found(provider::remove_file(&new).await)?;
-			provider::create(&new).await?;
-		}
+		AUDIT.done(AuditOp::Create, None, &new, Self::create(&new, &parent, dir).await)?;
 
 		if let Ok(f) = File::new(new.clone()).await {
 			FilesOp::Upserting(parent, [(f.urn_owned(), f)].into()).emit();
@@ -62,4 +55,19 @@ impl Create {
 		}
 		Ok(())
 	}
+
+	async fn create(new: &Url, parent: &Url, dir: bool) -> Result<()> {
+		if dir {
+			provider::create_dir_all(new).await?;
+		} else if let Some(real) = realname(new).await {
+			ok_or_not_found(provider::remove_file(new).await)?;
+			FilesOp::Deleting(parent.clone(), [UrnBuf::from(real)].into()).emit();
+			provider::create(new).await?;
+		} else {
+			provider::create_dir_all(parent).await.ok();
+			ok_or_not_found(provider::remove_file(new).await)?;
+			provider::create(new).await?;
+		}
+		Ok(())
+	}
 }
@@ -0,0 +1,39 @@
+use anyhow::Result;
+use yazi_config::popup::InputCfg;
+use yazi_macro::succ;
+use yazi_parser::mgr::DownloadOpt;
+use yazi_proxy::{AppProxy, InputProxy, MgrProxy};
+use yazi_scheduler::file;
+use yazi_shared::event::Data;
+
+use crate::{Actor, Ctx};
+
+pub struct Download;
+
+impl Actor for Download {
+	type Options = DownloadOpt;
+
+	const NAME: &str = "download";
+
+	fn act(cx: &mut Ctx, opt: Self::Options) -> Result<Data> {
+		let Some(url) = opt.url else {
+			let mut input = InputProxy::show(InputCfg::download());
+			tokio::spawn(async move {
+				if let Some(Ok(url)) = input.recv().await
+					&& !url.trim().is_empty()
+				{
+					MgrProxy::download(url.trim().to_owned());
+				}
+			});
+			succ!();
+		};
+
+		if !file::Download::valid(&url) {
+			AppProxy::notify_warn("Download", format!("`{url}` is not an HTTP(S) URL"));
+			succ!();
+		}
+
+		cx.core.tasks.file_download(url.into_owned(), cx.cwd());
+		succ!();
+	}
+}
@@ -0,0 +1,90 @@
+use anyhow::Result;
+use yazi_macro::{act, render, succ};
+use yazi_parser::mgr::{DualOpt, DualOptAction, PasteOptConflict, TabCreateOpt};
+use yazi_proxy::AppProxy;
+use yazi_scheduler::file::Conflict;
+use yazi_shared::{event::Data, url::CovUrl};
+
+use crate::{Actor, Ctx};
+
+pub struct Dual;
+
+impl Actor for Dual {
+	type Options = DualOpt;
+
+	const NAME: &str = "dual";
+
+	fn act(cx: &mut Ctx, opt: Self::Options) -> Result<Data> {
+		match opt.action {
+			DualOptAction::Toggle if cx.tabs().other.is_some() => Self::off(cx),
+			DualOptAction::Toggle | DualOptAction::On => Self::on(cx),
+			DualOptAction::Off => Self::off(cx),
+			DualOptAction::Focus => Self::focus(cx),
+			DualOptAction::Copy => Self::transfer(cx, false, opt.force),
+			DualOptAction::Move => Self::transfer(cx, true, opt.force),
+		}
+	}
+}
+
+impl Dual {
+	fn on(cx: &mut Ctx) -> Result<Data> {
+		if cx.tabs().other.is_some() {
+			succ!();
+		}
+
+		// Open the other panel in the same directory, as a new tab
+		if cx.tabs().len() < 2 {
+			act!(mgr:tab_create, cx, TabCreateOpt { wd: Some(cx.cwd().to_regular()) })?;
+		}
+
+		let tabs = cx.tabs_mut();
+		let idx = if tabs.cursor + 1 < tabs.len() { tabs.cursor + 1 } else { tabs.cursor - 1 };
+		tabs.other = Some(tabs[idx].id);
+
+		act!(mgr:watch, cx)?;
+		succ!(render!());
+	}
+
+	fn off(cx: &mut Ctx) -> Result<Data> {
+		if cx.tabs_mut().other.take().is_none() {
+			succ!();
+		}
+
+		act!(mgr:watch, cx)?;
+		act!(mgr:peek, cx, true)?;
+		succ!(render!());
+	}
+
+	fn focus(cx: &mut Ctx) -> Result<Data> {
+		let Some(idx) = cx.tabs().other_idx() else { succ!() };
+
+		cx.tabs_mut().set_idx(idx);
+		let cx = &mut Ctx::renew(cx);
+
+		act!(mgr:refresh, cx)?;
+		act!(mgr:peek, cx, true)?;
+		succ!(render!());
+	}
+
+	fn transfer(cx: &mut Ctx, cut: bool, force: bool) -> Result<Data> {
+		let Some(dest) = cx.tabs().other().map(|t| t.cwd().clone()) else {
+			succ!(AppProxy::notify_warn("Dual panel", "The dual-panel layout isn't on"));
+		};
+
+		let src: Vec<CovUrl> = cx.tab().selected_or_hovered().cloned().map(CovUrl).collect();
+		let src: Vec<_> = src.iter().collect();
+		if src.is_empty() {
+			succ!();
+		}
+
+		let conflict =
+			Conflict::new(if force { PasteOptConflict::Overwrite } else { PasteOptConflict::Rename });
+		if cut {
+			cx.tasks.file_cut(&src, &dest, &conflict, false, Default::default());
+			cx.tabs_mut().iter_mut().for_each(|t| _ = t.selected.remove_many(&src));
+		} else {
+			cx.tasks.file_copy(&src, &dest, &conflict, false, false, Default::default());
+		}
+		succ!(render!());
+	}
+}
@@ -0,0 +1,86 @@
+use std::collections::HashSet;
+
+use anyhow::Result;
+use yazi_fs::DUPES;
+use yazi_macro::{act, render, succ};
+use yazi_parser::mgr::{DupesOpt, DupesOptAction};
+use yazi_proxy::AppProxy;
+use yazi_shared::{event::Data, url::Url};
+
+use crate::{Actor, Ctx};
+
+pub struct Dupes;
+
+impl Actor for Dupes {
+	type Options = DupesOpt;
+
+	const NAME: &str = "dupes";
+
+	fn act(cx: &mut Ctx, opt: Self::Options) -> Result<Data> {
+		match opt.action {
+			DupesOptAction::Find => {
+				act!(mgr:escape_visual, cx)?;
+
+				let tab = cx.tab();
+				let targets: Vec<_> = if !tab.selected.is_empty() {
+					tab.selected.values().cloned().collect()
+				} else if tab.cwd().is_listing_root() {
+					// Their root is `/`, which is never what's meant
+					AppProxy::notify_warn("Dupes", "Select the files to find the duplicates among");
+					succ!();
+				} else {
+					vec![tab.cwd().clone()]
+				};
+
+				cx.core.tasks.file_dupes(targets);
+			}
+			DupesOptAction::Select => {
+				let Some(groups) = Self::listed(cx) else { succ!() };
+				let dupes: Vec<_> = groups.into_iter().flat_map(|g| g.into_iter().skip(1)).collect();
+				render!(cx.tab_mut().selected.add_many(&dupes) > 0);
+			}
+			DupesOptAction::Link => {
+				let Some(groups) = Self::listed(cx) else { succ!() };
+
+				let selected = &cx.tab().selected;
+				let all = selected.is_empty();
+				let (mut pairs, mut linked) = (vec![], vec![]);
+				for group in groups {
+					// The newest one left unselected is kept, or the newest of all
+					let keep = group.iter().position(|u| all || !selected.contains(u)).unwrap_or(0);
+					for (i, u) in group.iter().enumerate() {
+						if i != keep && (all || selected.contains(u)) {
+							pairs.push((group[keep].to_regular(), u.to_regular()));
+							linked.push(u.clone());
+						}
+					}
+				}
+
+				render!(cx.tab_mut().selected.remove_many(&linked) > 0);
+				cx.core.tasks.file_dedupe(pairs);
+			}
+		}
+		succ!();
+	}
+}
+
+impl Dupes {
+	/// The groups of duplicates as currently listed in `dupes://`.
+	fn listed(cx: &Ctx) -> Option<Vec<Vec<Url>>> {
+		let cwd = cx.cwd();
+		if !cwd.is_dupes() {
+			AppProxy::notify_warn("Dupes", "Only available in the listing of duplicates");
+			return None;
+		}
+
+		let shown: HashSet<_> = cx.current().files.iter().map(|f| &f.url).collect();
+		let groups = DUPES.groups().into_iter().map(|g| {
+			g.iter()
+				.filter_map(|p| p.strip_prefix(cwd.loc.base()).ok())
+				.map(|rel| cwd.join(rel))
+				.filter(|u| shown.contains(u))
+				.collect::<Vec<_>>()
+		});
+		Some(groups.filter(|g| g.len() > 1).collect())
+	}
+}
@@ -15,7 +15,7 @@ impl Actor for Enter {
 	fn act(cx: &mut Ctx, _: Self::Options) -> Result<Data> {
 		let Some(h) = cx.hovered().filter(|h| h.is_dir()) else { succ!() };
 
-		let url = if h.url.is_search() { h.url.to_regular() } else { h.url.clone() };
+		let url = if h.url.is_listing() { h.url.to_regular() } else { h.url.clone() };
 
 		act!(mgr:cd, cx, (url, CdSource::Enter))
 	}
@@ -0,0 +1,40 @@
+use anyhow::Result;
+use yazi_macro::{act, succ};
+use yazi_parser::mgr::ExtractOpt;
+use yazi_scheduler::file::Archive;
+use yazi_shared::{event::Data, url::Url};
+
+use crate::{Actor, Ctx};
+
+pub struct Extract;
+
+impl Actor for Extract {
+	type Options = ExtractOpt;
+
+	const NAME: &str = "extract";
+
+	fn act(cx: &mut Ctx, opt: Self::Options) -> Result<Data> {
+		act!(mgr:escape_visual, cx)?;
+
+		let targets = if opt.targets.is_empty() {
+			cx.tab().selected_or_hovered().cloned().collect()
+		} else {
+			opt.targets
+		};
+
+		// A multi-volume archive is extracted once, from its first volume
+		let mut archives: Vec<Url> = Vec::with_capacity(targets.len());
+		for u in targets.into_iter().filter(|u| u.as_path().is_some()) {
+			let u = match Archive::first_volume(&u.name().to_string_lossy()) {
+				Some(name) => u.parent_url().map_or(u, |p| p.join(name)),
+				None => u,
+			};
+			if !archives.contains(&u) {
+				archives.push(u);
+			}
+		}
+
+		cx.core.tasks.file_extract(archives, opt.to.as_ref());
+		succ!();
+	}
+}
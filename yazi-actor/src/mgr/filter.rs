@@ -4,7 +4,7 @@ use anyhow::Result;
 use tokio::pin;
 use tokio_stream::{StreamExt, wrappers::UnboundedReceiverStream};
 use yazi_config::popup::InputCfg;
-use yazi_macro::succ;
+use yazi_macro::{act, succ};
 use yazi_parser::mgr::FilterOpt;
 use yazi_proxy::{InputProxy, MgrProxy};
 use yazi_shared::{Debounce, errors::InputError, event::Data};
@@ -18,7 +18,11 @@ impl Actor for Filter {
 
 	const NAME: &str = "filter";
 
-	fn act(_: &mut Ctx, opt: Self::Options) -> Result<Data> {
+	fn act(cx: &mut Ctx, opt: Self::Options) -> Result<Data> {
+		if opt.tag.is_some() {
+			return act!(mgr:filter_do, cx, FilterOpt { done: true, ..opt });
+		}
+
 		let input = InputProxy::show(InputCfg::filter());
 
 		tokio::spawn(async move {
@@ -29,7 +33,7 @@ impl Actor for Filter {
 				let done = result.is_ok();
 				let (Ok(s) | Err(InputError::Typed(s))) = result else { continue };
 
-				MgrProxy::filter_do(FilterOpt { query: s.into(), case: opt.case, done });
+				MgrProxy::filter_do(FilterOpt { query: s.into(), case: opt.case, done, tag: None });
 			}
 		});
 		succ!();
@@ -1,5 +1,5 @@
 use anyhow::Result;
-use yazi_fs::Filter;
+use yazi_fs::{Filter, TAGS};
 use yazi_macro::{act, render, succ};
 use yazi_parser::mgr::FilterOpt;
 use yazi_shared::event::Data;
@@ -14,10 +14,17 @@ impl Actor for FilterDo {
 	const NAME: &str = "filter_do";
 
 	fn act(cx: &mut Ctx, opt: Self::Options) -> Result<Data> {
-		let filter = if opt.query.is_empty() { None } else { Some(Filter::new(&opt.query, opt.case)?) };
+		let filter = if let Some(tag) = &opt.tag {
+			let names = cx.current().files.all().filter(|f| TAGS.has(&f.url, tag)).map(|f| f.urn());
+			Some(Filter::exact(&format!("#{tag}"), names)?)
+		} else if opt.query.is_empty() {
+			None
+		} else {
+			Some(Filter::new(&opt.query, opt.case)?)
+		};
 
 		let hovered = cx.hovered().map(|f| f.urn_owned());
-		cx.current_mut().files.set_filter(filter);
+		let changed = cx.current_mut().files.set_filter(filter);
 
 		if cx.hovered().map(|f| f.urn()) != hovered.as_deref() {
 			act!(mgr:hover, cx, hovered)?;
@@ -25,6 +32,11 @@ impl Actor for FilterDo {
 			act!(mgr:watch, cx)?;
 		}
 
+		// Not remembered while it's being typed, but only once it's done or cleared
+		if opt.done || (changed && cx.current().files.filter().is_none()) {
+			cx.tab().save_view();
+		}
+
 		if opt.done {
 			act!(mgr:update_paged, cx)?;
 		}
@@ -0,0 +1,61 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use tokio::pin;
+use tokio_stream::{StreamExt, wrappers::UnboundedReceiverStream};
+use yazi_fs::{FilesOp, cha::Cha};
+use yazi_macro::{act, succ};
+use yazi_parser::mgr::FlattenOpt;
+use yazi_plugin::external;
+use yazi_proxy::MgrProxy;
+use yazi_shared::event::Data;
+
+use crate::{Actor, Ctx};
+
+pub struct Flatten;
+
+impl Flatten {
+	/// The prefix of the search keyword of a flattened directory, which is followed by its depth.
+	const PREFIX: &str = "flatten:";
+}
+
+impl Actor for Flatten {
+	type Options = FlattenOpt;
+
+	const NAME: &str = "flatten";
+
+	fn act(cx: &mut Ctx, opt: Self::Options) -> Result<Data> {
+		let cwd = cx.cwd();
+		if !cwd.is_grep() && cwd.keyword().is_some_and(|kw| kw.starts_with(Self::PREFIX)) {
+			return act!(mgr:search_stop, cx);
+		}
+
+		let tab = cx.tab_mut();
+		if let Some(handle) = tab.search.take() {
+			handle.abort();
+		}
+
+		let cwd = tab.cwd().to_search(format!("{}{}", Self::PREFIX, opt.depth));
+		let hidden = tab.pref.show_hidden;
+
+		// Listed as search results, so the descendants can be operated on as usual,
+		// and `escape --search` goes back to the directory
+		tab.search = Some(tokio::spawn(async move {
+			let rx =
+				external::flatten(external::FlattenOpt { cwd: cwd.clone(), hidden, depth: opt.depth })?;
+
+			let rx = UnboundedReceiverStream::new(rx).chunks_timeout(5000, Duration::from_millis(500));
+			pin!(rx);
+
+			let ((), ticket) = (MgrProxy::cd(&cwd), FilesOp::prepare(&cwd));
+			while let Some(chunk) = rx.next().await {
+				FilesOp::Part(cwd.clone(), chunk, ticket).emit();
+			}
+			FilesOp::Done(cwd, Cha::default(), ticket).emit();
+
+			Ok(())
+		}));
+
+		succ!();
+	}
+}
@@ -1,7 +1,8 @@
 use anyhow::Result;
-use yazi_fs::path::clean_url;
+use yazi_fs::{path::clean_url, provider};
 use yazi_macro::{act, succ};
-use yazi_parser::VoidOpt;
+use yazi_parser::mgr::FollowOpt;
+use yazi_proxy::{AppProxy, MgrProxy};
 use yazi_shared::event::Data;
 
 use crate::{Actor, Ctx};
@@ -9,14 +10,30 @@ use crate::{Actor, Ctx};
 pub struct Follow;
 
 impl Actor for Follow {
-	type Options = VoidOpt;
+	type Options = FollowOpt;
 
 	const NAME: &str = "follow";
 
-	fn act(cx: &mut Ctx, _: Self::Options) -> Result<Data> {
+	fn act(cx: &mut Ctx, opt: Self::Options) -> Result<Data> {
 		let Some(file) = cx.hovered() else { succ!() };
 		let Some(link_to) = &file.link_to else { succ!() };
 
+		// Resolved by the filesystem rather than joined with the parent lexically,
+		// which goes wrong across links and mount points
+		if opt.resolve {
+			let url = file.url_owned();
+			tokio::spawn(async move {
+				match provider::canonicalize(&url).await {
+					Ok(target) => MgrProxy::reveal(&target),
+					Err(e) => AppProxy::notify_warn(
+						"Follow",
+						format!("Could not resolve the target of {}: {e}", url.display()),
+					),
+				}
+			});
+			succ!();
+		}
+
 		if link_to.is_absolute() {
 			act!(mgr:reveal, cx, link_to.to_owned())
 		} else if let Some(p) = file.url.parent_url() {
@@ -0,0 +1,81 @@
+use std::{borrow::Cow, time::Duration};
+
+use anyhow::Result;
+use tokio::pin;
+use tokio_stream::{StreamExt, wrappers::UnboundedReceiverStream};
+use yazi_config::popup::InputCfg;
+use yazi_fs::{FilesOp, cha::Cha};
+use yazi_macro::succ;
+use yazi_parser::mgr::GrepOpt;
+use yazi_plugin::external;
+use yazi_proxy::{InputProxy, MgrProxy};
+use yazi_shared::event::Data;
+
+use crate::{Actor, Ctx};
+
+pub struct Grep;
+
+impl Actor for Grep {
+	type Options = GrepOpt;
+
+	const NAME: &str = "grep";
+
+	fn act(cx: &mut Ctx, mut opt: Self::Options) -> Result<Data> {
+		if let Some(handle) = cx.tab_mut().search.take() {
+			handle.abort();
+		}
+
+		let mut input = InputProxy::show(InputCfg::search("rg").with_value(&*opt.subject));
+
+		tokio::spawn(async move {
+			if let Some(Ok(subject)) = input.recv().await {
+				opt.subject = Cow::Owned(subject);
+				MgrProxy::grep_do(opt);
+			}
+		});
+		succ!();
+	}
+}
+
+// --- Do
+pub struct GrepDo;
+
+impl Actor for GrepDo {
+	type Options = GrepOpt;
+
+	const NAME: &str = "grep_do";
+
+	fn act(cx: &mut Ctx, opt: Self::Options) -> Result<Data> {
+		let tab = cx.tab_mut();
+		if let Some(handle) = tab.search.take() {
+			handle.abort();
+		}
+
+		let cwd = tab.cwd().to_grep(opt.subject.as_ref());
+		let hidden = tab.pref.show_hidden;
+
+		// Shares the handle with `search`, so either one replaces the other, and
+		// `escape --search` stops both
+		tab.search = Some(tokio::spawn(async move {
+			let rx = external::grep(external::GrepOpt {
+				cwd: cwd.clone(),
+				hidden,
+				subject: opt.subject.into_owned(),
+				args: opt.args,
+			})?;
+
+			let rx = UnboundedReceiverStream::new(rx).chunks_timeout(5000, Duration::from_millis(500));
+			pin!(rx);
+
+			let ((), ticket) = (MgrProxy::cd(&cwd), FilesOp::prepare(&cwd));
+			while let Some(chunk) = rx.next().await {
+				FilesOp::Part(cwd.clone(), chunk, ticket).emit();
+			}
+			FilesOp::Done(cwd, Cha::default(), ticket).emit();
+
+			Ok(())
+		}));
+
+		succ!();
+	}
+}
@@ -18,6 +18,9 @@ impl Actor for Hardlink {
 
 		if !mgr.yanked.cut {
 			cx.core.tasks.file_hardlink(&mgr.yanked, tab.cwd(), opt.force, opt.follow);
+			if let Some(lazy) = mgr.yanked.lazy() {
+				cx.core.tasks.file_hardlink_lazy(lazy, tab.cwd(), opt.force, opt.follow);
+			}
 		}
 
 		succ!();
@@ -16,7 +16,10 @@ impl Actor for Hidden {
 
 	fn act(cx: &mut Ctx, opt: Self::Options) -> Result<Data> {
 		let state = opt.state.bool(cx.tab().pref.show_hidden);
-		cx.tab_mut().pref.show_hidden = state;
+		if state != cx.tab().pref.show_hidden {
+			cx.tab_mut().pref.show_hidden = state;
+			cx.tab().save_view();
+		}
 
 		let hovered = cx.hovered().map(|f| f.urn_owned());
 		let apply = |f: &mut Folder| {
@@ -0,0 +1,30 @@
+use anyhow::Result;
+use yazi_macro::{act, render, succ};
+use yazi_parser::mgr::InfoOpt;
+use yazi_shared::event::Data;
+
+use crate::{Actor, Ctx};
+
+pub struct Info;
+
+impl Actor for Info {
+	type Options = InfoOpt;
+
+	const NAME: &str = "info";
+
+	fn act(cx: &mut Ctx, opt: Self::Options) -> Result<Data> {
+		let preview = &mut cx.tab_mut().preview;
+
+		let state = opt.state.bool(preview.info);
+		if state == preview.info {
+			succ!();
+		}
+
+		preview.info = state;
+		preview.skip = 0;
+		preview.reset();
+
+		act!(mgr:peek, cx, true)?;
+		succ!(render!());
+	}
+}
@@ -20,7 +20,7 @@ impl Actor for Leave {
 			.or_else(|| cx.cwd().parent_url());
 
 		let Some(mut url) = url else { succ!() };
-		if url.is_search() {
+		if url.is_listing() {
 			url = url.into_regular();
 		}
 
@@ -17,6 +17,7 @@ impl Actor for Linemode {
 
 		if opt.new != tab.pref.linemode {
 			tab.pref.linemode = opt.new.into_owned();
+			tab.save_view();
 			render!();
 		}
 
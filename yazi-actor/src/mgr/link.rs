@@ -18,6 +18,9 @@ impl Actor for Link {
 
 		if !mgr.yanked.cut {
 			cx.core.tasks.file_link(&mgr.yanked, tab.cwd(), opt.relative, opt.force);
+			if let Some(lazy) = mgr.yanked.lazy() {
+				cx.core.tasks.file_link_lazy(lazy, tab.cwd(), opt.relative, opt.force);
+			}
 		}
 
 		succ!();
@@ -0,0 +1,39 @@
+use anyhow::Result;
+use yazi_config::{YAZI, popup::PickCfg};
+use yazi_macro::{act, emit, succ};
+use yazi_parser::VoidOpt;
+use yazi_proxy::{AppProxy, PickProxy};
+use yazi_shared::{Source, event::Data};
+
+use crate::{Actor, Ctx};
+
+pub struct Menu;
+
+impl Actor for Menu {
+	type Options = VoidOpt;
+
+	const NAME: &str = "menu";
+
+	fn act(cx: &mut Ctx, _: Self::Options) -> Result<Data> {
+		if cx.hovered().is_none() {
+			succ!();
+		} else if YAZI.mgr.menu.is_empty() {
+			return act!(mgr:open, cx);
+		}
+
+		let n = cx.tab().selected_or_hovered().count();
+		tokio::spawn(async move {
+			let items = YAZI.mgr.menu.iter().map(|m| m.desc.clone()).collect();
+			let Ok(choice) = PickProxy::show(PickCfg::menu(n, items)).await else { return };
+
+			match YAZI.mgr.menu[choice].cmd() {
+				Ok(mut cmd) => {
+					cmd.source = Source::Emit;
+					emit!(Call(cmd));
+				}
+				Err(e) => AppProxy::notify_error("Menu", e),
+			}
+		});
+		succ!();
+	}
+}
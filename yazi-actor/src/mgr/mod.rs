@@ -1,36 +1,53 @@
 yazi_macro::mod_flat!(
 	arrow
 	back
+	bookmark
 	bulk_rename
 	cd
+	chmod
+	chown
 	close
+	compare
 	copy
 	create
+	download
+	dual
+	dupes
 	enter
 	escape
+	extract
 	filter
 	filter_do
 	find
 	find_arrow
 	find_do
+	flatten
 	follow
 	forward
+	grep
 	hardlink
 	hidden
 	hover
+	info
 	leave
 	linemode
 	link
+	menu
 	open
 	paste
 	peek
 	quit
+	redo
 	refresh
+	relink
 	remove
 	rename
+	restore
 	reveal
+	sanitize
 	search
 	seek
+	selection
 	shell
 	sort
 	spot
@@ -39,10 +56,16 @@ yazi_macro::mod_flat!(
 	tab_create
 	tab_swap
 	tab_switch
+	tag
+	theme
 	toggle
 	toggle_all
+	tree
+	undo
 	unyank
+	update_compare
 	update_files
+	update_journal
 	update_mimes
 	update_paged
 	update_peeked
@@ -51,5 +74,6 @@ yazi_macro::mod_flat!(
 	update_yanked
 	visual_mode
 	watch
+	xattr
 	yank
 );
@@ -1,14 +1,14 @@
-use std::{borrow::Cow, iter};
+use std::{borrow::Cow, collections::HashSet, iter};
 
 use anyhow::Result;
 use tracing::error;
-use yazi_config::{YAZI, popup::PickCfg};
+use yazi_config::{YAZI, opener::OpenerRule, popup::PickCfg};
 use yazi_core::tab::Folder;
-use yazi_fs::File;
+use yazi_fs::{File, apps::{APPS, App, CHOSEN}};
 use yazi_macro::{act, succ};
 use yazi_parser::mgr::{OpenDoOpt, OpenOpt};
 use yazi_plugin::isolate;
-use yazi_proxy::{MgrProxy, PickProxy, TasksProxy};
+use yazi_proxy::{AppProxy, MgrProxy, PickProxy, TasksProxy};
 use yazi_shared::{MIME_DIR, event::{CmdCow, Data}, url::Url};
 
 use crate::{Actor, Ctx, mgr::Quit};
@@ -49,7 +49,13 @@ impl Actor for Open {
 
 		let cwd = cx.cwd().clone();
 		if todo.is_empty() {
-			return act!(mgr:open_do, cx, OpenDoOpt { cwd, hovered, targets, interactive: opt.interactive });
+			return act!(mgr:open_do, cx, OpenDoOpt {
+				cwd,
+				hovered,
+				targets,
+				interactive: opt.interactive,
+				remember: opt.remember,
+			});
 		}
 
 		tokio::spawn(async move {
@@ -61,12 +67,18 @@ impl Actor for Open {
 			}
 
 			for (fetcher, files) in YAZI.plugin.mime_fetchers(files) {
-				if let Err(e) = isolate::fetch(CmdCow::from(&fetcher.run), files).await {
+				if let Err(e) = isolate::fetch(CmdCow::from(&fetcher.run), &fetcher.hint, files).await {
 					error!("Fetch mime failed on opening: {e}");
 				}
 			}
 
-			MgrProxy::open_do(OpenDoOpt { cwd, hovered, targets, interactive: opt.interactive });
+			MgrProxy::open_do(OpenDoOpt {
+				cwd,
+				hovered,
+				targets,
+				interactive: opt.interactive,
+				remember: opt.remember,
+			});
 		});
 		succ!();
 	}
@@ -94,21 +106,60 @@ impl Actor for OpenDo {
 		}
 
 		let openers: Vec<_> = YAZI.opener.all(YAZI.open.common(&targets).into_iter());
-		if openers.is_empty() {
-			succ!();
-		}
-
-		let pick = PickProxy::show(PickCfg::open(openers.iter().map(|o| o.desc()).collect()));
-		let urls = [opt.hovered].into_iter().chain(targets.into_iter().map(|(u, _)| u)).collect();
 		tokio::spawn(async move {
-			if let Ok(choice) = pick.await {
-				TasksProxy::open_with(Cow::Borrowed(openers[choice]), opt.cwd, urls);
+			let remembered = OpenDo::remembered(&targets);
+			// The remembered one first, then the ones of `[opener]`, and the system ones
+			let mut rules: Vec<Cow<'static, OpenerRule>> =
+				remembered.iter().map(|a| Cow::Owned(a.clone().into())).collect();
+			rules.extend(openers.into_iter().map(Cow::Borrowed));
+			if YAZI.open.system_apps {
+				rules.extend(APPS.query(&targets).await.into_iter().map(|a| Cow::Owned(a.into())));
+			}
+
+			let mut seen = HashSet::new();
+			rules.retain(|r| seen.insert(r.run.clone()));
+			if rules.is_empty() {
+				return;
+			}
+
+			let mut items: Vec<_> = rules.iter().map(|r| r.desc()).collect();
+			if remembered.is_some() {
+				items[0].push_str(" (remembered)");
+				if opt.remember {
+					items.push("Forget the remembered app".to_owned());
+				}
+			}
+
+			let Ok(choice) = PickProxy::show(PickCfg::open(items)).await else { return };
+			let files = targets.iter().filter(|(_, m)| *m != MIME_DIR).map(|(u, _)| u);
+			let Some(rule) = rules.into_iter().nth(choice) else {
+				return CHOSEN.set(files, None).unwrap_or_else(|e| {
+					AppProxy::notify_warn("Open", format!("Failed to forget the app: {e}"));
+				});
+			};
+
+			if opt.remember {
+				CHOSEN.set(files, Some(App::from(&*rule))).unwrap_or_else(|e| {
+					AppProxy::notify_warn("Open", format!("Failed to remember the app: {e}"));
+				});
 			}
+
+			let urls = [opt.hovered].into_iter().chain(targets.into_iter().map(|(u, _)| u)).collect();
+			TasksProxy::open_with(rule, opt.cwd, urls);
 		});
 		succ!();
 	}
 }
 
+impl OpenDo {
+	/// The app remembered for all the files, if they agree on one.
+	fn remembered(targets: &[(Url, &str)]) -> Option<App> {
+		let mut it = targets.iter().filter(|(_, m)| *m != MIME_DIR).map(|(u, _)| CHOSEN.get(u));
+		let first = it.next()??;
+		it.all(|a| a.as_ref() == Some(&first)).then_some(first)
+	}
+}
+
 impl Open {
 	fn guess_folder(cx: &Ctx, url: &Url) -> bool {
 		let Some(p) = url.parent_url() else {
@@ -1,8 +1,17 @@
+use std::path::Path;
+
 use anyhow::Result;
-use yazi_macro::{act, succ};
-use yazi_parser::mgr::PasteOpt;
-use yazi_shared::event::Data;
+use yazi_config::popup::{InputCfg, PickCfg};
+use yazi_core::mgr::Yanked;
+use yazi_fs::{Selection, path::unique_name};
+use yazi_macro::{act, render, succ};
+use yazi_parser::{app::{PluginMode, PluginOpt}, mgr::{PasteOpt, PasteOptArchive}};
+use yazi_proxy::{AppProxy, InputProxy, MgrProxy, PickProxy};
+use yazi_scheduler::{Schedule, file::Conflict};
+use yazi_shared::{event::{Data, DataKey}, url::{CovUrl, Url}};
+use yazi_widgets::CLIPBOARD;
 
+use super::Create;
 use crate::{Actor, Ctx};
 
 pub struct Paste;
@@ -12,18 +21,141 @@ impl Actor for Paste {
 
 	const NAME: &str = "paste";
 
-	fn act(cx: &mut Ctx, opt: Self::Options) -> Result<Data> {
+	fn act(cx: &mut Ctx, mut opt: Self::Options) -> Result<Data> {
+		if let Some((cut, urls)) = opt.imported.take() {
+			cx.mgr.yanked = Yanked::new(cut, urls.into_iter().map(CovUrl).collect());
+			render!(cx.mgr.yanked.catchup_revision(true));
+		} else if opt.system {
+			tokio::spawn(async move {
+				let Some(list) = CLIPBOARD.get_files().await else {
+					return AppProxy::notify_warn("Paste", "No files in the system clipboard");
+				};
+				let urls = list.paths.into_iter().map(Url::from).collect();
+				MgrProxy::paste(PasteOpt { imported: Some((list.cut, urls)), ..opt });
+			});
+			succ!();
+		} else if opt.schedule && !cx.mgr.yanked.is_void() {
+			tokio::spawn(Self::schedule(cx.mgr.yanked.count(), opt));
+			succ!();
+		} else if let Some(format) = opt.archive
+			&& !cx.mgr.yanked.is_void()
+		{
+			let (yanked, cwd) = (&cx.mgr.yanked, cx.cwd().clone());
+			let urls = yanked.iter().map(|u| u.0.clone()).collect();
+			tokio::spawn(Self::archive(urls, yanked.lazy().cloned(), cwd, format));
+			succ!();
+		} else if opt.into && !cx.mgr.yanked.is_void() {
+			tokio::spawn(Self::into(cx.cwd().clone(), opt));
+			succ!();
+		}
+
 		let mgr = &mut cx.core.mgr;
 		let tab = &mgr.tabs[cx.tab];
-		let (src, dest) = (mgr.yanked.iter().collect::<Vec<_>>(), tab.cwd());
+		let src = mgr.yanked.iter().collect::<Vec<_>>();
+		let dest = opt.dest.as_ref().unwrap_or(tab.cwd());
+		let conflict = Conflict::new(opt.conflict);
+		let schedule = match opt.queue {
+			Some(queue) => Schedule { queue, start: opt.start },
+			None => Schedule { start: opt.start, ..Default::default() },
+		};
 
 		if mgr.yanked.cut {
-			cx.core.tasks.file_cut(&src, dest, opt.force);
-
+			cx.core.tasks.file_cut(&src, dest, &conflict, opt.verify, schedule);
 			mgr.tabs.iter_mut().for_each(|t| _ = t.selected.remove_many(&src));
+
+			if let Some(lazy) = mgr.yanked.lazy() {
+				cx.core.tasks.file_cut_lazy(lazy, dest, &conflict, opt.verify, schedule);
+				for t in mgr.tabs.iter_mut() {
+					let cut: Vec<_> = t.selected.values().filter(|&u| lazy.contains(u)).cloned().collect();
+					t.selected.remove_many(&cut);
+				}
+			}
+
 			act!(mgr:unyank, cx)
 		} else {
-			succ!(cx.core.tasks.file_copy(&src, dest, opt.force, opt.follow));
+			cx.core.tasks.file_copy(&src, dest, &conflict, opt.follow, opt.verify, schedule);
+			if let Some(lazy) = mgr.yanked.lazy() {
+				cx.core.tasks.file_copy_lazy(lazy, dest, &conflict, opt.follow, opt.verify, schedule);
+			}
+			succ!();
+		}
+	}
+}
+
+impl Paste {
+	/// Asks whether to start it now, queue it, or start it at a time, and pastes accordingly.
+	async fn schedule(n: usize, mut opt: PasteOpt) {
+		let items = ["Start now", "Queue after the pastes before it", "Start at a time"];
+		let cfg = PickCfg::schedule(n, items.map(ToOwned::to_owned).to_vec());
+		let Ok(choice) = PickProxy::show(cfg).await else { return };
+
+		match choice {
+			0 => opt.queue = Some(false),
+			1 => opt.queue = Some(true),
+			_ => {
+				let mut input = InputProxy::show(InputCfg::schedule());
+				let Some(Ok(s)) = input.recv().await else { return };
+				let Some(start) = PasteOpt::start_at(&s) else {
+					return AppProxy::notify_warn("Paste", format!("Invalid time `{s}`, expected HH:MM"));
+				};
+				opt.start = Some(start);
+			}
+		}
+		MgrProxy::paste(PasteOpt { schedule: false, ..opt });
+	}
+
+	/// Asks for the name of a new directory in the CWD, and pastes into it once it's created.
+	async fn into(cwd: Url, opt: PasteOpt) {
+		let mut input = InputProxy::show(InputCfg::paste(false));
+		let Some(Ok(name)) = input.recv().await else { return };
+		if name.is_empty() {
+			return;
+		}
+
+		let dest = cwd.join(&name);
+		if let Err(e) = Create::r#do(dest.clone(), true).await {
+			return AppProxy::notify_warn("Paste", format!("Failed to create `{name}`: {e}"));
+		}
+		MgrProxy::paste(PasteOpt { into: false, dest: Some(dest), ..opt });
+	}
+
+	/// Packs the yanked files into an archive with the `compress` plugin.
+	async fn archive(
+		mut urls: Vec<Url>,
+		lazy: Option<Selection>,
+		cwd: Url,
+		format: PasteOptArchive,
+	) {
+		let stem = match &urls[..] {
+			[u] if lazy.is_none() => Path::new(u.name()).file_stem().map(|s| s.to_string_lossy()),
+			_ => None,
+		};
+		let value = format!("{}.{}", stem.as_deref().unwrap_or("Archive"), format.ext());
+
+		let mut input = InputProxy::show(InputCfg::paste(true).with_value(value));
+		let Some(Ok(name)) = input.recv().await else { return };
+		if name.is_empty() {
+			return;
+		}
+
+		if let Some(lazy) = lazy {
+			let mut rx = lazy.expand();
+			while let Some(u) = rx.recv().await {
+				urls.push(u);
+			}
 		}
+
+		let to = match unique_name(cwd.join(&name), async { false }).await {
+			Ok(u) => u,
+			Err(e) => return AppProxy::notify_warn("Paste", format!("Failed to pack `{name}`: {e}")),
+		};
+
+		let args = [to].into_iter().chain(urls).enumerate();
+		AppProxy::plugin(PluginOpt {
+			id: "compress".into(),
+			args: args.map(|(i, u)| (DataKey::Integer(i as i64), Data::Url(u))).collect(),
+			mode: PluginMode::Async,
+			..Default::default()
+		});
 	}
 }
@@ -0,0 +1,48 @@
+use anyhow::Result;
+use yazi_fs::{JournalOp, maybe_exists};
+use yazi_macro::succ;
+use yazi_parser::VoidOpt;
+use yazi_proxy::AppProxy;
+use yazi_shared::event::Data;
+
+use super::{Create, Undo};
+use crate::{Actor, Ctx};
+
+pub struct Redo;
+
+impl Actor for Redo {
+	type Options = VoidOpt;
+
+	const NAME: &str = "redo";
+
+	fn act(cx: &mut Ctx, _: Self::Options) -> Result<Data> {
+		let Some(record) = cx.mgr.journal.redo() else {
+			succ!(AppProxy::notify_warn("Redo", "Nothing to redo"));
+		};
+
+		match record.op {
+			JournalOp::Rename { from, to } => {
+				tokio::spawn(Undo::rename("Redo", cx.tab().id, from, to));
+			}
+			JournalOp::Move { from, to } => {
+				let id =
+					cx.tasks.scheduler.file_cut(from, to, Default::default(), false, Default::default());
+				cx.mgr.journal.expect_task(id);
+			}
+			JournalOp::Trash { target } => {
+				cx.mgr.journal.expect(JournalOp::Trash { target: target.clone() });
+				cx.tasks.file_remove(vec![target], false);
+			}
+			JournalOp::Create { target, dir } => {
+				tokio::spawn(async move {
+					if maybe_exists(&target).await {
+						AppProxy::notify_warn("Redo", format!("`{}` already exists", target.display()));
+					} else if let Err(e) = Create::r#do(target, dir).await {
+						AppProxy::notify_warn("Redo", format!("{e:#}"));
+					}
+				});
+			}
+		}
+		succ!();
+	}
+}
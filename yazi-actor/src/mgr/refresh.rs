@@ -1,11 +1,13 @@
+use std::path::Path;
+
 use anyhow::Result;
 use crossterm::{execute, terminal::SetTitle};
 use yazi_config::YAZI;
-use yazi_fs::CWD;
+use yazi_fs::{CWD, provider::local::Local};
 use yazi_macro::{act, succ};
 use yazi_parser::VoidOpt;
 use yazi_shared::event::Data;
-use yazi_term::tty::TTY;
+use yazi_term::{ReportCwd, tty::TTY};
 
 use crate::{Actor, Ctx};
 
@@ -17,9 +19,13 @@ impl Actor for Refresh {
 	const NAME: &str = "refresh";
 
 	fn act(cx: &mut Ctx, _: Self::Options) -> Result<Data> {
-		if let (_, Some(s)) = (CWD.set(cx.cwd()), YAZI.mgr.title()) {
+		let changed = CWD.set(cx.cwd());
+		if let Some(s) = YAZI.mgr.title() {
 			execute!(TTY.writer(), SetTitle(s)).ok();
 		}
+		if changed {
+			Self::report_cwd();
+		}
 
 		if let Some(p) = cx.parent() {
 			cx.mgr.watcher.trigger_dirs(&[cx.current(), p]);
@@ -35,3 +41,41 @@ impl Actor for Refresh {
 		succ!();
 	}
 }
+
+impl Refresh {
+	// Only a local CWD can be followed by the terminal and the shell
+	fn report_cwd() {
+		let cwd = CWD.load();
+		let Some(path) = cwd.as_path().filter(|_| cwd.is_regular()) else { return };
+
+		if YAZI.mgr.report_cwd {
+			execute!(TTY.writer(), ReportCwd(path)).ok();
+		}
+		if !YAZI.mgr.cwd_hook.as_os_str().is_empty() {
+			tokio::spawn(Self::hook(&YAZI.mgr.cwd_hook));
+		}
+	}
+
+	/// Writes the CWD into the hook, a file or a Unix socket.
+	async fn hook(hook: &'static Path) {
+		let cwd = CWD.load_full();
+		let Some(path) = cwd.as_path() else { return };
+
+		#[cfg(unix)]
+		{
+			use std::os::unix::fs::FileTypeExt;
+
+			use tokio::{io::AsyncWriteExt, net::UnixStream};
+
+			if tokio::fs::metadata(hook).await.is_ok_and(|m| m.file_type().is_socket()) {
+				if let Ok(mut stream) = UnixStream::connect(hook).await {
+					let line = [path.as_os_str().as_encoded_bytes(), b"\n"].concat();
+					stream.write_all(&line).await.ok();
+				}
+				return;
+			}
+		}
+
+		Local::write(hook, path.as_os_str().as_encoded_bytes()).await.ok();
+	}
+}
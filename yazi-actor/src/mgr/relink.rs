@@ -0,0 +1,79 @@
+use std::path::Path;
+
+use anyhow::{Result, bail};
+use yazi_config::popup::InputCfg;
+use yazi_fs::{File, FilesOp, ok_or_not_found, provider};
+use yazi_macro::{act, succ};
+use yazi_parser::mgr::RelinkOpt;
+use yazi_proxy::{AppProxy, InputProxy, WATCHER};
+use yazi_shared::{event::Data, url::Url};
+
+use crate::{Actor, Ctx};
+
+pub struct Relink;
+
+impl Actor for Relink {
+	type Options = RelinkOpt;
+
+	const NAME: &str = "relink";
+
+	fn act(cx: &mut Ctx, mut opt: Self::Options) -> Result<Data> {
+		act!(mgr:escape_visual, cx)?;
+
+		let Some(hovered) = cx.hovered() else { succ!() };
+		let Some(link_to) = &hovered.link_to else {
+			succ!(AppProxy::notify_warn("Relink", "The hovered file isn't a symlink"));
+		};
+
+		let url = hovered.url_owned();
+		let input = opt.target.is_none().then(|| {
+			InputProxy::show(InputCfg::relink().with_value(link_to.display().to_string()))
+		});
+
+		tokio::spawn(async move {
+			if let Some(mut rx) = input {
+				match rx.recv().await {
+					Some(Ok(s)) => opt.target = Some(s.into()),
+					_ => return,
+				}
+			}
+
+			let target = opt.target.unwrap_or_default();
+			if target.is_empty() {
+				return;
+			}
+
+			if let Err(e) = Self::r#do(&url, Url::from(Path::new(&*target))).await {
+				AppProxy::notify_warn("Relink", format!("Could not relink {}: {e}", url.display()));
+			}
+		});
+		succ!();
+	}
+}
+
+impl Relink {
+	/// Points the link at `to`, through a new link renamed over it.
+	async fn r#do(url: &Url, to: Url) -> Result<()> {
+		let Some((parent, urn)) = url.pair() else { bail!("no parent directory") };
+		let _permit = WATCHER.acquire().await.unwrap();
+
+		let tmp = parent.join(format!(".{}.relink", urn.to_string_lossy()));
+		ok_or_not_found(provider::remove_file(&tmp).await)?;
+
+		// Windows tells links to directories apart from those to files
+		if provider::metadata(parent.join(&to)).await.is_ok_and(|m| m.is_dir()) {
+			provider::symlink_dir(&to, &tmp).await?;
+		} else {
+			provider::symlink_file(&to, &tmp).await?;
+		}
+
+		if let Err(e) = provider::rename(&tmp, url).await {
+			provider::remove_file(&tmp).await.ok();
+			return Err(e.into());
+		}
+
+		let file = File::new(url.clone()).await?;
+		FilesOp::Upserting(parent, [(urn, file)].into()).emit();
+		Ok(())
+	}
+}
@@ -1,5 +1,5 @@
 use anyhow::Result;
-use yazi_config::popup::ConfirmCfg;
+use yazi_config::{YAZI, policy::{PolicyAnswer, PolicyOp}, popup::ConfirmCfg};
 use yazi_macro::{act, succ};
 use yazi_parser::mgr::RemoveOpt;
 use yazi_proxy::{ConfirmProxy, MgrProxy};
@@ -23,9 +23,20 @@ impl Actor for Remove {
 			cx.tab().selected_or_hovered().cloned().collect()
 		};
 
+		// Files that can't be trashed, such as those on MTP devices, are deleted
+		// instead, which is always confirmed
+		let untrashable = !opt.permanently && opt.targets.iter().any(|u| !u.caps().supports_trash());
+		opt.permanently |= untrashable;
+
+		// The policy can have them trashed instead of deleted, if they can be
+		let policy = YAZI.policy.answer(PolicyOp::Delete, opt.targets.len(), &opt.targets);
+		if opt.permanently && !untrashable && policy == PolicyAnswer::Trash {
+			opt.permanently = false;
+		}
+
 		if opt.targets.is_empty() {
 			succ!();
-		} else if opt.force {
+		} else if opt.force && !untrashable {
 			return act!(mgr:remove_do, cx, opt);
 		}
 
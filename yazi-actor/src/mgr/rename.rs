@@ -1,10 +1,11 @@
 use anyhow::Result;
 use yazi_config::popup::{ConfirmCfg, InputCfg};
 use yazi_dds::Pubsub;
-use yazi_fs::{File, FilesOp, maybe_exists, ok_or_not_found, paths_to_same_file, provider, realname};
+use yazi_fs::{AUDIT, AuditOp, File, FilesOp, JournalOp, maybe_exists, ok_or_not_found, paths_to_same_file, provider, realname};
 use yazi_macro::{act, err, succ};
 use yazi_parser::mgr::RenameOpt;
 use yazi_proxy::{ConfirmProxy, InputProxy, MgrProxy, WATCHER};
+use yazi_scheduler::{SUMMARY, SummaryKind};
 use yazi_shared::{Id, event::Data, url::{Url, UrnBuf}};
 
 use crate::{Actor, Ctx};
@@ -48,10 +49,16 @@ impl Actor for Rename {
 			}
 
 			let new = Url::from(old.parent().unwrap().join(name));
-			if opt.force || !maybe_exists(&new).await || paths_to_same_file(&old, &new).await {
-				Self::r#do(tab, old, new).await.ok();
-			} else if ConfirmProxy::show(ConfirmCfg::overwrite(&new)).await {
-				Self::r#do(tab, old, new).await.ok();
+			if !opt.force
+				&& maybe_exists(&new).await
+				&& !paths_to_same_file(&old, &new).await
+				&& !ConfirmProxy::show(ConfirmCfg::overwrite(&new)).await
+			{
+				return;
+			}
+
+			if Self::r#do(tab, old.clone(), new.clone()).await.is_ok() {
+				JournalOp::Rename { from: old, to: new }.emit();
 			}
 		});
 		succ!();
@@ -59,13 +66,14 @@ impl Actor for Rename {
 }
 
 impl Rename {
-	async fn r#do(tab: Id, old: Url, new: Url) -> Result<()> {
+	pub(crate) async fn r#do(tab: Id, old: Url, new: Url) -> Result<()> {
 		let Some((p_old, n_old)) = old.pair() else { return Ok(()) };
 		let Some((p_new, n_new)) = new.pair() else { return Ok(()) };
 		let _permit = WATCHER.acquire().await.unwrap();
 
 		let overwritten = realname(&new).await;
-		provider::rename(&old, &new).await?;
+		AUDIT.done(AuditOp::Rename, Some(&old), &new, provider::rename(&old, &new).await)?;
+		SUMMARY.record(SummaryKind::Renamed, 0);
 
 		if let Some(o) = overwritten {
 			ok_or_not_found(provider::rename(p_new.join(&o), &new).await)?;
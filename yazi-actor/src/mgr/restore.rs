@@ -0,0 +1,100 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::Result;
+use yazi_config::popup::{ConfirmCfg, PickCfg};
+use yazi_dds::{Trashed, ember::BodyTrashedItem};
+use yazi_fs::maybe_exists;
+use yazi_macro::succ;
+use yazi_parser::mgr::RestoreOpt;
+use yazi_proxy::{AppProxy, ConfirmProxy, PickProxy};
+use yazi_scheduler::file::Recycle;
+use yazi_shared::event::Data;
+
+use crate::{Actor, Ctx};
+
+pub struct Restore;
+
+impl Actor for Restore {
+	type Options = RestoreOpt;
+
+	const NAME: &str = "restore";
+
+	fn act(cx: &mut Ctx, opt: Self::Options) -> Result<Data> {
+		if !Recycle::SUPPORTED {
+			AppProxy::notify_warn(
+				"Restore",
+				"Restoring from the trash is not supported on this platform",
+			);
+			succ!();
+		}
+
+		// In `trash://`, the files chosen there are restored
+		if cx.cwd().is_trash() {
+			let targets: Vec<_> = cx.tab().selected_or_hovered().cloned().collect();
+			tokio::spawn(async move {
+				for url in targets {
+					match Recycle::locate(&url).await {
+						Some(item) => Self::restore(item).await,
+						None => AppProxy::notify_warn(
+							"Restore",
+							format!("`{}` isn't an item of the trash", url.display()),
+						),
+					}
+				}
+			});
+			succ!();
+		}
+
+		tokio::spawn(async move {
+			if let Err(e) = Recycle::prune().await {
+				AppProxy::notify_warn("Restore", format!("Could not read the trash: {e}"));
+				return;
+			}
+
+			let mut items = Trashed::list();
+			if items.is_empty() {
+				return AppProxy::notify_warn("Restore", "No recently trashed files to restore");
+			}
+
+			let item = if opt.recent {
+				let pick = PickProxy::show(PickCfg::restore(items.iter().map(Self::describe).collect()));
+				let Ok(choice) = pick.await else { return };
+				items.swap_remove(choice)
+			} else {
+				items.swap_remove(0)
+			};
+
+			Self::restore(item).await;
+		});
+		succ!();
+	}
+}
+
+impl Restore {
+	async fn restore(item: BodyTrashedItem) {
+		let overwrite = maybe_exists(&item.url).await;
+		if overwrite && !ConfirmProxy::show(ConfirmCfg::overwrite(&item.url)).await {
+			return;
+		}
+
+		if let Err(e) = Recycle::restore(item, overwrite).await {
+			AppProxy::notify_warn("Restore", e);
+		}
+	}
+
+	fn describe(item: &BodyTrashedItem) -> String {
+		let now = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs() as i64);
+		let ago = match now.saturating_sub(item.at).max(0) {
+			s @ 0..60 => format!("{s}s ago"),
+			s @ 60..3600 => format!("{}m ago", s / 60),
+			s @ 3600..86400 => format!("{}h ago", s / 3600),
+			s => format!("{}d ago", s / 86400),
+		};
+
+		let name = item.url.name().to_string_lossy();
+		match item.url.parent() {
+			Some(p) => format!("{name}  ({ago}, from {})", p.display()),
+			None => format!("{name}  ({ago})"),
+		}
+	}
+}
@@ -0,0 +1,65 @@
+use std::collections::VecDeque;
+
+use anyhow::Result;
+use yazi_fs::{Sanitizer, provider};
+use yazi_macro::succ;
+use yazi_parser::mgr::SanitizeOpt;
+use yazi_proxy::AppProxy;
+use yazi_shared::{event::Data, url::Url};
+
+use super::BulkRename;
+use crate::{Actor, Ctx};
+
+pub struct Sanitize;
+
+impl Actor for Sanitize {
+	type Options = SanitizeOpt;
+
+	const NAME: &str = "sanitize";
+
+	fn act(cx: &mut Ctx, opt: Self::Options) -> Result<Data> {
+		let selected: Vec<_> = cx.tab().selected_or_hovered().cloned().collect();
+		if selected.is_empty() {
+			succ!(AppProxy::notify_warn("Sanitize", "No files selected"));
+		}
+
+		let sanitizer = Sanitizer::new(opt.target);
+		tokio::spawn(async move {
+			let mut plans = sanitizer.plan(&Self::walk(selected).await);
+			plans.retain(|p| p.from != p.to);
+
+			if plans.is_empty() {
+				AppProxy::notify_warn("Sanitize", "No problematic names found");
+				Ok(())
+			} else {
+				BulkRename::review(plans, false).await
+			}
+		});
+		succ!();
+	}
+}
+
+impl Sanitize {
+	/// The files and everything in the directories among them, without following the symlinks.
+	async fn walk(roots: Vec<Url>) -> Vec<Url> {
+		let mut dirs = VecDeque::new();
+		for u in &roots {
+			if provider::symlink_metadata(u).await.is_ok_and(|m| m.is_dir()) {
+				dirs.push_back(u.clone());
+			}
+		}
+
+		let mut urls = roots;
+		while let Some(dir) = dirs.pop_front() {
+			let Ok(mut it) = provider::read_dir(&dir).await else { continue };
+			while let Ok(Some(entry)) = it.next_entry().await {
+				let url = entry.url();
+				if entry.file_type().await.is_ok_and(|t| t.is_dir()) {
+					dirs.push_back(url.clone());
+				}
+				urls.push(url);
+			}
+		}
+		urls
+	}
+}
@@ -2,11 +2,11 @@ use std::{borrow::Cow, mem, time::Duration};
 
 use anyhow::Result;
 use tokio::pin;
-use tokio_stream::{StreamExt, wrappers::UnboundedReceiverStream};
-use yazi_config::popup::InputCfg;
+use tokio_stream::{StreamExt, wrappers::ReceiverStream};
+use yazi_config::{YAZI, popup::InputCfg};
 use yazi_fs::{FilesOp, cha::Cha};
 use yazi_macro::{act, succ};
-use yazi_parser::{VoidOpt, mgr::{SearchOpt, SearchOptVia}};
+use yazi_parser::{VoidOpt, mgr::SearchOpt};
 use yazi_plugin::external;
 use yazi_proxy::{InputProxy, MgrProxy};
 use yazi_shared::event::Data;
@@ -25,8 +25,8 @@ impl Actor for Search {
 			handle.abort();
 		}
 
-		let mut input =
-			InputProxy::show(InputCfg::search(opt.via.into_str()).with_value(&*opt.subject));
+		let (_, via) = YAZI.search.pick(&opt.via);
+		let mut input = InputProxy::show(InputCfg::search(via).with_value(&*opt.subject));
 
 		tokio::spawn(async move {
 			if let Some(Ok(subject)) = input.recv().await {
@@ -56,28 +56,15 @@ impl Actor for SearchDo {
 		let hidden = tab.pref.show_hidden;
 
 		tab.search = Some(tokio::spawn(async move {
-			let rx = match opt.via {
-				SearchOptVia::Rg => external::rg(external::RgOpt {
-					cwd: cwd.clone(),
-					hidden,
-					subject: opt.subject.into_owned(),
-					args: opt.args,
-				}),
-				SearchOptVia::Rga => external::rga(external::RgaOpt {
-					cwd: cwd.clone(),
-					hidden,
-					subject: opt.subject.into_owned(),
-					args: opt.args,
-				}),
-				SearchOptVia::Fd => external::fd(external::FdOpt {
-					cwd: cwd.clone(),
-					hidden,
-					subject: opt.subject.into_owned(),
-					args: opt.args,
-				}),
-			}?;
-
-			let rx = UnboundedReceiverStream::new(rx).chunks_timeout(5000, Duration::from_millis(500));
+			let rx = external::search(external::SearchOpt {
+				via: opt.via.into_owned(),
+				cwd: cwd.clone(),
+				hidden,
+				subject: opt.subject.into_owned(),
+				args: opt.args,
+			})?;
+
+			let rx = ReceiverStream::new(rx).chunks_timeout(5000, Duration::from_millis(500));
 			pin!(rx);
 
 			let ((), ticket) = (MgrProxy::cd(&cwd), FilesOp::prepare(&cwd));
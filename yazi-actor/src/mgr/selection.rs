@@ -0,0 +1,64 @@
+use anyhow::Result;
+use yazi_core::mgr::Mgr;
+use yazi_macro::{act, render, succ};
+use yazi_parser::mgr::{SelectionOpt, SelectionOptAction};
+use yazi_proxy::AppProxy;
+use yazi_shared::event::Data;
+
+use crate::{Actor, Ctx};
+
+pub struct Selection;
+
+impl Actor for Selection {
+	type Options = SelectionOpt;
+
+	const NAME: &str = "selection";
+
+	fn act(cx: &mut Ctx, opt: Self::Options) -> Result<Data> {
+		act!(mgr:escape_visual, cx)?;
+
+		let Mgr { tabs, selections, .. } = &mut cx.core.mgr;
+		let selected = &mut tabs[cx.tab].selected;
+
+		let urls = match opt.action {
+			SelectionOptAction::Save => {
+				if selected.is_empty() {
+					AppProxy::notify_warn("Save selection", "No files are selected");
+				} else {
+					selections.save(&opt.name, selected.values().cloned());
+				}
+				succ!();
+			}
+			SelectionOptAction::Delete => succ!(selections.remove(&opt.name)),
+			_ => {
+				let Some(urls) = selections.get(&*opt.name) else {
+					let msg = format!("No selection saved as `{}`", opt.name);
+					AppProxy::notify_warn("Recall selection", msg);
+					succ!();
+				};
+				urls
+			}
+		};
+
+		let added = match opt.action {
+			SelectionOptAction::Recall => {
+				render!();
+				selected.clear();
+				selected.add_many(urls)
+			}
+			SelectionOptAction::Merge => render!(selected.add_many(urls), > 0),
+			_ => {
+				render!(selected.remove_many(urls) > 0);
+				succ!();
+			}
+		};
+
+		if added != urls.len() {
+			AppProxy::notify_warn(
+				"Recall selection",
+				"Some files cannot be selected, due to path nesting conflict.",
+			);
+		}
+		succ!();
+	}
+}
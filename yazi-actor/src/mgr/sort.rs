@@ -1,6 +1,6 @@
 use anyhow::Result;
 use yazi_core::tab::Folder;
-use yazi_fs::{FilesSorter, FolderStage};
+use yazi_fs::{FilesSorter, FolderStage, SortBy};
 use yazi_macro::{act, render, render_and, succ};
 use yazi_parser::mgr::SortOpt;
 use yazi_shared::event::Data;
@@ -16,6 +16,7 @@ impl Actor for Sort {
 
 	fn act(cx: &mut Ctx, opt: Self::Options) -> Result<Data> {
 		let pref = &mut cx.tab_mut().pref;
+		let old = FilesSorter::from(&*pref);
 		pref.sort_by = opt.by.unwrap_or(pref.sort_by);
 		pref.sort_reverse = opt.reverse.unwrap_or(pref.sort_reverse);
 		pref.sort_dir_first = opt.dir_first.unwrap_or(pref.sort_dir_first);
@@ -23,11 +24,19 @@ impl Actor for Sort {
 		pref.sort_translit = opt.translit.unwrap_or(pref.sort_translit);
 
 		let sorter = FilesSorter::from(&*pref);
+		if sorter != old {
+			cx.tab().save_view();
+		}
+
 		let hovered = cx.hovered().map(|f| f.urn_owned());
 		let apply = |f: &mut Folder| {
 			if f.stage == FolderStage::Loading {
 				render!();
 				false
+			} else if f.url.is_listing_root() {
+				// History listings come ranked, duplicates grouped, and the trash latest first
+				f.files.set_sorter(FilesSorter { by: SortBy::None, ..sorter });
+				render_and!(f.files.catchup_revision())
 			} else {
 				f.files.set_sorter(sorter);
 				render_and!(f.files.catchup_revision())
@@ -26,6 +26,7 @@ impl Actor for TabClose {
 		} else {
 			tabs.set_idx(usize::min(tabs.cursor + 1, tabs.len() - 1));
 		}
+		tabs.other = tabs.other_idx().map(|i| tabs[i].id);
 
 		let cx = &mut Ctx::renew(cx);
 		act!(mgr:refresh, cx)?;
@@ -0,0 +1,43 @@
+use anyhow::Result;
+use yazi_config::Color;
+use yazi_fs::TAGS;
+use yazi_macro::{act, render, succ};
+use yazi_parser::mgr::{TagOpt, TagOptAction};
+use yazi_shared::event::Data;
+
+use crate::{Actor, Ctx};
+
+pub struct Tag;
+
+impl Actor for Tag {
+	type Options = TagOpt;
+
+	const NAME: &str = "tag";
+
+	fn act(cx: &mut Ctx, opt: Self::Options) -> Result<Data> {
+		act!(mgr:escape_visual, cx)?;
+
+		if let (Some(name), Some(color)) = (&opt.name, &opt.color) {
+			color.parse::<Color>()?;
+			TAGS.set_color(name, Some(color));
+			render!();
+		}
+
+		let urls: Vec<_> = cx.tab().selected_or_hovered().collect();
+		let it = urls.iter().copied();
+
+		let n = match (opt.action, opt.name.as_deref()) {
+			(TagOptAction::Add, Some(tag)) => TAGS.add(it, tag),
+			(TagOptAction::Remove, Some(tag)) => TAGS.remove(it, Some(tag)),
+			(TagOptAction::Toggle, Some(tag)) if urls.iter().all(|&u| TAGS.has(u, tag)) => {
+				TAGS.remove(it, Some(tag))
+			}
+			(TagOptAction::Toggle, Some(tag)) => TAGS.add(it, tag),
+			// Only `clear` comes without a name
+			(_, _) => TAGS.remove(it, None),
+		};
+
+		render!(n > 0);
+		succ!();
+	}
+}
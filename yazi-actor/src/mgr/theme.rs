@@ -0,0 +1,98 @@
+use std::{sync::Mutex, time::Duration};
+
+use anyhow::Result;
+use tokio::{io::{AsyncBufReadExt, BufReader}, task::{JoinHandle, spawn_blocking}, time::sleep};
+use yazi_adapter::{EMULATOR, Host};
+use yazi_config::{LIGHT, THEME, theme::Appearance};
+use yazi_dds::Pubsub;
+use yazi_macro::{act, emit, err, relay, render, succ};
+use yazi_parser::mgr::ThemeOpt;
+use yazi_plugin::LUA;
+use yazi_shared::event::Data;
+
+use crate::{Actor, Ctx};
+
+pub struct Theme;
+
+static FOLLOWING: Mutex<Option<JoinHandle<()>>> = Mutex::new(None);
+
+impl Actor for Theme {
+	type Options = ThemeOpt;
+
+	const NAME: &str = "theme";
+
+	fn act(cx: &mut Ctx, opt: Self::Options) -> Result<Data> {
+		let flavor = if opt.reload {
+			None
+		} else {
+			let mut flavor = THEME.flavor.clone();
+			if let Some(name) = opt.flavor {
+				flavor.dark = name.clone();
+				flavor.light = name;
+			}
+
+			if opt.toggle {
+				flavor.appearance = if LIGHT.get() { Appearance::Dark } else { Appearance::Light };
+			} else if let Some(a) = opt.appearance {
+				flavor.appearance = a;
+			} else if opt.os.is_some() && flavor.appearance != Appearance::Auto {
+				succ!();
+			}
+			Some(flavor)
+		};
+
+		// The terminal's background is only detected at startup, so the OS takes
+		// precedence once its dark mode changes
+		let term = if opt.os.is_some() { None } else { EMULATOR.get().light };
+		yazi_config::switch_flavor(flavor, |a| a.light(term, || opt.os.or_else(Host::os_light)))?;
+		Self::follow_os();
+
+		LUA.globals().raw_set("th", yazi_plugin::theme::compose())?;
+		err!(Pubsub::pub_after_theme(LIGHT.get(), THEME.flavor.name(LIGHT.get())));
+
+		act!(mgr:peek, cx, true)?;
+		succ!(render!());
+	}
+}
+
+impl Theme {
+	/// Follows the dark mode of the OS while the appearance is `auto`, and stops otherwise.
+	pub fn follow_os() {
+		let mut following = FOLLOWING.lock().unwrap();
+		if THEME.flavor.appearance != Appearance::Auto {
+			if let Some(handle) = following.take() {
+				handle.abort();
+			}
+		} else if following.as_ref().is_none_or(JoinHandle::is_finished) {
+			*following = Some(tokio::spawn(Self::watch_os()));
+		}
+	}
+
+	// Waits for the notifications of the OS where it sends them, and polls it otherwise
+	async fn watch_os() {
+		let Ok(Some(mut last)) = spawn_blocking(Host::os_light).await else { return };
+
+		let mut lines = Host::os_light_monitor()
+			.and_then(|mut cmd| cmd.spawn().ok())
+			.and_then(|mut child| Some((BufReader::new(child.stdout.take()?).lines(), child)));
+		loop {
+			match &mut lines {
+				Some((it, _)) => {
+					if !matches!(it.next_line().await, Ok(Some(_))) {
+						lines = None;
+						continue;
+					}
+				}
+				None => sleep(Duration::from_secs(5)).await,
+			}
+
+			match spawn_blocking(Host::os_light).await {
+				Ok(Some(light)) if light != last => {
+					last = light;
+					emit!(Call(relay!(mgr:theme).with("os", light)));
+				}
+				_ => {}
+			}
+		}
+	}
+}
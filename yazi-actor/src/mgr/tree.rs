@@ -0,0 +1,111 @@
+use std::{path::Path, time::Duration};
+
+use anyhow::Result;
+use tokio::pin;
+use tokio_stream::{StreamExt, wrappers::UnboundedReceiverStream};
+use yazi_fs::{File, FilesOp, cha::Cha};
+use yazi_macro::{act, render, succ};
+use yazi_parser::mgr::{CdSource, TreeOpt, TreeOptAction};
+use yazi_plugin::external::{self, FlattenOpt};
+use yazi_shared::{event::Data, url::UrnBuf};
+
+use crate::{Actor, Ctx};
+
+pub struct Tree;
+
+impl Actor for Tree {
+	type Options = TreeOpt;
+
+	const NAME: &str = "tree";
+
+	fn act(cx: &mut Ctx, opt: Self::Options) -> Result<Data> {
+		use TreeOptAction as A;
+
+		let Some(tree) = &cx.current().tree else {
+			return if opt.action == A::None { Self::enter(cx) } else { succ!() };
+		};
+		let Some(hovered) = cx.hovered().cloned() else {
+			return if opt.action == A::None { act!(mgr:search_stop, cx) } else { succ!() };
+		};
+
+		let expanded = tree.is_expanded(hovered.urn());
+		match opt.action {
+			A::None => act!(mgr:search_stop, cx),
+			A::Expand | A::Toggle if hovered.is_dir() && !expanded => Self::expand(cx, hovered),
+			A::Collapse | A::Toggle if expanded => Self::collapse(cx, hovered.urn_owned()),
+			// Collapses the directory it's in, and moves onto it
+			A::Collapse => match hovered.urn().parent().filter(|p| *p != Path::new("")) {
+				Some(parent) => {
+					let parent = UrnBuf::from(parent);
+					cx.current_mut().hover(&parent);
+					Self::collapse(cx, parent)
+				}
+				None => succ!(),
+			},
+			A::Expand | A::Toggle => succ!(),
+		}
+	}
+}
+
+impl Tree {
+	fn enter(cx: &mut Ctx) -> Result<Data> {
+		if let Some(handle) = cx.tab_mut().search.take() {
+			handle.abort();
+		}
+
+		let cwd = cx.cwd().to_search(yazi_core::tab::Tree::KEYWORD);
+		act!(mgr:cd, cx, (cwd.clone(), CdSource::Cd))?;
+
+		let folder = cx.current_mut();
+		folder.tree = Some(Default::default());
+		folder.files.set_tree(true);
+
+		// Listed as search results, so `escape --search` goes back to the directory.
+		// The hidden files are always read, and left to be filtered as usual.
+		cx.tab_mut().search = Some(tokio::spawn(async move {
+			let rx = external::flatten(FlattenOpt { cwd: cwd.clone(), hidden: true, depth: 1 })?;
+			let rx = UnboundedReceiverStream::new(rx).chunks_timeout(5000, Duration::from_millis(500));
+			pin!(rx);
+
+			let ticket = FilesOp::prepare(&cwd);
+			while let Some(chunk) = rx.next().await {
+				FilesOp::Part(cwd.clone(), chunk, ticket).emit();
+			}
+			FilesOp::Done(cwd, Cha::default(), ticket).emit();
+
+			Ok(())
+		}));
+
+		succ!(render!());
+	}
+
+	fn expand(cx: &mut Ctx, dir: File) -> Result<Data> {
+		let cwd = cx.cwd().clone();
+		if let Some(tree) = &mut cx.current_mut().tree {
+			tree.expand(dir.urn());
+		}
+
+		tokio::spawn(async move {
+			let mut rx = external::flatten(FlattenOpt { cwd: dir.url, hidden: true, depth: 1 })?;
+			let mut files = vec![];
+			while let Some(file) = rx.recv().await {
+				files.push(file);
+			}
+
+			FilesOp::Creating(cwd, files).emit();
+			anyhow::Ok(())
+		});
+		succ!(render!());
+	}
+
+	fn collapse(cx: &mut Ctx, dir: UrnBuf) -> Result<Data> {
+		let folder = cx.current_mut();
+		if let Some(tree) = &mut folder.tree {
+			tree.collapse(&dir);
+		}
+
+		let urns = folder.files.descendants(&dir).map(|f| f.urn_owned()).collect();
+		FilesOp::Deleting(folder.url.clone(), urns).emit();
+		succ!(render!());
+	}
+}
@@ -0,0 +1,70 @@
+use anyhow::Result;
+use yazi_fs::{JournalOp, maybe_exists, paths_to_same_file};
+use yazi_macro::succ;
+use yazi_parser::VoidOpt;
+use yazi_proxy::AppProxy;
+use yazi_scheduler::file::Recycle;
+use yazi_shared::{Id, event::Data, url::Url};
+
+use super::Rename;
+use crate::{Actor, Ctx};
+
+pub struct Undo;
+
+impl Actor for Undo {
+	type Options = VoidOpt;
+
+	const NAME: &str = "undo";
+
+	fn act(cx: &mut Ctx, _: Self::Options) -> Result<Data> {
+		let Some(record) = cx.mgr.journal.undo() else {
+			succ!(AppProxy::notify_warn("Undo", "Nothing to undo"));
+		};
+		if let Some(reason) = record.irreversible {
+			succ!(AppProxy::notify_warn("Undo", format!("Cannot undo {}, as {reason}", record.op)));
+		}
+
+		match record.op {
+			JournalOp::Rename { from, to } => {
+				tokio::spawn(Self::rename("Undo", cx.tab().id, to, from));
+			}
+			JournalOp::Move { from, to } => {
+				let id =
+					cx.tasks.scheduler.file_cut(to, from, Default::default(), false, Default::default());
+				cx.mgr.journal.expect_task(id);
+			}
+			JournalOp::Trash { target } => {
+				tokio::spawn(Self::restore(target));
+			}
+			JournalOp::Create { target, .. } => {
+				cx.mgr.journal.expect(JournalOp::Trash { target: target.clone() });
+				cx.tasks.file_remove(vec![target], false);
+			}
+		}
+		succ!();
+	}
+}
+
+impl Undo {
+	/// Renames a file back or forth, refusing to overwrite another one.
+	pub(super) async fn rename(title: &'static str, tab: Id, from: Url, to: Url) {
+		if maybe_exists(&to).await && !paths_to_same_file(&from, &to).await {
+			return AppProxy::notify_warn(title, format!("`{}` already exists", to.display()));
+		}
+		if let Err(e) = Rename::r#do(tab, from, to).await {
+			AppProxy::notify_warn(title, format!("{e:#}"));
+		}
+	}
+
+	async fn restore(target: Url) {
+		let Some(item) = Recycle::find(&target) else {
+			return AppProxy::notify_warn(
+				"Undo",
+				format!("`{}` isn't found in the trash", target.display()),
+			);
+		};
+		if let Err(e) = Recycle::restore(item, false).await {
+			AppProxy::notify_warn("Undo", format!("{e:#}"));
+		}
+	}
+}
@@ -0,0 +1,54 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use yazi_macro::{render, succ};
+use yazi_parser::{app::{NotifyLevel, NotifyOpt}, mgr::UpdateCompareOpt};
+use yazi_proxy::AppProxy;
+use yazi_shared::event::Data;
+
+use super::Compare;
+use crate::{Actor, Ctx};
+
+pub struct UpdateCompare;
+
+impl Actor for UpdateCompare {
+	type Options = UpdateCompareOpt;
+
+	const NAME: &str = "update_compare";
+
+	fn act(cx: &mut Ctx, opt: Self::Options) -> Result<Data> {
+		let mut c = opt.comparison;
+		let (left, right, differ) = c.counts();
+		AppProxy::notify(NotifyOpt {
+			title:   "Compare".to_owned(),
+			content: if left + right + differ == 0 {
+				"No differences".to_owned()
+			} else {
+				format!(
+					"{left} only in `{}`, {right} only in `{}`, {differ} differing",
+					c.left.name().to_string_lossy(),
+					c.right.name().to_string_lossy()
+				)
+			},
+			level:   NotifyLevel::Info,
+			timeout: Duration::from_secs(5),
+			action:  None,
+		});
+
+		// Comparing again keeps showing only the differences, if it's been on
+		if let Some(old) = &mut cx.mgr.compare
+			&& old.filter
+		{
+			old.filter = false;
+			Compare::refilter(cx)?;
+			c.filter = true;
+		}
+
+		let filter = c.filter;
+		cx.mgr.compare = Some(c);
+		if filter {
+			Compare::refilter(cx)?;
+		}
+		succ!(render!());
+	}
+}
@@ -1,9 +1,12 @@
+use std::time::Duration;
+
 use anyhow::Result;
 use yazi_core::{mgr::LINKED, tab::Folder};
-use yazi_fs::FilesOp;
+use yazi_fs::{CWD, FilesOp, Relocation, provider};
 use yazi_macro::{act, render, succ};
-use yazi_parser::mgr::UpdateFilesOpt;
-use yazi_shared::event::Data;
+use yazi_parser::{app::{NotifyLevel, NotifyOpt}, mgr::{CdSource, UpdateFilesOpt}};
+use yazi_proxy::{AppProxy, MgrProxy};
+use yazi_shared::{event::Data, url::Url};
 
 use crate::{Actor, Ctx};
 
@@ -19,6 +22,7 @@ impl Actor for UpdateFiles {
 		let linked: Vec<_> = LINKED.read().from_dir(opt.op.cwd()).map(|u| opt.op.rebase(u)).collect();
 		for op in [opt.op].into_iter().chain(linked) {
 			cx.mgr.yanked.apply_op(&op);
+			Self::update_other(cx, &op);
 			Self::update_tab(cx, op).ok();
 		}
 
@@ -52,6 +56,22 @@ impl UpdateFiles {
 		}
 	}
 
+	// Only the CWD of the other panel is shown, which is kept up to date as well
+	fn update_other(cx: &mut Ctx, op: &FilesOp) {
+		let Some(idx) = cx.tabs().other_idx() else { return };
+		let tab = &mut cx.tabs_mut()[idx];
+		if tab.current.url != *op.cwd() {
+			return;
+		}
+
+		tab.selected.apply_op(op);
+		if tab.current.update_pub(tab.id, op.clone()) {
+			tab.current.files.catchup_revision();
+			tab.current.repos(None);
+			render!();
+		}
+	}
+
 	fn update_parent(cx: &mut Ctx, op: FilesOp) -> Result<Data> {
 		let tab = cx.tab_mut();
 
@@ -64,7 +84,7 @@ impl UpdateFiles {
 		}
 
 		if leave {
-			act!(mgr:leave, cx)?;
+			Self::leave(cx)?;
 		}
 		succ!();
 	}
@@ -106,8 +126,40 @@ impl UpdateFiles {
 			.update_pub(tab.id, op);
 
 		if leave {
-			act!(mgr:leave, cx)?;
+			Self::leave(cx)?;
 		}
 		succ!();
 	}
+
+	// The CWD has gone from its parent, follow it if it was renamed, or go to the
+	// nearest existing ancestor if it was deleted
+	fn leave(cx: &mut Ctx) -> Result<Data> {
+		let cwd = cx.cwd().clone();
+		match CWD.relocation(&cwd) {
+			Some(Relocation::Renamed(p)) => {
+				let to = Url::from(p);
+				AppProxy::notify(NotifyOpt {
+					title:   "Directory renamed".to_owned(),
+					content: format!("Directory was renamed to `{}`", to.display()),
+					level:   NotifyLevel::Info,
+					timeout: Duration::from_secs(5),
+					action:  None,
+				});
+				act!(mgr:cd, cx, (to, CdSource::Cd))
+			}
+			Some(Relocation::Deleted) => {
+				tokio::spawn(async move {
+					let mut url = cwd;
+					while let Some(parent) = url.parent_url() {
+						url = parent;
+						if provider::metadata(&url).await.is_ok_and(|m| m.is_dir()) {
+							return MgrProxy::cd(&url);
+						}
+					}
+				});
+				succ!();
+			}
+			None => act!(mgr:leave, cx),
+		}
+	}
 }
@@ -0,0 +1,25 @@
+use anyhow::Result;
+use yazi_fs::{JournalOp, TAGS};
+use yazi_macro::succ;
+use yazi_parser::mgr::UpdateJournalOpt;
+use yazi_shared::event::Data;
+
+use crate::{Actor, Ctx};
+
+pub struct UpdateJournal;
+
+impl Actor for UpdateJournal {
+	type Options = UpdateJournalOpt;
+
+	const NAME: &str = "update_journal";
+
+	fn act(cx: &mut Ctx, opt: Self::Options) -> Result<Data> {
+		// Kept for trashed files, so they're still tagged once restored
+		if let JournalOp::Rename { from, to } | JournalOp::Move { from, to } = &opt.op {
+			TAGS.rename(from, to);
+		}
+
+		cx.mgr.journal.push(opt.op, opt.task);
+		succ!();
+	}
+}
@@ -14,11 +14,13 @@ impl Actor for UpdateYanked {
 	const NAME: &str = "update_yanked";
 
 	fn act(cx: &mut Ctx, opt: Self::Options) -> Result<Data> {
-		if opt.urls.is_empty() && cx.mgr.yanked.is_empty() {
+		if opt.urls.is_empty() && opt.lazy.is_none() && cx.mgr.yanked.is_void() {
 			succ!();
 		}
 
-		cx.mgr.yanked = Yanked::new(opt.cut, opt.urls.into_owned());
+		cx.mgr.yanked =
+			Yanked::new_lazy(opt.cut, opt.urls.into_owned(), opt.lazy.map(|s| s.into_owned()));
+		cx.core.tasks.file_expand(&cx.mgr.yanked);
 		succ!(render!());
 	}
 }
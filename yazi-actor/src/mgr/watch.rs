@@ -17,7 +17,8 @@ impl Actor for Watch {
 	fn act(cx: &mut Ctx, _: Self::Options) -> Result<Data> {
 		let it = iter::once(cx.core.mgr.tabs.active().cwd())
 			.chain(cx.core.mgr.tabs.parent().map(|p| &p.url))
-			.chain(cx.core.mgr.tabs.hovered().filter(|h| h.is_dir()).map(|h| &h.url));
+			.chain(cx.core.mgr.tabs.hovered().filter(|h| h.is_dir()).map(|h| &h.url))
+			.chain(cx.core.mgr.tabs.other().map(|t| t.cwd()));
 
 		cx.core.mgr.watcher.watch(it);
 		succ!();
@@ -0,0 +1,48 @@
+use std::ffi::OsStr;
+
+use anyhow::Result;
+use yazi_macro::{act, succ};
+use yazi_parser::mgr::{XattrOpt, XattrOptAction};
+use yazi_proxy::AppProxy;
+use yazi_shared::event::Data;
+
+use crate::{Actor, Ctx};
+
+pub struct Xattr;
+
+impl Actor for Xattr {
+	type Options = XattrOpt;
+
+	const NAME: &str = "xattr";
+
+	fn act(cx: &mut Ctx, opt: Self::Options) -> Result<Data> {
+		act!(mgr:escape_visual, cx)?;
+
+		let urls: Vec<_> = cx.tab().selected_or_hovered().cloned().collect();
+		if urls.is_empty() {
+			succ!();
+		}
+
+		tokio::spawn(async move {
+			let name = OsStr::new(&*opt.name);
+			let (mut failed, mut last) = (0, None);
+			for url in &urls {
+				let result = match opt.action {
+					XattrOptAction::Set => yazi_fs::Xattr::set(url, name, opt.value.as_bytes()).await,
+					XattrOptAction::Remove => yazi_fs::Xattr::remove(url, name).await,
+				};
+				if let Err(e) = result {
+					(failed, last) = (failed + 1, Some(e));
+				}
+			}
+
+			if let Some(e) = last {
+				AppProxy::notify_warn(
+					"Extended attributes",
+					format!("Failed on {failed} of {} files: {e}", urls.len()),
+				);
+			}
+		});
+		succ!();
+	}
+}
@@ -1,8 +1,13 @@
+use std::borrow::Cow;
+
 use anyhow::Result;
 use yazi_core::mgr::Yanked;
+use yazi_fs::provider;
 use yazi_macro::{act, render};
 use yazi_parser::mgr::YankOpt;
-use yazi_shared::{event::Data, url::CovUrl};
+use yazi_proxy::AppProxy;
+use yazi_shared::{event::Data, url::{CovUrl, UriList, Url}};
+use yazi_widgets::CLIPBOARD;
 
 use crate::{Actor, Ctx};
 
@@ -16,10 +21,46 @@ impl Actor for Yank {
 	fn act(cx: &mut Ctx, opt: Self::Options) -> Result<Data> {
 		act!(mgr:escape_visual, cx)?;
 
-		cx.mgr.yanked =
-			Yanked::new(opt.cut, cx.tab().selected_or_hovered().cloned().map(CovUrl).collect());
+		let tab = cx.tab();
+		let yanked = if let Some(lazy) = tab.selected_lazily() {
+			Yanked::new_lazy(opt.cut, Default::default(), Some(lazy))
+		} else {
+			Yanked::new(opt.cut, tab.selected_or_hovered().cloned().map(CovUrl).collect())
+		};
+
+		cx.mgr.yanked = yanked;
+		cx.core.tasks.file_expand(&cx.mgr.yanked);
 		render!(cx.mgr.yanked.catchup_revision(true));
 
+		if opt.system {
+			Self::export(&cx.mgr.yanked);
+		}
+
 		act!(mgr:escape_select, cx)
 	}
 }
+
+impl Yank {
+	/// Puts the yanked files into the system clipboard as well.
+	fn export(yanked: &Yanked) {
+		let (cut, lazy) = (yanked.cut, yanked.lazy().cloned());
+		let urls: Vec<Url> = yanked.iter().map(|u| u.0.clone()).collect();
+
+		tokio::spawn(async move {
+			let local = |u: &Url| provider::local_path_sync(u).ok().map(Cow::into_owned);
+			let mut paths: Vec<_> = urls.iter().filter_map(local).collect();
+			if let Some(lazy) = lazy {
+				let mut rx = lazy.expand();
+				while let Some(url) = rx.recv().await {
+					paths.extend(local(&url));
+				}
+			}
+
+			if paths.is_empty() {
+				AppProxy::notify_warn("Yank", "No local files to put into the system clipboard");
+			} else if !CLIPBOARD.set_files(UriList::new(cut, paths)).await {
+				AppProxy::notify_warn("Yank", "Failed to put the files into the system clipboard");
+			}
+		});
+	}
+}
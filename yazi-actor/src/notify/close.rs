@@ -0,0 +1,24 @@
+use anyhow::Result;
+use yazi_macro::{render, succ};
+use yazi_parser::VoidOpt;
+use yazi_shared::event::Data;
+
+use crate::{Actor, Ctx};
+
+pub struct Close;
+
+impl Actor for Close {
+	type Options = VoidOpt;
+
+	const NAME: &str = "close";
+
+	fn act(cx: &mut Ctx, _: Self::Options) -> Result<Data> {
+		let notify = &mut cx.notify;
+		if !notify.visible {
+			succ!();
+		}
+
+		notify.visible = false;
+		succ!(render!());
+	}
+}
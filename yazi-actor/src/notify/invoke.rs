@@ -0,0 +1,25 @@
+use anyhow::Result;
+use yazi_macro::{act, emit, succ};
+use yazi_parser::VoidOpt;
+use yazi_shared::event::Data;
+
+use crate::{Actor, Ctx};
+
+pub struct Invoke;
+
+impl Actor for Invoke {
+	type Options = VoidOpt;
+
+	const NAME: &str = "invoke";
+
+	fn act(cx: &mut Ctx, _: Self::Options) -> Result<Data> {
+		let Some(action) = cx.notify.hovered().and_then(|r| r.action.as_ref()) else {
+			succ!();
+		};
+
+		let cmd = action.cmd()?;
+		act!(notify:close, cx)?;
+		emit!(Call(cmd));
+		succ!();
+	}
+}
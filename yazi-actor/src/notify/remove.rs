@@ -0,0 +1,25 @@
+use anyhow::Result;
+use yazi_macro::{render, succ};
+use yazi_parser::VoidOpt;
+use yazi_shared::event::Data;
+
+use crate::{Actor, Ctx};
+
+pub struct Remove;
+
+impl Actor for Remove {
+	type Options = VoidOpt;
+
+	const NAME: &str = "remove";
+
+	fn act(cx: &mut Ctx, _: Self::Options) -> Result<Data> {
+		let notify = &mut cx.notify;
+		if notify.history.remove(notify.cursor).is_none() {
+			succ!();
+		}
+
+		notify.cursor = notify.cursor.min(notify.history.len().saturating_sub(1));
+		notify.offset = notify.offset.min(notify.cursor);
+		succ!(render!());
+	}
+}
@@ -0,0 +1,26 @@
+use anyhow::Result;
+use yazi_macro::{render, succ};
+use yazi_parser::VoidOpt;
+use yazi_shared::event::Data;
+
+use crate::{Actor, Ctx};
+
+pub struct Show;
+
+impl Actor for Show {
+	type Options = VoidOpt;
+
+	const NAME: &str = "show";
+
+	fn act(cx: &mut Ctx, _: Self::Options) -> Result<Data> {
+		let notify = &mut cx.notify;
+		if notify.visible {
+			succ!();
+		}
+
+		notify.visible = true;
+		notify.cursor = 0;
+		notify.offset = 0;
+		succ!(render!());
+	}
+}
@@ -0,0 +1,20 @@
+use anyhow::Result;
+use yazi_fs::BANDWIDTH;
+use yazi_macro::succ;
+use yazi_parser::tasks::LimitOpt;
+use yazi_shared::event::Data;
+
+use crate::{Actor, Ctx};
+
+pub struct Limit;
+
+impl Actor for Limit {
+	type Options = LimitOpt;
+
+	const NAME: &str = "limit";
+
+	fn act(_: &mut Ctx, opt: Self::Options) -> Result<Data> {
+		BANDWIDTH.set_limit(opt.rate);
+		succ!();
+	}
+}
@@ -1,4 +1,5 @@
 use anyhow::Result;
+use yazi_fs::FRECENCY;
 use yazi_macro::succ;
 use yazi_parser::mgr::OpenWithOpt;
 use yazi_shared::event::Data;
@@ -13,10 +14,7 @@ impl Actor for OpenWith {
 	const NAME: &str = "open_with";
 
 	fn act(cx: &mut Ctx, opt: Self::Options) -> Result<Data> {
-		succ!(cx.tasks.process_from_opener(
-			opt.cwd,
-			opt.opener,
-			opt.targets.into_iter().map(|u| u.into_path().into_os_string()).collect(),
-		));
+		opt.targets.iter().for_each(|u| FRECENCY.open(u));
+		succ!(cx.tasks.process_from_opener(opt.cwd, opt.opener, opt.targets));
 	}
 }
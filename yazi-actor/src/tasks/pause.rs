@@ -0,0 +1,26 @@
+use anyhow::Result;
+use yazi_macro::{render, succ};
+use yazi_parser::VoidOpt;
+use yazi_shared::event::Data;
+
+use crate::{Actor, Ctx};
+
+pub struct Pause;
+
+impl Actor for Pause {
+	type Options = VoidOpt;
+
+	const NAME: &str = "pause";
+
+	fn act(cx: &mut Ctx, _: Self::Options) -> Result<Data> {
+		let tasks = &mut cx.tasks;
+
+		let id = tasks.ongoing().lock().get_id(tasks.cursor);
+		if id.and_then(|id| tasks.scheduler.pause(id)).is_none() {
+			succ!();
+		}
+
+		tasks.summaries = tasks.paginate();
+		succ!(render!());
+	}
+}
@@ -0,0 +1,18 @@
+use anyhow::Result;
+use yazi_macro::succ;
+use yazi_parser::tasks::PurgeTrashOpt;
+use yazi_shared::event::Data;
+
+use crate::{Actor, Ctx};
+
+pub struct PurgeTrash;
+
+impl Actor for PurgeTrash {
+	type Options = PurgeTrashOpt;
+
+	const NAME: &str = "purge_trash";
+
+	fn act(cx: &mut Ctx, opt: Self::Options) -> Result<Data> {
+		succ!(cx.tasks.scheduler.file_purge(opt.ids, opt.size));
+	}
+}
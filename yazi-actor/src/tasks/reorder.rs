@@ -0,0 +1,32 @@
+use anyhow::Result;
+use yazi_core::tasks::Tasks;
+use yazi_macro::{render, succ};
+use yazi_parser::tasks::ReorderOpt;
+use yazi_shared::event::Data;
+
+use crate::{Actor, Ctx};
+
+pub struct Reorder;
+
+impl Actor for Reorder {
+	type Options = ReorderOpt;
+
+	const NAME: &str = "reorder";
+
+	fn act(cx: &mut Ctx, opt: Self::Options) -> Result<Data> {
+		let tasks = &mut cx.tasks;
+
+		let id = tasks.ongoing().lock().get_id(tasks.cursor);
+		if id.map(|id| tasks.scheduler.reorder(id, opt.offset)) != Some(true) {
+			succ!();
+		}
+
+		// Follow the task moved
+		tasks.summaries = tasks.paginate();
+		tasks.cursor = tasks
+			.cursor
+			.saturating_add_signed(opt.offset)
+			.min(tasks.summaries.len().min(Tasks::limit()).saturating_sub(1));
+		succ!(render!());
+	}
+}
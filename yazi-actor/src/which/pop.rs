@@ -0,0 +1,28 @@
+use anyhow::Result;
+use yazi_macro::{render, succ};
+use yazi_parser::VoidOpt;
+use yazi_shared::event::Data;
+
+use crate::{Actor, Ctx};
+
+pub struct Pop;
+
+impl Actor for Pop {
+	type Options = VoidOpt;
+
+	const NAME: &str = "pop";
+
+	fn act(cx: &mut Ctx, _: Self::Options) -> Result<Data> {
+		let which = &mut cx.which;
+		if which.modes.pop().is_none() {
+			succ!();
+		}
+
+		if !which.resume() {
+			which.visible = false;
+			which.cands.clear();
+			which.times = 0;
+		}
+		succ!(render!());
+	}
+}
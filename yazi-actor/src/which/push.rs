@@ -0,0 +1,24 @@
+use anyhow::{Result, bail};
+use yazi_config::KEYMAP;
+use yazi_macro::succ;
+use yazi_parser::which::PushOpt;
+use yazi_shared::event::Data;
+
+use crate::{Actor, Ctx};
+
+pub struct Push;
+
+impl Actor for Push {
+	type Options = PushOpt;
+
+	const NAME: &str = "push";
+
+	fn act(cx: &mut Ctx, opt: Self::Options) -> Result<Data> {
+		if KEYMAP.mode(&opt.name).is_none() {
+			bail!("No such keymap mode: {}", opt.name);
+		}
+
+		cx.which.modes.push(opt.name.into_owned());
+		succ!(cx.which.resume());
+	}
+}
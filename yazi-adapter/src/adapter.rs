@@ -1,11 +1,12 @@
-use std::{env, fmt::Display};
+use std::{env, fmt::Display, str::FromStr};
 
-use anyhow::Result;
+use anyhow::{Result, bail};
 use ratatui::layout::Rect;
-use tracing::warn;
+use tracing::{debug, warn};
+use yazi_config::YAZI;
 use yazi_shared::{env_exists, url::Url};
 
-use crate::{Emulator, SHOWN, TMUX, drivers};
+use crate::{Brand, Emulator, SHOWN, Session, TMUX, drivers};
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum Adapter {
@@ -34,6 +35,23 @@ impl Display for Adapter {
 	}
 }
 
+impl FromStr for Adapter {
+	type Err = anyhow::Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		Ok(match s {
+			"kgp" => Self::Kgp,
+			"kgp-old" => Self::KgpOld,
+			"iip" => Self::Iip,
+			"sixel" => Self::Sixel,
+			"x11" => Self::X11,
+			"wayland" => Self::Wayland,
+			"chafa" => Self::Chafa,
+			_ => bail!("unknown image protocol `{s}`"),
+		})
+	}
+}
+
 impl Adapter {
 	pub async fn image_show(self, url: &Url, max: Rect) -> Result<Rect> {
 		if max.is_empty() {
@@ -81,6 +99,10 @@ impl Adapter {
 
 impl Adapter {
 	pub fn matches(emulator: Emulator) -> Self {
+		if let Some(p) = Self::configured(emulator) {
+			return p;
+		}
+
 		let mut protocols = emulator.adapters().to_owned();
 		if env_exists("ZELLIJ_SESSION_NAME") {
 			protocols.retain(|p| *p == Self::Sixel);
@@ -92,21 +114,49 @@ impl Adapter {
 		}
 
 		let supported_compositor = drivers::Ueberzug::supported_compositor();
-		match env::var("XDG_SESSION_TYPE").unwrap_or_default().as_str() {
-			"x11" => return Self::X11,
-			"wayland" if supported_compositor => return Self::Wayland,
-			"wayland" if !supported_compositor => return Self::Chafa,
-			_ => warn!("[Adapter] Could not identify XDG_SESSION_TYPE"),
-		}
-		if env_exists("WAYLAND_DISPLAY") {
-			return if supported_compositor { Self::Wayland } else { Self::Chafa };
+		Self::fallback(Session::display(|k| env::var(k).ok()), supported_compositor)
+	}
+
+	/// The protocol in `[preview].image_protocols` for the terminal, if any.
+	fn configured(emulator: Emulator) -> Option<Self> {
+		let map = &YAZI.preview.image_protocols;
+		if map.is_empty() {
+			return None;
 		}
-		match env::var("DISPLAY").unwrap_or_default().as_str() {
-			s if !s.is_empty() && !s.contains("/org.xquartz") => return Self::X11,
-			_ => {}
+
+		let mux = if TMUX.get() {
+			Some("tmux")
+		} else if env_exists("ZELLIJ_SESSION_NAME") {
+			Some("zellij")
+		} else {
+			None
+		};
+
+		let brand = emulator.kind.left().map(|b| b.to_string());
+		let keys = [mux.map(Into::into), brand, Some(Brand::env().0)];
+
+		let (key, value) = keys.into_iter().flatten().find_map(|k| map.get(&k).map(|v| (k, v)))?;
+		match value.parse() {
+			Ok(p) => {
+				debug!("[Adapter] Using `{p}` as configured for `{key}`");
+				Some(p)
+			}
+			Err(e) => {
+				warn!("[Adapter] Ignoring `[preview].image_protocols.{key}`: {e}");
+				None
+			}
 		}
+	}
 
-		warn!("[Adapter] Falling back to chafa");
-		Self::Chafa
+	pub(super) fn fallback(display: Option<Session>, supported_compositor: bool) -> Self {
+		match display {
+			Some(Session::X11) => Self::X11,
+			Some(Session::Wayland) if supported_compositor => Self::Wayland,
+			Some(Session::Wayland) => Self::Chafa,
+			_ => {
+				warn!("[Adapter] Falling back to chafa");
+				Self::Chafa
+			}
+		}
 	}
 }
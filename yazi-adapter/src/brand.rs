@@ -1,3 +1,5 @@
+use std::fmt::Display;
+
 use tracing::debug;
 use yazi_shared::env_exists;
 
@@ -26,6 +28,32 @@ pub enum Brand {
 	Bobcat,
 }
 
+impl Display for Brand {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.write_str(match self {
+			Self::Kitty => "kitty",
+			Self::Konsole => "konsole",
+			Self::Iterm2 => "iterm2",
+			Self::WezTerm => "wezterm",
+			Self::Foot => "foot",
+			Self::Ghostty => "ghostty",
+			Self::Microsoft => "windows-terminal",
+			Self::Warp => "warp",
+			Self::Rio => "rio",
+			Self::BlackBox => "blackbox",
+			Self::VSCode => "vscode",
+			Self::Tabby => "tabby",
+			Self::Hyper => "hyper",
+			Self::Mintty => "mintty",
+			Self::Tmux => "tmux",
+			Self::VTerm => "vterm",
+			Self::Apple => "apple-terminal",
+			Self::Urxvt => "urxvt",
+			Self::Bobcat => "bobcat",
+		})
+	}
+}
+
 impl Brand {
 	pub(super) fn from_csi(resp: &str) -> Option<Self> {
 		let names = [
@@ -59,14 +87,8 @@ impl Brand {
 			("TABBY_CONFIG_DIRECTORY", B::Tabby),
 		];
 
-		match term.as_str() {
-			"xterm-kitty" => return Some(B::Kitty),
-			"foot" => return Some(B::Foot),
-			"foot-extra" => return Some(B::Foot),
-			"xterm-ghostty" => return Some(B::Ghostty),
-			"rio" => return Some(B::Rio),
-			"rxvt-unicode-256color" => return Some(B::Urxvt),
-			_ => {}
+		if let Some(brand) = B::from_term(&term) {
+			return Some(brand);
 		}
 		match program.as_str() {
 			"iTerm.app" => return Some(B::Iterm2),
@@ -90,6 +112,21 @@ impl Brand {
 		None
 	}
 
+	/// The brand of a terminfo name, such as `$TERM` or the `TN` capability reported by XTGETTCAP.
+	pub(super) fn from_term(term: &str) -> Option<Self> {
+		Some(match term {
+			"xterm-kitty" => Self::Kitty,
+			"foot" => Self::Foot,
+			"foot-extra" => Self::Foot,
+			"xterm-ghostty" => Self::Ghostty,
+			"wezterm" => Self::WezTerm,
+			"rio" => Self::Rio,
+			"mintty" => Self::Mintty,
+			"rxvt-unicode-256color" => Self::Urxvt,
+			_ => return None,
+		})
+	}
+
 	pub(super) fn adapters(self) -> &'static [crate::Adapter] {
 		use Brand as B;
 
@@ -118,7 +155,7 @@ impl Brand {
 		}
 	}
 
-	fn env() -> (String, String) {
+	pub(super) fn env() -> (String, String) {
 		let (term, program) = Mux::term_program();
 		(
 			term.unwrap_or(std::env::var("TERM").unwrap_or_default()),
@@ -8,12 +8,12 @@ use tracing::{debug, error, warn};
 use yazi_shared::Either;
 use yazi_term::tty::{Handle, TTY};
 
-use crate::{Adapter, Brand, Dimension, Mux, TMUX, Unknown};
+use crate::{Adapter, Brand, Dimension, HOST, Host, Mux, TMUX, Unknown};
 
 #[derive(Clone, Copy, Debug)]
 pub struct Emulator {
 	pub kind:      Either<Brand, Unknown>,
-	pub light:     bool,
+	pub light:     Option<bool>,
 	pub csi_16t:   (u16, u16),
 	pub force_16t: bool,
 }
@@ -28,16 +28,22 @@ impl Emulator {
 		enable_raw_mode()?;
 
 		let resort = Brand::from_env();
-		let kgp_seq = if resort.is_none() {
-			Mux::csi("\x1b_Gi=31,s=1,v=1,a=q,t=d,f=24;AAAA\x1b\\")
+		let (kgp_seq, iip_seq, tcap_seq) = if resort.is_none() {
+			(
+				Mux::csi("\x1b_Gi=31,s=1,v=1,a=q,t=d,f=24;AAAA\x1b\\"),
+				Mux::csi("\x1b]1337;ReportCellSize\x07"),
+				Mux::csi("\x1bP+q544e\x1b\\"),
+			)
 		} else {
-			"".into()
+			Default::default()
 		};
 
 		execute!(
 			TTY.writer(),
 			SavePosition,
 			Print(kgp_seq),             // Detect KGP
+			Print(iip_seq),             // Detect IIP
+			Print(tcap_seq),            // Request terminal name
 			Print(Mux::csi("\x1b[>q")), // Request terminal version
 			Print("\x1b[16t"),          // Request cell size
 			Print("\x1b]11;?\x07"),     // Request background color
@@ -48,19 +54,26 @@ impl Emulator {
 		let resp = Self::read_until_da1();
 		Mux::tmux_drain()?;
 
-		let kind = if let Some(b) = Brand::from_csi(&resp).or(resort) {
+		let brand = Brand::from_csi(&resp)
+			.or(resort)
+			.or_else(|| Self::terminal_name(&resp).and_then(|s| Brand::from_term(&s)));
+
+		let kind = if let Some(b) = brand {
 			Either::Left(b)
 		} else {
 			Either::Right(Unknown {
 				kgp:   resp.contains("\x1b_Gi=31;OK"),
+				iip:   resp.contains("\x1b]1337;ReportCellSize="),
 				sixel: ["?4;", "?4c", ";4;", ";4c"].iter().any(|s| resp.contains(s)),
 			})
 		};
 
+		HOST.write().terminal_version = Host::xtversion(&resp);
+
 		let csi_16t = Self::csi_16t(&resp).unwrap_or_default();
 		Ok(Self {
 			kind,
-			light: Self::light_bg(&resp).unwrap_or_default(),
+			light: Self::light_bg(&resp),
 			csi_16t,
 			force_16t: Self::force_16t(csi_16t),
 		})
@@ -69,7 +82,7 @@ impl Emulator {
 	pub const fn unknown() -> Self {
 		Self {
 			kind:      Either::Right(Unknown::default()),
-			light:     false,
+			light:     None,
 			csi_16t:   (0, 0),
 			force_16t: false,
 		}
@@ -182,19 +195,37 @@ impl Emulator {
 		Some((w.parse().ok()?, h.parse().ok()?))
 	}
 
-	fn light_bg(resp: &str) -> Result<bool> {
+	/// The `TN` capability from the response of XTGETTCAP.
+	fn terminal_name(resp: &str) -> Option<String> {
+		let s = resp.split_once("\x1bP1+r")?.1;
+		let (key, value) = s.split_once('\x1b')?.0.split_once('=')?;
+		if !key.eq_ignore_ascii_case("544e") || value.len() % 2 != 0 {
+			return None;
+		}
+
+		let bytes: Vec<_> = (0..value.len())
+			.step_by(2)
+			.map(|i| u8::from_str_radix(value.get(i..i + 2)?, 16).ok())
+			.collect::<Option<_>>()?;
+
+		let name = String::from_utf8(bytes).ok()?;
+		debug!("Detected terminal name: {name}");
+		Some(name)
+	}
+
+	fn light_bg(resp: &str) -> Option<bool> {
 		match resp.split_once("]11;rgb:") {
 			Some((_, s)) if s.len() >= 14 => {
-				let r = u8::from_str_radix(&s[0..2], 16)? as f32;
-				let g = u8::from_str_radix(&s[5..7], 16)? as f32;
-				let b = u8::from_str_radix(&s[10..12], 16)? as f32;
+				let r = u8::from_str_radix(s.get(0..2)?, 16).ok()? as f32;
+				let g = u8::from_str_radix(s.get(5..7)?, 16).ok()? as f32;
+				let b = u8::from_str_radix(s.get(10..12)?, 16).ok()? as f32;
 				let luma = r * 0.2627 / 256.0 + g * 0.6780 / 256.0 + b * 0.0593 / 256.0;
 				debug!("Detected background color: {} (luma = {luma:.2})", &s[..14]);
-				Ok(luma > 0.6)
+				Some(luma > 0.6)
 			}
 			_ => {
 				warn!("Failed to detect background color: {resp:?}");
-				Ok(false)
+				None
 			}
 		}
 	}
@@ -209,3 +240,19 @@ impl Emulator {
 			.is_none_or(|(rw, rh)| rw.floor() as u16 != w || rh.floor() as u16 != h)
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_terminal_name() {
+		let name = |s: &str| Emulator::terminal_name(s);
+
+		assert_eq!(name("\x1bP1+r544e=787465726d2d6b69747479\x1b\\").as_deref(), Some("xterm-kitty"));
+		assert_eq!(name("\x1b[?62;4c\x1bP1+r544E=666F6F74\x1b\\").as_deref(), Some("foot"));
+		assert_eq!(name("\x1bP0+r544e\x1b\\"), None);
+		assert_eq!(name("\x1bP1+r436f=323536\x1b\\"), None);
+		assert_eq!(name("\x1bP1+r544e=7\x1b\\"), None);
+	}
+}
@@ -0,0 +1,85 @@
+use std::{ffi::OsString, io, path::Path};
+
+use tokio::process::Command;
+
+/// Converts the formats that can't be decoded in-process to PNG.
+pub(super) struct External;
+
+impl External {
+	pub(super) async fn convert(src: &Path, dst: &Path) -> io::Result<()> {
+		let ext = src.extension().and_then(|e| e.to_str()).unwrap_or_default().to_ascii_lowercase();
+		for (bin, args) in Self::decoders(&ext, src, dst) {
+			let status = match Command::new(bin)
+				.args(args)
+				.stdin(std::process::Stdio::null())
+				.stdout(std::process::Stdio::null())
+				.stderr(std::process::Stdio::null())
+				.kill_on_drop(true)
+				.status()
+				.await
+			{
+				Ok(status) => status,
+				Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+				Err(e) => return Err(e),
+			};
+
+			// Some of them exit successfully without writing anything
+			if status.success() && tokio::fs::metadata(dst).await.is_ok_and(|m| m.len() > 0) {
+				return Ok(());
+			}
+		}
+
+		Err(io::Error::other(format!(
+			"Failed to decode `.{ext}` files, install libheif, libavif, libjxl, or ImageMagick"
+		)))
+	}
+
+	fn decoders(ext: &str, src: &Path, dst: &Path) -> Vec<(&'static str, Vec<OsString>)> {
+		let (src, dst) = (src.as_os_str().to_owned(), dst.as_os_str().to_owned());
+		let mut frame = src.clone();
+		frame.push("[0]");
+
+		let mut v = match ext {
+			"heic" | "heif" => vec![
+				("heif-dec", vec![src.clone(), "-o".into(), dst.clone()]),
+				("heif-convert", vec![src.clone(), dst.clone()]),
+			],
+			"avif" => vec![
+				("avifdec", vec![src.clone(), dst.clone()]),
+				("heif-dec", vec![src.clone(), "-o".into(), dst.clone()]),
+			],
+			"jxl" => vec![("djxl", vec![src.clone(), dst.clone()])],
+			_ => vec![],
+		};
+		if cfg!(target_os = "macos") {
+			v.push((
+				"sips",
+				vec!["-s".into(), "format".into(), "png".into(), src, "--out".into(), dst.clone()],
+			));
+		}
+		v.push(("magick", vec![frame, "-auto-orient".into(), dst]));
+		v
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_decoders() {
+		let bins = |ext: &str| {
+			let v = External::decoders(ext, Path::new("/a/b"), Path::new("/c.png"));
+			v.into_iter().map(|(bin, _)| bin).filter(|&b| b != "sips").collect::<Vec<_>>()
+		};
+
+		assert_eq!(bins("heic"), ["heif-dec", "heif-convert", "magick"]);
+		assert_eq!(bins("avif"), ["avifdec", "heif-dec", "magick"]);
+		assert_eq!(bins("jxl"), ["djxl", "magick"]);
+		assert_eq!(bins("tiff"), ["magick"]);
+
+		let (_, args) =
+			External::decoders("png", Path::new("/a/b"), Path::new("/c.png")).pop().unwrap();
+		assert_eq!(args, ["/a/b[0]", "-auto-orient", "/c.png"]);
+	}
+}
@@ -0,0 +1,316 @@
+use std::{collections::HashMap, env, fmt::Display, path::{Path, PathBuf}, process::{Command, Stdio}, sync::LazyLock};
+
+use parking_lot::{Mutex, RwLock};
+use yazi_shared::{env_exists, in_ssh_connection};
+
+use crate::TMUX;
+
+pub static HOST: RwLock<Host> = RwLock::new(Host::new());
+
+static WHICH: LazyLock<Mutex<HashMap<String, Option<PathBuf>>>> = LazyLock::new(Default::default);
+
+/// The environment Yazi runs in, besides the emulator and adapter it detected.
+#[derive(Clone, Debug, Default)]
+pub struct Host {
+	pub terminal_version: Option<String>,
+	pub mux:              Option<&'static str>,
+	pub mux_version:      Option<String>,
+	pub session:          Session,
+}
+
+impl Host {
+	const fn new() -> Self {
+		Self { terminal_version: None, mux: None, mux_version: None, session: Session::Console }
+	}
+
+	/// Detects the multiplexer and session again, and forgets the `which()` cache.
+	pub fn refresh() {
+		let (mux, mux_version) = Self::mux();
+		let session = Session::detect();
+
+		let mut host = HOST.write();
+		if host.terminal_version.is_none() && mux != Some("tmux") {
+			host.terminal_version = env::var("TERM_PROGRAM_VERSION").ok().filter(|s| !s.is_empty());
+		}
+		host.mux = mux;
+		host.mux_version = mux_version;
+		host.session = session;
+
+		drop(host);
+		WHICH.lock().clear();
+	}
+
+	/// Finds an executable in `PATH`, caching the result until the next `refresh()`.
+	pub fn which(name: &str) -> Option<PathBuf> {
+		if let Some(path) = WHICH.lock().get(name) {
+			return path.clone();
+		}
+
+		let path = Self::lookup(name);
+		WHICH.lock().insert(name.to_owned(), path.clone());
+		path
+	}
+
+	/// Whether the OS is in light mode, or `None` if it doesn't tell.
+	pub fn os_light() -> Option<bool> {
+		if in_ssh_connection() {
+			return None;
+		}
+
+		const KEY: &str = r"HKCU\Software\Microsoft\Windows\CurrentVersion\Themes\Personalize";
+		let (bin, args): (&str, &[&str]) = if cfg!(target_os = "macos") {
+			("defaults", &["read", "-g", "AppleInterfaceStyle"])
+		} else if cfg!(windows) {
+			("reg", &["query", KEY, "/v", "AppsUseLightTheme"])
+		} else {
+			("gsettings", &["get", "org.gnome.desktop.interface", "color-scheme"])
+		};
+
+		let output =
+			Command::new(bin).args(args).stdin(Stdio::null()).stderr(Stdio::null()).output().ok()?;
+		Self::light_mode(bin, output.status.success(), &String::from_utf8_lossy(&output.stdout))
+	}
+
+	/// The command printing a line whenever the dark mode of the OS changes, if there's one.
+	pub fn os_light_monitor() -> Option<tokio::process::Command> {
+		if in_ssh_connection() || cfg!(any(target_os = "macos", windows)) {
+			return None;
+		}
+
+		let mut cmd = tokio::process::Command::new("gsettings");
+		cmd
+			.args(["monitor", "org.gnome.desktop.interface", "color-scheme"])
+			.stdin(Stdio::null())
+			.stdout(Stdio::piped())
+			.stderr(Stdio::null())
+			.kill_on_drop(true);
+		Some(cmd)
+	}
+
+	/// Extracts the version from an XTVERSION response.
+	pub(super) fn xtversion(resp: &str) -> Option<String> {
+		let s = resp.split_once("\x1bP>|")?.1.split_once("\x1b\\")?.0;
+		let v = match s.split_once('(') {
+			Some((_, v)) => v.split_once(')')?.0,
+			None => s.split_once(' ')?.1,
+		};
+
+		let v = v.trim();
+		if v.is_empty() { None } else { Some(v.to_owned()) }
+	}
+
+	fn light_mode(bin: &str, success: bool, stdout: &str) -> Option<bool> {
+		match bin {
+			// `AppleInterfaceStyle` only exists in dark mode
+			"defaults" => Some(!success || stdout.trim() != "Dark"),
+			"reg" if success => stdout.split_whitespace().last().map(|s| s != "0x0"),
+			"gsettings" if success => match stdout.trim().trim_matches('\'') {
+				"prefer-dark" => Some(false),
+				"prefer-light" | "default" => Some(true),
+				_ => None,
+			},
+			_ => None,
+		}
+	}
+
+	fn mux() -> (Option<&'static str>, Option<String>) {
+		if TMUX.get() || env_exists("TMUX") {
+			let version = if env::var("TERM_PROGRAM").is_ok_and(|s| s == "tmux") {
+				env::var("TERM_PROGRAM_VERSION").ok().filter(|s| !s.is_empty())
+			} else {
+				None
+			};
+			(Some("tmux"), version.or_else(|| Self::version_of("tmux", "-V")))
+		} else if env_exists("ZELLIJ_SESSION_NAME") {
+			(Some("zellij"), Self::version_of("zellij", "--version"))
+		} else if env_exists("STY") {
+			(Some("screen"), Self::version_of("screen", "-v"))
+		} else {
+			(None, None)
+		}
+	}
+
+	fn version_of(bin: &str, arg: &str) -> Option<String> {
+		let output = Command::new(bin).arg(arg).stdin(Stdio::null()).output().ok()?;
+		let stdout = String::from_utf8_lossy(&output.stdout);
+		Self::version(&stdout).or_else(|| Self::version(&String::from_utf8_lossy(&output.stderr)))
+	}
+
+	/// Picks the first word that contains a version number out of a `--version` output.
+	fn version(s: &str) -> Option<String> {
+		s.split_whitespace().find_map(|w| {
+			let w = w.trim_start_matches(|c: char| !c.is_ascii_digit());
+			if w.is_empty() { None } else { Some(w.to_owned()) }
+		})
+	}
+
+	fn lookup(name: &str) -> Option<PathBuf> {
+		let name = Path::new(name);
+		if name.as_os_str().is_empty() {
+			return None;
+		} else if name.components().count() > 1 {
+			return Self::candidates(name.to_owned()).into_iter().find(|p| Self::executable(p));
+		}
+
+		env::split_paths(&env::var_os("PATH")?)
+			.filter(|dir| !dir.as_os_str().is_empty())
+			.find_map(|dir| Self::candidates(dir.join(name)).into_iter().find(|p| Self::executable(p)))
+	}
+
+	#[cfg(unix)]
+	fn candidates(path: PathBuf) -> Vec<PathBuf> { vec![path] }
+
+	#[cfg(windows)]
+	fn candidates(path: PathBuf) -> Vec<PathBuf> {
+		if path.extension().is_some() {
+			return vec![path];
+		}
+
+		let exts = env::var("PATHEXT").unwrap_or_else(|_| ".COM;.EXE;.BAT;.CMD".to_owned());
+		exts
+			.split(';')
+			.filter(|e| !e.is_empty())
+			.map(|e| {
+				let mut s = path.clone().into_os_string();
+				s.push(e);
+				s.into()
+			})
+			.collect()
+	}
+
+	#[cfg(unix)]
+	fn executable(path: &Path) -> bool {
+		use std::os::unix::fs::PermissionsExt;
+		path.metadata().is_ok_and(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+	}
+
+	#[cfg(windows)]
+	fn executable(path: &Path) -> bool { path.is_file() }
+}
+
+// --- Session
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Session {
+	/// Neither a display server nor an SSH connection was found.
+	#[default]
+	Console,
+	X11,
+	Wayland,
+	Ssh,
+}
+
+impl Display for Session {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.write_str(match self {
+			Self::Console => "console",
+			Self::X11 => "x11",
+			Self::Wayland => "wayland",
+			Self::Ssh => "ssh",
+		})
+	}
+}
+
+impl Session {
+	pub fn detect() -> Self {
+		if in_ssh_connection() {
+			Self::Ssh
+		} else {
+			Self::display(|k| env::var(k).ok()).unwrap_or_default()
+		}
+	}
+
+	/// The display server, in the order the adapter checks it.
+	pub(super) fn display(var: impl Fn(&str) -> Option<String>) -> Option<Self> {
+		match var("XDG_SESSION_TYPE").unwrap_or_default().as_str() {
+			"x11" => return Some(Self::X11),
+			"wayland" => return Some(Self::Wayland),
+			_ => {}
+		}
+		if var("WAYLAND_DISPLAY").is_some_and(|s| !s.is_empty()) {
+			return Some(Self::Wayland);
+		}
+		match var("DISPLAY").unwrap_or_default().as_str() {
+			s if !s.is_empty() && !s.contains("/org.xquartz") => Some(Self::X11),
+			_ => None,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::Adapter;
+
+	#[test]
+	fn test_display() {
+		let cases: [(&[(&str, &str)], _); 6] = [
+			(&[("XDG_SESSION_TYPE", "x11"), ("WAYLAND_DISPLAY", "wayland-0")], Some(Session::X11)),
+			(&[("XDG_SESSION_TYPE", "wayland"), ("DISPLAY", ":0")], Some(Session::Wayland)),
+			(&[("XDG_SESSION_TYPE", "tty"), ("WAYLAND_DISPLAY", "wayland-0")], Some(Session::Wayland)),
+			(&[("DISPLAY", ":0")], Some(Session::X11)),
+			(&[("DISPLAY", "/private/tmp/com.apple.launchd.abc/org.xquartz:0")], None),
+			(&[], None),
+		];
+
+		for (vars, expected) in cases {
+			let display =
+				Session::display(|k| vars.iter().find(|&&(n, _)| n == k).map(|&(_, v)| v.to_owned()));
+			assert_eq!(display, expected, "{vars:?}");
+
+			// The session must agree with what the adapter falls back to
+			for compositor in [true, false] {
+				let adapter = Adapter::fallback(display, compositor);
+				match display {
+					Some(Session::X11) => assert_eq!(adapter, Adapter::X11),
+					Some(Session::Wayland) if compositor => assert_eq!(adapter, Adapter::Wayland),
+					_ => assert_eq!(adapter, Adapter::Chafa),
+				}
+			}
+		}
+	}
+
+	#[test]
+	fn test_xtversion() {
+		assert_eq!(Host::xtversion("\x1bP>|kitty(0.42.1)\x1b\\\x1b[?62c"), Some("0.42.1".to_owned()));
+		assert_eq!(Host::xtversion("\x1bP>|tmux 3.5a\x1b\\"), Some("3.5a".to_owned()));
+		assert_eq!(Host::xtversion("\x1bP>|WezTerm 20240203\x1b\\"), Some("20240203".to_owned()));
+		assert_eq!(Host::xtversion("\x1bP>|foot\x1b\\"), None);
+		assert_eq!(Host::xtversion("\x1b[?62;4c"), None);
+	}
+
+	#[test]
+	fn test_light_mode() {
+		assert_eq!(Host::light_mode("defaults", true, "Dark\n"), Some(false));
+		assert_eq!(Host::light_mode("defaults", false, ""), Some(true));
+
+		let reg = "\r\nHKEY_CURRENT_USER\\...\r\n    AppsUseLightTheme    REG_DWORD    0x0";
+		assert_eq!(Host::light_mode("reg", true, reg), Some(false));
+		assert_eq!(Host::light_mode("reg", true, &reg.replace("0x0", "0x1")), Some(true));
+		assert_eq!(Host::light_mode("reg", false, ""), None);
+
+		assert_eq!(Host::light_mode("gsettings", true, "'prefer-dark'\n"), Some(false));
+		assert_eq!(Host::light_mode("gsettings", true, "'default'\n"), Some(true));
+		assert_eq!(Host::light_mode("gsettings", false, ""), None);
+	}
+
+	#[test]
+	fn test_version() {
+		assert_eq!(Host::version("tmux 3.4\n"), Some("3.4".to_owned()));
+		assert_eq!(Host::version("tmux next-3.5"), Some("3.5".to_owned()));
+		assert_eq!(Host::version("zellij 0.42.2"), Some("0.42.2".to_owned()));
+		assert_eq!(Host::version("Screen version 4.09.01 (GNU) 20-Aug-23"), Some("4.09.01".to_owned()));
+		assert_eq!(Host::version("unknown"), None);
+	}
+
+	#[cfg(unix)]
+	#[test]
+	fn test_which() {
+		let sh = Host::which("sh").expect("`sh` should be in PATH");
+		assert!(sh.is_absolute());
+		assert_eq!(Host::which("sh"), Some(sh.clone()));
+		assert_eq!(Host::which(sh.to_str().unwrap()), Some(sh));
+
+		assert_eq!(Host::which("yazi-surely-not-a-binary"), None);
+		assert_eq!(Host::which(""), None);
+	}
+}
@@ -1,3 +1,5 @@
+use std::io::{BufRead, Seek};
+
 use anyhow::Result;
 use image::{DynamicImage, ExtendedColorType, ImageDecoder, ImageEncoder, ImageError, ImageFormat, ImageReader, ImageResult, Limits, codecs::{jpeg::JpegEncoder, png::PngEncoder}, imageops::FilterType, metadata::Orientation};
 use ratatui::layout::Rect;
@@ -5,22 +7,19 @@ use yazi_config::YAZI;
 use yazi_fs::provider;
 use yazi_shared::url::Url;
 
-use crate::Dimension;
+use crate::{Dimension, External, SCALED, ScaledKey};
+
+type Decoded = (DynamicImage, Orientation, Option<Vec<u8>>);
 
 pub struct Image;
 
 impl Image {
 	pub async fn precache(src: &Url, cache: &Url) -> Result<()> {
-		let (mut img, orientation, icc) = Self::decode_from(src).await?;
+		let (img, orientation, icc) = Self::decode_from(src).await?;
 		let (w, h) = Self::flip_size(orientation, (YAZI.preview.max_width, YAZI.preview.max_height));
 
 		let buf = tokio::task::spawn_blocking(move || {
-			if img.width() > w || img.height() > h {
-				img = img.resize(w, h, Self::filter());
-			}
-			if orientation != Orientation::NoTransforms {
-				img.apply_orientation(orientation);
-			}
+			let img = Self::scale(img, orientation, (w, h));
 
 			let mut buf = Vec::new();
 			if img.color().has_alpha() {
@@ -42,25 +41,30 @@ impl Image {
 	}
 
 	pub(super) async fn downscale(url: &Url, rect: Rect) -> Result<DynamicImage> {
-		let (mut img, orientation, _) = Self::decode_from(url).await?;
-		let (w, h) = Self::flip_size(orientation, Self::max_pixel(rect));
-
-		// Fast path.
-		if img.width() <= w && img.height() <= h && orientation == Orientation::NoTransforms {
+		let size = Self::max_pixel(rect);
+		let key = provider::metadata(url).await.ok().map(|m| ScaledKey {
+			url:   url.clone(),
+			mtime: m.modified().ok(),
+			len:   m.len(),
+			size,
+		});
+		if let Some(img) = key.as_ref().and_then(|k| SCALED.get(k)) {
 			return Ok(img);
 		}
 
-		let img = tokio::task::spawn_blocking(move || {
-			if img.width() > w || img.height() > h {
-				img = img.resize(w, h, Self::filter())
-			}
-			if orientation != Orientation::NoTransforms {
-				img.apply_orientation(orientation);
-			}
+		let (img, orientation, _) = Self::decode_from(url).await?;
+		let (w, h) = Self::flip_size(orientation, size);
+
+		// Fast path.
+		let img = if img.width() <= w && img.height() <= h && orientation == Orientation::NoTransforms {
 			img
-		})
-		.await?;
+		} else {
+			tokio::task::spawn_blocking(move || Self::scale(img, orientation, (w, h))).await?
+		};
 
+		if let Some(key) = key {
+			SCALED.put(key, &img);
+		}
 		Ok(img)
 	}
 
@@ -96,7 +100,49 @@ impl Image {
 		}
 	}
 
-	async fn decode_from(url: &Url) -> ImageResult<(DynamicImage, Orientation, Option<Vec<u8>>)> {
+	fn scale(mut img: DynamicImage, orientation: Orientation, (w, h): (u32, u32)) -> DynamicImage {
+		// Shrink the large ones, like camera photos, with a cheap filter first,
+		// leaving enough pixels for the configured one to work with
+		if img.width() > w.saturating_mul(4) || img.height() > h.saturating_mul(4) {
+			img = img.thumbnail(w.saturating_mul(2), h.saturating_mul(2));
+		}
+		if img.width() > w || img.height() > h {
+			img = img.resize(w, h, Self::filter());
+		}
+		if orientation != Orientation::NoTransforms {
+			img.apply_orientation(orientation);
+		}
+		img
+	}
+
+	async fn decode_from(url: &Url) -> ImageResult<Decoded> {
+		let mut reader = ImageReader::new(provider::open(&url).await?.reader_sync().await);
+		if let Ok(format) = ImageFormat::from_path(url) {
+			reader.set_format(format);
+		}
+
+		match Self::decode_with(reader).await {
+			Err(ImageError::Unsupported(_)) => Self::decode_external(url).await,
+			r => r,
+		}
+	}
+
+	/// Decodes the formats unsupported by the `image` crate through an external tool.
+	async fn decode_external(url: &Url) -> ImageResult<Decoded> {
+		let src = provider::local_path(url).await?;
+		let dst = YAZI.preview.tmpfile("decode").with_extension("png");
+		let _guard = scopeguard::guard(&dst, |p| _ = std::fs::remove_file(p));
+
+		External::convert(&src, &dst).await?;
+		let mut reader = ImageReader::open(&dst)?;
+		reader.set_format(ImageFormat::Png);
+		Self::decode_with(reader).await
+	}
+
+	async fn decode_with<R>(mut reader: ImageReader<R>) -> ImageResult<Decoded>
+	where
+		R: BufRead + Seek + Send + 'static,
+	{
 		let mut limits = Limits::no_limits();
 		if YAZI.tasks.image_alloc > 0 {
 			limits.max_alloc = Some(YAZI.tasks.image_alloc as u64);
@@ -108,11 +154,6 @@ impl Image {
 			limits.max_image_height = Some(YAZI.tasks.image_bound[1] as u32);
 		}
 
-		let mut reader = ImageReader::new(provider::open(&url).await?.reader_sync().await);
-		if let Ok(format) = ImageFormat::from_path(url) {
-			reader.set_format(format);
-		}
-
 		reader.limits(limits);
 		tokio::task::spawn_blocking(move || {
 			let mut decoder = reader.with_guessed_format()?.into_decoder()?;
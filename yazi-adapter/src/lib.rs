@@ -2,7 +2,7 @@
 
 yazi_macro::mod_pub!(drivers);
 
-yazi_macro::mod_flat!(adapter brand dimension emulator image info mux unknown);
+yazi_macro::mod_flat!(adapter brand dimension emulator external host image info mux scaled unknown);
 
 use yazi_shared::{SyncCell, in_wsl};
 
@@ -38,9 +38,11 @@ pub fn init() -> anyhow::Result<()> {
 		EMULATOR.set(Emulator::detect().unwrap_or_default());
 	}
 
-	yazi_config::init_flavor(EMULATOR.get().light)?;
+	yazi_config::init_flavor(|a| a.light(EMULATOR.get().light, Host::os_light))?;
 
 	ADAPTOR.set(Adapter::matches(EMULATOR.get()));
 	ADAPTOR.get().start();
+
+	Host::refresh();
 	Ok(())
 }
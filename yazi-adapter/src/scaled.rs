@@ -0,0 +1,53 @@
+use std::{num::NonZeroUsize, time::SystemTime};
+
+use image::DynamicImage;
+use lru::LruCache;
+use parking_lot::Mutex;
+use yazi_config::YAZI;
+use yazi_shared::url::Url;
+
+/// The images downscaled for previewing recently, bounded by `[preview].image_memory`.
+pub(super) static SCALED: Scaled = Scaled::new();
+
+#[derive(Clone, Eq, Hash, PartialEq)]
+pub(super) struct ScaledKey {
+	pub(super) url:   Url,
+	pub(super) mtime: Option<SystemTime>,
+	pub(super) len:   u64,
+	pub(super) size:  (u32, u32),
+}
+
+pub(super) struct Scaled {
+	inner: Mutex<Option<(LruCache<ScaledKey, DynamicImage>, usize)>>,
+}
+
+impl Scaled {
+	const CAP: usize = 100;
+
+	const fn new() -> Self { Self { inner: Mutex::new(None) } }
+
+	pub(super) fn get(&self, key: &ScaledKey) -> Option<DynamicImage> {
+		self.inner.lock().as_mut()?.0.get(key).cloned()
+	}
+
+	pub(super) fn put(&self, key: ScaledKey, img: &DynamicImage) {
+		let limit = YAZI.preview.image_memory as usize;
+		let bytes = img.as_bytes().len();
+		if bytes > limit {
+			return;
+		}
+
+		let mut inner = self.inner.lock();
+		let (cache, used) =
+			inner.get_or_insert_with(|| (LruCache::new(NonZeroUsize::new(Self::CAP).unwrap()), 0));
+
+		if let Some((_, old)) = cache.push(key, img.clone()) {
+			*used -= old.as_bytes().len();
+		}
+		*used += bytes;
+		while *used > limit {
+			let Some((_, old)) = cache.pop_lru() else { break };
+			*used -= old.as_bytes().len();
+		}
+	}
+}
@@ -3,20 +3,25 @@ use crate::Adapter;
 #[derive(Clone, Copy, Debug)]
 pub struct Unknown {
 	pub kgp:   bool,
+	pub iip:   bool,
 	pub sixel: bool,
 }
 
 impl Unknown {
-	pub(super) const fn default() -> Self { Self { kgp: false, sixel: false } }
+	pub(super) const fn default() -> Self { Self { kgp: false, iip: false, sixel: false } }
 
 	pub(super) fn adapters(self) -> &'static [Adapter] {
 		use Adapter as A;
 
-		match (self.kgp, self.sixel) {
-			(true, true) => &[A::Sixel, A::KgpOld],
-			(true, false) => &[A::KgpOld],
-			(false, true) => &[A::Sixel],
-			(false, false) => &[],
+		match (self.kgp, self.iip, self.sixel) {
+			(true, true, true) => &[A::Iip, A::Sixel, A::KgpOld],
+			(true, true, false) => &[A::Iip, A::KgpOld],
+			(true, false, true) => &[A::Sixel, A::KgpOld],
+			(true, false, false) => &[A::KgpOld],
+			(false, true, true) => &[A::Iip, A::Sixel],
+			(false, true, false) => &[A::Iip],
+			(false, false, true) => &[A::Sixel],
+			(false, false, false) => &[],
 		}
 	}
 }
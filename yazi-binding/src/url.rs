@@ -116,11 +116,26 @@ impl UserData for Url {
 			me.scheme.domain().map(|s| lua.create_string(s)).transpose()
 		});
 
+		fields.add_field_method_get("scheme", |lua, me| lua.create_string(me.scheme.kind()));
 		fields.add_field_method_get("is_regular", |_, me| Ok(me.is_regular()));
 		fields.add_field_method_get("is_search", |_, me| Ok(me.is_search()));
+		fields.add_field_method_get("is_grep", |_, me| Ok(me.is_grep()));
+		fields.add_field_method_get("is_history", |_, me| Ok(me.is_history()));
+		fields.add_field_method_get("is_dupes", |_, me| Ok(me.is_dupes()));
 		fields.add_field_method_get("is_archive", |_, me| Ok(me.is_archive()));
 		fields.add_field_method_get("is_absolute", |_, me| Ok(me.is_absolute()));
+		fields.add_field_method_get("caps", |lua, me| {
+			let caps = me.caps();
+			lua.create_table_from([
+				("write", caps.can_write()),
+				("remote", caps.is_remote()),
+				("watch", caps.supports_watch()),
+				("trash", caps.supports_trash()),
+				("poll", caps.supports_poll()),
+			])
+		});
 		fields.add_field_method_get("has_root", |_, me| Ok(me.has_root()));
+		fields.add_field_method_get("line", |_, me| Ok(me.frag()));
 	}
 
 	fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
@@ -2,7 +2,7 @@ use std::{env, ffi::OsStr, fmt::Write, path::Path};
 
 use regex::Regex;
 use yazi_adapter::Mux;
-use yazi_config::YAZI;
+use yazi_config::{YAZI, search::SearchMode};
 use yazi_shared::{timestamp_us, url::Url};
 
 use super::Actions;
@@ -71,6 +71,16 @@ impl Actions {
 			YAZI.opener.block(YAZI.open.all(Url::from(Path::new("bulk-rename.txt")), "text/plain"))
 		)?;
 
+		writeln!(s, "\nSearch")?;
+		for mode in [SearchMode::Name, SearchMode::Content] {
+			writeln!(s, "    {:<7}: {}", mode.as_str(), YAZI.search.pick(mode.as_str()).1)?;
+		}
+		for name in YAZI.search.name.iter().chain(&YAZI.search.content) {
+			if let Some(b) = YAZI.search.backends.get(name) {
+				writeln!(s, "      - {name}: {}", if b.available() { "available" } else { "not found" })?;
+			}
+		}
+
 		writeln!(s, "\nMultiplexers")?;
 		writeln!(s, "    TMUX               : {}", yazi_adapter::TMUX)?;
 		writeln!(s, "    tmux version       : {}", Self::process_output("tmux", "-V"))?;
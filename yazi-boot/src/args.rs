@@ -1,11 +1,14 @@
 use std::path::PathBuf;
 
-use clap::{Parser, command};
+use clap::{Parser, Subcommand, command};
 use yazi_shared::{Id, url::Url};
 
 #[derive(Debug, Default, Parser)]
-#[command(name = "yazi")]
+#[command(name = "yazi", args_conflicts_with_subcommands = true)]
 pub struct Args {
+	#[command(subcommand)]
+	pub command: Option<Command>,
+
 	/// Set the current working entry
 	#[arg(index = 1, num_args = 1..=9)]
 	pub entries: Vec<Url>,
@@ -16,6 +19,21 @@ pub struct Args {
 	/// Write the selected files to this file on open fired
 	#[arg(long)]
 	pub chooser_file: Option<PathBuf>,
+	/// Write the session summary as JSON to this file on exit
+	#[arg(long)]
+	pub summary_file: Option<PathBuf>,
+
+	/// Restore the tabs saved on the last exit
+	#[arg(long, conflicts_with = "entries")]
+	pub resume: bool,
+
+	/// Do not print the session summary on exit
+	#[arg(short, long)]
+	pub quiet: bool,
+
+	/// Render plain text lines for screen readers, without images or borders
+	#[arg(long)]
+	pub accessible: bool,
 
 	/// Clear the cache directory
 	#[arg(long)]
@@ -30,6 +48,12 @@ pub struct Args {
 	/// Report the specified remote events to stdout
 	#[arg(long)]
 	pub remote_events: Option<String>,
+	/// Write the specified events as JSON lines to stdout, such as `cd,hover,select,task`
+	#[arg(long)]
+	pub events:        Option<String>,
+	/// Write the events of `--events` to this file instead, such as a named pipe
+	#[arg(long, requires = "events")]
+	pub events_file:   Option<PathBuf>,
 
 	/// Print debug information
 	#[arg(long)]
@@ -39,3 +63,26 @@ pub struct Args {
 	#[arg(short = 'V', long)]
 	pub version: bool,
 }
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+	/// Run Lua with the config and plugins loaded but without the TUI, printing
+	/// what each chunk returns as JSON lines
+	///
+	/// It runs like the entry of a plugin, so `ya.sync()` and anything else that
+	/// needs the TUI isn't available.
+	Run(CommandRun),
+}
+
+#[derive(Debug, clap::Args)]
+pub struct CommandRun {
+	/// Lua script to run, `-` to read it from stdin
+	#[arg(required_unless_present = "exec")]
+	pub script: Option<PathBuf>,
+	/// Arguments passed to the script and each chunk, as `...`
+	#[arg(allow_hyphen_values = true, num_args = 0..)]
+	pub args:   Vec<String>,
+	/// Chunk of Lua to run after the script, can be given multiple times
+	#[arg(short, long)]
+	pub exec:   Vec<String>,
+}
@@ -2,6 +2,7 @@ use std::{borrow::Cow, collections::HashSet, path::PathBuf};
 
 use futures::executor::block_on;
 use serde::Serialize;
+use yazi_config::YAZI;
 use yazi_fs::{CWD, Xdg, path::expand_url, provider};
 use yazi_shared::url::{Url, UrnBuf};
 
@@ -12,6 +13,10 @@ pub struct Boot {
 
 	pub local_events:  HashSet<String>,
 	pub remote_events: HashSet<String>,
+	pub events:        HashSet<String>,
+
+	/// Whether to render plain text for screen readers, by `--accessible` or the config.
+	pub accessible: bool,
 
 	pub config_dir: PathBuf,
 	pub flavor_dir: PathBuf,
@@ -56,6 +61,11 @@ impl From<&crate::Args> for Boot {
 			.as_ref()
 			.map(|s| s.split(',').map(|s| s.to_owned()).collect())
 			.unwrap_or_default();
+		let events = args
+			.events
+			.as_ref()
+			.map(|s| s.split(',').map(|s| s.to_owned()).collect())
+			.unwrap_or_default();
 
 		Self {
 			cwds,
@@ -63,6 +73,9 @@ impl From<&crate::Args> for Boot {
 
 			local_events,
 			remote_events,
+			events,
+
+			accessible: args.accessible || YAZI.mgr.accessible,
 
 			flavor_dir: config_dir.join("flavors"),
 			plugin_dir: config_dir.join("plugins"),
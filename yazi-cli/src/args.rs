@@ -1,8 +1,8 @@
-use std::borrow::Cow;
+use std::{borrow::Cow, collections::{HashMap, HashSet}, ffi::{OsStr, OsString}, path::PathBuf};
 
-use anyhow::{Result, bail};
+use anyhow::{Context, Result, bail};
 use clap::{Parser, Subcommand, command};
-use yazi_shared::{Id, event::Cmd};
+use yazi_shared::{Id, event::{Data, DataKey}, url::{Scheme, Url}};
 
 #[derive(Parser)]
 #[command(name = "Ya", about, long_about = None)]
@@ -17,10 +17,14 @@ pub(super) struct Args {
 
 #[derive(Subcommand)]
 pub(super) enum Command {
-	/// Emit a command to be executed by the current instance.
+	/// Check the config files for errors and problems.
+	Check(CommandCheck),
+	/// Emit a command to be executed by the current instance, failing if it fails.
 	Emit(CommandEmit),
-	/// Emit a command to be executed by the specified instance.
+	/// Emit a command to be executed by the specified instance, failing if it fails.
 	EmitTo(CommandEmitTo),
+	/// Query the audit log of the file operations.
+	Log(CommandLog),
 	/// Manage packages.
 	#[command(subcommand)]
 	Pkg(CommandPkg),
@@ -31,28 +35,87 @@ pub(super) enum Command {
 	Pub(CommandPub),
 	/// Publish a message to the specified instance.
 	PubTo(CommandPubTo),
-	/// Subscribe to messages from all remote instances.
+	/// Subscribe to messages from all remote instances, printing them as JSON lines.
 	Sub(CommandSub),
 }
 
+#[derive(clap::Args)]
+pub(super) struct CommandCheck {
+	/// Print the problems as a JSON array, for editors and other tools.
+	#[arg(long)]
+	pub(super) json: bool,
+}
+
 #[derive(clap::Args)]
 pub(super) struct CommandEmit {
-	/// Name of the command.
-	pub(super) name: String,
+	/// Name of the command, prefixed with its layer if not `mgr`, such as `tasks:show`.
+	pub(super) name:    String,
 	/// Arguments of the command.
-	#[arg(allow_hyphen_values = true, trailing_var_arg = true)]
-	pub(super) args: Vec<String>,
+	#[arg(allow_hyphen_values = true, num_args = 0..)]
+	pub(super) args:    Vec<OsString>,
+	/// Arguments after `--`, passed literally as positional arguments.
+	#[arg(last = true)]
+	pub(super) literal: Vec<OsString>,
+	/// Receiver ID, the current instance if unspecified.
+	#[arg(long)]
+	pub(super) pid:     Option<Id>,
+	/// Send the arguments as a JSON object instead.
+	#[arg(long, conflicts_with_all = ["args", "literal"])]
+	pub(super) json:    Option<String>,
+}
+
+impl CommandEmit {
+	pub(super) fn receiver(&self) -> Result<Id> {
+		if let Some(pid) = self.pid { Ok(pid) } else { CommandPub::receiver() }
+	}
 }
 
 #[derive(clap::Args)]
 pub(super) struct CommandEmitTo {
 	/// Receiver ID.
 	pub(super) receiver: Id,
-	/// Name of the command.
+	/// Name of the command, prefixed with its layer if not `mgr`, such as `tasks:show`.
 	pub(super) name:     String,
 	/// Arguments of the command.
-	#[arg(allow_hyphen_values = true, trailing_var_arg = true)]
-	pub(super) args:     Vec<String>,
+	#[arg(allow_hyphen_values = true, num_args = 0..)]
+	pub(super) args:     Vec<OsString>,
+	/// Arguments after `--`, passed literally as positional arguments.
+	#[arg(last = true)]
+	pub(super) literal:  Vec<OsString>,
+	/// Send the arguments as a JSON object instead.
+	#[arg(long, conflicts_with_all = ["args", "literal"])]
+	pub(super) json:     Option<String>,
+}
+
+#[derive(clap::Args)]
+pub(super) struct CommandLog {
+	/// Only the operations on the paths containing this.
+	pub(super) path:   Option<String>,
+	/// Only these operations.
+	#[arg(
+		long,
+		value_delimiter = ',',
+		value_parser = ["create", "rename", "move", "trash", "delete"],
+	)]
+	pub(super) op:     Vec<String>,
+	/// Only the operations done by this user.
+	#[arg(long)]
+	pub(super) user:   Option<String>,
+	/// Only the operations since this time, in RFC 3339 and UTC, or a prefix of it such as `2025-07`.
+	#[arg(long)]
+	pub(super) since:  Option<String>,
+	/// Only the operations until this time, in RFC 3339 and UTC, or a prefix of it such as `2025-07`.
+	#[arg(long)]
+	pub(super) until:  Option<String>,
+	/// Only the failed operations.
+	#[arg(long)]
+	pub(super) failed: bool,
+	/// Print the last this many operations only.
+	#[arg(short = 'n', long)]
+	pub(super) limit:  Option<usize>,
+	/// Print the operations as JSON lines.
+	#[arg(long)]
+	pub(super) json:   bool,
 }
 
 #[derive(Subcommand)]
@@ -60,7 +123,7 @@ pub(super) enum CommandPkg {
 	/// Add packages.
 	#[command(arg_required_else_help = true)]
 	Add {
-		/// Packages to add.
+		/// Packages to add, as `owner/repo[:child][@rev]` or `path:<dir>`.
 		#[arg(index = 1, num_args = 1..)]
 		ids: Vec<String>,
 	},
@@ -71,16 +134,26 @@ pub(super) enum CommandPkg {
 		#[arg(index = 1, num_args = 1..)]
 		ids: Vec<String>,
 	},
-	/// Install all packages.
-	Install,
+	/// Install all packages, at the revisions of package.lock if recorded.
+	Install {
+		/// Fail if any package is not recorded in package.lock.
+		#[arg(long)]
+		locked: bool,
+	},
 	/// List all packages.
 	List,
 	/// Upgrade all packages.
+	#[command(visible_alias = "update")]
 	Upgrade {
 		/// Packages to upgrade, upgrade all if unspecified.
 		#[arg(index = 1, num_args = 0..)]
-		ids: Vec<String>,
+		ids:    Vec<String>,
+		/// Bring them to the revisions of package.lock instead of the latest.
+		#[arg(long)]
+		locked: bool,
 	},
+	/// Verify the installed packages against the hashes of package.lock.
+	Verify,
 }
 
 #[derive(clap::Args)]
@@ -151,9 +224,24 @@ pub(super) struct CommandPubTo {
 
 #[derive(clap::Args)]
 pub(super) struct CommandSub {
-	/// Kind of messages to subscribe to, separated by commas if multiple.
-	#[arg(index = 1)]
-	pub(super) kinds: String,
+	/// Kinds of messages to subscribe to, or `*` for all.
+	#[arg(required = true, num_args = 1..)]
+	pub(super) kinds:      Vec<String>,
+	/// Render URLs as objects of their scheme, domain and path.
+	#[arg(long)]
+	pub(super) structured: bool,
+	/// Keep reconnecting with a backoff until an instance is available.
+	#[arg(long)]
+	pub(super) retry:      bool,
+}
+
+impl CommandSub {
+	/// The kinds to subscribe to, where each one may still be a comma-separated
+	/// list as accepted by earlier versions.
+	#[allow(dead_code)]
+	pub(super) fn kinds(&self) -> HashSet<&str> {
+		self.kinds.iter().flat_map(|s| s.split(',')).filter(|s| !s.is_empty()).collect()
+	}
 }
 
 // --- Macros
@@ -162,10 +250,12 @@ macro_rules! impl_emit_body {
 		impl $name {
 			#[allow(dead_code)]
 			pub(super) fn body(self) -> Result<String> {
-				Ok(serde_json::to_string(&(
-					self.name,
-					Cmd::parse_args(self.args.into_iter(), None, false)?,
-				))?)
+				let args = if let Some(json) = &self.json {
+					serde_json::from_str(json).context("Arguments must be a JSON object")?
+				} else {
+					emit_args(self.args, self.literal)?
+				};
+				Ok(serde_json::to_string(&(self.name, args))?)
 			}
 		}
 	};
@@ -195,3 +285,109 @@ impl_emit_body!(CommandEmitTo);
 
 impl_pub_body!(CommandPub);
 impl_pub_body!(CommandPubTo);
+
+// --- Emit
+/// Converts the arguments of `ya emit` into those of a command, where:
+///   - `--key=value` is a named argument, and `--key` is a boolean one;
+///   - everything else, including anything after `--`, is positional;
+///   - values with a scheme are validated as URLs, and those that aren't valid
+///     UTF-8 are sent as encoded URLs, so they survive the JSON transport.
+#[allow(dead_code)]
+fn emit_args(args: Vec<OsString>, literal: Vec<OsString>) -> Result<HashMap<DataKey, Data>> {
+	let mut map = HashMap::new();
+	let mut args = args.into_iter();
+	let mut i = 0;
+
+	for arg in args.by_ref() {
+		if arg == "--" {
+			break;
+		}
+
+		let Some(named) = arg.as_encoded_bytes().strip_prefix(b"--") else {
+			i += 1;
+			map.insert(DataKey::Integer(i), emit_value(arg)?);
+			continue;
+		};
+
+		let (key, value) = match named.iter().position(|&b| b == b'=') {
+			Some(n) => (&named[..n], Some(&named[n + 1..])),
+			None => (named, None),
+		};
+		let Ok(key) = str::from_utf8(key) else {
+			bail!("Invalid argument name: {}", arg.display());
+		};
+
+		// SAFETY: The value was separated by an ASCII `=`.
+		let value = match value {
+			Some(v) => emit_value(unsafe { OsStr::from_encoded_bytes_unchecked(v) }.to_owned())?,
+			None => Data::Boolean(true),
+		};
+		map.insert(DataKey::String(key.to_owned().into()), value);
+	}
+
+	for arg in args.chain(literal) {
+		i += 1;
+		map.insert(DataKey::Integer(i), emit_value(arg)?);
+	}
+	Ok(map)
+}
+
+#[allow(dead_code)]
+fn emit_value(value: OsString) -> Result<Data> {
+	let s = match value.into_string() {
+		Ok(s) => s,
+		Err(value) => return Ok(Data::Url(PathBuf::from(value).into())),
+	};
+
+	let scheme = s.split_once("://").map(|(p, _)| p.trim_end_matches('~'));
+	if scheme.is_some_and(|p| Scheme::parse_kind(p.as_bytes()).is_ok()) {
+		s.parse::<Url>().with_context(|| format!("Invalid URL: {s}"))?;
+	}
+	Ok(Data::String(s.into()))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn args(words: &[&str], literal: &[&str]) -> Result<HashMap<DataKey, Data>> {
+		emit_args(words.iter().map(Into::into).collect(), literal.iter().map(Into::into).collect())
+	}
+
+	#[test]
+	fn test_emit_args() -> Result<()> {
+		let map = args(&["/tmp/a", "--hidden", "--mode=a=b", "-x", "--", "--k=v"], &["--raw"])?;
+		assert_eq!(map[&DataKey::Integer(1)].as_str(), Some("/tmp/a"));
+		assert_eq!(map[&DataKey::String("hidden".into())].as_bool(), Some(true));
+		assert_eq!(map[&DataKey::String("mode".into())].as_str(), Some("a=b"));
+		assert_eq!(map[&DataKey::Integer(2)].as_str(), Some("-x"));
+		assert_eq!(map[&DataKey::Integer(3)].as_str(), Some("--k=v"));
+		assert_eq!(map[&DataKey::Integer(4)].as_str(), Some("--raw"));
+		assert_eq!(map.len(), 6);
+
+		// Schemes are kept as is, but validated
+		let map = args(&["search://kw//tmp/a"], &[])?;
+		assert_eq!(map[&DataKey::Integer(1)].as_str(), Some("search://kw//tmp/a"));
+		assert!(args(&["search://kw:x//tmp"], &[]).is_err());
+		assert!(args(&["https://example.com"], &[]).is_ok());
+		Ok(())
+	}
+
+	#[cfg(unix)]
+	#[test]
+	fn test_emit_args_non_utf8() -> Result<()> {
+		use std::os::unix::ffi::OsStringExt;
+
+		let name = OsString::from_vec(b"/tmp/\xff\xfe".to_vec());
+		let map = emit_args(vec![name.clone()], vec![])?;
+		let Data::Url(url) = &map[&DataKey::Integer(1)] else { panic!("not a URL") };
+		assert_eq!(url.as_path().map(|p| p.as_os_str()), Some(name.as_os_str()));
+
+		// Survives the round trip through JSON
+		let json = serde_json::to_string(&map)?;
+		let back: HashMap<DataKey, Data> = serde_json::from_str(&json)?;
+		let url: Url = back[&DataKey::Integer(1)].as_str().unwrap().parse()?;
+		assert_eq!(url.as_path().map(|p| p.as_os_str()), Some(name.as_os_str()));
+		Ok(())
+	}
+}
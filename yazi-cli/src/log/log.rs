@@ -0,0 +1,95 @@
+use std::io::ErrorKind;
+
+use anyhow::{Context, Result};
+use yazi_fs::{Audit, AuditEntry, AuditOp, AuditOutcome};
+use yazi_macro::{errln, outln};
+
+/// Which entries of the audit log to print.
+#[derive(Default)]
+pub struct Query {
+	/// A part of the path of either end.
+	pub path:   Option<String>,
+	pub ops:    Vec<AuditOp>,
+	pub user:   Option<String>,
+	/// RFC 3339 in UTC, or a prefix of it, such as `2025-07-01`.
+	pub since:  Option<String>,
+	pub until:  Option<String>,
+	pub failed: bool,
+}
+
+pub struct Log;
+
+impl Log {
+	/// Prints the last `limit` entries matching the query, the oldest first.
+	pub fn run(query: Query, limit: Option<usize>, json: bool) -> Result<()> {
+		let path = Audit::path();
+		let entries = match Audit::read(&path) {
+			Ok(it) => it.filter(|e| query.matches(e)).collect::<Vec<_>>(),
+			Err(e) if e.kind() == ErrorKind::NotFound => {
+				errln!("No audit log yet, enable `[tasks].audit_log` to record the file operations")?;
+				return Ok(());
+			}
+			Err(e) => Err(e).with_context(|| format!("Cannot read {}", path.display()))?,
+		};
+
+		let skip = limit.map_or(0, |n| entries.len().saturating_sub(n));
+		for entry in &entries[skip..] {
+			if json {
+				outln!("{}", serde_json::to_string(entry)?)?;
+			} else {
+				outln!("{entry}")?;
+			}
+		}
+		Ok(())
+	}
+}
+
+impl Query {
+	fn matches(&self, e: &AuditEntry) -> bool {
+		let op = self.ops.is_empty() || self.ops.contains(&e.op);
+		let failed = !self.failed || e.outcome == AuditOutcome::Failed;
+		let user = self.user.as_ref().is_none_or(|u| *u == e.user);
+
+		// A prefix of the time covers all the times that start with it
+		let since = self.since.as_deref().is_none_or(|s| e.time.as_str() >= s);
+		let until = self.until.as_deref().is_none_or(|s| e.time.get(..s.len()).unwrap_or(&e.time) <= s);
+
+		let path = self.path.as_ref().is_none_or(|p| {
+			e.source.iter().chain([&e.target]).any(|u| u.display().to_string().contains(p))
+		});
+		op && failed && user && since && until && path
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_matches() -> Result<()> {
+		let mut entry = AuditEntry::new(AuditOp::Move, Some("/tmp/a".parse()?), "/home/b".parse()?);
+		entry.time = "2025-07-01T08:30:00Z".to_owned();
+
+		let matches = |q: Query| q.matches(&entry);
+		assert!(matches(Query::default()));
+		assert!(matches(Query { path: Some("tmp".to_owned()), ..Default::default() }));
+		assert!(matches(Query { path: Some("home/b".to_owned()), ..Default::default() }));
+		assert!(!matches(Query { path: Some("/c".to_owned()), ..Default::default() }));
+
+		assert!(matches(Query { ops: vec![AuditOp::Trash, AuditOp::Move], ..Default::default() }));
+		assert!(!matches(Query { ops: vec![AuditOp::Trash], ..Default::default() }));
+		assert!(!matches(Query { failed: true, ..Default::default() }));
+		assert!(!matches(Query { user: Some("nobody-at-all".to_owned()), ..Default::default() }));
+
+		let time = |since: &str, until: &str| Query {
+			since: Some(since.to_owned()),
+			until: Some(until.to_owned()),
+			..Default::default()
+		};
+		assert!(matches(time("2025-07-01", "2025-07-01")));
+		assert!(matches(time("2025-06", "2025-07-01T08:30")));
+		assert!(!matches(time("2025-07-02", "2026")));
+		assert!(!matches(time("2025", "2025-06-30")));
+		Ok(())
+	}
+}
@@ -1,4 +1,4 @@
-yazi_macro::mod_pub!(package shared);
+yazi_macro::mod_pub!(log package shared sub);
 
 yazi_macro::mod_flat!(args);
 
@@ -40,12 +40,26 @@ async fn run() -> anyhow::Result<()> {
 	}
 
 	match Args::parse().command {
+		Command::Check(cmd) => {
+			yazi_boot::init_default();
+			let diagnostics = yazi_config::doctor::Doctor::diagnose();
+			if cmd.json {
+				outln!("{}", serde_json::to_string(&diagnostics)?)?;
+			} else if diagnostics.is_empty() {
+				outln!("No problems found")?;
+			} else {
+				diagnostics.iter().try_for_each(|d| outln!("{d}"))?;
+			}
+
+			if diagnostics.iter().any(|d| d.level == yazi_config::doctor::DiagnosticLevel::Error) {
+				std::process::exit(1);
+			}
+		}
+
 		Command::Emit(cmd) => {
 			yazi_boot::init_default();
 			yazi_dds::init();
-			if let Err(e) =
-				yazi_dds::Client::shot("dds-emit", CommandPub::receiver()?, &cmd.body()?).await
-			{
+			if let Err(e) = yazi_dds::Client::emit(cmd.receiver()?, &cmd.body()?).await {
 				errln!("Cannot emit command: {e}")?;
 				std::process::exit(1);
 			}
@@ -54,36 +68,49 @@ async fn run() -> anyhow::Result<()> {
 		Command::EmitTo(cmd) => {
 			yazi_boot::init_default();
 			yazi_dds::init();
-			if let Err(e) = yazi_dds::Client::shot("dds-emit", cmd.receiver, &cmd.body()?).await {
+			if let Err(e) = yazi_dds::Client::emit(cmd.receiver, &cmd.body()?).await {
 				errln!("Cannot emit command: {e}")?;
 				std::process::exit(1);
 			}
 		}
 
+		Command::Log(cmd) => {
+			let query = log::Query {
+				path:   cmd.path,
+				ops:    cmd.op.iter().map(|s| s.parse()).collect::<Result<_, _>>()?,
+				user:   cmd.user,
+				since:  cmd.since,
+				until:  cmd.until,
+				failed: cmd.failed,
+			};
+			log::Log::run(query, cmd.limit, cmd.json)?;
+		}
+
 		Command::Pkg(cmd) => {
-			package::init()?;
+			package::init().await?;
 
 			let mut pkg = package::Package::load().await?;
 			match cmd {
 				CommandPkg::Add { ids } => pkg.add_many(&ids).await?,
 				CommandPkg::Delete { ids } => pkg.delete_many(&ids).await?,
-				CommandPkg::Install => pkg.install().await?,
+				CommandPkg::Install { locked } => pkg.install(locked).await?,
 				CommandPkg::List => pkg.print()?,
-				CommandPkg::Upgrade { ids } => pkg.upgrade_many(&ids).await?,
+				CommandPkg::Upgrade { ids, locked } => pkg.upgrade_many(&ids, locked).await?,
+				CommandPkg::Verify => pkg.verify().await?,
 			}
 		}
 
 		Command::Pack(cmd) => {
-			package::init()?;
+			package::init().await?;
 			outln!(
 				"WARNING: `ya pack` is deprecated, use the new `ya pkg` instead. See https://github.com/sxyazi/yazi/pull/2770 for more details."
 			)?;
 			if cmd.install {
-				package::Package::load().await?.install().await?;
+				package::Package::load().await?.install(false).await?;
 			} else if cmd.list {
 				package::Package::load().await?.print()?;
 			} else if cmd.upgrade {
-				package::Package::load().await?.upgrade_many(&[]).await?;
+				package::Package::load().await?.upgrade_many(&[], false).await?;
 			} else if let Some(uses) = cmd.add {
 				package::Package::load().await?.add_many(&uses).await?;
 			} else if let Some(uses) = cmd.delete {
@@ -113,9 +140,7 @@ async fn run() -> anyhow::Result<()> {
 		Command::Sub(cmd) => {
 			yazi_boot::init_default();
 			yazi_dds::init();
-			yazi_dds::Client::draw(cmd.kinds.split(',').collect()).await?;
-
-			tokio::signal::ctrl_c().await?;
+			sub::Sub::run(cmd.kinds(), cmd.structured, cmd.retry).await?;
 		}
 	}
 
@@ -1,10 +1,14 @@
 use anyhow::Result;
 
-use super::{Dependency, Git};
+use super::{Dependency, Git, Source};
 use crate::shared::must_exists;
 
 impl Dependency {
 	pub(super) async fn add(&mut self) -> Result<()> {
+		if self.source == Source::Path {
+			return self.link().await;
+		}
+
 		self.header("Upgrading package `{name}`")?;
 
 		let path = self.local();
@@ -14,8 +18,17 @@ impl Dependency {
 			Git::clone(&self.remote(), &path).await?;
 		};
 
+		let pinned = self.pinned().map(ToOwned::to_owned);
+		if let Some(rev) = &pinned {
+			Git::checkout(&path, rev).await?;
+		}
+
 		self.deploy().await?;
 		self.rev = Git::revision(&path).await?;
+		if pinned.is_some() {
+			self.rev.insert(0, '=');
+		}
+		self.commit = Git::commit(&path).await?;
 		Ok(())
 	}
 }
@@ -2,11 +2,15 @@ use anyhow::{Context, Result};
 use yazi_fs::{ok_or_not_found, provider::local::Local, remove_dir_clean};
 use yazi_macro::outln;
 
-use super::Dependency;
+use super::{Dependency, Source};
 use crate::shared::{maybe_exists, remove_sealed};
 
 impl Dependency {
 	pub(super) async fn delete(&self) -> Result<()> {
+		if self.source == Source::Path {
+			return self.unlink().await;
+		}
+
 		self.header("Deleting package `{name}`")?;
 
 		let dir = self.target();
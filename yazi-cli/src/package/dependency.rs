@@ -1,29 +1,36 @@
-use std::{io::BufWriter, path::PathBuf, str::FromStr};
+use std::{io::{self, BufWriter}, mem, path::{Path, PathBuf}, str::FromStr};
 
-use anyhow::{Result, bail};
+use anyhow::{Context, Result, bail};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use twox_hash::XxHash3_128;
-use yazi_fs::Xdg;
+use yazi_fs::{Xdg, provider::local::Local};
+
+use super::Source;
 
 #[derive(Clone, Default)]
 pub(crate) struct Dependency {
-	pub(crate) r#use: String, // owner/repo:child
+	pub(crate) r#use: String, // owner/repo:child, or path:/dir
 	pub(crate) name:  String, // child.yazi
 
-	pub(crate) parent: String, // owner/repo
+	pub(crate) parent: String, // owner/repo, or /dir
 	pub(crate) child:  String, // child.yazi
 
-	pub(crate) rev:  String,
-	pub(crate) hash: String,
+	pub(crate) source: Source,
+	pub(crate) rev:    String, // Prefixed with `=` if pinned
+	pub(crate) hash:   String,
+	pub(crate) commit: String, // Full revision, recorded in package.lock
 
 	pub(super) is_flavor: bool,
 }
 
 impl Dependency {
 	pub(super) fn local(&self) -> PathBuf {
-		Xdg::state_dir()
-			.join("packages")
-			.join(format!("{:x}", XxHash3_128::oneshot(self.remote().as_bytes())))
+		match self.source {
+			Source::Git => Xdg::state_dir()
+				.join("packages")
+				.join(format!("{:x}", XxHash3_128::oneshot(self.remote().as_bytes()))),
+			Source::Path => PathBuf::from(&self.parent),
+		}
 	}
 
 	pub(super) fn remote(&self) -> String {
@@ -41,7 +48,47 @@ impl Dependency {
 
 	#[inline]
 	pub(super) fn identical(&self, other: &Self) -> bool {
-		self.parent == other.parent && self.child == other.child
+		self.source == other.source && self.parent == other.parent && self.child == other.child
+	}
+
+	#[inline]
+	pub(super) fn pinned(&self) -> Option<&str> { self.rev.strip_prefix('=') }
+
+	/// Resolves the directory of a path source to an absolute one.
+	pub(super) async fn resolve(&mut self) -> Result<()> {
+		if self.source != Source::Path {
+			return Ok(());
+		}
+
+		let path = match Local::canonicalize(&self.parent).await {
+			Ok(p) => p,
+			Err(e) if e.kind() == io::ErrorKind::NotFound => std::path::absolute(&self.parent)?,
+			Err(e) => Err(e).context(format!("failed to resolve `{}`", self.parent))?,
+		};
+
+		let (rev, hash) = (mem::take(&mut self.rev), mem::take(&mut self.hash));
+		*self = Self { rev, hash, ..Self::from_path(&path)? };
+		Ok(())
+	}
+
+	fn from_path(path: &Path) -> Result<Self> {
+		let Some(dir) = path.to_str() else {
+			bail!("Package path `{}` must be valid UTF-8", path.display())
+		};
+
+		let name = path.file_name().and_then(|s| s.to_str()).unwrap_or_default();
+		let name = name.strip_suffix(".yazi").unwrap_or(name);
+		if name.is_empty() || !name.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'z' | b'-')) {
+			bail!("Package name `{name}` of `{dir}` must be in kebab-case")
+		}
+
+		Ok(Self {
+			r#use: format!("path:{dir}"),
+			name: format!("{name}.yazi"),
+			parent: dir.to_owned(),
+			source: Source::Path,
+			..Default::default()
+		})
 	}
 
 	pub(super) fn header(&self, s: &str) -> Result<()> {
@@ -66,6 +113,16 @@ impl FromStr for Dependency {
 	type Err = anyhow::Error;
 
 	fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+		if let Some(path) = s.strip_prefix("path:") {
+			return Self::from_path(Path::new(path));
+		}
+
+		let (s, rev) = match s.split_once('@') {
+			Some((_, "")) => bail!("Package revision of `{s}` cannot be empty"),
+			Some((s, rev)) => (s, format!("={rev}")),
+			None => (s, String::new()),
+		};
+
 		let mut parts = s.splitn(2, ':');
 
 		let Some(parent) = parts.next() else { bail!("Package url cannot be empty") };
@@ -85,6 +142,7 @@ impl FromStr for Dependency {
 			name: format!("{name}.yazi"),
 			parent: format!("{parent}{}", if child.is_empty() { ".yazi" } else { "" }),
 			child: if child.is_empty() { String::new() } else { format!("{child}.yazi") },
+			rev,
 			..Default::default()
 		})
 	}
@@ -97,19 +155,25 @@ impl<'de> Deserialize<'de> for Dependency {
 	{
 		#[derive(Deserialize)]
 		struct Shadow {
-			r#use: String,
+			r#use:  String,
 			#[serde(default)]
-			rev:   String,
+			source: Option<Source>,
 			#[serde(default)]
-			hash:  String,
+			rev:    String,
+			#[serde(default)]
+			hash:   String,
 		}
 
 		let outer = Shadow::deserialize(deserializer)?;
-		Ok(Self {
-			rev: outer.rev,
-			hash: outer.hash,
-			..Self::from_str(&outer.r#use).map_err(serde::de::Error::custom)?
-		})
+		let dep = Self::from_str(&outer.r#use).map_err(serde::de::Error::custom)?;
+		if outer.source.is_some_and(|s| s != dep.source) {
+			return Err(serde::de::Error::custom(format!(
+				"source of `{}` doesn't match its `use`",
+				outer.r#use
+			)));
+		}
+
+		Ok(Self { rev: outer.rev, hash: outer.hash, ..dep })
 	}
 }
 
@@ -120,11 +184,47 @@ impl Serialize for Dependency {
 	{
 		#[derive(Serialize)]
 		struct Shadow<'a> {
-			r#use: &'a str,
-			rev:   &'a str,
-			hash:  &'a str,
+			r#use:  &'a str,
+			source: Source,
+			rev:    &'a str,
+			hash:   &'a str,
 		}
 
-		Shadow { r#use: &self.r#use, rev: &self.rev, hash: &self.hash }.serialize(serializer)
+		Shadow { r#use: &self.r#use, source: self.source, rev: &self.rev, hash: &self.hash }
+			.serialize(serializer)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_from_str() -> Result<()> {
+		let d = Dependency::from_str("owner/repo:child")?;
+		assert_eq!((d.name.as_str(), d.parent.as_str()), ("child.yazi", "owner/repo"));
+		assert_eq!((d.source, d.pinned()), (Source::Git, None));
+
+		let d = Dependency::from_str("owner/plugin@v1.2.0")?;
+		assert_eq!((d.r#use.as_str(), d.name.as_str()), ("owner/plugin", "plugin.yazi"));
+		assert_eq!(d.pinned(), Some("v1.2.0"));
+		assert!(Dependency::from_str("owner/plugin@").is_err());
+
+		let d = Dependency::from_str("path:/src/my-plugin.yazi")?;
+		assert_eq!((d.r#use.as_str(), d.name.as_str()), ("path:/src/my-plugin.yazi", "my-plugin.yazi"));
+		assert_eq!((d.source, d.local()), (Source::Path, PathBuf::from("/src/my-plugin.yazi")));
+		assert!(Dependency::from_str("path:/src/MyPlugin").is_err());
+
+		// The same plugin from different sources are not identical
+		let git = Dependency::from_str("owner/my-plugin")?;
+		assert!(!git.identical(&d));
+		assert_eq!(git.name, d.name);
+		Ok(())
+	}
+
+	#[test]
+	fn test_source_mismatch() {
+		let r: Result<Dependency, _> = toml::from_str("use = 'owner/repo'\nsource = 'path'");
+		assert!(r.is_err());
 	}
 }
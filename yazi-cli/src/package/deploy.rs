@@ -5,7 +5,7 @@ use yazi_fs::{provider::local::Local, remove_dir_clean};
 use yazi_macro::outln;
 
 use super::Dependency;
-use crate::shared::{copy_and_seal, maybe_exists};
+use crate::shared::{copy_and_seal, ensure_dir, maybe_exists};
 
 impl Dependency {
 	pub(super) async fn deploy(&mut self) -> Result<()> {
@@ -20,7 +20,7 @@ impl Dependency {
 			self.hash_check().await?;
 		}
 
-		Local::create_dir_all(&to).await?;
+		ensure_dir(&to).await?;
 		self.delete_assets().await?;
 
 		let res1 = Self::deploy_assets(from.join("assets"), to.join("assets")).await;
@@ -42,7 +42,7 @@ impl Dependency {
 	async fn deploy_assets(from: PathBuf, to: PathBuf) -> Result<()> {
 		match Local::read_dir(&from).await {
 			Ok(mut it) => {
-				Local::create_dir_all(&to).await?;
+				ensure_dir(&to).await?;
 				while let Some(entry) = it.next_entry().await? {
 					let (src, dist) = (entry.path(), to.join(entry.file_name()));
 					copy_and_seal(&src, &dist).await.with_context(|| {
@@ -26,8 +26,16 @@ impl Git {
 	}
 
 	pub(super) async fn revision(path: &Path) -> Result<String> {
+		Self::rev_parse(path, &["rev-parse", "--short", "HEAD"]).await
+	}
+
+	pub(super) async fn commit(path: &Path) -> Result<String> {
+		Self::rev_parse(path, &["rev-parse", "HEAD"]).await
+	}
+
+	async fn rev_parse(path: &Path, args: &[&str]) -> Result<String> {
 		let output = Command::new("git")
-			.args(["rev-parse", "--short", "HEAD"])
+			.args(args)
 			.current_dir(path)
 			.output()
 			.await
@@ -1,25 +1,50 @@
-use anyhow::Result;
+use anyhow::{Context, Result, bail};
 
-use super::{Dependency, Git};
-use crate::shared::must_exists;
+use super::{Dependency, Git, LockEntry, Source};
+use crate::shared::{is_dir_empty, must_exists};
 
 impl Dependency {
-	pub(super) async fn install(&mut self) -> Result<()> {
+	/// Installs the package at the revision of `lock` if given.
+	pub(super) async fn install(&mut self, lock: Option<&LockEntry>) -> Result<()> {
+		if self.source == Source::Path {
+			return self.link().await;
+		}
+
 		self.header("Fetching package `{name}`")?;
 
 		let path = self.local();
-		if must_exists(&path).await {
-			Git::fetch(&path).await?;
+		let empty = if must_exists(&path).await {
+			is_dir_empty(&path)
+				.await
+				.with_context(|| format!("failed to read package directory `{}`", path.display()))?
 		} else {
+			true
+		};
+
+		if empty {
 			Git::clone(&self.remote(), &path).await?;
+		} else {
+			Git::fetch(&path).await?;
 		};
 
-		if !self.rev.is_empty() {
+		if let Some(lock) = lock {
+			Git::checkout(&path, &lock.rev).await?;
+		} else if !self.rev.is_empty() {
 			Git::checkout(&path, self.rev.trim_start_matches('=')).await?;
 		}
 
 		self.deploy().await?;
-		self.rev = Git::revision(&path).await?;
+		if let Some(lock) = lock.filter(|l| l.hash != self.hash) {
+			bail!(
+				"`{}` at `{}` doesn't match the hash in package.lock, it may have been tampered with.",
+				self.name,
+				lock.rev
+			);
+		}
+
+		let rev = Git::revision(&path).await?;
+		self.rev = if self.pinned().is_some() { format!("={rev}") } else { rev };
+		self.commit = Git::commit(&path).await?;
 		Ok(())
 	}
 }
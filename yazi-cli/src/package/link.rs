@@ -0,0 +1,65 @@
+use anyhow::{Context, Result, bail};
+use yazi_fs::provider::local::Local;
+use yazi_macro::outln;
+
+use super::Dependency;
+use crate::shared::{ensure_dir, maybe_exists};
+
+impl Dependency {
+	pub(super) async fn link(&mut self) -> Result<()> {
+		let from = self.local();
+		if !Local::metadata(&from).await.is_ok_and(|m| m.is_dir()) {
+			bail!("Package source `{}` is not a directory", from.display());
+		}
+
+		self.header("Linking package `{name}`")?;
+		self.is_flavor = maybe_exists(from.join("flavor.toml")).await;
+
+		let to = self.target();
+		if Local::read_link(&to).await.is_ok_and(|p| p == from) {
+			return Ok(outln!("Already linked, skipping")?);
+		} else if maybe_exists(&to).await {
+			bail!(
+				"`{}` already exists and is not a link to `{}`, please remove it and re-run the command.",
+				to.display(),
+				from.display()
+			);
+		}
+
+		ensure_dir(to.parent().unwrap()).await?;
+		Local::symlink_dir(&from, &to)
+			.await
+			.with_context(|| format!("failed to link `{}` to `{}`", to.display(), from.display()))?;
+
+		self.hash.clear();
+		outln!("Done!")?;
+		Ok(())
+	}
+
+	pub(super) async fn unlink(&self) -> Result<()> {
+		self.header("Unlinking package `{name}`")?;
+
+		let to = self.target();
+		match Local::symlink_metadata(&to).await {
+			Ok(m) if m.is_symlink() => {}
+			Ok(_) => bail!(
+				"`{}` is not a link to the package source, for safety, please remove it manually.",
+				to.display()
+			),
+			Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+				return Ok(outln!("Not found, skipping")?);
+			}
+			Err(e) => Err(e).context(format!("failed to read `{}`", to.display()))?,
+		}
+
+		// Only the link itself is removed, never the source directory it points to
+		#[cfg(unix)]
+		let result = Local::remove_file(&to).await;
+		#[cfg(windows)]
+		let result = Local::remove_dir(&to).await;
+		result.with_context(|| format!("failed to remove `{}`", to.display()))?;
+
+		outln!("Done!")?;
+		Ok(())
+	}
+}
@@ -0,0 +1,99 @@
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use yazi_fs::{Xdg, provider::local::Local};
+
+use super::{Dependency, Source};
+
+/// The exact revisions and content hashes of the Git packages, in `package.lock`.
+#[derive(Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub(crate) struct Lock {
+	#[serde(default, rename = "package")]
+	entries: Vec<LockEntry>,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub(crate) struct LockEntry {
+	pub(crate) r#use: String,
+	pub(crate) rev:   String, // Full commit hash
+	pub(crate) hash:  String,
+}
+
+impl Lock {
+	pub(super) async fn load() -> Result<Self> {
+		match Local::read_to_string(Self::path()).await {
+			Ok(s) => toml::from_str(&s).context("Failed to parse package.lock"),
+			Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+			Err(e) => Err(e).context("Failed to read package.lock"),
+		}
+	}
+
+	pub(super) async fn save(&self) -> Result<()> {
+		let s = toml::to_string_pretty(self)?;
+		Local::write(Self::path(), s).await.context("Failed to write package.lock")
+	}
+
+	#[inline]
+	pub(super) fn get(&self, r#use: &str) -> Option<&LockEntry> {
+		self.entries.iter().find(|e| e.r#use == r#use)
+	}
+
+	/// Records the packages as they're deployed now.
+	pub(super) fn update<'a>(&mut self, deps: impl Iterator<Item = &'a Dependency>) {
+		self.entries = deps
+			.filter(|d| d.source == Source::Git)
+			.filter_map(|d| {
+				if d.commit.is_empty() {
+					self.get(&d.r#use).cloned()
+				} else {
+					Some(LockEntry { r#use: d.r#use.clone(), rev: d.commit.clone(), hash: d.hash.clone() })
+				}
+			})
+			.collect();
+	}
+
+	#[inline]
+	fn path() -> PathBuf { Xdg::config_dir().join("package.lock") }
+}
+
+#[cfg(test)]
+mod tests {
+	use std::str::FromStr;
+
+	use super::*;
+
+	#[test]
+	fn test_update() -> Result<()> {
+		let mut lock: Lock = toml::from_str(
+			r#"
+			[[package]]
+			use  = "owner/foo"
+			rev  = "1111111111111111111111111111111111111111"
+			hash = "aaa"
+
+			[[package]]
+			use  = "owner/gone"
+			rev  = "2222222222222222222222222222222222222222"
+			hash = "bbb"
+			"#,
+		)?;
+
+		let foo = Dependency::from_str("owner/foo")?;
+		let bar = Dependency {
+			commit: "3333333333333333333333333333333333333333".to_owned(),
+			hash: "ccc".to_owned(),
+			..Dependency::from_str("owner/bar@v1")?
+		};
+		let local = Dependency::from_str("path:/src/baz")?;
+
+		lock.update([&foo, &bar, &local].into_iter());
+		assert_eq!(lock.entries.len(), 2);
+		assert_eq!(lock.get("owner/foo").map(|e| e.hash.as_str()), Some("aaa"));
+		assert_eq!(lock.get("owner/bar").map(|e| e.rev.as_str()), Some(bar.commit.as_str()));
+		assert_eq!(lock.get("owner/gone"), None);
+
+		assert_eq!(toml::from_str::<Lock>(&toml::to_string_pretty(&lock)?)?, lock);
+		Ok(())
+	}
+}
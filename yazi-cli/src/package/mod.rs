@@ -1,14 +1,16 @@
 #![allow(clippy::module_inception)]
 
-yazi_macro::mod_flat!(add delete dependency deploy git hash install package upgrade);
+yazi_macro::mod_flat!(add delete dependency deploy git hash install link lock package source upgrade verify);
 
 use anyhow::Context;
 use yazi_fs::Xdg;
 
-pub(super) fn init() -> anyhow::Result<()> {
-	let root = Xdg::state_dir().join("packages");
-	std::fs::create_dir_all(&root)
-		.with_context(|| format!("failed to create packages directory: {root:?}"))?;
+use crate::shared::ensure_dir;
+
+pub(super) async fn init() -> anyhow::Result<()> {
+	ensure_dir(Xdg::state_dir().join("packages"))
+		.await
+		.context("failed to prepare the packages directory")?;
 
 	Ok(())
 }
@@ -5,21 +5,27 @@ use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use yazi_fs::{Xdg, provider::local::Local};
 use yazi_macro::outln;
 
-use super::Dependency;
+use super::{Dependency, Lock, Source};
+use crate::shared::ensure_dir;
 
 #[derive(Default)]
 pub(crate) struct Package {
 	pub(crate) plugins: Vec<Dependency>,
 	pub(crate) flavors: Vec<Dependency>,
+	pub(crate) lock:    Lock,
 }
 
 impl Package {
 	pub(crate) async fn load() -> Result<Self> {
-		Ok(match Local::read_to_string(Self::toml()).await {
+		let mut pkg: Self = match Local::read_to_string(Self::toml()).await {
 			Ok(s) => toml::from_str(&s)?,
 			Err(e) if e.kind() == std::io::ErrorKind::NotFound => Self::default(),
 			Err(e) => Err(e)?,
-		})
+		};
+
+		pkg.validate()?;
+		pkg.lock = Lock::load().await?;
+		Ok(pkg)
 	}
 
 	pub(crate) async fn add_many(&mut self, uses: &[String]) -> Result<()> {
@@ -40,10 +46,18 @@ impl Package {
 		Ok(())
 	}
 
-	pub(crate) async fn install(&mut self) -> Result<()> {
+	pub(crate) async fn install(&mut self, locked: bool) -> Result<()> {
 		macro_rules! go {
 			($dep:expr) => {
-				let r = $dep.install().await;
+				let lock = self.lock.get(&$dep.r#use).cloned();
+				if locked && lock.is_none() && $dep.source == Source::Git {
+					bail!(
+						"`{}` is not in package.lock, run `ya pkg install` without `--locked` to add it",
+						$dep.r#use
+					);
+				}
+
+				let r = $dep.install(lock.as_ref()).await;
 				self.save().await?;
 				r?;
 			};
@@ -58,11 +72,12 @@ impl Package {
 		Ok(())
 	}
 
-	pub(crate) async fn upgrade_many(&mut self, uses: &[String]) -> Result<()> {
+	pub(crate) async fn upgrade_many(&mut self, uses: &[String], locked: bool) -> Result<()> {
 		macro_rules! go {
 			($dep:expr) => {
 				if uses.is_empty() || uses.contains(&$dep.r#use) {
-					let r = $dep.upgrade().await;
+					let lock = self.lock.get(&$dep.r#use).cloned();
+					let r = if locked { $dep.upgrade_locked(lock).await } else { $dep.upgrade().await };
 					self.save().await?;
 					r?;
 				}
@@ -79,35 +94,42 @@ impl Package {
 	}
 
 	pub(crate) fn print(&self) -> Result<()> {
-		outln!("Plugins:")?;
-		for d in &self.plugins {
-			if d.rev.is_empty() {
-				outln!("\t{}", d.r#use)?;
+		fn line(d: &Dependency) -> std::io::Result<()> {
+			if let Some(rev) = d.pinned() {
+				outln!("\t{} ({rev}, pinned)", d.r#use)
+			} else if d.rev.is_empty() {
+				outln!("\t{}", d.r#use)
 			} else {
-				outln!("\t{} ({})", d.r#use, d.rev)?;
+				outln!("\t{} ({})", d.r#use, d.rev)
 			}
 		}
 
+		outln!("Plugins:")?;
+		self.plugins.iter().try_for_each(line)?;
+
 		outln!("Flavors:")?;
-		for d in &self.flavors {
-			if d.rev.is_empty() {
-				outln!("\t{}", d.r#use)?;
-			} else {
-				outln!("\t{} ({})", d.r#use, d.rev)?;
-			}
-		}
+		self.flavors.iter().try_for_each(line)?;
 
 		Ok(())
 	}
 
 	async fn add(&mut self, r#use: &str) -> Result<()> {
 		let mut dep = Dependency::from_str(r#use)?;
+		dep.resolve().await?;
+
 		if let Some(d) = self.identical(&dep) {
 			bail!(
 				"{} `{}` already exists in package.toml",
 				if d.is_flavor { "Flavor" } else { "Plugin" },
 				dep.name
 			)
+		} else if let Some(d) = self.conflicting(&dep) {
+			bail!(
+				"`{}` from `{}` conflicts with the one from `{}` in package.toml, delete it first",
+				dep.name,
+				dep.r#use,
+				d.r#use
+			)
 		}
 
 		dep.add().await?;
@@ -120,7 +142,10 @@ impl Package {
 	}
 
 	async fn delete(&mut self, r#use: &str) -> Result<()> {
-		let Some(dep) = self.identical(&Dependency::from_str(r#use)?).cloned() else {
+		let mut dep = Dependency::from_str(r#use)?;
+		dep.resolve().await?;
+
+		let Some(dep) = self.identical(&dep).cloned() else {
 			bail!("`{}` was not found in package.toml", r#use)
 		};
 
@@ -133,9 +158,13 @@ impl Package {
 		Ok(())
 	}
 
-	async fn save(&self) -> Result<()> {
+	async fn save(&mut self) -> Result<()> {
 		let s = toml::to_string_pretty(self)?;
-		Local::write(Self::toml(), s).await.context("Failed to write package.toml")
+		ensure_dir(Xdg::config_dir()).await.context("Failed to prepare the config directory")?;
+		Local::write(Self::toml(), s).await.context("Failed to write package.toml")?;
+
+		self.lock.update(self.plugins.iter().chain(&self.flavors));
+		self.lock.save().await
 	}
 
 	#[inline]
@@ -145,6 +174,27 @@ impl Package {
 	fn identical(&self, other: &Dependency) -> Option<&Dependency> {
 		self.plugins.iter().chain(&self.flavors).find(|d| d.identical(other))
 	}
+
+	/// Finds a different package that would be deployed to the same directory.
+	#[inline]
+	fn conflicting(&self, other: &Dependency) -> Option<&Dependency> {
+		self.plugins.iter().chain(&self.flavors).find(|d| d.name == other.name && !d.identical(other))
+	}
+
+	fn validate(&self) -> Result<()> {
+		let all: Vec<_> = self.plugins.iter().chain(&self.flavors).collect();
+		for (i, a) in all.iter().enumerate() {
+			if let Some(b) = all[i + 1..].iter().find(|b| b.name == a.name) {
+				bail!(
+					"`{}` is declared more than once in package.toml, from `{}` and `{}`, keep only one of them",
+					a.name,
+					a.r#use,
+					b.r#use
+				);
+			}
+		}
+		Ok(())
+	}
 }
 
 impl<'de> Deserialize<'de> for Package {
@@ -167,7 +217,7 @@ impl<'de> Deserialize<'de> for Package {
 		let mut outer = Outer::deserialize(deserializer)?;
 		outer.flavor.deps.iter_mut().for_each(|d| d.is_flavor = true);
 
-		Ok(Self { plugins: outer.plugin.deps, flavors: outer.flavor.deps })
+		Ok(Self { plugins: outer.plugin.deps, flavors: outer.flavor.deps, ..Default::default() })
 	}
 }
 
@@ -190,3 +240,34 @@ impl Serialize for Package {
 			.serialize(serializer)
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::package::Source;
+
+	#[test]
+	fn test_validate() -> Result<()> {
+		let pkg: Package = toml::from_str(
+			r#"
+			[plugin]
+			deps = [
+				{ use = "owner/foo", rev = "=abc1234" },
+				{ use = "path:/src/bar", source = "path" },
+			]
+			"#,
+		)?;
+		pkg.validate()?;
+		assert_eq!(pkg.plugins[0].pinned(), Some("abc1234"));
+		assert_eq!(pkg.plugins[1].source, Source::Path);
+
+		let pkg: Package = toml::from_str(
+			r#"
+			[plugin]
+			deps = [{ use = "owner/foo" }, { use = "path:/src/foo.yazi" }]
+			"#,
+		)?;
+		assert!(pkg.validate().is_err());
+		Ok(())
+	}
+}
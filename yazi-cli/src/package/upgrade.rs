@@ -1,9 +1,28 @@
-use anyhow::Result;
+use anyhow::{Result, bail};
+use yazi_macro::outln;
 
-use super::Dependency;
+use super::{Dependency, LockEntry, Source};
 
 impl Dependency {
 	pub(super) async fn upgrade(&mut self) -> Result<()> {
-		if self.rev.starts_with('=') { Ok(()) } else { self.add().await }
+		if self.source == Source::Path {
+			self.header("Upgrading package `{name}`")?;
+			outln!("Skipped, it's linked to the local directory `{}`", self.parent)?;
+		} else if let Some(rev) = self.pinned() {
+			self.header("Upgrading package `{name}`")?;
+			outln!("Skipped, it's pinned to `{rev}`")?;
+		} else {
+			self.add().await?;
+		}
+		Ok(())
+	}
+
+	/// Brings the package to the revision of package.lock, instead of the latest.
+	pub(super) async fn upgrade_locked(&mut self, lock: Option<LockEntry>) -> Result<()> {
+		match lock {
+			_ if self.source == Source::Path => self.upgrade().await,
+			Some(lock) => self.install(Some(&lock)).await,
+			None => bail!("`{}` is not in package.lock, upgrade it without `--locked` first", self.r#use),
+		}
 	}
 }
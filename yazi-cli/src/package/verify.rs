@@ -0,0 +1,42 @@
+use anyhow::{Result, bail};
+use yazi_macro::outln;
+
+use super::{Dependency, Package, Source};
+
+impl Package {
+	/// Checks the deployed packages against the hashes in package.lock, without changing anything.
+	pub(crate) async fn verify(&self) -> Result<()> {
+		let mut failed = 0;
+		for dep in self.plugins.iter().chain(&self.flavors) {
+			let state = self.verify_one(dep).await;
+			if state.is_err() {
+				failed += 1;
+			}
+
+			match state {
+				Ok(s) | Err(s) => outln!("\t{} ({s})", dep.r#use)?,
+			}
+		}
+
+		if failed > 0 {
+			bail!("{failed} package(s) failed the verification, run `ya pkg install` to restore them");
+		}
+		Ok(())
+	}
+
+	async fn verify_one(&self, dep: &Dependency) -> Result<String, String> {
+		if dep.source == Source::Path {
+			return Ok("linked, skipped".to_owned());
+		}
+
+		let Some(lock) = self.lock.get(&dep.r#use) else {
+			return Err("not in package.lock".to_owned());
+		};
+
+		match dep.hash().await {
+			Ok(h) if h == lock.hash => Ok(format!("{}, ok", &lock.rev[..lock.rev.len().min(7)])),
+			Ok(_) => Err("modified, or not installed".to_owned()),
+			Err(e) => Err(format!("failed to hash: {e}")),
+		}
+	}
+}
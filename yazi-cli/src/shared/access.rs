@@ -0,0 +1,169 @@
+use std::{fmt::Display, io, path::{Path, PathBuf}};
+
+use yazi_fs::provider::local::Local;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Access {
+	Read,
+	Write,
+	Execute,
+}
+
+#[derive(Debug)]
+pub enum EnsureError {
+	WrongKind(PathBuf),
+	PermissionDenied(PathBuf),
+	MissingParent(PathBuf),
+	Io(PathBuf, io::Error),
+}
+
+impl Display for EnsureError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::WrongKind(p) => write!(f, "`{}` exists but is not a directory", p.display()),
+			Self::PermissionDenied(p) => write!(f, "`{}` is not writable", p.display()),
+			Self::MissingParent(p) => write!(f, "`{}` has no existing parent directory", p.display()),
+			Self::Io(p, e) => write!(f, "failed to create `{}`: {e}", p.display()),
+		}
+	}
+}
+
+impl std::error::Error for EnsureError {
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		match self {
+			Self::Io(_, e) => Some(e),
+			_ => None,
+		}
+	}
+}
+
+/// Whether the current user has the `access` to the path.
+pub async fn accessible(path: impl AsRef<Path>, access: Access) -> bool {
+	let path = path.as_ref().to_owned();
+
+	#[cfg(unix)]
+	{
+		use std::{ffi::CString, os::unix::ffi::OsStrExt};
+
+		let Ok(c) = CString::new(path.as_os_str().as_bytes()) else { return false };
+		let mode = match access {
+			Access::Read => libc::R_OK,
+			Access::Write => libc::W_OK,
+			Access::Execute => libc::X_OK,
+		};
+
+		tokio::task::spawn_blocking(move || unsafe {
+			libc::faccessat(libc::AT_FDCWD, c.as_ptr(), mode, libc::AT_EACCESS) == 0
+		})
+		.await
+		.unwrap_or(false)
+	}
+
+	#[cfg(windows)]
+	{
+		match Local::metadata(&path).await {
+			Ok(m) if access == Access::Write => m.is_dir() || !m.permissions().readonly(),
+			Ok(_) => true,
+			Err(_) => false,
+		}
+	}
+}
+
+pub async fn is_dir_empty(path: impl AsRef<Path>) -> io::Result<bool> {
+	Ok(Local::read_dir(path).await?.next_entry().await?.is_none())
+}
+
+/// Makes sure the directory exists and is writable, creating it if needed.
+pub async fn ensure_dir(path: impl AsRef<Path>) -> Result<(), EnsureError> {
+	let path = path.as_ref();
+
+	let mut existing = None;
+	for p in path.ancestors().filter(|p| !p.as_os_str().is_empty()) {
+		match Local::metadata(p).await {
+			Ok(m) if m.is_dir() => {
+				existing = Some(p);
+				break;
+			}
+			Ok(_) => return Err(EnsureError::WrongKind(p.to_owned())),
+			Err(e) if matches!(e.kind(), io::ErrorKind::NotFound | io::ErrorKind::NotADirectory) => {}
+			Err(e) if e.kind() == io::ErrorKind::PermissionDenied => {
+				return Err(EnsureError::PermissionDenied(p.to_owned()));
+			}
+			Err(e) => return Err(EnsureError::Io(p.to_owned(), e)),
+		}
+	}
+
+	let Some(existing) = existing else {
+		return Err(EnsureError::MissingParent(path.to_owned()));
+	};
+
+	if existing != path {
+		if !accessible(existing, Access::Write).await {
+			return Err(EnsureError::PermissionDenied(existing.to_owned()));
+		}
+		Local::create_dir_all(path).await.map_err(|e| EnsureError::Io(path.to_owned(), e))?;
+	}
+
+	if accessible(path, Access::Write).await {
+		Ok(())
+	} else {
+		Err(EnsureError::PermissionDenied(path.to_owned()))
+	}
+}
+
+#[cfg(test)]
+#[path = "../../tests/common/mod.rs"]
+mod common;
+
+#[cfg(test)]
+mod tests {
+	use std::fs;
+
+	use super::{common::TempDir, *};
+
+	#[tokio::test]
+	async fn test_ensure_dir() -> anyhow::Result<()> {
+		let root = TempDir::new("cli-access")?;
+
+		// Missing parents are created
+		ensure_dir(root.join("a/b/c")).await?;
+		assert!(is_dir_empty(root.join("a/b/c")).await?);
+		assert!(!is_dir_empty(root.join("a")).await?);
+
+		// A path component that is a regular file
+		fs::write(root.join("file"), "")?;
+		assert!(matches!(
+			ensure_dir(root.join("file/sub")).await,
+			Err(EnsureError::WrongKind(p)) if p == root.join("file")
+		));
+
+		// A symlink to a directory counts as a directory
+		#[cfg(unix)]
+		{
+			std::os::unix::fs::symlink(root.join("a"), root.join("link"))?;
+			ensure_dir(root.join("link/d")).await?;
+		}
+
+		// A read-only directory, where root can still write to
+		#[cfg(unix)]
+		if unsafe { libc::geteuid() } != 0 {
+			use std::os::unix::fs::PermissionsExt;
+
+			fs::create_dir(root.join("ro"))?;
+			fs::set_permissions(root.join("ro"), fs::Permissions::from_mode(0o555))?;
+			assert!(accessible(root.join("ro"), Access::Read).await);
+			assert!(!accessible(root.join("ro"), Access::Write).await);
+			assert!(matches!(
+				ensure_dir(root.join("ro/sub")).await,
+				Err(EnsureError::PermissionDenied(p)) if p == root.join("ro")
+			));
+			assert!(matches!(
+				ensure_dir(root.join("ro")).await,
+				Err(EnsureError::PermissionDenied(p)) if p == root.join("ro")
+			));
+			fs::set_permissions(root.join("ro"), fs::Permissions::from_mode(0o755))?;
+		}
+
+		Ok(())
+	}
+}
@@ -1 +1 @@
-yazi_macro::mod_flat!(shared);
+yazi_macro::mod_flat!(access shared);
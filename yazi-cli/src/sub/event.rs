@@ -0,0 +1,137 @@
+use serde_json::{Value, json};
+use yazi_shared::url::Url;
+
+pub struct Event;
+
+impl Event {
+	/// Renders a `kind,receiver,sender,body` message as a JSON line.
+	pub fn render(line: &str, structured: bool) -> Option<String> {
+		let mut parts = line.splitn(4, ',');
+		let kind = parts.next()?;
+		let _receiver = parts.next()?;
+		let sender: u64 = parts.next()?.parse().ok()?;
+
+		let mut body = match parts.next()? {
+			"" => Value::Null,
+			s => serde_json::from_str(s).unwrap_or_else(|_| Value::String(s.to_owned())),
+		};
+		Self::urls(kind, &mut body, structured);
+
+		serde_json::to_string(&json!({ "kind": kind, "sender": sender, "body": body })).ok()
+	}
+
+	fn urls(kind: &str, body: &mut Value, structured: bool) {
+		let each = |v: Option<&mut Value>| match v {
+			Some(Value::Array(a)) => a.iter_mut().for_each(|v| Self::url(v, structured)),
+			Some(v) => Self::url(v, structured),
+			None => {}
+		};
+
+		match kind {
+			"cd" | "hover" | "load" => each(body.get_mut("url")),
+			"rename" => {
+				each(body.get_mut("from"));
+				each(body.get_mut("to"));
+			}
+			"trash" | "delete" => each(body.get_mut("urls")),
+			"move" => {
+				let Some(Value::Array(items)) = body.get_mut("items") else { return };
+				for item in items {
+					each(item.get_mut("from"));
+					each(item.get_mut("to"));
+				}
+			}
+			"@trashed" => {
+				let Some(Value::Array(items)) = body.get_mut("items") else { return };
+				items.iter_mut().for_each(|item| each(item.get_mut("url")));
+			}
+			"bulk" => {
+				let Some(changes) = body.get_mut("changes").filter(|v| v.is_object()) else { return };
+				let Value::Object(map) = changes.take() else { return };
+
+				let pairs = map.into_iter().map(|(from, mut to)| {
+					let mut from = Value::String(from);
+					Self::url(&mut from, structured);
+					Self::url(&mut to, structured);
+					(from, to)
+				});
+
+				// Objects can't be keys, so the structured form is a list of pairs instead
+				*changes = if structured {
+					pairs.map(|(from, to)| json!({ "from": from, "to": to })).collect()
+				} else {
+					pairs.filter_map(|(from, to)| Some((from.as_str()?.to_owned(), to))).collect()
+				};
+			}
+			"@yank" => {
+				each(body.get_mut("urls"));
+				if let Some(lazy) = body.get_mut("lazy") {
+					each(lazy.get_mut("roots"));
+					each(lazy.get_mut("excluded"));
+				}
+			}
+			_ => {}
+		}
+	}
+
+	fn url(v: &mut Value, structured: bool) {
+		let Some(url) = v.as_str().and_then(|s| s.parse::<Url>().ok()) else { return };
+		*v = if structured {
+			json!({
+				"scheme": url.scheme.kind(),
+				"domain": url.scheme.domain(),
+				"path": url.loc.display().to_string(),
+			})
+		} else {
+			Value::String(url.display().to_string())
+		};
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn render(line: &str, structured: bool) -> Value {
+		serde_json::from_str(&Event::render(line, structured).unwrap()).unwrap()
+	}
+
+	#[test]
+	fn test_render() {
+		assert_eq!(
+			render(r#"cd,0,123,{"tab":1,"url":"search://kw:1:1//tmp/a"}"#, false),
+			json!({ "kind": "cd", "sender": 123, "body": { "tab": 1, "url": "/tmp/a" } })
+		);
+		assert_eq!(
+			render(r#"cd,0,123,{"tab":1,"url":"search://kw:1:1//tmp/a"}"#, true),
+			json!({ "kind": "cd", "sender": 123, "body": {
+				"tab": 1,
+				"url": { "scheme": "search", "domain": "kw", "path": "/tmp/a" },
+			}})
+		);
+
+		assert_eq!(
+			render(r#"@yank,0,1,{"cut":true,"urls":["/a","/b"]}"#, true)["body"]["urls"],
+			json!([
+				{ "scheme": "regular", "domain": null, "path": "/a" },
+				{ "scheme": "regular", "domain": null, "path": "/b" },
+			])
+		);
+		assert_eq!(
+			render(r#"bulk,0,1,{"changes":{"/a":"/b"}}"#, false)["body"],
+			json!({ "changes": { "/a": "/b" } })
+		);
+		assert_eq!(
+			render(r#"bulk,0,1,{"changes":{"/a":"/b"}}"#, true)["body"]["changes"][0]["to"]["path"],
+			"/b"
+		);
+
+		// Custom kinds are left alone, and bodies that aren't JSON are kept as strings
+		assert_eq!(render(r#"my-event,0,1,{"url":"/a"}"#, true)["body"], json!({ "url": "/a" }));
+		assert_eq!(render("my-event,0,1,", false)["body"], Value::Null);
+		assert_eq!(render("my-event,0,1,oops", false)["body"], "oops");
+
+		assert!(Event::render("cd,0,not-an-id,{}", false).is_none());
+		assert!(Event::render("", false).is_none());
+	}
+}
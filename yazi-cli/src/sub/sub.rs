@@ -0,0 +1,53 @@
+use std::{collections::HashSet, sync::{Arc, atomic::{AtomicUsize, Ordering}}};
+
+use anyhow::Result;
+use tokio::{io::AsyncWriteExt, select, sync::mpsc};
+use yazi_macro::errln;
+
+use super::Event;
+
+/// Events held while stdout is blocked, any more than that are dropped.
+const BUFFER: usize = 1024;
+
+pub struct Sub;
+
+impl Sub {
+	pub async fn run(kinds: HashSet<&str>, structured: bool, retry: bool) -> Result<()> {
+		let (tx, mut rx) = mpsc::channel(BUFFER);
+		let dropped = Arc::new(AtomicUsize::new(0));
+
+		let draw = yazi_dds::Client::draw(kinds, retry, {
+			let dropped = dropped.clone();
+			move |line| {
+				if tx.try_send(line).is_err() {
+					dropped.fetch_add(1, Ordering::Relaxed);
+				}
+			}
+		});
+
+		let write = async {
+			let mut stdout = tokio::io::stdout();
+			while let Some(line) = rx.recv().await {
+				let Some(event) = Event::render(&line, structured) else { continue };
+				stdout.write_all(format!("{event}\n").as_bytes()).await?;
+				stdout.flush().await?;
+
+				match dropped.swap(0, Ordering::Relaxed) {
+					0 => {}
+					n => errln!("Dropped {n} events while stdout was blocked")?,
+				}
+			}
+			Ok(())
+		};
+
+		tokio::pin!(write);
+		select! {
+			r = draw => {
+				r?;
+				write.await
+			}
+			r = &mut write => r,
+			r = tokio::signal::ctrl_c() => Ok(r?),
+		}
+	}
+}
@@ -0,0 +1,30 @@
+use std::{io, ops::Deref, path::{Path, PathBuf}, sync::atomic::{AtomicUsize, Ordering}};
+
+// A directory under the temporary one, unique to the process, and removed with
+// its contents once dropped, for the tests to clean up even if they fail
+pub struct TempDir(PathBuf);
+
+impl TempDir {
+	pub fn new(name: &str) -> io::Result<Self> {
+		static N: AtomicUsize = AtomicUsize::new(0);
+
+		let n = N.fetch_add(1, Ordering::Relaxed);
+		let path = std::env::temp_dir().join(format!("yazi-{name}-{}-{n}", std::process::id()));
+		std::fs::create_dir_all(&path)?;
+		Ok(Self(path))
+	}
+}
+
+impl Deref for TempDir {
+	type Target = Path;
+
+	fn deref(&self) -> &Self::Target { &self.0 }
+}
+
+impl AsRef<Path> for TempDir {
+	fn as_ref(&self) -> &Path { &self.0 }
+}
+
+impl Drop for TempDir {
+	fn drop(&mut self) { std::fs::remove_dir_all(&self.0).ok(); }
+}
@@ -0,0 +1,79 @@
+#![cfg(unix)]
+
+use std::{path::Path, process::ExitStatus};
+
+use serde_json::{Value, json};
+use tokio::{io::{AsyncBufReadExt, AsyncWriteExt, BufReader}, net::UnixListener, process::Command};
+
+use crate::common::TempDir;
+
+mod common;
+
+async fn ya(tmp: &Path, args: &[&str]) -> ExitStatus {
+	Command::new(env!("CARGO_BIN_EXE_ya"))
+		.env("TMPDIR", tmp)
+		.env("YAZI_ID", "123")
+		.args(args)
+		.status()
+		.await
+		.unwrap()
+}
+
+/// Accepts a single client, and returns the `dds-emit` message it sent.
+async fn serve(listener: UnixListener) -> (String, String, Value) {
+	let (stream, _) = listener.accept().await.unwrap();
+	let (reader, mut writer) = stream.into_split();
+	let mut lines = BufReader::new(reader).lines();
+
+	let hi = lines.next_line().await.unwrap().unwrap();
+	let hi: Value = serde_json::from_str(hi.splitn(4, ',').nth(3).unwrap()).unwrap();
+
+	let msg = lines.next_line().await.unwrap().unwrap();
+	let hey = json!({ "peers": { "123": { "abilities": ["dds-emit"] } }, "version": hi["version"] });
+	writer.write_all(format!("hey,0,0,{hey}\nbye,0,0,null\n").as_bytes()).await.unwrap();
+
+	let parts: Vec<_> = msg.splitn(4, ',').collect();
+	(parts[0].to_owned(), parts[1].to_owned(), serde_json::from_str(parts[3]).unwrap())
+}
+
+#[tokio::test]
+async fn test_emit() {
+	let cases: [(&[&str], _); 4] = [
+		(
+			&["emit", "cd", "/tmp/a", "--interactive"],
+			json!(["cd", { "1": "/tmp/a", "interactive": true }]),
+		),
+		(
+			&["emit", "cd", "--", "--weird=name", "-x"],
+			json!(["cd", { "1": "--weird=name", "2": "-x" }]),
+		),
+		(
+			&["emit", "--pid", "123", "cd", "search://kw//tmp"],
+			json!(["cd", { "1": "search://kw//tmp" }]),
+		),
+		(
+			&["emit", "--json", r#"{"1":"/tmp/b","hidden":"yes"}"#, "cd"],
+			json!(["cd", { "1": "/tmp/b", "hidden": "yes" }]),
+		),
+	];
+
+	for (args, expected) in cases {
+		let tmp = TempDir::new("emit-ok").unwrap();
+		let sock = tmp.join(format!(".yazi_dds-{}.sock", unsafe { libc::getuid() }));
+		std::fs::remove_file(&sock).ok();
+
+		let server = tokio::spawn(serve(UnixListener::bind(&sock).unwrap()));
+		assert!(ya(&tmp, args).await.success());
+
+		let (kind, receiver, body) = server.await.unwrap();
+		assert_eq!(kind, "dds-emit");
+		assert_eq!(receiver, "123");
+		assert_eq!(body, expected);
+	}
+}
+
+#[tokio::test]
+async fn test_emit_unreachable() {
+	let tmp = TempDir::new("emit-unreachable").unwrap();
+	assert!(!ya(&tmp, &["emit", "cd", "/tmp"]).await.success());
+}
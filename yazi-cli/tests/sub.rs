@@ -0,0 +1,95 @@
+#![cfg(unix)]
+
+use serde_json::{Value, json};
+use tokio::{io::{AsyncBufReadExt, AsyncWriteExt, BufReader}, net::UnixListener, process::Command};
+
+use crate::common::TempDir;
+
+mod common;
+
+/// Accepts a single client, feeds it `events`, and returns its abilities.
+async fn publish(listener: UnixListener, events: &[&str]) -> Value {
+	let (stream, _) = listener.accept().await.unwrap();
+	let (reader, mut writer) = stream.into_split();
+	let mut lines = BufReader::new(reader).lines();
+
+	let hi = lines.next_line().await.unwrap().unwrap();
+	let hi: Value = serde_json::from_str(hi.splitn(4, ',').nth(3).unwrap()).unwrap();
+
+	for event in events {
+		writer.write_all(format!("{event}\n").as_bytes()).await.unwrap();
+	}
+	hi["abilities"].clone()
+}
+
+async fn sub(name: &str, args: &[&str], events: &'static [&'static str]) -> (Value, Vec<Value>) {
+	let tmp = TempDir::new(&format!("sub-{name}")).unwrap();
+	let sock = tmp.join(format!(".yazi_dds-{}.sock", unsafe { libc::getuid() }));
+	std::fs::remove_file(&sock).ok();
+
+	let publisher = tokio::spawn(publish(UnixListener::bind(&sock).unwrap(), events));
+	let output = Command::new(env!("CARGO_BIN_EXE_ya"))
+		.env("TMPDIR", &*tmp)
+		.arg("sub")
+		.args(args)
+		.output()
+		.await
+		.unwrap();
+
+	assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+	let lines = String::from_utf8(output.stdout).unwrap();
+	let mut abilities = publisher.await.unwrap();
+	abilities.as_array_mut().unwrap().sort_by_key(|v| v.to_string());
+
+	(abilities, lines.lines().map(|l| serde_json::from_str(l).unwrap()).collect())
+}
+
+#[tokio::test]
+async fn test_sub() {
+	let (abilities, lines) = sub("kinds", &["cd", "hover,tab"], &[
+		r#"hey,0,0,{"peers":{},"version":"0"}"#,
+		"",
+		r#"cd,0,100,{"tab":1,"url":"/tmp/a"}"#,
+		r#"rename,0,100,{"tab":1,"from":"/tmp/a","to":"/tmp/b"}"#,
+		r#"hover,0,100,{"tab":1,"url":"/tmp/a/b"}"#,
+	])
+	.await;
+
+	assert_eq!(abilities, json!(["cd", "hover", "tab"]));
+	assert_eq!(lines, [
+		json!({ "kind": "cd", "sender": 100, "body": { "tab": 1, "url": "/tmp/a" } }),
+		json!({ "kind": "hover", "sender": 100, "body": { "tab": 1, "url": "/tmp/a/b" } }),
+	]);
+}
+
+#[tokio::test]
+async fn test_sub_all() {
+	let (abilities, lines) = sub("all", &["*", "--structured"], &[
+		r#"hey,0,0,{"peers":{},"version":"0"}"#,
+		r#"dds-emit,0,100,["quit",{}]"#,
+		r#"trash,0,100,{"urls":["/tmp/a"]}"#,
+		r#"my-event,0,100,{"url":"/tmp/a"}"#,
+	])
+	.await;
+
+	assert_eq!(abilities, json!(["*"]));
+	assert_eq!(lines, [
+		json!({ "kind": "trash", "sender": 100, "body": {
+			"urls": [{ "scheme": "regular", "domain": null, "path": "/tmp/a" }],
+		}}),
+		json!({ "kind": "my-event", "sender": 100, "body": { "url": "/tmp/a" } }),
+	]);
+}
+
+#[tokio::test]
+async fn test_sub_unreachable() {
+	let tmp = TempDir::new("sub-unreachable").unwrap();
+	let status = Command::new(env!("CARGO_BIN_EXE_ya"))
+		.env("TMPDIR", &*tmp)
+		.args(["sub", "cd"])
+		.status()
+		.await
+		.unwrap();
+
+	assert!(!status.success());
+}
@@ -10,12 +10,16 @@ impl<'de> Deserialize<'de> for Color {
 	where
 		D: serde::Deserializer<'de>,
 	{
-		ratatui::style::Color::from_str(&String::deserialize(deserializer)?)
-			.map_err(serde::de::Error::custom)
-			.map(Self)
+		Self::from_str(&String::deserialize(deserializer)?).map_err(serde::de::Error::custom)
 	}
 }
 
+impl FromStr for Color {
+	type Err = ratatui::style::ParseColorError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> { ratatui::style::Color::from_str(s).map(Self) }
+}
+
 impl From<Color> for ratatui::style::Color {
 	fn from(value: Color) -> Self { value.0 }
 }
@@ -0,0 +1,37 @@
+use std::fmt::{self, Display};
+
+use serde::Serialize;
+
+/// A problem found in a config file, see [`Doctor::diagnose`](super::Doctor::diagnose).
+#[derive(Debug, Eq, PartialEq, Serialize)]
+pub struct Diagnostic {
+	pub file:    &'static str,
+	pub level:   DiagnosticLevel,
+	/// The dotted path to the key, such as `mgr.ratio`, or the layer of a keybinding.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub key:     Option<String>,
+	pub message: String,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiagnosticLevel {
+	/// The file can't be loaded, and Yazi falls back to the preset.
+	Error,
+	Warning,
+}
+
+impl Display for Diagnostic {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let level = match self.level {
+			DiagnosticLevel::Error => "error",
+			DiagnosticLevel::Warning => "warning",
+		};
+
+		write!(f, "{}: {level}: ", self.file)?;
+		if let Some(key) = &self.key {
+			write!(f, "`{key}`: ")?;
+		}
+		write!(f, "{}", self.message)
+	}
+}
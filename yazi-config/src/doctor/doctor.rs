@@ -0,0 +1,311 @@
+use std::{collections::HashSet, env, str::FromStr};
+
+use anyhow::Result;
+use toml::{Table, Value};
+use yazi_macro::{config_preset, theme_preset};
+use yazi_shared::Layer;
+
+use super::{Diagnostic, DiagnosticLevel};
+use crate::{Color, installed, keymap::{Chord, Keymap}, load_keymap, load_theme, load_yazi, theme::Theme, yazi::Yazi};
+
+/// The preset plugins that run external programs, with the programs any of which is enough.
+const PROGRAMS: &[(&str, &[&str])] = &[
+	("archive", &["7zz", "7z"]),
+	("file", &["file"]),
+	("font", &["magick"]),
+	("json", &["jq"]),
+	("magick", &["magick"]),
+	("mime", &["file"]),
+	("pdf", &["pdftoppm"]),
+	("svg", &["resvg"]),
+	("video", &["ffmpeg"]),
+];
+
+/// The shell built-ins that commands of the openers may start with.
+const BUILTINS: &[&str] =
+	&["[", "cd", "echo", "exec", "export", "false", "pause", "read", "set", "start", "test", "true"];
+
+#[derive(Default)]
+pub struct Doctor {
+	diagnostics: Vec<Diagnostic>,
+}
+
+impl Doctor {
+	/// Looks for the problems in the config files of the user.
+	pub fn diagnose() -> Vec<Diagnostic> {
+		let mut me = Self::default();
+
+		if let Some(user) = me.parse("yazi.toml", Yazi::read()) {
+			me.unknown_keys("yazi.toml", &user, &config_preset!("yazi"), &["opener"]);
+			match load_yazi(true) {
+				Ok(yazi) => me.programs(&yazi),
+				Err(e) => me.error("yazi.toml", e),
+			}
+		}
+
+		if let Some(user) = me.parse("keymap.toml", Keymap::read()) {
+			me.unknown_keys("keymap.toml", &user, &config_preset!("keymap"), &["mode"]);
+			match load_keymap(true) {
+				Ok(keymap) => me.conflicts(&keymap),
+				Err(e) => me.error("keymap.toml", e),
+			}
+		}
+
+		if let Some(user) = me.parse("theme.toml", Theme::read()) {
+			me.unknown_keys("theme.toml", &user, &theme_preset!("dark"), &[]);
+			if !me.colors("", &Value::Table(user)) {
+				// Either of the flavors can be broken
+				let load = |l| load_theme(None, |_| l, true).err();
+				if let Some(e) = [false, true].into_iter().find_map(load) {
+					me.error("theme.toml", e);
+				}
+			}
+		}
+
+		me.diagnostics
+	}
+
+	fn parse(&mut self, file: &'static str, s: Result<String>) -> Option<Table> {
+		match s.and_then(|s| Ok(toml::from_str(&s)?)) {
+			Ok(t) => Some(t),
+			Err(e) => {
+				self.error(file, e);
+				None
+			}
+		}
+	}
+
+	fn unknown_keys(&mut self, file: &'static str, user: &Table, preset: &str, open: &[&str]) {
+		fn lookup<'a>(table: &'a Table, key: &str) -> Option<&'a Value> {
+			table.get(key).or_else(|| {
+				let rest = key.strip_prefix("prepend_").or_else(|| key.strip_prefix("append_"))?;
+				table.get(rest)
+			})
+		}
+
+		let preset: Table = toml::from_str(preset).unwrap_or_default();
+		for (section, value) in user {
+			let Some(base) = lookup(&preset, section) else {
+				self.warn(file, section.clone(), "unknown key, which is ignored");
+				continue;
+			};
+
+			let (Value::Table(value), Value::Table(base)) = (value, base) else { continue };
+			if open.contains(&section.as_str()) {
+				continue;
+			}
+
+			for key in value.keys().filter(|&k| lookup(base, k).is_none()) {
+				self.warn(file, format!("{section}.{key}"), "unknown key, which is ignored");
+			}
+		}
+	}
+
+	fn conflicts(&mut self, keymap: &Keymap) {
+		use Layer as L;
+		let layers =
+			[L::Mgr, L::Tasks, L::Notify, L::Spot, L::Pick, L::Input, L::Confirm, L::Help, L::Cmp];
+		for layer in layers {
+			self.conflicts_in(layer.to_string(), keymap.get(layer));
+		}
+
+		let mut modes: Vec<_> = keymap.mode.iter().collect();
+		modes.sort_unstable_by_key(|&(name, _)| name);
+		for (name, mode) in modes {
+			self.conflicts_in(format!("mode.{name}"), &mode.rules);
+		}
+	}
+
+	/// Keybindings shadowed by an earlier one with the same keys or the same first key.
+	fn conflicts_in(&mut self, key: String, chords: &[Chord]) {
+		for (i, a) in chords.iter().enumerate() {
+			let Some(first) = a.on.first() else { continue };
+			let Some(b) = chords[..i].iter().find(|b| {
+				b.on == a.on || (b.on.first() == Some(first) && (b.on.len() == 1) != (a.on.len() == 1))
+			}) else {
+				continue;
+			};
+
+			let message = format!(
+				"`{}` ({}) is never reached, since `{}` ({}) comes first",
+				a.on(),
+				a.desc_or_run(),
+				b.on(),
+				b.desc_or_run()
+			);
+			self.warn("keymap.toml", key.clone(), message);
+		}
+	}
+
+	fn programs(&mut self, yazi: &Yazi) {
+		let mut openers: Vec<_> = yazi.opener.iter().collect();
+		openers.sort_unstable_by_key(|&(name, _)| name);
+
+		let mut seen = HashSet::new();
+		for (name, rules) in openers {
+			for program in rules.iter().flat_map(|r| Self::shell_programs(&r.run)) {
+				if seen.insert(program) && !installed(program) {
+					self.warn("yazi.toml", format!("opener.{name}"), format!("`{program}` is not found"));
+				}
+			}
+		}
+
+		let plugin = &yazi.plugin;
+		let used: HashSet<_> = (plugin.previewers.iter().map(|p| &p.run.name))
+			.chain(plugin.preloaders.iter().map(|p| &p.run.name))
+			.chain(plugin.fetchers.iter().map(|p| &p.run.name))
+			.collect();
+
+		for &(name, programs) in PROGRAMS {
+			// `YAZI_FILE_ONE` replaces the `file` program of these
+			let replaced = matches!(name, "file" | "mime") && env::var_os("YAZI_FILE_ONE").is_some();
+			if replaced || !used.iter().any(|&n| n == name) || programs.iter().any(|p| installed(p)) {
+				continue;
+			}
+
+			let message = format!("`{name}` needs `{}`, which is not found", programs.join("` or `"));
+			self.warn("yazi.toml", "plugin".to_owned(), message);
+		}
+	}
+
+	/// The programs the shell commands of `run` start with, if they can be told.
+	fn shell_programs(run: &str) -> impl Iterator<Item = &str> {
+		run.split([';', '&', '|', '\n']).filter_map(|cmd| {
+			let first = cmd.split_whitespace().next()?;
+			Some(first).filter(|s| {
+				!s.starts_with(['"', '\'', '<', '>'])
+					&& !s.contains(['$', '%', '(', '`', '='])
+					&& !s.bytes().all(|b| b.is_ascii_digit())
+					&& !BUILTINS.contains(s)
+			})
+		})
+	}
+
+	/// Checks the `fg` and `bg` colors in `value`, returning whether any is invalid.
+	fn colors(&mut self, path: &str, value: &Value) -> bool {
+		let join = |k: &str| if path.is_empty() { k.to_owned() } else { format!("{path}.{k}") };
+
+		let mut invalid = false;
+		match value {
+			Value::Table(t) => {
+				for (k, v) in t {
+					if let (true, Value::String(s)) = (k == "fg" || k == "bg", v)
+						&& Color::from_str(s).is_err()
+					{
+						self.diagnostics.push(Diagnostic {
+							file:    "theme.toml",
+							level:   DiagnosticLevel::Error,
+							key:     Some(join(k)),
+							message: format!("invalid color `{s}`"),
+						});
+						invalid = true;
+					} else {
+						invalid |= self.colors(&join(k), v);
+					}
+				}
+			}
+			Value::Array(a) => {
+				for (i, v) in a.iter().enumerate() {
+					invalid |= self.colors(&format!("{path}[{i}]"), v);
+				}
+			}
+			_ => {}
+		}
+		invalid
+	}
+
+	fn error(&mut self, file: &'static str, e: anyhow::Error) {
+		self.diagnostics.push(Diagnostic {
+			file,
+			level: DiagnosticLevel::Error,
+			key: None,
+			message: format!("{e:#}"),
+		});
+	}
+
+	fn warn(&mut self, file: &'static str, key: String, message: impl Into<String>) {
+		self.diagnostics.push(Diagnostic {
+			file,
+			level: DiagnosticLevel::Warning,
+			key: Some(key),
+			message: message.into(),
+		});
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::Preset;
+
+	#[test]
+	fn test_preset() {
+		let mut doctor = Doctor::default();
+		doctor.conflicts(&Preset::keymap().unwrap().reshape().unwrap());
+		for theme in [theme_preset!("dark"), theme_preset!("light")] {
+			assert!(!doctor.colors("", &toml::from_str(&theme).unwrap()));
+		}
+		assert_eq!(doctor.diagnostics, []);
+	}
+
+	#[test]
+	fn test_unknown_keys() {
+		let user = toml::from_str(
+			r#"
+			foo = 1
+			[mgr]
+			ratio = [1, 4, 3]
+			ratoi = [1, 4, 3]
+			[open]
+			prepend_rules = []
+			[opener]
+			mine = [{ run = "mine" }]
+			"#,
+		)
+		.unwrap();
+
+		let mut doctor = Doctor::default();
+		doctor.unknown_keys("yazi.toml", &user, &config_preset!("yazi"), &["opener"]);
+
+		let keys: Vec<_> = doctor.diagnostics.iter().filter_map(|d| d.key.as_deref()).collect();
+		assert_eq!(keys, ["foo", "mgr.ratoi"]);
+	}
+
+	#[test]
+	fn test_conflicts() {
+		let rules = |s: &str| {
+			toml::from_str::<crate::keymap::KeymapRules>(s).unwrap().reshape(Layer::Mgr).unwrap()
+		};
+
+		let chords = rules(
+			r#"keymap = [
+				{ on = "g", run = "arrow top" },
+				{ on = ["g", "h"], run = "cd ~" },
+				{ on = "x", run = "yank --cut" },
+				{ on = "x", run = "remove" },
+				{ on = ["z", "a"], run = "hidden toggle" },
+				{ on = ["z", "b"], run = "hidden toggle" },
+			]"#,
+		);
+
+		let mut doctor = Doctor::default();
+		doctor.conflicts_in("mgr".to_owned(), &chords);
+
+		let messages: Vec<_> = doctor.diagnostics.iter().map(|d| d.message.as_str()).collect();
+		assert_eq!(messages, [
+			"`gh` (cd ~) is never reached, since `g` (arrow top) comes first",
+			"`x` (remove) is never reached, since `x` (yank --cut) comes first",
+		]);
+	}
+
+	#[test]
+	fn test_shell_programs() {
+		let programs = |s| Doctor::shell_programs(s).collect::<Vec<_>>();
+
+		assert_eq!(programs(r#"xdg-open "$(dirname "$1")""#), ["xdg-open"]);
+		assert_eq!(programs(r#"clear; exiftool "$1"; read _"#), ["clear", "exiftool"]);
+		assert_eq!(programs(r#"${EDITOR:-vi} "$@""#), Vec::<&str>::new());
+		assert_eq!(programs(r#"foo 2>&1 | less"#), ["foo", "less"]);
+		assert_eq!(programs(r#"mediainfo "%1" & pause"#), ["mediainfo"]);
+	}
+}
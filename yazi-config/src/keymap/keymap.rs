@@ -4,18 +4,22 @@ use yazi_codegen::DeserializeOver1;
 use yazi_fs::{Xdg, ok_or_not_found};
 use yazi_shared::Layer;
 
-use super::{Chord, KeymapRules};
+use super::{Chord, KeymapMode, KeymapModes, KeymapRules};
 
 #[derive(Deserialize, DeserializeOver1)]
 pub struct Keymap {
 	pub mgr:     KeymapRules,
 	pub tasks:   KeymapRules,
+	pub notify:  KeymapRules,
 	pub spot:    KeymapRules,
 	pub pick:    KeymapRules,
 	pub input:   KeymapRules,
 	pub confirm: KeymapRules,
 	pub help:    KeymapRules,
 	pub cmp:     KeymapRules,
+
+	#[serde(default)]
+	pub mode: KeymapModes,
 }
 
 impl Keymap {
@@ -25,6 +29,7 @@ impl Keymap {
 			Layer::App => &[],
 			Layer::Mgr => &self.mgr,
 			Layer::Tasks => &self.tasks,
+			Layer::Notify => &self.notify,
 			Layer::Spot => &self.spot,
 			Layer::Pick => &self.pick,
 			Layer::Input => &self.input,
@@ -34,6 +39,9 @@ impl Keymap {
 			Layer::Which => &[],
 		}
 	}
+
+	#[inline]
+	pub fn mode(&self, name: &str) -> Option<&KeymapMode> { self.mode.get(name) }
 }
 
 impl Keymap {
@@ -47,12 +55,15 @@ impl Keymap {
 		Ok(Self {
 			mgr:     self.mgr.reshape(Layer::Mgr)?,
 			tasks:   self.tasks.reshape(Layer::Tasks)?,
+			notify:  self.notify.reshape(Layer::Notify)?,
 			spot:    self.spot.reshape(Layer::Spot)?,
 			pick:    self.pick.reshape(Layer::Pick)?,
 			input:   self.input.reshape(Layer::Input)?,
 			confirm: self.confirm.reshape(Layer::Confirm)?,
 			help:    self.help.reshape(Layer::Help)?,
 			cmp:     self.cmp.reshape(Layer::Cmp)?,
+
+			mode: self.mode.reshape()?,
 		})
 	}
 }
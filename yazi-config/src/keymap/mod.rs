@@ -1 +1 @@
-yazi_macro::mod_flat!(chord cow deserializers key keymap rules);
+yazi_macro::mod_flat!(chord cow deserializers key keymap mode rules);
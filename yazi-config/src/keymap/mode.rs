@@ -0,0 +1,69 @@
+use std::{collections::HashMap, ops::Deref};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Deserializer};
+use yazi_shared::Layer;
+
+use super::KeymapRules;
+
+/// The named keymap modes, pushed with `which:push <name>`.
+#[derive(Default, Deserialize)]
+#[serde(transparent)]
+pub struct KeymapModes(HashMap<String, KeymapMode>);
+
+#[derive(Deserialize)]
+pub struct KeymapMode {
+	/// Whether it stays after running a command, rather than after the first one.
+	#[serde(default)]
+	pub sticky: bool,
+	#[serde(flatten)]
+	pub rules:  KeymapRules,
+}
+
+impl Deref for KeymapModes {
+	type Target = HashMap<String, KeymapMode>;
+
+	fn deref(&self) -> &Self::Target { &self.0 }
+}
+
+impl KeymapModes {
+	/// The user's modes are added to the preset ones, replacing the same names.
+	pub(crate) fn deserialize_over<'de, D>(mut self, deserializer: D) -> Result<Self, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		self.0.extend(Self::deserialize(deserializer)?.0);
+		Ok(self)
+	}
+
+	pub(crate) fn reshape(self) -> Result<Self> {
+		let mut modes = HashMap::with_capacity(self.0.len());
+		for (name, mode) in self.0 {
+			let rules = mode.rules.reshape(Layer::Mgr).with_context(|| format!("Invalid mode `{name}`"))?;
+			modes.insert(name, KeymapMode { rules, ..mode });
+		}
+		Ok(Self(modes))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::Preset;
+
+	#[test]
+	fn test_modes() -> Result<()> {
+		let user = r#"
+			[mode.git]
+			keymap = [ { on = "s", run = "shell 'git status' --block" } ]
+		"#;
+		let keymap = Preset::keymap()?.deserialize_over(toml::Deserializer::parse(user)?)?.reshape()?;
+
+		assert!(keymap.mode("tabs").is_some_and(|m| m.sticky));
+		let git = keymap.mode("git").unwrap();
+		assert!(!git.sticky);
+		let cmd = &git.rules[0].run[0];
+		assert_eq!((cmd.layer, cmd.name.as_ref()), (Layer::Mgr, "shell"));
+		Ok(())
+	}
+}
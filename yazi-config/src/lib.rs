@@ -1,17 +1,18 @@
 #![allow(clippy::module_inception)]
 
-yazi_macro::mod_pub!(keymap mgr open opener plugin popup preview scheme tasks theme which);
+yazi_macro::mod_pub!(doctor keymap mgr open opener plugin policy popup preview scheme search tasks theme trash which);
 
 yazi_macro::mod_flat!(color icon layout pattern platform preset priority style yazi);
 
 use std::io::{Read, Write};
 
-use yazi_shared::{RoCell, SyncCell};
+use yazi_shared::{RoCell, SwapCell, SyncCell};
 use yazi_term::tty::TTY;
 
 pub static YAZI: RoCell<yazi::Yazi> = RoCell::new();
 pub static KEYMAP: RoCell<keymap::Keymap> = RoCell::new();
-pub static THEME: RoCell<theme::Theme> = RoCell::new();
+pub static THEME: SwapCell<theme::Theme> = SwapCell::new();
+pub static LIGHT: SyncCell<bool> = SyncCell::new(false);
 pub static LAYOUT: SyncCell<Layout> = SyncCell::new(Layout::default());
 
 pub fn init() -> anyhow::Result<()> {
@@ -23,40 +24,80 @@ pub fn init() -> anyhow::Result<()> {
 }
 
 fn try_init(merge: bool) -> anyhow::Result<()> {
-	let mut yazi = Preset::yazi()?;
-	let mut keymap = Preset::keymap()?;
+	let yazi = load_yazi(merge)?;
+	let keymap = load_keymap(merge)?;
+
+	YAZI.init(yazi);
+	KEYMAP.init(keymap);
+	Ok(())
+}
 
+pub(crate) fn load_yazi(merge: bool) -> anyhow::Result<yazi::Yazi> {
+	let mut yazi = Preset::yazi()?;
 	if merge {
 		yazi = yazi.deserialize_over(toml::Deserializer::parse(&yazi::Yazi::read()?)?)?;
-		keymap = keymap.deserialize_over(toml::Deserializer::parse(&keymap::Keymap::read()?)?)?;
 	}
+	yazi.reshape()
+}
 
-	YAZI.init(yazi.reshape()?);
-	KEYMAP.init(keymap.reshape()?);
-	Ok(())
+pub(crate) fn load_keymap(merge: bool) -> anyhow::Result<keymap::Keymap> {
+	let mut keymap = Preset::keymap()?;
+	if merge {
+		keymap = keymap.deserialize_over(toml::Deserializer::parse(&keymap::Keymap::read()?)?)?;
+	}
+	keymap.reshape()
 }
 
-pub fn init_flavor(light: bool) -> anyhow::Result<()> {
-	if let Err(e) = try_init_flavor(light, true) {
+pub fn init_flavor(light: impl Fn(theme::Appearance) -> bool) -> anyhow::Result<()> {
+	if let Err(e) = try_init_flavor(&light, true) {
 		wait_for_key(e)?;
-		try_init_flavor(light, false)?;
+		try_init_flavor(&light, false)?;
 	}
 	Ok(())
 }
 
-fn try_init_flavor(light: bool, merge: bool) -> anyhow::Result<()> {
-	let mut theme = Preset::theme(light)?;
+fn try_init_flavor(light: impl Fn(theme::Appearance) -> bool, merge: bool) -> anyhow::Result<()> {
+	let (theme, light) = load_theme(None, light, merge)?;
+	THEME.init(theme);
+	LIGHT.set(light);
+	Ok(())
+}
 
-	if merge {
-		let shadow =
-			theme::Theme::deserialize_shadow(toml::Deserializer::parse(&theme::Theme::read()?)?)?;
-		let flavor = shadow.flavor.as_ref().map(theme::Flavor::from).unwrap_or_default().read(light)?;
-		theme = theme.deserialize_over(toml::Deserializer::parse(&flavor)?)?;
+/// Switches the theme at runtime to `flavor`, or to the configured one if `None`.
+pub fn switch_flavor(
+	flavor: Option<theme::Flavor>,
+	light: impl FnOnce(theme::Appearance) -> bool,
+) -> anyhow::Result<()> {
+	let (theme, light) = load_theme(flavor, light, true)?;
+	THEME.set(theme);
+	LIGHT.set(light);
+	Ok(())
+}
+
+pub(crate) fn load_theme(
+	flavor: Option<theme::Flavor>,
+	light: impl FnOnce(theme::Appearance) -> bool,
+	merge: bool,
+) -> anyhow::Result<(theme::Theme, bool)> {
+	let shadow = if merge {
+		Some(theme::Theme::deserialize_shadow(toml::Deserializer::parse(&theme::Theme::read()?)?)?)
+	} else {
+		None
+	};
+
+	let flavor = flavor
+		.or_else(|| shadow.as_ref()?.flavor.as_ref().map(theme::Flavor::from))
+		.unwrap_or_default();
+	let light = light(flavor.appearance);
+
+	let mut theme = Preset::theme(light)?;
+	if let Some(shadow) = shadow {
+		theme = theme.deserialize_over(toml::Deserializer::parse(&flavor.read(light)?)?)?;
 		theme = theme.deserialize_over_with::<toml::Value>(shadow)?;
 	}
 
-	THEME.init(theme.reshape(light)?);
-	Ok(())
+	theme.flavor = flavor;
+	Ok((theme.reshape(light)?, light))
 }
 
 fn wait_for_key(e: anyhow::Error) -> anyhow::Result<()> {
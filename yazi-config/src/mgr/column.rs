@@ -0,0 +1,71 @@
+use serde::Deserialize;
+use yazi_shared::{MIME_DIR, url::Url};
+
+use crate::Pattern;
+
+/// An extra column of the file list, filled by the linemode of its name.
+#[derive(Debug, Deserialize)]
+pub struct Column {
+	pub name:  String,
+	pub width: u16,
+	#[serde(default)]
+	pub align: ColumnAlign,
+	pub url:   Option<Pattern>,
+	pub mime:  Option<Pattern>,
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ColumnAlign {
+	#[default]
+	Left,
+	Center,
+	Right,
+}
+
+impl Column {
+	pub fn matches(&self, url: &Url, mime: &str) -> bool {
+		if self.url.is_none() && self.mime.is_none() {
+			return true;
+		}
+
+		self.mime.as_ref().is_some_and(|p| p.match_mime(mime))
+			|| self.url.as_ref().is_some_and(|p| p.match_url(url, mime == MIME_DIR))
+	}
+}
+
+impl ColumnAlign {
+	pub const fn as_str(self) -> &'static str {
+		match self {
+			Self::Left => "left",
+			Self::Center => "center",
+			Self::Right => "right",
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::path::Path;
+
+	use super::*;
+
+	#[test]
+	fn test_matches() {
+		let column = |s: &str| toml::from_str::<Column>(s).unwrap();
+		let url = Url::from(Path::new("/tmp/a.rs"));
+
+		let all = column("name = 'owner'\nwidth = 10");
+		assert_eq!(all.align, ColumnAlign::Left);
+		assert!(all.matches(&url, "text/rust"));
+
+		let rust = column("name = 'git'\nwidth = 2\nalign = 'right'\nurl = '*.rs'");
+		assert_eq!(rust.align, ColumnAlign::Right);
+		assert!(rust.matches(&url, "text/rust"));
+		assert!(!rust.matches(&Url::from(Path::new("/tmp/a.md")), "text/markdown"));
+
+		let dirs = column("name = 'size'\nwidth = 6\nmime = 'inode/directory'");
+		assert!(dirs.matches(&Url::from(Path::new("/tmp")), MIME_DIR));
+		assert!(!dirs.matches(&url, "text/rust"));
+	}
+}
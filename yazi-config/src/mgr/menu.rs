@@ -0,0 +1,40 @@
+use std::str::FromStr;
+
+use anyhow::Result;
+use serde::Deserialize;
+use yazi_shared::{Layer, event::Cmd};
+
+/// An item of the context menu, shown by right-clicking a file.
+#[derive(Debug, Deserialize)]
+pub struct MenuItem {
+	pub run:  String,
+	pub desc: String,
+}
+
+impl MenuItem {
+	pub fn cmd(&self) -> Result<Cmd> {
+		let mut cmd = Cmd::from_str(&self.run)?;
+		if cmd.layer == Layer::App {
+			cmd.layer = Layer::Mgr;
+		}
+		Ok(cmd)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_cmd() {
+		let item = |run: &str| MenuItem { run: run.to_owned(), desc: String::new() };
+
+		let cmd = item("open --interactive").cmd().unwrap();
+		assert_eq!((cmd.layer, cmd.name.as_ref()), (Layer::Mgr, "open"));
+		assert!(cmd.bool("interactive"));
+
+		let cmd = item("tasks:show").cmd().unwrap();
+		assert_eq!((cmd.layer, cmd.name.as_ref()), (Layer::Tasks, "show"));
+		assert!(item("").cmd().is_err());
+	}
+}
@@ -1,10 +1,12 @@
-use anyhow::{Result, bail};
+use std::path::PathBuf;
+
+use anyhow::{Context, Result, bail};
 use serde::Deserialize;
 use yazi_codegen::DeserializeOver2;
-use yazi_fs::{CWD, SortBy};
+use yazi_fs::{CWD, SortBy, path::expand_path};
 use yazi_shared::{SyncCell, url::Url};
 
-use super::{MgrRatio, MouseEvents};
+use super::{Column, MenuItem, MgrRatio, MouseEvents};
 
 #[derive(Debug, Deserialize, DeserializeOver2)]
 pub struct Mgr {
@@ -24,6 +26,24 @@ pub struct Mgr {
 	pub scrolloff:    SyncCell<u8>,
 	pub mouse_events: SyncCell<MouseEvents>,
 	pub title_format: String,
+	pub columns:      Vec<Column>,
+	pub menu:         Vec<MenuItem>,
+	pub accessible:   bool,
+
+	// Yanking
+	pub yank_threshold: usize,
+	pub sync_yanked:    bool,
+
+	// Session
+	pub save_session:   bool,
+	pub remember_views: bool,
+
+	// Reporting
+	pub report_cwd: bool,
+	pub cwd_hook:   PathBuf,
+
+	// Polling
+	pub poll_interval: u16,
 }
 
 impl Mgr {
@@ -42,10 +62,25 @@ impl Mgr {
 		Some(self.title_format.replace("{cwd}", &cwd))
 	}
 
-	pub(crate) fn reshape(self) -> Result<Self> {
+	/// The extra columns shown for the file, in the order they're defined.
+	pub fn columns<'a>(&'a self, url: &'a Url, mime: &'a str) -> impl Iterator<Item = &'a Column> {
+		self.columns.iter().filter(move |c| c.matches(url, mime))
+	}
+
+	pub(crate) fn reshape(mut self) -> Result<Self> {
 		if self.linemode.is_empty() || self.linemode.len() > 20 {
 			bail!("[mgr].linemode must be between 1 and 20 characters.");
 		}
+		if let Some(c) = self.columns.iter().find(|c| c.name.is_empty() || c.width == 0) {
+			bail!("[mgr].columns must each have a name and a non-zero width: {:?}", c.name);
+		}
+		for item in &self.menu {
+			item.cmd().with_context(|| format!("Invalid command in [mgr].menu: {:?}", item.run))?;
+		}
+
+		if !self.cwd_hook.as_os_str().is_empty() {
+			self.cwd_hook = expand_path(&self.cwd_hook);
+		}
 
 		Ok(self)
 	}
@@ -10,11 +10,12 @@ use crate::{Preset, open::OpenRule};
 
 #[derive(Default, Deserialize, DeserializeOver2)]
 pub struct Open {
-	rules:         Vec<OpenRule>,
+	rules:           Vec<OpenRule>,
 	#[serde(default)]
-	prepend_rules: Vec<OpenRule>,
+	prepend_rules:   Vec<OpenRule>,
 	#[serde(default)]
-	append_rules:  Vec<OpenRule>,
+	append_rules:    Vec<OpenRule>,
+	pub system_apps: bool,
 }
 
 impl Deref for Open {
@@ -65,6 +66,7 @@ impl Open {
 
 		Ok(Self {
 			rules: Preset::mix(self.prepend_rules, it, self.append_rules).collect(),
+			system_apps: self.system_apps,
 			..Default::default()
 		})
 	}
@@ -1,5 +1,6 @@
 use anyhow::{Result, bail};
 use serde::Deserialize;
+use yazi_fs::apps::App;
 
 #[derive(Clone, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct OpenerRule {
@@ -34,15 +35,37 @@ impl OpenerRule {
 			bail!("[open].rules.*.run cannot be empty.");
 		}
 
+		self.spread = Self::spread(&self.run);
+		Ok(self)
+	}
+
+	fn spread(run: &str) -> bool {
 		#[cfg(unix)]
 		{
-			self.spread = self.run.contains("$@") || self.run.contains("$*");
+			run.contains("$@") || run.contains("$*")
 		}
 		#[cfg(windows)]
 		{
-			self.spread = self.run.contains("%*");
+			run.contains("%*")
+		}
+	}
+}
+
+impl From<App> for OpenerRule {
+	fn from(app: App) -> Self {
+		Self {
+			spread: Self::spread(&app.run),
+			run:    app.run,
+			block:  app.block,
+			orphan: app.orphan,
+			desc:   app.name,
+			r#for:  None,
 		}
+	}
+}
 
-		Ok(self)
+impl From<&OpenerRule> for App {
+	fn from(rule: &OpenerRule) -> Self {
+		Self { name: rule.desc(), run: rule.run.clone(), block: rule.block, orphan: rule.orphan }
 	}
 }
@@ -1,3 +1,5 @@
+use std::{env, path::Path};
+
 #[inline]
 pub(crate) fn check_for(r#for: Option<&str>) -> bool {
 	match r#for.as_ref().map(|s| s.as_ref()) {
@@ -7,3 +9,21 @@ pub(crate) fn check_for(r#for: Option<&str>) -> bool {
 		None => true,
 	}
 }
+
+/// Whether the program can be found, either as a path or in `PATH`.
+pub(crate) fn installed(program: &str) -> bool {
+	let program = Path::new(program);
+	if program.components().count() > 1 {
+		return program.is_file();
+	}
+
+	let Some(paths) = env::var_os("PATH") else { return false };
+	env::split_paths(&paths).any(|dir| {
+		let p = dir.join(program);
+		#[cfg(windows)]
+		if ["exe", "cmd", "bat"].iter().any(|ext| p.with_extension(ext).is_file()) {
+			return true;
+		}
+		p.is_file()
+	})
+}
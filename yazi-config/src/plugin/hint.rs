@@ -0,0 +1,48 @@
+use std::num::NonZeroUsize;
+
+use serde::Deserialize;
+
+/// Scheduling hints a fetcher, preloader or previewer declares.
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct Hint {
+	/// Maximum number of concurrent runs of the plugin.
+	pub concurrency: Option<NonZeroUsize>,
+	/// Plugins in the same group never run at the same time.
+	pub group:       Option<String>,
+	/// Estimated memory usage of a single run, in bytes.
+	#[serde(default)]
+	pub memory:      u64,
+}
+
+impl Hint {
+	#[inline]
+	pub fn is_empty(&self) -> bool {
+		self.concurrency.is_none() && self.group.is_none() && self.memory == 0
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::plugin::Previewer;
+
+	#[test]
+	fn test_flatten() {
+		let p: Previewer = toml::from_str(
+			r#"
+			mime        = "application/pdf"
+			run         = "pdf"
+			concurrency = 1
+			group       = "poppler"
+			memory      = 268435456
+			"#,
+		)
+		.unwrap();
+		assert_eq!(p.hint.concurrency, NonZeroUsize::new(1));
+		assert_eq!(p.hint.group.as_deref(), Some("poppler"));
+		assert_eq!(p.hint.memory, 256 << 20);
+
+		let p: Previewer = toml::from_str("mime = 'text/*'\nrun = 'code'").unwrap();
+		assert!(p.hint.is_empty());
+	}
+}
@@ -7,7 +7,7 @@ use yazi_codegen::DeserializeOver2;
 use yazi_fs::File;
 use yazi_shared::url::Url;
 
-use super::{Fetcher, Preloader, Previewer, Spotter};
+use super::{Fetcher, Preloader, Previewer, Service, Spotter};
 use crate::{Preset, plugin::MAX_PREWORKERS};
 
 #[derive(Default, Deserialize, DeserializeOver2)]
@@ -35,6 +35,8 @@ pub struct Plugin {
 	prepend_previewers: Vec<Previewer>,
 	#[serde(default)]
 	append_previewers:  Vec<Previewer>,
+
+	pub services: Vec<Service>,
 }
 
 impl Plugin {
@@ -134,6 +136,7 @@ impl Plugin {
 			spotters: self.spotters,
 			preloaders: self.preloaders,
 			previewers: self.previewers,
+			services: self.services,
 			..Default::default()
 		})
 	}
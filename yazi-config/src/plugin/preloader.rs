@@ -1,6 +1,7 @@
 use serde::Deserialize;
 use yazi_shared::{MIME_DIR, event::Cmd, url::Url};
 
+use super::Hint;
 use crate::{Pattern, Priority};
 
 #[derive(Debug, Deserialize)]
@@ -15,6 +16,8 @@ pub struct Preloader {
 	pub next: bool,
 	#[serde(default)]
 	pub prio: Priority,
+	#[serde(flatten)]
+	pub hint: Hint,
 }
 
 impl Preloader {
@@ -1,13 +1,18 @@
 use serde::Deserialize;
 use yazi_shared::{MIME_DIR, event::Cmd, url::Url};
 
+use super::Hint;
 use crate::Pattern;
 
 #[derive(Debug, Deserialize)]
 pub struct Previewer {
-	pub url:  Option<Pattern>,
-	pub mime: Option<Pattern>,
-	pub run:  Cmd,
+	pub url:     Option<Pattern>,
+	pub mime:    Option<Pattern>,
+	pub run:     Cmd,
+	/// Seconds before the previewer is killed, where `0` means no limit.
+	pub timeout: Option<u16>,
+	#[serde(flatten)]
+	pub hint:    Hint,
 }
 
 impl Previewer {
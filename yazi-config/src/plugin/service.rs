@@ -0,0 +1,8 @@
+use serde::Deserialize;
+use yazi_shared::event::Cmd;
+
+/// A plugin run in the background for as long as Yazi is running.
+#[derive(Debug, Deserialize)]
+pub struct Service {
+	pub run: Cmd,
+}
@@ -0,0 +1,88 @@
+use anyhow::{Result, bail};
+use serde::Deserialize;
+use yazi_codegen::DeserializeOver2;
+use yazi_shared::url::{Scheme, Url};
+
+use super::{PolicyAnswer, PolicyOp, PolicyRule};
+use crate::Preset;
+
+#[derive(Default, Deserialize, DeserializeOver2)]
+pub struct Policy {
+	pub profile:   String,
+	rules:         Vec<PolicyRule>,
+	#[serde(default)]
+	prepend_rules: Vec<PolicyRule>,
+	#[serde(default)]
+	append_rules:  Vec<PolicyRule>,
+}
+
+impl Policy {
+	/// The answer of the first rule of the active profile that matches.
+	pub fn answer(&self, op: PolicyOp, n: usize, urls: &[Url]) -> PolicyAnswer {
+		self
+			.rules
+			.iter()
+			.find(|r| {
+				(r.on == op || r.on == PolicyOp::Any)
+					&& r.profile.as_ref().is_none_or(|p| *p == self.profile)
+					&& r.below.is_none_or(|b| n < b)
+					&& r
+						.scheme
+						.as_ref()
+						.is_none_or(|s| !urls.is_empty() && urls.iter().all(|u| u.scheme.kind() == s))
+			})
+			.map_or(PolicyAnswer::Ask, |r| r.answer)
+	}
+
+	pub(crate) fn reshape(self) -> Result<Self> {
+		let rules: Vec<_> = Preset::mix(self.prepend_rules, self.rules, self.append_rules).collect();
+		for r in &rules {
+			if r.answer == PolicyAnswer::Trash && r.on != PolicyOp::Delete {
+				bail!("The `trash` answer of the policy is only for `delete`, not {:?}", r.on);
+			} else if let Some(s) = &r.scheme {
+				Scheme::parse_kind(s.as_bytes())?;
+			}
+		}
+
+		// The profile can be picked for a run with `YAZI_POLICY`, such as the
+		// `headless` one for scripts
+		let profile = std::env::var("YAZI_POLICY").ok().filter(|s| !s.is_empty());
+		Ok(Self { profile: profile.unwrap_or(self.profile), rules, ..Default::default() })
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_answer() {
+		let policy: Policy = toml::from_str(
+			r#"
+			profile = "default"
+			rules = [
+				{ on = "delete", scheme = "sftp", answer = "trash" },
+				{ on = "trash", below = 3, answer = "yes" },
+				{ on = "overwrite", answer = "no", profile = "headless" },
+				{ on = "*", scheme = "regular", answer = "ask" },
+				{ on = "*", answer = "no", profile = "default" },
+			]
+			"#,
+		)
+		.unwrap();
+
+		let urls = |s: &[&str]| s.iter().map(|s| s.parse().unwrap()).collect::<Vec<Url>>();
+		let (local, remote) = (urls(&["/a", "/b"]), urls(&["sftp://host//a"]));
+
+		assert_eq!(policy.answer(PolicyOp::Delete, 1, &remote), PolicyAnswer::Trash);
+		assert_eq!(policy.answer(PolicyOp::Delete, 2, &local), PolicyAnswer::Ask);
+		assert_eq!(policy.answer(PolicyOp::Trash, 2, &local), PolicyAnswer::Yes);
+		assert_eq!(policy.answer(PolicyOp::Trash, 3, &local), PolicyAnswer::Ask);
+		assert_eq!(policy.answer(PolicyOp::Overwrite, 1, &remote), PolicyAnswer::No);
+		assert_eq!(policy.answer(PolicyOp::Quit, 1, &[]), PolicyAnswer::No);
+
+		let headless = Policy { profile: "headless".to_owned(), ..policy };
+		assert_eq!(headless.answer(PolicyOp::Overwrite, 1, &remote), PolicyAnswer::No);
+		assert_eq!(headless.answer(PolicyOp::Quit, 1, &[]), PolicyAnswer::Ask);
+	}
+}
@@ -0,0 +1,48 @@
+use serde::Deserialize;
+
+/// A predefined answer to the confirmations of an operation.
+#[derive(Debug, Deserialize)]
+pub struct PolicyRule {
+	pub on:      PolicyOp,
+	/// The kind of scheme all the files are in, such as `sftp`.
+	pub scheme:  Option<String>,
+	/// Applies only to fewer files, or tasks when quitting, than this.
+	pub below:   Option<usize>,
+	pub answer:  PolicyAnswer,
+	/// Applies only to the profile of this name, or to all if not given.
+	pub profile: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum PolicyOp {
+	#[serde(rename = "*")]
+	Any,
+	Trash,
+	Delete,
+	Overwrite,
+	Purge,
+	Quit,
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum PolicyAnswer {
+	#[default]
+	Ask,
+	Yes,
+	No,
+	/// Trashes the files instead, for `delete` only.
+	Trash,
+}
+
+impl PolicyAnswer {
+	/// The answer to the confirmation, or `None` if it has to be asked.
+	pub fn decided(self) -> Option<bool> {
+		match self {
+			Self::Ask | Self::Trash => None,
+			Self::Yes => Some(true),
+			Self::No => Some(false),
+		}
+	}
+}
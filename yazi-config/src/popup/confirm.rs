@@ -22,6 +22,12 @@ pub struct Confirm {
 	pub overwrite_origin: Origin,
 	pub overwrite_offset: Offset,
 
+	// purge
+	pub purge_title:  String,
+	pub purge_body:   String,
+	pub purge_origin: Origin,
+	pub purge_offset: Offset,
+
 	// quit
 	pub quit_title:  String,
 	pub quit_body:   String,
@@ -44,6 +50,10 @@ impl Confirm {
 		Position::new(self.overwrite_origin, self.overwrite_offset)
 	}
 
+	pub const fn purge_position(&self) -> Position {
+		Position::new(self.purge_origin, self.purge_offset)
+	}
+
 	pub const fn quit_position(&self) -> Position {
 		Position::new(self.quit_origin, self.quit_offset)
 	}
@@ -22,6 +22,31 @@ pub struct Input {
 	pub rename_origin: Origin,
 	pub rename_offset: Offset,
 
+	// chmod
+	pub chmod_title:  String,
+	pub chmod_origin: Origin,
+	pub chmod_offset: Offset,
+
+	// chown
+	pub chown_title:  String,
+	pub chown_origin: Origin,
+	pub chown_offset: Offset,
+
+	// relink
+	pub relink_title:  String,
+	pub relink_origin: Origin,
+	pub relink_offset: Offset,
+
+	// paste
+	pub paste_title:  [String; 2],
+	pub paste_origin: Origin,
+	pub paste_offset: Offset,
+
+	// schedule
+	pub schedule_title:  String,
+	pub schedule_origin: Origin,
+	pub schedule_offset: Offset,
+
 	// filter
 	pub filter_title:  String,
 	pub filter_origin: Origin,
@@ -41,6 +66,21 @@ pub struct Input {
 	pub shell_title:  [String; 2],
 	pub shell_origin: Origin,
 	pub shell_offset: Offset,
+
+	// palette
+	pub palette_title:  String,
+	pub palette_origin: Origin,
+	pub palette_offset: Offset,
+
+	// password
+	pub password_title:  String,
+	pub password_origin: Origin,
+	pub password_offset: Offset,
+
+	// download
+	pub download_title:  String,
+	pub download_origin: Origin,
+	pub download_offset: Offset,
 }
 
 impl Input {
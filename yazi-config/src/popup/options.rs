@@ -2,7 +2,7 @@ use ratatui::{text::{Line, Text}, widgets::{Paragraph, Wrap}};
 use yazi_shared::{IntoStringLossy, url::Url};
 
 use super::{Offset, Position};
-use crate::YAZI;
+use crate::{YAZI, policy::PolicyOp};
 
 #[derive(Debug, Default)]
 pub struct InputCfg {
@@ -28,6 +28,8 @@ pub struct ConfirmCfg {
 	pub title:    Line<'static>,
 	pub body:     Paragraph<'static>,
 	pub list:     Paragraph<'static>,
+	/// The answer predefined by the policy, without showing it.
+	pub answer:   Option<bool>,
 }
 
 impl InputCfg {
@@ -56,6 +58,46 @@ impl InputCfg {
 		}
 	}
 
+	pub fn chmod(n: usize) -> Self {
+		Self {
+			title: YAZI.input.chmod_title.replace("{n}", &n.to_string()),
+			position: Position::new(YAZI.input.chmod_origin, YAZI.input.chmod_offset),
+			..Default::default()
+		}
+	}
+
+	pub fn chown(n: usize) -> Self {
+		Self {
+			title: YAZI.input.chown_title.replace("{n}", &n.to_string()),
+			position: Position::new(YAZI.input.chown_origin, YAZI.input.chown_offset),
+			..Default::default()
+		}
+	}
+
+	pub fn relink() -> Self {
+		Self {
+			title: YAZI.input.relink_title.to_owned(),
+			position: Position::new(YAZI.input.relink_origin, YAZI.input.relink_offset),
+			..Default::default()
+		}
+	}
+
+	pub fn paste(archive: bool) -> Self {
+		Self {
+			title: YAZI.input.paste_title[archive as usize].to_owned(),
+			position: Position::new(YAZI.input.paste_origin, YAZI.input.paste_offset),
+			..Default::default()
+		}
+	}
+
+	pub fn schedule() -> Self {
+		Self {
+			title: YAZI.input.schedule_title.to_owned(),
+			position: Position::new(YAZI.input.schedule_origin, YAZI.input.schedule_offset),
+			..Default::default()
+		}
+	}
+
 	pub fn filter() -> Self {
 		Self {
 			title: YAZI.input.filter_title.to_owned(),
@@ -90,6 +132,32 @@ impl InputCfg {
 		}
 	}
 
+	pub fn palette(run: &str) -> Self {
+		Self {
+			title: YAZI.input.palette_title.to_owned(),
+			value: format!("{run} "),
+			position: Position::new(YAZI.input.palette_origin, YAZI.input.palette_offset),
+			..Default::default()
+		}
+	}
+
+	pub fn password(name: &str) -> Self {
+		Self {
+			title: YAZI.input.password_title.replace("{name}", name),
+			position: Position::new(YAZI.input.password_origin, YAZI.input.password_offset),
+			obscure: true,
+			..Default::default()
+		}
+	}
+
+	pub fn download() -> Self {
+		Self {
+			title: YAZI.input.download_title.to_owned(),
+			position: Position::new(YAZI.input.download_origin, YAZI.input.download_offset),
+			..Default::default()
+		}
+	}
+
 	#[inline]
 	pub fn with_value(mut self, value: impl Into<String>) -> Self {
 		self.value = value.into();
@@ -111,6 +179,7 @@ impl ConfirmCfg {
 		list: Option<Text<'static>>,
 	) -> Self {
 		Self {
+			answer: None,
 			position,
 			title: Line::raw(title),
 			body: body.map(|b| Paragraph::new(b).wrap(Wrap { trim: false })).unwrap_or_default(),
@@ -125,6 +194,7 @@ impl ConfirmCfg {
 			None,
 			Self::truncate_list(urls.iter(), urls.len(), 100),
 		)
+		.with_answer(PolicyOp::Trash, urls.len(), urls)
 	}
 
 	pub fn delete(urls: &[yazi_shared::url::Url]) -> Self {
@@ -134,6 +204,7 @@ impl ConfirmCfg {
 			None,
 			Self::truncate_list(urls.iter(), urls.len(), 100),
 		)
+		.with_answer(PolicyOp::Delete, urls.len(), urls)
 	}
 
 	pub fn overwrite(url: &Url) -> Self {
@@ -143,6 +214,18 @@ impl ConfirmCfg {
 			Some(Text::raw(&YAZI.confirm.overwrite_body)),
 			Some(url.into_string_lossy().into()),
 		)
+		.with_answer(PolicyOp::Overwrite, 1, std::slice::from_ref(url))
+	}
+
+	pub fn purge(names: Vec<String>) -> Self {
+		let len = names.len();
+		Self::new(
+			Self::replace_number(&YAZI.confirm.purge_title, len),
+			YAZI.confirm.purge_position(),
+			Some(Text::raw(&YAZI.confirm.purge_body)),
+			Self::truncate_list(names.into_iter(), len, 100),
+		)
+		.with_answer(PolicyOp::Purge, len, &[])
 	}
 
 	pub fn quit(len: usize, names: Vec<String>) -> Self {
@@ -152,6 +235,12 @@ impl ConfirmCfg {
 			Some(Text::raw(&YAZI.confirm.quit_body)),
 			Self::truncate_list(names.into_iter(), len, 10),
 		)
+		.with_answer(PolicyOp::Quit, len, &[])
+	}
+
+	fn with_answer(mut self, op: PolicyOp, n: usize, urls: &[Url]) -> Self {
+		self.answer = YAZI.policy.answer(op, n, urls).decided();
+		self
 	}
 
 	fn replace_number(tpl: &str, n: usize) -> String {
@@ -177,12 +266,12 @@ impl ConfirmCfg {
 
 impl PickCfg {
 	#[inline]
-	fn max_height(len: usize) -> u16 {
-		YAZI.pick.open_offset.height.min(YAZI.pick.border().saturating_add(len as u16))
+	fn max_height(offset: &Offset, len: usize) -> u16 {
+		offset.height.min(YAZI.pick.border().saturating_add(len as u16))
 	}
 
 	pub fn open(items: Vec<String>) -> Self {
-		let max_height = Self::max_height(items.len());
+		let max_height = Self::max_height(&YAZI.pick.open_offset, items.len());
 		Self {
 			title: YAZI.pick.open_title.to_owned(),
 			items,
@@ -192,4 +281,64 @@ impl PickCfg {
 			}),
 		}
 	}
+
+	pub fn restore(items: Vec<String>) -> Self {
+		let max_height = Self::max_height(&YAZI.pick.restore_offset, items.len());
+		Self {
+			title: YAZI.pick.restore_title.to_owned(),
+			items,
+			position: Position::new(YAZI.pick.restore_origin, Offset {
+				height: max_height,
+				..YAZI.pick.restore_offset
+			}),
+		}
+	}
+
+	pub fn conflict(name: &str, items: Vec<String>) -> Self {
+		let max_height = Self::max_height(&YAZI.pick.conflict_offset, items.len());
+		Self {
+			title: YAZI.pick.conflict_title.replace("{name}", name),
+			items,
+			position: Position::new(YAZI.pick.conflict_origin, Offset {
+				height: max_height,
+				..YAZI.pick.conflict_offset
+			}),
+		}
+	}
+
+	pub fn schedule(n: usize, items: Vec<String>) -> Self {
+		let max_height = Self::max_height(&YAZI.pick.schedule_offset, items.len());
+		Self {
+			title: ConfirmCfg::replace_number(&YAZI.pick.schedule_title, n),
+			items,
+			position: Position::new(YAZI.pick.schedule_origin, Offset {
+				height: max_height,
+				..YAZI.pick.schedule_offset
+			}),
+		}
+	}
+
+	pub fn menu(n: usize, items: Vec<String>) -> Self {
+		let max_height = Self::max_height(&YAZI.pick.menu_offset, items.len());
+		Self {
+			title: ConfirmCfg::replace_number(&YAZI.pick.menu_title, n),
+			items,
+			position: Position::new(YAZI.pick.menu_origin, Offset {
+				height: max_height,
+				..YAZI.pick.menu_offset
+			}),
+		}
+	}
+
+	pub fn bookmark(items: Vec<String>) -> Self {
+		let max_height = Self::max_height(&YAZI.pick.bookmark_offset, items.len());
+		Self {
+			title: YAZI.pick.bookmark_title.to_owned(),
+			items,
+			position: Position::new(YAZI.pick.bookmark_origin, Offset {
+				height: max_height,
+				..YAZI.pick.bookmark_offset
+			}),
+		}
+	}
 }
@@ -9,6 +9,31 @@ pub struct Pick {
 	pub open_title:  String,
 	pub open_origin: Origin,
 	pub open_offset: Offset,
+
+	// restore
+	pub restore_title:  String,
+	pub restore_origin: Origin,
+	pub restore_offset: Offset,
+
+	// conflict
+	pub conflict_title:  String,
+	pub conflict_origin: Origin,
+	pub conflict_offset: Offset,
+
+	// schedule
+	pub schedule_title:  String,
+	pub schedule_origin: Origin,
+	pub schedule_offset: Offset,
+
+	// menu
+	pub menu_title:  String,
+	pub menu_origin: Origin,
+	pub menu_offset: Offset,
+
+	// bookmark
+	pub bookmark_title:  String,
+	pub bookmark_origin: Origin,
+	pub bookmark_offset: Offset,
 }
 
 impl Pick {
@@ -1,4 +1,4 @@
-use std::{borrow::Cow, path::PathBuf};
+use std::{borrow::Cow, collections::HashMap, path::PathBuf};
 
 use anyhow::{Context, Result, bail};
 use serde::{Deserialize, Serialize};
@@ -19,13 +19,25 @@ pub struct Preview {
 	pub max_height: u32,
 
 	pub cache_dir: PathBuf,
+	pub timeout:   u16,
+
+	pub cpu_limit:    u16,
+	pub memory_limit: u64,
+	pub sandbox:      Vec<String>,
 
 	pub image_delay:   u8,
 	pub image_filter:  String,
 	pub image_quality: u8,
+	pub image_memory:  u32,
+
+	pub image_protocols: HashMap<String, String>,
+
+	pub video_frames: u8,
 
 	pub ueberzug_scale:  f32,
 	pub ueberzug_offset: (f32, f32, f32, f32),
+
+	pub text_fallback: bool,
 }
 
 impl Preview {
@@ -49,6 +61,10 @@ impl Preview {
 			bail!("[preview].image_delay must be between 0 and 100.");
 		} else if self.image_quality < 50 || self.image_quality > 90 {
 			bail!("[preview].image_quality must be between 50 and 90.");
+		} else if self.video_frames == 0 || self.video_frames > 100 {
+			bail!("[preview].video_frames must be between 1 and 100.");
+		} else if self.sandbox.first().is_some_and(|s| s.is_empty()) {
+			bail!("[preview].sandbox must start with the program to run.");
 		}
 
 		self.cache_dir = if self.cache_dir.as_os_str().is_empty() {
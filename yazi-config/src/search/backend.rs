@@ -0,0 +1,100 @@
+use std::{ffi::OsString, path::Path};
+
+use anyhow::{Result, bail};
+use serde::Deserialize;
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum SearchMode {
+	#[default]
+	Name,
+	Content,
+}
+
+impl SearchMode {
+	#[inline]
+	pub fn as_str(self) -> &'static str {
+		match self {
+			Self::Name => "name",
+			Self::Content => "content",
+		}
+	}
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct SearchBackend {
+	pub run:       Vec<String>,
+	pub mode:      SearchMode,
+	#[serde(default)]
+	pub hidden:    Vec<String>,
+	#[serde(default)]
+	pub no_hidden: Vec<String>,
+	#[serde(default)]
+	pub nul:       bool,
+	#[serde(default)]
+	pub lines:     bool,
+	pub r#for:     Option<String>,
+}
+
+impl SearchBackend {
+	pub const BUILTIN: &str = "builtin";
+	/// The index of the file names under `[search].index`, see `yazi_fs::Index`.
+	pub const INDEX: &str = "index";
+
+	/// Whether the program of this backend can be found.
+	#[inline]
+	pub fn available(&self) -> bool { crate::installed(&self.run[0]) }
+
+	/// Expands the placeholders `%s`, `%d`, `%h` and `%a` in `run` into the arguments.
+	pub fn args(&self, subject: &str, dir: &Path, hidden: bool, args: &[String]) -> Vec<OsString> {
+		let mut v = Vec::with_capacity(self.run.len() + args.len());
+		for s in &self.run[1..] {
+			match s.as_str() {
+				"%s" => v.push(subject.into()),
+				"%d" => v.push(dir.into()),
+				"%h" if hidden => v.extend(self.hidden.iter().map(Into::into)),
+				"%h" => v.extend(self.no_hidden.iter().map(Into::into)),
+				"%a" => v.extend(args.iter().map(Into::into)),
+				_ => v.push(s.into()),
+			}
+		}
+		v
+	}
+}
+
+impl SearchBackend {
+	pub(super) fn reshape(&self, name: &str) -> Result<()> {
+		if self.run.is_empty() || self.run[0].is_empty() {
+			bail!("[search].backends.{name}.run cannot be empty.");
+		} else if !self.run[1..].iter().any(|s| s == "%s") {
+			bail!("[search].backends.{name}.run must contain the `%s` placeholder.");
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_args() {
+		let backend = SearchBackend {
+			run:       ["fd", "--base-directory", "%d", "%h", "%a", "%s"].map(Into::into).to_vec(),
+			mode:      SearchMode::Name,
+			hidden:    vec!["--hidden".to_owned()],
+			no_hidden: vec![],
+			nul:       false,
+			lines:     false,
+			r#for:     None,
+		};
+
+		let args = |hidden, extra: &[&str]| {
+			let extra: Vec<_> = extra.iter().map(|&s| s.to_owned()).collect();
+			backend.args("foo bar", Path::new("/tmp/x y"), hidden, &extra)
+		};
+
+		assert_eq!(args(true, &[]), ["--base-directory", "/tmp/x y", "--hidden", "foo bar"]);
+		assert_eq!(args(false, &["-e", "rs"]), ["--base-directory", "/tmp/x y", "-e", "rs", "foo bar"]);
+	}
+}
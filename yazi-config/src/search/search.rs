@@ -0,0 +1,95 @@
+use std::{collections::HashMap, path::PathBuf};
+
+use anyhow::{Result, bail};
+use serde::Deserialize;
+use yazi_fs::path::expand_path;
+
+use super::{SearchBackend, SearchMode};
+
+#[derive(Debug, Deserialize)]
+pub struct Search {
+	pub name:     Vec<String>,
+	pub content:  Vec<String>,
+	#[serde(default)]
+	pub index:    Vec<PathBuf>,
+	pub backends: HashMap<String, SearchBackend>,
+}
+
+impl Search {
+	/// Picks the backend to run for `via`, a mode or the name of a backend.
+	pub fn pick<'a>(&'a self, via: &'a str) -> (SearchMode, &'a str) {
+		let mode = match via {
+			"name" => SearchMode::Name,
+			"content" => SearchMode::Content,
+			SearchBackend::BUILTIN | SearchBackend::INDEX => return (SearchMode::Name, via),
+			_ => match self.backends.get(via) {
+				Some(b) if b.available() => return (b.mode, via),
+				Some(b) => b.mode,
+				None => SearchMode::Name,
+			},
+		};
+
+		let first = self.mode(mode).iter().find(|&s| {
+			s == SearchBackend::BUILTIN || self.backends.get(s).is_some_and(|b| b.available())
+		});
+
+		(mode, first.map_or(SearchBackend::BUILTIN, |s| s.as_str()))
+	}
+
+	#[inline]
+	pub fn mode(&self, mode: SearchMode) -> &[String] {
+		match mode {
+			SearchMode::Name => &self.name,
+			SearchMode::Content => &self.content,
+		}
+	}
+}
+
+impl Search {
+	pub(crate) fn reshape(mut self) -> Result<Self> {
+		self.backends.retain(|_, b| crate::check_for(b.r#for.as_deref()));
+		for (name, backend) in &mut self.backends {
+			if name == SearchBackend::BUILTIN || name == SearchBackend::INDEX {
+				bail!("[search].backends.{name} is reserved for a builtin search engine.");
+			}
+			backend.reshape(name)?;
+		}
+
+		for name in self.name.iter().chain(&self.content) {
+			if name != SearchBackend::BUILTIN && !self.backends.contains_key(name) {
+				bail!("[search] refers to an unknown backend `{name}`.");
+			}
+		}
+
+		self.index = self.index.iter().map(expand_path).collect();
+		Ok(self)
+	}
+
+	pub(crate) fn deserialize_over<'de, D>(mut self, deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		#[derive(Deserialize)]
+		struct Shadow {
+			name:     Option<Vec<String>>,
+			content:  Option<Vec<String>>,
+			index:    Option<Vec<PathBuf>>,
+			#[serde(default)]
+			backends: HashMap<String, SearchBackend>,
+		}
+
+		let shadow = Shadow::deserialize(deserializer)?;
+		if let Some(name) = shadow.name {
+			self.name = name;
+		}
+		if let Some(content) = shadow.content {
+			self.content = content;
+		}
+		if let Some(index) = shadow.index {
+			self.index = index;
+		}
+
+		self.backends.extend(shadow.backends);
+		Ok(self)
+	}
+}
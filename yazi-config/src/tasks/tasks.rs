@@ -11,7 +11,18 @@ pub struct Tasks {
 	pub image_alloc: u32,
 	pub image_bound: [u16; 2],
 
+	pub plugin_memory: u64,
+
 	pub suppress_preload: bool,
+	pub verify_paste:     bool,
+	pub queue_paste:      bool,
+	pub paste_limit:      u64,
+	pub preserve_xattr:   bool,
+
+	pub copy_streams: u8,
+	pub copy_chunk:   u64,
+
+	pub audit_log: bool,
 }
 
 impl Tasks {
@@ -22,6 +33,10 @@ impl Tasks {
 			bail!("[tasks].macro_workers must be at least 1.");
 		} else if self.bizarre_retry < 1 {
 			bail!("[tasks].bizarre_retry` must be at least 1.");
+		} else if self.copy_streams < 1 {
+			bail!("[tasks].copy_streams must be at least 1.");
+		} else if self.copy_chunk < 1024 * 1024 {
+			bail!("[tasks].copy_chunk must be at least 1MB.");
 		}
 		Ok(self)
 	}
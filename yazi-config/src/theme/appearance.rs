@@ -0,0 +1,56 @@
+use std::str::FromStr;
+
+use anyhow::bail;
+use serde::{Deserialize, Serialize};
+
+/// Whether to use the light or dark theme.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Appearance {
+	#[default]
+	Auto,
+	Dark,
+	Light,
+}
+
+impl FromStr for Appearance {
+	type Err = anyhow::Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		Ok(match s {
+			"auto" => Self::Auto,
+			"dark" => Self::Dark,
+			"light" => Self::Light,
+			_ => bail!("Invalid appearance: {s}"),
+		})
+	}
+}
+
+impl Appearance {
+	/// Whether it's light, given the terminal's background and the OS's appearance.
+	pub fn light(self, term: Option<bool>, os: impl FnOnce() -> Option<bool>) -> bool {
+		match self {
+			Self::Auto => term.or_else(os).unwrap_or(false),
+			Self::Dark => false,
+			Self::Light => true,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_light() {
+		let unreachable = || -> Option<bool> { panic!("the OS should not be asked") };
+		assert!(Appearance::Auto.light(Some(true), unreachable));
+		assert!(!Appearance::Auto.light(Some(false), unreachable));
+		assert!(Appearance::Auto.light(None, || Some(true)));
+		assert!(!Appearance::Auto.light(None, || None));
+
+		assert!(!Appearance::Dark.light(Some(true), unreachable));
+		assert!(Appearance::Light.light(None, unreachable));
+		assert!("sepia".parse::<Appearance>().is_err());
+	}
+}
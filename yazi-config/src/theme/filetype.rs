@@ -2,7 +2,7 @@ use std::ops::Deref;
 
 use serde::Deserialize;
 use yazi_codegen::DeserializeOver2;
-use yazi_fs::File;
+use yazi_fs::{File, GIT, GitStatus};
 
 use super::Is;
 use crate::{Pattern, Style};
@@ -24,13 +24,14 @@ pub struct FiletypeRule {
 	is:        Is,
 	url:       Option<Pattern>,
 	mime:      Option<Pattern>,
+	git:       Option<GitStatus>,
 	#[serde(flatten)]
 	pub style: Style,
 }
 
 impl FiletypeRule {
 	pub fn matches(&self, file: &File, mime: &str) -> bool {
-		if !self.is.check(&file.cha) {
+		if !self.is.check(&file.cha) || self.git.is_some_and(|s| GIT.status(&file.url) != Some(s)) {
 			return false;
 		}
 
@@ -6,10 +6,13 @@ use toml::Value;
 use yazi_codegen::DeserializeOver2;
 use yazi_fs::Xdg;
 
-#[derive(Default, Deserialize, DeserializeOver2, Serialize)]
+use super::Appearance;
+
+#[derive(Clone, Default, Deserialize, DeserializeOver2, Serialize)]
 pub struct Flavor {
-	pub dark:  String,
-	pub light: String,
+	pub dark:       String,
+	pub light:      String,
+	pub appearance: Appearance,
 }
 
 impl From<&Value> for Flavor {
@@ -22,14 +25,21 @@ impl From<&Value> for Flavor {
 			if let Some(s) = t.get("light").and_then(|v| v.as_str()) {
 				me.light = s.to_owned();
 			}
+			if let Some(a) = t.get("appearance").and_then(|v| v.as_str()?.parse().ok()) {
+				me.appearance = a;
+			}
 		}
 		me
 	}
 }
 
 impl Flavor {
+	/// The name of the flavor in use for the light or dark theme.
+	#[inline]
+	pub fn name(&self, light: bool) -> &str { if light { &self.light } else { &self.dark } }
+
 	pub(crate) fn read(&self, light: bool) -> Result<String> {
-		Ok(match if light { self.light.as_str() } else { self.dark.as_str() } {
+		Ok(match self.name(light) {
 			"" => String::new(),
 			name => {
 				let p = Xdg::config_dir().join(format!("flavors/{name}.yazi/flavor.toml"));
@@ -39,7 +49,7 @@ impl Flavor {
 	}
 
 	pub(crate) fn syntect_path(&self, light: bool) -> Option<PathBuf> {
-		match if light { self.light.as_str() } else { self.dark.as_str() } {
+		match self.name(light) {
 			"" => None,
 			name => Some(Xdg::config_dir().join(format!("flavors/{name}.yazi/tmtheme.xml"))),
 		}
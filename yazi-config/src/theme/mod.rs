@@ -1 +1 @@
-yazi_macro::mod_flat!(filetype flavor icon is theme);
+yazi_macro::mod_flat!(appearance filetype flavor icon is theme);
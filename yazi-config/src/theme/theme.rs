@@ -47,6 +47,20 @@ pub struct Mgr {
 	// Symlink
 	pub symlink_target: Style,
 
+	// Compare
+	pub compare_only:   Style,
+	pub compare_differ: Style,
+
+	// Tag
+	pub tag: Style,
+
+	// Git
+	pub git_modified:   Style,
+	pub git_added:      Style,
+	pub git_untracked:  Style,
+	pub git_ignored:    Style,
+	pub git_conflicted: Style,
+
 	// Marker
 	pub marker_copied:   Style,
 	pub marker_cut:      Style,
@@ -99,6 +113,10 @@ pub struct Status {
 	pub sep_left:  StatusSep,
 	pub sep_right: StatusSep,
 
+	// Segments
+	pub left:  Vec<String>,
+	pub right: Vec<String>,
+
 	// Permissions
 	pub perm_sep:   Style,
 	pub perm_type:  Style,
@@ -160,6 +178,10 @@ pub struct Notify {
 	pub icon_info:  String,
 	pub icon_warn:  String,
 	pub icon_error: String,
+
+	pub border:  Style,
+	pub title:   Style,
+	pub hovered: Style,
 }
 
 #[derive(Deserialize, DeserializeOver2)]
@@ -0,0 +1,17 @@
+use serde::Deserialize;
+use yazi_codegen::DeserializeOver2;
+
+#[derive(Debug, Deserialize, DeserializeOver2)]
+pub struct Trash {
+	pub usage:    bool,
+	pub max_age:  u16,
+	pub max_size: u64,
+}
+
+impl Trash {
+	/// Whether any retention policy is set.
+	pub fn policy(&self) -> bool { self.max_age > 0 || self.max_size > 0 }
+
+	/// Whether the trash needs to be measured in the background.
+	pub fn measured(&self) -> bool { self.usage || self.policy() }
+}
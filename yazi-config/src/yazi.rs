@@ -3,7 +3,7 @@ use serde::Deserialize;
 use yazi_codegen::DeserializeOver1;
 use yazi_fs::{Xdg, ok_or_not_found};
 
-use crate::{mgr, open, opener, plugin, popup, preview, tasks, which};
+use crate::{mgr, open, opener, plugin, policy, popup, preview, search, tasks, trash, which};
 
 #[derive(Deserialize, DeserializeOver1)]
 pub struct Yazi {
@@ -11,12 +11,15 @@ pub struct Yazi {
 	pub preview: preview::Preview,
 	pub opener:  opener::Opener,
 	pub open:    open::Open,
+	pub search:  search::Search,
 	pub tasks:   tasks::Tasks,
+	pub trash:   trash::Trash,
 	pub plugin:  plugin::Plugin,
 	pub input:   popup::Input,
 	pub confirm: popup::Confirm,
 	pub pick:    popup::Pick,
 	pub which:   which::Which,
+	pub policy:  policy::Policy,
 }
 
 impl Yazi {
@@ -32,12 +35,15 @@ impl Yazi {
 			preview: self.preview.reshape()?,
 			opener:  self.opener.reshape()?,
 			open:    self.open.reshape()?,
+			search:  self.search.reshape()?,
 			tasks:   self.tasks.reshape()?,
+			trash:   self.trash,
 			plugin:  self.plugin.reshape()?,
 			input:   self.input,
 			confirm: self.confirm,
 			pick:    self.pick,
 			which:   self.which,
+			policy:  self.policy.reshape()?,
 		})
 	}
 }
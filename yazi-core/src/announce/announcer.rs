@@ -0,0 +1,209 @@
+use std::{collections::VecDeque, time::Instant};
+
+use ratatui::{layout::Rect, widgets::{Paragraph, Wrap}};
+use yazi_fs::FolderStage;
+use yazi_parser::app::NotifyLevel;
+use yazi_scheduler::readable_size;
+use yazi_shared::{Layer, url::Url};
+
+use crate::Core;
+
+/// Tells what has changed since the last render, for the accessible mode.
+#[derive(Default)]
+pub struct Announcer {
+	pub lines: VecDeque<String>,
+	last:      Snapshot,
+}
+
+#[derive(Default, PartialEq)]
+struct Snapshot {
+	cwd:      Option<Url>,
+	stage:    FolderStage,
+	tab:      (usize, usize),
+	mode:     String,
+	layer:    Layer,
+	focus:    Option<(Url, usize)>,
+	selected: usize,
+	notified: Option<Instant>,
+	tasks:    (bool, u32),
+}
+
+impl Announcer {
+	const CAP: usize = 100;
+
+	/// Announces what's changed in the core since the last time.
+	pub fn update(&mut self, core: &Core) {
+		let now = Snapshot::new(core);
+		if now == self.last {
+			return;
+		}
+
+		let last = std::mem::take(&mut self.last);
+		let tab = core.active();
+
+		if now.tab != last.tab && now.tab.1 > 1 {
+			self.push(format!("Tab {} of {}", now.tab.0 + 1, now.tab.1));
+		}
+		if now.cwd != last.cwd
+			&& let Some(cwd) = &now.cwd
+		{
+			self.push(format!("In {}", cwd.display()));
+		}
+		if now.stage != last.stage || now.cwd != last.cwd {
+			match now.stage {
+				FolderStage::Loaded if tab.current.files.is_empty() => self.push("Empty".to_owned()),
+				FolderStage::Failed(kind) => self.push(format!("Failed to read: {kind}")),
+				_ => {}
+			}
+		}
+		if now.mode != last.mode && !last.mode.is_empty() {
+			self.push(format!("{} mode", now.mode));
+		}
+
+		if now.layer != last.layer {
+			match now.layer {
+				Layer::Tasks => self.push("Tasks".to_owned()),
+				Layer::Notify => self.push("Notifications".to_owned()),
+				Layer::Spot => self.push("Spot".to_owned()),
+				Layer::Pick => self.push(core.pick.title.clone()),
+				Layer::Input => self.push(core.input.title.clone()),
+				Layer::Confirm => self.push(core.confirm.title.to_string()),
+				Layer::Help => self.push("Help".to_owned()),
+				Layer::App | Layer::Mgr | Layer::Cmp | Layer::Which => {}
+			}
+		}
+
+		// Back from a popup, the hovered file is told again
+		if now.focus != last.focus || (now.layer != last.layer && now.layer == Layer::Mgr) {
+			match now.layer {
+				Layer::Mgr => {
+					if let Some(f) = tab.hovered() {
+						let kind = if f.is_dir() { "folder".to_owned() } else { readable_size(f.len) };
+						let selected = if tab.selected.contains(&f.url) { ", selected" } else { "" };
+						self.push(format!(
+							"{}, {kind}, {} of {}{selected}",
+							f.name().to_string_lossy(),
+							tab.current.cursor + 1,
+							tab.current.files.len(),
+						));
+					}
+				}
+				Layer::Pick => {
+					let pick = &core.pick;
+					if let Some(item) = pick.items.get(pick.cursor) {
+						self.push(format!("{item}, {} of {}", pick.cursor + 1, pick.items.len()));
+					}
+				}
+				_ => {}
+			}
+		}
+		if now.selected != last.selected {
+			self.push(format!("{} selected", now.selected));
+		}
+
+		// The notifications pushed since, the oldest first
+		let fresh = core.notify.history.iter().take_while(|r| Some(r.instant) > last.notified);
+		for r in fresh.collect::<Vec<_>>().into_iter().rev() {
+			let level = match r.level {
+				NotifyLevel::Info => "",
+				NotifyLevel::Warn => "Warning, ",
+				NotifyLevel::Error => "Error, ",
+			};
+			self.push(format!("{level}{}: {}", r.title, r.content));
+		}
+
+		match (last.tasks, now.tasks) {
+			((false, _), (true, _)) => self.push("Tasks started".to_owned()),
+			((true, 0), (false, _)) => self.push("Tasks done".to_owned()),
+			((true, n), (false, _)) => self.push(format!("Tasks done, {n} failed")),
+			_ => {}
+		}
+		self.last = now;
+	}
+
+	/// The lines that fit in the area, the newest last.
+	pub fn window(&self, area: Rect) -> Vec<(Rect, &str)> {
+		let mut height = 0;
+		let mut lines: Vec<_> = self
+			.lines
+			.iter()
+			.rev()
+			.map(|s| (Self::height(s, area.width), s.as_str()))
+			.take_while(|&(h, _)| {
+				height += h;
+				height <= area.height || height == h
+			})
+			.collect();
+		lines.reverse();
+
+		let mut y = area.y;
+		lines
+			.into_iter()
+			.map(|(h, s)| {
+				let rect = Rect { y, height: h.min(area.bottom() - y), ..area };
+				y += h;
+				(rect, s)
+			})
+			.collect()
+	}
+
+	fn push(&mut self, line: String) {
+		if self.lines.len() >= Self::CAP {
+			self.lines.pop_front();
+		}
+		self.lines.push_back(line.replace(['\n', '\r'], " "));
+	}
+
+	#[inline]
+	fn height(s: &str, width: u16) -> u16 {
+		Paragraph::new(s).wrap(Wrap { trim: false }).line_count(width).max(1) as u16
+	}
+}
+
+impl Snapshot {
+	fn new(core: &Core) -> Self {
+		let tab = core.active();
+		let layer = core.layer();
+		let focus = match layer {
+			Layer::Mgr => tab.hovered().map(|f| (f.url.clone(), tab.current.cursor)),
+			Layer::Pick => Some((Url::default(), core.pick.cursor)),
+			_ => None,
+		};
+
+		let progress = &core.tasks.progress;
+		Self {
+			cwd:      Some(tab.cwd().clone()),
+			stage:    tab.current.stage,
+			tab:      (core.mgr.tabs.cursor, core.mgr.tabs.len()),
+			mode:     Self::mode(&tab.mode.to_string()),
+			layer,
+			focus,
+			selected: tab.selected.len(),
+			notified: core.notify.history.front().map(|r| r.instant),
+			tasks:    (progress.total > 0, progress.fail),
+		}
+	}
+
+	fn mode(s: &str) -> String {
+		let mut it = s.chars();
+		it.next().map(|c| c.to_uppercase().chain(it).collect()).unwrap_or_default()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_window() {
+		let mut a = Announcer::default();
+		for s in ["one", "two", "a line that\nwraps"] {
+			a.push(s.to_owned());
+		}
+
+		let rect = |y, height| Rect { x: 0, y, width: 10, height };
+		assert_eq!(a.window(rect(0, 3)), [(rect(0, 1), "two"), (rect(1, 2), "a line that wraps")]);
+		assert_eq!(a.window(rect(5, 1)), [(rect(5, 1), "a line that wraps")]);
+		assert_eq!(a.window(rect(0, 9)).len(), 3);
+	}
+}
@@ -6,10 +6,16 @@ use yazi_widgets::Scrollable;
 
 #[derive(Default)]
 pub struct Cmp {
-	pub caches: HashMap<Url, Vec<CmpItem>>,
-	pub cands:  Vec<CmpItem>,
-	pub offset: usize,
-	pub cursor: usize,
+	pub caches:  HashMap<Url, Vec<CmpItem>>,
+	// The directories typed with fuzzy components, e.g. `/u/lo/`, to those
+	// they're resolved to, e.g. `/usr/local/`
+	pub aliases: HashMap<Url, Url>,
+	pub cands:   Vec<CmpItem>,
+	pub offset:  usize,
+	pub cursor:  usize,
+	// The resolved directory of the candidates, which replaces the typed one
+	// once a candidate is completed
+	pub parent:  Option<String>,
 
 	pub ticket:  Id,
 	pub visible: bool,
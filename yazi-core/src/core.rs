@@ -1,36 +1,47 @@
+use std::mem;
+
 use crossterm::cursor::SetCursorStyle;
 use ratatui::layout::{Position, Rect};
 use yazi_shared::Layer;
 
-use crate::{cmp::Cmp, confirm::Confirm, help::Help, input::Input, mgr::Mgr, notify::Notify, pick::Pick, tab::{Folder, Tab}, tasks::Tasks, which::Which};
+use crate::{announce::Announcer, cmp::Cmp, confirm::Confirm, help::Help, input::Input, mgr::Mgr, notify::Notify, pick::Pick, tab::{Folder, Tab}, tasks::Tasks, which::Which};
 
 pub struct Core {
-	pub mgr:     Mgr,
-	pub tasks:   Tasks,
-	pub pick:    Pick,
-	pub input:   Input,
-	pub confirm: Confirm,
-	pub help:    Help,
-	pub cmp:     Cmp,
-	pub which:   Which,
-	pub notify:  Notify,
+	pub mgr:       Mgr,
+	pub tasks:     Tasks,
+	pub pick:      Pick,
+	pub input:     Input,
+	pub confirm:   Confirm,
+	pub help:      Help,
+	pub cmp:       Cmp,
+	pub which:     Which,
+	pub notify:    Notify,
+	pub announcer: Announcer,
 }
 
 impl Core {
 	pub fn make() -> Self {
 		Self {
-			mgr:     Mgr::make(),
-			tasks:   Tasks::serve(),
-			pick:    Default::default(),
-			input:   Default::default(),
-			confirm: Default::default(),
-			help:    Default::default(),
-			cmp:     Default::default(),
-			which:   Default::default(),
-			notify:  Default::default(),
+			mgr:       Mgr::make(),
+			tasks:     Tasks::serve(),
+			pick:      Default::default(),
+			input:     Default::default(),
+			confirm:   Default::default(),
+			help:      Default::default(),
+			cmp:       Default::default(),
+			which:     Default::default(),
+			notify:    Default::default(),
+			announcer: Default::default(),
 		}
 	}
 
+	/// Announces what's changed since the last render, in the accessible mode.
+	pub fn announce(&mut self) {
+		let mut announcer = mem::take(&mut self.announcer);
+		announcer.update(self);
+		self.announcer = announcer;
+	}
+
 	#[inline]
 	pub fn cursor(&self) -> Option<(Position, SetCursorStyle)> {
 		if self.input.visible {
@@ -48,7 +59,7 @@ impl Core {
 
 	#[inline]
 	pub fn layer(&self) -> Layer {
-		if self.which.visible {
+		if self.which.visible && !self.which.idle() {
 			Layer::Which
 		} else if self.cmp.visible {
 			Layer::Cmp
@@ -60,6 +71,10 @@ impl Core {
 			Layer::Input
 		} else if self.pick.visible {
 			Layer::Pick
+		} else if self.which.visible {
+			Layer::Which
+		} else if self.notify.visible {
+			Layer::Notify
 		} else if self.active().spot.visible() {
 			Layer::Spot
 		} else if self.tasks.visible {
@@ -0,0 +1,57 @@
+use std::str::FromStr;
+
+use anyhow::Result;
+use yazi_config::keymap::Chord;
+use yazi_shared::{Layer, Source, event::Cmd, fuzzy};
+
+/// An entry of the help, or of the command palette.
+pub struct HelpEntry {
+	pub chord: Option<&'static Chord>,
+	pub on:    String,
+	pub run:   String,
+	pub desc:  Option<String>,
+}
+
+impl From<&'static Chord> for HelpEntry {
+	fn from(chord: &'static Chord) -> Self {
+		Self {
+			chord: Some(chord),
+			on:    chord.on(),
+			run:   chord.run(),
+			desc:  chord.desc().map(Into::into),
+		}
+	}
+}
+
+impl HelpEntry {
+	pub(super) fn unbound(run: impl Into<String>, desc: impl Into<String>) -> Self {
+		Self { chord: None, on: String::new(), run: run.into(), desc: Some(desc.into()) }
+	}
+
+	pub(super) fn contains(&self, s: &str) -> bool {
+		let s = s.to_lowercase();
+		self.desc.as_ref().is_some_and(|d| d.to_lowercase().contains(&s))
+			|| self.run.to_lowercase().contains(&s)
+			|| self.on.to_lowercase().contains(&s)
+	}
+
+	pub(super) fn score(&self, s: &str) -> Option<u32> {
+		fuzzy(s, &self.run).max(self.desc.as_ref().and_then(|d| fuzzy(s, d)))
+	}
+
+	/// The commands of `s` separated by `;`, in the `mgr` layer unless given one.
+	pub fn parse(s: &str) -> Result<Vec<Cmd>> {
+		s.split(';')
+			.map(str::trim)
+			.filter(|s| !s.is_empty())
+			.map(|s| {
+				let mut cmd = Cmd::from_str(s)?;
+				if cmd.layer == Layer::App {
+					cmd.layer = Layer::Mgr;
+				}
+				cmd.source = Source::Key;
+				Ok(cmd)
+			})
+			.collect()
+	}
+}
@@ -1,19 +1,24 @@
+use std::cmp::Reverse;
+
 use anyhow::Result;
 use crossterm::{cursor::SetCursorStyle, event::KeyCode};
 use unicode_width::UnicodeWidthStr;
 use yazi_adapter::Dimension;
-use yazi_config::{KEYMAP, YAZI, keymap::{Chord, Key}};
-use yazi_macro::{act, render, render_and};
+use yazi_config::{KEYMAP, YAZI, keymap::Key};
+use yazi_macro::{act, emit, relay, render, render_and};
 use yazi_shared::Layer;
 use yazi_widgets::Scrollable;
 
-use crate::help::HELP_MARGIN;
+use crate::help::{HELP_MARGIN, HelpEntry};
 
 #[derive(Default)]
 pub struct Help {
 	pub visible:         bool,
 	pub layer:           Layer,
-	pub(super) bindings: Vec<&'static Chord>,
+	/// Whether it's the command palette, listing the unbound commands as well.
+	pub palette:         bool,
+	pub(super) entries:  Vec<HelpEntry>,
+	pub(super) bindings: Vec<usize>,
 
 	// Filter
 	pub keyword:   String,
@@ -25,6 +30,26 @@ pub struct Help {
 
 impl Help {
 	pub fn r#type(&mut self, key: &Key) -> Result<bool> {
+		// The palette takes the keys to pick an entry while filtering
+		let plain = *key == Key { code: key.code, ..Default::default() };
+		if self.palette && self.in_filter.is_some() && plain {
+			match key.code {
+				KeyCode::Esc => {
+					self.visible = false;
+					self.palette = false;
+					self.in_filter = None;
+					return Ok(render_and!(true));
+				}
+				KeyCode::Enter | KeyCode::Tab => {
+					emit!(Call(relay!(help:invoke).with("prompt", key.code == KeyCode::Tab)));
+					return Ok(true);
+				}
+				KeyCode::Up => return Ok(render_and!(self.scroll(-1))),
+				KeyCode::Down => return Ok(render_and!(self.scroll(1))),
+				_ => {}
+			}
+		}
+
 		let Some(input) = &mut self.in_filter else { return Ok(false) };
 		match key {
 			Key { code: KeyCode::Esc, shift: false, ctrl: false, alt: false, super_: false } => {
@@ -52,14 +77,32 @@ impl Help {
 
 		if kw.is_empty() {
 			self.keyword = String::new();
-			self.bindings = KEYMAP.get(self.layer).iter().collect();
+			self.bindings = (0..self.entries.len()).collect();
+		} else if self.keyword != kw && self.palette {
+			self.keyword = kw.to_owned();
+			let mut scored: Vec<_> =
+				self.entries.iter().enumerate().filter_map(|(i, e)| Some((e.score(kw)?, i))).collect();
+			scored.sort_by_key(|&(score, _)| Reverse(score));
+			self.bindings = scored.into_iter().map(|(_, i)| i).collect();
 		} else if self.keyword != kw {
 			self.keyword = kw.to_owned();
-			self.bindings = KEYMAP.get(self.layer).iter().filter(|&c| c.contains(kw)).collect();
+			self.bindings = (0..self.entries.len()).filter(|&i| self.entries[i].contains(kw)).collect();
 		}
 
 		render!(self.scroll(0));
 	}
+
+	/// Loads the entries of the layer, or of the palette.
+	pub fn reload(&mut self) {
+		self.entries = if self.palette {
+			Self::palette()
+		} else {
+			KEYMAP.get(self.layer).iter().map(HelpEntry::from).collect()
+		};
+
+		self.keyword = String::new();
+		self.bindings = (0..self.entries.len()).collect();
+	}
 }
 
 impl Help {
@@ -71,14 +114,19 @@ impl Help {
 			.as_ref()
 			.map(|i| i.value())
 			.or(Some(self.keyword.as_str()).filter(|&s| !s.is_empty()))
-			.map(|s| format!("Filter: {s}"))
+			.map(|s| if self.palette { format!("Run: {s}") } else { format!("Filter: {s}") })
 	}
 
 	// --- Bindings
 	#[inline]
-	pub fn window(&self) -> &[&Chord] {
+	pub fn window(&self) -> Vec<&HelpEntry> {
 		let end = (self.offset + self.limit()).min(self.bindings.len());
-		&self.bindings[self.offset..end]
+		self.bindings[self.offset..end].iter().map(|&i| &self.entries[i]).collect()
+	}
+
+	#[inline]
+	pub fn hovered(&self) -> Option<&HelpEntry> {
+		self.bindings.get(self.cursor).map(|&i| &self.entries[i])
 	}
 
 	// --- Cursor
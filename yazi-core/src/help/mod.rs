@@ -1,3 +1,3 @@
-yazi_macro::mod_flat!(help);
+yazi_macro::mod_flat!(entry help palette);
 
 const HELP_MARGIN: u16 = 1;
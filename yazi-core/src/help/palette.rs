@@ -0,0 +1,111 @@
+use std::collections::HashSet;
+
+use yazi_config::KEYMAP;
+use yazi_fs::Xdg;
+use yazi_shared::Layer;
+
+use super::{Help, HelpEntry};
+
+/// The commands of the `mgr` layer that can be run by the user.
+const COMMANDS: &[(&str, &str)] = &[
+	("arrow", "Move the cursor by the given step"),
+	("back", "Go back to the previous directory"),
+	("bookmark", "Set, jump to, or delete a bookmark"),
+	("bulk_rename", "Rename the selected files in the editor"),
+	("cd", "Change to the given directory"),
+	("chmod", "Change the mode of the selected files"),
+	("chown", "Change the owner of the selected files"),
+	("close", "Close the current tab, or quit if it's the last"),
+	("compare", "Compare with the other panel"),
+	("copy", "Copy the path of the given type"),
+	("create", "Create a file, or a directory ending with `/`"),
+	("download", "Download a file from an HTTP(S) URL into the current directory"),
+	("dual", "Toggle the dual-panel layout"),
+	("dupes", "Find duplicate files, then select or hardlink all but the newest"),
+	("enter", "Enter the hovered directory"),
+	("escape", "Exit visual mode, clear selection, or cancel search"),
+	("extract", "Extract the selected archives"),
+	("filter", "Filter the files"),
+	("find", "Find the next file"),
+	("flatten", "Toggle listing all files up to 3 levels deep"),
+	("follow", "Follow the hovered symlink"),
+	("forward", "Go forward to the next directory"),
+	("grep", "Search lines by content"),
+	("hardlink", "Hardlink the yanked files"),
+	("hidden", "Toggle showing the hidden files"),
+	("info", "Toggle the info of the hovered file in the preview"),
+	("leave", "Go back to the parent directory"),
+	("linemode", "Set the linemode"),
+	("link", "Symlink the yanked files"),
+	("menu", "Show the context menu of the hovered file"),
+	("open", "Open the selected files"),
+	("paste", "Paste the yanked files"),
+	("quit", "Quit the process"),
+	("redo", "Redo the last undone file operation"),
+	("refresh", "Refresh the current directory"),
+	("relink", "Change the target of the hovered symlink"),
+	("remove", "Trash the selected files"),
+	("rename", "Rename the hovered file"),
+	("restore", "Restore the recently trashed files"),
+	("reveal", "Reveal the given file"),
+	("sanitize", "Fix the names of the selected files for Windows, recursively"),
+	("search", "Search files by name"),
+	("seek", "Seek in the preview by the given units"),
+	("selection", "Save or restore the selection of the given name"),
+	("shell", "Run a shell command"),
+	("sort", "Sort the files by the given method"),
+	("spot", "Spot the hovered file"),
+	("suspend", "Suspend the process"),
+	("tab_close", "Close the tab of the given index"),
+	("tab_create", "Create a new tab"),
+	("tab_swap", "Swap the current tab with the given one"),
+	("tab_switch", "Switch to the given tab"),
+	("tag", "Tag the selected files with the given name"),
+	("theme", "Switch the flavor, or between the light and dark themes"),
+	("toggle", "Toggle the selection of the hovered file"),
+	("toggle_all", "Toggle the selection of all files"),
+	("tree", "Toggle showing the directory as a tree"),
+	("undo", "Undo the last file operation"),
+	("unyank", "Cancel the yank status"),
+	("visual_mode", "Enter visual mode"),
+	("xattr", "Set or remove an extended attribute"),
+	("yank", "Yank the selected files"),
+];
+
+impl Help {
+	/// The keybindings of the `mgr` layer, followed by the commands and plugins unbound.
+	pub(super) fn palette() -> Vec<HelpEntry> {
+		let mut bound = HashSet::new();
+		let mut entries: Vec<_> = KEYMAP.get(Layer::Mgr).iter().map(HelpEntry::from).collect();
+
+		for cmd in KEYMAP.get(Layer::Mgr).iter().flat_map(|c| &c.run) {
+			match cmd.name.as_ref() {
+				"plugin" => bound.insert(format!("plugin {}", cmd.first_str().unwrap_or_default())),
+				_ if cmd.layer == Layer::Mgr => bound.insert(cmd.name.to_string()),
+				_ => false,
+			};
+		}
+
+		entries.extend(
+			COMMANDS
+				.iter()
+				.filter(|&&(name, _)| !bound.contains(name))
+				.map(|&(name, desc)| HelpEntry::unbound(name, desc)),
+		);
+
+		let mut plugins: Vec<_> = std::fs::read_dir(Xdg::config_dir().join("plugins"))
+			.into_iter()
+			.flatten()
+			.flatten()
+			.filter_map(|e| e.file_name().to_str()?.strip_suffix(".yazi").map(ToOwned::to_owned))
+			.filter(|name| !bound.contains(&format!("plugin {name}")))
+			.collect();
+
+		plugins.sort_unstable();
+		entries.extend(plugins.into_iter().map(|name| {
+			let desc = format!("Run the `{name}` plugin");
+			HelpEntry::unbound(format!("plugin {name}"), desc)
+		}));
+		entries
+	}
+}
@@ -6,7 +6,7 @@
 	clippy::unit_arg
 )]
 
-yazi_macro::mod_pub!(cmp confirm help input mgr notify pick spot tab tasks which);
+yazi_macro::mod_pub!(announce cmp confirm help input mgr notify pick spot tab tasks which);
 
 yazi_macro::mod_flat!(core);
 
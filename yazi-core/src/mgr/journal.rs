@@ -0,0 +1,72 @@
+use std::collections::VecDeque;
+
+use yazi_fs::JournalOp;
+use yazi_scheduler::file::Recycle;
+use yazi_shared::Id;
+
+/// The file operations done by the user, most recent last, and those undone, to be redone.
+#[derive(Default)]
+pub struct Journal {
+	undos:   VecDeque<JournalRecord>,
+	redos:   Vec<JournalRecord>,
+	// Operations replayed by an undo or redo, not to be recorded again, or the
+	// tasks replaying them, where the name is only known once they're done
+	pending: Vec<JournalOp>,
+	tasks:   Vec<Id>,
+}
+
+#[derive(Clone, Debug)]
+pub struct JournalRecord {
+	pub op:           JournalOp,
+	/// Why the operation can't be reversed.
+	pub irreversible: Option<String>,
+}
+
+impl Journal {
+	const CAP: usize = 100;
+
+	pub fn push(&mut self, op: JournalOp, task: Option<Id>) {
+		if let Some(i) = task.and_then(|t| self.tasks.iter().position(|&p| p == t)) {
+			self.tasks.swap_remove(i);
+			return;
+		} else if let Some(i) = self.pending.iter().position(|p| *p == op) {
+			self.pending.swap_remove(i);
+			return;
+		}
+
+		let irreversible = match &op {
+			JournalOp::Trash { .. } if !Recycle::SUPPORTED => {
+				Some("restoring from the trash isn't supported on this platform".to_owned())
+			}
+			_ => op.irreversible(),
+		};
+
+		self.redos.clear();
+		if self.undos.len() >= Self::CAP {
+			self.undos.pop_front();
+		}
+		self.undos.push_back(JournalRecord { op, irreversible });
+	}
+
+	/// Takes the most recent operation to undo, which can then be redone unless it's irreversible.
+	pub fn undo(&mut self) -> Option<JournalRecord> {
+		let record = self.undos.pop_back()?;
+		if record.irreversible.is_none() {
+			self.redos.push(record.clone());
+		}
+		Some(record)
+	}
+
+	/// Takes the most recently undone operation to redo.
+	pub fn redo(&mut self) -> Option<JournalRecord> {
+		let record = self.redos.pop()?;
+		self.undos.push_back(record.clone());
+		Some(record)
+	}
+
+	/// Expects an operation to be reported by a task started by an undo or redo.
+	pub fn expect(&mut self, op: JournalOp) { self.pending.push(op); }
+
+	/// Same as [`Journal::expect`], for whatever operation the task reports.
+	pub fn expect_task(&mut self, task: Id) { self.tasks.push(task); }
+}
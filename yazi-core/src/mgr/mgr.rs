@@ -1,14 +1,18 @@
 use ratatui::layout::Rect;
 use yazi_adapter::Dimension;
 use yazi_config::popup::{Origin, Position};
+use yazi_fs::Comparison;
 use yazi_shared::url::Url;
 
-use super::{Mimetype, Tabs, Watcher, Yanked};
+use super::{Journal, Mimetype, Selections, Tabs, Watcher, Yanked};
 use crate::tab::{Folder, Tab};
 
 pub struct Mgr {
-	pub tabs:   Tabs,
-	pub yanked: Yanked,
+	pub tabs:       Tabs,
+	pub yanked:     Yanked,
+	pub journal:    Journal,
+	pub compare:    Option<Comparison>,
+	pub selections: Selections,
 
 	pub watcher:  Watcher,
 	pub mimetype: Mimetype,
@@ -17,8 +21,11 @@ pub struct Mgr {
 impl Mgr {
 	pub fn make() -> Self {
 		Self {
-			tabs:   Default::default(),
-			yanked: Default::default(),
+			tabs:       Default::default(),
+			yanked:     Default::default(),
+			journal:    Default::default(),
+			compare:    None,
+			selections: Default::default(),
 
 			watcher:  Watcher::serve(),
 			mimetype: Default::default(),
@@ -1 +1 @@
-yazi_macro::mod_flat!(linked mgr mimetype tabs watcher yanked);
+yazi_macro::mod_flat!(journal linked mgr mimetype selections tabs watcher yanked);
@@ -0,0 +1,26 @@
+use std::{collections::BTreeMap, ops::Deref};
+
+use yazi_shared::url::Url;
+
+/// The selections saved under a name.
+#[derive(Default)]
+pub struct Selections(BTreeMap<String, Vec<Url>>);
+
+impl Deref for Selections {
+	type Target = BTreeMap<String, Vec<Url>>;
+
+	fn deref(&self) -> &Self::Target { &self.0 }
+}
+
+impl Selections {
+	/// Saves the URLs under the name, replacing what was saved before.
+	pub fn save(&mut self, name: &str, urls: impl Iterator<Item = Url>) -> usize {
+		let urls: Vec<_> = urls.collect();
+		let len = urls.len();
+		self.0.insert(name.to_owned(), urls);
+		len
+	}
+
+	#[inline]
+	pub fn remove(&mut self, name: &str) -> bool { self.0.remove(name).is_some() }
+}
@@ -3,16 +3,19 @@ use std::ops::{Deref, DerefMut};
 use yazi_dds::Pubsub;
 use yazi_fs::File;
 use yazi_macro::err;
+use yazi_shared::Id;
 
 use crate::tab::{Folder, Tab};
 
 pub struct Tabs {
 	pub cursor: usize,
 	pub items:  Vec<Tab>,
+	/// The tab shown in the other panel, if the dual-panel layout is on.
+	pub other:  Option<Id>,
 }
 
 impl Default for Tabs {
-	fn default() -> Self { Self { cursor: 0, items: vec![Default::default()] } }
+	fn default() -> Self { Self { cursor: 0, items: vec![Default::default()], other: None } }
 }
 
 impl Tabs {
@@ -22,9 +25,27 @@ impl Tabs {
 			active.preview.reset_image();
 		}
 
+		// Focusing the other panel leaves the last active tab there
+		if let Some(other) = self.other
+			&& self.items.get(idx).is_some_and(|t| t.id == other)
+			&& let Some(active) = self.items.get(self.cursor)
+		{
+			self.other = Some(active.id);
+		}
+
 		self.cursor = idx;
 		err!(Pubsub::pub_after_tab(self.active().id));
 	}
+
+	/// The index of the tab shown in the other panel.
+	pub fn other_idx(&self) -> Option<usize> {
+		let id = self.other?;
+		if let Some(i) = self.items.iter().position(|t| t.id == id).filter(|&i| i != self.cursor) {
+			return Some(i);
+		}
+
+		if self.cursor + 1 < self.len() { Some(self.cursor + 1) } else { self.cursor.checked_sub(1) }
+	}
 }
 
 impl Tabs {
@@ -34,6 +55,9 @@ impl Tabs {
 	#[inline]
 	pub(super) fn active_mut(&mut self) -> &mut Tab { &mut self.items[self.cursor] }
 
+	#[inline]
+	pub fn other(&self) -> Option<&Tab> { self.other_idx().map(|i| &self[i]) }
+
 	#[inline]
 	pub fn parent(&self) -> Option<&Folder> { self.active().parent.as_ref() }
 
@@ -6,9 +6,11 @@ use parking_lot::RwLock;
 use tokio::{pin, sync::{mpsc::{self, UnboundedReceiver}, watch}};
 use tokio_stream::{StreamExt, wrappers::UnboundedReceiverStream};
 use tracing::error;
-use yazi_fs::{File, Files, FilesOp, cha::Cha, provider, realname_unchecked};
+use yazi_config::YAZI;
+use yazi_fs::{DUPES, FRECENCY, File, Files, FilesOp, GIT, INDEX, Poller, cha::Cha, provider::{self, ReadDirOpt}, realname_unchecked};
 use yazi_proxy::WATCHER;
-use yazi_shared::{RoCell, url::Url};
+use yazi_scheduler::file::Recycle;
+use yazi_shared::{RoCell, url::{Scheme, Url}};
 
 use super::Linked;
 use crate::tab::Folder;
@@ -17,8 +19,9 @@ pub(crate) static WATCHED: RoCell<RwLock<HashSet<Url>>> = RoCell::new();
 pub static LINKED: RoCell<RwLock<Linked>> = RoCell::new();
 
 pub struct Watcher {
-	in_tx:  watch::Sender<HashSet<Url>>,
-	out_tx: mpsc::UnboundedSender<Url>,
+	in_tx:   watch::Sender<HashSet<Url>>,
+	out_tx:  mpsc::UnboundedSender<Url>,
+	poll_tx: watch::Sender<HashSet<Url>>,
 }
 
 // FIXME: VFS
@@ -33,6 +36,9 @@ impl Watcher {
 			if event.kind.is_access() {
 				return;
 			}
+			for p in &event.paths {
+				GIT.invalidate(p);
+			}
 			Self::push_files_impl(&out_tx_, event.paths.into_iter().map(Url::from));
 		};
 
@@ -43,17 +49,43 @@ impl Watcher {
 			tokio::spawn(Self::fan_in(in_rx, RecommendedWatcher::new(handler, config).unwrap()));
 		}
 
-		#[cfg(any(target_os = "linux", target_os = "macos"))]
+		#[cfg(any(target_os = "linux", target_os = "macos", windows))]
 		yazi_fs::mounts::Partitions::monitor(yazi_fs::mounts::PARTITIONS.clone(), || {
 			yazi_macro::err!(yazi_dds::Pubsub::pub_after_mount())
 		});
 
+		if !YAZI.search.index.is_empty() {
+			let handler = |res: Result<notify::Event, notify::Error>| {
+				let Ok(event) = res else { return };
+				if event.kind.is_access() {
+					return;
+				}
+				for p in event.paths {
+					INDEX.update(&p, std::fs::symlink_metadata(&p).is_ok());
+				}
+			};
+			tokio::spawn(Self::index(RecommendedWatcher::new(handler, config).unwrap()));
+		}
+
+		let (poll_tx, poll_rx) = watch::channel(Default::default());
+		let interval = Duration::from_secs(YAZI.mgr.poll_interval as u64);
+		tokio::spawn(Poller::new(interval).serve(poll_rx));
+
 		tokio::spawn(Self::fan_out(out_rx));
-		Self { in_tx, out_tx }
+		Self { in_tx, out_tx, poll_tx }
 	}
 
 	pub fn watch<'a>(&mut self, it: impl Iterator<Item = &'a Url>) {
-		self.in_tx.send(it.filter(|u| u.is_regular()).cloned().collect()).ok();
+		let (watch, poll) = it.fold((HashSet::new(), HashSet::new()), |(mut w, mut p), u| {
+			if u.caps().supports_watch() {
+				w.insert(u.clone());
+			} else if u.caps().supports_poll() {
+				p.insert(u.clone());
+			}
+			(w, p)
+		});
+		self.in_tx.send(watch).ok();
+		self.poll_tx.send(poll).ok();
 	}
 
 	pub fn push_files(&self, urls: Vec<Url>) {
@@ -77,19 +109,54 @@ impl Watcher {
 
 	// TODO: performance improvement
 	pub fn trigger_dirs(&self, folders: &[&Folder]) {
-		let todo: Vec<_> =
-			folders.iter().filter(|&f| f.url.is_regular()).map(|&f| (f.url.to_owned(), f.cha)).collect();
+		let todo: Vec<_> = folders
+			.iter()
+			.filter(|&f| {
+				// MTP devices report no changes, so they are checked on every trigger instead
+				f.url.caps().supports_watch()
+					|| f.url.is_listing_root()
+					|| matches!(f.url.scheme, Scheme::Mtp(_))
+			})
+			.map(|&f| (f.url.to_owned(), f.cha))
+			.collect();
 		if todo.is_empty() {
 			return;
 		}
 
 		async fn go(cwd: Url, cha: Cha) {
+			// Listed from the records, which are always newer than what's shown
+			if cwd.is_history() {
+				let files = FRECENCY.read(&cwd).await;
+				return FilesOp::Full(cwd, files, Cha::default()).emit();
+			} else if cwd.is_dupes() {
+				let files = DUPES.read(&cwd).await;
+				return FilesOp::Full(cwd, files, Cha::default()).emit();
+			} else if cwd.is_trash() && cwd.urn().is_empty() {
+				let files = Recycle::read(&cwd).await;
+				return FilesOp::Full(cwd, files, Cha::default()).emit();
+			}
+
 			let Some(cha) = Files::assert_stale(&cwd, cha).await else { return };
 
-			match Files::from_dir_bulk(&cwd).await {
-				Ok(files) => FilesOp::Full(cwd, files, cha).emit(),
-				Err(e) => FilesOp::issue_error(&cwd, e.kind()).await,
+			let mut rx = match provider::read_dir_streamed(&cwd, ReadDirOpt::default()).await {
+				Ok(rx) => rx,
+				Err(e) => return FilesOp::issue_error(&cwd, e.kind()).await,
+			};
+
+			// Directories that fit in a single batch are replaced at once to avoid flickering
+			let first = rx.recv().await.unwrap_or_default();
+			let Some(second) = rx.recv().await else {
+				return FilesOp::Full(cwd, first, cha).emit();
+			};
+
+			let ticket = FilesOp::prepare(&cwd);
+			for files in [first, second] {
+				FilesOp::Part(cwd.clone(), files, ticket).emit();
 			}
+			while let Some(files) = rx.recv().await {
+				FilesOp::Part(cwd.clone(), files, ticket).emit();
+			}
+			FilesOp::Done(cwd, cha, ticket).emit();
 		}
 
 		tokio::spawn(async move {
@@ -154,6 +221,34 @@ impl Watcher {
 		}
 	}
 
+	async fn index(mut watcher: impl notify::Watcher + Send + 'static) {
+		let roots = &YAZI.search.index;
+		tokio::task::spawn_blocking(|| INDEX.load()).await.ok();
+		INDEX.retain(roots);
+
+		// The saved index answers searches until the roots have been walked again
+		let _watcher = tokio::task::spawn_blocking(move || {
+			for root in roots {
+				if let Err(e) = watcher.watch(root, RecursiveMode::Recursive) {
+					error!("Watching {root:?} for the index failed: {e:?}");
+				}
+			}
+			watcher
+		})
+		.await
+		.unwrap();
+
+		for root in roots {
+			INDEX.build(root.clone()).await;
+		}
+		loop {
+			if let Ok(Err(e)) = tokio::task::spawn_blocking(|| INDEX.save()).await {
+				error!("Saving the index failed: {e:?}");
+			}
+			tokio::time::sleep(Duration::from_secs(60)).await;
+		}
+	}
+
 	async fn sync_watched<W>(mut watcher: W, to_unwatch: HashSet<Url>, to_watch: HashSet<Url>) -> W
 	where
 		W: notify::Watcher + Send + 'static,
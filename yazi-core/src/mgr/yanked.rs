@@ -1,7 +1,8 @@
-use std::{collections::HashSet, ops::Deref};
+use std::{collections::HashSet, ops::Deref, sync::{Arc, Weak}};
 
+use parking_lot::Mutex;
 use yazi_dds::Pubsub;
-use yazi_fs::FilesOp;
+use yazi_fs::{FilesOp, Selection};
 use yazi_macro::err;
 use yazi_shared::url::{CovUrl, Url};
 
@@ -9,6 +10,10 @@ use yazi_shared::url::{CovUrl, Url};
 pub struct Yanked {
 	pub cut: bool,
 	urls:    HashSet<CovUrl>,
+	lazy:    Option<Selection>,
+
+	// The files of the lazy selection, as far as they've been expanded to
+	expanded: Arc<Mutex<Vec<Url>>>,
 
 	version:  u64,
 	revision: u64,
@@ -23,25 +28,48 @@ impl Deref for Yanked {
 impl Yanked {
 	pub fn new(cut: bool, urls: HashSet<CovUrl>) -> Self { Self { cut, urls, ..Default::default() } }
 
+	pub fn new_lazy(cut: bool, urls: HashSet<CovUrl>, lazy: Option<Selection>) -> Self {
+		Self { cut, urls, lazy: lazy.filter(|s| !s.is_empty()), ..Default::default() }
+	}
+
+	#[inline]
+	pub fn lazy(&self) -> Option<&Selection> { self.lazy.as_ref() }
+
+	#[inline]
+	pub fn count(&self) -> usize { self.urls.len() + self.lazy.as_ref().map_or(0, |s| s.len()) }
+
+	#[inline]
+	pub fn is_void(&self) -> bool { self.urls.is_empty() && self.lazy.is_none() }
+
 	pub fn remove(&mut self, url: &Url) {
 		if self.urls.remove(CovUrl::new(url)) {
 			self.revision += 1;
+		} else if self.lazy.as_mut().is_some_and(|s| s.exclude(url)) {
+			self.revision += 1;
 		}
 	}
 
 	pub fn clear(&mut self) {
-		if self.urls.is_empty() {
+		if self.is_void() {
 			return;
 		}
 
 		self.urls.clear();
+		self.lazy = None;
 		self.revision += 1;
 	}
 
 	#[inline]
-	pub fn contains(&self, url: impl AsRef<Url>) -> bool { self.urls.contains(CovUrl::new(&url)) }
+	pub fn contains(&self, url: impl AsRef<Url>) -> bool {
+		let url = url.as_ref();
+		self.urls.contains(CovUrl::new(url)) || self.lazy.as_ref().is_some_and(|s| s.contains(url))
+	}
 
 	pub fn contains_in(&self, dir: &Url) -> bool {
+		if self.lazy.as_ref().is_some_and(|s| s.contains_in(dir)) {
+			return true;
+		}
+
 		self.urls.iter().any(|u| {
 			let mut it = u.components();
 			it.next_back().is_some()
@@ -56,6 +84,11 @@ impl Yanked {
 			let old = self.urls.len();
 			self.urls.retain(|u| !removal.contains(u));
 			self.revision += (old != self.urls.len()) as u64;
+
+			if let Some(lazy) = &mut self.lazy {
+				let n = removal.iter().filter(|&u| lazy.exclude(u)).count();
+				self.revision += (n != 0) as u64;
+			}
 		}
 
 		if !addition.is_empty() {
@@ -65,13 +98,22 @@ impl Yanked {
 		}
 	}
 
+	/// The yanked URLs, with those of a lazy yank as far as they've been expanded.
+	pub fn expand(&self) -> impl Iterator<Item = Url> {
+		self.urls.iter().map(|u| u.0.clone()).chain(self.expanded.lock().clone())
+	}
+
+	/// Where the task expanding the lazy yank puts the files it finds.
+	#[inline]
+	pub fn expanding(&self) -> Weak<Mutex<Vec<Url>>> { Arc::downgrade(&self.expanded) }
+
 	pub fn catchup_revision(&mut self, force: bool) -> bool {
 		if self.version == self.revision && !force {
 			return false;
 		}
 
 		self.version = self.revision;
-		err!(Pubsub::pub_after_yank(self.cut, &self.urls));
+		err!(Pubsub::pub_after_yank(self.cut, &self.urls, self.lazy.as_ref()));
 		true
 	}
 }
@@ -1,12 +1,14 @@
 use std::time::{Duration, Instant};
 
+use yazi_macro::render;
 use yazi_parser::app::NotifyOpt;
 use yazi_proxy::AppProxy;
 
-use crate::notify::{Message, Notify};
+use crate::notify::{HISTORY_CAP, Message, Notify, Record};
 
 impl Notify {
 	pub fn push(&mut self, opt: NotifyOpt) {
+		self.record(Record::from(&opt));
 		let instant = Instant::now();
 
 		let mut msg = Message::from(opt);
@@ -17,4 +19,16 @@ impl Notify {
 			AppProxy::update_notify(Duration::ZERO);
 		}
 	}
+
+	/// Keeps it in the history, merged into the same one pushed before if any.
+	fn record(&mut self, mut record: Record) {
+		if let Some(old) = self.history.iter().position(|r| r == &record) {
+			record.count += self.history.remove(old).map_or(0, |r| r.count);
+		}
+
+		self.history.push_front(record);
+		self.history.truncate(HISTORY_CAP);
+		self.cursor = self.cursor.min(self.history.len() - 1);
+		render!(self.visible);
+	}
 }
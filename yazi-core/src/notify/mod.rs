@@ -1,6 +1,12 @@
 yazi_macro::mod_pub!(commands);
 
-yazi_macro::mod_flat!(message notify);
+yazi_macro::mod_flat!(message notify record);
 
 pub const NOTIFY_BORDER: u16 = 2;
 pub const NOTIFY_SPACING: u16 = 1;
+
+pub const HISTORY_BORDER: u16 = 2;
+pub const HISTORY_PADDING: u16 = 2;
+pub const HISTORY_PERCENT: u16 = 80;
+/// How many notifications are kept in the history, the oldest ones go first.
+pub const HISTORY_CAP: usize = 100;
@@ -1,14 +1,22 @@
-use std::ops::ControlFlow;
+use std::{collections::VecDeque, ops::ControlFlow};
 
 use ratatui::layout::Rect;
 use tokio::task::JoinHandle;
+use yazi_adapter::Dimension;
+use yazi_widgets::Scrollable;
 
-use super::{Message, NOTIFY_SPACING};
+use super::{HISTORY_BORDER, HISTORY_PADDING, HISTORY_PERCENT, Message, NOTIFY_SPACING, Record};
 
 #[derive(Default)]
 pub struct Notify {
 	pub(super) tick_handle: Option<JoinHandle<()>>,
 	pub messages:           Vec<Message>,
+
+	/// The notifications pushed so far, the latest first.
+	pub history: VecDeque<Record>,
+	pub visible: bool,
+	pub cursor:  usize,
+	pub offset:  usize,
 }
 
 impl Notify {
@@ -33,4 +41,29 @@ impl Notify {
 			ControlFlow::Break(i) => i,
 		})
 	}
+
+	#[inline]
+	pub fn hovered(&self) -> Option<&Record> { self.history.get(self.cursor) }
+
+	#[inline]
+	pub fn window(&self) -> impl Iterator<Item = (usize, &Record)> {
+		self.history.iter().enumerate().skip(self.offset).take(Scrollable::limit(self))
+	}
+}
+
+impl Scrollable for Notify {
+	#[inline]
+	fn total(&self) -> usize { self.history.len() }
+
+	#[inline]
+	fn limit(&self) -> usize {
+		(Dimension::available().rows * HISTORY_PERCENT / 100)
+			.saturating_sub(HISTORY_BORDER + HISTORY_PADDING) as usize
+	}
+
+	#[inline]
+	fn cursor_mut(&mut self) -> &mut usize { &mut self.cursor }
+
+	#[inline]
+	fn offset_mut(&mut self) -> &mut usize { &mut self.offset }
 }
@@ -0,0 +1,35 @@
+use std::time::Instant;
+
+use yazi_parser::app::{NotifyAction, NotifyLevel, NotifyOpt};
+
+/// A notification kept in the history, after its toast is gone.
+pub struct Record {
+	pub title:   String,
+	pub content: String,
+	pub level:   NotifyLevel,
+	pub action:  Option<NotifyAction>,
+
+	pub instant: Instant,
+	/// How many times it was pushed, as the same ones are merged into one.
+	pub count:   u32,
+}
+
+impl From<&NotifyOpt> for Record {
+	fn from(opt: &NotifyOpt) -> Self {
+		Self {
+			title:   opt.title.lines().next().unwrap_or_default().to_owned(),
+			content: opt.content.clone(),
+			level:   opt.level,
+			action:  opt.action.clone(),
+
+			instant: Instant::now(),
+			count:   1,
+		}
+	}
+}
+
+impl PartialEq for Record {
+	fn eq(&self, other: &Self) -> bool {
+		self.level == other.level && self.content == other.content && self.title == other.title
+	}
+}
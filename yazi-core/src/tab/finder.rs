@@ -30,7 +30,7 @@ impl Finder {
 	pub fn prev(&self, files: &Files, cursor: usize, include: bool) -> Option<isize> {
 		for i in !include as usize..files.len() {
 			let idx = (cursor + files.len() - i) % files.len();
-			if self.filter.matches(files[idx].name()) {
+			if self.filter.matches_with(files[idx].name(), &files[idx].cha) {
 				return Some(idx as isize - cursor as isize);
 			}
 		}
@@ -40,7 +40,7 @@ impl Finder {
 	pub fn next(&self, files: &Files, cursor: usize, include: bool) -> Option<isize> {
 		for i in !include as usize..files.len() {
 			let idx = (cursor + i) % files.len();
-			if self.filter.matches(files[idx].name()) {
+			if self.filter.matches_with(files[idx].name(), &files[idx].cha) {
 				return Some(idx as isize - cursor as isize);
 			}
 		}
@@ -55,7 +55,7 @@ impl Finder {
 
 		let mut i = 0u8;
 		for file in folder.files.iter() {
-			if !self.filter.matches(file.name()) {
+			if !self.filter.matches_with(file.name(), &file.cha) {
 				continue;
 			}
 
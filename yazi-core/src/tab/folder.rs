@@ -9,6 +9,8 @@ use yazi_proxy::MgrProxy;
 use yazi_shared::{Id, url::{Url, Urn, UrnBuf}};
 use yazi_widgets::Scrollable;
 
+use super::Tree;
+
 pub struct Folder {
 	pub url:   Url,
 	pub cha:   Cha,
@@ -20,6 +22,8 @@ pub struct Folder {
 
 	pub page:  usize,
 	pub trace: Option<UrnBuf>,
+
+	pub tree: Option<Tree>,
 }
 
 impl Default for Folder {
@@ -33,6 +37,7 @@ impl Default for Folder {
 			cursor: Default::default(),
 			page:   Default::default(),
 			trace:  Default::default(),
+			tree:   Default::default(),
 		}
 	}
 }
@@ -1 +1 @@
-yazi_macro::mod_flat!(backstack finder folder history mode preference preview selected tab);
+yazi_macro::mod_flat!(backstack finder folder history mode preference preview selected tab tree);
@@ -1,5 +1,5 @@
 use yazi_config::YAZI;
-use yazi_fs::{FilesSorter, SortBy};
+use yazi_fs::{FilesSorter, SortBy, View};
 
 #[derive(Clone, PartialEq)]
 pub struct Preference {
@@ -43,3 +43,20 @@ impl From<&Preference> for FilesSorter {
 		}
 	}
 }
+
+impl From<&View> for Preference {
+	fn from(value: &View) -> Self {
+		Self {
+			// Sorting
+			sort_by:        value.sorter.by,
+			sort_sensitive: value.sorter.sensitive,
+			sort_reverse:   value.sorter.reverse,
+			sort_dir_first: value.sorter.dir_first,
+			sort_translit:  value.sorter.translit,
+
+			// Display
+			linemode:    value.linemode.clone(),
+			show_hidden: value.show_hidden,
+		}
+	}
+}
@@ -1,20 +1,29 @@
-use std::{borrow::Cow, time::Duration};
+use std::{borrow::Cow, sync::LazyLock};
 
-use tokio::{pin, task::JoinHandle};
-use tokio_stream::{StreamExt, wrappers::UnboundedReceiverStream};
+use tokio::task::JoinHandle;
 use tokio_util::sync::CancellationToken;
 use yazi_adapter::ADAPTOR;
-use yazi_config::{LAYOUT, YAZI};
-use yazi_fs::{File, Files, FilesOp, cha::Cha};
+use yazi_config::{LAYOUT, YAZI, plugin::Previewer};
+use yazi_fs::{File, Files, FilesOp, cha::Cha, provider::{self, ReadDirOpt}};
 use yazi_macro::render;
 use yazi_parser::mgr::PreviewLock;
 use yazi_plugin::{external::Highlighter, isolate};
-use yazi_shared::{MIME_DIR, SStr, url::Url};
+use yazi_shared::{MIME_DIR, SStr, event::Cmd, url::Url};
+
+/// The previewer of the info, which takes over the preview while it's on.
+static INFO: LazyLock<Previewer> = LazyLock::new(|| Previewer {
+	url:     None,
+	mime:    None,
+	run:     Cmd { name: "info".into(), ..Default::default() },
+	timeout: None,
+	hint:    Default::default(),
+});
 
 #[derive(Default)]
 pub struct Preview {
 	pub lock: Option<PreviewLock>,
 	pub skip: usize,
+	pub info: bool,
 
 	previewer_ct:  Option<CancellationToken>,
 	folder_loader: Option<JoinHandle<()>>,
@@ -28,12 +37,13 @@ impl Preview {
 			return;
 		}
 
-		let Some(previewer) = YAZI.plugin.previewer(&file.url, &mime) else {
+		let previewer = if self.info { Some(&*INFO) } else { YAZI.plugin.previewer(&file.url, &mime) };
+		let Some(previewer) = previewer else {
 			return self.reset();
 		};
 
 		self.abort();
-		self.previewer_ct = isolate::peek(&previewer.run, file, mime, self.skip);
+		self.previewer_ct = isolate::peek(previewer, file, mime, self.skip);
 	}
 
 	pub fn go_folder(&mut self, file: File, dir: Option<Cha>, force: bool) {
@@ -51,18 +61,14 @@ impl Preview {
 		self.folder_loader = Some(tokio::spawn(async move {
 			let Some(new) = Files::assert_stale(&wd, dir.unwrap_or_default()).await else { return };
 
-			let rx = match Files::from_dir(&wd).await {
+			let mut rx = match provider::read_dir_streamed(&wd, ReadDirOpt::default()).await {
 				Ok(rx) => rx,
 				Err(e) => return FilesOp::issue_error(&wd, e.kind()).await,
 			};
 
-			let stream =
-				UnboundedReceiverStream::new(rx).chunks_timeout(50000, Duration::from_millis(500));
-			pin!(stream);
-
 			let ticket = FilesOp::prepare(&wd);
-			while let Some(chunk) = stream.next().await {
-				FilesOp::Part(wd.clone(), chunk, ticket).emit();
+			while let Some(files) = rx.recv().await {
+				FilesOp::Part(wd.clone(), files, ticket).emit();
 			}
 			FilesOp::Done(wd, new, ticket).emit();
 		}));
@@ -8,6 +8,7 @@ use yazi_shared::{timestamp_us, url::{CovUrl, Url}};
 pub struct Selected {
 	inner:   IndexMap<CovUrl, u64>,
 	parents: HashMap<CovUrl, usize>,
+	changed: bool,
 }
 
 impl Selected {
@@ -20,6 +21,10 @@ impl Selected {
 	#[inline]
 	pub fn values(&self) -> impl Iterator<Item = &Url> { self.inner.keys().map(Deref::deref) }
 
+	/// Whether it's changed since the last call, for reporting the changes.
+	#[inline]
+	pub fn take_changed(&mut self) -> bool { std::mem::take(&mut self.changed) }
+
 	#[inline]
 	pub fn contains(&self, url: impl AsRef<Url>) -> bool {
 		self.inner.contains_key(CovUrl::new(&url))
@@ -64,6 +69,7 @@ impl Selected {
 		for u in parents {
 			*self.parents.entry(u).or_insert(0) += self.inner.len() - len;
 		}
+		self.changed = true;
 		urls.len()
 	}
 
@@ -103,10 +109,12 @@ impl Selected {
 
 			parent = u.parent_url();
 		}
+		self.changed = true;
 		count
 	}
 
 	pub fn clear(&mut self) {
+		self.changed |= !self.inner.is_empty();
 		self.inner.clear();
 		self.parents.clear();
 	}
@@ -166,6 +174,25 @@ mod tests {
 		assert!(s.parents.is_empty());
 	}
 
+	#[test]
+	fn test_take_changed() {
+		let mut s = Selected::default();
+		assert!(!s.take_changed());
+
+		assert!(s.add(&url("/a/b")));
+		assert!(s.take_changed());
+		assert!(!s.take_changed());
+
+		assert!(!s.add(&url("/a/b/c")));
+		assert!(!s.remove(&url("/a/c")));
+		assert!(!s.take_changed());
+
+		s.clear();
+		assert!(s.take_changed());
+		s.clear();
+		assert!(!s.take_changed());
+	}
+
 	#[test]
 	fn insert_many_success() {
 		let mut s = Selected::default();
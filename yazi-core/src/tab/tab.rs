@@ -4,8 +4,8 @@ use anyhow::Result;
 use ratatui::layout::Rect;
 use tokio::task::JoinHandle;
 use yazi_adapter::Dimension;
-use yazi_config::{LAYOUT, popup::{Origin, Position}};
-use yazi_fs::File;
+use yazi_config::{LAYOUT, YAZI, popup::{Origin, Position}};
+use yazi_fs::{File, Selection, VIEWS, View};
 use yazi_shared::{Id, Ids, url::Url};
 
 use super::{Backstack, Finder, Folder, History, Mode, Preference, Preview};
@@ -69,6 +69,35 @@ impl Tab {
 	#[inline]
 	pub fn hovered_mut(&mut self) -> Option<&mut File> { self.current.hovered_mut() }
 
+	/// Remembers the view of the CWD, if `[mgr].remember_views` is enabled.
+	pub fn save_view(&self) {
+		if !YAZI.mgr.remember_views {
+			return;
+		}
+
+		VIEWS.set(self.cwd(), View {
+			sorter:      (&self.pref).into(),
+			linemode:    self.pref.linemode.clone(),
+			show_hidden: self.pref.show_hidden,
+			filter:      self.current.files.filter().cloned(),
+		});
+	}
+
+	/// Restores the view the CWD was last left in, if `[mgr].remember_views` is enabled.
+	pub fn restore_view(&mut self) {
+		if !YAZI.mgr.remember_views {
+			return;
+		}
+
+		let Some(view) = VIEWS.get(self.cwd()) else {
+			self.pref = Default::default();
+			return;
+		};
+
+		self.pref = Preference::from(&view);
+		self.current.files.set_filter(view.filter);
+	}
+
 	pub fn hovered_rect(&self) -> Option<Rect> {
 		let y = self.current.files.position(self.hovered()?.urn())? - self.current.offset;
 
@@ -95,6 +124,26 @@ impl Tab {
 		}
 	}
 
+	/// The selection described by the current folder and its filter, if it covers all.
+	pub fn selected_lazily(&self) -> Option<Selection> {
+		let files = &self.current.files;
+		if self.selected.len() < YAZI.mgr.yank_threshold || self.selected.len() != files.len() {
+			return None;
+		} else if !files.iter().all(|f| self.selected.contains(&f.url)) {
+			return None;
+		} else if files.filter().is_some_and(|f| !f.by_name()) {
+			// The metadata may have changed by the time it's expanded
+			return None;
+		}
+
+		Some(Selection::new(
+			self.cwd().clone(),
+			files.filter().cloned(),
+			files.show_hidden(),
+			files.len(),
+		))
+	}
+
 	pub fn hovered_and_selected(&self) -> Box<dyn Iterator<Item = &Url> + '_> {
 		let Some(h) = self.hovered() else { return Box::new(iter::empty()) };
 		if self.selected.is_empty() {
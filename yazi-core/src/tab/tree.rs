@@ -0,0 +1,27 @@
+use std::collections::HashSet;
+
+use yazi_shared::url::{Urn, UrnBuf};
+
+/// The state of a folder shown as a tree.
+#[derive(Default)]
+pub struct Tree {
+	expanded: HashSet<UrnBuf>,
+}
+
+impl Tree {
+	/// The keyword of the search URL the tree is listed under.
+	pub const KEYWORD: &str = "tree";
+
+	#[inline]
+	pub fn is_expanded(&self, urn: &Urn) -> bool { self.expanded.contains(urn) }
+
+	#[inline]
+	pub fn expand(&mut self, urn: &Urn) -> bool { self.expanded.insert(urn.to_owned()) }
+
+	/// Collapses the directory, along with the expanded ones under it.
+	pub fn collapse(&mut self, urn: &Urn) -> bool {
+		let len = self.expanded.len();
+		self.expanded.retain(|u| !u.starts_with(urn));
+		self.expanded.len() != len
+	}
+}
@@ -1,36 +1,67 @@
-use std::collections::HashSet;
+use std::{collections::HashSet, sync::Arc};
 
 use tracing::debug;
+use yazi_fs::Selection;
+use yazi_parser::mgr::LinkOptRelative;
+use yazi_scheduler::{Schedule, Scheduler, file::{Conflict, Recycle}};
 use yazi_shared::url::{CovUrl, Url};
 
 use super::Tasks;
+use crate::mgr::Yanked;
 
 impl Tasks {
-	pub fn file_cut(&self, src: &[&CovUrl], dest: &Url, force: bool) {
+	pub fn file_cut(
+		&self,
+		src: &[&CovUrl],
+		dest: &Url,
+		conflict: &Arc<Conflict>,
+		verify: bool,
+		schedule: Schedule,
+	) {
 		for &u in src {
-			let to = dest.join(u.file_name().unwrap());
-			if force && *u == to {
+			let to = u.rebase(dest);
+			if conflict.overwrites() && *u == to {
 				debug!("file_cut: same file, skipping {:?}", to);
 			} else {
-				self.scheduler.file_cut(u.0.clone(), to, force);
+				self.scheduler.file_cut(u.0.clone(), to, conflict.clone(), verify, schedule);
 			}
 		}
 	}
 
-	pub fn file_copy(&self, src: &[&CovUrl], dest: &Url, force: bool, follow: bool) {
+	pub fn file_copy(
+		&self,
+		src: &[&CovUrl],
+		dest: &Url,
+		conflict: &Arc<Conflict>,
+		follow: bool,
+		verify: bool,
+		schedule: Schedule,
+	) {
 		for &u in src {
-			let to = dest.join(u.file_name().unwrap());
-			if force && *u == to {
+			let to = u.rebase(dest);
+			if conflict.overwrites() && *u == to {
 				debug!("file_copy: same file, skipping {:?}", to);
 			} else {
-				self.scheduler.file_copy(u.0.clone(), to, force, follow);
+				self.scheduler.file_copy(u.0.clone(), to, conflict.clone(), follow, verify, schedule);
 			}
 		}
 	}
 
-	pub fn file_link(&self, src: &HashSet<CovUrl>, dest: &Url, relative: bool, force: bool) {
+	pub fn file_sync(&self, src: &[CovUrl], dest: &Url) {
+		for u in src {
+			self.scheduler.file_sync(u.0.clone(), u.rebase(dest));
+		}
+	}
+
+	pub fn file_link(
+		&self,
+		src: &HashSet<CovUrl>,
+		dest: &Url,
+		relative: LinkOptRelative,
+		force: bool,
+	) {
 		for u in src {
-			let to = dest.join(u.file_name().unwrap());
+			let to = u.rebase(dest);
 			if force && *u == to {
 				debug!("file_link: same file, skipping {:?}", to);
 			} else {
@@ -41,7 +72,7 @@ impl Tasks {
 
 	pub fn file_hardlink(&self, src: &HashSet<CovUrl>, dest: &Url, force: bool, follow: bool) {
 		for u in src {
-			let to = dest.join(u.file_name().unwrap());
+			let to = u.rebase(dest);
 			if force && *u == to {
 				debug!("file_hardlink: same file, skipping {:?}", to);
 			} else {
@@ -50,7 +81,64 @@ impl Tasks {
 		}
 	}
 
+	pub fn file_cut_lazy(
+		&self,
+		src: &Selection,
+		dest: &Url,
+		conflict: &Arc<Conflict>,
+		verify: bool,
+		schedule: Schedule,
+	) {
+		let conflict = conflict.clone();
+		self.expand_lazy(src, dest, conflict.overwrites(), move |s, from, to| {
+			s.file_cut(from, to, conflict.clone(), verify, schedule);
+		});
+	}
+
+	pub fn file_copy_lazy(
+		&self,
+		src: &Selection,
+		dest: &Url,
+		conflict: &Arc<Conflict>,
+		follow: bool,
+		verify: bool,
+		schedule: Schedule,
+	) {
+		let conflict = conflict.clone();
+		self.expand_lazy(src, dest, conflict.overwrites(), move |s, from, to| {
+			s.file_copy(from, to, conflict.clone(), follow, verify, schedule)
+		});
+	}
+
+	pub fn file_link_lazy(
+		&self,
+		src: &Selection,
+		dest: &Url,
+		relative: LinkOptRelative,
+		force: bool,
+	) {
+		self.expand_lazy(src, dest, force, move |s, from, to| s.file_link(from, to, relative, force));
+	}
+
+	pub fn file_hardlink_lazy(&self, src: &Selection, dest: &Url, force: bool, follow: bool) {
+		self.expand_lazy(src, dest, force, move |s, from, to| s.file_hardlink(from, to, force, follow));
+	}
+
+	/// Expands a lazy yank to its files in the background, for what needs the full list of them.
+	pub fn file_expand(&self, yanked: &Yanked) {
+		if let Some(lazy) = yanked.lazy() {
+			self.scheduler.file_expand(lazy.clone(), yanked.expanding());
+		}
+	}
+
 	pub fn file_remove(&self, targets: Vec<Url>, permanently: bool) {
+		// Files in the trash are purged from it instead, so it forgets them too
+		let (trashed, targets): (Vec<_>, Vec<_>) = targets.into_iter().partition(|u| u.is_trash());
+		let ids: Vec<_> = trashed.iter().filter_map(Recycle::id_at).collect();
+		if !ids.is_empty() {
+			self.scheduler.file_purge(ids, 0);
+		}
+
 		for u in targets {
 			if permanently {
 				self.scheduler.file_delete(u);
@@ -59,4 +147,44 @@ impl Tasks {
 			}
 		}
 	}
+
+	pub fn file_extract(&self, archives: Vec<Url>, dest: Option<&Url>) {
+		for u in archives {
+			if let Some(to) = dest.cloned().or_else(|| u.parent_url()) {
+				self.scheduler.file_extract(u, to);
+			}
+		}
+	}
+
+	#[inline]
+	pub fn file_download(&self, url: String, dest: &Url) {
+		self.scheduler.file_download(url, dest.clone());
+	}
+
+	#[inline]
+	pub fn file_dupes(&self, targets: Vec<Url>) { self.scheduler.file_dupes(targets); }
+
+	/// Replaces each duplicate with a hardlink to the file kept in its place.
+	pub fn file_dedupe(&self, pairs: Vec<(Url, Url)>) {
+		for (keep, dupe) in pairs {
+			self.scheduler.file_dedupe(keep, dupe);
+		}
+	}
+
+	fn expand_lazy<F>(&self, src: &Selection, dest: &Url, force: bool, f: F)
+	where
+		F: Fn(&Scheduler, Url, Url) + Send + 'static,
+	{
+		let (scheduler, dest, mut rx) = (self.scheduler.clone(), dest.clone(), src.expand());
+		tokio::spawn(async move {
+			while let Some(u) = rx.recv().await {
+				let to = u.rebase(dest);
+				if force && u == to {
+					debug!("expand_lazy: same file, skipping {:?}", to);
+				} else {
+					f(&scheduler, u, to);
+				}
+			}
+		});
+	}
 }
@@ -1,5 +1,7 @@
+use std::collections::HashMap;
+
 use yazi_config::{YAZI, plugin::MAX_PREWORKERS};
-use yazi_fs::{File, Files, SortBy};
+use yazi_fs::{File, Files, FilesOp, SIZES, SortBy};
 
 use super::Tasks;
 use crate::mgr::Mimetype;
@@ -48,23 +50,33 @@ impl Tasks {
 			return;
 		}
 
-		let targets: Vec<_> = {
+		let (mut parent, mut cached, mut pending) = (None, HashMap::new(), vec![]);
+		{
 			let loading = self.scheduler.prework.sizing.read();
-			targets
-				.iter()
-				.filter(|f| f.is_dir() && !targets.sizes.contains_key(f.urn()) && !loading.contains(&f.url))
-				.map(|f| &f.url)
-				.collect()
-		};
-		if targets.is_empty() {
+			for f in targets.iter() {
+				if !f.is_dir() || targets.sizes.contains_key(f.urn()) || loading.contains(&f.url) {
+					continue;
+				} else if let Some(size) = SIZES.get(&f.url, f.cha.mtime) {
+					parent = parent.or_else(|| f.url.parent_url());
+					cached.insert(f.urn_owned(), size);
+				} else {
+					pending.push(f);
+				}
+			}
+		}
+
+		if let Some(parent) = parent {
+			FilesOp::Size(parent, cached).emit();
+		}
+		if pending.is_empty() {
 			return;
 		}
 
 		let mut loading = self.scheduler.prework.sizing.write();
-		for &target in &targets {
-			loading.insert(target.clone());
+		for target in &pending {
+			loading.insert(target.url.clone());
 		}
 
-		self.scheduler.prework_size(targets);
+		self.scheduler.prework_size(pending);
 	}
 }
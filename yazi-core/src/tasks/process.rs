@@ -1,8 +1,9 @@
-use std::{borrow::Cow, collections::HashMap, ffi::OsString, mem};
+use std::{borrow::Cow, collections::HashMap, mem};
 
 use yazi_config::{YAZI, opener::OpenerRule};
+use yazi_fs::apps::CHOSEN;
 use yazi_parser::tasks::ProcessExecOpt;
-use yazi_shared::url::Url;
+use yazi_shared::{MIME_DIR, url::Url};
 
 use super::Tasks;
 
@@ -10,16 +11,19 @@ impl Tasks {
 	pub fn process_from_files(&self, cwd: Url, hovered: Url, targets: Vec<(Url, &str)>) {
 		let mut openers = HashMap::new();
 		for (url, mime) in targets {
-			if let Some(opener) = YAZI.opener.first(YAZI.open.all(&url, mime)) {
-				openers.entry(opener).or_insert_with(|| vec![hovered.clone()]).push(url);
-			}
+			// The app chosen to be remembered for the extension goes first
+			let chosen = if mime == MIME_DIR { None } else { CHOSEN.get(&url) };
+			let opener: Cow<OpenerRule> = match chosen {
+				Some(app) => Cow::Owned(app.into()),
+				None => match YAZI.opener.first(YAZI.open.all(&url, mime)) {
+					Some(opener) => Cow::Borrowed(opener),
+					None => continue,
+				},
+			};
+			openers.entry(opener).or_insert_with(|| vec![hovered.clone()]).push(url);
 		}
-		for (opener, args) in openers {
-			self.process_from_opener(
-				cwd.clone(),
-				Cow::Borrowed(opener),
-				args.into_iter().map(|u| u.into_path().into_os_string()).collect(),
-			);
+		for (opener, targets) in openers {
+			self.process_from_opener(cwd.clone(), opener, targets);
 		}
 	}
 
@@ -27,25 +31,25 @@ impl Tasks {
 		&self,
 		cwd: Url,
 		opener: Cow<'static, OpenerRule>,
-		mut args: Vec<OsString>,
+		mut targets: Vec<Url>,
 	) {
-		if opener.spread {
-			self.scheduler.process_open(ProcessExecOpt { cwd, opener, args, done: None });
+		if opener.spread || targets.len() == 2 {
+			// Only a single target is opened at the line it points at
+			let line = if targets.len() == 2 { targets[1].frag() } else { None };
+			let args = targets.into_iter().map(|u| u.into_path().into_os_string()).collect();
+			self.scheduler.process_open(ProcessExecOpt { cwd, opener, args, line, done: None });
 			return;
 		}
-		if args.is_empty() {
+		if targets.is_empty() {
 			return;
 		}
-		if args.len() == 2 {
-			self.scheduler.process_open(ProcessExecOpt { cwd, opener, args, done: None });
-			return;
-		}
-		let hovered = mem::take(&mut args[0]);
-		for target in args.into_iter().skip(1) {
+		let hovered = mem::take(&mut targets[0]).into_path().into_os_string();
+		for target in targets.into_iter().skip(1) {
 			self.scheduler.process_open(ProcessExecOpt {
 				cwd:    cwd.clone(),
 				opener: opener.clone(),
-				args:   vec![hovered.clone(), target],
+				line:   target.frag(),
+				args:   vec![hovered.clone(), target.into_path().into_os_string()],
 				done:   None,
 			});
 		}
@@ -1,3 +1,4 @@
+use crossterm::event::KeyCode;
 use yazi_config::{KEYMAP, keymap::{ChordCow, Key}};
 use yazi_macro::{emit, render, render_and};
 use yazi_shared::Layer;
@@ -9,6 +10,9 @@ pub struct Which {
 	pub times: usize,
 	pub cands: Vec<ChordCow>,
 
+	/// The keymap modes pushed, the innermost last.
+	pub modes: Vec<String>,
+
 	// Visibility
 	pub visible: bool,
 	pub silent:  bool,
@@ -19,16 +23,29 @@ impl Which {
 		self.cands.retain(|c| c.on.len() > self.times && c.on[self.times] == key);
 		self.times += 1;
 
-		if self.cands.is_empty() {
-			self.reset();
+		let chosen = if self.cands.is_empty() {
+			None
 		} else if self.cands.len() == 1 {
-			emit!(Seq(self.cands.remove(0).into_seq()));
-			self.reset();
+			Some(self.cands.remove(0))
 		} else if let Some(i) = self.cands.iter().position(|c| c.on.len() == self.times) {
-			emit!(Seq(self.cands.remove(i).into_seq()));
-			self.reset();
+			Some(self.cands.remove(i))
+		} else {
+			return render_and!(true);
+		};
+
+		self.reset();
+		let matched = chosen.is_some();
+		if let Some(chord) = chosen {
+			emit!(Seq(chord.into_seq()));
 		}
 
+		// A sticky mode stays until `<Esc>`, and the others leave on any key
+		if let Some(mode) = self.modes.last().and_then(|m| KEYMAP.mode(m)) {
+			if !mode.sticky || (!matched && key.code == KeyCode::Esc) {
+				self.modes.pop();
+			}
+			self.resume();
+		}
 		render_and!(true)
 	}
 
@@ -54,4 +71,24 @@ impl Which {
 		self.silent = false;
 		render!();
 	}
+
+	/// Shows the keys of the innermost mode, if any, waiting for the first one.
+	pub fn resume(&mut self) -> bool {
+		let Some(mode) = self.modes.last().and_then(|m| KEYMAP.mode(m)) else {
+			self.modes.clear();
+			return false;
+		};
+
+		self.times = 0;
+		self.cands = mode.rules.iter().map(|c| c.into()).collect();
+
+		WhichSorter::default().sort(&mut self.cands);
+		self.visible = true;
+		self.silent = false;
+		render_and!(true)
+	}
+
+	/// Whether it's waiting for the first key of a mode, rather than the rest of a chord.
+	#[inline]
+	pub fn idle(&self) -> bool { self.times == 0 && !self.modes.is_empty() }
 }
@@ -1,14 +1,16 @@
-use std::{collections::{HashMap, HashSet}, iter, mem, str::FromStr};
+use std::{borrow::Borrow, collections::{HashMap, HashSet}, hash::Hash, iter, mem, str::FromStr};
 
-use anyhow::{Context, Result, bail};
+use anyhow::{Result, bail};
 use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
 use tokio::{io::AsyncWriteExt, select, sync::mpsc, task::JoinHandle, time};
 use tracing::error;
-use yazi_macro::try_format;
+use yazi_config::YAZI;
+use yazi_macro::{emit, relay, try_format};
+use yazi_parser::mgr::UpdateYankedOpt;
 use yazi_shared::{Id, RoCell};
 
-use crate::{ClientReader, ClientWriter, Payload, Pubsub, Server, Stream, ember::{Ember, EmberBye, EmberHi}};
+use crate::{ClientReader, ClientWriter, Payload, Pubsub, SERVICES, Server, Stream, Trashed, ember::{Ember, EmberBye, EmberHi}};
 
 pub static ID: RoCell<Id> = RoCell::new();
 pub(super) static PEERS: RoCell<RwLock<HashMap<Id, Peer>>> = RoCell::new();
@@ -28,6 +30,13 @@ pub struct Peer {
 	pub(super) abilities: HashSet<String>,
 }
 
+/// The reply to a command emitted by `ya emit`, with the error if it failed.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Reply {
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub error: Option<String>,
+}
+
 impl Client {
 	/// Connect to an existing server or start a new one.
 	pub(super) fn serve() {
@@ -56,7 +65,11 @@ impl Client {
 							continue;
 						} else if line.starts_with("hey,") {
 							Self::handle_hey(&line);
-						} else if let Err(e) = Payload::from_str(&line).and_then(|p| p.emit()) {
+						} else if line.starts_with("@trashed,") {
+							Self::handle_trashed(&line);
+						} else if YAZI.mgr.sync_yanked && line.starts_with("@yank,") {
+							Self::handle_yank(&line);
+						} else if let Err(e) = Self::handle_payload(&line) {
 							error!("Could not parse payload:\n{line}\n\nError:\n{e}");
 						}
 					}
@@ -93,40 +106,64 @@ impl Client {
 			}
 		}
 
-		if version.as_deref() != Some(EmberHi::version()) {
-			bail!(
-				"Incompatible version (Ya {}, Yazi {}). Restart all `ya` and `yazi` processes if you upgrade either one.",
-				EmberHi::version(),
-				version.as_deref().unwrap_or("Unknown")
-			);
-		}
+		Self::check(&peers, version.as_deref(), kind, receiver)
+	}
 
-		match (receiver, peers.get(&receiver).map(|p| p.able(kind))) {
-			// Send to all receivers
-			(Id(0), _) if peers.is_empty() => {
-				bail!("No receiver found. Check if any receivers are running.")
-			}
-			(Id(0), _) if peers.values().all(|p| !p.able(kind)) => {
-				bail!("No receiver has the ability to receive `{kind}` messages.")
-			}
-			(Id(0), _) => {}
+	/// Connect to an existing server to emit a command to the `receiver`.
+	pub async fn emit(receiver: Id, body: &str) -> Result<()> {
+		let payload = try_format!(
+			"{}\ndds-emit,{receiver},{ID},{body}\n",
+			Payload::new(EmberHi::borrowed(iter::once("dds-reply")))
+		)?;
 
-			// Send to a specific receiver
-			(_, Some(true)) => {}
-			(_, Some(false)) => {
-				bail!("Receiver `{receiver}` does not have the ability to receive `{kind}` messages.")
+		let (mut lines, mut writer) = Stream::connect().await?;
+		writer.write_all(payload.as_bytes()).await?;
+		writer.flush().await?;
+
+		let result = time::timeout(time::Duration::from_secs(5), async {
+			let mut pending = None;
+			while let Some(line) = lines.next_line().await? {
+				let mut parts = line.splitn(4, ',');
+				match parts.next() {
+					Some("hey") if pending.is_none() => {
+						let Ok(Ember::Hey(hey)) = Payload::from_str(&line).map(|p| p.body) else { continue };
+						Self::check(&hey.peers, Some(&*hey.version), "dds-emit", receiver)?;
+						pending = Some(if receiver == 0 {
+							hey.peers.values().filter(|p| p.able("dds-emit")).count()
+						} else {
+							1
+						});
+					}
+					Some("dds-reply") => {
+						let Some(reply) = parts.nth(2) else { continue };
+						if let Some(e) = serde_json::from_str::<Reply>(reply)?.error {
+							bail!("{e}");
+						}
+						pending = pending.map(|n| n - 1);
+					}
+					_ => {}
+				}
+				if pending == Some(0) {
+					return Ok(());
+				}
 			}
-			(_, None) => bail!("Receiver `{receiver}` not found. Check if the receiver is running."),
-		}
+			bail!("Connection closed before all receivers replied.")
+		})
+		.await;
 
-		Ok(())
+		let bye = Payload::new(EmberBye::owned());
+		writer.write_all(try_format!("{bye}\n")?.as_bytes()).await.ok();
+		match result {
+			Ok(r) => r,
+			Err(_) => bail!("Timed out waiting for the receivers to reply."),
+		}
 	}
 
 	/// Connect to an existing server and listen in on the messages that are being
 	/// sent by other yazi instances:
-	///   - If no server is running, fail right away;
-	///   - If a server is closed, attempt to reconnect forever.
-	pub async fn draw(kinds: HashSet<&str>) -> Result<()> {
+	///   - If no server is running, fail right away, or retry with `retry`;
+	///   - If a server is closed, stop listening, or reconnect with `retry`.
+	pub async fn draw(kinds: HashSet<&str>, retry: bool, mut cb: impl FnMut(String)) -> Result<()> {
 		async fn make(kinds: &HashSet<&str>) -> Result<ClientReader> {
 			let (lines, mut writer) = Stream::connect().await?;
 			let hi = Payload::new(EmberHi::borrowed(kinds.iter().copied()));
@@ -135,22 +172,34 @@ impl Client {
 			Ok(lines)
 		}
 
-		let mut lines = make(&kinds).await.context("No running Yazi instance found")?;
+		async fn remake(kinds: &HashSet<&str>) -> ClientReader {
+			let mut delay = time::Duration::from_millis(500);
+			loop {
+				time::sleep(delay).await;
+				if let Ok(lines) = make(kinds).await {
+					return lines;
+				}
+				delay = (delay * 2).min(time::Duration::from_secs(10));
+			}
+		}
+
+		let mut lines = match make(&kinds).await {
+			Ok(lines) => lines,
+			Err(_) if retry => remake(&kinds).await,
+			Err(e) => return Err(e.context("No running Yazi instance found")),
+		};
+
 		loop {
-			match lines.next_line().await? {
-				Some(s) => {
+			match lines.next_line().await {
+				Ok(Some(s)) => {
 					let kind = s.split(',').next();
-					if matches!(kind, Some(kind) if kinds.contains(kind)) {
-						println!("{s}");
+					if matches!(kind, Some(kind) if Peer::covers(&kinds, kind)) {
+						cb(s);
 					}
 				}
-				None => loop {
-					time::sleep(time::Duration::from_secs(1)).await;
-					if let Ok(new) = make(&kinds).await {
-						lines = new;
-						break;
-					}
-				},
+				Ok(None) | Err(_) if retry => lines = remake(&kinds).await,
+				Ok(None) => return Ok(()),
+				Err(e) => return Err(e.into()),
 			}
 		}
 	}
@@ -161,7 +210,43 @@ impl Client {
 	}
 
 	#[inline]
-	pub(super) fn able(&self, ability: &str) -> bool { self.abilities.contains(ability) }
+	pub(super) fn able(&self, ability: &str) -> bool { Peer::covers(&self.abilities, ability) }
+
+	// Whether the `kind` can be delivered to the `receiver` per the handshake
+	fn check(
+		peers: &HashMap<Id, Peer>,
+		version: Option<&str>,
+		kind: &str,
+		receiver: Id,
+	) -> Result<()> {
+		if version != Some(EmberHi::version()) {
+			bail!(
+				"Incompatible version (Ya {}, Yazi {}). Restart all `ya` and `yazi` processes if you upgrade either one.",
+				EmberHi::version(),
+				version.unwrap_or("Unknown")
+			);
+		}
+
+		match (receiver, peers.get(&receiver).map(|p| p.able(kind))) {
+			// Send to all receivers
+			(Id(0), _) if peers.is_empty() => {
+				bail!("No receiver found. Check if any receivers are running.")
+			}
+			(Id(0), _) if peers.values().all(|p| !p.able(kind)) => {
+				bail!("No receiver has the ability to receive `{kind}` messages.")
+			}
+			(Id(0), _) => {}
+
+			// Send to a specific receiver
+			(_, Some(true)) => {}
+			(_, Some(false)) => {
+				bail!("Receiver `{receiver}` does not have the ability to receive `{kind}` messages.")
+			}
+			(_, None) => bail!("Receiver `{receiver}` not found. Check if the receiver is running."),
+		}
+
+		Ok(())
+	}
 
 	async fn connect(server: &mut Option<JoinHandle<()>>) -> (ClientReader, ClientWriter) {
 		let mut first = true;
@@ -197,6 +282,42 @@ impl Client {
 			*PEERS.write() = hey.peers;
 		}
 	}
+
+	fn handle_payload(s: &str) -> Result<()> {
+		let payload = Payload::from_str(s)?;
+		if let Some(txs) = SERVICES.write().get_mut(payload.body.kind()) {
+			txs.retain(|tx| Payload::from_str(s).is_ok_and(|p| tx.send(p).is_ok()));
+		}
+		payload.emit()
+	}
+
+	fn handle_trashed(s: &str) {
+		let Ok(payload) = Payload::from_str(s) else { return };
+		if let Ember::Trashed(b) = &payload.body
+			&& let Err(e) = Trashed::accept(b.items.to_vec())
+		{
+			error!("Could not accept the trashed index: {e}");
+		}
+		payload.emit().ok();
+	}
+
+	fn handle_yank(s: &str) {
+		if let Some(opt) = Self::yanked_from(s, *ID) {
+			emit!(Call(relay!(mgr:update_yanked).with_any("opt", opt)));
+		}
+		if let Err(e) = Self::handle_payload(s) {
+			error!("Could not parse payload:\n{s}\n\nError:\n{e}");
+		}
+	}
+
+	// The files yanked in another instance, which are empty once it pasted the
+	// files cut, so the cut ones are dropped here too
+	fn yanked_from(s: &str, me: Id) -> Option<UpdateYankedOpt<'static>> {
+		match Payload::from_str(s) {
+			Ok(Payload { sender, body: Ember::Yank(b), .. }) if sender != me => Some(b.into_opt()),
+			_ => None,
+		}
+	}
 }
 
 impl Peer {
@@ -204,5 +325,44 @@ impl Peer {
 	pub(super) fn new(abilities: &HashSet<String>) -> Self { Self { abilities: abilities.clone() } }
 
 	#[inline]
-	pub(super) fn able(&self, ability: &str) -> bool { self.abilities.contains(ability) }
+	pub(super) fn able(&self, ability: &str) -> bool { Self::covers(&self.abilities, ability) }
+
+	/// Whether the `abilities` cover the `kind`, where `*` stands for every kind.
+	fn covers<T: Borrow<str> + Eq + Hash>(abilities: &HashSet<T>, kind: &str) -> bool {
+		abilities.contains(kind)
+			|| (abilities.contains("*")
+				&& !matches!(kind, "hi" | "hey" | "bye")
+				&& !kind.starts_with("dds-"))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::path::PathBuf;
+
+	use yazi_shared::url::{CovUrl, Url};
+
+	use super::*;
+	use crate::ember::EmberYank;
+
+	fn line(sender: Id, cut: bool, urls: &[&str]) -> String {
+		let urls = urls.iter().map(|s| CovUrl(Url::from(PathBuf::from(s)))).collect();
+		let body = EmberYank::borrowed(cut, &urls, None);
+		Payload { receiver: Id(0), sender, body }.to_string()
+	}
+
+	#[test]
+	fn test_yanked_from() {
+		// Files cut in another instance are taken
+		let opt = Client::yanked_from(&line(Id(1), true, &["/a", "/b"]), Id(2)).unwrap();
+		assert!(opt.cut);
+		assert_eq!(opt.urls.len(), 2);
+
+		// And dropped once it has pasted them
+		let opt = Client::yanked_from(&line(Id(1), false, &[]), Id(2)).unwrap();
+		assert!(opt.urls.is_empty() && opt.lazy.is_none());
+
+		// Its own ones are ignored
+		assert!(Client::yanked_from(&line(Id(2), true, &["/a"]), Id(2)).is_none());
+	}
 }
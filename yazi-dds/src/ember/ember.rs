@@ -1,8 +1,9 @@
 use anyhow::{Result, bail};
 use mlua::{ExternalResult, IntoLua, Lua, Value};
+use serde::Serialize;
 use yazi_shared::Id;
 
-use super::{EmberBulk, EmberBye, EmberCd, EmberCustom, EmberDelete, EmberHey, EmberHi, EmberHover, EmberLoad, EmberMount, EmberMove, EmberRename, EmberTab, EmberTrash, EmberYank};
+use super::{EmberBulk, EmberBye, EmberCd, EmberCustom, EmberDelete, EmberHey, EmberHi, EmberHover, EmberLoad, EmberMount, EmberMove, EmberRename, EmberTab, EmberTheme, EmberTrash, EmberTrashed, EmberYank};
 use crate::Payload;
 
 #[derive(Debug)]
@@ -19,8 +20,10 @@ pub enum Ember<'a> {
 	Yank(EmberYank<'a>),
 	Move(EmberMove<'a>),
 	Trash(EmberTrash<'a>),
+	Trashed(EmberTrashed<'a>),
 	Delete(EmberDelete<'a>),
 	Mount(EmberMount),
+	Theme(EmberTheme<'a>),
 	Custom(EmberCustom),
 }
 
@@ -39,8 +42,10 @@ impl Ember<'static> {
 			"@yank" => Self::Yank(serde_json::from_str(body)?),
 			"move" => Self::Move(serde_json::from_str(body)?),
 			"trash" => Self::Trash(serde_json::from_str(body)?),
+			"@trashed" => Self::Trashed(serde_json::from_str(body)?),
 			"delete" => Self::Delete(serde_json::from_str(body)?),
 			"mount" => Self::Mount(serde_json::from_str(body)?),
+			"@theme" => Self::Theme(serde_json::from_str(body)?),
 			_ => EmberCustom::from_str(kind, body)?,
 		})
 	}
@@ -65,8 +70,10 @@ impl Ember<'static> {
 				| "@yank"
 				| "move"
 				| "trash"
+				| "@trashed"
 				| "delete"
 				| "mount"
+				| "@theme"
 		) || kind.starts_with("emit-")
 			|| kind.starts_with("emit-ind-")
 			|| kind.starts_with("ind-")
@@ -105,8 +112,10 @@ impl<'a> Ember<'a> {
 			Self::Yank(_) => "@yank",
 			Self::Move(_) => "move",
 			Self::Trash(_) => "trash",
+			Self::Trashed(_) => "@trashed",
 			Self::Delete(_) => "delete",
 			Self::Mount(_) => "mount",
+			Self::Theme(_) => "@theme",
 			Self::Custom(b) => b.kind.as_str(),
 		}
 	}
@@ -120,6 +129,30 @@ impl<'a> Ember<'a> {
 	pub fn with_sender(self, sender: Id) -> Payload<'a> { Payload::new(self).with_sender(sender) }
 }
 
+impl Serialize for Ember<'_> {
+	fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		match self {
+			Self::Hi(b) => b.serialize(serializer),
+			Self::Hey(b) => b.serialize(serializer),
+			Self::Bye(b) => b.serialize(serializer),
+			Self::Cd(b) => b.serialize(serializer),
+			Self::Load(b) => b.serialize(serializer),
+			Self::Hover(b) => b.serialize(serializer),
+			Self::Tab(b) => b.serialize(serializer),
+			Self::Rename(b) => b.serialize(serializer),
+			Self::Bulk(b) => b.serialize(serializer),
+			Self::Yank(b) => b.serialize(serializer),
+			Self::Move(b) => b.serialize(serializer),
+			Self::Trash(b) => b.serialize(serializer),
+			Self::Trashed(b) => b.serialize(serializer),
+			Self::Delete(b) => b.serialize(serializer),
+			Self::Mount(b) => b.serialize(serializer),
+			Self::Theme(b) => b.serialize(serializer),
+			Self::Custom(b) => b.serialize(serializer),
+		}
+	}
+}
+
 impl<'a> IntoLua for Ember<'a> {
 	fn into_lua(self, lua: &Lua) -> mlua::Result<Value> {
 		match self {
@@ -135,8 +168,10 @@ impl<'a> IntoLua for Ember<'a> {
 			Self::Yank(b) => b.into_lua(lua),
 			Self::Move(b) => b.into_lua(lua),
 			Self::Trash(b) => b.into_lua(lua),
+			Self::Trashed(b) => b.into_lua(lua),
 			Self::Delete(b) => b.into_lua(lua),
 			Self::Mount(b) => b.into_lua(lua),
+			Self::Theme(b) => b.into_lua(lua),
 			Self::Custom(b) => b.into_lua(lua),
 		}
 	}
@@ -1,5 +1,5 @@
 #![allow(clippy::module_inception)]
 
 yazi_macro::mod_flat!(
-	bulk bye cd custom delete ember hey hi hover load mount r#move rename tab trash yank
+	bulk bye cd custom delete ember hey hi hover load mount r#move rename tab theme trash trashed yank
 );
@@ -0,0 +1,41 @@
+use std::borrow::Cow;
+
+use mlua::{IntoLua, Lua, Value};
+use serde::{Deserialize, Serialize};
+
+use super::Ember;
+
+/// The theme in use, after it's switched at runtime.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EmberTheme<'a> {
+	pub light:  bool,
+	/// The name of the flavor, or empty if none.
+	pub flavor: Cow<'a, str>,
+}
+
+impl<'a> EmberTheme<'a> {
+	pub fn borrowed(light: bool, flavor: &'a str) -> Ember<'a> {
+		Self { light, flavor: Cow::Borrowed(flavor) }.into()
+	}
+}
+
+impl EmberTheme<'static> {
+	pub fn owned(light: bool, flavor: &str) -> Ember<'static> {
+		Self { light, flavor: Cow::Owned(flavor.to_owned()) }.into()
+	}
+}
+
+impl<'a> From<EmberTheme<'a>> for Ember<'a> {
+	fn from(value: EmberTheme<'a>) -> Self { Self::Theme(value) }
+}
+
+impl IntoLua for EmberTheme<'_> {
+	fn into_lua(self, lua: &Lua) -> mlua::Result<Value> {
+		lua
+			.create_table_from([
+				("light", self.light.into_lua(lua)?),
+				("flavor", lua.create_string(self.flavor.as_ref())?.into_lua(lua)?),
+			])?
+			.into_lua(lua)
+	}
+}
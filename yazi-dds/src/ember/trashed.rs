@@ -0,0 +1,60 @@
+use std::borrow::Cow;
+
+use mlua::{IntoLua, Lua, Value};
+use serde::{Deserialize, Serialize};
+use yazi_shared::{Id, url::Url};
+
+use super::Ember;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EmberTrashed<'a> {
+	pub items: Cow<'a, Vec<BodyTrashedItem>>,
+}
+
+impl<'a> EmberTrashed<'a> {
+	pub fn borrowed(items: &'a Vec<BodyTrashedItem>) -> Ember<'a> {
+		Self { items: Cow::Borrowed(items) }.into()
+	}
+}
+
+impl EmberTrashed<'static> {
+	pub fn owned(items: Vec<BodyTrashedItem>) -> Ember<'static> {
+		Self { items: Cow::Owned(items) }.into()
+	}
+}
+
+impl<'a> From<EmberTrashed<'a>> for Ember<'a> {
+	fn from(value: EmberTrashed<'a>) -> Self { Self::Trashed(value) }
+}
+
+impl IntoLua for EmberTrashed<'_> {
+	fn into_lua(self, lua: &Lua) -> mlua::Result<Value> {
+		lua.create_table_from([("items", self.items.into_owned())])?.into_lua(lua)
+	}
+}
+
+// --- Item
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct BodyTrashedItem {
+	/// Where the file was before it was trashed.
+	pub url:    Url,
+	/// The ID of the item in the trash backend.
+	pub id:     String,
+	/// When the file was trashed, in seconds since the Unix epoch.
+	pub at:     i64,
+	/// The instance that trashed the file.
+	pub sender: Id,
+}
+
+impl IntoLua for BodyTrashedItem {
+	fn into_lua(self, lua: &Lua) -> mlua::Result<Value> {
+		lua
+			.create_table_from([
+				("url", yazi_binding::Url::new(self.url).into_lua(lua)?),
+				("id", self.id.into_lua(lua)?),
+				("at", self.at.into_lua(lua)?),
+				("sender", self.sender.get().into_lua(lua)?),
+			])?
+			.into_lua(lua)
+	}
+}
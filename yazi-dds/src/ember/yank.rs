@@ -2,6 +2,7 @@ use std::{borrow::Cow, collections::HashSet};
 
 use mlua::{IntoLua, Lua, Value};
 use serde::{Deserialize, Serialize};
+use yazi_fs::Selection;
 use yazi_parser::mgr::UpdateYankedOpt;
 use yazi_shared::url::CovUrl;
 
@@ -11,14 +12,18 @@ use super::Ember;
 pub struct EmberYank<'a>(UpdateYankedOpt<'a>);
 
 impl<'a> EmberYank<'a> {
-	pub fn borrowed(cut: bool, urls: &'a HashSet<CovUrl>) -> Ember<'a> {
-		Self(UpdateYankedOpt { cut, urls: Cow::Borrowed(urls) }).into()
+	pub fn borrowed(cut: bool, urls: &'a HashSet<CovUrl>, lazy: Option<&'a Selection>) -> Ember<'a> {
+		Self(UpdateYankedOpt { cut, urls: Cow::Borrowed(urls), lazy: lazy.map(Cow::Borrowed) }).into()
 	}
 }
 
 impl EmberYank<'static> {
-	pub fn owned(cut: bool, _: &HashSet<CovUrl>) -> Ember<'static> {
-		Self(UpdateYankedOpt { cut, urls: Default::default() }).into()
+	#[inline]
+	pub(crate) fn into_opt(self) -> UpdateYankedOpt<'static> { self.0 }
+
+	pub fn owned(cut: bool, _: &HashSet<CovUrl>, lazy: Option<&Selection>) -> Ember<'static> {
+		Self(UpdateYankedOpt { cut, urls: Default::default(), lazy: lazy.cloned().map(Cow::Owned) })
+			.into()
 	}
 }
 
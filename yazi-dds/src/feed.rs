@@ -0,0 +1,89 @@
+use std::{fs::File, io::{self, Write}, sync::mpsc};
+
+use parking_lot::Mutex;
+use serde::Serialize;
+use tracing::error;
+use yazi_boot::{ARGS, BOOT};
+use yazi_shared::{Id, url::Url};
+
+static TX: Mutex<Option<mpsc::Sender<String>>> = Mutex::new(None);
+
+/// Writes the events given by `--events` as JSON lines.
+pub struct Feed;
+
+#[derive(Serialize)]
+struct Line<'a, T> {
+	kind: &'a str,
+	body: T,
+}
+
+impl Feed {
+	#[inline]
+	pub fn wants(kind: &str) -> bool { BOOT.events.contains(kind) }
+
+	pub fn push(kind: &str, body: impl Serialize) {
+		let Some(tx) = &*TX.lock() else { return };
+		match serde_json::to_string(&Line { kind, body }) {
+			Ok(s) => _ = tx.send(s),
+			Err(e) => error!("Could not serialize the `{kind}` event: {e}"),
+		}
+	}
+
+	pub fn select<'a>(tab: Id, urls: impl Iterator<Item = &'a Url>) {
+		#[derive(Serialize)]
+		struct Body<'a> {
+			tab:  Id,
+			urls: Vec<&'a Url>,
+		}
+
+		if Self::wants("select") {
+			Self::push("select", Body { tab, urls: urls.collect() });
+		}
+	}
+
+	pub fn task(id: Id, name: &str, state: &str, succ: u32, fail: u32) {
+		#[derive(Serialize)]
+		struct Body<'a> {
+			id:    Id,
+			name:  &'a str,
+			state: &'a str,
+			succ:  u32,
+			fail:  u32,
+		}
+
+		if Self::wants("task") {
+			Self::push("task", Body { id, name, state, succ, fail });
+		}
+	}
+
+	// The writing is done on its own thread, since opening a named pipe blocks
+	// until there's a reader, and stops once the reader is gone.
+	pub(super) fn serve() {
+		if BOOT.events.is_empty() {
+			return;
+		}
+
+		let (tx, rx) = mpsc::channel::<String>();
+		TX.lock().replace(tx);
+
+		std::thread::spawn(move || {
+			let mut w: Box<dyn Write> = match &ARGS.events_file {
+				Some(p) => match File::create(p) {
+					Ok(f) => Box::new(f),
+					Err(e) => {
+						error!("Could not open the events file {p:?}: {e}");
+						return;
+					}
+				},
+				None => Box::new(io::stdout()),
+			};
+
+			while let Ok(line) = rx.recv() {
+				if writeln!(w, "{line}").and_then(|()| w.flush()).is_err() {
+					break;
+				}
+			}
+			TX.lock().take();
+		});
+	}
+}
@@ -4,7 +4,7 @@ mod macros;
 
 yazi_macro::mod_pub!(ember spark);
 
-yazi_macro::mod_flat!(client payload pubsub pump sendable server state stream);
+yazi_macro::mod_flat!(client feed payload pubsub pump sendable server state stream trashed);
 
 pub fn init() {
 	let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
@@ -22,6 +22,7 @@ pub fn init() {
 	// Pubsub
 	LOCAL.with(<_>::default);
 	REMOTE.with(<_>::default);
+	SERVICES.with(<_>::default);
 
 	// Env
 	unsafe {
@@ -37,6 +38,7 @@ pub fn init() {
 }
 
 pub fn serve() {
+	Feed::serve();
 	Pump::serve();
 	Client::serve();
 }
@@ -78,25 +78,7 @@ impl<'a> From<Ember<'a>> for Payload<'a> {
 
 impl Display for Payload<'_> {
 	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-		let result = match &self.body {
-			Ember::Hi(b) => serde_json::to_string(b),
-			Ember::Hey(b) => serde_json::to_string(b),
-			Ember::Bye(b) => serde_json::to_string(b),
-			Ember::Cd(b) => serde_json::to_string(b),
-			Ember::Load(b) => serde_json::to_string(b),
-			Ember::Hover(b) => serde_json::to_string(b),
-			Ember::Tab(b) => serde_json::to_string(b),
-			Ember::Rename(b) => serde_json::to_string(b),
-			Ember::Bulk(b) => serde_json::to_string(b),
-			Ember::Yank(b) => serde_json::to_string(b),
-			Ember::Move(b) => serde_json::to_string(b),
-			Ember::Trash(b) => serde_json::to_string(b),
-			Ember::Delete(b) => serde_json::to_string(b),
-			Ember::Mount(b) => serde_json::to_string(b),
-			Ember::Custom(b) => serde_json::to_string(b),
-		};
-
-		if let Ok(s) = result {
+		if let Ok(s) = serde_json::to_string(&self.body) {
 			write!(f, "{},{},{},{s}", self.body.kind(), self.receiver, self.sender)
 		} else {
 			Err(std::fmt::Error)
@@ -3,16 +3,23 @@ use std::collections::{HashMap, HashSet};
 use anyhow::Result;
 use mlua::Function;
 use parking_lot::RwLock;
+use tokio::sync::mpsc;
 use yazi_boot::BOOT;
-use yazi_fs::FolderStage;
+use yazi_config::YAZI;
+use yazi_fs::{FolderStage, Selection};
 use yazi_shared::{Id, RoCell, url::{CovUrl, Url}};
 
-use crate::{Client, ID, PEERS, ember::{BodyMoveItem, Ember, EmberBulk, EmberHi}};
+use crate::{Client, Feed, ID, PEERS, Payload, Reply, ember::{BodyMoveItem, Ember, EmberBulk, EmberCustom, EmberHi}};
 
 pub static LOCAL: RoCell<RwLock<HashMap<String, HashMap<String, Function>>>> = RoCell::new();
 
 pub static REMOTE: RoCell<RwLock<HashMap<String, HashMap<String, Function>>>> = RoCell::new();
 
+// The channels of the background services subscribed to remote messages
+pub(super) static SERVICES: RoCell<RwLock<HashMap<String, Vec<ServiceTx>>>> = RoCell::new();
+
+type ServiceTx = mpsc::UnboundedSender<Payload<'static>>;
+
 macro_rules! sub {
 	($var:ident) => {
 		|plugin: &str, kind: &str, f: Function| {
@@ -58,6 +65,9 @@ macro_rules! pub_after {
 				if BOOT.local_events.contains(n) {
 					B::borrowed($($borrowed),*).with_receiver(*ID).flush()?;
 				}
+				if Feed::wants(n) {
+					Feed::push(n, B::borrowed($($borrowed),*));
+				}
 				if ($static && Self::any_remote_own(n)) || (!$static && PEERS.read().values().any(|p| p.able(n))) {
 					Client::push(B::borrowed($($borrowed),*))?;
 				}
@@ -97,6 +107,14 @@ impl Pubsub {
 		unsub!(REMOTE)(plugin, kind) && Self::pub_inner_hi()
 	}
 
+	/// Subscribes a background service to the `kind` of messages from remote instances.
+	pub fn sub_service(kind: &str) -> mpsc::UnboundedReceiver<Payload<'static>> {
+		let (tx, rx) = mpsc::unbounded_channel();
+		SERVICES.write().entry(kind.to_owned()).or_default().push(tx);
+		Self::pub_inner_hi();
+		rx
+	}
+
 	pub fn r#pub(body: Ember<'static>) -> Result<()> { body.with_receiver(*ID).emit() }
 
 	pub fn pub_to(receiver: Id, body: Ember<'static>) -> Result<()> {
@@ -114,14 +132,26 @@ impl Pubsub {
 	}
 
 	pub fn pub_inner_hi() -> bool {
-		let abilities = REMOTE.read().keys().cloned().collect();
+		let abilities = REMOTE.read().keys().chain(SERVICES.read().keys()).cloned().collect();
 		let abilities = BOOT.remote_events.union(&abilities).map(AsRef::as_ref);
 
+		// Always able to receive the trashed index to keep it in sync, the yanked
+		// files unless `sync_yanked` is off, and the commands emitted by `ya emit`
+		let abilities = abilities
+			.chain(["@trashed", "dds-emit"])
+			.chain(YAZI.mgr.sync_yanked.then_some("@yank"));
+
 		// FIXME: handle error
 		Client::push(EmberHi::borrowed(abilities)).ok();
 		true
 	}
 
+	/// Replies to the `ya emit` of the `receiver` with how running the command went.
+	pub fn pub_reply(receiver: Id, error: Option<String>) -> Result<()> {
+		let reply = serde_json::to_string(&Reply { error })?;
+		Client::push(EmberCustom::from_str("dds-reply", &reply)?.with_receiver(receiver))
+	}
+
 	pub fn pub_after_bulk<'a, I>(changes: I) -> Result<()>
 	where
 		I: Iterator<Item = (&'a Url, &'a Url)> + Clone,
@@ -129,6 +159,9 @@ impl Pubsub {
 		if BOOT.local_events.contains("bulk") {
 			EmberBulk::borrowed(changes.clone()).with_receiver(*ID).flush()?;
 		}
+		if Feed::wants("bulk") {
+			Feed::push("bulk", EmberBulk::borrowed(changes.clone()));
+		}
 		if PEERS.read().values().any(|p| p.able("bulk")) {
 			Client::push(EmberBulk::borrowed(changes.clone()))?;
 		}
@@ -157,7 +190,7 @@ impl Pubsub {
 
 	pub_after!(rename(tab: Id, from: &Url, to: &Url), (tab, from, to));
 
-	pub_after!(@yank(cut: bool, urls: &HashSet<CovUrl>), (cut, urls));
+	pub_after!(@yank(cut: bool, urls: &HashSet<CovUrl>, lazy: Option<&Selection>), (cut, urls, lazy));
 
 	pub_after!(move(items: Vec<BodyMoveItem>), (&items), (items));
 
@@ -166,4 +199,6 @@ impl Pubsub {
 	pub_after!(delete(urls: Vec<Url>), (&urls), (urls));
 
 	pub_after!(mount(), ());
+
+	pub_after!(@theme(light: bool, flavor: &str), (light, flavor));
 }
@@ -154,6 +154,19 @@ impl Sendable {
 		Ok(args)
 	}
 
+	/// Converts the arguments of a command emitted from the outside.
+	pub fn dict_to_args(dict: HashMap<DataKey, Data>) -> anyhow::Result<HashMap<DataKey, Data>> {
+		let mut args = HashMap::with_capacity(dict.len());
+		for (k, v) in dict {
+			match k {
+				DataKey::Integer(i) if i > 0 => _ = args.insert(DataKey::Integer(i - 1), v),
+				DataKey::String(s) => _ = args.insert(DataKey::String(replace_cow(s, "_", "-")), v),
+				_ => anyhow::bail!("invalid key in Cmd"),
+			}
+		}
+		Ok(args)
+	}
+
 	pub fn args_to_table(lua: &Lua, args: HashMap<DataKey, Data>) -> mlua::Result<Table> {
 		let seq_len = args.keys().filter(|&k| k.is_integer()).count();
 		let tbl = lua.create_table_with_capacity(seq_len, args.len() - seq_len)?;
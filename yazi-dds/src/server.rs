@@ -19,6 +19,10 @@ impl Server {
 
 		Ok(tokio::spawn(async move {
 			while let Ok((stream, _)) = listener.accept().await {
+				if !Stream::trusted(&stream) {
+					continue;
+				}
+
 				let (tx, mut rx) = mpsc::unbounded_channel::<String>();
 				let (reader, mut writer) = tokio::io::split(stream);
 
@@ -10,11 +10,18 @@ pub enum Spark<'a> {
 	// Mgr
 	Arrow(yazi_parser::ArrowOpt),
 	Back(yazi_parser::VoidOpt),
-	BulkRename(yazi_parser::VoidOpt),
+	Bookmark(yazi_parser::mgr::BookmarkOpt),
+	BulkRename(yazi_parser::mgr::BulkRenameOpt),
 	Cd(yazi_parser::mgr::CdOpt),
+	Chmod(yazi_parser::mgr::ChmodOpt),
+	Chown(yazi_parser::mgr::ChownOpt),
 	Close(yazi_parser::mgr::CloseOpt),
+	Compare(yazi_parser::mgr::CompareOpt),
 	Copy(yazi_parser::mgr::CopyOpt),
 	Create(yazi_parser::mgr::CreateOpt),
+	Download(yazi_parser::mgr::DownloadOpt),
+	Dual(yazi_parser::mgr::DualOpt),
+	Dupes(yazi_parser::mgr::DupesOpt),
 	Enter(yazi_parser::VoidOpt),
 	Escape(yazi_parser::mgr::EscapeOpt),
 	EscapeFilter(yazi_parser::VoidOpt),
@@ -22,16 +29,21 @@ pub enum Spark<'a> {
 	EscapeSearch(yazi_parser::VoidOpt),
 	EscapeSelect(yazi_parser::VoidOpt),
 	EscapeVisual(yazi_parser::VoidOpt),
+	Extract(yazi_parser::mgr::ExtractOpt),
 	Filter(yazi_parser::mgr::FilterOpt),
 	FilterDo(yazi_parser::mgr::FilterOpt),
 	Find(yazi_parser::mgr::FindOpt),
 	FindArrow(yazi_parser::mgr::FindArrowOpt),
 	FindDo(yazi_parser::mgr::FindDoOpt),
-	Follow(yazi_parser::VoidOpt),
+	Flatten(yazi_parser::mgr::FlattenOpt),
+	Follow(yazi_parser::mgr::FollowOpt),
 	Forward(yazi_parser::VoidOpt),
+	Grep(yazi_parser::mgr::GrepOpt),
+	GrepDo(yazi_parser::mgr::GrepOpt),
 	Hardlink(yazi_parser::mgr::HardlinkOpt),
 	Hidden(yazi_parser::mgr::HiddenOpt),
 	Hover(yazi_parser::mgr::HoverOpt),
+	Info(yazi_parser::mgr::InfoOpt),
 	Leave(yazi_parser::VoidOpt),
 	Linemode(yazi_parser::mgr::LinemodeOpt),
 	Link(yazi_parser::mgr::LinkOpt),
@@ -41,15 +53,19 @@ pub enum Spark<'a> {
 	Paste(yazi_parser::mgr::PasteOpt),
 	Peek(yazi_parser::mgr::PeekOpt),
 	Quit(yazi_parser::mgr::QuitOpt),
+	Redo(yazi_parser::VoidOpt),
 	Refresh(yazi_parser::VoidOpt),
+	Relink(yazi_parser::mgr::RelinkOpt),
 	Remove(yazi_parser::mgr::RemoveOpt),
 	RemoveDo(yazi_parser::mgr::RemoveOpt),
 	Rename(yazi_parser::mgr::RenameOpt),
 	Reveal(yazi_parser::mgr::RevealOpt),
+	Sanitize(yazi_parser::mgr::SanitizeOpt),
 	Search(yazi_parser::mgr::SearchOpt),
 	SearchDo(yazi_parser::mgr::SearchOpt),
 	SearchStop(yazi_parser::VoidOpt),
 	Seek(yazi_parser::mgr::SeekOpt),
+	Selection(yazi_parser::mgr::SelectionOpt),
 	Shell(yazi_parser::mgr::ShellOpt),
 	Sort(yazi_parser::mgr::SortOpt),
 	Spot(yazi_parser::mgr::SpotOpt),
@@ -58,10 +74,16 @@ pub enum Spark<'a> {
 	TabCreate(yazi_parser::mgr::TabCreateOpt),
 	TabSwap(yazi_parser::ArrowOpt),
 	TabSwitch(yazi_parser::mgr::TabSwitchOpt),
+	Tag(yazi_parser::mgr::TagOpt),
+	Theme(yazi_parser::mgr::ThemeOpt),
 	Toggle(yazi_parser::mgr::ToggleOpt),
 	ToggleAll(yazi_parser::mgr::ToggleAllOpt),
+	Tree(yazi_parser::mgr::TreeOpt),
+	Undo(yazi_parser::VoidOpt),
 	Unyank(yazi_parser::VoidOpt),
+	UpdateCompare(yazi_parser::mgr::UpdateCompareOpt),
 	UpdateFiles(yazi_parser::mgr::UpdateFilesOpt),
+	UpdateJournal(yazi_parser::mgr::UpdateJournalOpt),
 	UpdateMimes(yazi_parser::mgr::UpdateMimesOpt),
 	UpdatePaged(yazi_parser::mgr::UpdatePagedOpt),
 	UpdatePeeked(yazi_parser::mgr::UpdatePeekedOpt),
@@ -70,6 +92,7 @@ pub enum Spark<'a> {
 	UpdateYanked(yazi_parser::mgr::UpdateYankedOpt<'a>),
 	VisualMode(yazi_parser::mgr::VisualModeOpt),
 	Watch(yazi_parser::VoidOpt),
+	Xattr(yazi_parser::mgr::XattrOpt),
 	Yank(yazi_parser::mgr::YankOpt),
 
 	// Cmp
@@ -82,6 +105,7 @@ pub enum Spark<'a> {
 	ConfirmShow(Box<yazi_parser::confirm::ShowOpt>),
 
 	// Help
+	HelpInvoke(yazi_parser::help::InvokeOpt),
 	HelpToggle(yazi_parser::help::ToggleOpt),
 
 	// Input
@@ -108,10 +132,13 @@ pub enum Spark<'a> {
 	SpotCopy(yazi_parser::spot::CopyOpt),
 
 	// Tasks
+	TasksLimit(yazi_parser::tasks::LimitOpt),
 	TasksProcessExec(yazi_parser::tasks::ProcessExecOpt),
+	TasksReorder(yazi_parser::tasks::ReorderOpt),
 
 	// Which
 	WhichCallback(yazi_parser::which::CallbackOpt),
+	WhichPush(yazi_parser::which::PushOpt),
 	WhichShow(yazi_parser::which::ShowOpt),
 }
 
@@ -132,11 +159,18 @@ impl<'a> IntoLua for Spark<'a> {
 			// Mgr
 			Self::Arrow(b) => b.into_lua(lua),
 			Self::Back(b) => b.into_lua(lua),
+			Self::Bookmark(b) => b.into_lua(lua),
 			Self::BulkRename(b) => b.into_lua(lua),
 			Self::Cd(b) => b.into_lua(lua),
+			Self::Chmod(b) => b.into_lua(lua),
+			Self::Chown(b) => b.into_lua(lua),
 			Self::Close(b) => b.into_lua(lua),
+			Self::Compare(b) => b.into_lua(lua),
 			Self::Copy(b) => b.into_lua(lua),
 			Self::Create(b) => b.into_lua(lua),
+			Self::Download(b) => b.into_lua(lua),
+			Self::Dual(b) => b.into_lua(lua),
+			Self::Dupes(b) => b.into_lua(lua),
 			Self::Enter(b) => b.into_lua(lua),
 			Self::Escape(b) => b.into_lua(lua),
 			Self::EscapeFilter(b) => b.into_lua(lua),
@@ -144,16 +178,21 @@ impl<'a> IntoLua for Spark<'a> {
 			Self::EscapeSearch(b) => b.into_lua(lua),
 			Self::EscapeSelect(b) => b.into_lua(lua),
 			Self::EscapeVisual(b) => b.into_lua(lua),
+			Self::Extract(b) => b.into_lua(lua),
 			Self::Filter(b) => b.into_lua(lua),
 			Self::FilterDo(b) => b.into_lua(lua),
 			Self::Find(b) => b.into_lua(lua),
 			Self::FindArrow(b) => b.into_lua(lua),
 			Self::FindDo(b) => b.into_lua(lua),
+			Self::Flatten(b) => b.into_lua(lua),
 			Self::Follow(b) => b.into_lua(lua),
 			Self::Forward(b) => b.into_lua(lua),
+			Self::Grep(b) => b.into_lua(lua),
+			Self::GrepDo(b) => b.into_lua(lua),
 			Self::Hardlink(b) => b.into_lua(lua),
 			Self::Hidden(b) => b.into_lua(lua),
 			Self::Hover(b) => b.into_lua(lua),
+			Self::Info(b) => b.into_lua(lua),
 			Self::Leave(b) => b.into_lua(lua),
 			Self::Linemode(b) => b.into_lua(lua),
 			Self::Link(b) => b.into_lua(lua),
@@ -163,15 +202,19 @@ impl<'a> IntoLua for Spark<'a> {
 			Self::Paste(b) => b.into_lua(lua),
 			Self::Peek(b) => b.into_lua(lua),
 			Self::Quit(b) => b.into_lua(lua),
+			Self::Redo(b) => b.into_lua(lua),
 			Self::Refresh(b) => b.into_lua(lua),
+			Self::Relink(b) => b.into_lua(lua),
 			Self::Remove(b) => b.into_lua(lua),
 			Self::RemoveDo(b) => b.into_lua(lua),
 			Self::Rename(b) => b.into_lua(lua),
 			Self::Reveal(b) => b.into_lua(lua),
+			Self::Sanitize(b) => b.into_lua(lua),
 			Self::Search(b) => b.into_lua(lua),
 			Self::SearchDo(b) => b.into_lua(lua),
 			Self::SearchStop(b) => b.into_lua(lua),
 			Self::Seek(b) => b.into_lua(lua),
+			Self::Selection(b) => b.into_lua(lua),
 			Self::Shell(b) => b.into_lua(lua),
 			Self::Sort(b) => b.into_lua(lua),
 			Self::Spot(b) => b.into_lua(lua),
@@ -180,10 +223,16 @@ impl<'a> IntoLua for Spark<'a> {
 			Self::TabCreate(b) => b.into_lua(lua),
 			Self::TabSwap(b) => b.into_lua(lua),
 			Self::TabSwitch(b) => b.into_lua(lua),
+			Self::Tag(b) => b.into_lua(lua),
+			Self::Theme(b) => b.into_lua(lua),
 			Self::Toggle(b) => b.into_lua(lua),
 			Self::ToggleAll(b) => b.into_lua(lua),
+			Self::Tree(b) => b.into_lua(lua),
+			Self::Undo(b) => b.into_lua(lua),
 			Self::Unyank(b) => b.into_lua(lua),
+			Self::UpdateCompare(b) => b.into_lua(lua),
 			Self::UpdateFiles(b) => b.into_lua(lua),
+			Self::UpdateJournal(b) => b.into_lua(lua),
 			Self::UpdateMimes(b) => b.into_lua(lua),
 			Self::UpdatePaged(b) => b.into_lua(lua),
 			Self::UpdatePeeked(b) => b.into_lua(lua),
@@ -192,6 +241,7 @@ impl<'a> IntoLua for Spark<'a> {
 			Self::UpdateYanked(b) => b.into_lua(lua),
 			Self::VisualMode(b) => b.into_lua(lua),
 			Self::Watch(b) => b.into_lua(lua),
+			Self::Xattr(b) => b.into_lua(lua),
 			Self::Yank(b) => b.into_lua(lua),
 
 			// Cmp
@@ -204,6 +254,7 @@ impl<'a> IntoLua for Spark<'a> {
 			Self::ConfirmShow(b) => b.into_lua(lua),
 
 			// Help
+			Self::HelpInvoke(b) => b.into_lua(lua),
 			Self::HelpToggle(b) => b.into_lua(lua),
 
 			// Input
@@ -230,10 +281,13 @@ impl<'a> IntoLua for Spark<'a> {
 			Self::SpotCopy(b) => b.into_lua(lua),
 
 			// Tasks
+			Self::TasksLimit(b) => b.into_lua(lua),
 			Self::TasksProcessExec(b) => b.into_lua(lua),
+			Self::TasksReorder(b) => b.into_lua(lua),
 
 			// Which
 			Self::WhichCallback(b) => b.into_lua(lua),
+			Self::WhichPush(b) => b.into_lua(lua),
 			Self::WhichShow(b) => b.into_lua(lua),
 		}
 	}
@@ -242,19 +296,19 @@ impl<'a> IntoLua for Spark<'a> {
 try_from_spark!(
 	VoidOpt,
 	mgr:back,
-	mgr:bulk_rename,
 	mgr:enter,
 	mgr:escape_filter,
 	mgr:escape_find,
 	mgr:escape_search,
 	mgr:escape_select,
 	mgr:escape_visual,
-	mgr:follow,
 	mgr:forward,
 	mgr:leave,
+	mgr:redo,
 	mgr:refresh,
 	mgr:search_stop,
 	mgr:suspend,
+	mgr:undo,
 	mgr:unyank,
 	mgr:watch
 );
@@ -264,6 +318,7 @@ try_from_spark!(cmp::ShowOpt, cmp:show);
 try_from_spark!(cmp::TriggerOpt, cmp:trigger);
 try_from_spark!(confirm::CloseOpt, confirm:close);
 try_from_spark!(confirm::ShowOpt, confirm:show);
+try_from_spark!(help::InvokeOpt, help:invoke);
 try_from_spark!(help::ToggleOpt, help:toggle);
 try_from_spark!(input::BackspaceOpt, input:backspace);
 try_from_spark!(input::BackwardOpt, input:backward);
@@ -276,18 +331,31 @@ try_from_spark!(input::KillOpt, input:kill);
 try_from_spark!(input::MoveOpt, input:move);
 try_from_spark!(input::PasteOpt, input:paste);
 try_from_spark!(input::ShowOpt, input:show);
+try_from_spark!(mgr::BookmarkOpt, mgr:bookmark);
+try_from_spark!(mgr::BulkRenameOpt, mgr:bulk_rename);
 try_from_spark!(mgr::CdOpt, mgr:cd);
+try_from_spark!(mgr::ChmodOpt, mgr:chmod);
+try_from_spark!(mgr::ChownOpt, mgr:chown);
 try_from_spark!(mgr::CloseOpt, mgr:close);
+try_from_spark!(mgr::CompareOpt, mgr:compare);
 try_from_spark!(mgr::CopyOpt, mgr:copy);
 try_from_spark!(mgr::CreateOpt, mgr:create);
+try_from_spark!(mgr::DownloadOpt, mgr:download);
+try_from_spark!(mgr::DualOpt, mgr:dual);
+try_from_spark!(mgr::DupesOpt, mgr:dupes);
 try_from_spark!(mgr::EscapeOpt, mgr:escape);
+try_from_spark!(mgr::ExtractOpt, mgr:extract);
 try_from_spark!(mgr::FilterOpt, mgr:filter, mgr:filter_do);
 try_from_spark!(mgr::FindArrowOpt, mgr:find_arrow);
 try_from_spark!(mgr::FindDoOpt, mgr:find_do);
 try_from_spark!(mgr::FindOpt, mgr:find);
+try_from_spark!(mgr::FlattenOpt, mgr:flatten);
+try_from_spark!(mgr::FollowOpt, mgr:follow);
+try_from_spark!(mgr::GrepOpt, mgr:grep, mgr:grep_do);
 try_from_spark!(mgr::HardlinkOpt, mgr:hardlink);
 try_from_spark!(mgr::HiddenOpt, mgr:hidden);
 try_from_spark!(mgr::HoverOpt, mgr:hover);
+try_from_spark!(mgr::InfoOpt, mgr:info);
 try_from_spark!(mgr::LinemodeOpt, mgr:linemode);
 try_from_spark!(mgr::LinkOpt, mgr:link);
 try_from_spark!(mgr::OpenDoOpt, mgr:open_do);
@@ -296,20 +364,28 @@ try_from_spark!(mgr::OpenWithOpt, mgr:open_with);
 try_from_spark!(mgr::PasteOpt, mgr:paste);
 try_from_spark!(mgr::PeekOpt, mgr:peek);
 try_from_spark!(mgr::QuitOpt, mgr:quit);
+try_from_spark!(mgr::RelinkOpt, mgr:relink);
 try_from_spark!(mgr::RemoveOpt, mgr:remove, mgr:remove_do);
 try_from_spark!(mgr::RenameOpt, mgr:rename);
 try_from_spark!(mgr::RevealOpt, mgr:reveal);
+try_from_spark!(mgr::SanitizeOpt, mgr:sanitize);
 try_from_spark!(mgr::SearchOpt, mgr:search, mgr:search_do);
 try_from_spark!(mgr::SeekOpt, mgr:seek);
+try_from_spark!(mgr::SelectionOpt, mgr:selection);
 try_from_spark!(mgr::ShellOpt, mgr:shell);
 try_from_spark!(mgr::SortOpt, mgr:sort);
 try_from_spark!(mgr::SpotOpt, mgr:spot);
 try_from_spark!(mgr::TabCloseOpt, mgr:tab_close);
 try_from_spark!(mgr::TabCreateOpt, mgr:tab_create);
 try_from_spark!(mgr::TabSwitchOpt, mgr:tab_switch);
+try_from_spark!(mgr::TagOpt, mgr:tag);
+try_from_spark!(mgr::ThemeOpt, mgr:theme);
 try_from_spark!(mgr::ToggleAllOpt, mgr:toggle_all);
+try_from_spark!(mgr::TreeOpt, mgr:tree);
 try_from_spark!(mgr::ToggleOpt, mgr:toggle);
+try_from_spark!(mgr::UpdateCompareOpt, mgr:update_compare);
 try_from_spark!(mgr::UpdateFilesOpt, mgr:update_files);
+try_from_spark!(mgr::UpdateJournalOpt, mgr:update_journal);
 try_from_spark!(mgr::UpdateMimesOpt, mgr:update_mimes);
 try_from_spark!(mgr::UpdatePagedOpt, mgr:update_paged);
 try_from_spark!(mgr::UpdatePeekedOpt, mgr:update_peeked);
@@ -317,11 +393,15 @@ try_from_spark!(mgr::UpdateSpottedOpt, mgr:update_spotted);
 try_from_spark!(mgr::UpdateTasksOpt, mgr:update_tasks);
 try_from_spark!(mgr::UpdateYankedOpt<'a>, mgr:update_yanked);
 try_from_spark!(mgr::VisualModeOpt, mgr:visual_mode);
+try_from_spark!(mgr::XattrOpt, mgr:xattr);
 try_from_spark!(mgr::YankOpt, mgr:yank);
 try_from_spark!(notify::TickOpt, notify:tick);
 try_from_spark!(pick::CloseOpt, pick:close);
 try_from_spark!(pick::ShowOpt, pick:show);
 try_from_spark!(spot::CopyOpt, spot:copy);
+try_from_spark!(tasks::LimitOpt, tasks:limit);
 try_from_spark!(tasks::ProcessExecOpt, tasks:process_exec);
+try_from_spark!(tasks::ReorderOpt, tasks:reorder);
 try_from_spark!(which::CallbackOpt, which:callback);
+try_from_spark!(which::PushOpt, which:push);
 try_from_spark!(which::ShowOpt, which:show);
@@ -36,10 +36,14 @@ impl Stream {
 
 	#[cfg(unix)]
 	pub(super) async fn bind() -> std::io::Result<ServerListener> {
+		use std::{fs::Permissions, os::unix::fs::PermissionsExt};
+
 		let p = Self::socket_file();
 
 		yazi_fs::provider::local::Local::remove_file(&p).await.ok();
-		tokio::net::UnixListener::bind(p)
+		let listener = tokio::net::UnixListener::bind(&p)?;
+		std::fs::set_permissions(p, Permissions::from_mode(0o600))?;
+		Ok(listener)
 	}
 
 	#[cfg(not(unix))]
@@ -47,6 +51,21 @@ impl Stream {
 		tokio::net::TcpListener::bind("127.0.0.1:33581").await
 	}
 
+	/// Whether the peer is run by the same user.
+	#[cfg(unix)]
+	pub(super) fn trusted(stream: &tokio::net::UnixStream) -> bool {
+		use uzers::Users;
+		use yazi_shared::USERS_CACHE;
+
+		stream.peer_cred().is_ok_and(|c| c.uid() == USERS_CACHE.get_current_uid())
+	}
+
+	/// Whether the peer is on this machine, where the users can't be told apart.
+	#[cfg(not(unix))]
+	pub(super) fn trusted(stream: &tokio::net::TcpStream) -> bool {
+		stream.peer_addr().is_ok_and(|a| a.ip().is_loopback())
+	}
+
 	#[cfg(unix)]
 	fn socket_file() -> std::path::PathBuf {
 		use std::env::temp_dir;
@@ -0,0 +1,95 @@
+use std::collections::HashSet;
+
+use anyhow::Result;
+use parking_lot::RwLock;
+
+use crate::{Client, ember::{BodyTrashedItem, EmberTrashed}};
+
+/// Files trashed by Yazi, newest first.
+pub static TRASHED: RwLock<Vec<BodyTrashedItem>> = RwLock::new(Vec::new());
+
+pub struct Trashed;
+
+impl Trashed {
+	/// The most items kept, the oldest ones are forgotten first.
+	const CAP: usize = 200;
+
+	#[inline]
+	pub fn list() -> Vec<BodyTrashedItem> { TRASHED.read().clone() }
+
+	/// Records newly trashed items and syncs them to other instances.
+	pub fn record(items: Vec<BodyTrashedItem>) -> Result<()> {
+		let mut trashed = TRASHED.write();
+		if Self::merge(&mut trashed, items) { Self::publish(&trashed) } else { Ok(()) }
+	}
+
+	/// Forgets the items that no longer satisfy `f`, and syncs it to other instances.
+	pub fn retain(mut f: impl FnMut(&BodyTrashedItem) -> bool) -> Result<()> {
+		let mut trashed = TRASHED.write();
+
+		let old = trashed.len();
+		trashed.retain(|item| f(item));
+		if trashed.len() != old { Self::publish(&trashed) } else { Ok(()) }
+	}
+
+	/// Accepts the index from the state or another instance.
+	pub(super) fn accept(items: Vec<BodyTrashedItem>) -> Result<()> {
+		let mut trashed = TRASHED.write();
+		let received: HashSet<_> = items.iter().map(|t| t.id.clone()).collect();
+
+		Self::merge(&mut trashed, items);
+		if trashed.iter().any(|t| !received.contains(&t.id)) { Self::publish(&trashed) } else { Ok(()) }
+	}
+
+	fn merge(trashed: &mut Vec<BodyTrashedItem>, items: Vec<BodyTrashedItem>) -> bool {
+		let old = trashed.clone();
+		for item in items {
+			if !trashed.iter().any(|t| t.id == item.id) {
+				trashed.push(item);
+			}
+		}
+
+		trashed.sort_by(|a, b| b.at.cmp(&a.at).then_with(|| a.id.cmp(&b.id)));
+		trashed.truncate(Self::CAP);
+		*trashed != old
+	}
+
+	#[inline]
+	fn publish(trashed: &Vec<BodyTrashedItem>) -> Result<()> {
+		Client::push(EmberTrashed::borrowed(trashed))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::path::PathBuf;
+
+	use yazi_shared::{Id, url::Url};
+
+	use super::*;
+
+	fn item(id: &str, at: i64) -> BodyTrashedItem {
+		let url = Url::from(PathBuf::from(format!("/tmp/{id}")));
+		BodyTrashedItem { url, id: id.to_owned(), at, sender: Id(1) }
+	}
+
+	#[test]
+	fn test_merge() {
+		let mut trashed = vec![];
+		assert!(Trashed::merge(&mut trashed, vec![item("a", 1), item("b", 3)]));
+		assert_eq!(trashed, [item("b", 3), item("a", 1)]);
+
+		// Nothing new
+		assert!(!Trashed::merge(&mut trashed, vec![item("a", 1)]));
+
+		// Items trashed by another instance in the meantime
+		assert!(Trashed::merge(&mut trashed, vec![item("c", 2), item("b", 3)]));
+		assert_eq!(trashed, [item("b", 3), item("c", 2), item("a", 1)]);
+
+		// The oldest ones are forgotten once it's full
+		let items = (0..Trashed::CAP as i64).map(|i| item(&format!("n{i}"), 10 + i)).collect();
+		assert!(Trashed::merge(&mut trashed, items));
+		assert_eq!(trashed.len(), Trashed::CAP);
+		assert!(trashed.iter().all(|t| t.at >= 10));
+	}
+}
@@ -0,0 +1,14 @@
+use core_foundation_sys::{array::CFArrayRef, error::CFErrorRef, url::CFURLRef};
+
+pub const LS_ROLES_ALL: u32 = 0xFFFFFFFF;
+
+#[link(name = "CoreServices", kind = "framework")]
+unsafe extern "C" {
+	pub fn LSCopyApplicationURLsForURL(url: CFURLRef, roles: u32) -> CFArrayRef;
+
+	pub fn LSCopyDefaultApplicationURLForURL(
+		url: CFURLRef,
+		roles: u32,
+		error: *mut CFErrorRef,
+	) -> CFURLRef;
+}
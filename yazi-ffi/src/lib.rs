@@ -1,2 +1,2 @@
 #[cfg(target_os = "macos")]
-yazi_macro::mod_flat!(cf_dict cf_string disk_arbitration io_kit);
+yazi_macro::mod_flat!(cf_dict cf_string disk_arbitration io_kit launch_services);
@@ -0,0 +1,20 @@
+use ratatui::{buffer::Buffer, layout::Rect, widgets::{Paragraph, Widget, Wrap}};
+use yazi_core::Core;
+
+/// The announcements shown instead of the panes in the accessible mode.
+pub(crate) struct Announce<'a> {
+	core: &'a Core,
+}
+
+impl<'a> Announce<'a> {
+	#[inline]
+	pub(crate) fn new(core: &'a Core) -> Self { Self { core } }
+}
+
+impl Widget for Announce<'_> {
+	fn render(self, area: Rect, buf: &mut Buffer) {
+		for (rect, line) in self.core.announcer.window(area) {
+			Paragraph::new(line).wrap(Wrap { trim: false }).render(rect, buf);
+		}
+	}
+}
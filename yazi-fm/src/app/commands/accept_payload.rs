@@ -3,19 +3,25 @@ use mlua::IntoLua;
 use tracing::error;
 use yazi_actor::lives::Lives;
 use yazi_binding::runtime_mut;
-use yazi_dds::{LOCAL, Payload, REMOTE};
+use yazi_dds::{LOCAL, Payload, Pubsub, REMOTE, Sendable, ember::Ember};
 use yazi_macro::succ;
 use yazi_plugin::LUA;
-use yazi_shared::event::{CmdCow, Data};
+use yazi_shared::{Layer, Source, event::{Cmd, CmdCow, Data}};
 
-use crate::app::App;
+use crate::{Executor, app::App};
 
 impl App {
-	pub(crate) fn accept_payload(&self, mut c: CmdCow) -> Result<Data> {
+	pub(crate) fn accept_payload(&mut self, mut c: CmdCow) -> Result<Data> {
 		let Some(payload) = c.take_any2::<Payload>("payload").transpose()? else {
 			bail!("'payload' is required for accept_payload");
 		};
 
+		if payload.body.kind() == "dds-emit" {
+			let Ember::Custom(b) = payload.body else { succ!() };
+			let result = Self::parse_emit(b.data).and_then(|cmd| Executor::new(self).execute(cmd.into()));
+			succ!(Pubsub::pub_reply(payload.sender, result.err().map(|e| format!("{e:#}")))?);
+		}
+
 		let kind = payload.body.kind().to_owned();
 		let lock = if payload.receiver == 0 || payload.receiver != payload.sender {
 			REMOTE.read()
@@ -38,4 +44,19 @@ impl App {
 			Ok(())
 		})?);
 	}
+
+	// The command emitted by `ya emit`, in the form of `[name, args]`
+	fn parse_emit(data: Data) -> Result<Cmd> {
+		let Data::List(list) = data else { bail!("Invalid command, expected `[name, args]`") };
+		let Ok([name, args]) = <[Data; 2]>::try_from(list) else {
+			bail!("Invalid command, expected `[name, args]`");
+		};
+		let (Some(name), Some(args)) = (name.into_string(), args.into_dict()) else {
+			bail!("Invalid command, expected a string name and a table of arguments");
+		};
+
+		let mut cmd = Cmd::new(name, Source::Emit, Some(Layer::Mgr))?;
+		cmd.args = Sendable::dict_to_args(args)?;
+		Ok(cmd)
+	}
 }
@@ -1,14 +1,45 @@
 use anyhow::Result;
-use yazi_actor::Ctx;
-use yazi_boot::BOOT;
+use yazi_actor::{Ctx, mgr::Theme};
+use yazi_boot::{ARGS, BOOT};
+use yazi_config::YAZI;
+use yazi_fs::{FRECENCY, Session, TAGS, VIEWS};
 use yazi_macro::act;
 use yazi_parser::{VoidOpt, mgr::CdSource};
-use yazi_shared::event::Data;
+use yazi_plugin::isolate;
+use yazi_proxy::AppProxy;
+use yazi_shared::event::{CmdCow, Data};
 
 use crate::app::App;
 
 impl App {
 	pub fn bootstrap(&mut self, _: VoidOpt) -> Result<Data> {
+		// Before the first `cd`, which is recorded as a visit
+		FRECENCY.load().ok();
+		TAGS.load().ok();
+		if YAZI.mgr.remember_views {
+			VIEWS.load().ok();
+		}
+
+		// The services of plugins, running in the background until Yazi exits
+		for service in &YAZI.plugin.services {
+			tokio::spawn(async move {
+				if let Err(e) = isolate::serve(CmdCow::from(&service.run)).await {
+					AppProxy::notify_error(&format!("Service `{}` failed", service.run.name), e);
+				}
+			});
+		}
+
+		// Switch the theme along with the dark mode of the OS
+		Theme::follow_os();
+
+		if ARGS.resume {
+			match Session::load() {
+				Ok(session) if !session.tabs.is_empty() => return self.resume(session),
+				Ok(_) => AppProxy::notify_warn("Resume failed", "The last session has no tabs"),
+				Err(e) => AppProxy::notify_warn("Resume failed", format!("No session to resume: {e}")),
+			}
+		}
+
 		for (i, file) in BOOT.files.iter().enumerate() {
 			let tabs = &mut self.core.mgr.tabs;
 			if tabs.len() <= i {
@@ -27,4 +58,41 @@ impl App {
 
 		act!(render, self)
 	}
+
+	fn resume(&mut self, session: Session) -> Result<Data> {
+		for (i, t) in session.tabs.into_iter().enumerate() {
+			let tabs = &mut self.core.mgr.tabs;
+			if tabs.len() <= i {
+				tabs.push(Default::default());
+			}
+
+			let pref = &mut tabs[i].pref;
+			pref.sort_by = t.sorter.by;
+			pref.sort_sensitive = t.sorter.sensitive;
+			pref.sort_reverse = t.sorter.reverse;
+			pref.sort_dir_first = t.sorter.dir_first;
+			pref.sort_translit = t.sorter.translit;
+
+			// Search results are a snapshot, so a search is resumed in the directory
+			// it was run in, with the found files that were selected still selected
+			let selected: Vec<_> =
+				t.selected.into_iter().map(|u| if u.is_search() { u.into_regular() } else { u }).collect();
+			tabs[i].selected.add_many(&selected);
+
+			let cx = &mut Ctx::active(&mut self.core);
+			cx.tab = i;
+
+			if t.cwd.is_search() {
+				act!(mgr:cd, cx, (t.cwd.into_regular(), CdSource::Tab))?;
+			} else if let Some(h) = t.hovered {
+				act!(mgr:reveal, cx, (h, CdSource::Tab))?;
+			} else {
+				act!(mgr:cd, cx, (t.cwd, CdSource::Tab))?;
+			}
+		}
+
+		self.core.mgr.tabs.set_idx(session.active);
+		act!(render, self)
+	}
+
 }
@@ -12,6 +12,7 @@ impl App {
 			content: opt.content,
 			level:   NotifyLevel::Warn,
 			timeout: std::time::Duration::from_secs(20),
+			action:  None,
 		}));
 	}
 }
@@ -1,38 +1,87 @@
-use std::ffi::OsString;
+use std::{ffi::OsString, path::PathBuf};
 
 use yazi_boot::ARGS;
-use yazi_fs::provider::local::Local;
+use yazi_config::YAZI;
+use yazi_fs::{AUDIT, DUPES, FRECENCY, INDEX, Session, SessionTab, TAGS, VIEWS, provider::local::Local};
+use yazi_scheduler::{SUMMARY, SummaryReport, TaskKind};
 use yazi_shared::event::EventQuit;
 
 use crate::{Term, app::App};
 
 impl App {
 	pub(crate) fn quit(&mut self, opt: EventQuit) -> ! {
+		let incomplete =
+			self.core.tasks.ongoing().lock().values().filter(|t| t.kind == TaskKind::User).count();
+
+		self.session_to_file();
+		INDEX.save().ok();
+		DUPES.save().ok();
+		FRECENCY.save().ok();
+		TAGS.save().ok();
+		VIEWS.save().ok();
 		self.core.tasks.shutdown();
 		self.core.mgr.shutdown();
+		AUDIT.flush();
 
-		futures::executor::block_on(async {
-			_ = futures::join!(
+		let report = futures::executor::block_on(async {
+			let (.., cwd, selected) = futures::join!(
 				yazi_dds::shutdown(),
 				yazi_dds::STATE.drain(),
 				self.cwd_to_file(opt.no_cwd_file),
 				self.selected_to_file(opt.selected)
 			);
+
+			let report = SUMMARY.report(incomplete, cwd.into_iter().chain(selected).collect());
+			Self::summary_to_file(&report).await;
+			report
+		});
+
+		Term::goodbye(|| {
+			// Printed after leaving the alternate screen, so it stays on the normal screen
+			if !ARGS.quiet && ARGS.chooser_file.is_none() && !report.is_empty() {
+				print!("{report}");
+			}
+			opt.code
 		});
+	}
+
+	async fn cwd_to_file(&self, no: bool) -> Option<PathBuf> {
+		let p = ARGS.cwd_file.as_ref().filter(|_| !no)?;
+		let cwd = self.core.mgr.cwd().os_str();
+		Local::write(p, cwd.as_encoded_bytes()).await.ok().map(|_| p.clone())
+	}
 
-		Term::goodbye(|| opt.code);
+	async fn selected_to_file(&self, selected: Option<OsString>) -> Option<PathBuf> {
+		let (s, p) = (selected?, ARGS.chooser_file.as_ref()?);
+		Local::write(p, s.as_encoded_bytes()).await.ok().map(|_| p.clone())
 	}
 
-	async fn cwd_to_file(&self, no: bool) {
-		if let Some(p) = ARGS.cwd_file.as_ref().filter(|_| !no) {
-			let cwd = self.core.mgr.cwd().os_str();
-			Local::write(p, cwd.as_encoded_bytes()).await.ok();
+	fn session_to_file(&self) {
+		if !YAZI.mgr.save_session {
+			return;
 		}
+
+		let tabs = &self.core.mgr.tabs;
+		let session = Session {
+			active: tabs.cursor,
+			tabs:   tabs
+				.iter()
+				.map(|t| SessionTab {
+					cwd:      t.cwd().clone(),
+					hovered:  t.hovered().map(|h| h.url.clone()),
+					sorter:   (&t.pref).into(),
+					selected: t.selected.values().cloned().collect(),
+				})
+				.collect(),
+		};
+		session.save().ok();
 	}
 
-	async fn selected_to_file(&self, selected: Option<OsString>) {
-		if let (Some(s), Some(p)) = (selected, &ARGS.chooser_file) {
-			Local::write(p, s.as_encoded_bytes()).await.ok();
+	async fn summary_to_file(report: &SummaryReport) {
+		if let Some(p) = &ARGS.summary_file
+			&& let Ok(s) = report.to_json()
+		{
+			Local::write(p, s).await.ok();
 		}
 	}
 }
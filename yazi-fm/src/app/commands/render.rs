@@ -2,9 +2,13 @@ use std::sync::atomic::{AtomicU8, Ordering};
 
 use anyhow::Result;
 use crossterm::{cursor::{MoveTo, SetCursorStyle, Show}, execute, queue, terminal::{BeginSynchronizedUpdate, EndSynchronizedUpdate}};
-use ratatui::{CompletedFrame, backend::{Backend, CrosstermBackend}, buffer::Buffer, layout::Position};
+use ratatui::{CompletedFrame, backend::{Backend, CrosstermBackend}, buffer::Buffer, layout::{Position, Rect}};
 use yazi_actor::{Ctx, lives::Lives};
+use yazi_adapter::Dimension;
 use yazi_binding::elements::COLLISION;
+use yazi_boot::BOOT;
+use yazi_core::Core;
+use yazi_dds::Feed;
 use yazi_macro::{act, succ};
 use yazi_parser::VoidOpt;
 use yazi_shared::event::{Data, NEED_RENDER};
@@ -15,10 +19,19 @@ use crate::{app::App, root::Root};
 impl App {
 	pub(crate) fn render(&mut self, _: VoidOpt) -> Result<Data> {
 		NEED_RENDER.store(false, Ordering::Relaxed);
+		for tab in self.core.mgr.tabs.iter_mut() {
+			if tab.selected.take_changed() {
+				Feed::select(tab.id, tab.selected.values());
+			}
+		}
+
 		let Some(term) = &mut self.term else { succ!() };
+		if BOOT.accessible {
+			self.core.announce();
+		}
 
 		Self::routine(true, None);
-		let _guard = scopeguard::guard(self.core.cursor(), |c| Self::routine(false, c));
+		let _guard = scopeguard::guard(Self::cursor(&self.core), |c| Self::routine(false, c));
 
 		let collision = COLLISION.swap(false, Ordering::Relaxed);
 		let frame = term
@@ -30,7 +43,7 @@ impl App {
 		if COLLISION.load(Ordering::Relaxed) {
 			Self::patch(frame);
 		}
-		if !self.core.notify.messages.is_empty() {
+		if !self.core.notify.messages.is_empty() && !BOOT.accessible {
 			act!(render_partially, self)?;
 		}
 
@@ -44,7 +57,7 @@ impl App {
 
 	pub(crate) fn render_partially(&mut self, _: VoidOpt) -> Result<Data> {
 		let Some(term) = &mut self.term else { succ!() };
-		if !term.can_partial() {
+		if !term.can_partial() || BOOT.accessible {
 			return act!(render, self);
 		}
 
@@ -67,6 +80,20 @@ impl App {
 		succ!();
 	}
 
+	// In the accessible mode, the cursor is put on the latest announcement, which
+	// is what the screen readers follow, unless a popup takes it
+	fn cursor(core: &Core) -> Option<(Position, SetCursorStyle)> {
+		if let Some(c) = core.cursor() {
+			return Some(c);
+		} else if !BOOT.accessible {
+			return None;
+		}
+
+		let Dimension { rows, columns, .. } = Dimension::available();
+		let (rect, _) = core.announcer.window(Rect::new(0, 0, columns, rows)).pop()?;
+		Some((rect.as_position(), SetCursorStyle::DefaultUserShape))
+	}
+
 	#[inline]
 	fn patch(frame: CompletedFrame) {
 		let mut new = Buffer::empty(frame.area);
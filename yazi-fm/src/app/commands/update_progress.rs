@@ -21,6 +21,9 @@ impl App {
 				let cx = &mut Ctx::active(&mut self.core);
 				act!(tasks:arrow, cx)?;
 				succ!(render!());
+			} else if progressed {
+				// For the speed at the bottom
+				succ!(render!());
 			}
 		}
 
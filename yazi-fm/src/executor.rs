@@ -1,7 +1,7 @@
-use anyhow::Result;
+use anyhow::{Result, bail};
 use yazi_actor::Ctx;
 use yazi_macro::{act, succ};
-use yazi_shared::{Layer, event::{CmdCow, Data}};
+use yazi_shared::{Layer, Source, event::{CmdCow, Data}};
 use yazi_widgets::input::InputMode;
 
 use crate::app::App;
@@ -20,6 +20,7 @@ impl<'a> Executor<'a> {
 			Layer::App => self.app(cmd),
 			Layer::Mgr => self.mgr(cmd),
 			Layer::Tasks => self.tasks(cmd),
+			Layer::Notify => self.notify(cmd),
 			Layer::Spot => self.spot(cmd),
 			Layer::Pick => self.pick(cmd),
 			Layer::Input => self.input(cmd),
@@ -50,7 +51,7 @@ impl<'a> Executor<'a> {
 		on!(resume);
 		on!(deprecate);
 
-		succ!();
+		Self::unknown(&cmd)
 	}
 
 	fn mgr(&mut self, cmd: CmdCow) -> Result<Data> {
@@ -68,13 +69,16 @@ impl<'a> Executor<'a> {
 		on!(update_tasks);
 		on!(update_yanked);
 
+		on!(update_compare);
 		on!(update_files);
+		on!(update_journal);
 		on!(update_mimes);
 		on!(update_paged);
 		on!(watch);
 		on!(peek);
 		on!(seek);
 		on!(spot);
+		on!(info);
 		on!(refresh);
 		on!(quit);
 		on!(close);
@@ -91,31 +95,50 @@ impl<'a> Executor<'a> {
 		on!(forward);
 		on!(reveal);
 		on!(follow);
+		on!(bookmark);
 
 		// Toggle
 		on!(toggle);
 		on!(toggle_all);
 		on!(visual_mode);
+		on!(selection);
+		on!(tag);
+		on!(xattr);
 
 		// Operation
 		on!(open);
 		on!(open_do);
+		on!(menu);
 		on!(yank);
 		on!(unyank);
 		on!(paste);
 		on!(link);
+		on!(relink);
 		on!(hardlink);
+		on!(extract);
+		on!(download);
+		on!(dupes);
 		on!(remove);
 		on!(remove_do);
+		on!(restore);
 		on!(create);
 		on!(rename);
 		on!(copy);
 		on!(shell);
+		on!(chmod);
+		on!(chown);
 		on!(hidden);
 		on!(linemode);
 		on!(search);
 		on!(search_do);
+		on!(grep);
+		on!(grep_do);
+		on!(flatten);
+		on!(tree);
 		on!(bulk_rename);
+		on!(sanitize);
+		on!(undo);
+		on!(redo);
 
 		// Filter
 		on!(filter);
@@ -135,12 +158,19 @@ impl<'a> Executor<'a> {
 		on!(tab_switch);
 		on!(tab_swap);
 
+		// Dual panel
+		on!(dual);
+		on!(compare);
+
+		// Theme
+		on!(theme);
+
 		match cmd.name.as_ref() {
 			// Help
 			"help" => act!(help:toggle, cx, Layer::Mgr),
 			// Plugin
 			"plugin" => act!(plugin, self.app, cmd),
-			_ => succ!(),
+			_ => Self::unknown(&cmd),
 		}
 	}
 
@@ -160,15 +190,46 @@ impl<'a> Executor<'a> {
 		on!(arrow);
 		on!(inspect);
 		on!(cancel);
+		on!(pause);
+		on!(reorder);
+		on!(limit);
 		on!(open_with);
 		on!(process_exec);
+		on!(purge_trash);
 
 		match cmd.name.as_ref() {
 			// Help
 			"help" => act!(help:toggle, cx, Layer::Tasks),
 			// Plugin
 			"plugin" => act!(plugin, self.app, cmd),
-			_ => succ!(),
+			_ => Self::unknown(&cmd),
+		}
+	}
+
+	fn notify(&mut self, cmd: CmdCow) -> Result<Data> {
+		let cx = &mut Ctx::new(&mut self.app.core, &cmd)?;
+
+		macro_rules! on {
+			($name:ident) => {
+				if cmd.name == stringify!($name) {
+					return act!(notify:$name, cx, cmd);
+				}
+			};
+		}
+
+		on!(show);
+		on!(close);
+		on!(arrow);
+		on!(invoke);
+		on!(remove);
+		on!(clear);
+
+		match cmd.name.as_ref() {
+			// Help
+			"help" => act!(help:toggle, cx, Layer::Notify),
+			// Plugin
+			"plugin" => act!(plugin, self.app, cmd),
+			_ => Self::unknown(&cmd),
 		}
 	}
 
@@ -193,7 +254,7 @@ impl<'a> Executor<'a> {
 			"help" => act!(help:toggle, cx, Layer::Spot),
 			// Plugin
 			"plugin" => act!(plugin, self.app, cmd),
-			_ => succ!(),
+			_ => Self::unknown(&cmd),
 		}
 	}
 
@@ -217,7 +278,7 @@ impl<'a> Executor<'a> {
 			"help" => act!(help:toggle, cx, Layer::Pick),
 			// Plugin
 			"plugin" => act!(plugin, self.app, cmd),
-			_ => succ!(),
+			_ => Self::unknown(&cmd),
 		}
 	}
 
@@ -268,7 +329,7 @@ impl<'a> Executor<'a> {
 		on!(show);
 		on!(close);
 
-		succ!();
+		Self::unknown(&cmd)
 	}
 
 	fn help(&mut self, cmd: CmdCow) -> Result<Data> {
@@ -285,13 +346,15 @@ impl<'a> Executor<'a> {
 		on!(escape);
 		on!(arrow);
 		on!(filter);
+		on!(invoke);
+		on!(palette);
 
 		match cmd.name.as_ref() {
 			// Help
 			"close" => act!(help:toggle, cx, Layer::Help),
 			// Plugin
 			"plugin" => act!(plugin, self.app, cmd),
-			_ => succ!(),
+			_ => Self::unknown(&cmd),
 		}
 	}
 
@@ -316,7 +379,7 @@ impl<'a> Executor<'a> {
 			"help" => act!(help:toggle, cx, Layer::Cmp),
 			// Plugin
 			"plugin" => act!(plugin, self.app, cmd),
-			_ => succ!(),
+			_ => Self::unknown(&cmd),
 		}
 	}
 
@@ -333,7 +396,20 @@ impl<'a> Executor<'a> {
 
 		on!(show);
 		on!(callback);
+		on!(push);
+		on!(pop);
 
+		Self::unknown(&cmd)
+	}
+
+	// Only for the emitted ones, such as those of `ya emit`, so that they can be
+	// told apart from a success, while a keybinding of a command missing in this
+	// version doesn't stop the rest of its sequence.
+	#[inline]
+	fn unknown(cmd: &CmdCow) -> Result<Data> {
+		if cmd.source == Source::Emit {
+			bail!("Unknown command `{}:{}`", cmd.layer, cmd.name);
+		}
 		succ!();
 	}
 }
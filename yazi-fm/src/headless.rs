@@ -0,0 +1,32 @@
+use std::{io::Read, path::Path, process};
+
+use anyhow::{Context, Result};
+use yazi_boot::CommandRun;
+
+/// `yazi run`, which runs Lua without the TUI.
+pub(super) struct Headless;
+
+impl Headless {
+	pub(super) async fn run(cmd: &CommandRun) -> Result<()> {
+		let mut chunks = vec![];
+		match cmd.script.as_deref() {
+			Some(p) if p == Path::new("-") => {
+				let mut code = vec![];
+				std::io::stdin().read_to_end(&mut code).context("Failed to read the script from stdin")?;
+				chunks.push(("stdin".to_owned(), code));
+			}
+			Some(p) => {
+				let code = std::fs::read(p).with_context(|| format!("Failed to read the script {p:?}"))?;
+				chunks.push((p.display().to_string(), code));
+			}
+			None => {}
+		}
+
+		for (i, code) in cmd.exec.iter().enumerate() {
+			chunks.push((format!("exec-{}", i + 1), code.clone().into_bytes()));
+		}
+
+		let succeeded = yazi_plugin::isolate::run(chunks, cmd.args.clone()).await?;
+		process::exit(if succeeded { 0 } else { 1 });
+	}
+}
@@ -4,9 +4,9 @@
 #[global_allocator]
 static GLOBAL: tikv_jemallocator::Jemalloc = tikv_jemallocator::Jemalloc;
 
-yazi_macro::mod_pub!(app cmp confirm help input mgr notify pick spot tasks which);
+yazi_macro::mod_pub!(announce app cmp confirm help input mgr notify pick spot tasks which);
 
-yazi_macro::mod_flat!(dispatcher executor logs panic root router signals term);
+yazi_macro::mod_flat!(dispatcher executor headless logs panic root router signals term);
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
@@ -34,6 +34,10 @@ async fn main() -> anyhow::Result<()> {
 
 	yazi_plugin::init()?;
 
+	if let Some(yazi_boot::Command::Run(cmd)) = &yazi_boot::ARGS.command {
+		return Headless::run(cmd).await;
+	}
+
 	yazi_core::init();
 
 	yazi_dds::serve();
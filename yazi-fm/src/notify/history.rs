@@ -0,0 +1,97 @@
+use std::time::Instant;
+
+use ratatui::{buffer::Buffer, layout::{self, Alignment, Constraint, Rect}, text::{Line, Span, Text}, widgets::{Block, BorderType, List, Padding, Paragraph, Widget, Wrap}};
+use yazi_config::THEME;
+use yazi_core::{Core, notify::{HISTORY_PERCENT, Record}};
+
+pub(crate) struct History<'a> {
+	core: &'a Core,
+}
+
+impl<'a> History<'a> {
+	pub(crate) fn new(core: &'a Core) -> Self { Self { core } }
+
+	fn area(area: Rect) -> Rect {
+		let chunk = layout::Layout::vertical([
+			Constraint::Percentage((100 - HISTORY_PERCENT) / 2),
+			Constraint::Percentage(HISTORY_PERCENT),
+			Constraint::Percentage((100 - HISTORY_PERCENT) / 2),
+		])
+		.split(area)[1];
+
+		layout::Layout::horizontal([
+			Constraint::Percentage((100 - HISTORY_PERCENT) / 2),
+			Constraint::Percentage(HISTORY_PERCENT),
+			Constraint::Percentage((100 - HISTORY_PERCENT) / 2),
+		])
+		.split(chunk)[1]
+	}
+
+	fn row(record: &Record) -> Line<'_> {
+		let mut spans = vec![
+			Span::styled(format!("{} {}", record.level.icon(), record.title), record.level.style()),
+			Span::raw("  "),
+			Span::raw(record.content.lines().next().unwrap_or_default()),
+		];
+		if record.count > 1 {
+			spans.push(Span::raw(format!(" (×{})", record.count)));
+		}
+		Line::from(spans)
+	}
+
+	fn detail(record: &Record) -> Text<'_> {
+		let ago = match Instant::now().duration_since(record.instant).as_secs() {
+			s @ 0..60 => format!("{s}s ago"),
+			s @ 60..3600 => format!("{}m ago", s / 60),
+			s => format!("{}h ago", s / 3600),
+		};
+		let when = match record.count {
+			1 => ago,
+			n => format!("{n} times, the last {ago}"),
+		};
+
+		let mut text = Text::from(Line::styled(record.title.as_str(), record.level.style()));
+		text.push_line(Line::styled(when, THEME.notify.title));
+		text.push_line("");
+		text.extend(record.content.lines().map(Line::raw));
+		text
+	}
+}
+
+impl Widget for History<'_> {
+	fn render(self, area: Rect, buf: &mut Buffer) {
+		let area = Self::area(area);
+		let notify = &self.core.notify;
+
+		yazi_binding::elements::Clear::default().render(area, buf);
+		let mut block = Block::bordered()
+			.title(Line::styled("Notifications", THEME.notify.title))
+			.title_alignment(Alignment::Center)
+			.padding(Padding::symmetric(1, 1))
+			.border_type(BorderType::Rounded)
+			.border_style(THEME.notify.border);
+		if let Some(action) = notify.hovered().and_then(|r| r.action.as_ref()) {
+			block =
+				block.title_bottom(Line::styled(format!(" <Enter>: {} ", action.desc), THEME.notify.title));
+		}
+
+		let inner = block.inner(area);
+		block.render(area, buf);
+
+		let Some(hovered) = notify.hovered() else {
+			return Line::raw("No notifications").centered().render(inner, buf);
+		};
+
+		let [list, detail] = layout::Layout::horizontal([Constraint::Fill(1), Constraint::Fill(1)])
+			.spacing(2)
+			.areas(inner);
+
+		let items = notify.window().map(|(i, r)| {
+			let row = Self::row(r);
+			if i == notify.cursor { row.style(THEME.notify.hovered) } else { row }
+		});
+		List::new(items).render(list, buf);
+
+		Paragraph::new(Self::detail(hovered)).wrap(Wrap { trim: false }).render(detail, buf);
+	}
+}
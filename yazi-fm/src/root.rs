@@ -2,10 +2,12 @@ use mlua::{ObjectLike, Table};
 use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
 use tracing::error;
 use yazi_binding::elements::render_once;
+use yazi_boot::BOOT;
 use yazi_core::Core;
 use yazi_plugin::LUA;
+use yazi_shared::Layer;
 
-use super::{cmp, confirm, help, input, mgr, pick, spot, tasks, which};
+use super::{announce, cmp, confirm, help, input, mgr, notify, pick, spot, tasks, which};
 
 pub(super) struct Root<'a> {
 	core: &'a Core,
@@ -19,10 +21,8 @@ impl<'a> Root<'a> {
 		let root = LUA.globals().raw_get::<Table>("Root")?.call_method::<Table>("new", area)?;
 		root.call_method("reflow", ())
 	}
-}
 
-impl Widget for Root<'_> {
-	fn render(self, area: Rect, buf: &mut Buffer) {
+	fn redraw(&self, area: Rect, buf: &mut Buffer) {
 		let mut f = || {
 			let area = yazi_binding::elements::Rect::from(area);
 			let root = LUA.globals().raw_get::<Table>("Root")?.call_method::<Table>("new", area)?;
@@ -33,14 +33,28 @@ impl Widget for Root<'_> {
 		if let Err(e) = f() {
 			error!("Failed to redraw the `Root` component:\n{e}");
 		}
+	}
+}
+
+impl Widget for Root<'_> {
+	fn render(self, area: Rect, buf: &mut Buffer) {
+		if BOOT.accessible {
+			announce::Announce::new(self.core).render(area, buf);
+		} else {
+			self.redraw(area, buf);
+			mgr::Preview::new(self.core).render(area, buf);
+		}
 
-		mgr::Preview::new(self.core).render(area, buf);
 		mgr::Modal::new(self.core).render(area, buf);
 
 		if self.core.tasks.visible {
 			tasks::Tasks::new(self.core).render(area, buf);
 		}
 
+		if self.core.notify.visible {
+			notify::History::new(self.core).render(area, buf);
+		}
+
 		if self.core.active().spot.visible() {
 			spot::Spot::new(self.core).render(area, buf);
 		}
@@ -65,7 +79,8 @@ impl Widget for Root<'_> {
 			cmp::Cmp::new(self.core).render(area, buf);
 		}
 
-		if self.core.which.visible {
+		// The keys of a mode wait behind the popups it opened
+		if self.core.layer() == Layer::Which {
 			which::Which::new(self.core).render(area, buf);
 		}
 	}
@@ -28,7 +28,7 @@ impl<'a> Router<'a> {
 		use Layer as L;
 		Ok(match layer {
 			L::App => unreachable!(),
-			L::Mgr | L::Tasks | L::Spot | L::Pick | L::Input | L::Confirm | L::Help => {
+			L::Mgr | L::Tasks | L::Notify | L::Spot | L::Pick | L::Input | L::Confirm | L::Help => {
 				self.matches(layer, key)
 			}
 			L::Cmp => self.matches(L::Cmp, key) || self.matches(L::Input, key),
@@ -1,6 +1,11 @@
-use ratatui::{buffer::Buffer, layout::{self, Alignment, Constraint, Rect}, text::{Line, Text}, widgets::{Block, BorderType, List, Padding, Widget}};
-use yazi_config::THEME;
+use std::borrow::Cow;
+
+use ratatui::{buffer::Buffer, layout::{self, Alignment, Constraint, Rect}, text::{Line, Text}, widgets::{Block, BorderType, List, Padding, Sparkline, Widget}};
+use yazi_config::{THEME, YAZI};
 use yazi_core::{Core, tasks::TASKS_PERCENT};
+use yazi_parser::app::TasksProgress;
+use yazi_plugin::isolate::GATE;
+use yazi_scheduler::readable_size;
 
 pub(crate) struct Tasks<'a> {
 	core: &'a Core,
@@ -24,6 +29,52 @@ impl<'a> Tasks<'a> {
 		])
 		.split(chunk)[1]
 	}
+
+	/// Running and queued runs of the plugins held back by their scheduling hints.
+	fn hints() -> Line<'static> {
+		let stats = GATE.stats();
+		if stats.is_empty() {
+			return Line::default();
+		}
+
+		let mut s: Vec<_> = stats
+			.into_iter()
+			.map(|(name, stat)| format!("{name}: {} running, {} queued", stat.running, stat.queued))
+			.collect();
+
+		let (memory, cap) = (GATE.memory(), YAZI.tasks.plugin_memory);
+		if memory > 0 {
+			s.push(format!("{}/{} MiB", memory >> 20, cap >> 20));
+		}
+
+		Line::styled(format!(" {} ", s.join(" | ")), THEME.tasks.title)
+	}
+
+	/// The speed with a graph of the recent ones, the bytes processed, and the time left.
+	fn speed(progress: &TasksProgress, area: Rect, buf: &mut Buffer) {
+		let mut s = format!(
+			"{}/s, {} of {}",
+			readable_size(progress.speed),
+			readable_size(progress.processed),
+			readable_size(progress.found)
+		);
+		if let Some(eta) = progress.eta {
+			let eta = match eta {
+				0..60 => format!("{eta}s"),
+				60..3600 => format!("{}m {}s", eta / 60, eta % 60),
+				_ => format!("{}h {}m", eta / 3600, eta % 3600 / 60),
+			};
+			s.push_str(&format!(", {eta} left"));
+		}
+
+		let [graph, label] = layout::Layout::vertical([Constraint::Length(2), Constraint::Length(1)])
+			.areas(area);
+		Sparkline::default()
+			.data(progress.speeds)
+			.style(THEME.tasks.border)
+			.render(graph, buf);
+		Line::styled(s, THEME.tasks.title).render(label, buf);
+	}
 }
 
 impl Widget for Tasks<'_> {
@@ -36,15 +87,31 @@ impl Widget for Tasks<'_> {
 			.title_alignment(Alignment::Center)
 			.padding(Padding::symmetric(1, 1))
 			.border_type(BorderType::Rounded)
-			.border_style(THEME.tasks.border);
+			.border_style(THEME.tasks.border)
+			.title_bottom(Self::hints());
 
 		let inner = block.inner(area);
 		block.render(area, buf);
 
 		let tasks = &self.core.tasks;
+		let inner = if tasks.progress.speeds.iter().any(|&n| n > 0) && inner.height > 4 {
+			let [list, speed] = layout::Layout::vertical([Constraint::Fill(1), Constraint::Length(3)])
+				.spacing(1)
+				.areas(inner);
+			Self::speed(&tasks.progress, speed, buf);
+			list
+		} else {
+			inner
+		};
+
 		let items = tasks.summaries.iter().take(inner.height as usize).enumerate().map(|(i, v)| {
+			let name = match (v.paused, v.scheduled) {
+				(true, _) => Cow::Owned(format!("[Paused] {}", v.name)),
+				(_, true) => Cow::Owned(format!("[Scheduled] {}", v.name)),
+				_ => Cow::Borrowed(&v.name),
+			};
 			let mut item =
-				Text::from_iter(textwrap::wrap(&v.name, inner.width as usize).into_iter().map(Line::from));
+				Text::from_iter(textwrap::wrap(&name, inner.width as usize).into_iter().map(Line::from));
 			if i == tasks.cursor {
 				item = item.style(THEME.tasks.hovered);
 			}
@@ -45,6 +45,7 @@ impl Term {
 		let resp = Emulator::read_until_da1();
 		Mux::tmux_drain()?;
 		yazi_term::RestoreCursor::store(&resp);
+		yazi_term::RestoreCwd::store();
 
 		CSI_U.store(resp.contains("\x1b[?0u"), Ordering::Relaxed);
 		if CSI_U.load(Ordering::Relaxed) {
@@ -99,6 +100,7 @@ impl Term {
 			TTY.writer(),
 			yazi_term::If(!YAZI.mgr.mouse_events.get().is_empty(), DisableMouseCapture),
 			yazi_term::RestoreCursor,
+			yazi_term::If(YAZI.mgr.report_cwd, yazi_term::RestoreCwd),
 			DisableBracketedPaste,
 			LeaveAlternateScreen,
 			crossterm::cursor::Show
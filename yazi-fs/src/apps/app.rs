@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+
+/// An application that can open files.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct App {
+	pub name:   String,
+	pub run:    String,
+	pub block:  bool,
+	pub orphan: bool,
+}
+
+impl App {
+	/// A GUI application, which runs on its own.
+	#[cfg_attr(not(any(target_os = "macos", windows)), allow(dead_code))]
+	pub(super) fn gui(name: impl Into<String>, run: String) -> Self {
+		Self { name: name.into(), run, block: false, orphan: true }
+	}
+}
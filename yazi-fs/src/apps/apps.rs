@@ -0,0 +1,85 @@
+use yazi_shared::url::Url;
+
+use super::App;
+use crate::provider;
+
+/// The applications registered on the system.
+pub static APPS: Apps = Apps::new();
+
+pub struct Apps {
+	#[cfg(all(unix, not(any(target_os = "macos", target_os = "android"))))]
+	desktop: parking_lot::Mutex<Option<std::sync::Arc<super::Desktop>>>,
+}
+
+impl Apps {
+	const fn new() -> Self {
+		Self {
+			#[cfg(all(unix, not(any(target_os = "macos", target_os = "android"))))]
+			desktop: parking_lot::Mutex::new(None),
+		}
+	}
+
+	/// The applications that can open all the `targets`, the preferred ones first.
+	pub async fn query(&self, targets: &[(Url, &str)]) -> Vec<App> {
+		let targets: Vec<_> = targets
+			.iter()
+			.map(|(u, m)| {
+				let path = provider::local_path_sync(u).map(|p| p.into_owned()).unwrap_or_default();
+				(path, m.to_string())
+			})
+			.collect();
+
+		#[cfg(all(unix, not(any(target_os = "macos", target_os = "android"))))]
+		let desktop = self.desktop.lock().clone();
+
+		tokio::task::spawn_blocking(move || {
+			#[cfg(all(unix, not(any(target_os = "macos", target_os = "android"))))]
+			{
+				let desktop = desktop.unwrap_or_else(|| {
+					let scanned = std::sync::Arc::new(super::Desktop::scan());
+					APPS.desktop.lock().get_or_insert(scanned).clone()
+				});
+				common(targets.iter().map(|(_, mime)| desktop.query(mime)))
+			}
+			#[cfg(target_os = "macos")]
+			{
+				common(targets.iter().map(|(path, _)| super::launch::query(path)))
+			}
+			#[cfg(windows)]
+			{
+				common(targets.iter().map(|(path, _)| super::registry::query(path)))
+			}
+			#[cfg(target_os = "android")]
+			{
+				_ = targets;
+				common(std::iter::empty())
+			}
+		})
+		.await
+		.unwrap_or_default()
+	}
+}
+
+/// The ones of the first list that are in all the others, in its order.
+fn common(mut lists: impl Iterator<Item = Vec<App>>) -> Vec<App> {
+	let Some(mut first) = lists.next() else { return vec![] };
+	for list in lists {
+		first.retain(|a| list.contains(a));
+	}
+	first
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_common() {
+		let app = |name: &str| App::gui(name, format!("{name} \"$@\""));
+		let lists =
+			[vec![app("a"), app("b"), app("c")], vec![app("c"), app("a")], vec![app("a"), app("c")]];
+
+		assert_eq!(common(lists.into_iter()), vec![app("a"), app("c")]);
+		assert_eq!(common(std::iter::empty()), vec![]);
+	}
+}
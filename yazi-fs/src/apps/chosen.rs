@@ -0,0 +1,84 @@
+use std::{collections::HashMap, path::{Path, PathBuf}};
+
+use anyhow::Result;
+use parking_lot::Mutex;
+use yazi_shared::url::Url;
+
+use super::App;
+use crate::{Xdg, write_atomic};
+
+/// The applications chosen to always open the files of an extension with.
+pub static CHOSEN: Chosen = Chosen::new();
+
+pub struct Chosen {
+	inner: Mutex<Option<HashMap<String, App>>>,
+}
+
+impl Chosen {
+	const fn new() -> Self { Self { inner: Mutex::new(None) } }
+
+	/// The application chosen for the extension of `url`, if any.
+	pub fn get(&self, url: &Url) -> Option<App> {
+		let key = Self::key(url)?;
+		self.with(|map| map.get(&key).cloned())
+	}
+
+	/// Remembers `app` for the extensions of `urls`, or forgets them if `None`.
+	pub fn set<'a>(&self, urls: impl Iterator<Item = &'a Url>, app: Option<App>) -> Result<()> {
+		let keys: Vec<_> = urls.filter_map(Self::key).collect();
+		let map = self.with(|map| {
+			for key in keys {
+				match &app {
+					Some(app) => _ = map.insert(key, app.clone()),
+					None => _ = map.remove(&key),
+				}
+			}
+			map.clone()
+		});
+		Self::save_to(&map, &Self::path())
+	}
+
+	fn with<T>(&self, f: impl FnOnce(&mut HashMap<String, App>) -> T) -> T {
+		let mut inner = self.inner.lock();
+		f(inner.get_or_insert_with(|| Self::load_from(&Self::path()).unwrap_or_default()))
+	}
+
+	fn key(url: &Url) -> Option<String> {
+		Some(url.extension()?.to_str()?.to_lowercase()).filter(|s| !s.is_empty())
+	}
+
+	fn load_from(path: &Path) -> Result<HashMap<String, App>> {
+		Ok(serde_json::from_slice(&std::fs::read(path)?)?)
+	}
+
+	fn save_to(map: &HashMap<String, App>, path: &Path) -> Result<()> {
+		Ok(write_atomic(path, &serde_json::to_vec(map)?)?)
+	}
+
+	#[inline]
+	fn path() -> PathBuf { Xdg::state_dir().join("chosen.json") }
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::TempDir;
+
+	#[test]
+	fn test_chosen() -> Result<()> {
+		let key = |s: &str| Chosen::key(&s.parse().unwrap());
+		assert_eq!(key("/a/b.PDF").as_deref(), Some("pdf"));
+		assert_eq!(key("/a/b.tar.gz").as_deref(), Some("gz"));
+		assert_eq!(key("/a/Makefile"), None);
+
+		let dir = TempDir::new("chosen")?;
+		let path = dir.join("chosen.json");
+		let app =
+			App { name: "Foo".to_owned(), run: "foo \"$@\"".to_owned(), block: false, orphan: true };
+		let map = HashMap::from([("pdf".to_owned(), app)]);
+
+		Chosen::save_to(&map, &path)?;
+		assert_eq!(Chosen::load_from(&path)?, map);
+		Ok(())
+	}
+}
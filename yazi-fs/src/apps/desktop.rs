@@ -0,0 +1,313 @@
+use std::{collections::{HashMap, HashSet}, env, fs, path::{Path, PathBuf}};
+
+use yazi_shared::{natsort, shell};
+
+use super::App;
+
+/// The applications of the `.desktop` files, and the associations of `mimeapps.list`.
+#[derive(Default)]
+pub(crate) struct Desktop {
+	apps:     HashMap<String, App>,
+	by_mime:  HashMap<String, Vec<String>>,
+	defaults: HashMap<String, Vec<String>>,
+	added:    HashMap<String, Vec<String>>,
+	removed:  HashMap<String, HashSet<String>>,
+}
+
+impl Desktop {
+	pub(super) fn scan() -> Self {
+		let mut me = Self::default();
+		let data = Self::data_dirs();
+
+		// The earlier directories take precedence for the same desktop ID
+		for dir in data.iter().map(|d| d.join("applications")) {
+			me.scan_dir(&dir, &dir);
+		}
+		for ids in me.by_mime.values_mut() {
+			ids.sort_by(|a, b| natsort(me.apps[a].name.as_bytes(), me.apps[b].name.as_bytes(), true));
+		}
+
+		let config = env::var_os("XDG_CONFIG_HOME")
+			.map(PathBuf::from)
+			.filter(|p| p.is_absolute())
+			.or_else(|| dirs::home_dir().map(|h| h.join(".config")));
+		let config_dirs = env::var_os("XDG_CONFIG_DIRS").unwrap_or_else(|| "/etc/xdg".into());
+
+		let lists = config
+			.into_iter()
+			.chain(env::split_paths(&config_dirs).filter(|p| p.is_absolute()))
+			.chain(data.iter().map(|d| d.join("applications")));
+		for list in lists {
+			if let Ok(s) = fs::read_to_string(list.join("mimeapps.list")) {
+				me.parse_list(&s);
+			}
+		}
+		me
+	}
+
+	/// The applications for `mime`, the default ones first.
+	pub(super) fn query(&self, mime: &str) -> Vec<App> {
+		let wildcard = mime.split_once('/').map(|(major, _)| format!("{major}/*"));
+
+		let removed = self.removed.get(mime);
+		let mut ids: Vec<&str> = vec![];
+		let lists = [self.defaults.get(mime), self.added.get(mime), self.by_mime.get(mime)]
+			.into_iter()
+			.chain([wildcard.and_then(|w| self.by_mime.get(&w))]);
+
+		for id in lists.flatten().flatten() {
+			if !ids.contains(&id.as_str()) && !removed.is_some_and(|r| r.contains(id)) {
+				ids.push(id);
+			}
+		}
+		ids.into_iter().filter_map(|id| self.apps.get(id)).cloned().collect()
+	}
+
+	fn data_dirs() -> Vec<PathBuf> {
+		let home = env::var_os("XDG_DATA_HOME")
+			.map(PathBuf::from)
+			.filter(|p| p.is_absolute())
+			.or_else(|| dirs::home_dir().map(|h| h.join(".local/share")));
+		let dirs = env::var_os("XDG_DATA_DIRS")
+			.filter(|s| !s.is_empty())
+			.unwrap_or_else(|| "/usr/local/share:/usr/share".into());
+
+		home.into_iter().chain(env::split_paths(&dirs).filter(|p| p.is_absolute())).collect()
+	}
+
+	fn scan_dir(&mut self, root: &Path, dir: &Path) {
+		let Ok(it) = fs::read_dir(dir) else { return };
+		for entry in it.flatten() {
+			let path = entry.path();
+			if entry.file_type().is_ok_and(|t| t.is_dir()) {
+				self.scan_dir(root, &path);
+				continue;
+			} else if path.extension().is_none_or(|e| e != "desktop") {
+				continue;
+			}
+
+			// The desktop ID is the path relative to the directory, with `/` as `-`
+			let Ok(rel) = path.strip_prefix(root) else { continue };
+			let id = rel.to_string_lossy().replace('/', "-");
+			if self.apps.contains_key(&id) {
+				continue;
+			}
+
+			let Ok(s) = fs::read_to_string(&path) else { continue };
+			if let Some((app, mimes)) = Self::parse_entry(&s, &path) {
+				for mime in mimes {
+					self.by_mime.entry(mime).or_default().push(id.clone());
+				}
+				self.apps.insert(id, app);
+			}
+		}
+	}
+
+	fn parse_entry(s: &str, path: &Path) -> Option<(App, Vec<String>)> {
+		let (mut name, mut exec, mut mimes, mut terminal) = (None, None, vec![], false);
+		for (key, value) in Self::section(s, "Desktop Entry") {
+			match key {
+				"Type" if value != "Application" => return None,
+				"Hidden" if value == "true" => return None,
+				"Name" => name = Some(value),
+				"Exec" => exec = Some(value),
+				"MimeType" => mimes = value.split(';').filter(|s| !s.is_empty()).map(Into::into).collect(),
+				"Terminal" => terminal = value == "true",
+				_ => {}
+			}
+		}
+
+		let name = Self::unescape(name?);
+		let run = Self::parse_exec(&Self::unescape(exec?), &name, path)?;
+		Some((App { name, run, block: terminal, orphan: !terminal }, mimes))
+	}
+
+	/// Turns the `Exec` key into a shell command.
+	fn parse_exec(exec: &str, name: &str, path: &Path) -> Option<String> {
+		let mut args = vec![];
+		let mut files = false;
+		for arg in Self::split_exec(exec)? {
+			let (mut out, mut lit) = (String::new(), String::new());
+			let mut it = arg.chars();
+			while let Some(c) = it.next() {
+				if c != '%' {
+					lit.push(c);
+					continue;
+				}
+
+				let code = match it.next()? {
+					'%' => {
+						lit.push('%');
+						continue;
+					}
+					'f' | 'u' => "\"$1\"".to_owned(),
+					'F' | 'U' => "\"$@\"".to_owned(),
+					'c' => shell::escape_unix(name).into_owned(),
+					'k' => shell::escape_unix(&path.to_string_lossy()).into_owned(),
+					_ => String::new(),
+				};
+				files |= code.starts_with("\"$");
+				if !lit.is_empty() {
+					out.push_str(&shell::escape_unix(&std::mem::take(&mut lit)));
+				}
+				out.push_str(&code);
+			}
+
+			if !lit.is_empty() {
+				out.push_str(&shell::escape_unix(&lit));
+			}
+			if !out.is_empty() {
+				args.push(out);
+			}
+		}
+
+		if args.is_empty() {
+			return None;
+		} else if !files {
+			args.push("\"$@\"".to_owned());
+		}
+		Some(args.join(" "))
+	}
+
+	/// Splits the `Exec` key into arguments.
+	fn split_exec(s: &str) -> Option<Vec<String>> {
+		let (mut args, mut cur) = (vec![], String::new());
+		let (mut quoted, mut pending) = (false, false);
+
+		let mut it = s.chars();
+		while let Some(c) = it.next() {
+			match c {
+				'"' => (quoted, pending) = (!quoted, true),
+				'\\' if quoted => cur.push(it.next()?),
+				c if c.is_whitespace() && !quoted => {
+					if pending || !cur.is_empty() {
+						args.push(std::mem::take(&mut cur));
+					}
+					pending = false;
+				}
+				c => cur.push(c),
+			}
+		}
+
+		if quoted {
+			return None;
+		} else if pending || !cur.is_empty() {
+			args.push(cur);
+		}
+		Some(args)
+	}
+
+	fn parse_list(&mut self, s: &str) {
+		let sections =
+			[("Default Applications", &mut self.defaults), ("Added Associations", &mut self.added)];
+		for (name, map) in sections {
+			for (mime, ids) in Self::section(s, name) {
+				let ids = ids.split(';').filter(|s| !s.is_empty()).map(Into::into);
+				map.entry(mime.to_owned()).or_default().extend(ids);
+			}
+		}
+
+		for (mime, ids) in Self::section(s, "Removed Associations") {
+			let ids = ids.split(';').filter(|s| !s.is_empty()).map(Into::into);
+			self.removed.entry(mime.to_owned()).or_default().extend(ids);
+		}
+	}
+
+	/// The keys of a section, without the localized ones such as `Name[fr]`.
+	fn section<'a>(s: &'a str, name: &'a str) -> impl Iterator<Item = (&'a str, &'a str)> {
+		s.lines()
+			.map(str::trim)
+			.skip_while(move |l| l.strip_prefix('[').and_then(|l| l.strip_suffix(']')) != Some(name))
+			.skip(1)
+			.take_while(|l| !l.starts_with('['))
+			.filter(|l| !l.starts_with('#'))
+			.filter_map(|l| l.split_once('='))
+			.map(|(k, v)| (k.trim(), v.trim()))
+			.filter(|(k, _)| !k.contains('['))
+	}
+
+	fn unescape(s: &str) -> String {
+		let mut out = String::with_capacity(s.len());
+		let mut it = s.chars();
+		while let Some(c) = it.next() {
+			match (c, it.clone().next()) {
+				('\\', Some(n @ ('s' | 'n' | 't' | 'r' | '\\'))) => {
+					it.next();
+					out.push(match n {
+						's' => ' ',
+						'n' => '\n',
+						't' => '\t',
+						'r' => '\r',
+						_ => '\\',
+					});
+				}
+				_ => out.push(c),
+			}
+		}
+		out
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_parse_exec() {
+		let exec = |s: &str| Desktop::parse_exec(s, "Foo Bar", Path::new("/a/foo.desktop"));
+
+		assert_eq!(exec("firefox %u").as_deref(), Some(r#"firefox "$1""#));
+		assert_eq!(
+			exec("vlc --started-from-file %U").as_deref(),
+			Some(r#"vlc --started-from-file "$@""#)
+		);
+		assert_eq!(exec("gimp-2.10").as_deref(), Some(r#"gimp-2.10 "$@""#));
+		assert_eq!(
+			exec("foo --name=%c %i --file=%f").as_deref(),
+			Some(r#"foo --name='Foo Bar' --file="$1""#)
+		);
+		assert_eq!(
+			exec(r#""/opt/My App/app" "--title=a \"b\"" 100%% %F"#).as_deref(),
+			Some(r#"'/opt/My App/app' '--title=a "b"' '100%' "$@""#)
+		);
+		assert_eq!(exec(r#"foo "unclosed %f"#), None);
+	}
+
+	#[test]
+	fn test_query() {
+		let mut d = Desktop::default();
+		let entries = [
+			("a.desktop", "Type=Application\nName=Zed\nName[fr]=Zède\nExec=zed %F\nMimeType=text/plain;"),
+			("b.desktop", "Type=Application\nName=Alpha\nExec=alpha %f\nMimeType=text/plain;text/x-c;"),
+			("c.desktop", "Type=Application\nName=Viewer\nExec=viewer\nMimeType=text/*;"),
+			("d.desktop", "Type=Application\nName=Vim\nExec=vim %F\nTerminal=true\nMimeType=text/plain;"),
+			("e.desktop", "Type=Link\nName=Web\nURL=https://example.com"),
+			("f.desktop", "Type=Application\nName=Gone\nExec=gone %f\nHidden=true"),
+		];
+		for (id, s) in entries {
+			let s = format!("[Desktop Entry]\n{s}");
+			if let Some((app, mimes)) = Desktop::parse_entry(&s, Path::new(id)) {
+				mimes.into_iter().for_each(|m| d.by_mime.entry(m).or_default().push(id.to_owned()));
+				d.apps.insert(id.to_owned(), app);
+			}
+		}
+		for ids in d.by_mime.values_mut() {
+			ids.sort_by(|a, b| natsort(d.apps[a].name.as_bytes(), d.apps[b].name.as_bytes(), true));
+		}
+
+		d.parse_list(concat!(
+			"[Default Applications]\ntext/plain=a.desktop;missing.desktop;\n",
+			"[Added Associations]\ntext/plain=c.desktop;\n",
+			"[Removed Associations]\ntext/plain=d.desktop;\n",
+		));
+
+		let names = |mime: &str| d.query(mime).into_iter().map(|a| a.name).collect::<Vec<_>>();
+		assert_eq!(names("text/plain"), ["Zed", "Viewer", "Alpha"]);
+		assert_eq!(names("text/x-c"), ["Alpha", "Viewer"]);
+		assert_eq!(names("image/png"), Vec::<String>::new());
+
+		let vim = d.apps["d.desktop"].clone();
+		assert!(vim.block && !vim.orphan);
+		assert_eq!(d.apps.len(), 4);
+	}
+}
@@ -0,0 +1,63 @@
+use std::{ffi::OsStr, os::unix::ffi::OsStrExt, path::{Path, PathBuf}, ptr};
+
+use core_foundation_sys::{array::{CFArrayGetCount, CFArrayGetValueAtIndex}, base::{CFRelease, kCFAllocatorDefault}, url::{CFURLCreateFromFileSystemRepresentation, CFURLGetFileSystemRepresentation, CFURLRef}};
+use yazi_ffi::{LS_ROLES_ALL, LSCopyApplicationURLsForURL, LSCopyDefaultApplicationURLForURL};
+use yazi_shared::shell;
+
+use super::App;
+
+/// The applications that LaunchServices knows can open `path`, the default one first.
+pub(super) fn query(path: &Path) -> Vec<App> {
+	let bytes = path.as_os_str().as_bytes();
+	let url = unsafe {
+		CFURLCreateFromFileSystemRepresentation(
+			kCFAllocatorDefault,
+			bytes.as_ptr(),
+			bytes.len() as _,
+			path.is_dir() as _,
+		)
+	};
+	if url.is_null() {
+		return vec![];
+	}
+
+	let mut paths = vec![];
+	unsafe {
+		let default = LSCopyDefaultApplicationURLForURL(url, LS_ROLES_ALL, ptr::null_mut());
+		if !default.is_null() {
+			paths.extend(url_to_path(default));
+			CFRelease(default as _);
+		}
+
+		let all = LSCopyApplicationURLsForURL(url, LS_ROLES_ALL);
+		if !all.is_null() {
+			for i in 0..CFArrayGetCount(all) {
+				paths.extend(url_to_path(CFArrayGetValueAtIndex(all, i) as CFURLRef));
+			}
+			CFRelease(all as _);
+		}
+		CFRelease(url as _);
+	}
+
+	let mut apps: Vec<App> = vec![];
+	for path in paths {
+		let Some(name) = path.file_stem() else { continue };
+		let run = format!("open -a {} \"$@\"", shell::escape_unix(&path.to_string_lossy()));
+		if !apps.iter().any(|a| a.run == run) {
+			apps.push(App::gui(name.to_string_lossy(), run));
+		}
+	}
+	apps
+}
+
+unsafe fn url_to_path(url: CFURLRef) -> Option<PathBuf> {
+	let mut buf = [0u8; 1024];
+	let ok =
+		unsafe { CFURLGetFileSystemRepresentation(url, true as _, buf.as_mut_ptr(), buf.len() as _) };
+	if ok == 0 {
+		return None;
+	}
+
+	let len = buf.iter().position(|&b| b == 0)?;
+	Some(PathBuf::from(OsStr::from_bytes(&buf[..len])))
+}
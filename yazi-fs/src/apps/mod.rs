@@ -0,0 +1,10 @@
+yazi_macro::mod_flat!(app apps chosen);
+
+#[cfg(all(unix, not(any(target_os = "macos", target_os = "android"))))]
+yazi_macro::mod_flat!(desktop);
+
+#[cfg(target_os = "macos")]
+yazi_macro::mod_flat!(launch);
+
+#[cfg(windows)]
+yazi_macro::mod_flat!(registry);
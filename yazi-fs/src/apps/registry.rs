@@ -0,0 +1,121 @@
+use std::{ffi::{OsStr, OsString}, os::windows::ffi::{OsStrExt, OsStringExt}, path::Path, ptr};
+
+use windows_sys::Win32::{Foundation::ERROR_SUCCESS, System::Registry::{HKEY, HKEY_CLASSES_ROOT, HKEY_CURRENT_USER, KEY_READ, RRF_RT_REG_SZ, RegCloseKey, RegEnumKeyExW, RegEnumValueW, RegGetValueW, RegOpenKeyExW}};
+
+use super::App;
+
+const FILE_EXTS: &str = r"Software\Microsoft\Windows\CurrentVersion\Explorer\FileExts";
+
+/// The applications associated with the extension of `path` in the registry.
+pub(super) fn query(path: &Path) -> Vec<App> {
+	let Some(ext) = path.extension().and_then(|e| e.to_str()) else { return vec![] };
+	let ext = format!(".{ext}");
+
+	let user = format!(r"{FILE_EXTS}\{ext}");
+	let apps = |list: Vec<String>| list.into_iter().map(|s| format!(r"Applications\{s}"));
+
+	let ids = get(HKEY_CURRENT_USER, &format!(r"{user}\UserChoice"), Some("ProgId"))
+		.into_iter()
+		.chain(get(HKEY_CLASSES_ROOT, &ext, None))
+		.chain(values(HKEY_CURRENT_USER, &format!(r"{user}\OpenWithProgids")))
+		.chain(values(HKEY_CLASSES_ROOT, &format!(r"{ext}\OpenWithProgids")))
+		.chain(apps(
+			values(HKEY_CURRENT_USER, &format!(r"{user}\OpenWithList"))
+				.into_iter()
+				.filter(|v| v != "MRUList")
+				.filter_map(|v| get(HKEY_CURRENT_USER, &format!(r"{user}\OpenWithList"), Some(&v)))
+				.collect(),
+		))
+		.chain(apps(subkeys(HKEY_CLASSES_ROOT, &format!(r"{ext}\OpenWithList"))));
+
+	let mut out: Vec<App> = vec![];
+	for id in ids.filter(|s| !s.is_empty()) {
+		let Some(app) = app(&id) else { continue };
+		if !out.iter().any(|a| a.run == app.run) {
+			out.push(app);
+		}
+	}
+	out
+}
+
+fn app(id: &str) -> Option<App> {
+	let command = get(HKEY_CLASSES_ROOT, &format!(r"{id}\shell\open\command"), None)?;
+	let mut run = ["%L", "%l", "%V", "%v"].into_iter().fold(command, |s, p| s.replace(p, "%1"));
+	if !run.contains("%1") && !run.contains("%*") {
+		run.push_str(r#" "%1""#);
+	}
+
+	let exe = id.strip_prefix(r"Applications\");
+	let friendly =
+		|| get(HKEY_CLASSES_ROOT, &format!(r"Applications\{}", exe?), Some("FriendlyAppName"));
+	let name = get(HKEY_CLASSES_ROOT, &format!(r"{id}\Application"), Some("ApplicationName"))
+		.or_else(friendly)
+		.or_else(|| get(HKEY_CLASSES_ROOT, id, None))
+		.filter(|s| !s.is_empty() && !s.starts_with('@'))
+		.unwrap_or_else(|| exe.and_then(|e| e.rsplit_once('.')).map_or(id, |(s, _)| s).to_owned());
+
+	Some(App::gui(name, run))
+}
+
+fn get(root: HKEY, key: &str, value: Option<&str>) -> Option<String> {
+	let key = wide(key);
+	let value = value.map(wide);
+	let value = value.as_ref().map_or(ptr::null(), |v| v.as_ptr());
+
+	let mut len = 0u32;
+	let read = |buf: *mut u16, len: &mut u32| unsafe {
+		RegGetValueW(root, key.as_ptr(), value, RRF_RT_REG_SZ, ptr::null_mut(), buf.cast(), len)
+	};
+	if read(ptr::null_mut(), &mut len) != ERROR_SUCCESS {
+		return None;
+	}
+
+	let mut buf = vec![0u16; len as usize / 2];
+	if read(buf.as_mut_ptr(), &mut len) != ERROR_SUCCESS {
+		return None;
+	}
+	string(&buf)
+}
+
+fn values(root: HKEY, key: &str) -> Vec<String> {
+	enumerate(root, key, |hkey, i, buf, len| unsafe {
+		let null = ptr::null_mut();
+		RegEnumValueW(hkey, i, buf, len, ptr::null(), null, null.cast(), null)
+	})
+}
+
+fn subkeys(root: HKEY, key: &str) -> Vec<String> {
+	enumerate(root, key, |hkey, i, buf, len| unsafe {
+		let null = ptr::null_mut();
+		RegEnumKeyExW(hkey, i, buf, len, ptr::null(), null, null, null.cast())
+	})
+}
+
+fn enumerate<F>(root: HKEY, key: &str, f: F) -> Vec<String>
+where
+	F: Fn(HKEY, u32, *mut u16, &mut u32) -> u32,
+{
+	let mut hkey: HKEY = ptr::null_mut();
+	if unsafe { RegOpenKeyExW(root, wide(key).as_ptr(), 0, KEY_READ, &mut hkey) } != ERROR_SUCCESS {
+		return vec![];
+	}
+
+	let (mut out, mut buf) = (vec![], vec![0u16; 16384]);
+	for i in 0.. {
+		let mut len = buf.len() as u32;
+		if f(hkey, i, buf.as_mut_ptr(), &mut len) != ERROR_SUCCESS {
+			break;
+		}
+		out.extend(string(&buf[..len as usize]));
+	}
+
+	unsafe { RegCloseKey(hkey) };
+	out
+}
+
+fn wide(s: &str) -> Vec<u16> { OsStr::new(s).encode_wide().chain([0]).collect() }
+
+fn string(buf: &[u16]) -> Option<String> {
+	let len = buf.iter().position(|&c| c == 0).unwrap_or(buf.len());
+	OsString::from_wide(&buf[..len]).into_string().ok()
+}
@@ -0,0 +1,253 @@
+use std::{fmt::{self, Display}, io::{self, BufRead, BufReader, Write}, path::{Path, PathBuf}, str::FromStr, sync::{atomic::{AtomicBool, Ordering}, mpsc}, thread::{self, JoinHandle}, time::{SystemTime, UNIX_EPOCH}};
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use yazi_shared::{rfc3339, url::Url};
+
+use crate::Xdg;
+
+/// The log of the file operations done, queried by `ya log`.
+pub static AUDIT: Audit = Audit::new();
+
+pub struct Audit {
+	enabled: AtomicBool,
+	// Entries are written by a thread of its own, so the callers don't wait on the disk
+	writer:  Mutex<Option<(mpsc::Sender<AuditEntry>, JoinHandle<()>)>>,
+}
+
+/// An operation recorded in the log, once it's finished.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct AuditEntry {
+	/// When it finished, in RFC 3339 and UTC.
+	pub time:    String,
+	pub user:    String,
+	pub pid:     u32,
+	pub op:      AuditOp,
+	/// Where the file was, for a rename or move.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub source:  Option<Url>,
+	pub target:  Url,
+	pub outcome: AuditOutcome,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub error:   Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AuditOp {
+	Create,
+	Rename,
+	Move,
+	Trash,
+	Delete,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AuditOutcome {
+	Succeeded,
+	Failed,
+	Cancelled,
+}
+
+impl Audit {
+	const fn new() -> Self { Self { enabled: AtomicBool::new(false), writer: Mutex::new(None) } }
+
+	#[inline]
+	pub fn enable(&self, enabled: bool) { self.enabled.store(enabled, Ordering::Relaxed); }
+
+	#[inline]
+	pub fn enabled(&self) -> bool { self.enabled.load(Ordering::Relaxed) }
+
+	/// Appends the entry to the log if it's enabled, stamped with the time now.
+	pub fn record(&self, mut entry: AuditEntry) {
+		if !self.enabled() {
+			return;
+		}
+
+		let secs = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+		entry.time = rfc3339(secs);
+
+		let mut writer = self.writer.lock();
+		let (tx, _) = writer.get_or_insert_with(|| Self::spawn(Self::path()));
+		tx.send(entry).ok();
+	}
+
+	/// Waits for the entries recorded so far to be written, before exiting.
+	pub fn flush(&self) {
+		if let Some((tx, handle)) = self.writer.lock().take() {
+			drop(tx);
+			handle.join().ok();
+		}
+	}
+
+	/// Appends an operation done right away, passing its result through.
+	pub fn done<T, E: Display>(
+		&self,
+		op: AuditOp,
+		source: Option<&Url>,
+		target: &Url,
+		result: Result<T, E>,
+	) -> Result<T, E> {
+		if self.enabled() {
+			let entry = AuditEntry::new(op, source.cloned(), target.clone());
+			self.record(match &result {
+				Ok(_) => entry,
+				Err(e) => entry.fail(e),
+			});
+		}
+		result
+	}
+
+	/// Reads the entries of the log, skipping the lines that can't be parsed.
+	pub fn read(path: &Path) -> io::Result<impl Iterator<Item = AuditEntry>> {
+		let reader = BufReader::new(std::fs::File::open(path)?);
+		Ok(reader.lines().map_while(Result::ok).filter_map(|l| serde_json::from_str(&l).ok()))
+	}
+
+	#[inline]
+	pub fn path() -> PathBuf { Xdg::state_dir().join("audit.jsonl") }
+
+	fn spawn(path: PathBuf) -> (mpsc::Sender<AuditEntry>, JoinHandle<()>) {
+		let (tx, rx) = mpsc::channel::<AuditEntry>();
+		let handle = thread::spawn(move || {
+			for entry in rx {
+				if let Err(e) = Self::append_to(&path, &entry) {
+					tracing::error!("Failed to write the audit log: {e}");
+				}
+			}
+		});
+		(tx, handle)
+	}
+
+	// Each entry is written in one go, so the instances appending at the same
+	// time don't interleave
+	fn append_to(path: &Path, entry: &AuditEntry) -> io::Result<()> {
+		let mut buf = serde_json::to_vec(entry)?;
+		buf.push(b'\n');
+
+		if let Some(parent) = path.parent() {
+			std::fs::create_dir_all(parent)?;
+		}
+		std::fs::OpenOptions::new().create(true).append(true).open(path)?.write_all(&buf)
+	}
+}
+
+impl AuditEntry {
+	pub fn new(op: AuditOp, source: Option<Url>, target: Url) -> Self {
+		Self {
+			time: String::new(),
+			user: Self::user(),
+			pid: std::process::id(),
+			op,
+			source,
+			target,
+			outcome: AuditOutcome::Succeeded,
+			error: None,
+		}
+	}
+
+	/// Marks it as failed for the reason.
+	pub fn fail(mut self, error: impl Display) -> Self {
+		self.outcome = AuditOutcome::Failed;
+		self.error = Some(error.to_string());
+		self
+	}
+
+	#[cfg(unix)]
+	fn user() -> String {
+		match uzers::get_current_username() {
+			Some(name) => name.to_string_lossy().into_owned(),
+			None => uzers::get_current_uid().to_string(),
+		}
+	}
+
+	#[cfg(windows)]
+	fn user() -> String { std::env::var("USERNAME").unwrap_or_default() }
+}
+
+impl Display for AuditEntry {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}  {}  {:<6}  ", self.time, self.user, self.op.to_string())?;
+		if let Some(source) = &self.source {
+			write!(f, "{} -> ", source.display())?;
+		}
+		write!(f, "{}", self.target.display())?;
+
+		match (self.outcome, &self.error) {
+			(AuditOutcome::Succeeded, _) => Ok(()),
+			(outcome, None) => write!(f, "  [{outcome}]"),
+			(outcome, Some(e)) => write!(f, "  [{outcome}: {e}]"),
+		}
+	}
+}
+
+impl Display for AuditOp {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(match self {
+			Self::Create => "create",
+			Self::Rename => "rename",
+			Self::Move => "move",
+			Self::Trash => "trash",
+			Self::Delete => "delete",
+		})
+	}
+}
+
+impl FromStr for AuditOp {
+	type Err = serde::de::value::Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		Self::deserialize(serde::de::value::StrDeserializer::new(s))
+	}
+}
+
+impl Display for AuditOutcome {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(match self {
+			Self::Succeeded => "succeeded",
+			Self::Failed => "failed",
+			Self::Cancelled => "cancelled",
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use anyhow::Result;
+
+	use super::*;
+	use crate::TempDir;
+
+	#[test]
+	fn test_audit() -> Result<()> {
+		let dir = TempDir::new("audit")?;
+		let path = dir.join("audit.jsonl");
+		let audit = Audit::new();
+		audit.enable(true);
+		*audit.writer.lock() = Some(Audit::spawn(path.clone()));
+
+		let rename = AuditEntry::new(AuditOp::Rename, Some("/tmp/a".parse()?), "/tmp/b".parse()?);
+		let delete = AuditEntry::new(AuditOp::Delete, None, "/tmp/c".parse()?).fail("Denied");
+		audit.record(rename.clone());
+		audit.record(delete.clone());
+		audit.flush();
+
+		// A line cut short doesn't stop the ones after it from being read
+		std::fs::OpenOptions::new().append(true).open(&path)?.write_all(b"{\"time\":\n")?;
+		Audit::append_to(&path, &rename)?;
+
+		let entries: Vec<_> = Audit::read(&path)?.collect();
+		let untimed: Vec<_> =
+			entries.iter().map(|e| AuditEntry { time: String::new(), ..e.clone() }).collect();
+		assert_eq!(untimed, [rename.clone(), delete.clone(), rename]);
+		assert!(entries[..2].iter().all(|e| !e.time.is_empty()));
+		assert_eq!(entries[1].outcome, AuditOutcome::Failed);
+		assert!(!std::fs::read_to_string(&path)?.lines().next().unwrap().contains("error"));
+
+		assert_eq!("trash".parse::<AuditOp>()?, AuditOp::Trash);
+		assert!(delete.to_string().ends_with("  delete  /tmp/c  [failed: Denied]"));
+
+		Ok(())
+	}
+}
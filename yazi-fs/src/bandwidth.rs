@@ -0,0 +1,104 @@
+use std::{io::{self, Read, Write}, sync::atomic::{AtomicU64, Ordering}, time::{Duration, Instant}};
+
+use parking_lot::Mutex;
+
+use crate::Pause;
+
+/// The bandwidth limit shared by all the copy tasks.
+pub static BANDWIDTH: Bandwidth = Bandwidth::new();
+
+/// Limits the rate of the bytes going through it, across all the threads.
+pub struct Bandwidth {
+	rate: AtomicU64,
+	next: Mutex<Option<Instant>>,
+}
+
+impl Bandwidth {
+	const CHUNK: usize = 64 * 1024;
+
+	pub const fn new() -> Self { Self { rate: AtomicU64::new(0), next: Mutex::new(None) } }
+
+	/// The limit in bytes per second, 0 for unlimited.
+	#[inline]
+	pub fn limit(&self) -> u64 { self.rate.load(Ordering::Relaxed) }
+
+	/// Changes the limit, which also applies to the transfers in progress.
+	#[inline]
+	pub fn set_limit(&self, rate: u64) { self.rate.store(rate, Ordering::Relaxed) }
+
+	/// Blocks the current thread until `n` more bytes are allowed.
+	pub fn acquire(&self, n: usize) {
+		let wait = self.reserve(n, Instant::now());
+		if !wait.is_zero() {
+			std::thread::sleep(wait);
+		}
+	}
+
+	// How long to wait from `now` for `n` more bytes, after the ones reserved before
+	fn reserve(&self, n: usize, now: Instant) -> Duration {
+		let rate = self.limit();
+		if rate == 0 {
+			return Duration::ZERO;
+		}
+
+		let mut next = self.next.lock();
+		let start = next.filter(|&t| t > now).unwrap_or(now);
+		let end = start + Duration::from_secs_f64(n as f64 / rate as f64);
+		*next = Some(end);
+		end - now
+	}
+
+	/// Like [`io::copy`], but at the rate allowed by the limit.
+	pub fn copy<R: Read, W: Write>(
+		&self,
+		reader: &mut R,
+		writer: &mut W,
+		pause: &Pause,
+	) -> io::Result<u64> {
+		let mut buf = vec![0; Self::CHUNK];
+		let mut written = 0;
+		loop {
+			pause.wait();
+			let n = match reader.read(&mut buf) {
+				Ok(0) => return Ok(written),
+				Ok(n) => n,
+				Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+				Err(e) => return Err(e),
+			};
+
+			self.acquire(n);
+			writer.write_all(&buf[..n])?;
+			written += n as u64;
+		}
+	}
+}
+
+impl Default for Bandwidth {
+	fn default() -> Self { Self::new() }
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_throttle() -> io::Result<()> {
+		let bandwidth = Bandwidth::new();
+		let data = vec![7; 300 * 1024];
+
+		let mut out = vec![];
+		assert_eq!(bandwidth.copy(&mut &data[..], &mut out, &Pause::default())?, data.len() as u64);
+		assert_eq!(out, data);
+
+		// Unlimited
+		let (now, half) = (Instant::now(), 512 * 1024);
+		assert_eq!(bandwidth.reserve(half, now), Duration::ZERO);
+
+		// 1 MiB/s, where the reservations queue up, but the idle time isn't made up for
+		bandwidth.set_limit(1024 * 1024);
+		assert_eq!(bandwidth.reserve(half, now), Duration::from_millis(500));
+		assert_eq!(bandwidth.reserve(half, now), Duration::from_secs(1));
+		assert_eq!(bandwidth.reserve(half, now + Duration::from_secs(3)), Duration::from_millis(500));
+		Ok(())
+	}
+}
@@ -0,0 +1,141 @@
+use std::{collections::BTreeMap, path::{Path, PathBuf}, time::SystemTime};
+
+use anyhow::Result;
+use parking_lot::{Mutex, MutexGuard};
+use yazi_shared::url::Url;
+
+use crate::{Xdg, write_atomic};
+
+/// The bookmarks, persisted in the state directory.
+pub static BOOKMARKS: Bookmarks = Bookmarks::new(|| Xdg::state_dir().join("bookmarks.json"));
+
+/// Marks naming the directories and files to jump to, on any scheme.
+pub struct Bookmarks {
+	path:  fn() -> PathBuf,
+	inner: Mutex<Inner>,
+}
+
+struct Inner {
+	marks: BTreeMap<String, Url>,
+	// The modified time and size of the file when it was last read or written,
+	// to tell whether another instance has changed it since
+	stamp: Option<(SystemTime, u64)>,
+}
+
+impl Bookmarks {
+	const fn new(path: fn() -> PathBuf) -> Self {
+		Self { path, inner: Mutex::new(Inner { marks: BTreeMap::new(), stamp: None }) }
+	}
+
+	/// The target of the mark.
+	pub fn get(&self, key: &str) -> Option<Url> { self.synced().marks.get(key).cloned() }
+
+	/// All the marks, in the order of their keys.
+	pub fn list(&self) -> Vec<(String, Url)> {
+		self.synced().marks.iter().map(|(k, u)| (k.clone(), u.clone())).collect()
+	}
+
+	/// The first letter from `a` to `z` that isn't a mark yet.
+	pub fn vacant(&self) -> Option<char> {
+		let inner = self.synced();
+		('a'..='z').find(|c| !inner.marks.contains_key(&c.to_string()))
+	}
+
+	/// Sets the mark to the URL, returning its previous target.
+	pub fn set(&self, key: &str, url: &Url) -> Result<Option<Url>> {
+		// Listed files are marked as the ones they stand for
+		let url = if url.is_listing() {
+			url.to_regular()
+		} else {
+			url.clone()
+		};
+
+		let mut inner = self.synced();
+		let old = inner.marks.insert(key.to_owned(), url);
+		self.save(&mut inner)?;
+		Ok(old)
+	}
+
+	/// Removes the mark, returning its target, and saves the marks right away.
+	pub fn remove(&self, key: &str) -> Result<Option<Url>> {
+		let mut inner = self.synced();
+		let Some(old) = inner.marks.remove(key) else { return Ok(None) };
+		self.save(&mut inner)?;
+		Ok(Some(old))
+	}
+
+	/// Locks the marks, after reloading them if the file has changed since.
+	fn synced(&self) -> MutexGuard<'_, Inner> {
+		let mut inner = self.inner.lock();
+		let path = (self.path)();
+
+		let stamp = Self::stamp(&path);
+		if stamp == inner.stamp {
+			return inner;
+		}
+
+		// A broken file is left for the next write to replace
+		let marks = match std::fs::read(&path) {
+			Ok(b) => serde_json::from_slice(&b).ok(),
+			Err(_) => Some(BTreeMap::new()),
+		};
+		if let Some(marks) = marks {
+			(inner.marks, inner.stamp) = (marks, stamp);
+		}
+		inner
+	}
+
+	fn save(&self, inner: &mut Inner) -> Result<()> {
+		let path = (self.path)();
+		write_atomic(&path, &serde_json::to_vec(&inner.marks)?)?;
+
+		inner.stamp = Self::stamp(&path);
+		Ok(())
+	}
+
+	fn stamp(path: &Path) -> Option<(SystemTime, u64)> {
+		let meta = std::fs::metadata(path).ok()?;
+		Some((meta.modified().ok()?, meta.len()))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::sync::OnceLock;
+
+	use super::*;
+	use crate::TempDir;
+
+	static DIR: OnceLock<PathBuf> = OnceLock::new();
+
+	fn path() -> PathBuf { DIR.get().unwrap().join("bookmarks.json") }
+
+	#[test]
+	fn test_bookmarks() -> Result<()> {
+		let dir = TempDir::new("bookmarks")?;
+		DIR.set(dir.to_path_buf()).unwrap();
+		let (a, b) = (Bookmarks::new(path), Bookmarks::new(path));
+		let url = |s: &str| -> Url { s.parse().unwrap() };
+
+		assert_eq!(a.set("a", &url("/home/u/projects"))?, None);
+		assert_eq!(a.set("w", &url("sftp://work//srv/www"))?, None);
+		assert_eq!(a.set("x", &url("archive:///tmp/x.zip//src"))?, None);
+		assert_eq!(a.vacant(), Some('b'));
+
+		// Search results are the files they stand for
+		a.set("s", &url("search://kw//tmp/s.txt"))?;
+		assert_eq!(a.get("s"), Some(url("/tmp/s.txt")));
+
+		// Shared through the file
+		assert_eq!(b.get("w"), Some(url("sftp://work//srv/www")));
+		assert_eq!(b.remove("x")?, Some(url("archive:///tmp/x.zip//src")));
+		assert_eq!(b.set("a", &url("/tmp"))?, Some(url("/home/u/projects")));
+
+		let keys: Vec<_> = a.list().into_iter().map(|(k, _)| k).collect();
+		assert_eq!(keys, ["a", "s", "w"]);
+		assert_eq!(a.get("a"), Some(url("/tmp")));
+		assert_eq!(a.remove("x")?, None);
+
+		Ok(())
+	}
+}
@@ -0,0 +1,203 @@
+use std::{fs::Metadata, io, str::FromStr};
+
+use anyhow::{Result, bail};
+use yazi_shared::url::Url;
+
+/// A change of permissions, octal such as `755`, or symbolic such as `u+x,go-w`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Chmod {
+	Octal(u32),
+	Symbolic(Vec<Clause>),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Clause {
+	who: u32,
+	ops: Vec<(Op, Perm)>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Op {
+	Add,
+	Remove,
+	Assign,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Perm {
+	Bits { bits: u32, cond_x: bool },
+	Copy(u32),
+}
+
+impl Chmod {
+	const ALL: u32 = Self::USER | Self::GROUP | Self::OTHER;
+	const GROUP: u32 = 0o2070;
+	const OTHER: u32 = 0o1007;
+	const USER: u32 = 0o4700;
+
+	/// Applies the change to the permission bits of a file.
+	pub fn apply(&self, mode: u32, dir: bool) -> u32 {
+		let clauses = match self {
+			Self::Octal(m) => return *m,
+			Self::Symbolic(clauses) => clauses,
+		};
+
+		let mut mode = mode & 0o7777;
+		for Clause { who, ops } in clauses {
+			for &(op, perm) in ops {
+				let bits = match perm {
+					Perm::Bits { bits, cond_x } if cond_x && (dir || mode & 0o111 != 0) => bits | 0o111,
+					Perm::Bits { bits, .. } => bits,
+					Perm::Copy(shift) => (mode >> shift & 0o7) * 0o111,
+				} & who;
+
+				mode = match op {
+					Op::Add => mode | bits,
+					Op::Remove => mode & !bits,
+					// Like `chmod`, the set-ID bits of directories are kept unless given
+					Op::Assign if dir => mode & !(who & !0o6000) | bits,
+					Op::Assign => mode & !who | bits,
+				};
+			}
+		}
+		mode
+	}
+
+	/// Changes the permissions of the file, skipping symlinks.
+	pub async fn set(&self, url: &Url, meta: &Metadata) -> io::Result<()> {
+		#[cfg(unix)]
+		{
+			use std::{fs::Permissions, os::unix::fs::PermissionsExt};
+
+			let old = meta.permissions().mode() & 0o7777;
+			let new = self.apply(old, meta.is_dir());
+			if meta.is_symlink() || new == old {
+				return Ok(());
+			}
+			crate::provider::set_permissions(url, Permissions::from_mode(new)).await
+		}
+		#[cfg(not(unix))]
+		{
+			_ = (url, meta);
+			Err(io::Error::new(io::ErrorKind::Unsupported, "Changing the mode is not supported"))
+		}
+	}
+}
+
+impl FromStr for Chmod {
+	type Err = anyhow::Error;
+
+	fn from_str(s: &str) -> Result<Self> {
+		let s = s.trim();
+		if s.is_empty() {
+			bail!("empty mode");
+		} else if s.bytes().all(|b| b.is_ascii_digit()) {
+			return match u32::from_str_radix(s, 8) {
+				Ok(m) if s.len() <= 4 => Ok(Self::Octal(m)),
+				_ => bail!("invalid octal mode `{s}`"),
+			};
+		}
+
+		s.split(',').map(Clause::from_str).collect::<Result<_>>().map(Self::Symbolic)
+	}
+}
+
+impl FromStr for Clause {
+	type Err = anyhow::Error;
+
+	fn from_str(s: &str) -> Result<Self> {
+		let mut chars = s.chars().peekable();
+
+		let mut who = 0;
+		while let Some(c) = chars.next_if(|c| "ugoa".contains(*c)) {
+			who |= match c {
+				'u' => Chmod::USER,
+				'g' => Chmod::GROUP,
+				'o' => Chmod::OTHER,
+				_ => Chmod::ALL,
+			};
+		}
+
+		let mut ops = vec![];
+		while let Some(c) = chars.next() {
+			let op = match c {
+				'+' => Op::Add,
+				'-' => Op::Remove,
+				'=' => Op::Assign,
+				_ => bail!("invalid operator `{c}` in `{s}`"),
+			};
+
+			let perm = match chars.peek() {
+				Some('u') => Perm::Copy(6),
+				Some('g') => Perm::Copy(3),
+				Some('o') => Perm::Copy(0),
+				_ => {
+					let (mut bits, mut cond_x) = (0, false);
+					while let Some(c) = chars.next_if(|c| !"+-=".contains(*c)) {
+						match c {
+							'r' => bits |= 0o444,
+							'w' => bits |= 0o222,
+							'x' => bits |= 0o111,
+							'X' => cond_x = true,
+							's' => bits |= 0o6000,
+							't' => bits |= 0o1000,
+							_ => bail!("invalid permission `{c}` in `{s}`"),
+						}
+					}
+					ops.push((op, Perm::Bits { bits, cond_x }));
+					continue;
+				}
+			};
+
+			chars.next();
+			ops.push((op, perm));
+		}
+
+		if ops.is_empty() {
+			bail!("missing operator in `{s}`");
+		}
+		Ok(Self { who: if who == 0 { Chmod::ALL } else { who }, ops })
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn apply(s: &str, mode: u32, dir: bool) -> u32 { s.parse::<Chmod>().unwrap().apply(mode, dir) }
+
+	#[test]
+	fn test_octal() {
+		assert_eq!(apply("755", 0o644, false), 0o755);
+		assert_eq!(apply("0600", 0o644, false), 0o600);
+		assert_eq!(apply("4755", 0o644, false), 0o4755);
+
+		assert!("8".parse::<Chmod>().is_err());
+		assert!("17777".parse::<Chmod>().is_err());
+	}
+
+	#[test]
+	fn test_symbolic() {
+		assert_eq!(apply("u+x", 0o644, false), 0o744);
+		assert_eq!(apply("+x", 0o644, false), 0o755);
+		assert_eq!(apply("go-w", 0o666, false), 0o644);
+		assert_eq!(apply("u=rw,go=r", 0o777, false), 0o644);
+		assert_eq!(apply("o=", 0o777, false), 0o770);
+		assert_eq!(apply("u+x-w", 0o644, false), 0o544);
+		assert_eq!(apply("g=u", 0o640, false), 0o660);
+		assert_eq!(apply("u+s,+t", 0o755, false), 0o5755);
+
+		// `X` only for directories, and files executable by anyone
+		assert_eq!(apply("a+X", 0o644, false), 0o644);
+		assert_eq!(apply("a+X", 0o744, false), 0o755);
+		assert_eq!(apply("a+X", 0o700, true), 0o711);
+
+		// Set-ID bits of directories are kept by `=`
+		assert_eq!(apply("g=rx", 0o2775, true), 0o2755);
+		assert_eq!(apply("g=rx", 0o2775, false), 0o755);
+
+		assert!("u".parse::<Chmod>().is_err());
+		assert!("u+y".parse::<Chmod>().is_err());
+		assert!("x+r".parse::<Chmod>().is_err());
+	}
+}
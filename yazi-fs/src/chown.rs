@@ -0,0 +1,111 @@
+use std::{io, str::FromStr};
+
+use anyhow::{Result, bail};
+use yazi_shared::url::Url;
+
+/// A change of ownership, as in `user`, `user:group` and `:group`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Chown {
+	pub uid: Option<u32>,
+	pub gid: Option<u32>,
+}
+
+impl Chown {
+	/// Changes the owner and group of the file, or the symlink itself.
+	pub async fn set(&self, url: &Url) -> io::Result<()> {
+		#[cfg(unix)]
+		{
+			crate::provider::lchown(url, self.uid, self.gid).await
+		}
+		#[cfg(not(unix))]
+		{
+			_ = url;
+			Err(io::Error::new(io::ErrorKind::Unsupported, "Changing the owner is not supported"))
+		}
+	}
+
+	/// The `user:group` form of the owner and group, named where possible.
+	#[cfg(unix)]
+	pub fn display(uid: u32, gid: u32) -> String {
+		use uzers::{Groups, Users};
+		use yazi_shared::USERS_CACHE;
+
+		let user = USERS_CACHE.get_user_by_uid(uid).map(|u| u.name().to_string_lossy().into_owned());
+		let group = USERS_CACHE.get_group_by_gid(gid).map(|g| g.name().to_string_lossy().into_owned());
+		format!(
+			"{}:{}",
+			user.unwrap_or_else(|| uid.to_string()),
+			group.unwrap_or_else(|| gid.to_string())
+		)
+	}
+}
+
+impl FromStr for Chown {
+	type Err = anyhow::Error;
+
+	fn from_str(s: &str) -> Result<Self> {
+		let (user, group) = s.trim().split_once(':').unwrap_or((s.trim(), ""));
+		let chown = Self {
+			uid: if user.is_empty() { None } else { Some(sys::uid(user)?) },
+			gid: if group.is_empty() { None } else { Some(sys::gid(group)?) },
+		};
+
+		if chown == Self::default() {
+			bail!("empty owner");
+		}
+		Ok(chown)
+	}
+}
+
+#[cfg(unix)]
+mod sys {
+	use anyhow::{Result, bail};
+
+	pub(super) fn uid(s: &str) -> Result<u32> {
+		if let Ok(id) = s.parse() {
+			Ok(id)
+		} else if let Some(user) = uzers::get_user_by_name(s) {
+			Ok(user.uid())
+		} else {
+			bail!("no such user `{s}`")
+		}
+	}
+
+	pub(super) fn gid(s: &str) -> Result<u32> {
+		if let Ok(id) = s.parse() {
+			Ok(id)
+		} else if let Some(group) = uzers::get_group_by_name(s) {
+			Ok(group.gid())
+		} else {
+			bail!("no such group `{s}`")
+		}
+	}
+}
+
+#[cfg(not(unix))]
+mod sys {
+	use anyhow::{Result, bail};
+
+	pub(super) fn uid(_: &str) -> Result<u32> { bail!("Changing the owner is not supported") }
+
+	pub(super) fn gid(_: &str) -> Result<u32> { bail!("Changing the group is not supported") }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_parse() {
+		let parse = |s: &str| s.parse::<Chown>().ok();
+
+		assert_eq!(parse("1000"), Some(Chown { uid: Some(1000), gid: None }));
+		assert_eq!(parse("1000:100"), Some(Chown { uid: Some(1000), gid: Some(100) }));
+		assert_eq!(parse(":100"), Some(Chown { uid: None, gid: Some(100) }));
+		assert_eq!(parse("root:"), Some(Chown { uid: Some(0), gid: None }));
+
+		assert_eq!(parse(""), None);
+		assert_eq!(parse(":"), None);
+		assert_eq!(parse("no-such-user-of-yazi"), None);
+	}
+}
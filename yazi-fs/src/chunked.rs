@@ -0,0 +1,143 @@
+use std::{fs::File, io, sync::atomic::{AtomicU8, AtomicU64, Ordering}};
+
+use crate::{BANDWIDTH, Pause};
+
+/// The settings of the chunked copy shared by all the copy tasks.
+pub static CHUNKED: Chunked = Chunked::new();
+
+/// Copies large files in chunks by multiple streams at once.
+pub struct Chunked {
+	size:    AtomicU64,
+	streams: AtomicU8,
+}
+
+impl Chunked {
+	const BUF: usize = 1024 * 1024;
+
+	pub const fn new() -> Self { Self { size: AtomicU64::new(0), streams: AtomicU8::new(1) } }
+
+	/// Changes the size of each chunk, and how many are copied at once.
+	pub fn set(&self, size: u64, streams: u8) {
+		self.size.store(size, Ordering::Relaxed);
+		self.streams.store(streams, Ordering::Relaxed);
+	}
+
+	/// The size of each chunk and the number of streams, if `len` is large enough.
+	pub fn get(&self, len: u64) -> Option<(u64, u8)> {
+		let (size, streams) = (self.size.load(Ordering::Relaxed), self.streams.load(Ordering::Relaxed));
+		(streams > 1 && size > 0 && len > size).then_some((size, streams))
+	}
+
+	/// Copies the first `len` bytes of `reader` into `writer`, chunk by chunk.
+	pub(crate) fn copy(
+		reader: &File,
+		writer: &File,
+		len: u64,
+		(size, streams): (u64, u8),
+		progress: &AtomicU64,
+		pause: &Pause,
+	) -> io::Result<u64> {
+		writer.set_len(len)?;
+
+		let next = AtomicU64::new(0);
+		let work = || -> io::Result<()> {
+			let mut buf = vec![0; Self::BUF];
+			loop {
+				let start = next.fetch_add(size, Ordering::Relaxed);
+				if start >= len {
+					return Ok(());
+				}
+
+				let (mut off, end) = (start, len.min(start + size));
+				while off < end {
+					pause.wait();
+					let n = buf.len().min((end - off) as usize);
+					let n = match read_at(reader, &mut buf[..n], off) {
+						Ok(0) => Err(io::ErrorKind::UnexpectedEof.into()),
+						Ok(n) => Ok(n),
+						Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+						Err(e) => Err(e),
+					}
+					.inspect_err(|_| next.store(len, Ordering::Relaxed))?;
+
+					BANDWIDTH.acquire(n);
+					write_all_at(writer, &buf[..n], off)
+						.inspect_err(|_| next.store(len, Ordering::Relaxed))?;
+
+					off += n as u64;
+					progress.fetch_add(n as u64, Ordering::Relaxed);
+				}
+			}
+		};
+
+		std::thread::scope(|s| {
+			let handles: Vec<_> = (0..streams).map(|_| s.spawn(work)).collect();
+			handles.into_iter().try_for_each(|h| {
+				h.join().unwrap_or_else(|_| Err(io::Error::other("Copy stream panicked")))
+			})
+		})?;
+		Ok(len)
+	}
+}
+
+impl Default for Chunked {
+	fn default() -> Self { Self::new() }
+}
+
+#[cfg(unix)]
+fn read_at(file: &File, buf: &mut [u8], offset: u64) -> io::Result<usize> {
+	std::os::unix::fs::FileExt::read_at(file, buf, offset)
+}
+
+#[cfg(unix)]
+fn write_all_at(file: &File, buf: &[u8], offset: u64) -> io::Result<()> {
+	std::os::unix::fs::FileExt::write_all_at(file, buf, offset)
+}
+
+#[cfg(windows)]
+fn read_at(file: &File, buf: &mut [u8], offset: u64) -> io::Result<usize> {
+	std::os::windows::fs::FileExt::seek_read(file, buf, offset)
+}
+
+#[cfg(windows)]
+fn write_all_at(file: &File, mut buf: &[u8], mut offset: u64) -> io::Result<()> {
+	while !buf.is_empty() {
+		match std::os::windows::fs::FileExt::seek_write(file, buf, offset) {
+			Ok(0) => return Err(io::ErrorKind::WriteZero.into()),
+			Ok(n) => (buf, offset) = (&buf[n..], offset + n as u64),
+			Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+			Err(e) => return Err(e),
+		}
+	}
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::TempDir;
+
+	#[test]
+	fn test_copy() -> io::Result<()> {
+		let dir = TempDir::new("chunked")?;
+		let (a, b) = (dir.join("a"), dir.join("b"));
+
+		let data: Vec<u8> = (0..3_000_017u32).map(|i| (i % 251) as u8).collect();
+		std::fs::write(&a, &data)?;
+
+		let chunked = Chunked::new();
+		assert_eq!(chunked.get(data.len() as u64), None);
+		chunked.set(1024 * 1024, 4);
+		assert_eq!(chunked.get(1024 * 1024), None);
+
+		let (progress, pause) = (AtomicU64::new(0), Pause::default());
+		let (reader, writer) = (File::open(&a)?, File::create(&b)?);
+		let opts = chunked.get(data.len() as u64).unwrap();
+		let copied = Chunked::copy(&reader, &writer, data.len() as u64, opts, &progress, &pause)?;
+		assert_eq!(copied, 3_000_017);
+		assert_eq!(progress.into_inner(), 3_000_017);
+		assert_eq!(std::fs::read(&b)?, data);
+
+		Ok(())
+	}
+}
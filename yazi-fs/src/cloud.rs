@@ -0,0 +1,84 @@
+use std::{fs::Metadata, io};
+
+use yazi_shared::url::Url;
+
+use crate::provider;
+
+/// Where the content of a file synced by a cloud drive is.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Cloud {
+	/// Only a placeholder, reading it downloads the content.
+	Online,
+	/// Managed by a cloud drive, with the content downloaded.
+	Local,
+}
+
+impl Cloud {
+	/// The status of the file, or `None` if it isn't known to be a cloud one.
+	pub async fn status(url: &Url) -> io::Result<Option<Self>> {
+		Ok(Self::from_meta(&provider::metadata(url).await?))
+	}
+
+	/// Tells from the attributes, the `SF_DATALESS` flag, or the blocks allocated.
+	pub fn from_meta(meta: &Metadata) -> Option<Self> {
+		if meta.is_dir() {
+			return None;
+		}
+
+		#[cfg(windows)]
+		{
+			use std::os::windows::fs::MetadataExt;
+
+			use windows_sys::Win32::Storage::FileSystem::{FILE_ATTRIBUTE_OFFLINE, FILE_ATTRIBUTE_PINNED, FILE_ATTRIBUTE_RECALL_ON_DATA_ACCESS, FILE_ATTRIBUTE_RECALL_ON_OPEN, FILE_ATTRIBUTE_UNPINNED};
+
+			let online = FILE_ATTRIBUTE_OFFLINE
+				| FILE_ATTRIBUTE_RECALL_ON_OPEN
+				| FILE_ATTRIBUTE_RECALL_ON_DATA_ACCESS;
+
+			let attrs = meta.file_attributes();
+			if attrs & online != 0 {
+				Some(Self::Online)
+			} else if attrs & (FILE_ATTRIBUTE_PINNED | FILE_ATTRIBUTE_UNPINNED) != 0 {
+				Some(Self::Local)
+			} else {
+				None
+			}
+		}
+		#[cfg(target_os = "macos")]
+		{
+			use std::os::macos::fs::MetadataExt;
+
+			const SF_DATALESS: u32 = 0x40000000;
+			(meta.st_flags() & SF_DATALESS != 0).then_some(Self::Online)
+		}
+		#[cfg(all(unix, not(target_os = "macos")))]
+		{
+			use std::os::unix::fs::MetadataExt;
+
+			(meta.len() > 0 && meta.blocks() == 0).then_some(Self::Online)
+		}
+	}
+}
+
+#[cfg(all(test, unix, not(target_os = "macos")))]
+mod tests {
+	use std::fs;
+
+	use super::*;
+	use crate::TempDir;
+
+	#[test]
+	fn test_from_meta() {
+		let dir = TempDir::new("cloud").unwrap();
+
+		let path = dir.join("local");
+		fs::write(&path, b"downloaded").unwrap();
+		assert_eq!(Cloud::from_meta(&fs::metadata(&path).unwrap()), None);
+
+		let path = dir.join("online");
+		fs::File::create(&path).unwrap().set_len(1 << 20).unwrap();
+		assert_eq!(Cloud::from_meta(&fs::metadata(&path).unwrap()), Some(Cloud::Online));
+
+		assert_eq!(Cloud::from_meta(&fs::metadata(&dir).unwrap()), None);
+	}
+}
@@ -0,0 +1,212 @@
+use std::{collections::HashMap, io, str::FromStr, time::UNIX_EPOCH};
+
+use serde::Deserialize;
+use yazi_macro::relay;
+use yazi_shared::url::{Url, Urn, UrnBuf};
+
+use crate::{cha::Cha, checksum, provider};
+
+/// The differences between the entries of two directories, which can be of different schemes.
+#[derive(Debug)]
+pub struct Comparison {
+	pub left:   Url,
+	pub right:  Url,
+	pub by:     CompareBy,
+	pub filter: bool,
+	diffs:      HashMap<UrnBuf, Difference>,
+}
+
+/// What two files with the same name are compared by, besides their type.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum CompareBy {
+	Size,
+	/// Size and modification time, to the second as it's what all the schemes can keep.
+	#[default]
+	Mtime,
+	/// Size and content, which has to be read on both sides.
+	Hash,
+}
+
+impl FromStr for CompareBy {
+	type Err = serde::de::value::Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		Self::deserialize(serde::de::value::StrDeserializer::new(s))
+	}
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Difference {
+	OnlyLeft,
+	OnlyRight,
+	Differ,
+}
+
+impl Difference {
+	pub fn as_str(self) -> &'static str {
+		match self {
+			Self::OnlyLeft => "only-left",
+			Self::OnlyRight => "only-right",
+			Self::Differ => "differ",
+		}
+	}
+}
+
+impl Comparison {
+	pub async fn new(left: Url, right: Url, by: CompareBy) -> io::Result<Self> {
+		let (l, r) = tokio::try_join!(Self::read(&left), Self::read(&right))?;
+		let mut diffs = Self::diff(&l, &r, by);
+
+		if by == CompareBy::Hash {
+			for (urn, a) in &l {
+				if a.is_dir() || diffs.contains_key(urn) {
+					continue;
+				}
+				let (from, to) = (left.join(urn), right.join(urn));
+				match tokio::try_join!(checksum(&from), checksum(&to)) {
+					Ok((a, b)) if a == b => {}
+					_ => _ = diffs.insert(urn.clone(), Difference::Differ),
+				}
+			}
+		}
+
+		Ok(Self { left, right, by, filter: false, diffs })
+	}
+
+	#[inline]
+	pub fn emit(self) {
+		yazi_shared::event::Event::Call(relay!(mgr:update_compare).with_any("comparison", self).into())
+			.emit();
+	}
+
+	/// The difference of a file in one of the two directories, if any.
+	pub fn get(&self, dir: &Url, urn: &Urn) -> Option<Difference> {
+		let diff = *self.diffs.get(urn)?;
+		match diff {
+			Difference::OnlyLeft | Difference::Differ if *dir == self.left => Some(diff),
+			Difference::OnlyRight | Difference::Differ if *dir == self.right => Some(diff),
+			_ => None,
+		}
+	}
+
+	/// The files in one of the two directories that differ from the other side.
+	pub fn differences<'a>(&'a self, dir: &'a Url) -> impl Iterator<Item = &'a Urn> {
+		self.diffs.keys().filter(|&u| self.get(dir, u).is_some()).map(|u| u.as_urn())
+	}
+
+	/// Counts the files only in the left, only in the right, and differing.
+	pub fn counts(&self) -> (usize, usize, usize) {
+		self.diffs.values().fold((0, 0, 0), |(l, r, d), diff| match diff {
+			Difference::OnlyLeft => (l + 1, r, d),
+			Difference::OnlyRight => (l, r + 1, d),
+			Difference::Differ => (l, r, d + 1),
+		})
+	}
+
+	/// Forgets the differences of the files, once they've been synced.
+	pub fn resolve<'a>(&mut self, urns: impl IntoIterator<Item = &'a Urn>) {
+		for urn in urns {
+			self.diffs.remove(urn);
+		}
+	}
+
+	fn diff(
+		left: &HashMap<UrnBuf, Cha>,
+		right: &HashMap<UrnBuf, Cha>,
+		by: CompareBy,
+	) -> HashMap<UrnBuf, Difference> {
+		let mut diffs = HashMap::new();
+		for (urn, a) in left {
+			match right.get(urn) {
+				None => _ = diffs.insert(urn.clone(), Difference::OnlyLeft),
+				Some(b) if Self::differ(a, b, by) => _ = diffs.insert(urn.clone(), Difference::Differ),
+				Some(_) => {}
+			}
+		}
+		for urn in right.keys().filter(|&u| !left.contains_key(u)) {
+			diffs.insert(urn.clone(), Difference::OnlyRight);
+		}
+		diffs
+	}
+
+	fn differ(a: &Cha, b: &Cha, by: CompareBy) -> bool {
+		let secs =
+			|c: &Cha| c.mtime.and_then(|t| t.duration_since(UNIX_EPOCH).ok()).map(|d| d.as_secs());
+		if a.is_dir() || b.is_dir() {
+			a.is_dir() != b.is_dir()
+		} else if a.len != b.len {
+			true
+		} else {
+			by == CompareBy::Mtime && secs(a) != secs(b)
+		}
+	}
+
+	async fn read(dir: &Url) -> io::Result<HashMap<UrnBuf, Cha>> {
+		let mut it = provider::read_dir(dir).await?;
+		let mut chas = HashMap::new();
+		while let Some(entry) = it.next_entry().await? {
+			let url = entry.url();
+			let Ok(meta) = entry.metadata().await else { continue };
+			chas.insert(url.urn_owned(), Cha::from_follow(&url, meta).await);
+		}
+		Ok(chas)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use anyhow::Result;
+	use tokio::fs;
+
+	use super::*;
+	use crate::TempDir;
+
+	#[tokio::test]
+	async fn test_comparison() -> Result<()> {
+		let root = TempDir::new("compare")?;
+		let (left, right) = (Url::from(root.join("left")), Url::from(root.join("right")));
+		fs::create_dir_all(root.join("left/dir")).await?;
+		fs::create_dir_all(root.join("right/dir")).await?;
+
+		for (name, l, r) in [
+			("same", Some("abc"), Some("abc")),
+			("content", Some("abc"), Some("abd")),
+			("size", Some("abc"), Some("abcd")),
+			("only-left", Some("a"), None),
+			("only-right", None, Some("a")),
+		] {
+			if let Some(s) = l {
+				fs::write(root.join("left").join(name), s).await?;
+			}
+			if let Some(s) = r {
+				fs::write(root.join("right").join(name), s).await?;
+			}
+		}
+		fs::write(root.join("left/kind"), "").await?;
+		fs::create_dir(root.join("right/kind")).await?;
+
+		let urn = |s: &str| UrnBuf::from(s);
+		let diffs = |c: &Comparison, dir: &Url| {
+			let mut v: Vec<_> = c.differences(dir).map(|u| u.to_owned()).collect();
+			v.sort_by(|a, b| a.encoded_bytes().cmp(b.encoded_bytes()));
+			v
+		};
+
+		let c = Comparison::new(left.clone(), right.clone(), CompareBy::Size).await?;
+		assert_eq!(diffs(&c, &left), [urn("kind"), urn("only-left"), urn("size")]);
+		assert_eq!(diffs(&c, &right), [urn("kind"), urn("only-right"), urn("size")]);
+		assert_eq!(c.get(&left, &urn("size")), Some(Difference::Differ));
+		assert_eq!(c.get(&left, &urn("only-right")), None);
+		assert_eq!(c.get(&root.join("other").into(), &urn("size")), None);
+		assert_eq!(c.counts(), (1, 1, 2));
+
+		let mut c = Comparison::new(left.clone(), right.clone(), CompareBy::Hash).await?;
+		assert_eq!(diffs(&c, &left), [urn("content"), urn("kind"), urn("only-left"), urn("size")]);
+
+		c.resolve([urn("content").as_urn(), urn("only-left").as_urn()]);
+		assert_eq!(diffs(&c, &left), [urn("kind"), urn("size")]);
+
+		Ok(())
+	}
+}
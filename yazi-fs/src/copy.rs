@@ -0,0 +1,129 @@
+use std::fmt::{self, Display};
+
+/// How the content of a file was copied.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CopyMethod {
+	/// Shares the data with the source until either is modified.
+	Reflink,
+	/// Copies within the kernel without going through the userspace.
+	Kernel,
+	/// Reads and writes through a buffer by this many streams at once.
+	Chunked(u8),
+	/// Reads and writes through a buffer, which the bandwidth limit applies to.
+	Buffered,
+}
+
+impl Display for CopyMethod {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Reflink => f.write_str("reflink (copy-on-write)"),
+			Self::Kernel => f.write_str("in-kernel copy"),
+			Self::Chunked(n) => write!(f, "chunked copy in {n} streams"),
+			Self::Buffered => f.write_str("buffered copy"),
+		}
+	}
+}
+
+/// The progress of [`crate::copy_with_progress`].
+#[derive(Debug)]
+pub enum CopyProg {
+	/// The number of bytes copied since the last report.
+	Adv(u64),
+	/// Finished copying, and how.
+	Done(CopyMethod),
+}
+
+/// Clones the content of `reader` into `writer`.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub(crate) fn reflink(reader: &std::fs::File, writer: &std::fs::File) -> std::io::Result<()> {
+	use std::{io, os::fd::AsRawFd};
+
+	if unsafe { libc::ioctl(writer.as_raw_fd(), libc::FICLONE, reader.as_raw_fd()) } == -1 {
+		return Err(io::Error::last_os_error());
+	}
+	Ok(())
+}
+
+/// Copies the content of `reader` into `writer` within the kernel, if possible.
+#[cfg(target_os = "linux")]
+pub(crate) fn copy_range(
+	reader: &std::fs::File,
+	writer: &std::fs::File,
+) -> Option<std::io::Result<u64>> {
+	use std::{io, os::fd::AsRawFd, ptr::null_mut};
+
+	let (r, w) = (reader.as_raw_fd(), writer.as_raw_fd());
+	let mut written = 0;
+	loop {
+		match unsafe { libc::copy_file_range(r, null_mut(), w, null_mut(), 1 << 30, 0) } {
+			// Some pseudo files report no data, which a buffered copy still reads
+			0 if written == 0 => return None,
+			0 => return Some(Ok(written)),
+			n if n > 0 => written += n as u64,
+			_ => {
+				let e = io::Error::last_os_error();
+				match e.raw_os_error() {
+					Some(libc::EINTR) => continue,
+					// Not supported between them, e.g. across filesystems on older kernels
+					Some(
+						libc::ENOSYS
+						| libc::EXDEV
+						| libc::EINVAL
+						| libc::EOPNOTSUPP
+						| libc::EPERM
+						| libc::EBADF,
+					) if written == 0 => return None,
+					_ => return Some(Err(e)),
+				}
+			}
+		}
+	}
+}
+
+#[cfg(target_os = "android")]
+pub(crate) fn copy_range(_: &std::fs::File, _: &std::fs::File) -> Option<std::io::Result<u64>> {
+	None
+}
+
+/// Clones `from` to `to`, which must not exist yet.
+#[cfg(target_os = "macos")]
+pub(crate) fn clonefile(from: &std::path::Path, to: &std::path::Path) -> std::io::Result<()> {
+	use std::{ffi::CString, io, os::unix::ffi::OsStrExt};
+
+	let from = CString::new(from.as_os_str().as_bytes())?;
+	let to = CString::new(to.as_os_str().as_bytes())?;
+	if unsafe { libc::clonefile(from.as_ptr(), to.as_ptr(), 0) } == -1 {
+		return Err(io::Error::last_os_error());
+	}
+	Ok(())
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android", target_os = "macos")))]
+pub(crate) fn clonefile(_: &std::path::Path, _: &std::path::Path) -> std::io::Result<()> {
+	Err(std::io::ErrorKind::Unsupported.into())
+}
+
+#[cfg(test)]
+mod tests {
+	#[cfg(target_os = "linux")]
+	#[test]
+	fn test_copy_range() -> std::io::Result<()> {
+		use std::fs::{self, File};
+
+		let dir = crate::TempDir::new("copy-range")?;
+		let (a, b) = (dir.join("a"), dir.join("b"));
+		fs::write(&a, "yazi".repeat(100_000))?;
+
+		let (mut reader, mut writer) = (File::open(&a)?, File::create(&b)?);
+		match super::copy_range(&reader, &writer) {
+			Some(n) => assert_eq!(n?, 400_000),
+			None => _ = std::io::copy(&mut reader, &mut writer)?,
+		}
+		assert_eq!(fs::read(&a)?, fs::read(&b)?);
+
+		// Pseudo files fall back to a buffered copy
+		assert!(super::copy_range(&File::open("/proc/self/stat")?, &File::create(&b)?).is_none());
+
+		Ok(())
+	}
+}
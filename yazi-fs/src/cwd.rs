@@ -1,16 +1,22 @@
 use std::{env::{current_dir, set_current_dir}, ops::Deref, path::PathBuf, sync::{Arc, atomic::{AtomicBool, Ordering}}};
 
 use arc_swap::ArcSwap;
+use parking_lot::Mutex;
 use yazi_shared::{RoCell, url::Url};
 
+use crate::{DirIdentity, Relocation};
+
 pub static CWD: RoCell<Cwd> = RoCell::new();
 
-pub struct Cwd(ArcSwap<Url>);
+pub struct Cwd {
+	url:      ArcSwap<Url>,
+	identity: Mutex<Option<(PathBuf, DirIdentity)>>,
+}
 
 impl Deref for Cwd {
 	type Target = ArcSwap<Url>;
 
-	fn deref(&self) -> &Self::Target { &self.0 }
+	fn deref(&self) -> &Self::Target { &self.url }
 }
 
 impl Default for Cwd {
@@ -21,7 +27,7 @@ impl Default for Cwd {
 			.or_else(|| current_dir().ok())
 			.expect("failed to get current working directory");
 
-		Self(ArcSwap::new(Arc::new(Url::from(p))))
+		Self { url: ArcSwap::new(Arc::new(Url::from(p))), identity: Default::default() }
 	}
 }
 
@@ -40,6 +46,15 @@ impl Cwd {
 		true
 	}
 
+	/// Where the directory at `url` has gone, if it's the current one.
+	pub fn relocation(&self, url: &Url) -> Option<Relocation> {
+		let path = url.as_path().filter(|_| url.is_regular())?;
+		let identity = self.identity.lock();
+
+		let (old, id) = identity.as_ref().filter(|(p, _)| p == path)?;
+		id.relocation(old)
+	}
+
 	fn sync_cwd() {
 		static SYNCING: AtomicBool = AtomicBool::new(false);
 		if SYNCING.swap(true, Ordering::Relaxed) {
@@ -59,6 +74,12 @@ impl Cwd {
 			{
 				set_current_dir(p).ok();
 			}
+
+			let url = CWD.load();
+			*CWD.identity.lock() = url
+				.as_path()
+				.filter(|_| url.is_regular())
+				.and_then(|p| Some((p.to_owned(), DirIdentity::open(p).ok()?)));
 		});
 	}
 }
@@ -0,0 +1,355 @@
+use std::{cmp::Reverse, collections::{HashMap, HashSet}, hash::Hash, io, path::{Path, PathBuf}, time::SystemTime};
+
+use anyhow::Result;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use yazi_shared::url::Url;
+
+use crate::{File, LruStore, Xdg, checksum_sync, fingerprint, hard_link_over};
+
+/// The duplicates found the last time, and the checksums of the files hashed.
+pub static DUPES: Dupes = Dupes::new();
+
+/// The groups behind the `dupes://` listing.
+pub struct Dupes {
+	groups: RwLock<Vec<Vec<DupeFile>>>,
+	sums:   LruStore<PathBuf, (SystemTime, u64, u128)>,
+}
+
+/// A file to be checked for duplicates.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DupeFile {
+	pub path:  PathBuf,
+	pub len:   u64,
+	pub mtime: Option<SystemTime>,
+}
+
+#[derive(Deserialize, Serialize)]
+struct Entry {
+	path:  PathBuf,
+	mtime: SystemTime,
+	len:   u64,
+	sum:   u128,
+}
+
+impl From<Entry> for (PathBuf, (SystemTime, u64, u128)) {
+	fn from(e: Entry) -> Self { (e.path, (e.mtime, e.len, e.sum)) }
+}
+
+impl From<(&PathBuf, &(SystemTime, u64, u128))> for Entry {
+	fn from((path, &(mtime, len, sum)): (&PathBuf, &(SystemTime, u64, u128))) -> Self {
+		Self { path: path.clone(), mtime, len, sum }
+	}
+}
+
+impl Dupes {
+	// Files up to twice the sample of a fingerprint are sampled whole by it
+	const WHOLE: u64 = 128 * 1024;
+
+	const fn new() -> Self {
+		Self { groups: RwLock::new(vec![]), sums: LruStore::new(100000) }
+	}
+
+	/// The files that can have duplicates, grouped by their size.
+	pub fn by_size(files: Vec<DupeFile>) -> Vec<Vec<DupeFile>> {
+		let files = files.into_iter().filter(|f| f.len > 0).collect();
+		Self::group_by(files, |f| Some(f.len), &mut |_| {})
+	}
+
+	/// Splits the files of a size into the groups of the same content.
+	pub fn split(&self, files: Vec<DupeFile>, mut adv: impl FnMut(u64)) -> Vec<Vec<DupeFile>> {
+		let files = Self::unlinked(files, &mut adv);
+
+		// Both ends tell most of the files apart without reading them whole
+		let mut groups = Self::group_by(files, |f| fingerprint(&Url::from(&f.path)).ok(), &mut adv);
+
+		// They're all of the same size, and only the larger ones are read whole
+		if groups.first().is_some_and(|g| g[0].len > Self::WHOLE) {
+			groups = groups
+				.into_iter()
+				.flat_map(|g| Self::group_by(g, |f| self.checksum(f), &mut adv))
+				.collect();
+		}
+
+		groups.iter().flatten().for_each(|f| adv(f.len));
+		groups
+	}
+
+	/// Replaces the duplicates listed with the groups found, returning how many there are.
+	pub fn publish(&self, mut groups: Vec<Vec<DupeFile>>) -> usize {
+		for g in &mut groups {
+			g.sort_by_key(|f| Reverse(f.mtime));
+		}
+		groups.sort_by_key(|g| Reverse(g[0].len * (g.len() as u64 - 1)));
+
+		let len = groups.len();
+		*self.groups.write() = groups;
+		len
+	}
+
+	/// The root of the listing.
+	pub fn root() -> Url { "dupes://".parse().expect("valid URL") }
+
+	/// The groups of duplicates found the last time, each the newest first.
+	pub fn groups(&self) -> Vec<Vec<PathBuf>> {
+		self.groups.read().iter().map(|g| g.iter().map(|f| f.path.clone()).collect()).collect()
+	}
+
+	/// Replaces the duplicate with a hardlink to the file kept.
+	pub async fn link(&self, keep: &Path, dupe: &Path) -> io::Result<()> {
+		let found = |p: &Path| {
+			let groups = self.groups.read();
+			let f = groups.iter().flatten().find(|f| f.path == p).cloned();
+			f.ok_or_else(|| io::Error::other(format!("`{}` isn't a duplicate found", p.display())))
+		};
+
+		let (keep, dupe) = (found(keep)?, found(dupe)?);
+		Self::same_device(Self::unchanged(&keep).await?, Self::unchanged(&dupe).await?)?;
+		hard_link_over(Url::from(&keep.path), Url::from(&dupe.path)).await
+	}
+
+	/// Reads the listing of a `dupes://` URL, one group after another.
+	pub async fn read(&self, url: &Url) -> Vec<File> {
+		let mut files = vec![];
+		for group in self.groups() {
+			let (mut seen, mut found) = (HashSet::new(), Vec::with_capacity(group.len()));
+			for path in group {
+				let Ok(rel) = path.strip_prefix(url.loc.base()) else { continue };
+				let Ok(file) = File::new(url.join(rel)).await else { continue };
+				if Self::identity(&path).await.is_none_or(|id| seen.insert(id)) {
+					found.push(file);
+				}
+			}
+			if found.len() > 1 {
+				files.extend(found);
+			}
+		}
+		files
+	}
+
+	/// Loads the checksums saved by [`Self::save`], keeping those computed in the meantime.
+	pub fn load(&self) -> Result<()> { self.sums.load_from::<Entry>(&Self::path()) }
+
+	/// Saves the checksums to the state directory, blocking the current thread.
+	pub fn save(&self) -> Result<()> { self.sums.save_to::<Entry>(&Self::path()) }
+
+	// The checksum of the whole file, cached by its path as long as its size and
+	// modification time stay the same
+	fn checksum(&self, f: &DupeFile) -> Option<u128> {
+		if let Some(mtime) = f.mtime
+			&& let Some((t, len, sum)) = self.sums.get(&f.path)
+			&& (t, len) == (mtime, f.len)
+		{
+			return Some(sum);
+		}
+
+		let sum = checksum_sync(&f.path).ok()?;
+		if let Some(mtime) = f.mtime {
+			self.sums.put(f.path.clone(), (mtime, f.len, sum));
+		}
+		Some(sum)
+	}
+
+	fn group_by<K: Eq + Hash>(
+		files: Vec<DupeFile>,
+		key: impl Fn(&DupeFile) -> Option<K>,
+		adv: &mut impl FnMut(u64),
+	) -> Vec<Vec<DupeFile>> {
+		let mut map: HashMap<K, Vec<DupeFile>> = HashMap::new();
+		for f in files {
+			match key(&f) {
+				Some(k) => map.entry(k).or_default().push(f),
+				None => adv(f.len),
+			}
+		}
+
+		let (groups, singles): (Vec<_>, Vec<_>) = map.into_values().partition(|g| g.len() > 1);
+		singles.iter().flatten().for_each(|f| adv(f.len));
+		groups
+	}
+
+	// Hardlinks to the same file aren't duplicates of each other, only one of
+	// them is kept
+	fn unlinked(files: Vec<DupeFile>, adv: &mut impl FnMut(u64)) -> Vec<DupeFile> {
+		let mut seen = HashSet::new();
+		files
+			.into_iter()
+			.filter(|f| {
+				let keep = match Self::identity_sync(&f.path) {
+					Ok(id) => id.is_none_or(|id| seen.insert(id)),
+					Err(_) => false,
+				};
+				if !keep {
+					adv(f.len);
+				}
+				keep
+			})
+			.collect()
+	}
+
+	#[cfg(unix)]
+	fn identity_sync(path: &Path) -> std::io::Result<Option<(u64, u64)>> {
+		use std::os::unix::fs::MetadataExt;
+		std::fs::metadata(path).map(|m| Some((m.dev(), m.ino())))
+	}
+
+	#[cfg(not(unix))]
+	fn identity_sync(path: &Path) -> std::io::Result<Option<(u64, u64)>> {
+		std::fs::metadata(path).map(|_| None)
+	}
+
+	// The device of the file, as long as it's the same size and modification
+	// time as when it was found
+	async fn unchanged(f: &DupeFile) -> io::Result<Option<u64>> {
+		let meta = tokio::fs::symlink_metadata(&f.path).await?;
+		if meta.is_file()
+			&& meta.len() == f.len
+			&& f.mtime.is_some()
+			&& meta.modified().ok() == f.mtime
+		{
+			return Ok(Self::device(&meta));
+		}
+		Err(io::Error::other(format!("`{}` has changed since it was found", f.path.display())))
+	}
+
+	#[cfg(unix)]
+	fn device(meta: &std::fs::Metadata) -> Option<u64> {
+		use std::os::unix::fs::MetadataExt;
+		Some(meta.dev())
+	}
+
+	// Linking across volumes fails anyway, leaving the duplicate as it was
+	#[cfg(not(unix))]
+	fn device(_: &std::fs::Metadata) -> Option<u64> { None }
+
+	fn same_device(a: Option<u64>, b: Option<u64>) -> io::Result<()> {
+		if a == b {
+			Ok(())
+		} else {
+			Err(io::Error::new(io::ErrorKind::CrossesDevices, "The files are on different devices"))
+		}
+	}
+
+	#[cfg(unix)]
+	async fn identity(path: &Path) -> Option<(u64, u64)> {
+		use std::os::unix::fs::MetadataExt;
+		tokio::fs::metadata(path).await.ok().map(|m| (m.dev(), m.ino()))
+	}
+
+	#[cfg(not(unix))]
+	async fn identity(_: &Path) -> Option<(u64, u64)> { None }
+
+	#[inline]
+	fn path() -> PathBuf { Xdg::state_dir().join("checksums.json") }
+}
+
+#[cfg(test)]
+mod tests {
+	use std::time::Duration;
+
+	use super::*;
+	use crate::TempDir;
+
+	#[test]
+	fn test_dupes() -> Result<()> {
+		let dir = TempDir::new("dupes")?;
+
+		let t = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+		let big = |b: u8| vec![b; Dupes::WHOLE as usize * 2];
+		let mut middle = big(1);
+		middle[Dupes::WHOLE as usize] = 2;
+
+		let mut files = vec![];
+		for (i, (name, content)) in [
+			("a", b"same".to_vec()),
+			("b", b"same".to_vec()),
+			("c", b"diff".to_vec()),
+			("d", b"unique size".to_vec()),
+			("e", vec![]),
+			("f", vec![]),
+			("g", big(1)),
+			("h", big(1)),
+			("i", middle),
+		]
+		.into_iter()
+		.enumerate()
+		{
+			let path = dir.join(name);
+			std::fs::write(&path, &content)?;
+			let mtime = Some(t + Duration::from_secs(i as u64));
+			files.push(DupeFile { path, len: content.len() as u64, mtime });
+		}
+
+		#[cfg(unix)]
+		{
+			std::fs::hard_link(dir.join("a"), dir.join("j"))?;
+			files.push(DupeFile { path: dir.join("j"), len: 4, mtime: Some(t) });
+		}
+
+		let dupes = Dupes::new();
+		let mut done = 0;
+		let by_size = Dupes::by_size(files);
+		let total: u64 = by_size.iter().flatten().map(|f| f.len).sum();
+		let groups: Vec<_> = by_size.into_iter().flat_map(|g| dupes.split(g, |n| done += n)).collect();
+		assert_eq!(done, total);
+
+		// Only the one that differs in the middle is told apart by the checksum
+		let summed = |d: &Dupes, n: &str| d.sums.get(&dir.join(n)).is_some();
+		assert_eq!(["a", "g", "h", "i"].map(|n| summed(&dupes, n)), [false, true, true, true]);
+
+		assert_eq!(dupes.publish(groups), 2);
+		let names: Vec<Vec<_>> = dupes
+			.groups()
+			.into_iter()
+			.map(|g| g.iter().map(|p| p.file_name().unwrap().to_owned()).collect())
+			.collect();
+		assert_eq!(names, [vec!["h", "g"], vec!["b", "a"]]);
+
+		let path = dir.join("checksums.json");
+		dupes.sums.save_to::<Entry>(&path)?;
+		let loaded = Dupes::new();
+		loaded.sums.load_from::<Entry>(&path)?;
+		assert_eq!(loaded.sums.get(&dir.join("i")).map(|e| e.1), Some(262144));
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_link() -> Result<()> {
+		let dir = TempDir::new("dupes-link")?;
+
+		let mut files = vec![];
+		for name in ["a", "b", "c"] {
+			let path = dir.join(name);
+			std::fs::write(&path, b"same")?;
+			let mtime = std::fs::metadata(&path)?.modified().ok();
+			files.push(DupeFile { path, len: 4, mtime });
+		}
+
+		let dupes = Dupes::new();
+		dupes.publish(vec![files]);
+
+		// Changed since it was found, so it's left alone
+		std::fs::write(dir.join("c"), b"changed")?;
+		let e = dupes.link(&dir.join("a"), &dir.join("c")).await.unwrap_err();
+		assert!(e.to_string().contains("has changed since it was found"));
+		assert_eq!(std::fs::read(dir.join("c"))?, b"changed");
+		assert!(dupes.link(&dir.join("a"), &dir.join("d")).await.is_err());
+
+		dupes.link(&dir.join("a"), &dir.join("b")).await?;
+		#[cfg(unix)]
+		{
+			use std::os::unix::fs::MetadataExt;
+			let ino = |n| std::fs::metadata(dir.join(n)).map(|m| m.ino());
+			assert_eq!(ino("a")?, ino("b")?);
+		}
+		assert!(!dir.join(".b.link.tmp").exists());
+
+		// Files on different devices can't be linked
+		let e = Dupes::same_device(Some(1), Some(2)).unwrap_err();
+		assert_eq!(e.kind(), io::ErrorKind::CrossesDevices);
+		assert!(Dupes::same_device(Some(1), Some(1)).is_ok());
+
+		Ok(())
+	}
+}
@@ -1,10 +1,9 @@
-use std::{collections::{HashMap, HashSet}, mem, ops::{Deref, DerefMut, Not}};
+use std::{collections::{HashMap, HashSet}, mem, ops::{Deref, DerefMut, Not}, path::Path};
 
-use tokio::{select, sync::mpsc::{self, UnboundedReceiver}};
 use yazi_shared::{Id, url::{Url, Urn, UrnBuf}};
 
 use super::{FilesSorter, Filter};
-use crate::{FILES_TICKET, File, FilesOp, SortBy, cha::Cha, mounts::PARTITIONS, provider::{self, DirEntry}};
+use crate::{FILES_TICKET, File, FilesOp, SortBy, cha::Cha, mounts::PARTITIONS};
 
 #[derive(Default)]
 pub struct Files {
@@ -19,6 +18,7 @@ pub struct Files {
 	sorter:      FilesSorter,
 	filter:      Option<Filter>,
 	show_hidden: bool,
+	tree:        bool,
 }
 
 impl Deref for Files {
@@ -34,57 +34,6 @@ impl DerefMut for Files {
 impl Files {
 	pub fn new(show_hidden: bool) -> Self { Self { show_hidden, ..Default::default() } }
 
-	pub async fn from_dir(dir: &Url) -> std::io::Result<UnboundedReceiver<File>> {
-		let mut it = provider::read_dir(dir).await?;
-		let (tx, rx) = mpsc::unbounded_channel();
-
-		tokio::spawn(async move {
-			while let Ok(Some(item)) = it.next_entry().await {
-				select! {
-					_ = tx.closed() => break,
-					result = item.metadata() => {
-						let url = item.url();
-						_ = tx.send(match result {
-							Ok(meta) => File::from_follow(url, meta).await,
-							Err(_) => File::from_dummy(url, item.file_type().await.ok())
-						});
-					}
-				}
-			}
-		});
-		Ok(rx)
-	}
-
-	pub async fn from_dir_bulk(dir: &Url) -> std::io::Result<Vec<File>> {
-		let mut it = provider::read_dir(dir).await?;
-		let mut entries = Vec::with_capacity(5000);
-		while let Ok(Some(entry)) = it.next_entry().await {
-			entries.push(entry);
-		}
-
-		let (first, rest) = entries.split_at(entries.len() / 3);
-		let (second, third) = rest.split_at(entries.len() / 3);
-		async fn go(entries: &[DirEntry]) -> Vec<File> {
-			let mut files = Vec::with_capacity(entries.len());
-			for entry in entries {
-				let url = entry.url();
-				files.push(match entry.metadata().await {
-					Ok(meta) => File::from_follow(url, meta).await,
-					Err(_) => File::from_dummy(url, entry.file_type().await.ok()),
-				});
-			}
-			files
-		}
-
-		Ok(
-			futures::future::join_all([go(first), go(second), go(third)])
-				.await
-				.into_iter()
-				.flatten()
-				.collect(),
-		)
-	}
-
 	pub async fn assert_stale(dir: &Url, cha: Cha) -> Option<Cha> {
 		use std::io::ErrorKind;
 		match Cha::from_url(dir).await {
@@ -121,7 +70,11 @@ impl Files {
 			}
 
 			self.hidden.extend(hidden);
-			self.items.extend(items);
+			if self.sorter.by_name() && !self.tree {
+				self.sorter.merge(&mut self.items, items);
+			} else {
+				self.items.extend(items);
+			}
 			return;
 		}
 
@@ -189,14 +142,14 @@ impl Files {
 			return vec![];
 		}
 
-		let (mut hidden, mut items) = if let Some(filter) = &self.filter {
-			urns
-				.into_iter()
-				.partition(|u| (!self.show_hidden && u.as_urn().is_hidden()) || !filter.matches(u.as_urn()))
-		} else if self.show_hidden {
-			(HashSet::new(), urns)
-		} else {
-			urns.into_iter().partition(|u| u.as_urn().is_hidden())
+		let (mut hidden, mut items) = match &self.filter {
+			// Which of the two they're in can't be told by the names
+			Some(filter) if !filter.by_name() => (HashSet::new(), urns),
+			Some(filter) => urns.into_iter().partition(|u| {
+				(!self.show_hidden && u.as_urn().is_hidden()) || !filter.matches(u.as_urn())
+			}),
+			None if self.show_hidden => (HashSet::new(), urns),
+			None => urns.into_iter().partition(|u| u.as_urn().is_hidden()),
 		};
 
 		let mut deleted = Vec::with_capacity(items.len());
@@ -211,6 +164,8 @@ impl Files {
 				!b
 			});
 		}
+
+		hidden.extend(items);
 		if !hidden.is_empty() {
 			self.hidden.retain(|f| !hidden.remove(f.urn()));
 		}
@@ -268,15 +223,24 @@ impl Files {
 		}
 
 		let (mut hidden, mut items) = if let Some(filter) = &self.filter {
-			files
-				.into_iter()
-				.partition(|(_, f)| (f.is_hidden() && !self.show_hidden) || !filter.matches(f.urn()))
+			files.into_iter().partition(|(_, f)| {
+				(f.is_hidden() && !self.show_hidden) || !filter.matches_with(f.urn(), &f.cha)
+			})
 		} else if self.show_hidden {
 			(HashMap::new(), files)
 		} else {
 			files.into_iter().partition(|(_, f)| f.is_hidden())
 		};
 
+		// Which of the two they're in may change along with the metadata
+		if self.filter.as_ref().is_some_and(|f| !f.by_name()) {
+			let shown: Vec<_> = self.hidden.extract_if(.., |f| items.contains_key(f.urn())).collect();
+			let gone: Vec<_> = self.items.extract_if(.., |f| hidden.contains_key(f.urn())).collect();
+			self.revision += (!shown.is_empty() || !gone.is_empty()) as u64;
+			self.items.extend(shown);
+			self.hidden.extend(gone);
+		}
+
 		if !items.is_empty() {
 			go!(self.items, items, 1);
 		}
@@ -315,15 +279,45 @@ impl Files {
 		}
 
 		self.version = self.revision;
-		self.sorter.sort(&mut self.items, &self.sizes);
+		self.sort();
 		true
 	}
 
+	fn sort(&mut self) {
+		self.sorter.sort(&mut self.items, &self.sizes);
+		if self.tree {
+			Self::nest(&mut self.items);
+		}
+	}
+
+	/// Moves the descendants of each directory right after it.
+	fn nest(items: &mut Vec<File>) {
+		let order = {
+			let mut children: HashMap<&Path, Vec<usize>> = HashMap::new();
+			for (i, f) in items.iter().enumerate() {
+				children.entry(f.urn().parent().unwrap_or(Path::new(""))).or_default().push(i);
+			}
+
+			let mut order = Vec::with_capacity(items.len());
+			let mut stack: Vec<_> = children.get(Path::new("")).into_iter().flatten().rev().collect();
+			while let Some(&i) = stack.pop() {
+				order.push(i);
+				stack.extend(children.get(&**items[i].urn()).into_iter().flatten().rev());
+			}
+			order
+		};
+
+		let mut taken: Vec<_> = mem::take(items).into_iter().map(Some).collect();
+		items.extend(order.into_iter().filter_map(|i| taken[i].take()));
+		// Those whose parent isn't listed, such as filtered out, go last
+		items.extend(taken.into_iter().flatten());
+	}
+
 	fn split_files(&self, files: impl IntoIterator<Item = File>) -> (Vec<File>, Vec<File>) {
 		if let Some(filter) = &self.filter {
-			files
-				.into_iter()
-				.partition(|f| (f.is_hidden() && !self.show_hidden) || !filter.matches(f.urn()))
+			files.into_iter().partition(|f| {
+				(f.is_hidden() && !self.show_hidden) || !filter.matches_with(f.urn(), &f.cha)
+			})
 		} else if self.show_hidden {
 			(vec![], files.into_iter().collect())
 		} else {
@@ -352,6 +346,26 @@ impl Files {
 		}
 	}
 
+	// --- Tree
+	#[inline]
+	pub fn tree(&self) -> bool { self.tree }
+
+	pub fn set_tree(&mut self, state: bool) {
+		if self.tree != state {
+			self.tree = state;
+			self.revision += 1;
+		}
+	}
+
+	/// The files under a directory in a tree, including the hidden ones.
+	pub fn descendants<'a>(&'a self, urn: &'a Urn) -> impl Iterator<Item = &'a File> {
+		self.all().filter(move |f| f.urn() != urn && f.urn().starts_with(urn))
+	}
+
+	/// All the files, including those hidden or filtered out.
+	#[inline]
+	pub fn all(&self) -> impl Iterator<Item = &File> { self.items.iter().chain(&self.hidden) }
+
 	// --- Filter
 	#[inline]
 	pub fn filter(&self) -> Option<&Filter> { self.filter.as_ref() }
@@ -369,18 +383,21 @@ impl Files {
 			self.hidden = hidden;
 			if !items.is_empty() {
 				self.items.extend(items);
-				self.sorter.sort(&mut self.items, &self.sizes);
+				self.sort();
 			}
 			return true;
 		}
 
 		let it = mem::take(&mut self.items).into_iter().chain(mem::take(&mut self.hidden));
 		(self.hidden, self.items) = self.split_files(it);
-		self.sorter.sort(&mut self.items, &self.sizes);
+		self.sort();
 		true
 	}
 
 	// --- Show hidden
+	#[inline]
+	pub fn show_hidden(&self) -> bool { self.show_hidden }
+
 	pub fn set_show_hidden(&mut self, state: bool) {
 		if self.show_hidden == state {
 			return;
@@ -404,3 +421,21 @@ impl Files {
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_nest() {
+		let root: Url = "search://tree//tmp".parse().unwrap();
+		let mut items: Vec<_> = ["a", "b", "a/c", "a/c/d", "a/b", "x/y", "c"]
+			.into_iter()
+			.map(|s| File::from_dummy(root.join(s), None))
+			.collect();
+
+		Files::nest(&mut items);
+		let urns: Vec<_> = items.iter().map(|f| f.urn().to_str().unwrap()).collect();
+		assert_eq!(urns, ["a", "a/c", "a/c/d", "a/b", "b", "c", "x/y"]);
+	}
+}
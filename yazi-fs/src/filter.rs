@@ -1,47 +1,101 @@
-use std::{ffi::OsStr, fmt::Display, ops::Range};
+use std::{ffi::OsStr, fmt::{Display, Write}, ops::Range};
 
 use anyhow::Result;
 use regex::bytes::{Regex, RegexBuilder};
-use yazi_shared::event::Cmd;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use yazi_shared::{event::Cmd, url::Urn};
 
+use crate::{Query, cha::Cha};
+
+/// A regex of names, or a query of the filter language such as `size>10M and ext:mp4`.
+#[derive(Clone, Debug)]
 pub struct Filter {
 	raw:   String,
-	regex: Regex,
+	case:  FilterCase,
+	query: Query,
+	label: Option<String>,
 }
 
 impl Filter {
 	pub fn new(s: &str, case: FilterCase) -> Result<Self> {
-		let regex = match case {
-			FilterCase::Smart => {
-				let uppercase = s.chars().any(|c| c.is_uppercase());
-				RegexBuilder::new(s).case_insensitive(!uppercase).build()?
-			}
-			FilterCase::Sensitive => Regex::new(s)?,
-			FilterCase::Insensitive => RegexBuilder::new(s).case_insensitive(true).build()?,
+		let query = match Query::parse(s, case)? {
+			Some(query) => query,
+			None => Query::Name(case.regex(s)?),
 		};
-		Ok(Self { raw: s.to_owned(), regex })
+		Ok(Self { raw: s.to_owned(), case, query, label: None })
+	}
+
+	/// A filter matching exactly the given names, shown as `label` instead of the generated pattern.
+	pub fn exact<'a>(label: &str, names: impl IntoIterator<Item = &'a Urn>) -> Result<Self> {
+		let mut s = String::from("(?-u)^(?:");
+		for (i, name) in names.into_iter().enumerate() {
+			if i > 0 {
+				s.push('|');
+			}
+			for b in name.encoded_bytes() {
+				write!(s, "\\x{b:02x}")?;
+			}
+		}
+		s.push_str(")$");
+		Ok(Self {
+			query: Query::Name(Regex::new(&s)?),
+			raw:   s,
+			case:  FilterCase::Sensitive,
+			label: Some(label.to_owned()),
+		})
 	}
 
+	/// Whether the name matches, for the filters [`Self::by_name`].
 	#[inline]
 	pub fn matches(&self, name: impl AsRef<OsStr>) -> bool {
-		self.regex.is_match(name.as_ref().as_encoded_bytes())
+		self.query.matches(name.as_ref(), None)
+	}
+
+	/// Whether the file matches, by its name and metadata.
+	#[inline]
+	pub fn matches_with(&self, name: impl AsRef<OsStr>, cha: &Cha) -> bool {
+		self.query.matches(name.as_ref(), Some(cha))
 	}
 
 	#[inline]
 	pub fn highlighted(&self, name: impl AsRef<OsStr>) -> Option<Vec<Range<usize>>> {
-		self.regex.find(name.as_ref().as_encoded_bytes()).map(|m| vec![m.range()])
+		let m = self.query.highlighter()?.find(name.as_ref().as_encoded_bytes())?;
+		Some(vec![m.range()])
 	}
+
+	/// Whether it only looks at names, so it can be matched without metadata.
+	#[inline]
+	pub fn by_name(&self) -> bool { self.query.by_name() }
+
+	/// Whether it's a regex of names as a whole, instead of a query.
+	pub fn is_plain(&self) -> bool { Query::parse(&self.raw, self.case).is_ok_and(|q| q.is_none()) }
 }
 
 impl PartialEq for Filter {
-	fn eq(&self, other: &Self) -> bool { self.raw == other.raw }
+	fn eq(&self, other: &Self) -> bool { self.raw == other.raw && self.label == other.label }
 }
 
 impl Display for Filter {
-	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { f.write_str(&self.raw) }
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.write_str(self.label.as_ref().unwrap_or(&self.raw))
+	}
 }
 
-#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+impl Serialize for Filter {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		(&self.raw, self.case).serialize(serializer)
+	}
+}
+
+impl<'de> Deserialize<'de> for Filter {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		let (raw, case) = <(String, FilterCase)>::deserialize(deserializer)?;
+		Self::new(&raw, case).map_err(serde::de::Error::custom)
+	}
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "kebab-case")]
 pub enum FilterCase {
 	Smart,
 	#[default]
@@ -49,6 +103,19 @@ pub enum FilterCase {
 	Insensitive,
 }
 
+impl FilterCase {
+	pub(crate) fn regex(self, s: &str) -> Result<Regex> {
+		Ok(match self {
+			Self::Smart => {
+				let uppercase = s.chars().any(|c| c.is_uppercase());
+				RegexBuilder::new(s).case_insensitive(!uppercase).build()?
+			}
+			Self::Sensitive => Regex::new(s)?,
+			Self::Insensitive => RegexBuilder::new(s).case_insensitive(true).build()?,
+		})
+	}
+}
+
 impl From<&Cmd> for FilterCase {
 	fn from(c: &Cmd) -> Self {
 		match (c.bool("smart"), c.bool("insensitive")) {
@@ -58,3 +125,26 @@ impl From<&Cmd> for FilterCase {
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use yazi_shared::url::UrnBuf;
+
+	use super::*;
+
+	#[test]
+	fn test_exact() -> Result<()> {
+		let names = [UrnBuf::from("a.txt"), UrnBuf::from("(b)|ü")];
+		let filter = Filter::exact("differences", names.iter().map(|u| u.as_urn()))?;
+
+		assert!(filter.matches("a.txt"));
+		assert!(filter.matches("(b)|ü"));
+		assert!(!filter.matches("a.txt.bak"));
+		assert!(!filter.matches("abtxt"));
+		assert!(!filter.matches("b"));
+		assert_eq!(filter.to_string(), "differences");
+
+		assert!(!Filter::exact("differences", [])?.matches("a.txt"));
+		Ok(())
+	}
+}
@@ -1,12 +1,12 @@
 // FIXME: VFS
 
-use std::{borrow::Cow, collections::{HashMap, HashSet}, ffi::{OsStr, OsString}, path::{Path, PathBuf}};
+use std::{borrow::Cow, collections::{HashMap, HashSet}, ffi::{OsStr, OsString}, path::{Path, PathBuf}, sync::{Arc, atomic::{AtomicU64, Ordering}}};
 
 use anyhow::{Result, bail};
 use tokio::{fs, io, select, sync::{mpsc, oneshot}, time};
 use yazi_shared::url::{Component, Url};
 
-use crate::{cha::Cha, provider};
+use crate::{BANDWIDTH, CHUNKED, Chunked, CopyMethod, CopyProg, Pause, cha::Cha, provider::{self, vfs::Vfs}};
 
 #[inline]
 pub async fn maybe_exists(u: impl AsRef<Url>) -> bool {
@@ -30,6 +30,25 @@ pub fn ok_or_not_found<T: Default>(result: io::Result<T>) -> io::Result<T> {
 	}
 }
 
+/// Hardlinks `link` to `original` in place of what's there.
+pub async fn hard_link_over(original: impl AsRef<Url>, link: impl AsRef<Url>) -> io::Result<()> {
+	let link = link.as_ref();
+	let mut name = OsString::from(".");
+	name.push(link.name());
+	name.push(".link.tmp");
+
+	let parent = link.parent_url().ok_or_else(|| io::Error::from(io::ErrorKind::InvalidInput))?;
+	let tmp = parent.join(name);
+	ok_or_not_found(provider::remove_file(&tmp).await)?;
+	provider::hard_link(original, &tmp).await?;
+
+	let result = provider::rename(&tmp, link).await;
+	if result.is_err() {
+		provider::remove_file(&tmp).await.ok();
+	}
+	result
+}
+
 #[inline]
 pub async fn paths_to_same_file(a: impl AsRef<Path>, b: impl AsRef<Path>) -> bool {
 	_paths_to_same_file(a.as_ref(), b.as_ref()).await.unwrap_or(false)
@@ -83,6 +102,26 @@ async fn _paths_to_same_file(a: &Path, b: &Path) -> std::io::Result<bool> {
 	Ok(final_name(a).await? == final_name(b).await?)
 }
 
+/// Whether `a` is on the same device as the directory `b`.
+#[cfg(unix)]
+pub async fn paths_on_same_device(a: impl AsRef<Url>, b: impl AsRef<Url>) -> bool {
+	use std::os::unix::fs::MetadataExt;
+
+	match (provider::symlink_metadata(a).await, provider::metadata(b).await) {
+		(Ok(a), Ok(b)) => a.dev() == b.dev(),
+		_ => false,
+	}
+}
+
+#[cfg(windows)]
+pub async fn paths_on_same_device(a: impl AsRef<Url>, b: impl AsRef<Url>) -> bool {
+	let prefix = |u: &Url| match u.loc.components().next() {
+		Some(std::path::Component::Prefix(p)) => Some(p.as_os_str().to_ascii_lowercase()),
+		_ => None,
+	};
+	prefix(a.as_ref()).is_some_and(|p| Some(p) == prefix(b.as_ref()))
+}
+
 pub async fn realname(u: &Url) -> Option<OsString> {
 	let name = u.file_name()?;
 	if *u == provider::canonicalize(u).await.ok()? {
@@ -162,14 +201,19 @@ pub fn copy_with_progress(
 	from: &Url,
 	to: &Url,
 	cha: Cha,
-) -> mpsc::Receiver<Result<u64, io::Error>> {
+	pause: Pause,
+) -> mpsc::Receiver<Result<CopyProg, io::Error>> {
 	let (tx, rx) = mpsc::channel(1);
 	let (tick_tx, mut tick_rx) = oneshot::channel();
 
+	// The bytes copied by a chunked copy, which can't be told by the size of the
+	// destination, or `u64::MAX` for the other methods
+	let counted = Arc::new(AtomicU64::new(u64::MAX));
+
 	tokio::spawn({
-		let (from, to) = (from.clone(), to.clone());
+		let (from, to, counted) = (from.clone(), to.clone(), counted.clone());
 		async move {
-			tick_tx.send(_copy_with_progress(from, to, cha).await).ok();
+			tick_tx.send(_copy_with_progress(from, to, cha, counted, pause).await).ok();
 		}
 	});
 
@@ -186,11 +230,11 @@ pub fn copy_with_progress(
 				}
 
 				match exit {
-					Some(Ok(len)) => {
+					Some(Ok((len, method))) => {
 						if len > last {
-							tx.send(Ok(len - last)).await.ok();
+							tx.send(Ok(CopyProg::Adv(len - last))).await.ok();
 						}
-						tx.send(Ok(0)).await.ok();
+						tx.send(Ok(CopyProg::Done(method))).await.ok();
 						break;
 					}
 					Some(Err(e)) => {
@@ -200,9 +244,12 @@ pub fn copy_with_progress(
 					None => {}
 				}
 
-				let len = provider::symlink_metadata(&to).await.map(|m| m.len()).unwrap_or(0);
+				let len = match counted.load(Ordering::Relaxed) {
+					u64::MAX => provider::symlink_metadata(&to).await.map(|m| m.len()).unwrap_or(0),
+					n => n,
+				};
 				if len > last {
-					tx.send(Ok(len - last)).await.ok();
+					tx.send(Ok(CopyProg::Adv(len - last))).await.ok();
 					last = len;
 				}
 			}
@@ -212,7 +259,37 @@ pub fn copy_with_progress(
 	rx
 }
 
-async fn _copy_with_progress(from: Url, to: Url, cha: Cha) -> io::Result<u64> {
+// Files of plugin filesystems are copied through their mirror
+async fn _copy_with_progress(
+	from: Url,
+	to: Url,
+	cha: Cha,
+	counted: Arc<AtomicU64>,
+	pause: Pause,
+) -> io::Result<(u64, CopyMethod)> {
+	Vfs::fetch(&from).await?;
+	let copied = copy_local(
+		provider::local_path(&from).await?.into_owned(),
+		provider::local_path(&to).await?.into_owned(),
+		cha,
+		counted,
+		pause,
+	)
+	.await?;
+
+	Vfs::push(&to).await?;
+	Ok(copied)
+}
+
+// Tries the methods from the fastest one, see `CopyMethod`, where only the
+// chunked and buffered ones can be paused halfway
+async fn copy_local(
+	from: PathBuf,
+	to: PathBuf,
+	cha: Cha,
+	counted: Arc<AtomicU64>,
+	pause: Pause,
+) -> io::Result<(u64, CopyMethod)> {
 	let mut ft = std::fs::FileTimes::new();
 	cha.atime.map(|t| ft = ft.set_accessed(t));
 	cha.mtime.map(|t| ft = ft.set_modified(t));
@@ -232,6 +309,7 @@ async fn _copy_with_progress(from: Url, to: Url, cha: Cha) -> io::Result<u64> {
 		use std::os::{fd::AsRawFd, unix::fs::OpenOptionsExt};
 
 		tokio::task::spawn_blocking(move || {
+			pause.wait();
 			let mut reader = std::fs::File::open(from)?;
 			let mut writer = std::fs::OpenOptions::new()
 				.mode(cha.mode as u32)  // Do not remove `as u32`, https://github.com/termux/termux-packages/pull/22481
@@ -240,11 +318,25 @@ async fn _copy_with_progress(from: Url, to: Url, cha: Cha) -> io::Result<u64> {
 				.truncate(true)
 				.open(to)?;
 
-			let written = std::io::copy(&mut reader, &mut writer)?;
+			// Cloning doesn't go through the bandwidth limit, as nothing is transferred
+			let len = reader.metadata()?.len();
+			let copied = if crate::reflink(&reader, &writer).is_ok() {
+				(len, CopyMethod::Reflink)
+			} else if let Some(opts) = CHUNKED.get(len) {
+				counted.store(0, Ordering::Relaxed);
+				let n = Chunked::copy(&reader, &writer, len, opts, &counted, &pause)?;
+				(n, CopyMethod::Chunked(opts.1))
+			} else if BANDWIDTH.limit() != 0 {
+				(BANDWIDTH.copy(&mut reader, &mut writer, &pause)?, CopyMethod::Buffered)
+			} else if let Some(n) = crate::copy_range(&reader, &writer) {
+				(n?, CopyMethod::Kernel)
+			} else {
+				(std::io::copy(&mut reader, &mut writer)?, CopyMethod::Buffered)
+			};
 			unsafe { libc::fchmod(writer.as_raw_fd(), cha.mode) };
 			writer.set_times(ft).ok();
 
-			Ok(written)
+			Ok(copied)
 		})
 		.await?
 	}
@@ -252,14 +344,80 @@ async fn _copy_with_progress(from: Url, to: Url, cha: Cha) -> io::Result<u64> {
 	#[cfg(not(any(target_os = "linux", target_os = "android")))]
 	{
 		tokio::task::spawn_blocking(move || {
-			let written = std::fs::copy(from, &to)?;
+			pause.wait();
+			let copied = if crate::clonefile(&from, &to).is_ok() {
+				(cha.len, CopyMethod::Reflink)
+			} else if let Some(opts) = CHUNKED.get(cha.len) {
+				let (reader, writer) = (std::fs::File::open(from)?, std::fs::File::create(&to)?);
+				let meta = reader.metadata()?;
+				writer.set_permissions(meta.permissions())?;
+
+				counted.store(0, Ordering::Relaxed);
+				let n = Chunked::copy(&reader, &writer, meta.len(), opts, &counted, &pause)?;
+				(n, CopyMethod::Chunked(opts.1))
+			} else if BANDWIDTH.limit() == 0 {
+				(std::fs::copy(from, &to)?, CopyMethod::Kernel)
+			} else {
+				let mut reader = std::fs::File::open(from)?;
+				let mut writer = std::fs::File::create(&to)?;
+				writer.set_permissions(reader.metadata()?.permissions())?;
+				(BANDWIDTH.copy(&mut reader, &mut writer, &pause)?, CopyMethod::Buffered)
+			};
 			std::fs::File::options().write(true).open(to).and_then(|f| f.set_times(ft)).ok();
-			Ok(written)
+			Ok(copied)
 		})
 		.await?
 	}
 }
 
+/// Hashes the content of a file with XXH3-128.
+pub async fn checksum(url: &Url) -> io::Result<u128> {
+	Vfs::fetch(url).await?;
+	let path = provider::local_path(url).await?.into_owned();
+	tokio::task::spawn_blocking(move || checksum_sync(&path)).await?
+}
+
+/// Same as [`checksum`], but for a local path, blocking the current thread.
+pub(crate) fn checksum_sync(path: &Path) -> io::Result<u128> {
+	use std::io::Read;
+
+	let mut file = std::fs::File::open(path)?;
+	let mut hasher = twox_hash::XxHash3_128::new();
+	let mut buf = vec![0; 256 * 1024];
+	loop {
+		match file.read(&mut buf) {
+			Ok(0) => break,
+			Ok(n) => hasher.write(&buf[..n]),
+			Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+			Err(e) => Err(e)?,
+		}
+	}
+	Ok(hasher.finish_128())
+}
+
+/// A cheap identity of the content of a file, from its size and both ends.
+pub fn fingerprint(url: &Url) -> io::Result<u128> {
+	use std::io::{Read, Seek, SeekFrom};
+
+	const SAMPLE: u64 = 64 * 1024;
+
+	let mut file = std::fs::File::open(provider::local_path_sync(url)?)?;
+	let len = file.metadata()?.len();
+
+	let mut hasher = twox_hash::XxHash3_128::new();
+	hasher.write(&len.to_le_bytes());
+
+	let mut buf = Vec::with_capacity(2 * SAMPLE as usize);
+	file.by_ref().take(SAMPLE).read_to_end(&mut buf)?;
+	if len > SAMPLE {
+		file.seek(SeekFrom::Start(len.saturating_sub(SAMPLE).max(SAMPLE)))?;
+		file.take(SAMPLE).read_to_end(&mut buf)?;
+	}
+
+	hasher.write(&buf);
+	Ok(hasher.finish_128())
+}
+
 pub async fn remove_dir_clean(dir: &Url) {
 	let Ok(mut it) = provider::read_dir(dir).await else { return };
 
@@ -274,6 +432,25 @@ pub async fn remove_dir_clean(dir: &Url) {
 	provider::remove_dir(dir).await.ok();
 }
 
+/// The file, and everything under it if it's a directory.
+pub async fn walk(url: &Url) -> io::Result<Vec<(Url, std::fs::Metadata)>> {
+	let mut files = vec![(url.clone(), provider::symlink_metadata(url).await?)];
+	let mut i = 0;
+	while i < files.len() {
+		if files[i].1.is_dir()
+			&& let Ok(mut it) = provider::read_dir(&files[i].0).await
+		{
+			while let Ok(Some(entry)) = it.next_entry().await {
+				if let Ok(meta) = entry.metadata().await {
+					files.push((entry.url(), meta));
+				}
+			}
+		}
+		i += 1;
+	}
+	Ok(files)
+}
+
 // Convert a file mode to a string representation
 #[cfg(unix)]
 #[allow(clippy::collapsible_else_if)]
@@ -390,3 +567,39 @@ fn test_max_common_root() {
 	assert(&["/aa/bb/cc", "/aa/dd/ee"], "/aa");
 	assert(&["/aa/bb/cc", "/aa/bb/cc/dd/ee", "/aa/bb/cc/ff"], "/aa/bb");
 }
+
+#[tokio::test]
+async fn test_checksum() -> Result<()> {
+	let dir = crate::TempDir::new("checksum")?;
+
+	let (a, b, c) = (Url::from(dir.join("a")), Url::from(dir.join("b")), Url::from(dir.join("c")));
+	let data: Vec<u8> = (0..1_000_000u32).map(|i| (i % 251) as u8).collect();
+	fs::write(&a, &data).await?;
+	fs::write(&b, &data).await?;
+	fs::write(&c, &data[1..]).await?;
+
+	assert_eq!(checksum(&a).await?, checksum(&b).await?);
+	assert_ne!(checksum(&a).await?, checksum(&c).await?);
+	assert!(checksum(&Url::from(dir.join("d"))).await.is_err());
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_fingerprint() -> Result<()> {
+	let dir = crate::TempDir::new("fingerprint")?;
+
+	let (a, b, c) = (Url::from(dir.join("a")), Url::from(dir.join("b")), Url::from(dir.join("c")));
+	let mut data: Vec<u8> = (0..1_000_000u32).map(|i| (i % 251) as u8).collect();
+	fs::write(&a, &data).await?;
+	fs::write(&b, &data).await?;
+
+	// Only the tail differs
+	*data.last_mut().unwrap() ^= 1;
+	fs::write(&c, &data).await?;
+
+	assert_eq!(fingerprint(&a)?, fingerprint(&b)?);
+	assert_ne!(fingerprint(&a)?, fingerprint(&c)?);
+
+	Ok(())
+}
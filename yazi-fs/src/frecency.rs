@@ -0,0 +1,184 @@
+use std::{collections::BTreeMap, path::{Path, PathBuf}, sync::atomic::{AtomicBool, Ordering}, time::{SystemTime, UNIX_EPOCH}};
+
+use anyhow::Result;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use yazi_shared::url::{Scheme, Url};
+
+use crate::{File, Xdg, write_atomic};
+
+/// The directories visited and the files opened, persisted in the state directory.
+pub static FRECENCY: Frecency = Frecency::new();
+
+/// The records behind the `recent://` and `frecency://` listings.
+pub struct Frecency {
+	records: RwLock<Records>,
+	dirty:   AtomicBool,
+}
+
+#[derive(Debug, Default, Deserialize, PartialEq, Serialize)]
+struct Records {
+	dirs:  BTreeMap<PathBuf, Visits>,
+	files: BTreeMap<PathBuf, Visits>,
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+struct Visits {
+	count: u32,
+	last:  u64,
+}
+
+impl Visits {
+	/// The count weighted by how long ago the last visit was, as zoxide does.
+	fn score(self, now: u64) -> f64 {
+		let weight = match now.saturating_sub(self.last) {
+			0..3600 => 4.0,
+			3600..86400 => 2.0,
+			86400..604800 => 0.5,
+			_ => 0.25,
+		};
+		self.count as f64 * weight
+	}
+}
+
+impl Frecency {
+	/// How many directories and files are kept at most.
+	const LIMIT: usize = 1000;
+
+	const fn new() -> Self {
+		Self {
+			records: RwLock::new(Records { dirs: BTreeMap::new(), files: BTreeMap::new() }),
+			dirty:   AtomicBool::new(false),
+		}
+	}
+
+	/// Records a visit to a directory.
+	pub fn visit(&self, url: &Url) { self.record(url, false, now()) }
+
+	/// Records the opening of a file.
+	pub fn open(&self, url: &Url) { self.record(url, true, now()) }
+
+	/// Reads the listing of a `recent://` or `frecency://` URL.
+	pub async fn read(&self, url: &Url) -> Vec<File> {
+		let paths = self.ranked(&url.scheme, now());
+		let mut files = Vec::with_capacity(paths.len());
+		for path in paths {
+			let Ok(rel) = path.strip_prefix(url.loc.base()) else { continue };
+			if let Ok(file) = File::new(url.join(rel)).await {
+				files.push(file);
+			}
+		}
+		files
+	}
+
+	/// How frecent a directory or file is, `0.0` if it's never been visited or opened.
+	pub fn score(&self, url: &Url) -> f64 {
+		url.as_path().map_or(0.0, |p| self.score_at(p, now()))
+	}
+
+	/// Loads the records saved by [`Self::save`].
+	pub fn load(&self) -> Result<()> { self.load_from(&Self::path()) }
+
+	/// Saves the records to the state directory if they have changed, blocking the current thread.
+	pub fn save(&self) -> Result<()> {
+		if self.dirty.swap(false, Ordering::Relaxed) { self.save_to(&Self::path()) } else { Ok(()) }
+	}
+
+	fn record(&self, url: &Url, file: bool, now: u64) {
+		let Some(path) = url.as_path().filter(|p| p.is_absolute()) else { return };
+
+		let mut records = self.records.write();
+		let map = if file { &mut records.files } else { &mut records.dirs };
+
+		let visits = map.entry(path.to_owned()).or_default();
+		(visits.count, visits.last) = (visits.count.saturating_add(1), now);
+
+		// Pruned to a bit under the limit, so it's not done on every visit
+		if map.len() > Self::LIMIT {
+			let mut scores: Vec<_> = map.values().map(|v| v.score(now)).collect();
+			scores.sort_unstable_by(|a, b| b.total_cmp(a));
+			let min = scores[Self::LIMIT * 9 / 10];
+			map.retain(|_, v| v.score(now) > min);
+		}
+		self.dirty.store(true, Ordering::Relaxed);
+	}
+
+	/// The files by recency for `recent://`, and the directories by frecency otherwise.
+	fn ranked(&self, scheme: &Scheme, now: u64) -> Vec<PathBuf> {
+		let records = self.records.read();
+		let mut v: Vec<_> = match scheme {
+			Scheme::Recent => records.files.iter().map(|(p, v)| (v.last as f64, p)).collect(),
+			Scheme::Frecency => records.dirs.iter().map(|(p, v)| (v.score(now), p)).collect(),
+			_ => vec![],
+		};
+		v.sort_by(|a, b| b.0.total_cmp(&a.0));
+		v.into_iter().map(|(_, p)| p.clone()).collect()
+	}
+
+	fn score_at(&self, path: &Path, now: u64) -> f64 {
+		let records = self.records.read();
+		let visits = [&records.dirs, &records.files].into_iter().filter_map(|m| m.get(path));
+		visits.map(|v| v.score(now)).fold(0.0, f64::max)
+	}
+
+	fn load_from(&self, path: &Path) -> Result<()> {
+		*self.records.write() = serde_json::from_slice(&std::fs::read(path)?)?;
+		Ok(())
+	}
+
+	fn save_to(&self, path: &Path) -> Result<()> {
+		Ok(write_atomic(path, &serde_json::to_vec(&*self.records.read())?)?)
+	}
+
+	#[inline]
+	fn path() -> PathBuf { Xdg::state_dir().join("frecency.json") }
+}
+
+#[inline]
+fn now() -> u64 { SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs()) }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::TempDir;
+
+	#[test]
+	fn test_frecency() -> Result<()> {
+		let frecency = Frecency::new();
+		let url = |s: &str| Url::from(Path::new(s));
+		let paths = |v: &[&str]| v.iter().map(PathBuf::from).collect::<Vec<_>>();
+
+		// Visited often but long ago, visited once just now, and visited twice today
+		let now = 30 * 86400;
+		for _ in 0..5 {
+			frecency.record(&url("/often"), false, 0);
+		}
+		frecency.record(&url("/now"), false, now);
+		frecency.record(&url("/today"), false, now - 7200);
+		frecency.record(&url("/today"), false, now - 7200);
+		assert_eq!(frecency.ranked(&Scheme::Frecency, now), paths(&["/now", "/today", "/often"]));
+		assert_eq!(frecency.score_at(Path::new("/today"), now), 4.0);
+		assert_eq!(frecency.score_at(Path::new("/never"), now), 0.0);
+
+		// Files are ranked by recency alone
+		frecency.record(&url("/a"), true, 2);
+		frecency.record(&url("/b"), true, 1);
+		frecency.record(&url("/b"), true, 1);
+		assert_eq!(frecency.ranked(&Scheme::Recent, now), paths(&["/a", "/b"]));
+
+		// Opened from the listing, it's recorded as the file itself
+		frecency.record(&"recent://".parse::<Url>()?.join("b"), true, 3);
+		frecency.record(&url("rel"), true, now);
+		assert_eq!(frecency.ranked(&Scheme::Recent, now), paths(&["/b", "/a"]));
+
+		// Round trip
+		let dir = TempDir::new("frecency")?;
+		let path = dir.join("frecency.json");
+		frecency.save_to(&path)?;
+		let loaded = Frecency::new();
+		loaded.load_from(&path)?;
+		assert_eq!(*loaded.records.read(), *frecency.records.read());
+
+		Ok(())
+	}
+}
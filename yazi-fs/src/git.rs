@@ -0,0 +1,199 @@
+use std::{collections::BTreeMap, io, path::{Path, PathBuf}};
+
+use parking_lot::RwLock;
+use serde::Deserialize;
+use tokio::{process::Command, sync::Mutex};
+use yazi_shared::{IntoOsStr, url::Url};
+
+/// The Git status of the files in the repositories visited.
+pub static GIT: Git = Git::new();
+
+/// The status of a file in its worktree, in the order a directory takes it by.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum GitStatus {
+	Ignored,
+	Untracked,
+	Added,
+	Modified,
+	Conflicted,
+}
+
+impl GitStatus {
+	pub const fn as_str(self) -> &'static str {
+		match self {
+			Self::Ignored => "ignored",
+			Self::Untracked => "untracked",
+			Self::Added => "added",
+			Self::Modified => "modified",
+			Self::Conflicted => "conflicted",
+		}
+	}
+}
+
+pub struct Git {
+	repos:   RwLock<BTreeMap<PathBuf, Repo>>,
+	// The worktree each directory is in, if any
+	roots:   RwLock<BTreeMap<PathBuf, Option<PathBuf>>>,
+	running: Mutex<()>,
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct Repo {
+	stale: bool,
+	// Relative to the root, where untracked and ignored directories are given
+	// as a whole, as `git status` does
+	files: BTreeMap<PathBuf, GitStatus>,
+	dirs:  BTreeMap<PathBuf, GitStatus>,
+}
+
+impl Git {
+	const fn new() -> Self {
+		Self {
+			repos:   RwLock::new(BTreeMap::new()),
+			roots:   RwLock::new(BTreeMap::new()),
+			running: Mutex::const_new(()),
+		}
+	}
+
+	/// The status of the file, or `None` if it's clean or unknown.
+	pub fn status(&self, url: &Url) -> Option<GitStatus> {
+		let path = url.as_path()?;
+		let repos = self.repos.read();
+		let (root, repo) =
+			repos.range(..=path.to_path_buf()).rev().find(|(r, _)| path.starts_with(r))?;
+		repo.get(path.strip_prefix(root).ok()?)
+	}
+
+	/// Fetches the status of the worktree the directory is in, if it's changed.
+	pub async fn refresh(&self, dir: &Url) -> io::Result<bool> {
+		let Some(dir) = dir.as_path() else { return Ok(false) };
+		let Some(root) = self.root(dir) else { return Ok(false) };
+
+		let _guard = self.running.lock().await;
+		if self.repos.read().get(&root).is_some_and(|r| !r.stale) {
+			return Ok(true);
+		}
+
+		let output = Command::new("git")
+			.arg("-C")
+			.arg(&root)
+			.args(["status", "--porcelain", "-z", "--ignored=matching", "--untracked-files=normal"])
+			.kill_on_drop(true)
+			.output()
+			.await?;
+		if !output.status.success() {
+			let err = String::from_utf8_lossy(&output.stderr);
+			return Err(io::Error::other(format!("`git status` failed: {}", err.trim())));
+		}
+
+		self.repos.write().insert(root, Repo::parse(&output.stdout));
+		Ok(true)
+	}
+
+	/// Marks the worktree the path is in stale, unless it's in `.git`.
+	pub fn invalidate(&self, path: &Path) {
+		let mut repos = self.repos.write();
+		let found = repos.range_mut(..=path.to_path_buf()).rev().find(|(r, _)| path.starts_with(r));
+		if let Some((root, repo)) = found
+			&& !path.strip_prefix(root).is_ok_and(|p| p.starts_with(".git"))
+		{
+			repo.stale = true;
+		}
+	}
+
+	fn root(&self, dir: &Path) -> Option<PathBuf> {
+		if let Some(root) = self.roots.read().get(dir) {
+			return root.clone();
+		}
+
+		// `.git` is a file in the worktrees added with `git worktree`
+		let root = dir.ancestors().find(|p| p.join(".git").exists()).map(Path::to_path_buf);
+		self.roots.write().insert(dir.to_path_buf(), root.clone());
+		root
+	}
+}
+
+impl Repo {
+	fn parse(out: &[u8]) -> Self {
+		let mut me = Self::default();
+		let mut it = out.split(|&b| b == 0);
+		while let Some(entry) = it.next() {
+			let [x, y, b' ', path @ ..] = entry else { continue };
+			let Ok(path) = path.into_os_str() else { continue };
+			let path = PathBuf::from(path.into_owned());
+
+			// Renamed or copied, followed by the path it's from
+			if matches!(x, b'R' | b'C') || matches!(y, b'R' | b'C') {
+				it.next();
+			}
+
+			let status = match (x, y) {
+				(b'!', b'!') => GitStatus::Ignored,
+				(b'?', b'?') => GitStatus::Untracked,
+				(b'U', _) | (_, b'U') | (b'A', b'A') | (b'D', b'D') => GitStatus::Conflicted,
+				(b'A' | b'R' | b'C', b' ') => GitStatus::Added,
+				_ => GitStatus::Modified,
+			};
+
+			if status != GitStatus::Ignored {
+				for dir in path.ancestors().skip(1).filter(|p| !p.as_os_str().is_empty()) {
+					let s = me.dirs.entry(dir.to_path_buf()).or_insert(status);
+					*s = status.max(*s);
+				}
+			}
+			me.files.insert(path, status);
+		}
+		me
+	}
+
+	fn get(&self, rel: &Path) -> Option<GitStatus> {
+		if let Some(&s) = self.files.get(rel).or_else(|| self.dirs.get(rel)) {
+			return Some(s);
+		}
+
+		// Everything in an untracked or ignored directory is too
+		rel.ancestors().skip(1).find_map(|p| self.files.get(p)).copied()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_parse() {
+		let out =
+			b" M src/main.rs\0?? notes/\0!! target/\0R  new.rs\0old.rs\0UU src/lib.rs\0A  src/a/b.rs\0";
+		let repo = Repo::parse(out);
+		let get = |s: &str| repo.get(Path::new(s));
+
+		assert_eq!(get("src/main.rs"), Some(GitStatus::Modified));
+		assert_eq!(get("new.rs"), Some(GitStatus::Added));
+		assert_eq!(get("old.rs"), None);
+		assert_eq!(get("src/a/b.rs"), Some(GitStatus::Added));
+		assert_eq!(get("README.md"), None);
+
+		// Directories take the highest status of their files, except ignored ones
+		assert_eq!(get("src"), Some(GitStatus::Conflicted));
+		assert_eq!(get("src/a"), Some(GitStatus::Added));
+		assert_eq!(get("notes/todo.md"), Some(GitStatus::Untracked));
+		assert_eq!(get("target/debug/yazi"), Some(GitStatus::Ignored));
+	}
+
+	#[test]
+	fn test_status() {
+		let git = Git::new();
+		git.repos.write().insert(PathBuf::from("/repo"), Repo::parse(b" M a.rs\0"));
+
+		let status = |s: &str| git.status(&Url::from(Path::new(s)));
+		assert_eq!(status("/repo/a.rs"), Some(GitStatus::Modified));
+		assert_eq!(status("/repo/b.rs"), None);
+		assert_eq!(status("/repository/a.rs"), None);
+
+		git.invalidate(Path::new("/repo/.git/index"));
+		assert!(!git.repos.read()[Path::new("/repo")].stale);
+		git.invalidate(Path::new("/repo/a.rs"));
+		assert!(git.repos.read()[Path::new("/repo")].stale);
+	}
+}
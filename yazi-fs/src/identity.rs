@@ -0,0 +1,160 @@
+use std::{io, path::{Path, PathBuf}};
+
+/// A directory known by an open handle to it rather than its path.
+#[derive(Debug)]
+pub struct DirIdentity {
+	file: std::fs::File,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Relocation {
+	Renamed(PathBuf),
+	Deleted,
+}
+
+impl DirIdentity {
+	pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+		let file = Self::handle(path.as_ref())?;
+		if file.metadata()?.is_dir() {
+			Ok(Self { file })
+		} else {
+			Err(io::Error::from(io::ErrorKind::NotADirectory))
+		}
+	}
+
+	/// Where the directory is now, compared to its `old` path.
+	pub fn relocation(&self, old: &Path) -> Option<Relocation> {
+		#[cfg(unix)]
+		if std::os::unix::fs::MetadataExt::nlink(&self.file.metadata().ok()?) == 0 {
+			return Some(Relocation::Deleted);
+		}
+
+		match self.locate() {
+			Ok(Some(p)) if p == old => None,
+			Ok(Some(p)) => Some(Relocation::Renamed(p)),
+			Ok(None) => Some(Relocation::Deleted),
+			Err(_) => None,
+		}
+	}
+
+	fn locate(&self) -> io::Result<Option<PathBuf>> {
+		let path = self.path()?;
+		match self.same_as(&path) {
+			Ok(same) => Ok(same.then_some(path)),
+			Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+			Err(e) => Err(e),
+		}
+	}
+
+	#[cfg(unix)]
+	fn handle(path: &Path) -> io::Result<std::fs::File> { std::fs::File::open(path) }
+
+	#[cfg(windows)]
+	fn handle(path: &Path) -> io::Result<std::fs::File> {
+		use std::os::windows::fs::OpenOptionsExt;
+		std::fs::OpenOptions::new()
+			.read(true)
+			.custom_flags(windows_sys::Win32::Storage::FileSystem::FILE_FLAG_BACKUP_SEMANTICS)
+			.open(path)
+	}
+
+	#[cfg(unix)]
+	fn same_as(&self, path: &Path) -> io::Result<bool> {
+		use std::os::unix::fs::MetadataExt;
+		let (me, other) = (self.file.metadata()?, std::fs::metadata(path)?);
+		Ok(me.dev() == other.dev() && me.ino() == other.ino())
+	}
+
+	// The volume serial number and file index, which is what identifies a file
+	#[cfg(windows)]
+	fn same_as(&self, path: &Path) -> io::Result<bool> {
+		use std::os::windows::io::AsRawHandle;
+
+		use windows_sys::Win32::{Foundation::HANDLE, Storage::FileSystem::{BY_HANDLE_FILE_INFORMATION, GetFileInformationByHandle}};
+
+		fn id(file: &std::fs::File) -> io::Result<(u32, u64)> {
+			let mut info: BY_HANDLE_FILE_INFORMATION = unsafe { std::mem::zeroed() };
+			if unsafe { GetFileInformationByHandle(file.as_raw_handle() as HANDLE, &mut info) } == 0 {
+				return Err(io::Error::last_os_error());
+			}
+			let index = ((info.nFileIndexHigh as u64) << 32) | info.nFileIndexLow as u64;
+			Ok((info.dwVolumeSerialNumber, index))
+		}
+
+		Ok(id(&self.file)? == id(&Self::handle(path)?)?)
+	}
+
+	#[cfg(any(target_os = "linux", target_os = "android"))]
+	fn path(&self) -> io::Result<PathBuf> {
+		use std::os::fd::AsRawFd;
+		std::fs::read_link(format!("/proc/self/fd/{}", self.file.as_raw_fd()))
+	}
+
+	#[cfg(target_os = "macos")]
+	fn path(&self) -> io::Result<PathBuf> {
+		use std::{ffi::OsString, os::{fd::AsRawFd, unix::ffi::OsStringExt}};
+
+		let mut buf = vec![0u8; libc::PATH_MAX as usize];
+		if unsafe { libc::fcntl(self.file.as_raw_fd(), libc::F_GETPATH, buf.as_mut_ptr()) } == -1 {
+			return Err(io::Error::last_os_error());
+		}
+
+		buf.truncate(buf.iter().position(|&b| b == 0).unwrap_or(buf.len()));
+		Ok(OsString::from_vec(buf).into())
+	}
+
+	#[cfg(windows)]
+	fn path(&self) -> io::Result<PathBuf> {
+		use std::{ffi::OsString, os::windows::{ffi::OsStringExt, io::AsRawHandle}};
+
+		use windows_sys::Win32::{Foundation::{HANDLE, MAX_PATH}, Storage::FileSystem::{GetFinalPathNameByHandleW, VOLUME_NAME_DOS}};
+
+		let mut buf = [0u16; MAX_PATH as usize];
+		let len = unsafe {
+			GetFinalPathNameByHandleW(
+				self.file.as_raw_handle() as HANDLE,
+				buf.as_mut_ptr(),
+				buf.len() as u32,
+				VOLUME_NAME_DOS,
+			)
+		};
+		if len == 0 || len as usize > buf.len() {
+			return Err(io::Error::last_os_error());
+		}
+
+		// Strip the verbatim prefix `\\?\` to get a normal path
+		let s = OsString::from_wide(&buf[..len as usize]).to_string_lossy().into_owned();
+		Ok(PathBuf::from(s.strip_prefix(r"\\?\").unwrap_or(&s)))
+	}
+
+	#[cfg(not(any(windows, target_os = "linux", target_os = "android", target_os = "macos")))]
+	fn path(&self) -> io::Result<PathBuf> { Err(io::Error::from(io::ErrorKind::Unsupported)) }
+}
+
+#[cfg(test)]
+mod tests {
+	use std::fs;
+
+	use super::*;
+	use crate::TempDir;
+
+	#[test]
+	fn test_relocation() -> io::Result<()> {
+		let root = TempDir::new("identity")?;
+		fs::create_dir_all(root.join("a/b"))?;
+		let root = root.canonicalize()?;
+
+		// Renamed by another process
+		let id = DirIdentity::open(root.join("a/b"))?;
+		assert_eq!(id.relocation(&root.join("a/b")), None);
+
+		fs::rename(root.join("a/b"), root.join("c"))?;
+		assert_eq!(id.relocation(&root.join("a/b")), Some(Relocation::Renamed(root.join("c"))));
+
+		// Deleted outright
+		fs::remove_dir(root.join("c"))?;
+		assert_eq!(id.relocation(&root.join("c")), Some(Relocation::Deleted));
+
+		Ok(())
+	}
+}
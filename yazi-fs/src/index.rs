@@ -0,0 +1,267 @@
+use std::{collections::{BTreeMap, BTreeSet}, ffi::OsStr, ops::Bound::{Included, Unbounded}, path::{Path, PathBuf}, sync::atomic::{AtomicBool, Ordering}};
+
+use anyhow::{Result, bail};
+use parking_lot::{Mutex, RwLock};
+use yazi_shared::IntoOsStr;
+
+use crate::{Xdg, write_atomic};
+
+/// The file names under the indexed roots, persisted in the state directory.
+pub static INDEX: Index = Index::new();
+
+/// An index of the paths under some roots, to search files by name.
+pub struct Index {
+	roots:  RwLock<BTreeMap<PathBuf, BTreeSet<PathBuf>>>,
+	dirty:  AtomicBool,
+	saving: Mutex<()>,
+}
+
+impl Index {
+	const MAGIC: &[u8] = b"yazi-index-1\n";
+
+	const fn new() -> Self {
+		Self {
+			roots:  RwLock::new(BTreeMap::new()),
+			dirty:  AtomicBool::new(false),
+			saving: Mutex::new(()),
+		}
+	}
+
+	/// Whether the directory is under one of the indexed roots.
+	pub fn covers(&self, dir: &Path) -> bool {
+		self.roots.read().keys().any(|root| dir.starts_with(root))
+	}
+
+	/// The paths under the directory, relative to it, whose names match.
+	pub fn search(
+		&self,
+		dir: &Path,
+		hidden: bool,
+		mut matches: impl FnMut(&OsStr) -> bool,
+	) -> Option<Vec<PathBuf>> {
+		let roots = self.roots.read();
+		let (root, paths) = roots.iter().rev().find(|(root, _)| dir.starts_with(root))?;
+
+		let base = dir.strip_prefix(root).ok()?;
+		let mut found = vec![];
+		for path in paths.range::<Path, _>((Included(base), Unbounded)).skip_while(|&p| p == base) {
+			let Ok(rel) = path.strip_prefix(base) else { break };
+			if !hidden && rel.iter().any(|s| s.as_encoded_bytes().starts_with(b".")) {
+				continue;
+			}
+			if rel.file_name().is_some_and(&mut matches) {
+				found.push(rel.to_owned());
+			}
+		}
+		Some(found)
+	}
+
+	/// Walks the root to index the paths under it, replacing the old ones.
+	pub async fn build(&self, root: PathBuf) {
+		let mut paths = BTreeSet::new();
+		let mut dirs = vec![PathBuf::new()];
+		while let Some(dir) = dirs.pop() {
+			let Ok(mut it) = tokio::fs::read_dir(root.join(&dir)).await else { continue };
+			while let Ok(Some(entry)) = it.next_entry().await {
+				let path = dir.join(entry.file_name());
+				if entry.file_type().await.is_ok_and(|t| t.is_dir()) {
+					dirs.push(path.clone());
+				}
+				paths.insert(path);
+			}
+		}
+
+		self.roots.write().insert(root, paths);
+		self.dirty.store(true, Ordering::Relaxed);
+	}
+
+	/// Drops the roots that are no longer to be indexed.
+	pub fn retain(&self, roots: &[PathBuf]) {
+		let mut old = self.roots.write();
+		let len = old.len();
+		old.retain(|root, _| roots.contains(root));
+		if old.len() != len {
+			self.dirty.store(true, Ordering::Relaxed);
+		}
+	}
+
+	/// Updates the index with the current state of a path that has changed.
+	pub fn update(&self, path: &Path, exists: bool) {
+		let mut roots = self.roots.write();
+		let Some((root, paths)) = roots.iter_mut().rev().find(|(root, _)| path.starts_with(root))
+		else {
+			return;
+		};
+		let Ok(rel) = path.strip_prefix(root) else { return };
+		if rel.as_os_str().is_empty() {
+			return;
+		}
+
+		if exists {
+			// A new directory is indexed by the events of its children
+			paths.insert(rel.to_owned());
+		} else {
+			let gone: Vec<_> = paths
+				.range::<Path, _>((Included(rel), Unbounded))
+				.take_while(|p| p.starts_with(rel))
+				.cloned()
+				.collect();
+			gone.iter().for_each(|p| _ = paths.remove(p));
+		}
+		self.dirty.store(true, Ordering::Relaxed);
+	}
+
+	/// Loads the index saved by [`Self::save`].
+	pub fn load(&self) -> Result<()> { self.load_from(&Self::path()) }
+
+	/// Saves the index to the state directory if it has changed, blocking the current thread.
+	pub fn save(&self) -> Result<()> {
+		if self.dirty.swap(false, Ordering::Relaxed) { self.save_to(&Self::path()) } else { Ok(()) }
+	}
+
+	fn load_from(&self, path: &Path) -> Result<()> {
+		let buf = std::fs::read(path)?;
+		let Some(mut r) = buf.strip_prefix(Self::MAGIC) else {
+			bail!("Invalid index file: {}", path.display());
+		};
+
+		let mut roots = BTreeMap::new();
+		for _ in 0..read_varint(&mut r)? {
+			let root = PathBuf::from(read_bytes(&mut r)?.into_os_str()?.into_owned());
+
+			// Front-coded, each path is stored as the length of the prefix it shares
+			// with the previous one, followed by the rest
+			let (mut paths, mut prev) = (BTreeSet::new(), Vec::new());
+			for _ in 0..read_varint(&mut r)? {
+				let shared = read_varint(&mut r)?;
+				if shared > prev.len() {
+					bail!("Corrupted index file: {}", path.display());
+				}
+				prev.truncate(shared);
+				prev.extend_from_slice(read_bytes(&mut r)?);
+				paths.insert(PathBuf::from(prev.as_slice().into_os_str()?.into_owned()));
+			}
+			roots.insert(root, paths);
+		}
+
+		*self.roots.write() = roots;
+		Ok(())
+	}
+
+	fn save_to(&self, path: &Path) -> Result<()> {
+		let _guard = self.saving.lock();
+		let mut buf = Self::MAGIC.to_vec();
+		{
+			let roots = self.roots.read();
+			write_varint(&mut buf, roots.len());
+			for (root, paths) in &*roots {
+				write_bytes(&mut buf, root.as_os_str().as_encoded_bytes());
+				write_varint(&mut buf, paths.len());
+
+				let mut prev: &[u8] = &[];
+				for p in paths {
+					let b = p.as_os_str().as_encoded_bytes();
+					let shared = prev.iter().zip(b).take_while(|(a, b)| a == b).count();
+					write_varint(&mut buf, shared);
+					write_bytes(&mut buf, &b[shared..]);
+					prev = b;
+				}
+			}
+		}
+
+		Ok(write_atomic(path, &buf)?)
+	}
+
+	#[inline]
+	fn path() -> PathBuf { Xdg::state_dir().join("index.bin") }
+}
+
+fn write_varint(buf: &mut Vec<u8>, mut n: usize) {
+	while n >= 0x80 {
+		buf.push(n as u8 | 0x80);
+		n >>= 7;
+	}
+	buf.push(n as u8);
+}
+
+fn write_bytes(buf: &mut Vec<u8>, b: &[u8]) {
+	write_varint(buf, b.len());
+	buf.extend_from_slice(b);
+}
+
+fn read_varint(r: &mut &[u8]) -> Result<usize> {
+	let (mut n, mut shift) = (0usize, 0);
+	loop {
+		let Some((&b, rest)) = r.split_first() else { bail!("Unexpected end of index file") };
+		*r = rest;
+		if shift >= usize::BITS {
+			bail!("Invalid varint in index file");
+		}
+
+		n |= ((b & 0x7f) as usize) << shift;
+		if b & 0x80 == 0 {
+			return Ok(n);
+		}
+		shift += 7;
+	}
+}
+
+fn read_bytes<'a>(r: &mut &'a [u8]) -> Result<&'a [u8]> {
+	let len = read_varint(r)?;
+	if len > r.len() {
+		bail!("Unexpected end of index file");
+	}
+
+	let (b, rest) = r.split_at(len);
+	*r = rest;
+	Ok(b)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::TempDir;
+
+	#[tokio::test]
+	async fn test_index() -> Result<()> {
+		let dir = TempDir::new("index")?;
+		let root = dir.join("root");
+		for p in ["a/b/foo.rs", "a/foo.md", "a b/foo", ".hidden/foo", "bar"] {
+			std::fs::create_dir_all(root.join(p).parent().unwrap())?;
+			std::fs::write(root.join(p), "")?;
+		}
+
+		let index = Index::new();
+		index.build(root.clone()).await;
+
+		let search = |index: &Index, dir: &Path, hidden: bool| {
+			let mut v = index.search(dir, hidden, |s| s.to_string_lossy().starts_with("foo"))?;
+			v.sort();
+			Some(v)
+		};
+		let paths = |v: &[&str]| Some(v.iter().map(PathBuf::from).collect::<Vec<_>>());
+
+		assert_eq!(search(&index, &root, false), paths(&["a/b/foo.rs", "a/foo.md", "a b/foo"]));
+		assert_eq!(
+			search(&index, &root, true),
+			paths(&[".hidden/foo", "a/b/foo.rs", "a/foo.md", "a b/foo"])
+		);
+		assert_eq!(search(&index, &root.join("a"), false), paths(&["b/foo.rs", "foo.md"]));
+		assert_eq!(search(&index, &dir, false), None);
+		assert!(index.covers(&root.join("a/b")) && !index.covers(&dir));
+
+		// Removing a directory removes everything under it
+		index.update(&root.join("a"), false);
+		index.update(&root.join("food"), true);
+		assert_eq!(search(&index, &root, false), paths(&["a b/foo", "food"]));
+
+		// Round trip
+		let path = dir.join("index.bin");
+		index.save_to(&path)?;
+		let loaded = Index::new();
+		loaded.load_from(&path)?;
+		assert_eq!(*loaded.roots.read(), *index.roots.read());
+
+		Ok(())
+	}
+}
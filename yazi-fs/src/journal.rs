@@ -0,0 +1,84 @@
+use std::fmt::{self, Display};
+
+use yazi_macro::relay;
+use yazi_shared::{Id, url::Url};
+
+/// A file operation done by the user, to be undone and redone.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum JournalOp {
+	Rename { from: Url, to: Url },
+	Move { from: Url, to: Url },
+	Trash { target: Url },
+	Create { target: Url, dir: bool },
+}
+
+impl JournalOp {
+	#[inline]
+	pub fn emit(self) {
+		yazi_shared::event::Event::Call(relay!(mgr:update_journal).with_any("op", self).into()).emit();
+	}
+
+	/// Same as [`JournalOp::emit`], for an operation done by the task.
+	#[inline]
+	pub fn emit_by(self, task: Id) {
+		let cmd = relay!(mgr:update_journal).with_any("op", self).with("task", task);
+		yazi_shared::event::Event::Call(cmd.into()).emit();
+	}
+
+	/// Why the operation can't be reversed, if it can't.
+	pub fn irreversible(&self) -> Option<String> {
+		match self {
+			Self::Rename { from, to } | Self::Move { from, to } => {
+				if from.scheme.kind() != to.scheme.kind()
+					&& (from.as_path().is_none() || to.as_path().is_none())
+				{
+					Some(format!("it crosses from {} to {}", from.scheme.kind(), to.scheme.kind()))
+				} else if from.as_path().is_none() {
+					Some(format!("{} files aren't supported", from.scheme.kind()))
+				} else {
+					None
+				}
+			}
+			Self::Trash { target } | Self::Create { target, .. } => {
+				Some(format!("{} files aren't supported", target.scheme.kind()))
+					.filter(|_| target.as_path().is_none())
+			}
+		}
+	}
+}
+
+impl Display for JournalOp {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Rename { from, to } => {
+				write!(f, "renaming `{}` to `{}`", from.name().display(), to.name().display())
+			}
+			Self::Move { from, to } => write!(f, "moving `{}` to `{}`", from.display(), to.display()),
+			Self::Trash { target } => write!(f, "trashing `{}`", target.display()),
+			Self::Create { target, .. } => write!(f, "creating `{}`", target.display()),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use anyhow::Result;
+
+	use super::*;
+
+	#[test]
+	fn test_irreversible() -> Result<()> {
+		let op = |from: &str, to: &str| -> Result<_> {
+			Ok(JournalOp::Move { from: from.parse()?, to: to.parse()? }.irreversible())
+		};
+
+		assert_eq!(op("/tmp/a", "/tmp/b/a")?, None);
+		assert_eq!(op("search://kw//tmp/a", "/tmp/b/a")?, None);
+		assert_eq!(op("sftp://r//a", "/tmp/a")?.as_deref(), Some("it crosses from sftp to regular"));
+		assert_eq!(op("sftp://r//a", "sftp://r//b")?.as_deref(), Some("sftp files aren't supported"));
+
+		let trash = JournalOp::Trash { target: "archive://:1:1//tmp/a.zip/b".parse()? };
+		assert_eq!(trash.irreversible().as_deref(), Some("archive files aren't supported"));
+		Ok(())
+	}
+}
@@ -1,8 +1,13 @@
 #![allow(clippy::if_same_then_else, clippy::option_map_unit_fn)]
 
-yazi_macro::mod_pub!(cha mounts provider path);
+yazi_macro::mod_pub!(apps cha mounts provider path);
 
-yazi_macro::mod_flat!(calculator cwd file files filter fns op sorter sorting stage xdg);
+yazi_macro::mod_flat!(audit bandwidth bookmarks calculator chmod chunked chown cloud compare copy cwd dupes file files filter fns frecency git identity index journal op pause poller query renamer sanitizer selection session sizes sorter sorting stage store tags trash views xattr xdg);
+
+#[cfg(test)]
+mod temp;
+#[cfg(test)]
+use temp::TempDir;
 
 pub fn init() {
 	CWD.init(<_>::default());
@@ -1,6 +1,6 @@
 use std::{ffi::OsString, path::PathBuf};
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, PartialEq)]
 pub struct Partition {
 	pub src:       OsString,
 	pub dist:      Option<PathBuf>,
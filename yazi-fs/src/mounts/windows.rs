@@ -0,0 +1,69 @@
+use std::{ffi::OsString, os::windows::ffi::OsStringExt, path::PathBuf, ptr::null_mut, time::Duration};
+
+use tokio::time::sleep;
+use windows_sys::Win32::Storage::FileSystem::{GetDriveTypeW, GetLogicalDrives, GetVolumeInformationW};
+
+use super::{Locked, Partition, Partitions};
+
+// The types returned by `GetDriveTypeW`
+const DRIVE_REMOVABLE: u32 = 2;
+const DRIVE_REMOTE: u32 = 4;
+const DRIVE_CDROM: u32 = 5;
+
+impl Partitions {
+	pub fn monitor<F>(me: Locked, cb: F)
+	where
+		F: Fn() + Copy + Send + 'static,
+	{
+		// There's no notification for drive letters without a window to receive
+		// `WM_DEVICECHANGE`, so they're polled instead
+		tokio::spawn(async move {
+			loop {
+				let new = tokio::task::spawn_blocking(Self::all).await.unwrap_or_default();
+				if me.read().inner != new {
+					me.write().inner = new;
+					cb();
+				}
+				sleep(Duration::from_secs(3)).await;
+			}
+		});
+	}
+
+	fn all() -> Vec<Partition> {
+		let mask = unsafe { GetLogicalDrives() };
+		(0..26u8).filter(|&i| mask & (1 << i) != 0).map(|i| Self::drive((b'A' + i) as char)).collect()
+	}
+
+	fn drive(letter: char) -> Partition {
+		let root: Vec<u16> = format!("{letter}:\\").encode_utf16().chain([0]).collect();
+		let kind = unsafe { GetDriveTypeW(root.as_ptr()) };
+
+		let (mut label, mut fstype) = ([0u16; 261], [0u16; 261]);
+		let ok = unsafe {
+			GetVolumeInformationW(
+				root.as_ptr(),
+				label.as_mut_ptr(),
+				label.len() as _,
+				null_mut(),
+				null_mut(),
+				null_mut(),
+				fstype.as_mut_ptr(),
+				fstype.len() as _,
+			)
+		} != 0;
+
+		let s =
+			|b: &[u16]| OsString::from_wide(&b[..b.iter().position(|&c| c == 0).unwrap_or(b.len())]);
+		Partition {
+			src: format!("{letter}:").into(),
+			// A drive without a medium, like a card reader without a card, can't be
+			// entered
+			dist: ok.then(|| PathBuf::from(format!("{letter}:\\"))),
+			label: Some(s(&label)).filter(|s| ok && !s.is_empty()),
+			fstype: ok.then(|| s(&fstype)),
+			external: Some(kind == DRIVE_REMOTE),
+			removable: Some(matches!(kind, DRIVE_REMOVABLE | DRIVE_CDROM)),
+			..Default::default()
+		}
+	}
+}
@@ -15,54 +15,113 @@ pub fn skip_url(url: &Url, n: usize) -> Cow<'_, OsStr> {
 	it.os_str()
 }
 
+/// Attempts before falling back to a timestamp suffix in [`unique_name`].
+const UNIQUE_ATTEMPTS: u64 = 1000;
+
 pub async fn unique_name<F>(u: Url, append: F) -> io::Result<Url>
 where
 	F: Future<Output = bool>,
 {
 	match provider::symlink_metadata(&u).await {
-		Ok(_) => _unique_name(u, append.await).await,
-		Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(u),
-		Err(e) => Err(e),
+		Ok(_) => {}
+		Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(u),
+		Err(e) => return Err(e),
+	}
+
+	for url in UniqueNames::new(u, append.await)? {
+		match provider::symlink_metadata(&url).await {
+			Ok(_) => {}
+			Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(url),
+			Err(e) => return Err(e),
+		}
 	}
+	Err(io::Error::new(io::ErrorKind::AlreadyExists, "no unique name available"))
 }
 
-async fn _unique_name(mut url: Url, append: bool) -> io::Result<Url> {
-	let Some(stem) = url.file_stem().map(|s| s.to_owned()) else {
-		return Err(io::Error::new(io::ErrorKind::InvalidInput, "empty file stem"));
-	};
+/// Same as [`unique_name`], but takes each candidate by calling `create` on it.
+pub async fn unique_name_with<F, C, Fut, T>(
+	u: Url,
+	append: F,
+	mut create: C,
+) -> io::Result<(Url, T)>
+where
+	F: Future<Output = bool>,
+	C: FnMut(Url) -> Fut,
+	Fut: Future<Output = io::Result<T>>,
+{
+	match create(u.clone()).await {
+		Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {}
+		r => return r.map(|t| (u, t)),
+	}
 
-	let dot_ext = url.extension().map_or_else(OsString::new, |e| {
-		let mut s = OsString::with_capacity(e.len() + 1);
-		s.push(".");
-		s.push(e);
-		s
-	});
-
-	let mut i = 1u64;
-	let mut name = OsString::with_capacity(stem.len() + dot_ext.len() + 5);
-	loop {
-		name.clear();
-		name.push(&stem);
-
-		if append {
-			name.push(&dot_ext);
-			name.push("_");
-			name.push(i.to_string());
-		} else {
-			name.push("_");
-			name.push(i.to_string());
-			name.push(&dot_ext);
+	for url in UniqueNames::new(u, append.await)? {
+		match create(url.clone()).await {
+			Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {}
+			r => return r.map(|t| (url, t)),
 		}
+	}
+	Err(io::Error::new(io::ErrorKind::AlreadyExists, "no unique name available"))
+}
 
-		url.set_name(&name);
-		match provider::symlink_metadata(&url).await {
-			Ok(_) => i += 1,
-			Err(e) if e.kind() == io::ErrorKind::NotFound => break,
-			Err(e) => return Err(e),
+/// Candidates of [`unique_name`], then one with a timestamp.
+struct UniqueNames {
+	url:  Url,
+	stem: OsString,
+	ext:  OsString,
+	i:    u64,
+	buf:  OsString,
+}
+
+impl UniqueNames {
+	fn new(url: Url, append: bool) -> io::Result<Self> {
+		let name = url.name();
+		if name.is_empty() {
+			return Err(io::Error::new(io::ErrorKind::InvalidInput, "empty file name"));
 		}
+
+		let (stem, ext) = if append { (name, OsStr::new("")) } else { split_ext(name) };
+		let (stem, ext) = (stem.to_owned(), ext.to_owned());
+		Ok(Self { url, buf: OsString::with_capacity(stem.len() + ext.len() + 5), stem, ext, i: 1 })
 	}
+}
+
+impl Iterator for UniqueNames {
+	type Item = Url;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let n = match self.i {
+			i if i < UNIQUE_ATTEMPTS => i,
+			UNIQUE_ATTEMPTS => yazi_shared::timestamp_us(),
+			_ => return None,
+		};
+		self.i += 1;
 
-	Ok(url)
+		self.buf.clear();
+		self.buf.push(&self.stem);
+		self.buf.push("_");
+		self.buf.push(n.to_string());
+		self.buf.push(&self.ext);
+
+		self.url.set_name(&self.buf);
+		Some(self.url.clone())
+	}
+}
+
+/// Splits the name into the stem and the extension with its leading dot.
+fn split_ext(name: &OsStr) -> (&OsStr, &OsStr) {
+	let b = name.as_encoded_bytes();
+	let Some(i) = b.iter().rposition(|&c| c == b'.').filter(|&i| i > 0) else {
+		return (name, OsStr::new(""));
+	};
+
+	let i = match b[..i].iter().rposition(|&c| c == b'.') {
+		Some(j) if j > 0 && b[j + 1..i].eq_ignore_ascii_case(b"tar") => j,
+		_ => i,
+	};
+
+	let (stem, ext) = b.split_at(i);
+	// SAFETY: These substrings were separated by an ASCII dot.
+	unsafe { (OsStr::from_encoded_bytes_unchecked(stem), OsStr::from_encoded_bytes_unchecked(ext)) }
 }
 
 pub fn url_relative_to<'a>(from: &Url, to: &'a Url) -> Result<Cow<'a, Url>> {
@@ -125,9 +184,64 @@ pub fn backslash_to_slash(p: &std::path::Path) -> Cow<'_, std::path::Path> {
 
 #[cfg(test)]
 mod tests {
-	use std::borrow::Cow;
+	use std::{borrow::Cow, fs};
+
+	use super::*;
+	use crate::TempDir;
+
+	#[test]
+	fn test_split_ext() {
+		fn assert(name: &str, stem: &str, ext: &str) {
+			assert_eq!(split_ext(OsStr::new(name)), (OsStr::new(stem), OsStr::new(ext)));
+		}
+
+		assert("file.txt", "file", ".txt");
+		assert("file", "file", "");
+		assert("archive.tar.gz", "archive", ".tar.gz");
+		assert("archive.TAR.xz", "archive", ".TAR.xz");
+		assert("my.notes.md", "my.notes", ".md");
+		assert(".bashrc", ".bashrc", "");
+		assert(".config.toml", ".config", ".toml");
+		assert(".tar.gz", ".tar", ".gz");
+		assert(".backup.tar.gz", ".backup", ".tar.gz");
+	}
 
-	use super::url_relative_to;
+	#[tokio::test]
+	async fn test_unique_name() -> io::Result<()> {
+		let root = TempDir::new("unique-name")?;
+		fs::create_dir_all(root.join("docs"))?;
+		fs::create_dir_all(root.join("docs_1"))?;
+		for f in ["file.txt", "file_1.txt", "archive.tar.gz", "archive_1.tar.gz", ".hidden"] {
+			fs::write(root.join(f), "")?;
+		}
+
+		let unique = async |name: &str, dir: bool| {
+			let url = unique_name(Url::from(root.join(name)), async { dir }).await.unwrap();
+			url.name().to_string_lossy().into_owned()
+		};
+
+		assert_eq!(unique("file.txt", false).await, "file_2.txt");
+		assert_eq!(unique("archive.tar.gz", false).await, "archive_2.tar.gz");
+		assert_eq!(unique(".hidden", false).await, ".hidden_1");
+		assert_eq!(unique("docs", true).await, "docs_2");
+		assert_eq!(unique("fresh", false).await, "fresh");
+
+		// Taking the name atomically, where `file_2.txt` is taken by someone else
+		// right before we try to create it
+		let mut raced = false;
+		let (url, _) = unique_name_with(Url::from(root.join("file.txt")), async { false }, |u| {
+			let path = u.as_path().unwrap().to_owned();
+			if !raced && path.ends_with("file_2.txt") {
+				raced = true;
+				fs::write(&path, "").ok();
+			}
+			async move { tokio::fs::OpenOptions::new().write(true).create_new(true).open(path).await }
+		})
+		.await?;
+		assert_eq!(url.name(), "file_3.txt");
+
+		Ok(())
+	}
 
 	#[test]
 	fn test_path_relative_to() {
@@ -0,0 +1,151 @@
+use std::{ffi::OsString, io, path::{Component, Path, PathBuf}};
+
+use yazi_shared::url::{Loc, Scheme, Url};
+
+use crate::provider::local::Local;
+
+/// Maximum number of symlinks to follow, the same as Linux's `MAXSYMLINKS`.
+const MAX_SYMLINKS: usize = 40;
+
+/// Resolves the path like `realpath -m`.
+pub async fn realpath_missing(path: impl AsRef<Path>) -> io::Result<PathBuf> {
+	let path = path.as_ref();
+	let path = if path.is_absolute() { path.to_owned() } else { std::env::current_dir()?.join(path) };
+
+	// Components yet to be resolved, in reverse order
+	let mut todo: Vec<OsString> = vec![];
+	let mut out = PathBuf::new();
+	push_components(&path, &mut out, &mut todo);
+
+	let mut links = 0;
+	while let Some(c) = todo.pop() {
+		match c.to_str() {
+			Some(".") => continue,
+			Some("..") => {
+				out.pop();
+				continue;
+			}
+			_ => out.push(&c),
+		}
+
+		let meta = match Local::symlink_metadata(&out).await {
+			Ok(m) => m,
+			Err(e) if matches!(e.kind(), io::ErrorKind::NotFound | io::ErrorKind::NotADirectory) => {
+				continue;
+			}
+			Err(e) => return Err(e),
+		};
+		if !meta.is_symlink() {
+			continue;
+		}
+
+		links += 1;
+		if links > MAX_SYMLINKS {
+			return Err(symlink_loop());
+		}
+
+		let target = Local::read_link(&out).await?;
+		out.pop();
+		push_components(&target, &mut out, &mut todo);
+	}
+
+	Ok(out)
+}
+
+/// Same as [`realpath_missing`], but for a [`Url`].
+pub async fn realpath_missing_url(url: &Url) -> io::Result<Url> {
+	let Some(path) = url.as_path() else {
+		return Err(io::Error::new(io::ErrorKind::Unsupported, "Unsupported filesystem"));
+	};
+
+	let real = realpath_missing(path).await?;
+	let base = url.loc.base();
+	Ok(match url.scheme {
+		Scheme::Search(_)
+		| Scheme::Grep(_)
+		| Scheme::Recent
+		| Scheme::Frecency
+		| Scheme::Dupes
+		| Scheme::Trash
+			if real.starts_with(base) =>
+		{
+			Url { loc: Loc::new(&real, base, base), scheme: url.scheme.clone() }
+		}
+		Scheme::Archive(_) if real.starts_with(base) => {
+			Url { loc: Loc::floated(&real, base), scheme: url.scheme.clone() }
+		}
+		_ => real.into(),
+	})
+}
+
+/// Pushes the components of `path` to `todo` to be resolved.
+fn push_components(path: &Path, out: &mut PathBuf, todo: &mut Vec<OsString>) {
+	let mut it = path.components().peekable();
+	if path.has_root() {
+		out.clear();
+	}
+	while let Some(c @ (Component::Prefix(_) | Component::RootDir)) = it.peek() {
+		out.push(c);
+		it.next();
+	}
+
+	let rest: Vec<_> = it.map(|c| c.as_os_str().to_owned()).collect();
+	todo.extend(rest.into_iter().rev());
+}
+
+fn symlink_loop() -> io::Error {
+	#[cfg(unix)]
+	{
+		io::Error::from_raw_os_error(libc::ELOOP)
+	}
+	#[cfg(windows)]
+	{
+		io::Error::from_raw_os_error(windows_sys::Win32::Foundation::ERROR_CANT_RESOLVE_FILENAME as _)
+	}
+}
+
+#[cfg(unix)]
+#[cfg(test)]
+mod tests {
+	use std::{fs, os::unix::fs::symlink};
+
+	use super::*;
+	use crate::TempDir;
+
+	#[tokio::test]
+	async fn test_realpath_missing() -> io::Result<()> {
+		let root = TempDir::new("realpath")?;
+		fs::create_dir_all(root.join("real/sub"))?;
+		let root = root.canonicalize()?;
+
+		symlink(root.join("real/sub"), root.join("abs"))?;
+		symlink("real/sub", root.join("rel"))?;
+		symlink("nowhere/deeper", root.join("dangling"))?;
+		symlink("loop-b", root.join("loop-a"))?;
+		symlink("loop-a", root.join("loop-b"))?;
+
+		let real = async |p: &str| realpath_missing(root.join(p)).await;
+
+		// Missing trailing components
+		assert_eq!(real("real/new/name").await?, root.join("real/new/name"));
+		assert_eq!(real("real/./new/../name").await?, root.join("real/name"));
+
+		// `..` climbs out of the symlinked directory, not the symlink itself
+		assert_eq!(real("abs/../x").await?, root.join("real/x"));
+		assert_eq!(real("rel/../../newdir/newname").await?, root.join("newdir/newname"));
+
+		// A dangling symlink in the prefix
+		assert_eq!(real("dangling/x").await?, root.join("nowhere/deeper/x"));
+
+		// Symlink loops
+		assert_eq!(real("loop-a/x").await.unwrap_err().raw_os_error(), Some(libc::ELOOP));
+
+		// Keeps the scheme
+		let url: Url = format!("search://kw/{}", root.display()).parse().unwrap();
+		let url = realpath_missing_url(&url.join("rel/new")).await?;
+		assert!(url.is_search());
+		assert_eq!(url.as_path(), Some(root.join("real/sub/new").as_path()));
+
+		Ok(())
+	}
+}
@@ -0,0 +1,56 @@
+use std::sync::Arc;
+
+use parking_lot::{Condvar, Mutex};
+
+/// Pauses a transfer cooperatively, between the chunks of the copies.
+#[derive(Clone, Debug, Default)]
+pub struct Pause(Arc<(Mutex<bool>, Condvar)>);
+
+impl Pause {
+	#[inline]
+	pub fn paused(&self) -> bool { *self.0.0.lock() }
+
+	/// Pauses or resumes it, which wakes up the ones waiting on it for the latter.
+	pub fn set(&self, paused: bool) {
+		*self.0.0.lock() = paused;
+		if !paused {
+			self.0.1.notify_all();
+		}
+	}
+
+	/// Blocks the current thread as long as it's paused.
+	pub fn wait(&self) {
+		let mut paused = self.0.0.lock();
+		while *paused {
+			self.0.1.wait(&mut paused);
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::{thread, time::{Duration, Instant}};
+
+	use super::*;
+
+	#[test]
+	fn test_pause() {
+		let pause = Pause::default();
+		pause.wait();
+
+		pause.set(true);
+		let handle = thread::spawn({
+			let pause = pause.clone();
+			move || {
+				let now = Instant::now();
+				pause.wait();
+				now.elapsed()
+			}
+		});
+
+		thread::sleep(Duration::from_millis(100));
+		assert!(pause.paused());
+		pause.set(false);
+		assert!(handle.join().unwrap() >= Duration::from_millis(100));
+	}
+}
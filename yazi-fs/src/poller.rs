@@ -0,0 +1,89 @@
+use std::{collections::{HashMap, HashSet}, hash::{DefaultHasher, Hash, Hasher}, io, time::Duration};
+
+use tokio::sync::watch;
+use yazi_shared::url::Url;
+
+use crate::{File, FilesOp, cha::Cha, provider::{self, ReadDirOpt}};
+
+/// Watches the directories that can't be watched natively by polling them.
+pub struct Poller {
+	interval: Duration,
+	etags:    HashMap<Url, u64>,
+}
+
+impl Poller {
+	pub fn new(interval: Duration) -> Self { Self { interval, etags: Default::default() } }
+
+	/// Polls the directories received until the sender is dropped.
+	pub async fn serve(mut self, mut rx: watch::Receiver<HashSet<Url>>) {
+		if self.interval.is_zero() {
+			return;
+		}
+
+		loop {
+			tokio::time::sleep(self.interval).await;
+			if rx.has_changed().is_err() {
+				break;
+			}
+
+			let dirs = rx.borrow_and_update().clone();
+			self.etags.retain(|u, _| dirs.contains(u));
+			for dir in dirs {
+				self.poll(dir).await;
+			}
+		}
+	}
+
+	// The first time only records the etag, since what's listed is up to date
+	async fn poll(&mut self, dir: Url) {
+		let Ok((files, cha)) = Self::list(&dir).await else { return };
+
+		let etag = Self::etag(&files);
+		if self.etags.insert(dir.clone(), etag).is_some_and(|old| old != etag) {
+			FilesOp::Full(dir, files, cha).emit();
+		}
+	}
+
+	async fn list(dir: &Url) -> io::Result<(Vec<File>, Cha)> {
+		let cha = Cha::from_url(dir).await?;
+		let mut rx = provider::read_dir_streamed(dir, ReadDirOpt::default()).await?;
+
+		let mut files = vec![];
+		while let Some(chunk) = rx.recv().await {
+			files.extend(chunk);
+		}
+		Ok((files, cha))
+	}
+
+	fn etag(files: &[File]) -> u64 {
+		let mut tags: Vec<_> = files.iter().map(|f| (f.url.file_name(), f.len, f.mtime)).collect();
+		tags.sort_unstable();
+
+		let mut h = DefaultHasher::new();
+		tags.hash(&mut h);
+		h.finish()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::{path::Path, time::SystemTime};
+
+	use super::*;
+
+	#[test]
+	fn test_etag() {
+		let file = |name: &str, len: u64| File {
+			url: Url::from(Path::new("/remote").join(name)),
+			cha: Cha { len, mtime: Some(SystemTime::UNIX_EPOCH), ..Default::default() },
+			link_to: None,
+		};
+
+		let etag = Poller::etag(&[file("a", 1), file("b", 2)]);
+		assert_eq!(etag, Poller::etag(&[file("b", 2), file("a", 1)]));
+
+		assert_ne!(etag, Poller::etag(&[file("a", 1)]));
+		assert_ne!(etag, Poller::etag(&[file("a", 1), file("b", 3)]));
+		assert_ne!(etag, Poller::etag(&[file("a", 1), file("c", 2)]));
+	}
+}
@@ -1,7 +1,7 @@
 // --- BufRead
-pub trait BufRead: tokio::io::AsyncRead + Send {}
+pub trait BufRead: tokio::io::AsyncBufRead + tokio::io::AsyncSeek + Send + Unpin {}
 
-impl<T: tokio::io::AsyncRead + Send> BufRead for T {}
+impl<T: tokio::io::AsyncBufRead + tokio::io::AsyncSeek + Send + Unpin> BufRead for T {}
 
 // --- BufReadSync
 pub trait BufReadSync: std::io::BufRead + std::io::Seek + Send {}
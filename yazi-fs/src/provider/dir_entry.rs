@@ -4,6 +4,7 @@ use yazi_shared::url::Url;
 
 pub enum DirEntry {
 	Local(super::local::DirEntry),
+	Mtp(super::mtp::DirEntry),
 }
 
 impl DirEntry {
@@ -11,6 +12,7 @@ impl DirEntry {
 	pub fn url(&self) -> Url {
 		match self {
 			DirEntry::Local(local) => local.url(),
+			DirEntry::Mtp(mtp) => mtp.url(),
 		}
 	}
 
@@ -18,18 +20,21 @@ impl DirEntry {
 	pub fn file_name(&self) -> OsString {
 		match self {
 			DirEntry::Local(local) => local.file_name(),
+			DirEntry::Mtp(mtp) => mtp.file_name(),
 		}
 	}
 
 	pub async fn metadata(&self) -> io::Result<std::fs::Metadata> {
 		match self {
 			DirEntry::Local(local) => local.metadata().await,
+			DirEntry::Mtp(mtp) => mtp.metadata().await,
 		}
 	}
 
 	pub async fn file_type(&self) -> io::Result<std::fs::FileType> {
 		match self {
 			DirEntry::Local(local) => local.file_type().await,
+			DirEntry::Mtp(mtp) => mtp.file_type().await,
 		}
 	}
 }
@@ -37,6 +42,7 @@ impl DirEntry {
 // --- DirEntrySync
 pub enum DirEntrySync {
 	Local(super::local::DirEntrySync),
+	Mtp(super::mtp::DirEntrySync),
 }
 
 impl DirEntrySync {
@@ -44,6 +50,7 @@ impl DirEntrySync {
 	pub fn url(&self) -> Url {
 		match self {
 			DirEntrySync::Local(local) => local.url(),
+			DirEntrySync::Mtp(mtp) => mtp.url(),
 		}
 	}
 
@@ -51,18 +58,21 @@ impl DirEntrySync {
 	pub fn file_name(&self) -> OsString {
 		match self {
 			DirEntrySync::Local(local) => local.file_name(),
+			DirEntrySync::Mtp(mtp) => mtp.file_name(),
 		}
 	}
 
 	pub fn metadata(&self) -> io::Result<std::fs::Metadata> {
 		match self {
 			DirEntrySync::Local(local) => local.metadata(),
+			DirEntrySync::Mtp(mtp) => mtp.metadata(),
 		}
 	}
 
 	pub fn file_type(&self) -> io::Result<std::fs::FileType> {
 		match self {
 			DirEntrySync::Local(local) => local.file_type(),
+			DirEntrySync::Mtp(mtp) => mtp.file_type(),
 		}
 	}
 }
@@ -1,6 +1,8 @@
 use std::{io, path::{Path, PathBuf}};
 
-use crate::provider::local::{Gate, ReadDir, ReadDirSync, RwFile};
+use tokio::sync::mpsc::UnboundedReceiver;
+
+use crate::{File, provider::{ReadDirOpt, local::{Gate, ReadDir, ReadDirSync, RwFile}}};
 
 pub struct Local;
 
@@ -48,6 +50,14 @@ impl Local {
 		tokio::fs::read_dir(path).await.map(Into::into)
 	}
 
+	#[inline]
+	pub async fn read_dir_streamed(
+		path: impl AsRef<Path>,
+		opt: ReadDirOpt,
+	) -> io::Result<UnboundedReceiver<Vec<File>>> {
+		Ok(crate::provider::ReadDir::from(Self::read_dir(path).await?).stream(opt))
+	}
+
 	#[inline]
 	pub fn read_dir_sync(path: impl AsRef<Path>) -> io::Result<ReadDirSync> {
 		std::fs::read_dir(path).map(Into::into)
@@ -83,6 +93,22 @@ impl Local {
 		tokio::fs::rename(from, to).await
 	}
 
+	#[inline]
+	pub async fn set_permissions(path: impl AsRef<Path>, perm: std::fs::Permissions) -> io::Result<()> {
+		tokio::fs::set_permissions(path, perm).await
+	}
+
+	/// Changes the owner and group of the file without following symlinks.
+	#[cfg(unix)]
+	pub async fn lchown(
+		path: impl AsRef<Path>,
+		uid: Option<u32>,
+		gid: Option<u32>,
+	) -> io::Result<()> {
+		let path = path.as_ref().to_owned();
+		tokio::task::spawn_blocking(move || std::os::unix::fs::lchown(path, uid, gid)).await?
+	}
+
 	#[inline]
 	pub async fn symlink_dir(original: impl AsRef<Path>, link: impl AsRef<Path>) -> io::Result<()> {
 		#[cfg(unix)]
@@ -122,3 +148,44 @@ impl Local {
 		tokio::fs::write(path, contents).await
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use std::{fs, time::Duration};
+
+	use super::*;
+	use crate::{FilesSorter, SortBy, TempDir};
+
+	#[tokio::test]
+	async fn test_read_dir_streamed() -> io::Result<()> {
+		let dir = TempDir::new("read-dir")?;
+		for i in 0..100_000 {
+			fs::write(dir.join(format!("{}{i}", if i % 10 == 0 { "." } else { "" })), "")?;
+		}
+
+		// Only full batches are sent before the end, as the timeout never passes
+		let sorter = FilesSorter { by: SortBy::Natural, ..Default::default() };
+		let opt = ReadDirOpt {
+			batch:   1000,
+			timeout: Duration::from_secs(3600),
+			hidden:  false,
+			sorter:  Some(sorter),
+		};
+		let mut rx = Local::read_dir_streamed(&dir, opt).await?;
+
+		let first = rx.recv().await.unwrap();
+		assert!(!rx.is_closed(), "the first batch should arrive before the enumeration completes");
+
+		let (mut items, mut next) = (vec![], Some(first));
+		while let Some(batch) = next {
+			assert_eq!(batch.len(), 1000);
+			assert!(batch.is_sorted_by_key(|f| f.name().to_str().unwrap().parse::<u32>().unwrap()));
+			sorter.merge(&mut items, batch);
+			next = rx.recv().await;
+		}
+		assert_eq!(items.len(), 90_000);
+		assert!(items.is_sorted_by_key(|f| f.name().to_str().unwrap().parse::<u32>().unwrap()));
+
+		Ok(())
+	}
+}
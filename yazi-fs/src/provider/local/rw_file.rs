@@ -1,3 +1,7 @@
+use std::io::{self, SeekFrom};
+
+use tokio::io::AsyncSeekExt;
+
 pub struct RwFile(tokio::fs::File);
 
 impl From<tokio::fs::File> for RwFile {
@@ -16,6 +20,15 @@ impl RwFile {
 	#[inline]
 	pub fn reader(self) -> tokio::io::BufReader<tokio::fs::File> { tokio::io::BufReader::new(self.0) }
 
+	#[inline]
+	pub async fn reader_at(
+		mut self,
+		offset: u64,
+	) -> io::Result<tokio::io::BufReader<tokio::fs::File>> {
+		self.0.seek(SeekFrom::Start(offset)).await?;
+		Ok(self.reader())
+	}
+
 	#[inline]
 	pub async fn reader_sync(self) -> std::io::BufReader<std::fs::File> {
 		std::io::BufReader::new(self.0.into_std().await)
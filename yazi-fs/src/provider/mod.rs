@@ -1,3 +1,3 @@
-yazi_macro::mod_pub!(local);
+yazi_macro::mod_pub!(local mtp vfs);
 
 yazi_macro::mod_flat!(buffer dir_entry provider read_dir rw_file);
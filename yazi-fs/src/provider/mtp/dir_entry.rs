@@ -0,0 +1,57 @@
+use std::ops::Deref;
+
+use yazi_shared::url::Url;
+
+use crate::provider::local;
+
+/// An entry of a directory on the device, named by its URL.
+pub struct DirEntry {
+	inner: local::DirEntry,
+	url:   Url,
+}
+
+impl Deref for DirEntry {
+	type Target = local::DirEntry;
+
+	fn deref(&self) -> &Self::Target { &self.inner }
+}
+
+impl From<DirEntry> for crate::provider::DirEntry {
+	fn from(value: DirEntry) -> Self { crate::provider::DirEntry::Mtp(value) }
+}
+
+impl DirEntry {
+	pub(super) fn new(inner: local::DirEntry, dir: &Url) -> Self {
+		let url = dir.join(inner.file_name());
+		Self { inner, url }
+	}
+
+	#[must_use]
+	pub fn url(&self) -> Url { self.url.clone() }
+}
+
+// --- DirEntrySync
+pub struct DirEntrySync {
+	inner: local::DirEntrySync,
+	url:   Url,
+}
+
+impl Deref for DirEntrySync {
+	type Target = local::DirEntrySync;
+
+	fn deref(&self) -> &Self::Target { &self.inner }
+}
+
+impl From<DirEntrySync> for crate::provider::DirEntrySync {
+	fn from(value: DirEntrySync) -> Self { crate::provider::DirEntrySync::Mtp(value) }
+}
+
+impl DirEntrySync {
+	pub(super) fn new(inner: local::DirEntrySync, dir: &Url) -> Self {
+		let url = dir.join(inner.file_name());
+		Self { inner, url }
+	}
+
+	#[must_use]
+	pub fn url(&self) -> Url { self.url.clone() }
+}
@@ -0,0 +1 @@
+yazi_macro::mod_flat!(dir_entry mtp read_dir);
@@ -0,0 +1,105 @@
+use std::{env, io, path::{Path, PathBuf}};
+
+use tokio::{process::Command, sync::Mutex};
+use yazi_shared::url::{Scheme, Url};
+
+/// Phones and cameras over MTP or PTP, accessed through where GVfs mounts them.
+pub struct Mtp;
+
+impl Mtp {
+	// The backends of GVfs a device can be mounted with, in the order they're tried
+	const BACKENDS: [&str; 2] = ["mtp", "gphoto2"];
+
+	/// The local path of the file on the device, once it's mounted.
+	pub fn path(url: &Url) -> Option<PathBuf> {
+		let Scheme::Mtp(host) = &url.scheme else { return None };
+		Some(Self::path_in(&Self::runtime()?, host, url))
+	}
+
+	/// Maps a local path under the mount of the device back to a URL.
+	pub fn url(base: &Url, path: &Path) -> Option<Url> {
+		let Scheme::Mtp(host) = &base.scheme else { return None };
+		Self::url_in(&Self::runtime()?, host, base, path)
+	}
+
+	/// Mounts the device of the URL with `gio`, unless it's already mounted.
+	pub async fn mount(url: &Url) -> io::Result<()> {
+		static MOUNTING: Mutex<()> = Mutex::const_new(());
+
+		let Scheme::Mtp(host) = &url.scheme else { return Ok(()) };
+		let Some(runtime) = Self::runtime() else {
+			return Err(io::Error::new(io::ErrorKind::Unsupported, "GVfs is not available"));
+		};
+
+		let roots = Self::roots(&runtime, host);
+		let _guard = MOUNTING.lock().await;
+		if roots.iter().any(|r| r.exists()) {
+			return Ok(());
+		}
+
+		let mut err = String::new();
+		for backend in Self::BACKENDS {
+			let output = Command::new("gio")
+				.args(["mount", &format!("{backend}://{host}/")])
+				.kill_on_drop(true)
+				.output()
+				.await?;
+			if output.status.success() {
+				return Ok(());
+			}
+			err = String::from_utf8_lossy(&output.stderr).trim().to_owned();
+		}
+		Err(io::Error::other(format!("Failed to mount `{host}`: {err}")))
+	}
+
+	fn path_in(runtime: &Path, host: &str, url: &Url) -> PathBuf {
+		let rel = url.loc.strip_prefix("/").unwrap_or(&url.loc);
+
+		let roots = Self::roots(runtime, host);
+		let root = roots.iter().find(|r| r.exists()).unwrap_or(&roots[0]);
+		root.join(rel)
+	}
+
+	fn url_in(runtime: &Path, host: &str, base: &Url, path: &Path) -> Option<Url> {
+		let rel = Self::roots(runtime, host).into_iter().find_map(|r| path.strip_prefix(r).ok())?;
+		Some(Url { loc: Path::new("/").join(rel).into(), scheme: base.scheme.clone() })
+	}
+
+	// Where GVfs mounts the devices under
+	fn runtime() -> Option<PathBuf> {
+		let runtime = env::var_os("XDG_RUNTIME_DIR").map(PathBuf::from).filter(|p| p.is_absolute());
+		#[cfg(unix)]
+		let runtime = runtime.or_else(|| {
+			use uzers::Users;
+			Some(format!("/run/user/{}", yazi_shared::USERS_CACHE.get_current_uid()).into())
+		});
+		runtime
+	}
+
+	fn roots(runtime: &Path, host: &str) -> [PathBuf; 2] {
+		let gvfs = runtime.join("gvfs");
+		Self::BACKENDS.map(|b| gvfs.join(format!("{b}:host={host}")))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[cfg(unix)]
+	#[test]
+	fn test_path() {
+		let runtime = Path::new("/run/user/1000");
+		let url: Url = "mtp://phone//DCIM/a.jpg".parse().unwrap();
+
+		let path = Mtp::path_in(runtime, "phone", &url);
+		assert_eq!(path, Path::new("/run/user/1000/gvfs/mtp:host=phone/DCIM/a.jpg"));
+		assert_eq!(Mtp::url_in(runtime, "phone", &url, &path), Some(url.clone()));
+
+		// Cameras mounted with the PTP backend
+		let path = Path::new("/run/user/1000/gvfs/gphoto2:host=phone/DCIM/a.jpg");
+		assert_eq!(Mtp::url_in(runtime, "phone", &url, path), Some(url));
+
+		assert_eq!(Mtp::path(&Url::from(Path::new("/DCIM"))), None);
+	}
+}
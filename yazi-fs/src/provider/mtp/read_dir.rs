@@ -0,0 +1,49 @@
+use std::io;
+
+use yazi_shared::url::Url;
+
+use super::{DirEntry, DirEntrySync};
+
+pub struct ReadDir {
+	inner: crate::provider::local::ReadDir,
+	dir:   Url,
+}
+
+impl From<ReadDir> for crate::provider::ReadDir {
+	fn from(value: ReadDir) -> Self { crate::provider::ReadDir::Mtp(value) }
+}
+
+impl ReadDir {
+	pub(crate) fn new(inner: crate::provider::local::ReadDir, dir: Url) -> Self {
+		Self { inner, dir }
+	}
+
+	pub async fn next_entry(&mut self) -> io::Result<Option<DirEntry>> {
+		let entry = self.inner.next_entry().await?;
+		Ok(entry.map(|e| DirEntry::new(e, &self.dir)))
+	}
+}
+
+// --- ReadDirSync
+pub struct ReadDirSync {
+	inner: crate::provider::local::ReadDirSync,
+	dir:   Url,
+}
+
+impl From<ReadDirSync> for crate::provider::ReadDirSync {
+	fn from(value: ReadDirSync) -> Self { crate::provider::ReadDirSync::Mtp(value) }
+}
+
+impl ReadDirSync {
+	pub(crate) fn new(inner: crate::provider::local::ReadDirSync, dir: Url) -> Self {
+		Self { inner, dir }
+	}
+}
+
+impl Iterator for ReadDirSync {
+	type Item = io::Result<DirEntrySync>;
+
+	fn next(&mut self) -> Option<io::Result<DirEntrySync>> {
+		self.inner.next().map(|result| result.map(|e| DirEntrySync::new(e, &self.dir)))
+	}
+}
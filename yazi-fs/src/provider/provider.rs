@@ -1,175 +1,179 @@
-use std::io;
+use std::{borrow::Cow, io, path::{Path, PathBuf}};
 
-use yazi_shared::url::Url;
+use tokio::{io::AsyncReadExt, sync::mpsc::UnboundedReceiver};
+use yazi_shared::url::{Scheme, Url};
 
-use crate::provider::{ReadDir, ReadDirSync, RwFile, local::Local};
+use crate::{File, provider::{ReadDir, ReadDirOpt, ReadDirSync, RwFile, local::Local, mtp::{self, Mtp}, vfs::Vfs}};
 
 #[inline]
 pub async fn canonicalize(url: impl AsRef<Url>) -> io::Result<Url> {
-	if let Some(path) = url.as_ref().as_path() {
-		Local::canonicalize(path).await.map(Into::into)
-	} else {
-		Err(io::Error::new(io::ErrorKind::Unsupported, "Unsupported filesystem"))
-	}
+	let url = url.as_ref();
+	Local::canonicalize(local_path(url).await?).await.map(|p| from_local(url, p))
 }
 
 #[inline]
 pub async fn create(url: impl AsRef<Url>) -> io::Result<RwFile> {
-	if let Some(path) = url.as_ref().as_path() {
-		Local::create(path).await.map(Into::into)
-	} else {
-		Err(io::Error::new(io::ErrorKind::Unsupported, "Unsupported filesystem"))
-	}
+	Local::create(local_path_mut(url.as_ref()).await?).await.map(Into::into)
 }
 
 #[inline]
 pub async fn create_dir(url: impl AsRef<Url>) -> io::Result<()> {
-	if let Some(path) = url.as_ref().as_path() {
-		Local::create_dir(path).await
-	} else {
-		Err(io::Error::new(io::ErrorKind::Unsupported, "Unsupported filesystem"))
-	}
+	Local::create_dir(local_path_mut(url.as_ref()).await?).await
 }
 
 #[inline]
 pub async fn create_dir_all(url: impl AsRef<Url>) -> io::Result<()> {
-	if let Some(path) = url.as_ref().as_path() {
-		Local::create_dir_all(path).await
-	} else {
-		Err(io::Error::new(io::ErrorKind::Unsupported, "Unsupported filesystem"))
-	}
+	Local::create_dir_all(local_path_mut(url.as_ref()).await?).await
 }
 
 #[inline]
 pub async fn hard_link(original: impl AsRef<Url>, link: impl AsRef<Url>) -> io::Result<()> {
-	if let (Some(original), Some(link)) = (original.as_ref().as_path(), link.as_ref().as_path()) {
-		Local::hard_link(original, link).await
-	} else {
-		Err(io::Error::new(io::ErrorKind::Unsupported, "Unsupported filesystem"))
-	}
+	Local::hard_link(local_path(original.as_ref()).await?, local_path_mut(link.as_ref()).await?).await
 }
 
 #[inline]
 pub async fn metadata(url: impl AsRef<Url>) -> io::Result<std::fs::Metadata> {
-	if let Some(path) = url.as_ref().as_path() {
-		Local::metadata(path).await
-	} else {
-		Err(io::Error::new(io::ErrorKind::Unsupported, "Unsupported filesystem"))
-	}
+	let url = url.as_ref();
+	Vfs::stat(url).await?;
+	Local::metadata(local_path(url).await?).await
 }
 
 #[inline]
 pub async fn open(url: impl AsRef<Url>) -> io::Result<RwFile> {
-	if let Some(path) = url.as_ref().as_path() {
-		Local::open(path).await.map(Into::into)
-	} else {
-		Err(io::Error::new(io::ErrorKind::Unsupported, "Unsupported filesystem"))
-	}
+	let url = url.as_ref();
+	Vfs::fetch(url).await?;
+	Local::open(local_path(url).await?).await.map(Into::into)
+}
+
+/// Reads at most `len` bytes of the file from `offset`.
+pub async fn read_range(url: impl AsRef<Url>, offset: u64, len: u64) -> io::Result<Vec<u8>> {
+	let mut buf = Vec::with_capacity(len.min(64 * 1024) as usize);
+	open(url).await?.reader_at(offset).await?.take(len).read_to_end(&mut buf).await?;
+	Ok(buf)
 }
 
 #[inline]
 pub async fn read_dir(url: impl AsRef<Url>) -> io::Result<ReadDir> {
-	if let Some(path) = url.as_ref().as_path() {
-		Local::read_dir(path).await.map(Into::into)
-	} else {
-		Err(io::Error::new(io::ErrorKind::Unsupported, "Unsupported filesystem"))
-	}
+	let url = url.as_ref();
+	Vfs::list(url).await?;
+	let it = Local::read_dir(local_path(url).await?).await?;
+	Ok(if url.as_path().is_some() { it.into() } else { mtp::ReadDir::new(it, url.clone()).into() })
+}
+
+#[inline]
+pub async fn read_dir_streamed(
+	url: impl AsRef<Url>,
+	opt: ReadDirOpt,
+) -> io::Result<UnboundedReceiver<Vec<File>>> {
+	Ok(read_dir(url).await?.stream(opt))
 }
 
 #[inline]
 pub fn read_dir_sync(url: impl AsRef<Url>) -> io::Result<ReadDirSync> {
-	if let Some(path) = url.as_ref().as_path() {
-		Local::read_dir_sync(path).map(Into::into)
+	let url = url.as_ref();
+	let it = Local::read_dir_sync(local_path_sync(url)?)?;
+	Ok(if url.as_path().is_some() {
+		it.into()
 	} else {
-		Err(io::Error::new(io::ErrorKind::Unsupported, "Unsupported filesystem"))
-	}
+		mtp::ReadDirSync::new(it, url.clone()).into()
+	})
 }
 
 #[inline]
 pub async fn read_link(url: impl AsRef<Url>) -> io::Result<Url> {
-	if let Some(path) = url.as_ref().as_path() {
-		Local::read_link(path).await.map(Into::into)
-	} else {
-		Err(io::Error::new(io::ErrorKind::Unsupported, "Unsupported filesystem"))
-	}
+	let url = url.as_ref();
+	Local::read_link(local_path(url).await?).await.map(|p| from_local(url, p))
 }
 
 #[inline]
 pub async fn remove_dir(url: impl AsRef<Url>) -> io::Result<()> {
-	if let Some(path) = url.as_ref().as_path() {
-		Local::remove_dir(path).await
-	} else {
-		Err(io::Error::new(io::ErrorKind::Unsupported, "Unsupported filesystem"))
-	}
+	Local::remove_dir(local_path_mut(url.as_ref()).await?).await
 }
 
 #[inline]
 pub async fn remove_dir_all(url: impl AsRef<Url>) -> io::Result<()> {
-	if let Some(path) = url.as_ref().as_path() {
-		Local::remove_dir_all(path).await
-	} else {
-		Err(io::Error::new(io::ErrorKind::Unsupported, "Unsupported filesystem"))
-	}
+	Local::remove_dir_all(local_path_mut(url.as_ref()).await?).await
 }
 
 #[inline]
 pub async fn remove_file(url: impl AsRef<Url>) -> io::Result<()> {
-	if let Some(path) = url.as_ref().as_path() {
-		Local::remove_file(path).await
-	} else {
-		Err(io::Error::new(io::ErrorKind::Unsupported, "Unsupported filesystem"))
-	}
+	Local::remove_file(local_path_mut(url.as_ref()).await?).await
 }
 
 #[inline]
 pub async fn rename(from: impl AsRef<Url>, to: impl AsRef<Url>) -> io::Result<()> {
-	if let (Some(from), Some(to)) = (from.as_ref().as_path(), to.as_ref().as_path()) {
-		Local::rename(from, to).await
-	} else {
-		Err(io::Error::new(io::ErrorKind::Unsupported, "Unsupported filesystem"))
-	}
+	Local::rename(local_path_mut(from.as_ref()).await?, local_path_mut(to.as_ref()).await?).await
+}
+
+#[inline]
+pub async fn set_permissions(url: impl AsRef<Url>, perm: std::fs::Permissions) -> io::Result<()> {
+	Local::set_permissions(local_path_mut(url.as_ref()).await?, perm).await
+}
+
+#[cfg(unix)]
+#[inline]
+pub async fn lchown(url: impl AsRef<Url>, uid: Option<u32>, gid: Option<u32>) -> io::Result<()> {
+	Local::lchown(local_path_mut(url.as_ref()).await?, uid, gid).await
 }
 
 #[inline]
 pub async fn symlink_dir(original: impl AsRef<Url>, link: impl AsRef<Url>) -> io::Result<()> {
-	if let (Some(original), Some(link)) = (original.as_ref().as_path(), link.as_ref().as_path()) {
-		Local::symlink_dir(original, link).await
-	} else {
-		Err(io::Error::new(io::ErrorKind::Unsupported, "Unsupported filesystem"))
-	}
+	Local::symlink_dir(local_path(original.as_ref()).await?, local_path_mut(link.as_ref()).await?)
+		.await
 }
 
 #[inline]
 pub async fn symlink_file(original: impl AsRef<Url>, link: impl AsRef<Url>) -> io::Result<()> {
-	if let (Some(original), Some(link)) = (original.as_ref().as_path(), link.as_ref().as_path()) {
-		Local::symlink_file(original, link).await
-	} else {
-		Err(io::Error::new(io::ErrorKind::Unsupported, "Unsupported filesystem"))
-	}
+	Local::symlink_file(local_path(original.as_ref()).await?, local_path_mut(link.as_ref()).await?)
+		.await
 }
 
 #[inline]
 pub async fn symlink_metadata(url: impl AsRef<Url>) -> io::Result<std::fs::Metadata> {
-	if let Some(path) = url.as_ref().as_path() {
-		Local::symlink_metadata(path).await
-	} else {
-		Err(io::Error::new(io::ErrorKind::Unsupported, "Unsupported filesystem"))
-	}
+	let url = url.as_ref();
+	Vfs::stat(url).await?;
+	Local::symlink_metadata(local_path(url).await?).await
 }
 
 pub fn symlink_metadata_sync(url: impl AsRef<Url>) -> io::Result<std::fs::Metadata> {
-	if let Some(path) = url.as_ref().as_path() {
-		Local::symlink_metadata_sync(path)
-	} else {
-		Err(io::Error::new(io::ErrorKind::Unsupported, "Unsupported filesystem"))
-	}
+	Local::symlink_metadata_sync(local_path_sync(url.as_ref())?)
 }
 
 #[inline]
 pub async fn write(url: impl AsRef<Url>, contents: impl AsRef<[u8]>) -> io::Result<()> {
-	if let Some(path) = url.as_ref().as_path() {
-		Local::write(path, contents).await
+	let url = url.as_ref();
+	Local::write(local_path(url).await?, contents).await?;
+	Vfs::push(url).await
+}
+
+/// The path on the local filesystem the URL is accessed through.
+pub async fn local_path(url: &Url) -> io::Result<Cow<'_, Path>> {
+	if url.as_path().is_none() {
+		Mtp::mount(url).await?;
+	}
+	local_path_sync(url)
+}
+
+/// Same as [`local_path`], but for changes other than writing files.
+async fn local_path_mut(url: &Url) -> io::Result<Cow<'_, Path>> {
+	if matches!(url.scheme, Scheme::Vfs(_)) {
+		return Err(io::Error::new(io::ErrorKind::Unsupported, "Unsupported by the plugin filesystem"));
+	}
+	local_path(url).await
+}
+
+/// Same as [`local_path`], but without mounting the device of MTP URLs.
+pub fn local_path_sync(url: &Url) -> io::Result<Cow<'_, Path>> {
+	if let Some(path) = url.as_path() {
+		Ok(Cow::Borrowed(path))
+	} else if let Some(path) = Mtp::path(url).or_else(|| Vfs::path(url)) {
+		Ok(Cow::Owned(path))
 	} else {
 		Err(io::Error::new(io::ErrorKind::Unsupported, "Unsupported filesystem"))
 	}
 }
+
+// Maps a path returned for the URL back into the scheme of the URL
+fn from_local(url: &Url, path: PathBuf) -> Url {
+	Mtp::url(url, &path).or_else(|| Vfs::url(url, &path)).unwrap_or_else(|| path.into())
+}
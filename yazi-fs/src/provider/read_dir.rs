@@ -1,22 +1,72 @@
-use std::io;
+use std::{io, time::Duration};
+
+use futures::{StreamExt, future, stream};
+use tokio::{pin, select, sync::mpsc::{self, UnboundedReceiver}};
 
 use super::{DirEntry, DirEntrySync};
+use crate::{File, FilesSorter};
 
 pub enum ReadDir {
 	Local(super::local::ReadDir),
+	Mtp(super::mtp::ReadDir),
 }
 
 impl ReadDir {
+	// The entries stat'ed at once
+	const STATS: usize = 3;
+
 	pub async fn next_entry(&mut self) -> io::Result<Option<DirEntry>> {
 		match self {
 			ReadDir::Local(local) => local.next_entry().await.map(|entry| entry.map(Into::into)),
+			ReadDir::Mtp(mtp) => mtp.next_entry().await.map(|entry| entry.map(Into::into)),
 		}
 	}
+
+	/// Reads the entries in the background, sending them in batches.
+	pub fn stream(self, opt: ReadDirOpt) -> UnboundedReceiver<Vec<File>> {
+		let (tx, rx) = mpsc::unbounded_channel();
+
+		tokio::spawn(async move {
+			let entries = stream::unfold(self, |mut it| async move {
+				let entry = it.next_entry().await.ok().flatten()?;
+				Some((entry, it))
+			});
+
+			let files = entries
+				.map(|entry| async move {
+					let url = entry.url();
+					match entry.metadata().await {
+						Ok(meta) => File::from_follow(url, meta).await,
+						Err(_) => File::from_dummy(url, entry.file_type().await.ok()),
+					}
+				})
+				.buffer_unordered(Self::STATS)
+				.filter(move |f| future::ready(opt.hidden || !f.is_hidden()));
+
+			let chunks = tokio_stream::StreamExt::chunks_timeout(files, opt.batch.max(1), opt.timeout);
+			pin!(chunks);
+			loop {
+				let chunk = select! {
+					_ = tx.closed() => break,
+					chunk = chunks.next() => chunk,
+				};
+				let Some(mut chunk) = chunk else { break };
+				if let Some(sorter) = opt.sorter {
+					sorter.sort(&mut chunk, &Default::default());
+				}
+				if tx.send(chunk).is_err() {
+					break;
+				}
+			}
+		});
+		rx
+	}
 }
 
 // --- ReadDirSync
 pub enum ReadDirSync {
 	Local(super::local::ReadDirSync),
+	Mtp(super::mtp::ReadDirSync),
 }
 
 impl Iterator for ReadDirSync {
@@ -25,6 +75,23 @@ impl Iterator for ReadDirSync {
 	fn next(&mut self) -> Option<io::Result<DirEntrySync>> {
 		match self {
 			ReadDirSync::Local(local) => local.next().map(|result| result.map(Into::into)),
+			ReadDirSync::Mtp(mtp) => mtp.next().map(|result| result.map(Into::into)),
 		}
 	}
 }
+
+// --- ReadDirOpt
+#[derive(Clone, Copy, Debug)]
+pub struct ReadDirOpt {
+	pub batch:   usize,
+	pub timeout: Duration,
+	pub hidden:  bool,
+	// Sorts each batch, for the receiver to merge them as they come
+	pub sorter:  Option<FilesSorter>,
+}
+
+impl Default for ReadDirOpt {
+	fn default() -> Self {
+		Self { batch: 50000, timeout: Duration::from_millis(500), hidden: true, sorter: None }
+	}
+}
@@ -1,3 +1,5 @@
+use std::io;
+
 use crate::provider::{BufRead, BufReadSync};
 
 pub enum RwFile {
@@ -12,6 +14,14 @@ impl RwFile {
 		}
 	}
 
+	/// A reader starting at `offset`.
+	#[inline]
+	pub async fn reader_at(self, offset: u64) -> io::Result<Box<dyn BufRead>> {
+		Ok(match self {
+			RwFile::Local(local) => Box::new(local.reader_at(offset).await?),
+		})
+	}
+
 	#[inline]
 	pub async fn reader_sync(self) -> Box<dyn BufReadSync> {
 		match self {
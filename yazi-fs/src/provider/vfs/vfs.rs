@@ -0,0 +1,239 @@
+use std::{collections::{BTreeMap, BTreeSet, HashSet}, fs, io, path::{Path, PathBuf}, sync::OnceLock, time::SystemTime};
+
+use parking_lot::{Mutex, RwLock};
+use yazi_shared::url::{Scheme, Url};
+
+use crate::{File, Xdg, cha::Cha};
+
+static DRIVER: OnceLock<Box<dyn Driver>> = OnceLock::new();
+
+static PROVIDERS: RwLock<BTreeSet<String>> = RwLock::new(BTreeSet::new());
+
+static STAMPS: Mutex<BTreeMap<PathBuf, Stamp>> = Mutex::new(BTreeMap::new());
+
+/// The callbacks of the filesystems provided by plugins, on a blocking thread.
+pub trait Driver: Send + Sync {
+	/// The files in the directory.
+	fn list(&self, dir: &Url) -> io::Result<Vec<File>>;
+
+	/// The metadata of the file.
+	fn stat(&self, url: &Url) -> io::Result<Cha>;
+
+	/// Reads the content of the file into the local path.
+	fn read(&self, url: &Url, to: &Path) -> io::Result<()>;
+
+	/// Writes the content of the local path to the file.
+	fn write(&self, url: &Url, from: &Path) -> io::Result<()>;
+}
+
+/// Filesystems provided by plugins, accessed through a mirror in the cache directory.
+pub struct Vfs;
+
+// The size and mtime a file was listed with, and whether its content is read
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Stamp {
+	len:     u64,
+	mtime:   Option<SystemTime>,
+	fetched: bool,
+}
+
+impl Vfs {
+	/// Sets the driver the callbacks are called through, once at startup.
+	pub fn init(driver: impl Driver + 'static) { _ = DRIVER.set(Box::new(driver)); }
+
+	/// Registers the plugin as the provider of the `vfs://<name>` URLs.
+	pub fn register(name: &str) { PROVIDERS.write().insert(name.to_owned()); }
+
+	/// The path of the file in the mirror, if its provider is registered.
+	pub fn path(url: &Url) -> Option<PathBuf> {
+		let Scheme::Vfs(name) = &url.scheme else { return None };
+		if !PROVIDERS.read().contains(name) {
+			return None;
+		}
+
+		let rel = url.loc.strip_prefix("/").unwrap_or(&url.loc);
+		Some(Self::root(name).join(rel))
+	}
+
+	/// Maps a path in the mirror back to a URL.
+	pub fn url(base: &Url, path: &Path) -> Option<Url> {
+		let Scheme::Vfs(name) = &base.scheme else { return None };
+		let rel = path.strip_prefix(Self::root(name)).ok()?;
+		Some(Url { loc: Path::new("/").join(rel).into(), scheme: base.scheme.clone() })
+	}
+
+	/// Lists the directory with its provider, and updates the mirror of it.
+	pub async fn list(dir: &Url) -> io::Result<()> {
+		let Some(local) = Self::path(dir) else { return Ok(()) };
+		Self::run(dir, move |driver, dir| Self::mirror(&local, driver.list(&dir)?)).await
+	}
+
+	/// Stats the file with its provider, unless it's in the mirror already.
+	pub async fn stat(url: &Url) -> io::Result<()> {
+		let Some(local) = Self::path(url) else { return Ok(()) };
+		if fs::symlink_metadata(&local).is_ok() {
+			return Ok(());
+		}
+
+		Self::run(url, move |driver, url| {
+			let cha = driver.stat(&url)?;
+			if let Some(parent) = local.parent() {
+				fs::create_dir_all(parent)?;
+			}
+			Self::materialize(&local, &cha)
+		})
+		.await
+	}
+
+	/// Reads the file with its provider into the mirror, unless it's been read.
+	pub async fn fetch(url: &Url) -> io::Result<()> {
+		static FETCHING: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+		let Some(local) = Self::path(url) else { return Ok(()) };
+		Self::stat(url).await?;
+
+		let _guard = FETCHING.lock().await;
+		if local.is_dir() || STAMPS.lock().get(&local).is_some_and(|s| s.fetched) {
+			return Ok(());
+		}
+
+		Self::run(url, move |driver, url| {
+			driver.read(&url, &local)?;
+
+			// Keep the mtime it was listed with, so it's not seen as changed
+			let old = STAMPS.lock().get(&local).copied();
+			let stamp = match old {
+				Some(s) => Stamp { fetched: true, ..s },
+				None => Stamp::new(&fs::metadata(&local)?),
+			};
+			if let Some(mtime) = stamp.mtime {
+				fs::File::options().write(true).open(&local)?.set_modified(mtime)?;
+			}
+
+			STAMPS.lock().insert(local, stamp);
+			Ok(())
+		})
+		.await
+	}
+
+	/// Writes the file in the mirror back with its provider.
+	pub async fn push(url: &Url) -> io::Result<()> {
+		let Some(local) = Self::path(url) else { return Ok(()) };
+
+		Self::run(url, move |driver, url| {
+			driver.write(&url, &local)?;
+			STAMPS.lock().insert(local.clone(), Stamp::new(&fs::metadata(&local)?));
+			Ok(())
+		})
+		.await
+	}
+
+	fn root(name: &str) -> PathBuf { Xdg::cache_dir().join("vfs").join(name) }
+
+	async fn run<F>(url: &Url, f: F) -> io::Result<()>
+	where
+		F: FnOnce(&'static dyn Driver, Url) -> io::Result<()> + Send + 'static,
+	{
+		let Some(driver) = DRIVER.get() else {
+			return Err(io::Error::new(io::ErrorKind::Unsupported, "No driver for plugin filesystems"));
+		};
+
+		let url = url.clone();
+		tokio::task::spawn_blocking(move || f(driver.as_ref(), url)).await?
+	}
+
+	fn mirror(dir: &Path, files: Vec<File>) -> io::Result<()> {
+		fs::create_dir_all(dir)?;
+
+		let mut names = HashSet::with_capacity(files.len());
+		for file in files {
+			let Some(name) = file.url.file_name() else { continue };
+			names.insert(name.to_owned());
+			Self::materialize(&dir.join(name), &file.cha)?;
+		}
+
+		for entry in fs::read_dir(dir)? {
+			let entry = entry?;
+			if names.contains(&entry.file_name()) {
+				continue;
+			}
+
+			let path = entry.path();
+			if entry.file_type()?.is_dir() {
+				fs::remove_dir_all(&path)?;
+			} else {
+				fs::remove_file(&path)?;
+			}
+			STAMPS.lock().retain(|p, _| !p.starts_with(&path));
+		}
+		Ok(())
+	}
+
+	fn materialize(path: &Path, cha: &Cha) -> io::Result<()> {
+		let meta = fs::symlink_metadata(path).ok();
+		if cha.is_dir() {
+			if meta.is_some_and(|m| !m.is_dir()) {
+				fs::remove_file(path)?;
+			}
+			return fs::create_dir_all(path);
+		}
+
+		let stamp = Stamp { len: cha.len, mtime: cha.mtime, fetched: false };
+		if meta.as_ref().is_some_and(|m| m.is_file())
+			&& STAMPS.lock().get(path).is_some_and(|s| s.len == stamp.len && s.mtime == stamp.mtime)
+		{
+			return Ok(());
+		} else if meta.is_some_and(|m| m.is_dir()) {
+			fs::remove_dir_all(path)?;
+		}
+
+		let f = fs::File::create(path)?;
+		f.set_len(stamp.len)?;
+		if let Some(mtime) = stamp.mtime {
+			f.set_modified(mtime)?;
+		}
+
+		STAMPS.lock().insert(path.to_owned(), stamp);
+		Ok(())
+	}
+}
+
+impl Stamp {
+	fn new(meta: &fs::Metadata) -> Self {
+		Self { len: meta.len(), mtime: meta.modified().ok(), fetched: true }
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{TempDir, cha::ChaKind};
+
+	#[test]
+	fn test_mirror() -> io::Result<()> {
+		let dir = TempDir::new("vfs-mirror")?;
+
+		let file = |name: &str, len: u64, kind: ChaKind| File {
+			url: Url::from(Path::new("/").join(name)),
+			cha: Cha { kind, len, mtime: Some(SystemTime::UNIX_EPOCH), ..Default::default() },
+			link_to: None,
+		};
+
+		Vfs::mirror(&dir, vec![file("a", 3, ChaKind::empty()), file("b", 0, ChaKind::DIR)])?;
+		assert_eq!(fs::metadata(dir.join("a"))?.len(), 3);
+		assert!(dir.join("b").is_dir());
+
+		// Read files are kept unless they changed, and the ones gone are removed
+		fs::write(dir.join("a"), "abc")?;
+		STAMPS.lock().get_mut(&dir.join("a")).unwrap().fetched = true;
+		Vfs::mirror(&dir, vec![file("a", 3, ChaKind::empty())])?;
+		assert_eq!(fs::read(dir.join("a"))?, b"abc");
+		assert!(!dir.join("b").exists());
+
+		Vfs::mirror(&dir, vec![file("a", 4, ChaKind::empty())])?;
+		assert_eq!(fs::read(dir.join("a"))?, [0; 4]);
+		assert!(!STAMPS.lock()[&dir.join("a")].fetched);
+
+		Ok(())
+	}
+}
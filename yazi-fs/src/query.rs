@@ -0,0 +1,323 @@
+use std::{ffi::OsStr, iter::Peekable, path::Path, time::Duration, vec::IntoIter};
+
+use anyhow::{Result, bail};
+use regex::bytes::Regex;
+
+use crate::{FilterCase, cha::Cha};
+
+/// A query of the filter language, such as `size>10M and ext:mp4`.
+#[derive(Clone, Debug)]
+pub(crate) enum Query {
+	Name(Regex),
+	Ext(String),
+	Type(QueryType),
+	Size(QueryOp, u64),
+	Mtime(QueryOp, Duration),
+	Btime(QueryOp, Duration),
+	Not(Box<Self>),
+	And(Box<Self>, Box<Self>),
+	Or(Box<Self>, Box<Self>),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum QueryOp {
+	Lt,
+	Le,
+	Eq,
+	Ge,
+	Gt,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum QueryType {
+	Dir,
+	File,
+	Link,
+}
+
+#[derive(Debug, PartialEq)]
+enum Token {
+	Open,
+	Close,
+	Term(String, bool),
+}
+
+impl Query {
+	const KEYS: [&str; 6] = ["name", "ext", "type", "size", "mtime", "btime"];
+
+	/// Parses the query, or returns `None` if it's a regex of names as a whole.
+	pub(crate) fn parse(s: &str, case: FilterCase) -> Result<Option<Self>> {
+		let tokens = Self::tokenize(s);
+		if !tokens.iter().any(|t| matches!(t, Token::Term(s, false) if Self::is_special(s))) {
+			return Ok(None);
+		}
+
+		let mut it = tokens.into_iter().peekable();
+		let query = Self::parse_or(&mut it, case)?;
+		match it.next() {
+			None => Ok(Some(query)),
+			Some(Token::Close) => bail!("Unmatched `)` in the filter"),
+			Some(_) => bail!("Unexpected term in the filter"),
+		}
+	}
+
+	/// Whether the file matches, where the predicates on metadata never do without it.
+	pub(crate) fn matches(&self, name: &OsStr, cha: Option<&Cha>) -> bool {
+		let age = |t: Option<std::time::SystemTime>| t.and_then(|t| t.elapsed().ok());
+		match self {
+			Self::Name(re) => re.is_match(name.as_encoded_bytes()),
+			Self::Ext(ext) => match Path::new(name).extension() {
+				Some(e) => e.eq_ignore_ascii_case(ext),
+				None => ext.is_empty(),
+			},
+			Self::Type(t) => cha.is_some_and(|c| match t {
+				QueryType::Dir => c.is_dir(),
+				QueryType::File => !c.is_dir(),
+				QueryType::Link => c.is_link(),
+			}),
+			Self::Size(op, n) => cha.is_some_and(|c| op.test(c.len, *n)),
+			Self::Mtime(op, d) => age(cha.and_then(|c| c.mtime)).is_some_and(|a| op.test(a, *d)),
+			Self::Btime(op, d) => age(cha.and_then(|c| c.btime)).is_some_and(|a| op.test(a, *d)),
+			Self::Not(q) => !q.matches(name, cha),
+			Self::And(a, b) => a.matches(name, cha) && b.matches(name, cha),
+			Self::Or(a, b) => a.matches(name, cha) || b.matches(name, cha),
+		}
+	}
+
+	/// Whether it only looks at names, so it can be matched without metadata.
+	pub(crate) fn by_name(&self) -> bool {
+		match self {
+			Self::Name(_) | Self::Ext(_) => true,
+			Self::Type(_) | Self::Size(..) | Self::Mtime(..) | Self::Btime(..) => false,
+			Self::Not(q) => q.by_name(),
+			Self::And(a, b) | Self::Or(a, b) => a.by_name() && b.by_name(),
+		}
+	}
+
+	/// The first regex of names that isn't negated, to highlight the matches of.
+	pub(crate) fn highlighter(&self) -> Option<&Regex> {
+		match self {
+			Self::Name(re) => Some(re),
+			Self::And(a, b) | Self::Or(a, b) => a.highlighter().or_else(|| b.highlighter()),
+			_ => None,
+		}
+	}
+
+	fn tokenize(s: &str) -> Vec<Token> {
+		let mut tokens = vec![];
+		let mut it = s.chars().peekable();
+		while let Some(c) = it.next() {
+			match c {
+				_ if c.is_whitespace() => {}
+				'(' => tokens.push(Token::Open),
+				')' => tokens.push(Token::Close),
+				_ => {
+					let (mut term, mut next) = (String::new(), Some(c));
+					while let Some(c) = next {
+						if c == '"' {
+							// Unclosed quotes run to the end
+							term.extend(it.by_ref().take_while(|&c| c != '"'));
+						} else {
+							term.push(c);
+						}
+						next = it.next_if(|&c| !c.is_whitespace() && c != '(' && c != ')');
+					}
+					tokens.push(Token::Term(term, c == '"'));
+				}
+			}
+		}
+		tokens
+	}
+
+	fn is_special(s: &str) -> bool {
+		matches!(s, "and" | "or" | "not" | "AND" | "OR" | "NOT") || Self::key(s).is_some()
+	}
+
+	fn key(s: &str) -> Option<&'static str> {
+		Self::KEYS
+			.into_iter()
+			.find(|k| s.strip_prefix(k).is_some_and(|r| r.starts_with([':', '<', '>', '='])))
+	}
+
+	fn parse_or(it: &mut Peekable<IntoIter<Token>>, case: FilterCase) -> Result<Self> {
+		let mut left = Self::parse_and(it, case)?;
+		while it.next_if(|t| matches!(t, Token::Term(s, false) if s == "or" || s == "OR")).is_some() {
+			left = Self::Or(Box::new(left), Box::new(Self::parse_and(it, case)?));
+		}
+		Ok(left)
+	}
+
+	fn parse_and(it: &mut Peekable<IntoIter<Token>>, case: FilterCase) -> Result<Self> {
+		let mut left = Self::parse_not(it, case)?;
+		loop {
+			match it.peek() {
+				None | Some(Token::Close) => break,
+				Some(Token::Term(s, false)) if s == "or" || s == "OR" => break,
+				Some(Token::Term(s, false)) if s == "and" || s == "AND" => _ = it.next(),
+				Some(_) => {}
+			}
+			left = Self::And(Box::new(left), Box::new(Self::parse_not(it, case)?));
+		}
+		Ok(left)
+	}
+
+	fn parse_not(it: &mut Peekable<IntoIter<Token>>, case: FilterCase) -> Result<Self> {
+		match it.next() {
+			Some(Token::Term(s, false)) if s == "not" || s == "NOT" => {
+				Ok(Self::Not(Box::new(Self::parse_not(it, case)?)))
+			}
+			Some(Token::Open) => {
+				let query = Self::parse_or(it, case)?;
+				match it.next() {
+					Some(Token::Close) => Ok(query),
+					_ => bail!("Unmatched `(` in the filter"),
+				}
+			}
+			Some(Token::Term(s, false)) if matches!(s.as_str(), "and" | "or" | "AND" | "OR") => {
+				bail!("Missing a term before `{s}` in the filter")
+			}
+			Some(Token::Term(s, false)) => Self::parse_term(&s, case),
+			Some(Token::Term(s, true)) => Ok(Self::Name(case.regex(&s)?)),
+			Some(Token::Close) => bail!("Unmatched `)` in the filter"),
+			None => bail!("Missing a term at the end of the filter"),
+		}
+	}
+
+	fn parse_term(s: &str, case: FilterCase) -> Result<Self> {
+		let Some(key) = Self::key(s) else {
+			return Ok(Self::Name(case.regex(s)?));
+		};
+
+		let rest = &s[key.len()..];
+		let (op, value) = [
+			(":", QueryOp::Eq),
+			("<=", QueryOp::Le),
+			(">=", QueryOp::Ge),
+			("<", QueryOp::Lt),
+			(">", QueryOp::Gt),
+			("=", QueryOp::Eq),
+		]
+		.into_iter()
+		.find_map(|(p, op)| Some((op, rest.strip_prefix(p)?)))
+		.unwrap_or((QueryOp::Eq, rest));
+
+		if op != QueryOp::Eq && matches!(key, "name" | "ext" | "type") {
+			bail!("`{key}` can't be compared with `<` or `>` in the filter");
+		}
+
+		Ok(match key {
+			"name" => Self::Name(case.regex(value)?),
+			"ext" => Self::Ext(value.to_owned()),
+			"type" => Self::Type(match value {
+				"dir" => QueryType::Dir,
+				"file" => QueryType::File,
+				"link" => QueryType::Link,
+				_ => bail!("Unknown type `{value}` in the filter, expected `dir`, `file` or `link`"),
+			}),
+			"size" => Self::Size(op, Self::parse_size(value)?),
+			"mtime" => Self::Mtime(op, Self::parse_age(value)?),
+			"btime" => Self::Btime(op, Self::parse_age(value)?),
+			_ => unreachable!(),
+		})
+	}
+
+	fn parse_size(s: &str) -> Result<u64> {
+		let i = s.find(|c: char| !c.is_ascii_digit() && c != '.').unwrap_or(s.len());
+		let Ok(n) = s[..i].parse::<f64>() else { bail!("Invalid size `{s}` in the filter") };
+
+		let unit = match s[i..].to_ascii_uppercase().trim_end_matches("IB").trim_end_matches('B') {
+			"" => 1u64,
+			"K" => 1 << 10,
+			"M" => 1 << 20,
+			"G" => 1 << 30,
+			"T" => 1 << 40,
+			_ => bail!("Invalid size `{s}` in the filter, expected a unit of `K`, `M`, `G` or `T`"),
+		};
+		Ok((n * unit as f64) as u64)
+	}
+
+	fn parse_age(s: &str) -> Result<Duration> {
+		let i = s.find(|c: char| !c.is_ascii_digit() && c != '.').unwrap_or(s.len());
+		let Ok(n) = s[..i].parse::<f64>() else { bail!("Invalid age `{s}` in the filter") };
+
+		let secs = match &s[i..] {
+			"s" => 1,
+			"m" => 60,
+			"h" => 3600,
+			"d" => 86400,
+			"w" => 604800,
+			_ => bail!("Invalid age `{s}` in the filter, expected one of `s`, `m`, `h`, `d` or `w`"),
+		};
+		Ok(Duration::from_secs_f64(n * secs as f64))
+	}
+}
+
+impl QueryOp {
+	fn test<T: PartialOrd>(self, a: T, b: T) -> bool {
+		match self {
+			Self::Lt => a < b,
+			Self::Le => a <= b,
+			Self::Eq => a == b,
+			Self::Ge => a >= b,
+			Self::Gt => a > b,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::time::SystemTime;
+
+	use super::*;
+	use crate::cha::ChaKind;
+
+	fn parse(s: &str) -> Option<Query> { Query::parse(s, FilterCase::Smart).unwrap() }
+
+	#[test]
+	fn test_parse() {
+		// Regexes of names as a whole, unless there's an operator or a predicate
+		assert!(parse(r"foo (bar|baz)\.rs$").is_none());
+		assert!(parse(r#""size>1""#).is_none());
+		assert!(parse("sizes>1").is_none());
+
+		assert!(parse("size>10M and mtime<7d and (ext:mp4 or ext:mkv)").is_some());
+		assert!(parse(r#""and" or b"#).is_some());
+
+		for s in ["(ext:mp4", "ext:mp4)", "ext:mp4 or", "and ext:mp4", "size>10X", "mtime<7", "ext>a"] {
+			assert!(Query::parse(s, FilterCase::Smart).is_err(), "{s}");
+		}
+	}
+
+	#[test]
+	fn test_matches() {
+		let now = SystemTime::now();
+		let file = |len: u64, days: u64, kind: ChaKind| Cha {
+			kind,
+			len,
+			mtime: Some(now - Duration::from_secs(days * 86400)),
+			..Default::default()
+		};
+
+		let q = parse("size>10M and mtime<7d and (ext:mp4 or ext:MKV)").unwrap();
+		assert!(q.matches(OsStr::new("a.mp4"), Some(&file(11 << 20, 1, ChaKind::empty()))));
+		assert!(q.matches(OsStr::new("a.mkv"), Some(&file(11 << 20, 6, ChaKind::empty()))));
+		assert!(!q.matches(OsStr::new("a.avi"), Some(&file(11 << 20, 1, ChaKind::empty()))));
+		assert!(!q.matches(OsStr::new("a.mp4"), Some(&file(9 << 20, 1, ChaKind::empty()))));
+		assert!(!q.matches(OsStr::new("a.mp4"), Some(&file(11 << 20, 8, ChaKind::empty()))));
+		assert!(!q.matches(OsStr::new("a.mp4"), None));
+		assert!(!q.by_name());
+
+		// Spaces are `and`, which binds tighter than `or`
+		let q = parse(r#"^a not type:dir or "b c""#).unwrap();
+		assert!(q.matches(OsStr::new("ab"), Some(&file(0, 0, ChaKind::empty()))));
+		assert!(!q.matches(OsStr::new("ab"), Some(&file(0, 0, ChaKind::DIR))));
+		assert!(q.matches(OsStr::new("b c"), Some(&file(0, 0, ChaKind::DIR))));
+		assert_eq!(q.highlighter().map(Regex::as_str), Some("^a"));
+
+		let q = parse("not ext: and name:^READ").unwrap();
+		assert!(q.by_name());
+		assert!(q.matches(OsStr::new("README.md"), None));
+		assert!(!q.matches(OsStr::new("README"), None));
+		assert!(!q.matches(OsStr::new("readme.md"), None));
+	}
+}
@@ -0,0 +1,308 @@
+use std::{collections::HashMap, str::FromStr};
+
+use anyhow::{Result, bail};
+use regex::{Regex, RegexBuilder};
+use yazi_shared::url::Url;
+
+/// Computes new names for a batch of files from an expression.
+#[derive(Debug)]
+pub struct Renamer {
+	ops: Vec<Op>,
+}
+
+#[derive(Debug)]
+enum Op {
+	Replace { re: Regex, rep: String, global: bool },
+	Lower,
+	Upper,
+	Camel,
+}
+
+/// A file to rename, and the reason it cannot be renamed if any.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Rename {
+	pub from:     Url,
+	pub to:       Url,
+	pub conflict: Option<RenameConflict>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RenameConflict {
+	/// The old or new name isn't valid, e.g. it's empty or contains a separator.
+	Invalid,
+	/// Another file in the batch is renamed to the same name.
+	Duplicate,
+	/// A file not in the batch already has the new name.
+	Exists,
+}
+
+impl FromStr for Renamer {
+	type Err = anyhow::Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let mut ops = vec![];
+		let mut rest = s.trim_start();
+		while !rest.is_empty() {
+			let (op, remain) = Self::op(rest)?;
+			ops.push(op);
+
+			rest = remain.trim_start();
+			if let Some(s) = rest.strip_prefix(';') {
+				rest = s.trim_start();
+			} else if !rest.is_empty() {
+				bail!("Expected `;` between operations, found `{rest}`");
+			}
+		}
+
+		if ops.is_empty() {
+			bail!("Empty rename expression");
+		}
+		Ok(Self { ops })
+	}
+}
+
+impl Renamer {
+	/// Plans the renames of the files, where `to` equals `from` if the name doesn't change.
+	pub fn plan(&self, urls: &[Url]) -> Vec<Rename> {
+		let mut plans: Vec<_> = urls
+			.iter()
+			.enumerate()
+			.map(|(i, from)| {
+				let name = from.name().to_str().filter(|s| !s.is_empty());
+				match name.map(|s| self.apply(s, i + 1)) {
+					Some(new) if Self::valid(&new) => {
+						let mut to = from.clone();
+						to.set_name(new);
+						Rename { from: from.clone(), to, conflict: None }
+					}
+					_ => {
+						Rename { from: from.clone(), to: from.clone(), conflict: Some(RenameConflict::Invalid) }
+					}
+				}
+			})
+			.collect();
+
+		let mut seen: HashMap<&Url, usize> = HashMap::new();
+		for p in &plans {
+			*seen.entry(&p.to).or_default() += 1;
+		}
+
+		let duplicates: Vec<_> = plans.iter().map(|p| seen[&p.to] > 1).collect();
+		for (p, dup) in plans.iter_mut().zip(duplicates) {
+			if dup && p.conflict.is_none() {
+				p.conflict = Some(RenameConflict::Duplicate);
+			}
+		}
+		plans
+	}
+
+	/// Applies the operations to a name, where `n` is its position in the batch.
+	pub fn apply(&self, name: &str, n: usize) -> String {
+		let mut name = name.to_owned();
+		for op in &self.ops {
+			name = match op {
+				Op::Replace { re, rep, global } => {
+					let rep = Self::counter(rep, n);
+					let limit = if *global { 0 } else { 1 };
+					re.replacen(&name, limit, rep.as_str()).into_owned()
+				}
+				Op::Lower => name.to_lowercase(),
+				Op::Upper => name.to_uppercase(),
+				Op::Camel => Self::camel(&name),
+			};
+		}
+		name
+	}
+
+	fn op(s: &str) -> Result<(Op, &str)> {
+		let word_end = s.find(|c: char| !c.is_ascii_alphabetic()).unwrap_or(s.len());
+		match &s[..word_end] {
+			"lower" => return Ok((Op::Lower, &s[word_end..])),
+			"upper" => return Ok((Op::Upper, &s[word_end..])),
+			"camel" => return Ok((Op::Camel, &s[word_end..])),
+			_ => {}
+		}
+
+		let Some(s) = s.strip_prefix('s') else { bail!("Unknown rename operation `{s}`") };
+		let Some(delim) = s.chars().next().filter(|c| !c.is_alphanumeric() && !c.is_whitespace())
+		else {
+			bail!("Expected a delimiter after `s`");
+		};
+
+		let (pattern, s) = Self::until(&s[delim.len_utf8()..], delim)?;
+		let (rep, s) = Self::until(s, delim)?;
+		let flags_end = s.find(|c: char| !c.is_ascii_alphabetic()).unwrap_or(s.len());
+
+		let (mut global, mut insensitive) = (false, false);
+		for c in s[..flags_end].chars() {
+			match c {
+				'g' => global = true,
+				'i' => insensitive = true,
+				_ => bail!("Unknown flag `{c}` in `s{delim}{pattern}{delim}{rep}{delim}`"),
+			}
+		}
+
+		let re = RegexBuilder::new(&pattern).case_insensitive(insensitive).build()?;
+		Ok((Op::Replace { re, rep, global }, &s[flags_end..]))
+	}
+
+	/// Reads until the unescaped delimiter, unescaping `\<delim>` only.
+	fn until(s: &str, delim: char) -> Result<(String, &str)> {
+		let mut buf = String::new();
+		let mut it = s.char_indices();
+		while let Some((i, c)) = it.next() {
+			if c == delim {
+				return Ok((buf, &s[i + c.len_utf8()..]));
+			} else if c == '\\' {
+				match it.next() {
+					Some((_, d)) if d == delim => buf.push(d),
+					Some((_, d)) => buf.extend(['\\', d]),
+					None => buf.push('\\'),
+				}
+			} else {
+				buf.push(c);
+			}
+		}
+		bail!("Missing the closing delimiter `{delim}`")
+	}
+
+	fn counter(rep: &str, n: usize) -> String {
+		let mut buf = String::with_capacity(rep.len());
+		let mut it = rep.chars().peekable();
+		while let Some(c) = it.next() {
+			if c != '%' {
+				buf.push(c);
+				continue;
+			}
+
+			let mut width = String::new();
+			while let Some(d) = it.next_if(char::is_ascii_digit) {
+				width.push(d);
+			}
+			match it.peek() {
+				Some('n') => {
+					it.next();
+					buf.push_str(&format!("{n:0>w$}", w = width.parse().unwrap_or(0)));
+				}
+				Some('%') if width.is_empty() => {
+					it.next();
+					buf.push('%');
+				}
+				_ => {
+					buf.push('%');
+					buf.push_str(&width);
+				}
+			}
+		}
+		buf
+	}
+
+	fn camel(name: &str) -> String {
+		let (dot, name) = match name.strip_prefix('.') {
+			Some(s) => (".", s),
+			None => ("", name),
+		};
+		let (stem, ext) = match name.rfind('.') {
+			Some(i) => name.split_at(i),
+			None => (name, ""),
+		};
+
+		let mut buf = String::from(dot);
+		let words = stem.split(|c: char| !c.is_alphanumeric()).filter(|w| !w.is_empty());
+		for (i, word) in words.enumerate() {
+			let mut chars = word.chars();
+			if i == 0 {
+				buf.extend(word.chars().flat_map(char::to_lowercase));
+			} else if let Some(first) = chars.next() {
+				buf.extend(first.to_uppercase());
+				buf.extend(chars.flat_map(char::to_lowercase));
+			}
+		}
+		buf + ext
+	}
+
+	#[inline]
+	fn valid(name: &str) -> bool {
+		!name.is_empty()
+			&& name != "."
+			&& name != ".."
+			&& !name.contains('/')
+			&& !(cfg!(windows) && name.contains('\\'))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn apply(expr: &str, name: &str) -> String { expr.parse::<Renamer>().unwrap().apply(name, 7) }
+
+	#[test]
+	fn test_replace() {
+		assert_eq!(apply(r"s/IMG_(\d+)/photo-$1/", "IMG_0042.jpg"), "photo-0042.jpg");
+		assert_eq!(apply("s/a/b/", "aaa"), "baa");
+		assert_eq!(apply("s/a/b/g", "aaa"), "bbb");
+		assert_eq!(apply("s/A/b/gi", "aAa"), "bbb");
+		assert_eq!(apply(r"s|/|\||", "a/b"), "a|b");
+		assert_eq!(apply(r"s/\//-/g", "a/b/c"), "a-b-c");
+		assert_eq!(apply(r"s/(?<y>\d{4})-(?<m>\d\d)/${m}.${y}/", "2024-05.txt"), "05.2024.txt");
+	}
+
+	#[test]
+	fn test_counter() {
+		assert_eq!(apply("s/.*/%n/", "a"), "7");
+		assert_eq!(apply("s/^/%3n-/", "a"), "007-a");
+		assert_eq!(apply("s/$/ 100%%/", "a"), "a 100%");
+		assert_eq!(apply("s/$/ %x/", "a"), "a %x");
+		assert_eq!(apply("s/$/%2/", "a"), "a%2");
+	}
+
+	#[test]
+	fn test_case() {
+		assert_eq!(apply("lower", "README.MD"), "readme.md");
+		assert_eq!(apply("upper", "été.txt"), "ÉTÉ.TXT");
+		assert_eq!(apply("camel", "my first-file_NAME.tar.gz"), "myFirstFileNameTar.gz");
+		assert_eq!(apply("camel", ".hidden file"), ".hiddenFile");
+		assert_eq!(apply("s/ /_/g; upper ;s/TXT$/txt/", "a b.txt"), "A_B.txt");
+	}
+
+	#[test]
+	fn test_invalid() {
+		for (expr, err) in [
+			("", "Empty rename expression"),
+			("title", "Unknown rename operation"),
+			("s/a/b", "Missing the closing delimiter"),
+			("s/a/b/x", "Unknown flag `x`"),
+			("s/(/b/", "regex parse error"),
+			("lower upper", "Expected `;` between operations"),
+			("s a b ", "Expected a delimiter"),
+		] {
+			let e = expr.parse::<Renamer>().unwrap_err().to_string();
+			assert!(e.contains(err), "{expr}: {e}");
+		}
+	}
+
+	#[test]
+	fn test_plan() -> Result<()> {
+		let urls: Vec<Url> =
+			["/tmp/IMG_1.jpg", "/tmp/IMG_2.jpg", "/tmp/b/IMG_1.jpg", "/tmp/c.jpg", "/tmp/d.png"]
+				.into_iter()
+				.map(|s| s.parse())
+				.collect::<Result<_>>()?;
+
+		let plans = "s/IMG_\\d+/x/; s/^[cd]\\..*//".parse::<Renamer>()?.plan(&urls);
+		let conflicts: Vec<_> = plans.iter().map(|p| p.conflict).collect();
+		assert_eq!(
+			conflicts,
+			[
+				Some(RenameConflict::Duplicate),
+				Some(RenameConflict::Duplicate),
+				None,
+				Some(RenameConflict::Invalid),
+				Some(RenameConflict::Invalid),
+			]
+		);
+		assert_eq!(plans[2].to, "/tmp/b/x.jpg".parse::<Url>()?);
+		Ok(())
+	}
+}
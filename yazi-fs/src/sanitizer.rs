@@ -0,0 +1,199 @@
+use std::{borrow::Cow, collections::HashSet, str::FromStr};
+
+use anyhow::bail;
+use unicode_normalization::{UnicodeNormalization, is_nfc};
+use yazi_shared::url::Url;
+
+use crate::Rename;
+
+/// Fixes the file names that are problematic on a target file system.
+#[derive(Clone, Copy, Debug)]
+pub struct Sanitizer {
+	target: SanitizeTarget,
+}
+
+/// The target file system, where FAT, exFAT, NTFS and SMB follow Windows.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum SanitizeTarget {
+	#[default]
+	Windows,
+	Posix,
+}
+
+impl FromStr for SanitizeTarget {
+	type Err = anyhow::Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		Ok(match s {
+			"windows" | "fat" | "exfat" | "ntfs" | "smb" => Self::Windows,
+			"posix" => Self::Posix,
+			_ => bail!("Invalid sanitize target: {s}"),
+		})
+	}
+}
+
+impl Sanitizer {
+	const RESERVED: [&str; 22] = [
+		"CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8",
+		"COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+	];
+
+	#[inline]
+	pub fn new(target: SanitizeTarget) -> Self { Self { target } }
+
+	/// Plans the renames of the files, where `to` equals `from` if the name is fine.
+	pub fn plan(&self, urls: &[Url]) -> Vec<Rename> {
+		let fixed: Vec<_> =
+			urls.iter().map(|u| self.fix(&u.name().to_string_lossy()).into_owned()).collect();
+		let mut changed: Vec<_> =
+			urls.iter().zip(&fixed).map(|(u, f)| u.name() != f.as_str()).collect();
+
+		let mut taken = HashSet::with_capacity(urls.len());
+		for ((u, f), c) in urls.iter().zip(&fixed).zip(&mut changed) {
+			if !*c && !taken.insert((u.parent_url(), self.fold(f))) {
+				*c = true; // Clashes with another name that's fine
+			}
+		}
+
+		let mut plans = Vec::with_capacity(urls.len());
+		for ((from, mut name), changed) in urls.iter().zip(fixed).zip(changed) {
+			if changed {
+				let (parent, mut n) = (from.parent_url(), 0);
+				while !taken.insert((parent.clone(), self.fold(&name))) {
+					n += 1;
+					name = Self::suffixed(&name, n);
+				}
+			}
+
+			let mut to = from.clone();
+			to.set_name(name);
+			plans.push(Rename { from: from.clone(), to, conflict: None });
+		}
+		plans
+	}
+
+	/// The fixed name, which is always valid.
+	pub fn fix<'a>(&self, name: &'a str) -> Cow<'a, str> {
+		let windows = self.target == SanitizeTarget::Windows;
+
+		let mut s: Cow<str> =
+			if is_nfc(name) { name.into() } else { name.nfc().collect::<String>().into() };
+		if s.chars().any(|c| self.illegal(c)) {
+			s = s.chars().map(|c| if self.illegal(c) { '_' } else { c }).collect::<String>().into();
+		}
+
+		let trimmed = s.trim_end_matches(|c| c == ' ' || windows && c == '.');
+		if trimmed.is_empty() || trimmed == "." || trimmed == ".." {
+			return "_".into();
+		} else if trimmed.len() != s.len() {
+			s = trimmed.to_owned().into();
+		}
+
+		if !windows {
+			return s;
+		}
+
+		let base = s.split('.').next().unwrap_or_default().trim_end_matches(' ');
+		if Self::RESERVED.iter().any(|r| r.eq_ignore_ascii_case(base)) {
+			s = format!("{base}_{}", &s[base.len()..]).into();
+		}
+
+		if s.encode_utf16().count() > 255 {
+			s = Self::truncate(&s).into();
+		}
+		s
+	}
+
+	#[inline]
+	fn illegal(&self, c: char) -> bool {
+		match c {
+			'/' => true,
+			'"' | '*' | ':' | '<' | '>' | '?' | '\\' | '|' => self.target == SanitizeTarget::Windows,
+			_ => c.is_control(),
+		}
+	}
+
+	/// The name as it's compared on the target file system.
+	#[inline]
+	fn fold(&self, name: &str) -> String {
+		match self.target {
+			SanitizeTarget::Windows => name.to_lowercase(),
+			SanitizeTarget::Posix => name.to_owned(),
+		}
+	}
+
+	fn suffixed(name: &str, n: usize) -> String {
+		let (stem, ext) = Self::split_ext(name);
+		let stem = stem.strip_suffix(&format!("_{}", n - 1)).filter(|_| n > 1).unwrap_or(stem);
+		format!("{stem}_{n}{ext}")
+	}
+
+	fn truncate(name: &str) -> String {
+		let (stem, ext) = match Self::split_ext(name) {
+			(stem, ext) if ext.len() <= 16 => (stem, ext),
+			_ => (name, ""),
+		};
+
+		let mut left = 255 - ext.encode_utf16().count();
+		let stem: String = stem
+			.chars()
+			.take_while(|c| {
+				let fits = c.len_utf16() <= left;
+				left -= if fits { c.len_utf16() } else { 0 };
+				fits
+			})
+			.collect();
+		stem + ext
+	}
+
+	fn split_ext(name: &str) -> (&str, &str) {
+		match name.rfind('.') {
+			Some(i) if i > 0 => name.split_at(i),
+			_ => (name, ""),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use anyhow::Result;
+
+	use super::*;
+
+	fn fix(name: &str) -> String { Sanitizer::new(SanitizeTarget::Windows).fix(name).into_owned() }
+
+	#[test]
+	fn test_fix() {
+		assert_eq!(fix("a<b>:c?.txt"), "a_b__c_.txt");
+		assert_eq!(fix("tab\there"), "tab_here");
+		assert_eq!(fix("trailing. . "), "trailing");
+		assert_eq!(fix("e\u{301}te\u{301}.txt"), "\u{e9}t\u{e9}.txt");
+		assert_eq!(fix("CON"), "CON_");
+		assert_eq!(fix("nul.tar.gz"), "nul_.tar.gz");
+		assert_eq!(fix("console.txt"), "console.txt");
+		assert_eq!(fix("..."), "_");
+
+		let long = format!("{}.txt", "あ".repeat(300));
+		assert_eq!(fix(&long).encode_utf16().count(), 255);
+		assert!(fix(&long).ends_with("あ.txt"));
+
+		let posix = Sanitizer::new(SanitizeTarget::Posix);
+		assert_eq!(posix.fix("a:b. "), "a:b.");
+		assert_eq!(posix.fix("CON"), "CON");
+	}
+
+	#[test]
+	fn test_plan() -> Result<()> {
+		let urls: Vec<Url> =
+			["/tmp/a:b", "/tmp/a_b", "/tmp/README", "/tmp/readme", "/tmp/x/A?", "/tmp/x/a*"]
+				.into_iter()
+				.map(|s| s.parse())
+				.collect::<Result<_>>()?;
+
+		let plans = Sanitizer::new(SanitizeTarget::Windows).plan(&urls);
+		let names: Vec<_> = plans.iter().map(|p| p.to.name().to_string_lossy().into_owned()).collect();
+		assert_eq!(names, ["a_b_1", "a_b", "README", "readme_1", "A_", "a__1"]);
+		assert!(plans.iter().all(|p| p.conflict.is_none()));
+		Ok(())
+	}
+}
@@ -0,0 +1,128 @@
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc::{self, UnboundedReceiver};
+use yazi_shared::url::{CovUrl, Url};
+
+use crate::{Filter, cha::Cha, provider};
+
+/// A selection kept as its root directories plus a predicate.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct Selection {
+	pub roots:    Vec<Url>,
+	pub filter:   Option<Filter>,
+	pub hidden:   bool,
+	pub count:    usize,
+	#[serde(default, skip_serializing_if = "HashSet::is_empty")]
+	pub excluded: HashSet<CovUrl>,
+}
+
+impl Selection {
+	pub fn new(root: Url, filter: Option<Filter>, hidden: bool, count: usize) -> Self {
+		Self { roots: vec![root], filter, hidden, count, excluded: Default::default() }
+	}
+
+	#[inline]
+	pub fn len(&self) -> usize { self.count.saturating_sub(self.excluded.len()) }
+
+	#[inline]
+	pub fn is_empty(&self) -> bool { self.len() == 0 }
+
+	pub fn contains(&self, url: &Url) -> bool {
+		let Some(parent) = url.parent_url() else { return false };
+		if !self.roots.iter().any(|r| CovUrl::new(r) == &parent) {
+			return false;
+		}
+
+		#[cfg(unix)]
+		if !self.hidden && url.urn().is_hidden() {
+			return false;
+		}
+
+		self.filter.as_ref().is_none_or(|f| f.matches(url.urn()))
+			&& !self.excluded.contains(CovUrl::new(url))
+	}
+
+	#[inline]
+	pub fn contains_in(&self, dir: &Url) -> bool {
+		self.roots.iter().any(|r| CovUrl::new(r) == CovUrl::new(dir))
+	}
+
+	pub fn exclude(&mut self, url: &Url) -> bool {
+		self.contains(url) && self.excluded.insert(CovUrl(url.clone()))
+	}
+
+	pub fn expand(&self) -> UnboundedReceiver<Url> {
+		let (tx, rx) = mpsc::unbounded_channel();
+		let me = self.clone();
+
+		tokio::spawn(async move {
+			for root in &me.roots {
+				let Ok(mut it) = provider::read_dir(root).await else { continue };
+				while let Ok(Some(entry)) = it.next_entry().await {
+					let url = entry.url();
+					let Ok(meta) = entry.metadata().await else { continue };
+					if !me.accepts(&url, Cha::new(&url, meta)) {
+						continue;
+					} else if tx.send(url).is_err() {
+						return;
+					}
+				}
+			}
+		});
+		rx
+	}
+
+	fn accepts(&self, url: &Url, cha: Cha) -> bool {
+		(self.hidden || !cha.is_hidden())
+			&& self.filter.as_ref().is_none_or(|f| f.matches_with(url.urn(), &cha))
+			&& !self.excluded.contains(CovUrl::new(url))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::{collections::BTreeSet, fs};
+
+	use super::*;
+	use crate::{FilterCase, TempDir};
+
+	#[tokio::test]
+	async fn test_expand() -> anyhow::Result<()> {
+		let root = TempDir::new("selection")?;
+		fs::create_dir_all(root.join("dir"))?;
+		for i in 0..500 {
+			fs::write(root.join(format!("file-{i}.txt")), "")?;
+			fs::write(root.join(format!("file-{i}.md")), "")?;
+			fs::write(root.join(format!(".hidden-{i}.txt")), "")?;
+		}
+
+		// What the eager path would have yanked: the visible, filtered entries
+		let eager = |hidden: bool, filter: Option<&Filter>| -> BTreeSet<Url> {
+			fs::read_dir(&root)
+				.unwrap()
+				.flatten()
+				.map(|e| Url::from(e.path()))
+				.filter(|u| hidden || !u.name().to_string_lossy().starts_with('.'))
+				.filter(|u| filter.is_none_or(|f| f.matches(u.urn())))
+				.collect()
+		};
+
+		let filter = Filter::new(r"\.txt$", FilterCase::Sensitive)?;
+		for (hidden, filter) in [(false, None), (true, None), (false, Some(filter))] {
+			let expected = eager(hidden, filter.as_ref());
+			let sel = Selection::new(root.to_path_buf().into(), filter, hidden, expected.len());
+
+			let mut rx = sel.expand();
+			let mut lazy = BTreeSet::new();
+			while let Some(u) = rx.recv().await {
+				assert!(sel.contains(&u));
+				lazy.insert(u);
+			}
+
+			assert_eq!(lazy, expected);
+		}
+
+		Ok(())
+	}
+}
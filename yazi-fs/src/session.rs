@@ -0,0 +1,80 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use yazi_shared::url::Url;
+
+use crate::{FilesSorter, Xdg, write_atomic};
+
+/// The open tabs saved to the state directory on exit, to be restored with `yazi --resume`.
+#[derive(Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct Session {
+	pub active: usize,
+	pub tabs:   Vec<SessionTab>,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct SessionTab {
+	pub cwd:      Url,
+	pub hovered:  Option<Url>,
+	pub sorter:   FilesSorter,
+	pub selected: Vec<Url>,
+}
+
+impl Session {
+	/// Loads the session saved by [`Self::save`].
+	pub fn load() -> Result<Self> { Self::load_from(&Self::path()) }
+
+	/// Saves the session to the state directory, blocking the current thread.
+	pub fn save(&self) -> Result<()> { self.save_to(&Self::path()) }
+
+	fn load_from(path: &Path) -> Result<Self> {
+		let mut session: Self = serde_json::from_slice(&std::fs::read(path)?)?;
+		session.active = session.active.min(session.tabs.len().saturating_sub(1));
+		Ok(session)
+	}
+
+	fn save_to(&self, path: &Path) -> Result<()> {
+		Ok(write_atomic(path, &serde_json::to_vec(self)?)?)
+	}
+
+	#[inline]
+	fn path() -> PathBuf { Xdg::state_dir().join("session.json") }
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{SortBy, TempDir};
+
+	#[test]
+	fn test_session() -> Result<()> {
+		let dir = TempDir::new("session")?;
+		let path = dir.join("session.json");
+		let tab = |cwd: &str, hovered: Option<&str>, selected: &[&str]| -> Result<SessionTab> {
+			Ok(SessionTab {
+				cwd:      cwd.parse()?,
+				hovered:  hovered.map(str::parse).transpose()?,
+				sorter:   FilesSorter { by: SortBy::Mtime, reverse: true, ..Default::default() },
+				selected: selected.iter().map(|s| s.parse()).collect::<Result<_>>()?,
+			})
+		};
+
+		let session = Session {
+			active: 1,
+			tabs:   vec![
+				tab("/tmp", Some("/tmp/a b"), &["/tmp/a b", "/tmp/c"])?,
+				tab("search://kw #%//tmp", Some("search://kw #%//tmp/d/e"), &[])?,
+				tab("sftp://remote//home", None, &["sftp://remote//home/f"])?,
+			],
+		};
+		session.save_to(&path)?;
+		assert_eq!(Session::load_from(&path)?, session);
+
+		// An out-of-range active tab falls back to the last one
+		Session { active: 5, tabs: vec![tab("/tmp", None, &[])?] }.save_to(&path)?;
+		assert_eq!(Session::load_from(&path)?.active, 0);
+
+		Ok(())
+	}
+}
@@ -0,0 +1,80 @@
+use std::{path::PathBuf, time::SystemTime};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use yazi_shared::url::Url;
+
+use crate::{LruStore, Xdg};
+
+/// The sizes of the directories calculated so far, persisted in the state directory.
+pub static SIZES: Sizes = Sizes::new();
+
+/// Sizes of directories keyed by their URL and modification time.
+pub struct Sizes(LruStore<Url, (SystemTime, u64)>);
+
+#[derive(Deserialize, Serialize)]
+struct Entry {
+	url:   Url,
+	mtime: SystemTime,
+	size:  u64,
+}
+
+impl From<Entry> for (Url, (SystemTime, u64)) {
+	fn from(e: Entry) -> Self { (e.url, (e.mtime, e.size)) }
+}
+
+impl From<(&Url, &(SystemTime, u64))> for Entry {
+	fn from((url, &(mtime, size)): (&Url, &(SystemTime, u64))) -> Self {
+		Self { url: url.clone(), mtime, size }
+	}
+}
+
+impl Sizes {
+	const fn new() -> Self { Self(LruStore::new(10000)) }
+
+	/// The cached size of the directory, if it hasn't been modified since.
+	pub fn get(&self, url: &Url, mtime: Option<SystemTime>) -> Option<u64> {
+		let mtime = mtime?;
+		match self.0.get(url) {
+			Some((t, size)) if t == mtime => Some(size),
+			_ => None,
+		}
+	}
+
+	pub fn set(&self, url: Url, mtime: Option<SystemTime>, size: u64) {
+		let Some(mtime) = mtime else { return };
+		self.0.put(url, (mtime, size));
+	}
+
+	/// Loads the sizes saved by [`Self::save`], keeping those set in the meantime.
+	pub fn load(&self) -> Result<()> { self.0.load_from::<Entry>(&Self::path()) }
+
+	/// Saves the sizes to the state directory, blocking the current thread.
+	pub fn save(&self) -> Result<()> { self.0.save_to::<Entry>(&Self::path()) }
+
+	#[inline]
+	fn path() -> PathBuf { Xdg::state_dir().join("sizes.json") }
+}
+
+#[cfg(test)]
+mod tests {
+	use std::time::Duration;
+
+	use super::*;
+
+	#[test]
+	fn test_sizes() -> Result<()> {
+		let (a, b): (Url, Url) = ("/tmp/a".parse()?, "/tmp/b".parse()?);
+		let t = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+
+		let sizes = Sizes::new();
+		sizes.set(a.clone(), Some(t), 42);
+		sizes.set(b.clone(), None, 7);
+		assert_eq!(sizes.get(&a, Some(t)), Some(42));
+		assert_eq!(sizes.get(&a, Some(t + Duration::from_nanos(1))), None);
+		assert_eq!(sizes.get(&a, None), None);
+		assert_eq!(sizes.get(&b, None), None);
+
+		Ok(())
+	}
+}
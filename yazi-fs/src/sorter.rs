@@ -1,10 +1,15 @@
-use std::{cmp::Ordering, collections::HashMap};
+use std::{cmp::Ordering, collections::HashMap, mem, sync::OnceLock};
 
-use yazi_shared::{LcgRng, natsort, translit::Transliterator, url::UrnBuf};
+use serde::{Deserialize, Serialize};
+use yazi_shared::{LcgRng, natsort, translit::Transliterator, url::UrnBuf, vercmp};
 
 use crate::{File, SortBy};
 
-#[derive(Clone, Copy, Debug, Default, PartialEq)]
+type Hook = fn(&[File]) -> Option<Vec<usize>>;
+
+static HOOK: OnceLock<Hook> = OnceLock::new();
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
 pub struct FilesSorter {
 	pub by:        SortBy,
 	pub sensitive: bool,
@@ -14,18 +19,15 @@ pub struct FilesSorter {
 }
 
 impl FilesSorter {
+	/// Sets the hook `SortBy::Custom` is done with, once at startup.
+	pub fn hook(f: Hook) { _ = HOOK.set(f); }
+
 	pub(super) fn sort(&self, items: &mut [File], sizes: &HashMap<UrnBuf, u64>) {
 		if items.is_empty() {
 			return;
 		}
 
-		let by_alphabetical = |a: &File, b: &File| {
-			if self.sensitive {
-				self.cmp(a.urn().encoded_bytes(), b.urn().encoded_bytes(), self.promote(a, b))
-			} else {
-				self.cmp_insensitive(a.urn().encoded_bytes(), b.urn().encoded_bytes(), self.promote(a, b))
-			}
-		};
+		let by_alphabetical = |a: &File, b: &File| self.cmp_alphabetically(a, b);
 
 		match self.by {
 			SortBy::None => {}
@@ -61,28 +63,134 @@ impl FilesSorter {
 				let mut rng = LcgRng::default();
 				items.sort_unstable_by(|a, b| self.cmp(rng.next(), rng.next(), self.promote(a, b)))
 			}
+			SortBy::Version => items.sort_unstable_by(|a, b| {
+				let promote = self.promote(a, b);
+				if promote != Ordering::Equal {
+					return promote;
+				}
+
+				let ord = vercmp(a.urn().encoded_bytes(), b.urn().encoded_bytes(), !self.sensitive);
+				let ord = if self.reverse { ord.reverse() } else { ord };
+				if ord == Ordering::Equal { by_alphabetical(a, b) } else { ord }
+			}),
+			#[cfg(unix)]
+			SortBy::Owner => {
+				use uzers::Users;
+				use yazi_shared::USERS_CACHE;
+
+				// Unknown users come first, by their IDs
+				let mut owners = HashMap::new();
+				for f in items.iter() {
+					owners.entry(f.uid).or_insert_with(|| {
+						(USERS_CACHE.get_user_by_uid(f.uid).map(|u| u.name().to_owned()), f.uid)
+					});
+				}
+				items.sort_unstable_by(|a, b| {
+					let ord = self.cmp(&owners[&a.uid], &owners[&b.uid], self.promote(a, b));
+					if ord == Ordering::Equal { by_alphabetical(a, b) } else { ord }
+				})
+			}
+			#[cfg(not(unix))]
+			SortBy::Owner => items.sort_unstable_by(by_alphabetical),
+			SortBy::Custom => {
+				if !self.sort_by_hook(items) {
+					items.sort_unstable_by(by_alphabetical);
+				}
+			}
 		}
 	}
 
-	fn sort_naturally(&self, items: &mut [File]) {
-		items.sort_unstable_by(|a, b| {
-			let promote = self.promote(a, b);
-			if promote != Ordering::Equal {
-				return promote;
+	fn sort_by_hook(&self, items: &mut [File]) -> bool {
+		let Some(order) = HOOK.get().and_then(|f| f(items)) else { return false };
+		if !Self::permute(items, &order) {
+			return false;
+		}
+
+		if self.reverse {
+			items.reverse();
+		}
+		if self.dir_first {
+			items.sort_by_key(|f| !f.is_dir());
+		}
+		true
+	}
+
+	// Moves the item at `order[i]` to `i`, unless the `order` isn't a permutation of the items
+	fn permute<T>(items: &mut [T], order: &[usize]) -> bool {
+		let mut seen = vec![false; items.len()];
+		if order.len() != items.len()
+			|| !order.iter().all(|&j| j < seen.len() && !mem::replace(&mut seen[j], true))
+		{
+			return false;
+		}
+
+		// Following where the ones before went
+		for i in 0..order.len() {
+			let mut j = order[i];
+			while j < i {
+				j = order[j];
 			}
+			items.swap(i, j);
+		}
+		true
+	}
 
-			let ordering = if self.translit {
-				natsort(
-					a.urn().encoded_bytes().transliterate().as_bytes(),
-					b.urn().encoded_bytes().transliterate().as_bytes(),
-					!self.sensitive,
-				)
-			} else {
-				natsort(a.urn().encoded_bytes(), b.urn().encoded_bytes(), !self.sensitive)
-			};
-
-			if self.reverse { ordering.reverse() } else { ordering }
-		});
+	/// Whether it sorts by the names alone, so sorted chunks can be merged.
+	#[inline]
+	pub fn by_name(&self) -> bool { matches!(self.by, SortBy::Alphabetical | SortBy::Natural) }
+
+	// Merges the `chunk` into the `items` sorted by name, sorting it first unless it already is
+	pub(super) fn merge(&self, items: &mut Vec<File>, mut chunk: Vec<File>) {
+		let cmp = |a: &File, b: &File| match self.by {
+			SortBy::Natural => self.cmp_naturally(a, b),
+			_ => self.cmp_alphabetically(a, b),
+		};
+		if !chunk.is_sorted_by(|a, b| cmp(a, b).is_le()) {
+			chunk.sort_unstable_by(cmp);
+		}
+
+		let old = mem::take(items);
+		items.reserve(old.len() + chunk.len());
+
+		let (mut a, mut b) = (old.into_iter().peekable(), chunk.into_iter().peekable());
+		while let (Some(x), Some(y)) = (a.peek(), b.peek()) {
+			items.extend(if cmp(y, x).is_lt() { b.next() } else { a.next() });
+		}
+		items.extend(a.chain(b));
+	}
+
+	fn sort_naturally(&self, items: &mut [File]) {
+		items.sort_unstable_by(|a, b| self.cmp_naturally(a, b));
+	}
+
+	fn cmp_alphabetically(&self, a: &File, b: &File) -> Ordering {
+		let ord = if self.sensitive {
+			self.cmp(a.urn().encoded_bytes(), b.urn().encoded_bytes(), self.promote(a, b))
+		} else {
+			self.cmp_insensitive(a.urn().encoded_bytes(), b.urn().encoded_bytes(), self.promote(a, b))
+		};
+		// The matches of a grep in the same file, by their lines
+		ord.then_with(|| a.url.frag().cmp(&b.url.frag()))
+	}
+
+	fn cmp_naturally(&self, a: &File, b: &File) -> Ordering {
+		let promote = self.promote(a, b);
+		if promote != Ordering::Equal {
+			return promote;
+		}
+
+		let ordering = if self.translit {
+			natsort(
+				a.urn().encoded_bytes().transliterate().as_bytes(),
+				b.urn().encoded_bytes().transliterate().as_bytes(),
+				!self.sensitive,
+			)
+		} else {
+			natsort(a.urn().encoded_bytes(), b.urn().encoded_bytes(), !self.sensitive)
+		};
+
+		let ordering = if self.reverse { ordering.reverse() } else { ordering };
+		ordering.then_with(|| a.url.frag().cmp(&b.url.frag()))
 	}
 
 	#[inline(always)]
@@ -119,3 +227,79 @@ impl FilesSorter {
 		if self.dir_first { b.is_dir().cmp(&a.is_dir()) } else { Ordering::Equal }
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use std::path::Path;
+
+	use super::*;
+	use crate::cha::{Cha, ChaKind};
+
+	fn sort(sorter: FilesSorter, names: &[&str]) -> Vec<String> {
+		let mut files: Vec<_> = names
+			.iter()
+			.map(|&s| File {
+				url:     Path::new("/tmp").join(s.trim_end_matches('/')).into(),
+				cha:     Cha {
+					kind: if s.ends_with('/') { ChaKind::DIR } else { ChaKind::empty() },
+					..Default::default()
+				},
+				link_to: None,
+			})
+			.collect();
+
+		sorter.sort(&mut files, &HashMap::new());
+		files.iter().map(|f| f.urn().to_string_lossy().into_owned()).collect()
+	}
+
+	#[test]
+	fn test_version() {
+		let sorter = FilesSorter { by: SortBy::Version, ..Default::default() };
+		assert_eq!(sort(sorter, &["v1.10", "v1.2", "v1.2~rc1"]), ["v1.2~rc1", "v1.2", "v1.10"]);
+
+		let sorter = FilesSorter { by: SortBy::Version, dir_first: true, reverse: true, ..sorter };
+		assert_eq!(sort(sorter, &["a1", "a10/", "a2/"]), ["a10", "a2", "a1"]);
+	}
+
+	#[test]
+	fn test_merge() {
+		let sorter = FilesSorter { by: SortBy::Natural, ..Default::default() };
+		let files = |names: &[&str]| {
+			names.iter().map(|s| File::from_dummy(Path::new("/tmp").join(s).into(), None)).collect()
+		};
+
+		let mut items = files(&["a1", "a3", "a10"]);
+		sorter.merge(&mut items, files(&["a20", "a2", "a0"]));
+		let names: Vec<_> = items.iter().map(|f| f.urn().to_string_lossy().into_owned()).collect();
+		assert_eq!(names, ["a0", "a1", "a2", "a3", "a10", "a20"]);
+	}
+
+	#[test]
+	fn test_permute() {
+		let mut items = ['a', 'b', 'c', 'd'];
+		assert!(FilesSorter::permute(&mut items, &[2, 0, 3, 1]));
+		assert_eq!(items, ['c', 'a', 'd', 'b']);
+
+		// Out of range, duplicated, or of a different length
+		for order in [&[0, 1, 2, 4][..], &[0, 0, 1, 2], &[1, 0]] {
+			assert!(!FilesSorter::permute(&mut items, order));
+			assert_eq!(items, ['c', 'a', 'd', 'b']);
+		}
+	}
+
+	#[test]
+	fn test_custom() {
+		// By the length of the names, where the ties are kept in place
+		FilesSorter::hook(|files| {
+			let mut order: Vec<_> = (0..files.len()).collect();
+			order.sort_by_key(|&i| files[i].urn().encoded_bytes().len());
+			Some(order)
+		});
+
+		let sorter = FilesSorter { by: SortBy::Custom, ..Default::default() };
+		assert_eq!(sort(sorter, &["ccc", "a", "dd", "b"]), ["a", "b", "dd", "ccc"]);
+
+		let sorter = FilesSorter { reverse: true, dir_first: true, ..sorter };
+		assert_eq!(sort(sorter, &["ccc", "a/", "dd", "b"]), ["a", "ccc", "dd", "b"]);
+	}
+}
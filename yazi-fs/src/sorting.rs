@@ -14,6 +14,9 @@ pub enum SortBy {
 	Natural,
 	Size,
 	Random,
+	Version,
+	Owner,
+	Custom,
 }
 
 impl FromStr for SortBy {
@@ -35,6 +38,9 @@ impl Display for SortBy {
 			Self::Natural => "natural",
 			Self::Size => "size",
 			Self::Random => "random",
+			Self::Version => "version",
+			Self::Owner => "owner",
+			Self::Custom => "custom",
 		})
 	}
 }
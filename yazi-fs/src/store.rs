@@ -0,0 +1,114 @@
+use std::{hash::Hash, io, num::NonZeroUsize, path::Path};
+
+use anyhow::Result;
+use lru::LruCache;
+use parking_lot::Mutex;
+use serde::{Serialize, de::DeserializeOwned};
+
+// Writes to a temporary file next to the `path` first, so it's never left half-written
+pub(crate) fn write_atomic(path: &Path, buf: &[u8]) -> io::Result<()> {
+	if let Some(parent) = path.parent() {
+		std::fs::create_dir_all(parent)?;
+	}
+
+	let mut tmp = path.as_os_str().to_owned();
+	tmp.push(".tmp");
+	std::fs::write(&tmp, buf)?;
+	std::fs::rename(tmp, path)
+}
+
+// An LRU cache saved as a JSON list of the entries `E`, most recently used first
+pub(crate) struct LruStore<K, V> {
+	cache:  Mutex<Option<LruCache<K, V>>>,
+	saving: Mutex<()>,
+	cap:    usize,
+}
+
+impl<K: Clone + Eq + Hash, V: Clone> LruStore<K, V> {
+	pub(crate) const fn new(cap: usize) -> Self {
+		Self { cache: Mutex::new(None), saving: Mutex::new(()), cap }
+	}
+
+	pub(crate) fn get(&self, key: &K) -> Option<V> { self.cache.lock().as_mut()?.get(key).cloned() }
+
+	pub(crate) fn put(&self, key: K, value: V) {
+		let cap = NonZeroUsize::new(self.cap).unwrap();
+		self.cache.lock().get_or_insert_with(|| LruCache::new(cap)).put(key, value);
+	}
+
+	// Loads the entries saved by `save_to`, keeping those put in the meantime
+	pub(crate) fn load_from<E>(&self, path: &Path) -> Result<()>
+	where
+		E: DeserializeOwned + Into<(K, V)>,
+	{
+		let entries: Vec<E> = serde_json::from_slice(&std::fs::read(path)?)?;
+
+		let cap = NonZeroUsize::new(self.cap).unwrap();
+		let mut cache = self.cache.lock();
+		let cache = cache.get_or_insert_with(|| LruCache::new(cap));
+		for (key, value) in entries.into_iter().map(Into::into) {
+			if cache.len() >= self.cap {
+				break;
+			} else if !cache.contains(&key) {
+				cache.push(key.clone(), value);
+				cache.demote(&key);
+			}
+		}
+		Ok(())
+	}
+
+	pub(crate) fn save_to<E>(&self, path: &Path) -> Result<()>
+	where
+		E: Serialize + for<'a> From<(&'a K, &'a V)>,
+	{
+		let _guard = self.saving.lock();
+		let buf = {
+			let cache = self.cache.lock();
+			let Some(cache) = cache.as_ref() else { return Ok(()) };
+
+			// Most recently used first, which is the order they're loaded back in
+			serde_json::to_vec(&cache.iter().map(E::from).collect::<Vec<_>>())?
+		};
+		Ok(write_atomic(path, &buf)?)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use serde::Deserialize;
+
+	use super::*;
+	use crate::TempDir;
+
+	#[derive(Deserialize, Serialize)]
+	struct Entry(String, u64);
+
+	impl From<Entry> for (String, u64) {
+		fn from(e: Entry) -> Self { (e.0, e.1) }
+	}
+
+	impl From<(&String, &u64)> for Entry {
+		fn from((k, &v): (&String, &u64)) -> Self { Self(k.clone(), v) }
+	}
+
+	#[test]
+	fn test_lru_store() -> Result<()> {
+		let dir = TempDir::new("store")?;
+		let path = dir.join("store.json");
+
+		let store = LruStore::new(2);
+		for (k, v) in [("a", 1), ("b", 2), ("c", 3)] {
+			store.put(k.to_owned(), v);
+		}
+		store.save_to::<Entry>(&path)?;
+		assert!(!dir.join("store.json.tmp").exists());
+
+		// The ones put before loading take precedence, and the least recent are dropped
+		let store = LruStore::new(2);
+		store.put("c".to_owned(), 4);
+		store.load_from::<Entry>(&path)?;
+		assert_eq!(["a", "b", "c"].map(|k| store.get(&k.to_owned())), [None, Some(2), Some(4)]);
+
+		Ok(())
+	}
+}
@@ -0,0 +1,183 @@
+use std::{collections::{BTreeMap, BTreeSet}, path::{Path, PathBuf}, sync::atomic::{AtomicBool, Ordering}};
+
+use anyhow::Result;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use yazi_shared::url::Url;
+
+use crate::{Xdg, write_atomic};
+
+/// The tags of the files, persisted in the state directory.
+pub static TAGS: Tags = Tags::new();
+
+/// Named tags attached to files, each with an optional color to be shown in.
+pub struct Tags {
+	records: RwLock<Records>,
+	dirty:   AtomicBool,
+}
+
+#[derive(Debug, Default, Deserialize, PartialEq, Serialize)]
+struct Records {
+	colors: BTreeMap<String, String>,
+	files:  BTreeMap<Url, BTreeSet<String>>,
+}
+
+impl Tags {
+	const fn new() -> Self {
+		Self {
+			records: RwLock::new(Records { colors: BTreeMap::new(), files: BTreeMap::new() }),
+			dirty:   AtomicBool::new(false),
+		}
+	}
+
+	/// The tags of the file, with their colors, in the order of their names.
+	pub fn get(&self, url: &Url) -> Vec<(String, Option<String>)> {
+		let records = self.records.read();
+		let Some(tags) = records.files.get(&Self::key(url)) else { return vec![] };
+		tags.iter().map(|t| (t.clone(), records.colors.get(t).cloned())).collect()
+	}
+
+	/// Whether the file has the tag.
+	pub fn has(&self, url: &Url, tag: &str) -> bool {
+		self.records.read().files.get(&Self::key(url)).is_some_and(|t| t.contains(tag))
+	}
+
+	/// Adds the tag to the files, returning how many didn't have it.
+	pub fn add<'a>(&self, urls: impl IntoIterator<Item = &'a Url>, tag: &str) -> usize {
+		let mut records = self.records.write();
+		let n = urls
+			.into_iter()
+			.filter(|&u| records.files.entry(Self::key(u)).or_default().insert(tag.to_owned()))
+			.count();
+		self.touch(n > 0);
+		n
+	}
+
+	/// Removes the tag from the files, or all the tags if `tag` is `None`, returning how many had it.
+	pub fn remove<'a>(&self, urls: impl IntoIterator<Item = &'a Url>, tag: Option<&str>) -> usize {
+		let mut records = self.records.write();
+		let mut n = 0;
+		for url in urls {
+			let key = Self::key(url);
+			let Some(tags) = records.files.get_mut(&key) else { continue };
+
+			n += match tag {
+				Some(tag) => tags.remove(tag) as usize,
+				None => !tags.is_empty() as usize,
+			};
+			if tag.is_none() || tags.is_empty() {
+				records.files.remove(&key);
+			}
+		}
+		self.touch(n > 0);
+		n
+	}
+
+	/// Sets the color the tag is shown in, or resets it to the default.
+	pub fn set_color(&self, tag: &str, color: Option<&str>) {
+		let mut records = self.records.write();
+		let changed = match color {
+			Some(c) => records.colors.insert(tag.to_owned(), c.to_owned()).as_deref() != Some(c),
+			None => records.colors.remove(tag).is_some(),
+		};
+		self.touch(changed);
+	}
+
+	/// Moves the tags of a file, and of the files under it, to where it's moved.
+	pub fn rename(&self, from: &Url, to: &Url) {
+		let (from, to) = (Self::key(from), Self::key(to));
+		let mut records = self.records.write();
+
+		let moved: Vec<_> = records
+			.files
+			.keys()
+			.filter_map(|k| Some((k.clone(), k.rebase_keeping_structure(&from, &to)?)))
+			.collect();
+		for (old, new) in &moved {
+			let tags = records.files.remove(old).unwrap();
+			records.files.insert(new.clone(), tags);
+		}
+		self.touch(!moved.is_empty());
+	}
+
+	/// Loads the tags saved by [`Self::save`].
+	pub fn load(&self) -> Result<()> { self.load_from(&Self::path()) }
+
+	/// Saves the tags to the state directory if they have changed, blocking the current thread.
+	pub fn save(&self) -> Result<()> {
+		if self.dirty.swap(false, Ordering::Relaxed) { self.save_to(&Self::path()) } else { Ok(()) }
+	}
+
+	// Listed files are tagged as the ones they stand for
+	fn key(url: &Url) -> Url {
+		if url.is_listing() {
+			url.to_regular()
+		} else {
+			url.clone()
+		}
+	}
+
+	#[inline]
+	fn touch(&self, changed: bool) {
+		if changed {
+			self.dirty.store(true, Ordering::Relaxed);
+		}
+	}
+
+	fn load_from(&self, path: &Path) -> Result<()> {
+		*self.records.write() = serde_json::from_slice(&std::fs::read(path)?)?;
+		Ok(())
+	}
+
+	fn save_to(&self, path: &Path) -> Result<()> {
+		Ok(write_atomic(path, &serde_json::to_vec(&*self.records.read())?)?)
+	}
+
+	#[inline]
+	fn path() -> PathBuf { Xdg::state_dir().join("tags.json") }
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::TempDir;
+
+	#[test]
+	fn test_tags() -> Result<()> {
+		let tags = Tags::new();
+		let url = |s: &str| -> Url { s.parse().unwrap() };
+		let names = |u: &str| tags.get(&url(u)).into_iter().map(|(t, _)| t).collect::<Vec<_>>();
+
+		assert_eq!(tags.add(&[url("/a/b"), url("/a/c"), url("/d")], "work"), 3);
+		assert_eq!(tags.add(&[url("/a/b")], "todo"), 1);
+		assert_eq!(tags.add(&[url("/a/b")], "work"), 0);
+		tags.set_color("work", Some("red"));
+		assert_eq!(
+			tags.get(&url("/a/b")),
+			[("todo".to_owned(), None), ("work".to_owned(), Some("red".to_owned()))]
+		);
+
+		// Search results are the files they stand for
+		assert!(tags.has(&url("search://kw//a/c"), "work"));
+
+		// A directory takes the tags of its children with it
+		tags.rename(&url("/a"), &url("/e/a"));
+		assert_eq!(names("/e/a/b"), ["todo", "work"]);
+		assert!(names("/a/b").is_empty());
+
+		assert_eq!(tags.remove(&[url("/e/a/b"), url("/d")], Some("work")), 2);
+		assert_eq!(tags.remove(&[url("/e/a/c")], None), 1);
+		assert_eq!(names("/e/a/b"), ["todo"]);
+		assert_eq!(tags.records.read().files.len(), 1);
+
+		// Round trip
+		let dir = TempDir::new("tags")?;
+		let path = dir.join("tags.json");
+		tags.save_to(&path)?;
+		let loaded = Tags::new();
+		loaded.load_from(&path)?;
+		assert_eq!(*loaded.records.read(), *tags.records.read());
+
+		Ok(())
+	}
+}
@@ -0,0 +1,10 @@
+use parking_lot::RwLock;
+
+/// How much the trash takes up, or `None` if it hasn't been measured yet.
+pub static TRASH_USAGE: RwLock<Option<TrashUsage>> = RwLock::new(None);
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct TrashUsage {
+	pub items: usize,
+	pub size:  u64,
+}
@@ -0,0 +1,53 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use yazi_shared::url::Url;
+
+use crate::{FilesSorter, Filter, LruStore, Xdg};
+
+/// The view settings of the directories, persisted in the state directory.
+pub static VIEWS: Views = Views::new();
+
+/// How each directory was last viewed, keyed by the hash of its URL.
+pub struct Views(LruStore<u64, View>);
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct View {
+	pub sorter:      FilesSorter,
+	pub linemode:    String,
+	pub show_hidden: bool,
+	pub filter:      Option<Filter>,
+}
+
+#[derive(Deserialize, Serialize)]
+struct Entry {
+	key:  u64,
+	view: View,
+}
+
+impl From<Entry> for (u64, View) {
+	fn from(e: Entry) -> Self { (e.key, e.view) }
+}
+
+impl From<(&u64, &View)> for Entry {
+	fn from((&key, view): (&u64, &View)) -> Self { Self { key, view: view.clone() } }
+}
+
+impl Views {
+	const fn new() -> Self { Self(LruStore::new(1000)) }
+
+	/// The view the directory was last left in, if any.
+	pub fn get(&self, dir: &Url) -> Option<View> { self.0.get(&dir.hash_u64()) }
+
+	pub fn set(&self, dir: &Url, view: View) { self.0.put(dir.hash_u64(), view); }
+
+	/// Loads the views saved by [`Self::save`], keeping those set in the meantime.
+	pub fn load(&self) -> Result<()> { self.0.load_from::<Entry>(&Self::path()) }
+
+	/// Saves the views to the state directory, blocking the current thread.
+	pub fn save(&self) -> Result<()> { self.0.save_to::<Entry>(&Self::path()) }
+
+	#[inline]
+	fn path() -> PathBuf { Xdg::state_dir().join("views.json") }
+}
@@ -0,0 +1,281 @@
+use std::{ffi::{OsStr, OsString}, fmt::Write, io, path::PathBuf};
+
+use yazi_shared::url::Url;
+
+use crate::provider;
+
+/// Extended attributes of files, including the POSIX ACLs and SELinux labels Linux keeps in them.
+pub struct Xattr;
+
+impl Xattr {
+	/// The names and values of the attributes of the file.
+	pub async fn list(url: &Url) -> io::Result<Vec<(OsString, Vec<u8>)>> {
+		let path = Self::path(url).await?;
+		Self::blocking(move || {
+			let names = sys::list(&path)?;
+			let mut attrs = Vec::with_capacity(names.len());
+			for name in names {
+				match sys::get(&path, &name) {
+					Ok(value) => attrs.push((name, value)),
+					// Removed in between, or hidden from the current user
+					Err(e) if Self::skippable(&e) => {}
+					Err(e) => return Err(e),
+				}
+			}
+			Ok(attrs)
+		})
+		.await
+	}
+
+	/// Sets the attribute of the file, creating it if it doesn't exist.
+	pub async fn set(url: &Url, name: &OsStr, value: &[u8]) -> io::Result<()> {
+		let (path, name, value) = (Self::path(url).await?, name.to_owned(), value.to_owned());
+		Self::blocking(move || sys::set(&path, &name, &value)).await
+	}
+
+	/// Removes the attribute from the file.
+	pub async fn remove(url: &Url, name: &OsStr) -> io::Result<()> {
+		let (path, name) = (Self::path(url).await?, name.to_owned());
+		Self::blocking(move || sys::remove(&path, &name)).await
+	}
+
+	/// Copies the attributes of a file to another, skipping the ones that can't be set.
+	pub async fn copy(from: &Url, to: &Url) -> io::Result<()> {
+		let attrs = match Self::list(from).await {
+			Err(e) if Self::skippable(&e) => return Ok(()),
+			r => r?,
+		};
+
+		let to = Self::path(to).await?;
+		Self::blocking(move || {
+			for (name, value) in attrs {
+				match sys::set(&to, &name, &value) {
+					Err(e) if !Self::skippable(&e) => return Err(e),
+					_ => {}
+				}
+			}
+			Ok(())
+		})
+		.await
+	}
+
+	/// The value of the attribute in a readable form.
+	pub fn display(name: &OsStr, value: &[u8]) -> String {
+		if let Some(acl) = Self::acl(name, value) {
+			return acl;
+		}
+
+		let value = value.strip_suffix(b"\0").unwrap_or(value);
+		match str::from_utf8(value) {
+			Ok(s) if !s.chars().any(char::is_control) => s.to_owned(),
+			_ => value.iter().fold("0x".to_owned(), |mut s, b| {
+				_ = write!(s, "{b:02x}");
+				s
+			}),
+		}
+	}
+
+	/// Decodes the POSIX ACL that Linux keeps in the `system.posix_acl_*` attributes.
+	fn acl(name: &OsStr, value: &[u8]) -> Option<String> {
+		let prefix = match name.as_encoded_bytes() {
+			b"system.posix_acl_access" => "",
+			b"system.posix_acl_default" => "default:",
+			_ => return None,
+		};
+
+		let (version, entries) = value.split_first_chunk::<4>()?;
+		if u32::from_le_bytes(*version) != 2 || entries.len() % 8 != 0 {
+			return None;
+		}
+
+		let mut out = vec![];
+		for e in entries.chunks_exact(8) {
+			let tag = u16::from_le_bytes([e[0], e[1]]);
+			let perm = u16::from_le_bytes([e[2], e[3]]);
+			let id = u32::from_le_bytes([e[4], e[5], e[6], e[7]]);
+
+			let qualified = |kind: &str| format!("{prefix}{kind}:{id}:");
+			let head = match tag {
+				0x01 => format!("{prefix}user::"),
+				0x02 => qualified("user"),
+				0x04 => format!("{prefix}group::"),
+				0x08 => qualified("group"),
+				0x10 => format!("{prefix}mask::"),
+				0x20 => format!("{prefix}other::"),
+				_ => return None,
+			};
+
+			let bit = |b, c| if perm & b != 0 { c } else { '-' };
+			out.push(format!("{head}{}{}{}", bit(4, 'r'), bit(2, 'w'), bit(1, 'x')));
+		}
+		Some(out.join(","))
+	}
+
+	async fn path(url: &Url) -> io::Result<PathBuf> {
+		Ok(provider::local_path(url).await?.into_owned())
+	}
+
+	async fn blocking<T: Send + 'static>(
+		f: impl FnOnce() -> io::Result<T> + Send + 'static,
+	) -> io::Result<T> {
+		tokio::task::spawn_blocking(f).await?
+	}
+
+	// Not supported by the filesystem, not permitted, or no longer there
+	fn skippable(e: &io::Error) -> bool {
+		use io::ErrorKind::{NotFound, PermissionDenied, Unsupported};
+		matches!(e.kind(), NotFound | PermissionDenied | Unsupported)
+			|| e.raw_os_error() == Some(sys::ENODATA)
+	}
+}
+
+#[cfg(any(target_os = "linux", target_os = "android", target_os = "macos"))]
+mod sys {
+	use std::{ffi::{CString, OsStr, OsString}, io, os::unix::ffi::{OsStrExt, OsStringExt}, path::Path, ptr};
+
+	#[cfg(target_os = "macos")]
+	pub(super) const ENODATA: i32 = libc::ENOATTR;
+	#[cfg(not(target_os = "macos"))]
+	pub(super) const ENODATA: i32 = libc::ENODATA;
+
+	pub(super) fn list(path: &Path) -> io::Result<Vec<OsString>> {
+		let path = cstr(path.as_os_str())?;
+		let buf = sized(|buf, len| unsafe {
+			#[cfg(target_os = "macos")]
+			{
+				libc::listxattr(path.as_ptr(), buf.cast(), len, 0)
+			}
+			#[cfg(not(target_os = "macos"))]
+			{
+				libc::listxattr(path.as_ptr(), buf.cast(), len)
+			}
+		})?;
+
+		let names = buf.split(|&b| b == 0).filter(|s| !s.is_empty());
+		Ok(names.map(|s| OsString::from_vec(s.to_vec())).collect())
+	}
+
+	pub(super) fn get(path: &Path, name: &OsStr) -> io::Result<Vec<u8>> {
+		let (path, name) = (cstr(path.as_os_str())?, cstr(name)?);
+		sized(|buf, len| unsafe {
+			#[cfg(target_os = "macos")]
+			{
+				libc::getxattr(path.as_ptr(), name.as_ptr(), buf.cast(), len, 0, 0)
+			}
+			#[cfg(not(target_os = "macos"))]
+			{
+				libc::getxattr(path.as_ptr(), name.as_ptr(), buf.cast(), len)
+			}
+		})
+	}
+
+	pub(super) fn set(path: &Path, name: &OsStr, value: &[u8]) -> io::Result<()> {
+		let (path, name) = (cstr(path.as_os_str())?, cstr(name)?);
+		let (ptr, len) = (value.as_ptr().cast(), value.len());
+		let ret = unsafe {
+			#[cfg(target_os = "macos")]
+			{
+				libc::setxattr(path.as_ptr(), name.as_ptr(), ptr, len, 0, 0)
+			}
+			#[cfg(not(target_os = "macos"))]
+			{
+				libc::setxattr(path.as_ptr(), name.as_ptr(), ptr, len, 0)
+			}
+		};
+		if ret == 0 { Ok(()) } else { Err(io::Error::last_os_error()) }
+	}
+
+	pub(super) fn remove(path: &Path, name: &OsStr) -> io::Result<()> {
+		let (path, name) = (cstr(path.as_os_str())?, cstr(name)?);
+		let ret = unsafe {
+			#[cfg(target_os = "macos")]
+			{
+				libc::removexattr(path.as_ptr(), name.as_ptr(), 0)
+			}
+			#[cfg(not(target_os = "macos"))]
+			{
+				libc::removexattr(path.as_ptr(), name.as_ptr())
+			}
+		};
+		if ret == 0 { Ok(()) } else { Err(io::Error::last_os_error()) }
+	}
+
+	fn cstr(s: &OsStr) -> io::Result<CString> {
+		CString::new(s.as_bytes()).map_err(|_| io::Error::from(io::ErrorKind::InvalidInput))
+	}
+
+	// Sizes the buffer with a first call, and tries again if the value grew
+	// before the second one
+	fn sized(mut f: impl FnMut(*mut u8, usize) -> isize) -> io::Result<Vec<u8>> {
+		loop {
+			let len = f(ptr::null_mut(), 0);
+			if len < 0 {
+				return Err(io::Error::last_os_error());
+			}
+
+			let mut buf = vec![0; len as usize];
+			match f(buf.as_mut_ptr(), buf.len()) {
+				n if n >= 0 => {
+					buf.truncate(n as usize);
+					return Ok(buf);
+				}
+				_ => {
+					let e = io::Error::last_os_error();
+					if e.raw_os_error() != Some(libc::ERANGE) {
+						return Err(e);
+					}
+				}
+			}
+		}
+	}
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android", target_os = "macos")))]
+mod sys {
+	use std::{ffi::{OsStr, OsString}, io, path::Path};
+
+	pub(super) const ENODATA: i32 = -1;
+
+	pub(super) fn list(_: &Path) -> io::Result<Vec<OsString>> { Err(unsupported()) }
+
+	pub(super) fn get(_: &Path, _: &OsStr) -> io::Result<Vec<u8>> { Err(unsupported()) }
+
+	pub(super) fn set(_: &Path, _: &OsStr, _: &[u8]) -> io::Result<()> { Err(unsupported()) }
+
+	pub(super) fn remove(_: &Path, _: &OsStr) -> io::Result<()> { Err(unsupported()) }
+
+	fn unsupported() -> io::Error {
+		io::Error::new(io::ErrorKind::Unsupported, "Extended attributes are not supported")
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_display() {
+		let acl = [
+			&2u32.to_le_bytes()[..],
+			&[0x01, 0, 6, 0, 0xff, 0xff, 0xff, 0xff],
+			&[0x02, 0, 4, 0, 0xe8, 0x03, 0, 0],
+			&[0x04, 0, 5, 0, 0xff, 0xff, 0xff, 0xff],
+			&[0x10, 0, 7, 0, 0xff, 0xff, 0xff, 0xff],
+			&[0x20, 0, 0, 0, 0xff, 0xff, 0xff, 0xff],
+		]
+		.concat();
+
+		let display = |name: &str, value: &[u8]| Xattr::display(OsStr::new(name), value);
+		assert_eq!(
+			display("system.posix_acl_access", &acl),
+			"user::rw-,user:1000:r--,group::r-x,mask::rwx,other::---"
+		);
+		assert!(display("system.posix_acl_default", &acl).starts_with("default:user::rw-,"));
+
+		assert_eq!(
+			display("security.selinux", b"user_u:object_r:user_home_t:s0\0"),
+			"user_u:object_r:user_home_t:s0"
+		);
+		assert_eq!(display("user.checksum", &[0xde, 0xad, 0x01]), "0xdead01");
+	}
+}
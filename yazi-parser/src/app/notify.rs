@@ -4,13 +4,14 @@ use anyhow::anyhow;
 use mlua::{ExternalError, ExternalResult};
 use serde::Deserialize;
 use yazi_config::{Style, THEME};
-use yazi_shared::event::CmdCow;
+use yazi_shared::{Layer, Source, event::{Cmd, CmdCow}};
 
 pub struct NotifyOpt {
 	pub title:   String,
 	pub content: String,
 	pub level:   NotifyLevel,
 	pub timeout: Duration,
+	pub action:  Option<NotifyAction>,
 }
 
 impl TryFrom<CmdCow> for NotifyOpt {
@@ -41,10 +42,41 @@ impl TryFrom<mlua::Table> for NotifyOpt {
 			content: t.raw_get("content")?,
 			level,
 			timeout: Duration::from_secs_f64(timeout),
+			action: t.raw_get::<Option<mlua::Table>>("action")?.map(TryInto::try_into).transpose()?,
 		})
 	}
 }
 
+// --- Action
+/// The command to run from the notification history.
+#[derive(Clone, Debug)]
+pub struct NotifyAction {
+	pub desc: String,
+	pub run:  String,
+}
+
+impl NotifyAction {
+	/// The command to run, in the `mgr` layer unless given one.
+	pub fn cmd(&self) -> anyhow::Result<Cmd> {
+		let mut cmd = Cmd::from_str(&self.run)?;
+		if cmd.layer == Layer::App {
+			cmd.layer = Layer::Mgr;
+		}
+		cmd.source = Source::Emit;
+		Ok(cmd)
+	}
+}
+
+impl TryFrom<mlua::Table> for NotifyAction {
+	type Error = mlua::Error;
+
+	fn try_from(t: mlua::Table) -> Result<Self, Self::Error> {
+		let me = Self { desc: t.raw_get("desc")?, run: t.raw_get("run")? };
+		me.cmd().into_lua_err()?;
+		Ok(me)
+	}
+}
+
 // --- Level
 #[derive(Clone, Copy, Default, Deserialize, Eq, PartialEq)]
 #[serde(rename_all = "kebab-case")]
@@ -27,4 +27,12 @@ pub struct TasksProgress {
 
 	pub found:     u64,
 	pub processed: u64,
+
+	/// The bytes processed per second, averaged over the last few seconds.
+	pub speed:  u64,
+	/// The speeds twice a second, the oldest first, for a graph.
+	pub speeds: [u64; 32],
+	/// The seconds left at that speed, if it's known.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub eta:    Option<u64>,
 }
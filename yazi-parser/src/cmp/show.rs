@@ -8,6 +8,7 @@ use yazi_shared::{Id, event::CmdCow, url::{Url, UrnBuf}};
 pub struct ShowOpt {
 	pub cache:      Vec<CmpItem>,
 	pub cache_name: Url,
+	pub alias:      Option<Url>,
 	pub word:       UrnBuf,
 	pub ticket:     Id,
 }
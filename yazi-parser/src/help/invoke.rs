@@ -0,0 +1,19 @@
+use mlua::{ExternalError, FromLua, IntoLua, Lua, Value};
+use yazi_shared::event::CmdCow;
+
+#[derive(Debug)]
+pub struct InvokeOpt {
+	pub prompt: bool,
+}
+
+impl From<CmdCow> for InvokeOpt {
+	fn from(c: CmdCow) -> Self { Self { prompt: c.bool("prompt") } }
+}
+
+impl FromLua for InvokeOpt {
+	fn from_lua(_: Value, _: &Lua) -> mlua::Result<Self> { Err("unsupported".into_lua_err()) }
+}
+
+impl IntoLua for InvokeOpt {
+	fn into_lua(self, _: &Lua) -> mlua::Result<Value> { Err("unsupported".into_lua_err()) }
+}
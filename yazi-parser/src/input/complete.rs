@@ -7,6 +7,7 @@ use crate::cmp::CmpItem;
 #[derive(Debug)]
 pub struct CompleteOpt {
 	pub item:    CmpItem,
+	pub parent:  Option<String>,
 	pub _ticket: Id, // FIXME: not used
 }
 
@@ -18,7 +19,11 @@ impl TryFrom<CmdCow> for CompleteOpt {
 			bail!("Invalid 'item' in CompleteOpt");
 		};
 
-		Ok(Self { item, _ticket: c.id("ticket").unwrap_or_default() })
+		Ok(Self {
+			item,
+			parent: c.take_str("parent").map(|s| s.into_owned()),
+			_ticket: c.id("ticket").unwrap_or_default(),
+		})
 	}
 }
 
@@ -0,0 +1,59 @@
+use std::str::FromStr;
+
+use anyhow::bail;
+use mlua::{ExternalError, FromLua, IntoLua, Lua, Value};
+use serde::Deserialize;
+use yazi_shared::{SStr, event::CmdCow};
+
+#[derive(Debug)]
+pub struct BookmarkOpt {
+	pub action: BookmarkOptAction,
+	pub key:    Option<SStr>,
+	pub cwd:    bool,
+}
+
+impl TryFrom<CmdCow> for BookmarkOpt {
+	type Error = anyhow::Error;
+
+	fn try_from(mut c: CmdCow) -> Result<Self, Self::Error> {
+		let Some(action) = c.first_str() else {
+			bail!("Missing action in BookmarkOpt");
+		};
+
+		let action: BookmarkOptAction = action.parse()?;
+		let key = c.take_str(1);
+		if key.is_none() && action == BookmarkOptAction::Delete {
+			bail!("Missing the key of the bookmark in BookmarkOpt");
+		}
+
+		Ok(Self { action, key, cwd: c.bool("cwd") })
+	}
+}
+
+impl FromLua for BookmarkOpt {
+	fn from_lua(_: Value, _: &Lua) -> mlua::Result<Self> { Err("unsupported".into_lua_err()) }
+}
+
+impl IntoLua for BookmarkOpt {
+	fn into_lua(self, _: &Lua) -> mlua::Result<Value> { Err("unsupported".into_lua_err()) }
+}
+
+// --- Action
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum BookmarkOptAction {
+	/// Marks the hovered file, or the CWD with `--cwd`, under the key.
+	Set,
+	/// Jumps to the target of the key, or the one picked from all of them.
+	Jump,
+	/// Forgets the bookmark.
+	Delete,
+}
+
+impl FromStr for BookmarkOptAction {
+	type Err = serde::de::value::Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		Self::deserialize(serde::de::value::StrDeserializer::new(s))
+	}
+}
@@ -0,0 +1,19 @@
+use mlua::{ExternalError, FromLua, IntoLua, Lua, Value};
+use yazi_shared::{SStr, event::CmdCow};
+
+#[derive(Debug, Default)]
+pub struct BulkRenameOpt {
+	pub expr: Option<SStr>,
+}
+
+impl From<CmdCow> for BulkRenameOpt {
+	fn from(mut c: CmdCow) -> Self { Self { expr: c.take_first_str().filter(|s| !s.is_empty()) } }
+}
+
+impl FromLua for BulkRenameOpt {
+	fn from_lua(_: Value, _: &Lua) -> mlua::Result<Self> { Err("unsupported".into_lua_err()) }
+}
+
+impl IntoLua for BulkRenameOpt {
+	fn into_lua(self, _: &Lua) -> mlua::Result<Value> { Err("unsupported".into_lua_err()) }
+}
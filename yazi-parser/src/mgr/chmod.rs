@@ -0,0 +1,22 @@
+use mlua::{ExternalError, FromLua, IntoLua, Lua, Value};
+use yazi_shared::{SStr, event::CmdCow};
+
+#[derive(Debug)]
+pub struct ChmodOpt {
+	pub mode:      Option<SStr>,
+	pub recursive: bool,
+}
+
+impl From<CmdCow> for ChmodOpt {
+	fn from(mut c: CmdCow) -> Self {
+		Self { mode: c.take_first_str(), recursive: c.bool("recursive") }
+	}
+}
+
+impl FromLua for ChmodOpt {
+	fn from_lua(_: Value, _: &Lua) -> mlua::Result<Self> { Err("unsupported".into_lua_err()) }
+}
+
+impl IntoLua for ChmodOpt {
+	fn into_lua(self, _: &Lua) -> mlua::Result<Value> { Err("unsupported".into_lua_err()) }
+}
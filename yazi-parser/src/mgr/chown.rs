@@ -0,0 +1,22 @@
+use mlua::{ExternalError, FromLua, IntoLua, Lua, Value};
+use yazi_shared::{SStr, event::CmdCow};
+
+#[derive(Debug)]
+pub struct ChownOpt {
+	pub owner:     Option<SStr>,
+	pub recursive: bool,
+}
+
+impl From<CmdCow> for ChownOpt {
+	fn from(mut c: CmdCow) -> Self {
+		Self { owner: c.take_first_str(), recursive: c.bool("recursive") }
+	}
+}
+
+impl FromLua for ChownOpt {
+	fn from_lua(_: Value, _: &Lua) -> mlua::Result<Self> { Err("unsupported".into_lua_err()) }
+}
+
+impl IntoLua for ChownOpt {
+	fn into_lua(self, _: &Lua) -> mlua::Result<Value> { Err("unsupported".into_lua_err()) }
+}
@@ -0,0 +1,57 @@
+use std::str::FromStr;
+
+use mlua::{ExternalError, FromLua, IntoLua, Lua, Value};
+use serde::{Deserialize, Serialize};
+use yazi_fs::CompareBy;
+use yazi_shared::{event::CmdCow, url::Url};
+
+#[derive(Debug, Default)]
+pub struct CompareOpt {
+	pub action: CompareOptAction,
+	pub url:    Option<Url>,
+	pub by:     CompareBy,
+}
+
+impl TryFrom<CmdCow> for CompareOpt {
+	type Error = anyhow::Error;
+
+	fn try_from(mut c: CmdCow) -> Result<Self, Self::Error> {
+		Ok(Self {
+			action: c.first_str().map(FromStr::from_str).transpose()?.unwrap_or_default(),
+			url:    c.take_url(1),
+			by:     c.str("by").map(FromStr::from_str).transpose()?.unwrap_or_default(),
+		})
+	}
+}
+
+impl FromLua for CompareOpt {
+	fn from_lua(_: Value, _: &Lua) -> mlua::Result<Self> { Err("unsupported".into_lua_err()) }
+}
+
+impl IntoLua for CompareOpt {
+	fn into_lua(self, _: &Lua) -> mlua::Result<Value> { Err("unsupported".into_lua_err()) }
+}
+
+// --- Action
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CompareOptAction {
+	/// Compare the current directory with the given URL, or with the directory of the other panel.
+	#[default]
+	Start,
+	Stop,
+	/// Toggle showing only the files that differ.
+	Filter,
+	/// Copy the selected files that differ to the other side.
+	Sync,
+	/// Forget the difference of the given file, once it's been synced.
+	Resolve,
+}
+
+impl FromStr for CompareOptAction {
+	type Err = serde::de::value::Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		Self::deserialize(serde::de::value::StrDeserializer::new(s))
+	}
+}
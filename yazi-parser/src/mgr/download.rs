@@ -0,0 +1,19 @@
+use mlua::{ExternalError, FromLua, IntoLua, Lua, Value};
+use yazi_shared::{SStr, event::CmdCow};
+
+#[derive(Debug)]
+pub struct DownloadOpt {
+	pub url: Option<SStr>,
+}
+
+impl From<CmdCow> for DownloadOpt {
+	fn from(mut c: CmdCow) -> Self { Self { url: c.take_first_str() } }
+}
+
+impl FromLua for DownloadOpt {
+	fn from_lua(_: Value, _: &Lua) -> mlua::Result<Self> { Err("unsupported".into_lua_err()) }
+}
+
+impl IntoLua for DownloadOpt {
+	fn into_lua(self, _: &Lua) -> mlua::Result<Value> { Err("unsupported".into_lua_err()) }
+}
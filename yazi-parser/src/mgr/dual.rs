@@ -0,0 +1,54 @@
+use std::str::FromStr;
+
+use mlua::{ExternalError, FromLua, IntoLua, Lua, Value};
+use serde::{Deserialize, Serialize};
+use yazi_shared::event::CmdCow;
+
+#[derive(Debug, Default)]
+pub struct DualOpt {
+	pub action: DualOptAction,
+	pub force:  bool,
+}
+
+impl TryFrom<CmdCow> for DualOpt {
+	type Error = anyhow::Error;
+
+	fn try_from(c: CmdCow) -> Result<Self, Self::Error> {
+		Ok(Self {
+			action: c.first_str().map(FromStr::from_str).transpose()?.unwrap_or_default(),
+			force:  c.bool("force"),
+		})
+	}
+}
+
+impl FromLua for DualOpt {
+	fn from_lua(_: Value, _: &Lua) -> mlua::Result<Self> { Err("unsupported".into_lua_err()) }
+}
+
+impl IntoLua for DualOpt {
+	fn into_lua(self, _: &Lua) -> mlua::Result<Value> { Err("unsupported".into_lua_err()) }
+}
+
+// --- Action
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DualOptAction {
+	#[default]
+	Toggle,
+	On,
+	Off,
+	/// Focus the other panel.
+	Focus,
+	/// Copy the selected files to the directory of the other panel.
+	Copy,
+	/// Move the selected files to the directory of the other panel.
+	Move,
+}
+
+impl FromStr for DualOptAction {
+	type Err = serde::de::value::Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		Self::deserialize(serde::de::value::StrDeserializer::new(s))
+	}
+}
@@ -0,0 +1,51 @@
+use std::str::FromStr;
+
+use anyhow::bail;
+use mlua::{ExternalError, FromLua, IntoLua, Lua, Value};
+use serde::Deserialize;
+use yazi_shared::event::CmdCow;
+
+#[derive(Debug)]
+pub struct DupesOpt {
+	pub action: DupesOptAction,
+}
+
+impl TryFrom<CmdCow> for DupesOpt {
+	type Error = anyhow::Error;
+
+	fn try_from(c: CmdCow) -> Result<Self, Self::Error> {
+		let Some(action) = c.first_str() else {
+			bail!("Missing action in DupesOpt");
+		};
+
+		Ok(Self { action: action.parse()? })
+	}
+}
+
+impl FromLua for DupesOpt {
+	fn from_lua(_: Value, _: &Lua) -> mlua::Result<Self> { Err("unsupported".into_lua_err()) }
+}
+
+impl IntoLua for DupesOpt {
+	fn into_lua(self, _: &Lua) -> mlua::Result<Value> { Err("unsupported".into_lua_err()) }
+}
+
+// --- Action
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum DupesOptAction {
+	/// Finds the duplicates among the selected files, or in the CWD.
+	Find,
+	/// Selects all the duplicates but the newest one of each group.
+	Select,
+	/// Replaces the duplicates with hardlinks to the newest one of their group.
+	Link,
+}
+
+impl FromStr for DupesOptAction {
+	type Err = serde::de::value::Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		Self::deserialize(serde::de::value::StrDeserializer::new(s))
+	}
+}
@@ -0,0 +1,27 @@
+use mlua::{ExternalError, FromLua, IntoLua, Lua, Value};
+use yazi_shared::{event::CmdCow, url::Url};
+
+#[derive(Debug)]
+pub struct ExtractOpt {
+	pub targets: Vec<Url>,
+	pub to:      Option<Url>,
+}
+
+impl From<CmdCow> for ExtractOpt {
+	fn from(mut c: CmdCow) -> Self {
+		let mut targets = vec![];
+		while let Some(u) = c.take(targets.len()).and_then(|d| d.into_url()) {
+			targets.push(u);
+		}
+
+		Self { targets, to: c.take("to").and_then(|d| d.into_url()) }
+	}
+}
+
+impl FromLua for ExtractOpt {
+	fn from_lua(_: Value, _: &Lua) -> mlua::Result<Self> { Err("unsupported".into_lua_err()) }
+}
+
+impl IntoLua for ExtractOpt {
+	fn into_lua(self, _: &Lua) -> mlua::Result<Value> { Err("unsupported".into_lua_err()) }
+}
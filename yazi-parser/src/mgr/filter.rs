@@ -7,6 +7,8 @@ pub struct FilterOpt {
 	pub query: SStr,
 	pub case:  FilterCase,
 	pub done:  bool,
+	/// Shows only the files with the tag, instead of matching their names.
+	pub tag:   Option<SStr>,
 }
 
 impl TryFrom<CmdCow> for FilterOpt {
@@ -21,6 +23,7 @@ impl TryFrom<CmdCow> for FilterOpt {
 			query: c.take_first_str().unwrap_or_default(),
 			case:  FilterCase::from(&*c),
 			done:  c.bool("done"),
+			tag:   c.take_str("tag"),
 		})
 	}
 }
@@ -0,0 +1,21 @@
+use mlua::{ExternalError, FromLua, IntoLua, Lua, Value};
+use yazi_shared::event::{CmdCow, Data};
+
+#[derive(Debug)]
+pub struct FlattenOpt {
+	pub depth: usize,
+}
+
+impl From<CmdCow> for FlattenOpt {
+	fn from(c: CmdCow) -> Self {
+		Self { depth: c.get("depth").and_then(Data::as_usize).unwrap_or(3).max(1) }
+	}
+}
+
+impl FromLua for FlattenOpt {
+	fn from_lua(_: Value, _: &Lua) -> mlua::Result<Self> { Err("unsupported".into_lua_err()) }
+}
+
+impl IntoLua for FlattenOpt {
+	fn into_lua(self, _: &Lua) -> mlua::Result<Value> { Err("unsupported".into_lua_err()) }
+}
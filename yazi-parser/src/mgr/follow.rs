@@ -0,0 +1,19 @@
+use mlua::{ExternalError, FromLua, IntoLua, Lua, Value};
+use yazi_shared::event::CmdCow;
+
+#[derive(Debug, Default)]
+pub struct FollowOpt {
+	pub resolve: bool,
+}
+
+impl From<CmdCow> for FollowOpt {
+	fn from(c: CmdCow) -> Self { Self { resolve: c.bool("resolve") } }
+}
+
+impl FromLua for FollowOpt {
+	fn from_lua(_: Value, _: &Lua) -> mlua::Result<Self> { Err("unsupported".into_lua_err()) }
+}
+
+impl IntoLua for FollowOpt {
+	fn into_lua(self, _: &Lua) -> mlua::Result<Value> { Err("unsupported".into_lua_err()) }
+}
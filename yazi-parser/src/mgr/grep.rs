@@ -0,0 +1,34 @@
+use anyhow::bail;
+use mlua::{ExternalError, FromLua, IntoLua, Lua, Value};
+use yazi_shared::{SStr, event::CmdCow};
+
+#[derive(Debug)]
+pub struct GrepOpt {
+	pub subject:  SStr,
+	pub args:     Vec<String>,
+	pub args_raw: SStr,
+}
+
+impl TryFrom<CmdCow> for GrepOpt {
+	type Error = anyhow::Error;
+
+	fn try_from(mut c: CmdCow) -> Result<Self, Self::Error> {
+		let Ok(args) = yazi_shared::shell::split_unix(c.str("args").unwrap_or_default(), false) else {
+			bail!("Invalid 'args' argument in GrepOpt");
+		};
+
+		Ok(Self {
+			subject:  c.take_first_str().unwrap_or_default(),
+			args:     args.0,
+			args_raw: c.take_str("args").unwrap_or_default(),
+		})
+	}
+}
+
+impl FromLua for GrepOpt {
+	fn from_lua(_: Value, _: &Lua) -> mlua::Result<Self> { Err("unsupported".into_lua_err()) }
+}
+
+impl IntoLua for GrepOpt {
+	fn into_lua(self, _: &Lua) -> mlua::Result<Value> { Err("unsupported".into_lua_err()) }
+}
@@ -0,0 +1,27 @@
+use std::str::FromStr;
+
+use mlua::{ExternalError, FromLua, IntoLua, Lua, Value};
+use yazi_shared::event::CmdCow;
+
+use super::HiddenOptState;
+
+#[derive(Debug, Default)]
+pub struct InfoOpt {
+	pub state: HiddenOptState,
+}
+
+impl TryFrom<CmdCow> for InfoOpt {
+	type Error = anyhow::Error;
+
+	fn try_from(c: CmdCow) -> Result<Self, Self::Error> {
+		Ok(Self { state: c.first_str().map(FromStr::from_str).transpose()?.unwrap_or_default() })
+	}
+}
+
+impl FromLua for InfoOpt {
+	fn from_lua(_: Value, _: &Lua) -> mlua::Result<Self> { Err("unsupported".into_lua_err()) }
+}
+
+impl IntoLua for InfoOpt {
+	fn into_lua(self, _: &Lua) -> mlua::Result<Value> { Err("unsupported".into_lua_err()) }
+}
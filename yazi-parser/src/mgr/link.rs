@@ -1,14 +1,27 @@
+use std::str::FromStr;
+
 use mlua::{ExternalError, FromLua, IntoLua, Lua, Value};
+use serde::{Deserialize, Serialize};
 use yazi_shared::event::CmdCow;
 
 #[derive(Debug)]
 pub struct LinkOpt {
-	pub relative: bool,
+	pub relative: LinkOptRelative,
 	pub force:    bool,
 }
 
-impl From<CmdCow> for LinkOpt {
-	fn from(c: CmdCow) -> Self { Self { relative: c.bool("relative"), force: c.bool("force") } }
+impl TryFrom<CmdCow> for LinkOpt {
+	type Error = anyhow::Error;
+
+	fn try_from(c: CmdCow) -> Result<Self, Self::Error> {
+		let relative = match c.str("relative") {
+			Some(s) => s.parse()?,
+			None if c.bool("relative") => LinkOptRelative::Always,
+			None => LinkOptRelative::Never,
+		};
+
+		Ok(Self { relative, force: c.bool("force") })
+	}
 }
 
 impl FromLua for LinkOpt {
@@ -18,3 +31,23 @@ impl FromLua for LinkOpt {
 impl IntoLua for LinkOpt {
 	fn into_lua(self, _: &Lua) -> mlua::Result<Value> { Err("unsupported".into_lua_err()) }
 }
+
+// --- Relative
+/// Whether the target of a link is relative to the directory it's in.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum LinkOptRelative {
+	#[default]
+	Never,
+	Always,
+	/// Relative if the link and its target are on the same device, absolute otherwise.
+	Auto,
+}
+
+impl FromStr for LinkOptRelative {
+	type Err = serde::de::value::Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		Self::deserialize(serde::de::value::StrDeserializer::new(s))
+	}
+}
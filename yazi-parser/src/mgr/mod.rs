@@ -1,16 +1,29 @@
 yazi_macro::mod_flat!(
+	bookmark
+	bulk_rename
 	cd
+	chmod
+	chown
 	close
+	compare
 	copy
 	create
+	download
+	dual
+	dupes
 	escape
+	extract
 	filter
 	find
 	find_arrow
 	find_do
+	flatten
+	follow
+	grep
 	hardlink
 	hidden
 	hover
+	info
 	linemode
 	link
 	open
@@ -19,20 +32,29 @@ yazi_macro::mod_flat!(
 	paste
 	peek
 	quit
+	relink
 	remove
 	rename
+	restore
 	reveal
+	sanitize
 	search
 	seek
+	selection
 	shell
 	sort
 	spot
 	tab_close
 	tab_create
 	tab_switch
+	tag
+	theme
 	toggle
 	toggle_all
+	tree
+	update_compare
 	update_files
+	update_journal
 	update_mimes
 	update_paged
 	update_peeked
@@ -40,5 +62,6 @@ yazi_macro::mod_flat!(
 	update_tasks
 	update_yanked
 	visual_mode
+	xattr
 	yank
 );
@@ -1,15 +1,20 @@
 use mlua::{ExternalError, FromLua, IntoLua, Lua, Value};
 use yazi_shared::event::CmdCow;
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Default)]
 pub struct OpenOpt {
 	pub interactive: bool,
 	pub hovered:     bool,
+	pub remember:    bool,
 }
 
 impl From<CmdCow> for OpenOpt {
 	fn from(c: CmdCow) -> Self {
-		Self { interactive: c.bool("interactive"), hovered: c.bool("hovered") }
+		Self {
+			interactive: c.bool("interactive"),
+			hovered:     c.bool("hovered"),
+			remember:    c.bool("remember"),
+		}
 	}
 }
 
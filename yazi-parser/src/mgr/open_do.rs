@@ -7,6 +7,7 @@ pub struct OpenDoOpt {
 	pub hovered:     Url,
 	pub targets:     Vec<(Url, &'static str)>,
 	pub interactive: bool,
+	pub remember:    bool,
 }
 
 impl From<CmdCow> for OpenDoOpt {
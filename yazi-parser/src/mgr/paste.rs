@@ -1,14 +1,70 @@
+use std::{str::FromStr, time::Instant};
+
+use anyhow::anyhow;
 use mlua::{ExternalError, FromLua, IntoLua, Lua, Value};
-use yazi_shared::event::CmdCow;
+use serde::{Deserialize, Serialize};
+use yazi_shared::{event::CmdCow, until_clock, url::Url};
 
 #[derive(Debug)]
 pub struct PasteOpt {
-	pub force:  bool,
-	pub follow: bool,
+	pub conflict: PasteOptConflict,
+	pub follow:   bool,
+	pub verify:   bool,
+	pub system:   bool,
+	/// Whether to wait until the pastes before it finish.
+	pub queue:    Option<bool>,
+	/// When to start it, from `--at=HH:MM`.
+	pub start:    Option<Instant>,
+	/// Ask for the two above before pasting.
+	pub schedule: bool,
+	/// The files read from the system clipboard with `--system`, and whether they were cut.
+	pub imported: Option<(bool, Vec<Url>)>,
+	/// Ask for a new directory to paste into.
+	pub into:     bool,
+	/// The directory to paste into instead of the CWD, created by `--into`.
+	pub dest:     Option<Url>,
+	/// Pack the yanked files into an archive of the format instead.
+	pub archive:  Option<PasteOptArchive>,
+}
+
+impl TryFrom<CmdCow> for PasteOpt {
+	type Error = anyhow::Error;
+
+	fn try_from(mut c: CmdCow) -> Result<Self, Self::Error> {
+		if let Some(opt) = c.take_any("option") {
+			return Ok(opt);
+		}
+
+		let conflict = if c.bool("force") {
+			PasteOptConflict::Overwrite
+		} else {
+			c.str("conflict").map(FromStr::from_str).transpose()?.unwrap_or_default()
+		};
+
+		let start = match c.str("at") {
+			Some(s) => Some(Self::start_at(s).ok_or_else(|| anyhow!("Invalid 'at' argument: {s}"))?),
+			None => None,
+		};
+
+		Ok(Self {
+			conflict,
+			follow: c.bool("follow"),
+			verify: c.bool("verify"),
+			system: c.bool("system"),
+			queue: c.maybe_bool("queue"),
+			start,
+			schedule: c.bool("schedule"),
+			imported: None,
+			into: c.bool("into"),
+			dest: None,
+			archive: c.str("archive").map(FromStr::from_str).transpose()?,
+		})
+	}
 }
 
-impl From<CmdCow> for PasteOpt {
-	fn from(c: CmdCow) -> Self { Self { force: c.bool("force"), follow: c.bool("follow") } }
+impl PasteOpt {
+	/// The next `HH:MM` of the local time.
+	pub fn start_at(s: &str) -> Option<Instant> { Some(Instant::now() + until_clock(s)?) }
 }
 
 impl FromLua for PasteOpt {
@@ -18,3 +74,57 @@ impl FromLua for PasteOpt {
 impl IntoLua for PasteOpt {
 	fn into_lua(self, _: &Lua) -> mlua::Result<Value> { Err("unsupported".into_lua_err()) }
 }
+
+// --- Conflict
+/// What to do when a pasted file already exists in the destination.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PasteOptConflict {
+	/// Paste it with a unique suffix, e.g. `foo_1.txt`.
+	#[default]
+	Rename,
+	/// Replace the existing one, same as `--force`.
+	Overwrite,
+	/// Leave the existing one untouched.
+	Skip,
+	/// Replace the existing one only if the pasted one was modified later.
+	Newer,
+	/// Ask for each one, with the option to apply the answer to the rest.
+	Ask,
+}
+
+impl FromStr for PasteOptConflict {
+	type Err = serde::de::value::Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		Self::deserialize(serde::de::value::StrDeserializer::new(s))
+	}
+}
+
+// --- Archive
+/// The format of the archive that `--archive` packs the yanked files into.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum PasteOptArchive {
+	#[serde(rename = "zip")]
+	Zip,
+	#[serde(rename = "tar.zst")]
+	TarZst,
+}
+
+impl FromStr for PasteOptArchive {
+	type Err = serde::de::value::Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		Self::deserialize(serde::de::value::StrDeserializer::new(s))
+	}
+}
+
+impl PasteOptArchive {
+	#[inline]
+	pub fn ext(self) -> &'static str {
+		match self {
+			Self::Zip => "zip",
+			Self::TarZst => "tar.zst",
+		}
+	}
+}
@@ -0,0 +1,19 @@
+use mlua::{ExternalError, FromLua, IntoLua, Lua, Value};
+use yazi_shared::{SStr, event::CmdCow};
+
+#[derive(Debug)]
+pub struct RelinkOpt {
+	pub target: Option<SStr>,
+}
+
+impl From<CmdCow> for RelinkOpt {
+	fn from(mut c: CmdCow) -> Self { Self { target: c.take_first_str() } }
+}
+
+impl FromLua for RelinkOpt {
+	fn from_lua(_: Value, _: &Lua) -> mlua::Result<Self> { Err("unsupported".into_lua_err()) }
+}
+
+impl IntoLua for RelinkOpt {
+	fn into_lua(self, _: &Lua) -> mlua::Result<Value> { Err("unsupported".into_lua_err()) }
+}
@@ -0,0 +1,19 @@
+use mlua::{ExternalError, FromLua, IntoLua, Lua, Value};
+use yazi_shared::event::CmdCow;
+
+#[derive(Debug)]
+pub struct RestoreOpt {
+	pub recent: bool,
+}
+
+impl From<CmdCow> for RestoreOpt {
+	fn from(c: CmdCow) -> Self { Self { recent: c.bool("recent") } }
+}
+
+impl FromLua for RestoreOpt {
+	fn from_lua(_: Value, _: &Lua) -> mlua::Result<Self> { Err("unsupported".into_lua_err()) }
+}
+
+impl IntoLua for RestoreOpt {
+	fn into_lua(self, _: &Lua) -> mlua::Result<Value> { Err("unsupported".into_lua_err()) }
+}
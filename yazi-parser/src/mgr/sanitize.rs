@@ -0,0 +1,26 @@
+use std::str::FromStr;
+
+use mlua::{ExternalError, FromLua, IntoLua, Lua, Value};
+use yazi_fs::SanitizeTarget;
+use yazi_shared::event::CmdCow;
+
+#[derive(Debug)]
+pub struct SanitizeOpt {
+	pub target: SanitizeTarget,
+}
+
+impl TryFrom<CmdCow> for SanitizeOpt {
+	type Error = anyhow::Error;
+
+	fn try_from(c: CmdCow) -> Result<Self, Self::Error> {
+		Ok(Self { target: c.str("target").map(FromStr::from_str).transpose()?.unwrap_or_default() })
+	}
+}
+
+impl FromLua for SanitizeOpt {
+	fn from_lua(_: Value, _: &Lua) -> mlua::Result<Self> { Err("unsupported".into_lua_err()) }
+}
+
+impl IntoLua for SanitizeOpt {
+	fn into_lua(self, _: &Lua) -> mlua::Result<Value> { Err("unsupported".into_lua_err()) }
+}
@@ -4,7 +4,7 @@ use yazi_shared::{SStr, event::CmdCow};
 
 #[derive(Debug)]
 pub struct SearchOpt {
-	pub via:      SearchOptVia,
+	pub via:      SStr,
 	pub subject:  SStr,
 	pub args:     Vec<String>,
 	pub args_raw: SStr,
@@ -16,9 +16,9 @@ impl TryFrom<CmdCow> for SearchOpt {
 	fn try_from(mut c: CmdCow) -> Result<Self, Self::Error> {
 		// TODO: remove this
 		let (via, subject) = if let Some(s) = c.take_str("via") {
-			(s.as_ref().into(), c.take_first_str().unwrap_or_default())
+			(s, c.take_first_str().unwrap_or_default())
 		} else {
-			(c.take_first_str().unwrap_or_default().as_ref().into(), "".into())
+			(c.take_first_str().unwrap_or_default(), "".into())
 		};
 
 		let Ok(args) = yazi_shared::shell::split_unix(c.str("args").unwrap_or_default(), false) else {
@@ -42,31 +42,3 @@ impl FromLua for SearchOpt {
 impl IntoLua for SearchOpt {
 	fn into_lua(self, _: &Lua) -> mlua::Result<Value> { Err("unsupported".into_lua_err()) }
 }
-
-// Via
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-pub enum SearchOptVia {
-	Rg,
-	Rga,
-	Fd,
-}
-
-impl From<&str> for SearchOptVia {
-	fn from(value: &str) -> Self {
-		match value {
-			"rg" => Self::Rg,
-			"rga" => Self::Rga,
-			_ => Self::Fd,
-		}
-	}
-}
-
-impl SearchOptVia {
-	pub fn into_str(self) -> &'static str {
-		match self {
-			Self::Rg => "rg",
-			Self::Rga => "rga",
-			Self::Fd => "fd",
-		}
-	}
-}
@@ -0,0 +1,56 @@
+use std::str::FromStr;
+
+use anyhow::anyhow;
+use mlua::{ExternalError, FromLua, IntoLua, Lua, Value};
+use serde::Deserialize;
+use yazi_shared::{SStr, event::CmdCow};
+
+#[derive(Debug)]
+pub struct SelectionOpt {
+	pub action: SelectionOptAction,
+	pub name:   SStr,
+}
+
+impl TryFrom<CmdCow> for SelectionOpt {
+	type Error = anyhow::Error;
+
+	fn try_from(mut c: CmdCow) -> Result<Self, Self::Error> {
+		let Some(action) = c.first_str() else {
+			return Err(anyhow!("Missing action in SelectionOpt"));
+		};
+
+		Ok(Self { action: action.parse()?, name: c.take_str(1).unwrap_or("default".into()) })
+	}
+}
+
+impl FromLua for SelectionOpt {
+	fn from_lua(_: Value, _: &Lua) -> mlua::Result<Self> { Err("unsupported".into_lua_err()) }
+}
+
+impl IntoLua for SelectionOpt {
+	fn into_lua(self, _: &Lua) -> mlua::Result<Value> { Err("unsupported".into_lua_err()) }
+}
+
+// --- Action
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum SelectionOptAction {
+	/// Saves the selected files under the name.
+	Save,
+	/// Replaces the selected files with the saved ones.
+	Recall,
+	/// Adds the saved files to the selected ones.
+	Merge,
+	/// Removes the saved files from the selected ones.
+	Subtract,
+	/// Forgets the saved files.
+	Delete,
+}
+
+impl FromStr for SelectionOptAction {
+	type Err = serde::de::value::Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		Self::deserialize(serde::de::value::StrDeserializer::new(s))
+	}
+}
@@ -0,0 +1,61 @@
+use std::str::FromStr;
+
+use anyhow::bail;
+use mlua::{ExternalError, FromLua, IntoLua, Lua, Value};
+use serde::Deserialize;
+use yazi_shared::{SStr, event::CmdCow};
+
+#[derive(Debug)]
+pub struct TagOpt {
+	pub action: TagOptAction,
+	pub name:   Option<SStr>,
+	pub color:  Option<SStr>,
+}
+
+impl TryFrom<CmdCow> for TagOpt {
+	type Error = anyhow::Error;
+
+	fn try_from(mut c: CmdCow) -> Result<Self, Self::Error> {
+		let Some(action) = c.first_str() else {
+			bail!("Missing action in TagOpt");
+		};
+
+		let action: TagOptAction = action.parse()?;
+		let name = c.take_str(1);
+		if name.is_none() && action != TagOptAction::Clear {
+			bail!("Missing the name of the tag in TagOpt");
+		}
+
+		Ok(Self { action, name, color: c.take_str("color") })
+	}
+}
+
+impl FromLua for TagOpt {
+	fn from_lua(_: Value, _: &Lua) -> mlua::Result<Self> { Err("unsupported".into_lua_err()) }
+}
+
+impl IntoLua for TagOpt {
+	fn into_lua(self, _: &Lua) -> mlua::Result<Value> { Err("unsupported".into_lua_err()) }
+}
+
+// --- Action
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum TagOptAction {
+	/// Adds the tag to the selected files, or the hovered one.
+	Add,
+	/// Removes the tag from the selected files, or the hovered one.
+	Remove,
+	/// Removes the tag if all the files have it, adds it otherwise.
+	Toggle,
+	/// Removes all the tags of the files.
+	Clear,
+}
+
+impl FromStr for TagOptAction {
+	type Err = serde::de::value::Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		Self::deserialize(serde::de::value::StrDeserializer::new(s))
+	}
+}
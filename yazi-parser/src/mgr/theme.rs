@@ -0,0 +1,43 @@
+use std::str::FromStr;
+
+use mlua::{ExternalError, FromLua, IntoLua, Lua, Value};
+use yazi_config::theme::Appearance;
+use yazi_shared::event::CmdCow;
+
+#[derive(Debug, Default)]
+pub struct ThemeOpt {
+	pub appearance: Option<Appearance>,
+	pub toggle:     bool,
+	pub flavor:     Option<String>,
+	pub reload:     bool,
+	/// The dark mode of the OS has changed, with `true` for light.
+	pub os:         Option<bool>,
+}
+
+impl TryFrom<CmdCow> for ThemeOpt {
+	type Error = anyhow::Error;
+
+	fn try_from(mut c: CmdCow) -> Result<Self, Self::Error> {
+		let (appearance, toggle) = match c.first_str() {
+			Some("toggle") => (None, true),
+			Some(s) => (Some(Appearance::from_str(s)?), false),
+			None => (None, false),
+		};
+
+		Ok(Self {
+			appearance,
+			toggle,
+			flavor: c.take_str("flavor").map(Into::into),
+			reload: c.bool("reload"),
+			os: c.maybe_bool("os"),
+		})
+	}
+}
+
+impl FromLua for ThemeOpt {
+	fn from_lua(_: Value, _: &Lua) -> mlua::Result<Self> { Err("unsupported".into_lua_err()) }
+}
+
+impl IntoLua for ThemeOpt {
+	fn into_lua(self, _: &Lua) -> mlua::Result<Value> { Err("unsupported".into_lua_err()) }
+}
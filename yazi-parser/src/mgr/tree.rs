@@ -0,0 +1,49 @@
+use std::str::FromStr;
+
+use mlua::{ExternalError, FromLua, IntoLua, Lua, Value};
+use serde::Deserialize;
+use yazi_shared::event::CmdCow;
+
+#[derive(Debug, Default)]
+pub struct TreeOpt {
+	pub action: TreeOptAction,
+}
+
+impl TryFrom<CmdCow> for TreeOpt {
+	type Error = anyhow::Error;
+
+	fn try_from(c: CmdCow) -> Result<Self, Self::Error> {
+		Ok(Self { action: c.first_str().map(FromStr::from_str).transpose()?.unwrap_or_default() })
+	}
+}
+
+impl FromLua for TreeOpt {
+	fn from_lua(_: Value, _: &Lua) -> mlua::Result<Self> { Err("unsupported".into_lua_err()) }
+}
+
+impl IntoLua for TreeOpt {
+	fn into_lua(self, _: &Lua) -> mlua::Result<Value> { Err("unsupported".into_lua_err()) }
+}
+
+// --- Action
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum TreeOptAction {
+	/// Switches the current directory between the tree and the list.
+	#[default]
+	None,
+	/// Expands the hovered directory.
+	Expand,
+	/// Collapses the hovered directory, or the one it's in.
+	Collapse,
+	/// Expands or collapses the hovered directory.
+	Toggle,
+}
+
+impl FromStr for TreeOptAction {
+	type Err = serde::de::value::Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		Self::deserialize(serde::de::value::StrDeserializer::new(s))
+	}
+}
@@ -0,0 +1,29 @@
+use anyhow::bail;
+use mlua::{ExternalError, FromLua, IntoLua, Lua, Value};
+use yazi_fs::Comparison;
+use yazi_shared::event::CmdCow;
+
+#[derive(Debug)]
+pub struct UpdateCompareOpt {
+	pub comparison: Comparison,
+}
+
+impl TryFrom<CmdCow> for UpdateCompareOpt {
+	type Error = anyhow::Error;
+
+	fn try_from(mut c: CmdCow) -> Result<Self, Self::Error> {
+		let Some(comparison) = c.take_any("comparison") else {
+			bail!("Invalid 'comparison' argument in UpdateCompareOpt");
+		};
+
+		Ok(Self { comparison })
+	}
+}
+
+impl FromLua for UpdateCompareOpt {
+	fn from_lua(_: Value, _: &Lua) -> mlua::Result<Self> { Err("unsupported".into_lua_err()) }
+}
+
+impl IntoLua for UpdateCompareOpt {
+	fn into_lua(self, _: &Lua) -> mlua::Result<Value> { Err("unsupported".into_lua_err()) }
+}
@@ -0,0 +1,30 @@
+use anyhow::bail;
+use mlua::{ExternalError, FromLua, IntoLua, Lua, Value};
+use yazi_fs::JournalOp;
+use yazi_shared::{Id, event::CmdCow};
+
+#[derive(Debug)]
+pub struct UpdateJournalOpt {
+	pub op:   JournalOp,
+	pub task: Option<Id>,
+}
+
+impl TryFrom<CmdCow> for UpdateJournalOpt {
+	type Error = anyhow::Error;
+
+	fn try_from(mut c: CmdCow) -> Result<Self, Self::Error> {
+		let Some(op) = c.take_any("op") else {
+			bail!("Invalid 'op' argument in UpdateJournalOpt");
+		};
+
+		Ok(Self { op, task: c.id("task") })
+	}
+}
+
+impl FromLua for UpdateJournalOpt {
+	fn from_lua(_: Value, _: &Lua) -> mlua::Result<Self> { Err("unsupported".into_lua_err()) }
+}
+
+impl IntoLua for UpdateJournalOpt {
+	fn into_lua(self, _: &Lua) -> mlua::Result<Value> { Err("unsupported".into_lua_err()) }
+}
@@ -4,6 +4,7 @@ use anyhow::bail;
 use mlua::{AnyUserData, ExternalError, FromLua, IntoLua, Lua, MetaMethod, MultiValue, ObjectLike, UserData, UserDataFields, UserDataMethods, Value};
 use serde::{Deserialize, Serialize};
 use yazi_binding::get_metatable;
+use yazi_fs::Selection;
 use yazi_shared::{event::CmdCow, url::CovUrl};
 
 type Iter = yazi_binding::Iter<
@@ -15,6 +16,8 @@ type Iter = yazi_binding::Iter<
 pub struct UpdateYankedOpt<'a> {
 	pub cut:  bool,
 	pub urls: Cow<'a, HashSet<CovUrl>>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub lazy: Option<Cow<'a, Selection>>,
 }
 
 impl TryFrom<CmdCow> for UpdateYankedOpt<'_> {
@@ -37,7 +40,13 @@ impl IntoLua for UpdateYankedOpt<'_> {
 	fn into_lua(self, lua: &Lua) -> mlua::Result<Value> {
 		let len = self.urls.len();
 		let iter = Iter::new(self.urls.into_owned().into_iter().map(yazi_binding::Url::new), Some(len));
-		UpdateYankedIter { cut: self.cut, len, inner: lua.create_userdata(iter)? }.into_lua(lua)
+		UpdateYankedIter {
+			cut: self.cut,
+			len,
+			lazy: self.lazy.map(Cow::into_owned),
+			inner: lua.create_userdata(iter)?,
+		}
+		.into_lua(lua)
 	}
 }
 
@@ -45,6 +54,7 @@ impl IntoLua for UpdateYankedOpt<'_> {
 pub struct UpdateYankedIter {
 	cut:   bool,
 	len:   usize,
+	lazy:  Option<Selection>,
 	inner: AnyUserData,
 }
 
@@ -60,6 +70,7 @@ impl UpdateYankedIter {
 					.map(|result| result.map(Into::into))
 					.collect::<mlua::Result<_>>()?,
 			),
+			lazy: self.lazy.map(Cow::Owned),
 		})
 	}
 }
@@ -67,6 +78,8 @@ impl UpdateYankedIter {
 impl UserData for UpdateYankedIter {
 	fn add_fields<F: UserDataFields<Self>>(fields: &mut F) {
 		fields.add_field_method_get("cut", |_, me| Ok(me.cut));
+		fields
+			.add_field_method_get("count", |_, me| Ok(me.len + me.lazy.as_ref().map_or(0, |s| s.len())));
 	}
 
 	fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
@@ -0,0 +1,56 @@
+use std::str::FromStr;
+
+use anyhow::bail;
+use mlua::{ExternalError, FromLua, IntoLua, Lua, Value};
+use serde::Deserialize;
+use yazi_shared::{SStr, event::CmdCow};
+
+#[derive(Debug)]
+pub struct XattrOpt {
+	pub action: XattrOptAction,
+	pub name:   SStr,
+	pub value:  SStr,
+}
+
+impl TryFrom<CmdCow> for XattrOpt {
+	type Error = anyhow::Error;
+
+	fn try_from(mut c: CmdCow) -> Result<Self, Self::Error> {
+		let Some(action) = c.first_str() else {
+			bail!("Missing action in XattrOpt");
+		};
+
+		let action: XattrOptAction = action.parse()?;
+		let Some(name) = c.take_str(1) else {
+			bail!("Missing the name of the attribute in XattrOpt");
+		};
+
+		Ok(Self { action, name, value: c.take_str(2).unwrap_or_default() })
+	}
+}
+
+impl FromLua for XattrOpt {
+	fn from_lua(_: Value, _: &Lua) -> mlua::Result<Self> { Err("unsupported".into_lua_err()) }
+}
+
+impl IntoLua for XattrOpt {
+	fn into_lua(self, _: &Lua) -> mlua::Result<Value> { Err("unsupported".into_lua_err()) }
+}
+
+// --- Action
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum XattrOptAction {
+	/// Sets the attribute of the selected files, or the hovered one.
+	Set,
+	/// Removes the attribute from the selected files, or the hovered one.
+	Remove,
+}
+
+impl FromStr for XattrOptAction {
+	type Err = serde::de::value::Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		Self::deserialize(serde::de::value::StrDeserializer::new(s))
+	}
+}
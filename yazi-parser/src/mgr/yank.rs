@@ -3,11 +3,12 @@ use yazi_shared::event::CmdCow;
 
 #[derive(Debug)]
 pub struct YankOpt {
-	pub cut: bool,
+	pub cut:    bool,
+	pub system: bool,
 }
 
 impl From<CmdCow> for YankOpt {
-	fn from(c: CmdCow) -> Self { Self { cut: c.bool("cut") } }
+	fn from(c: CmdCow) -> Self { Self { cut: c.bool("cut"), system: c.bool("system") } }
 }
 
 impl FromLua for YankOpt {
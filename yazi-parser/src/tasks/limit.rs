@@ -0,0 +1,57 @@
+use anyhow::bail;
+use mlua::{ExternalError, FromLua, IntoLua, Lua, Value};
+use yazi_shared::event::{CmdCow, Data};
+
+#[derive(Debug)]
+pub struct LimitOpt {
+	pub rate: u64,
+}
+
+impl TryFrom<CmdCow> for LimitOpt {
+	type Error = anyhow::Error;
+
+	fn try_from(c: CmdCow) -> Result<Self, Self::Error> {
+		let rate = match c.first() {
+			Some(Data::Integer(n)) => u64::try_from(*n).ok(),
+			Some(Data::String(s)) => Self::parse(s),
+			_ => None,
+		};
+
+		let Some(rate) = rate else {
+			bail!("Invalid 'rate' argument in LimitOpt");
+		};
+		Ok(Self { rate })
+	}
+}
+
+impl FromLua for LimitOpt {
+	fn from_lua(_: Value, _: &Lua) -> mlua::Result<Self> { Err("unsupported".into_lua_err()) }
+}
+
+impl IntoLua for LimitOpt {
+	fn into_lua(self, _: &Lua) -> mlua::Result<Value> { Err("unsupported".into_lua_err()) }
+}
+
+impl LimitOpt {
+	/// Parses a rate like `1048576`, `512K`, `10M` or `1G`.
+	fn parse(s: &str) -> Option<u64> {
+		let s = s.trim();
+		if s.eq_ignore_ascii_case("off") {
+			return Some(0);
+		}
+
+		let (n, unit) = match s.char_indices().find(|(_, c)| !c.is_ascii_digit()) {
+			Some((i, _)) => (&s[..i], &s[i..]),
+			None => (s, ""),
+		};
+
+		let shift = match unit.to_ascii_uppercase().as_str() {
+			"" | "B" => 0,
+			"K" | "KB" => 10,
+			"M" | "MB" => 20,
+			"G" | "GB" => 30,
+			_ => return None,
+		};
+		n.parse::<u64>().ok()?.checked_mul(1 << shift)
+	}
+}
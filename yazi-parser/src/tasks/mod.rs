@@ -1 +1 @@
-yazi_macro::mod_flat!(process_exec);
+yazi_macro::mod_flat!(limit process_exec purge_trash reorder);
@@ -12,6 +12,8 @@ pub struct ProcessExecOpt {
 	pub cwd:    Url,
 	pub opener: Cow<'static, OpenerRule>,
 	pub args:   Vec<OsString>,
+	/// The line to open the target at, passed as `$YAZI_LINE` to the opener.
+	pub line:   Option<u32>,
 	pub done:   Option<oneshot::Sender<()>>,
 }
 
@@ -0,0 +1,27 @@
+use anyhow::anyhow;
+use mlua::{ExternalError, FromLua, IntoLua, Lua, Value};
+use yazi_shared::event::CmdCow;
+
+#[derive(Debug)]
+pub struct PurgeTrashOpt {
+	/// The IDs of the items in the trash backend.
+	pub ids:  Vec<String>,
+	/// Their total size, for the progress.
+	pub size: u64,
+}
+
+impl TryFrom<CmdCow> for PurgeTrashOpt {
+	type Error = anyhow::Error;
+
+	fn try_from(mut c: CmdCow) -> Result<Self, Self::Error> {
+		c.take_any("option").ok_or_else(|| anyhow!("Missing 'option' in PurgeTrashOpt"))
+	}
+}
+
+impl FromLua for PurgeTrashOpt {
+	fn from_lua(_: Value, _: &Lua) -> mlua::Result<Self> { Err("unsupported".into_lua_err()) }
+}
+
+impl IntoLua for PurgeTrashOpt {
+	fn into_lua(self, _: &Lua) -> mlua::Result<Value> { Err("unsupported".into_lua_err()) }
+}
@@ -0,0 +1,19 @@
+use mlua::{ExternalError, FromLua, IntoLua, Lua, Value};
+use yazi_shared::event::{CmdCow, Data};
+
+#[derive(Debug)]
+pub struct ReorderOpt {
+	pub offset: isize,
+}
+
+impl From<CmdCow> for ReorderOpt {
+	fn from(c: CmdCow) -> Self { Self { offset: c.first().and_then(Data::as_isize).unwrap_or(0) } }
+}
+
+impl FromLua for ReorderOpt {
+	fn from_lua(_: Value, _: &Lua) -> mlua::Result<Self> { Err("unsupported".into_lua_err()) }
+}
+
+impl IntoLua for ReorderOpt {
+	fn into_lua(self, _: &Lua) -> mlua::Result<Value> { Err("unsupported".into_lua_err()) }
+}
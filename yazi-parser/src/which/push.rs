@@ -0,0 +1,28 @@
+use anyhow::bail;
+use mlua::{ExternalError, FromLua, IntoLua, Lua, Value};
+use yazi_shared::{SStr, event::CmdCow};
+
+#[derive(Debug)]
+pub struct PushOpt {
+	pub name: SStr,
+}
+
+impl TryFrom<CmdCow> for PushOpt {
+	type Error = anyhow::Error;
+
+	fn try_from(mut c: CmdCow) -> Result<Self, Self::Error> {
+		let Some(name) = c.take_first_str() else {
+			bail!("a mode name is required for PushOpt");
+		};
+
+		Ok(Self { name })
+	}
+}
+
+impl FromLua for PushOpt {
+	fn from_lua(_: Value, _: &Lua) -> mlua::Result<Self> { Err("unsupported".into_lua_err()) }
+}
+
+impl IntoLua for PushOpt {
+	fn into_lua(self, _: &Lua) -> mlua::Result<Value> { Err("unsupported".into_lua_err()) }
+}
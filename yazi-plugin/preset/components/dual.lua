@@ -0,0 +1,100 @@
+Dual = {
+	_id = "dual",
+}
+
+function Dual:new(area, tab, other)
+	local me = setmetatable({ _area = area, _tab = tab, _other = other }, { __index = self })
+	me:layout()
+	me:build()
+	return me
+end
+
+function Dual:layout()
+	self._chunks = ui.Layout()
+		:direction(ui.Layout.HORIZONTAL)
+		:constraints({
+			ui.Constraint.Ratio(1, 2),
+			ui.Constraint.Ratio(1, 2),
+		})
+		:split(self._area)
+end
+
+function Dual:build()
+	-- The panels keep the order of their tabs, so they don't swap places on focus
+	local left, right = self._tab, self._other
+	if Dual.index(right) < cx.tabs.idx then
+		left, right = right, left
+	end
+
+	self._panes = {
+		{ tab = left, area = self._chunks[1]:pad(ui.Pad.right(1)) },
+		{ tab = right, area = self._chunks[2]:pad(ui.Pad.left(1)) },
+	}
+
+	self._base = {
+		ui.Bar(ui.Edge.RIGHT):area(self._chunks[1]):symbol(th.mgr.border_symbol):style(th.mgr.border_style),
+	}
+	self._children = {}
+	for _, pane in ipairs(self._panes) do
+		local chunks = ui.Layout()
+			:direction(ui.Layout.VERTICAL)
+			:constraints({ ui.Constraint.Length(1), ui.Constraint.Fill(1) })
+			:split(pane.area)
+
+		pane.active = pane.tab == self._tab
+		self._base[#self._base + 1] = self:title(chunks[1], pane)
+		pane.current = Current:new(chunks[2], pane.tab)
+		self._children[#self._children + 1] = pane.current
+		self._children[#self._children + 1] = Marker:new(chunks[2], pane.tab.current)
+	end
+end
+
+function Dual:title(area, pane)
+	local s = ya.readable_path(tostring(pane.tab.current.cwd))
+	local line = ui.Line(ui.truncate(s, { max = area.w, rtl = true })):area(area)
+	return pane.active and line:style(th.mgr.cwd) or line
+end
+
+function Dual:reflow()
+	-- Only the active panel takes mouse events, clicking the other one focuses it
+	local components = { self }
+	for _, pane in ipairs(self._panes) do
+		if pane.active then
+			components = ya.list_merge(components, pane.current:reflow())
+		end
+	end
+	return components
+end
+
+function Dual:redraw()
+	local elements = self._base or {}
+	for _, child in ipairs(self._children) do
+		elements = ya.list_merge(elements, ui.redraw(child))
+	end
+	return elements
+end
+
+function Dual.index(tab)
+	for i = 1, #cx.tabs do
+		if cx.tabs[i].id.value == tab.id.value then
+			return i
+		end
+	end
+	return 0
+end
+
+-- Mouse events
+function Dual:click(event, up)
+	if up then
+		return
+	end
+	for _, pane in ipairs(self._panes) do
+		if not pane.active and pane.area:contains(ui.Rect { x = event.x, y = event.y }) then
+			ya.emit("dual", { "focus" })
+		end
+	end
+end
+
+function Dual:scroll(event, step) end
+
+function Dual:touch(event, step) end
@@ -2,11 +2,16 @@ Entity = {
 	_inc = 1000,
 	_children = {
 		{ "spacer", id = 1, order = 1000 },
+		{ "tree", id = 9, order = 1500 },
 		{ "icon", id = 2, order = 2000 },
 		{ "prefix", id = 3, order = 3000 },
 		{ "highlights", id = 4, order = 4000 },
+		{ "line", id = 8, order = 4500 },
 		{ "found", id = 5, order = 5000 },
 		{ "symlink", id = 6, order = 6000 },
+		{ "compared", id = 7, order = 7000 },
+		{ "git", id = 11, order = 7500 },
+		{ "tags", id = 10, order = 8000 },
 	},
 }
 
@@ -14,6 +19,17 @@ function Entity:new(file) return setmetatable({ _file = file }, { __index = self
 
 function Entity:spacer() return " " end
 
+function Entity:tree()
+	local depth, expanded = self._file:tree()
+	if not depth then
+		return ""
+	elseif expanded == nil then
+		return string.rep("  ", depth + 1)
+	else
+		return string.rep("  ", depth) .. (expanded and "▾ " or "▸ ")
+	end
+end
+
 function Entity:icon()
 	local icon = self._file:icon()
 	if not icon then
@@ -51,6 +67,11 @@ function Entity:highlights()
 	return ui.Line(spans)
 end
 
+function Entity:line()
+	local line = self._file.url.line
+	return line and ui.Span(":" .. line):style(th.mgr.find_position) or ""
+end
+
 function Entity:found()
 	if not self._file.is_hovered then
 		return ""
@@ -76,6 +97,36 @@ function Entity:symlink()
 	return to and ui.Span(string.format(" -> %s", to)):style(th.mgr.symlink_target) or ""
 end
 
+function Entity:compared()
+	local diff = self._file:compared()
+	if not diff then
+		return ""
+	elseif diff == "differ" then
+		return ui.Span(" ≠"):style(th.mgr.compare_differ)
+	else
+		return ui.Span(" +"):style(th.mgr.compare_only)
+	end
+end
+
+function Entity:git()
+	local status = self._file:git()
+	if not status or status == "ignored" then
+		return ""
+	end
+
+	local signs = { modified = " M", added = " A", untracked = " ?", conflicted = " U" }
+	return ui.Span(signs[status]):style(th.mgr["git_" .. status])
+end
+
+function Entity:tags()
+	local spans = {}
+	for _, tag in ipairs(self._file:tags()) do
+		local span = ui.Span(" ●"):style(th.mgr.tag)
+		spans[#spans + 1] = tag.color and span:fg(tag.color) or span
+	end
+	return #spans == 0 and "" or ui.Line(spans)
+end
+
 function Entity:redraw()
 	local lines = {}
 	for _, c in ipairs(self._children) do
@@ -116,7 +167,7 @@ function Entity:click(event, up)
 
 	ya.emit("reveal", { self._file.url })
 	if event.is_right then
-		ya.emit("open", {})
+		ya.emit("menu", {})
 	end
 end
 
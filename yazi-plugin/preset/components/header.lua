@@ -38,7 +38,9 @@ function Header:flags()
 
 	local t = {}
 	if cwd.is_search then
-		t[#t + 1] = string.format("search: %s", cwd.domain)
+		t[#t + 1] = string.format("%s: %s", cwd.is_grep and "grep" or "search", cwd.domain)
+	elseif cwd.is_history or cwd.is_dupes then
+		t[#t + 1] = cwd.scheme
 	end
 	if filter then
 		t[#t + 1] = string.format("filter: %s", filter)
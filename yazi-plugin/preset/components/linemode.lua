@@ -2,6 +2,7 @@ Linemode = {
 	_inc = 1000,
 	_children = {
 		{ "solo", id = 1, order = 1000 },
+		{ "columns", id = 3, order = 1500 },
 		{ "spacer", id = 2, order = 2000 },
 	},
 }
@@ -22,6 +23,30 @@ function Linemode:solo()
 	end
 end
 
+function Linemode:columns()
+	local lines = {}
+	for _, col in ipairs(rt.mgr.columns(self._file, self._file:mime() or "")) do
+		local content
+		if self[col.name] then
+			content = self[col.name](self)
+		else
+			content = require(col.name):column(self._file)
+		end
+
+		local line = ui.Line(content or ""):truncate { max = col.width }
+		local pad = col.width - line:width()
+		if col.align == "right" then
+			lines[#lines + 1] = ui.Line { " ", string.rep(" ", pad), line }
+		elseif col.align == "center" then
+			local left = pad // 2
+			lines[#lines + 1] = ui.Line { " ", string.rep(" ", left), line, string.rep(" ", pad - left) }
+		else
+			lines[#lines + 1] = ui.Line { " ", line, string.rep(" ", pad) }
+		end
+	end
+	return ui.Line(lines)
+end
+
 function Linemode:size()
 	local size = self._file:size()
 	if size then
@@ -62,6 +87,15 @@ function Linemode:owner()
 	return string.format("%s:%s", user or "-", group or "-")
 end
 
+function Linemode:git()
+	local status = self._file:git()
+	return status and ui.Span(status):style(th.mgr["git_" .. status]) or ""
+end
+
+function Linemode:checksum() return require("checksum"):linemode(self._file) end
+
+function Linemode:cloud() return require("cloud"):linemode(self._file) end
+
 function Linemode:redraw()
 	local lines = {}
 	for _, c in ipairs(self._children) do
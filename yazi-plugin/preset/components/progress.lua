@@ -39,8 +39,25 @@ function Progress:redraw()
 		percent = math.min(99, ya.round(progress.processed * 100 / progress.found))
 	end
 
-	local left = progress.total - progress.succ
-	return gauge
-		:percent(percent)
-		:label(ui.Span(string.format("%3d%%, %d left", percent, left)):style(th.status.progress_label))
+	local label
+	if progress.speed > 0 then
+		label = string.format("%3d%%, %s/s", percent, ya.readable_size(progress.speed))
+		if progress.eta then
+			label = label .. ", " .. Progress.eta(progress.eta)
+		end
+	else
+		label = string.format("%3d%%, %d left", percent, progress.total - progress.succ)
+	end
+
+	return gauge:percent(percent):label(ui.Span(label):style(th.status.progress_label))
+end
+
+function Progress.eta(secs)
+	if secs < 60 then
+		return string.format("%ds", secs)
+	elseif secs < 3600 then
+		return string.format("%dm", math.ceil(secs / 60))
+	else
+		return string.format("%dh%02dm", secs // 3600, secs % 3600 // 60)
+	end
 end
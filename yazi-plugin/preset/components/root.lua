@@ -23,10 +23,11 @@ function Root:layout()
 end
 
 function Root:build()
+	local other = cx.tabs.other
 	self._children = {
 		Header:new(self._chunks[1], cx.active),
 		Tabs:new(self._chunks[2]),
-		Tab:new(self._chunks[3], cx.active),
+		other and Dual:new(self._chunks[3], cx.active, other) or Tab:new(self._chunks[3], cx.active),
 		Status:new(self._chunks[4], cx.active),
 		Modal:new(self._area),
 	}
@@ -50,6 +51,7 @@ end
 
 -- Mouse events
 function Root:click(event, up)
+	Tab._resizing = nil
 	if tostring(cx.layer) ~= "mgr" then
 		return
 	end
@@ -75,4 +77,7 @@ end
 
 function Root:move(event) end
 
-function Root:drag(event) end
+function Root:drag(event)
+	local c = self._children[3]
+	return c.drag and c:drag(event)
+end
@@ -5,23 +5,18 @@ Status = {
 
 	_id = "status",
 	_inc = 1000,
-	_left = {
-		{ "mode", id = 1, order = 1000 },
-		{ "size", id = 2, order = 2000 },
-		{ "name", id = 3, order = 3000 },
-	},
-	_right = {
-		{ "perm", id = 4, order = 1000 },
-		{ "percent", id = 5, order = 2000 },
-		{ "position", id = 6, order = 3000 },
-	},
+	_segments = {},
 }
 
+-- The built-in segments, with the IDs they've always had
+local BUILTIN = { mode = 1, size = 2, name = 3, perm = 4, percent = 5, position = 6, mount = 7, trash = 8 }
+
 function Status:new(area, tab)
 	return setmetatable({
 		_area = area,
 		_tab = tab,
 		_current = tab.current,
+		_hits = {},
 	}, { __index = self })
 end
 
@@ -96,6 +91,31 @@ function Status:perm()
 	return ui.Line(spans)
 end
 
+function Status:mount()
+	local cwd, best = tostring(self._current.cwd), nil
+	for _, p in ipairs(fs.partitions()) do
+		local dist = p.dist and tostring(p.dist) or ""
+		local next = cwd:sub(#dist + 1, #dist + 1)
+		if
+			(p.removable or p.external)
+			and #dist > (best and #tostring(best.dist) or 0)
+			and cwd:sub(1, #dist) == dist
+			and (next == "" or next == "/" or next == "\\" or dist:match("[/\\]$"))
+		then
+			best = p
+		end
+	end
+	return best and ui.Line(string.format(" ⏏ %s ", best.label or best.src)) or ""
+end
+
+function Status:trash()
+	local usage = fs.trash_usage()
+	if not usage or usage.items == 0 then
+		return ""
+	end
+	return ui.Line(string.format(" Trash %s ", ya.readable_size(usage.size)))
+end
+
 function Status:percent()
 	local percent = 0
 	local cursor = self._current.cursor
@@ -148,12 +168,53 @@ function Status:redraw()
 end
 
 -- Mouse events
-function Status:click(event, up) end
+function Status:click(event, up)
+	self:children_redraw(self.LEFT)
+	local offset = self._area.w - self:children_redraw(self.RIGHT):width()
+
+	local x = event.x - self._area.x
+	for side, base in pairs { [self.LEFT] = 0, [self.RIGHT] = offset } do
+		for _, hit in ipairs(self._hits[side]) do
+			if x >= base + hit.x and x < base + hit.x + hit.w then
+				return hit.segment.click and hit.segment.click(self, event, up)
+			end
+		end
+	end
+end
 
 function Status:scroll(event, step) end
 
 function Status:touch(event, step) end
 
+-- Segments, either a render function, or a table of `render(self)`, and the optional
+-- `cond(self)` to show it, `click(self, event, up)`, and `press(self)` to run by `plugin status -- <name>`
+function Status:segment(name, spec)
+	if type(spec) == "function" then
+		spec = { render = spec }
+	end
+	self._segments[name] = spec
+end
+
+function Status:press(name)
+	local spec = self._segments[name]
+	if not spec or not spec.press then
+		local content = string.format("No keypress handler for the segment `%s`", name)
+		return ya.notify { title = "Status", content = content, timeout = 5, level = "warn" }
+	end
+	return spec.press(self:new(ui.Rect {}, cx.active))
+end
+
+function Status:resolve(child)
+	local c = self._segments[child[1]] or child[1]
+	if type(c) == "table" then
+		return c
+	elseif type(c) == "string" then
+		return self[c] and { render = self[c] }
+	else
+		return { render = c }
+	end
+end
+
 -- Children
 function Status:children_add(fn, order, side)
 	self._inc = self._inc + 1
@@ -176,9 +237,31 @@ function Status:children_remove(id, side)
 end
 
 function Status:children_redraw(side)
-	local lines = {}
+	local lines, hits, x = {}, {}, 0
 	for _, c in ipairs(side == self.RIGHT and self._right or self._left) do
-		lines[#lines + 1] = (type(c[1]) == "string" and self[c[1]] or c[1])(self)
+		local segment = self:resolve(c)
+		if segment and (not segment.cond or segment.cond(self)) then
+			local line = ui.Line(segment.render(self))
+			local w = line:width()
+			lines[#lines + 1], hits[#hits + 1] = line, { x = x, w = w, segment = segment }
+			x = x + w
+		end
 	end
+
+	self._hits[side] = hits
 	return ui.Line(lines)
 end
+
+-- Lay out the segments as the theme orders them
+for side, names in pairs { [Status.LEFT] = th.status.left, [Status.RIGHT] = th.status.right } do
+	local children = {}
+	for i, name in ipairs(names) do
+		local id = BUILTIN[name]
+		if not id then
+			Status._inc = Status._inc + 1
+			id = Status._inc
+		end
+		children[i] = { name, id = id, order = i * 1000 }
+	end
+	Status[side == Status.RIGHT and "_right" or "_left"] = children
+end
@@ -47,8 +47,34 @@ function Tab:redraw()
 end
 
 -- Mouse events
-function Tab:click(event, up) end
+function Tab:click(event, up)
+	if up or not event.is_left then
+		return
+	elseif event.x == self._chunks[1].right - 1 then
+		Tab._resizing = "parent"
+	elseif event.x == self._chunks[3].x then
+		Tab._resizing = "preview"
+	end
+end
 
 function Tab:scroll(event, step) end
 
 function Tab:touch(event, step) end
+
+-- Drag the separator grabbed by `click()` to resize the panes, in columns
+function Tab:drag(event)
+	local w, x = self._area.w, event.x - self._area.x
+	local r = rt.mgr.ratio
+	local parent, preview = w * r.parent // r.all, w * r.preview // r.all
+
+	if Tab._resizing == "parent" then
+		parent = math.max(0, math.min(x + 1, w - preview - 1))
+	elseif Tab._resizing == "preview" then
+		preview = math.max(0, math.min(w - x, w - parent - 1))
+	else
+		return
+	end
+
+	rt.mgr.ratio = { parent, w - parent - preview, preview }
+	ya.emit("app:resize", {})
+end
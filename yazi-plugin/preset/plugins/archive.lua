@@ -1,28 +1,43 @@
 local M = {}
 
 function M:peek(job)
-	local limit = job.area.h
+	local url, limit = job.file.url, job.area.h
 
-	local files, bound, code = self.list_files({ "-p", tostring(job.file.url) }, job.skip, limit)
-	if code ~= 0 then
-		return require("empty").msg(
-			job,
-			code == 2 and "File list in this archive is encrypted"
-				or "Failed to start either `7zz` or `7z`. Do you have 7-zip installed?"
-		)
+	local files, bound, code, err = self.list_url(job, url, job.skip, limit)
+	local inner = job.skip == 0 and code == 0 and M.tarball(url, files)
+	if inner then
+		-- Compressed tarballs only hold the tar, so list what's in it instead
+		url = inner
+		files, bound, code, err = self.list_url(job, url, job.skip, limit)
+	end
+
+	if code == 1 then
+		return require("empty").msg(job, "Failed to start either `7zz` or `7z`. Do you have 7-zip installed?")
+	elseif code == 2 then
+		return require("empty").msg(job, "File list in this archive is encrypted")
+	elseif code == 4 and #files == 0 then
+		return require("empty").msg(job, "Archive is corrupt or truncated: " .. err)
 	end
 
 	local left, right = {}, {}
+	if code == 4 then
+		left[1] = ui.Line("Archive is corrupt or truncated, only the recoverable entries are listed"):fg("yellow")
+		right[1] = " "
+	end
+
+	local wide = job.area.w >= 60
 	for _, f in ipairs(files) do
 		local icon = File({
 			url = Url(f.path),
 			cha = Cha { kind = f.attr:sub(1, 1) == "D" and 1 or 0 },
 		}):icon()
 
-		if f.size > 0 then
-			right[#right + 1] = string.format(" %s ", ya.readable_size(f.size))
+		local size = f.size > 0 and ya.readable_size(f.size) or ""
+		if wide then
+			local packed = f.packed > 0 and ya.readable_size(f.packed) or ""
+			right[#right + 1] = string.format(" %6s %6s  %-16s ", size, packed, f.mtime:sub(1, 16))
 		else
-			right[#right + 1] = " "
+			right[#right + 1] = string.format(" %s ", size)
 		end
 
 		if icon then
@@ -52,28 +67,133 @@ end
 
 function M:seek(job) require("code"):seek(job) end
 
-function M.spawn_7z(args)
+---List files in the archive of a URL, where an `archive://` URL with an entry is
+---an archive nested inside another one, streamed out of it if it's a tar,
+---or extracted into the cache once otherwise, as random access is needed
+---@param job table
+---@param url Url
+---@param skip integer
+---@param limit integer
+function M.list_url(job, url, skip, limit)
+	local entry = url.is_archive and url.base and tostring(url.urn) or ""
+	if entry == "" then
+		return M.list_files({ "-p", tostring(url.base or url) }, skip, limit)
+	end
+
+	local outer = tostring(url.base)
+	if entry:lower():find("%.tar$") then
+		local child = M.spawn_7z { "x", "-so", "-sccUTF-8", "-p", "--", outer, entry }
+		if not child then
+			return {}, 0, 1
+		end
+		return M.list_files({ "-si", "-ttar" }, skip, limit, child:take_stdout())
+	end
+
+	local dir = ya.file_cache { file = File { url = url, cha = job.file.cha }, skip = 0 }
+	if not dir then
+		return {}, 0, 4, "nested archives in the cache are not listed"
+	end
+
+	local path = string.format("%s/%s", dir, url.name)
+	if not fs.cha(Url(path)) then
+		local child = M.spawn_7z { "e", "-y", "-sccUTF-8", "-p", "-o" .. tostring(dir), "--", outer, entry }
+		if not child then
+			return {}, 0, 1
+		end
+
+		local output = child:wait_with_output()
+		if not output or not output.status.success then
+			return {}, 0, 4, output and output.stderr or "failed to extract the nested archive"
+		end
+	end
+	return M.list_files({ "-p", path }, skip, limit)
+end
+
+---The URL of the tar inside a compressed tarball, if the files listed are only it
+---@param url Url
+---@param files table
+---@return Url?
+function M.tarball(url, files)
+	if url.is_archive or #files ~= 1 or files[1].attr:sub(1, 1) == "D" then
+		return
+	elseif files[1].path:lower():find("%.tar$") then
+		return Url(string.format("archive:///%s#%s", tostring(url), files[1].path))
+	end
+end
+
+function M.spawn_7z(args, stdin)
 	local last_err = nil
 	local try = function(name)
-		local stdout = args[1] == "l" and Command.PIPED or Command.NULL
-		local child, err = Command(name):arg(args):stdout(stdout):stderr(Command.PIPED):spawn()
-		if not child then
+		local stdout = (args[1] == "l" or args[2] == "-so") and Command.PIPED or Command.NULL
+		local child, err = Command(name)
+			:arg(args)
+			:stdin(stdin or Command.NULL)
+			:stdout(stdout)
+			:stderr(Command.PIPED)
+			:spawn()
+		if child then
+			M.bin = name
+		else
 			last_err = err
 		end
 		return child
 	end
 
-	local child = try("7zz") or try("7z")
+	-- A stdin can only be given once, so it goes to the 7-zip that started before
+	local child
+	if stdin then
+		child = try(M.bin or "7zz")
+	else
+		child = try("7zz") or try("7z")
+	end
 	if not child then
 		return ya.err("Failed to start either `7zz` or `7z`, error: " .. last_err)
 	end
 	return child, last_err
 end
 
+---Pack files into an archive of the format by its extension, either a `.zip`, or a `.tar.zst`,
+---which 7-zip streams out as a tar to `zstd`, as it can't write the zstd itself
+---@param to Url
+---@param files string[]
+---@return boolean ok
+---@return string? err
+function M.compress(to, files)
+	local path = tostring(to)
+	if not path:lower():find("%.tar%.zst$") then
+		local child = M.spawn_7z { "a", "-tzip", "-sccUTF-8", "--", path, table.unpack(files) }
+		local output = child and child:wait_with_output()
+		if not output then
+			return false, "failed to start 7-zip"
+		elseif not output.status.success then
+			return false, output.stderr
+		end
+		return true
+	end
+
+	local child = M.spawn_7z { "a", "-so", "-ttar", "-an", "-sccUTF-8", "--", table.unpack(files) }
+	if not child then
+		return false, "failed to start 7-zip"
+	end
+
+	local zstd = Command("zstd"):arg({ "-q", "-f", "-o", path }):stdin(child:take_stdout())
+	local output, err = zstd:stderr(Command.PIPED):output()
+	local status = child:wait()
+	if not output then
+		return false, string.format("failed to start `zstd`, error: %s", err)
+	elseif not output.status.success then
+		return false, output.stderr
+	elseif not status or not status.success then
+		return false, "7-zip exited with an error"
+	end
+	return true
+end
+
 ---List files in an archive
 ---@param args table
 ---@param skip integer
 ---@param limit integer
+---@param stdin? userdata The stdout of another child to read the archive from
 ---@return table files
 ---@return integer bound
 ---@return integer code
@@ -81,21 +201,26 @@ end
 ---  1: failed to spawn
 ---  2: wrong password
 ---  3: partial success
-function M.list_files(args, skip, limit)
-	local child = M.spawn_7z { "l", "-ba", "-slt", "-sccUTF-8", table.unpack(args) }
+---  4: corrupt or truncated archive, with the recoverable entries listed
+---@return string? err The error reported by 7-zip, if the archive is corrupt
+function M.list_files(args, skip, limit, stdin)
+	local child = M.spawn_7z({ "l", "-ba", "-slt", "-sccUTF-8", table.unpack(args) }, stdin)
 	if not child then
 		return {}, 0, 1
 	end
 
-	local i, files, code = 0, { { path = "", size = 0, attr = "" } }, 0
+	local i, files, code, err = 0, { { path = "", size = 0, packed = 0, mtime = "", attr = "" } }, 0, nil
 	local key, value = "", ""
 	repeat
 		local next, event = child:read_line()
 		if event == 1 and M.is_encrypted(next) then
 			code = 2
 			break
+		elseif event == 1 and M.is_corrupt(next) then
+			code, err = 4, next:gsub("^%s*ERROR:%s*", ""):gsub("%s+$", "")
+			goto continue
 		elseif event == 1 then
-			code = 3
+			code = code == 4 and 4 or 3
 			goto continue
 		elseif event ~= 0 then
 			break
@@ -104,18 +229,22 @@ function M.list_files(args, skip, limit)
 		if next == "\n" or next == "\r\n" then
 			i = i + 1
 			if files[#files].path ~= "" then
-				files[#files + 1] = { path = "", size = 0, attr = "" }
+				files[#files + 1] = { path = "", size = 0, packed = 0, mtime = "", attr = "" }
 			end
 			goto continue
 		elseif i < skip then
 			goto continue
 		end
 
-		key, value = next:match("^(%u%l+) = (.-)[\r\n]+")
+		key, value = next:match("^(%u[%a ]-) = (.-)[\r\n]+")
 		if key == "Path" then
 			files[#files].path = value
 		elseif key == "Size" then
 			files[#files].size = tonumber(value) or 0
+		elseif key == "Packed Size" then
+			files[#files].packed = tonumber(value) or 0
+		elseif key == "Modified" then
+			files[#files].mtime = value
 		elseif key == "Attributes" then
 			files[#files].attr = value
 		end
@@ -127,7 +256,7 @@ function M.list_files(args, skip, limit)
 	if files[#files].path == "" then
 		files[#files] = nil
 	end
-	return files, i, code
+	return files, i, code, err
 end
 
 ---List metadata of an archive
@@ -173,6 +302,23 @@ end
 
 function M.is_encrypted(s) return s:find(" Wrong password", 1, true) end
 
+function M.is_corrupt(s)
+	for _, pat in ipairs {
+		"Unexpected end of archive",
+		"Can not open the file as archive",
+		"Can not open the file as [",
+		"Headers Error",
+		"Data Error",
+		"CRC Failed",
+		"Unconfirmed start of archive",
+	} do
+		if s:find(pat, 1, true) then
+			return true
+		end
+	end
+	return false
+end
+
 function M.is_tar(url) return M.list_meta { "-p", tostring(url) } == "tar" end
 
 return M
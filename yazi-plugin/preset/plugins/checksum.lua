@@ -0,0 +1,166 @@
+-- Checksums of files, shown in the `checksum` linemode as they're computed in the background,
+-- and copied by the entry, with the algorithm chosen in `setup()` among md5, sha256 and blake3
+local M = {}
+
+-- The commands that print the checksum, tried in order until one starts
+local COMMANDS = {
+	md5 = { { "md5sum" }, { "md5", "-r" } },
+	sha256 = { { "sha256sum" }, { "shasum", "-a", "256" } },
+	blake3 = { { "b3sum" } },
+}
+
+local get = ya.sync(function(st, selected)
+	local urls, cached = {}, {}
+	if selected then
+		for _, url in pairs(cx.active.selected) do
+			urls[#urls + 1] = url
+		end
+	end
+	if #urls == 0 and cx.active.current.hovered then
+		urls[1] = cx.active.current.hovered.url
+	end
+
+	for i, url in ipairs(urls) do
+		cached[i] = st.sums and st.sums[tostring(url)]
+	end
+	return st.algo or "sha256", urls, cached, cx.active.pref.linemode
+end)
+
+local update = ya.sync(function(st, sums)
+	st.sums = st.sums or {}
+	for url, sum in pairs(sums) do
+		st.sums[url] = sum
+	end
+	ui.render()
+end)
+
+function M:setup(opts)
+	opts = opts or {}
+	if opts.algo and not COMMANDS[opts.algo] then
+		return ya.err("Unknown checksum algorithm: " .. opts.algo)
+	end
+	self.algo = opts.algo
+end
+
+function M:fetch(job)
+	local algo, _, _, linemode = get(false)
+	if linemode ~= "checksum" then
+		-- Not fetched, so they're tried again once the linemode is in use
+		return false
+	end
+
+	local urls = {}
+	for _, file in ipairs(job.files) do
+		urls[#urls + 1] = file.url
+	end
+
+	local sums, err = M.compute(algo, urls)
+	if not sums then
+		return true, err
+	end
+
+	local updates, state = {}, {}
+	for i, file in ipairs(job.files) do
+		local sum = sums[tostring(file.url)]
+		if sum then
+			updates[tostring(file.url)] = { algo = algo, sum = sum, mtime = file.cha.mtime or 0 }
+		end
+		state[i] = sum ~= nil
+	end
+
+	update(updates)
+	return state
+end
+
+function M:entry()
+	local algo, urls, cached = get(true)
+	if #urls == 0 then
+		return
+	end
+
+	local missing = {}
+	for i, url in ipairs(urls) do
+		if not cached[i] or cached[i].algo ~= algo then
+			missing[#missing + 1] = url
+		end
+	end
+
+	local sums, err = M.compute(algo, missing)
+	if not sums then
+		return ya.notify { title = "Checksum", content = tostring(err), timeout = 5, level = "error" }
+	end
+
+	local lines = {}
+	for i, url in ipairs(urls) do
+		local sum = sums[tostring(url)] or (cached[i] and cached[i].sum)
+		if not sum then
+			return ya.notify {
+				title = "Checksum",
+				content = string.format("Failed to compute the %s of `%s`", algo, url.name),
+				timeout = 5,
+				level = "error",
+			}
+		end
+		-- In the format `sha256sum --check` reads, unless there's only one
+		lines[i] = #urls == 1 and sum or string.format("%s  %s", sum, url.name)
+	end
+
+	ya.clipboard(table.concat(lines, "\n"))
+	ya.notify {
+		title = "Checksum",
+		content = string.format("Copied the %s of %d file(s)", algo, #urls),
+		timeout = 3,
+	}
+end
+
+---The checksum of the file in the `checksum` linemode, shortened to 8 digits
+---@param file File
+---@return string
+function M:linemode(file)
+	local c = self.sums and self.sums[tostring(file.url)]
+	if not c or c.algo ~= (self.algo or "sha256") or c.mtime ~= (file.cha.mtime or 0) then
+		return ""
+	end
+	return c.sum:sub(1, 8)
+end
+
+---Compute the checksums of the files, keyed by their URLs
+---@param algo string
+---@param urls Url[]
+---@return table? sums
+---@return Error? err
+function M.compute(algo, urls)
+	local sums, paths = {}, {}
+	for i, url in ipairs(urls) do
+		paths[i] = tostring(url)
+	end
+	if #paths == 0 then
+		return sums
+	end
+
+	local output, err
+	for _, cmd in ipairs(COMMANDS[algo]) do
+		output, err = Command(cmd[1]):arg({ table.unpack(cmd, 2) }):arg(paths):stdout(Command.PIPED):output()
+		if output then
+			break
+		end
+	end
+	if not output then
+		return nil, Err("Failed to start `%s`, error: %s", COMMANDS[algo][1][1], err)
+	end
+
+	-- Unreadable files are left out, and so are the names escaped for having newlines
+	local wanted = {}
+	for _, path in ipairs(paths) do
+		wanted[path] = true
+	end
+	for line in output.stdout:gmatch("[^\r\n]+") do
+		local sum, path = line:match("^(%x+) [ *]?(.+)$")
+		if sum and wanted[path] then
+			sums[path] = sum:lower()
+		end
+	end
+	return sums
+end
+
+return M
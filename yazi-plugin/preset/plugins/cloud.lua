@@ -0,0 +1,45 @@
+-- Marks the files of cloud drives that are only placeholders, such as the OneDrive, Dropbox
+-- and iCloud ones not downloaded yet, so they aren't copied without knowing it downloads them
+local M = {}
+
+local update = ya.sync(function(st, status)
+	st.status = st.status or {}
+	for url, s in pairs(status) do
+		st.status[url] = s or nil
+	end
+	ui.render()
+end)
+
+function M:setup(opts)
+	opts = opts or {}
+	self.icons = {
+		online = opts.online or "\u{f015f}",
+		["local"] = opts["local"] or "\u{f0160}",
+	}
+end
+
+function M:fetch(job)
+	local status, state = {}, {}
+	for i, file in ipairs(job.files) do
+		local s, err = fs.cloud(file.url)
+		status[tostring(file.url)], state[i] = s or false, err == nil
+	end
+
+	update(status)
+	return state
+end
+
+---The icon of the file in the `cloud` linemode, empty if it isn't a cloud one
+---@param file File
+---@return string
+function M:linemode(file)
+	local s = self.status and self.status[tostring(file.url)]
+	if not s then
+		return ""
+	elseif not self.icons then
+		self:setup()
+	end
+	return self.icons[s]
+end
+
+return M
@@ -0,0 +1,24 @@
+-- Packs files into an archive for `paste --archive`, as a task, with the same 7-zip
+-- that lists and extracts the archives
+
+local function fail(s, ...) error(string.format(s, ...)) end
+
+local M = {}
+
+function M:entry(job)
+	local to, files = job.args[1], {}
+	for i = 2, #job.args do
+		files[#files + 1] = tostring(job.args[i])
+	end
+	if not to or #files == 0 then
+		fail("No files to pack")
+	end
+
+	local ok, err = require("archive").compress(to, files)
+	if not ok then
+		fs.remove("file", to)
+		fail("Failed to pack '%s': %s", to.name, err or "unknown error")
+	end
+end
+
+return M
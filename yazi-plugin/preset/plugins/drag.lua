@@ -0,0 +1,190 @@
+-- Drag the selected files out of the terminal, from a small window that closes itself
+-- once they are dropped, unless `--keep` is given, like `dragon` but without installing it
+
+local LOCAL = { regular = true, search = true, grep = true, recent = true, frecency = true }
+
+-- GTK 3 with PyGObject, which the desktops on Linux and BSD mostly ship
+local GTK = [[
+import sys, gi
+gi.require_version("Gtk", "3.0")
+from gi.repository import Gdk, Gio, Gtk
+
+keep, label, paths = sys.argv[1] == "1", sys.argv[2], sys.argv[3:]
+uris, done = [Gio.File.new_for_path(p).get_uri() for p in paths], []
+
+def end(*_):
+	if done and not keep:
+		win.destroy()
+
+def get(w, ctx, data, info, time):
+	data.set_uris(uris)
+	done.append(True)
+
+win = Gtk.Window(title="Drag")
+win.set_keep_above(True)
+win.set_position(Gtk.WindowPosition.MOUSE)
+win.connect("destroy", Gtk.main_quit)
+win.connect("key-press-event", lambda w, e: e.keyval == Gdk.KEY_Escape and w.destroy())
+
+btn = Gtk.Button(label=label)
+btn.drag_source_set(Gdk.ModifierType.BUTTON1_MASK, [], Gdk.DragAction.COPY | Gdk.DragAction.LINK)
+btn.drag_source_add_uri_targets()
+btn.connect("drag-data-get", get)
+btn.connect("drag-end", end)
+
+win.add(btn)
+win.show_all()
+Gtk.main()
+]]
+
+-- JavaScript for Automation, where the window stays until closed
+local COCOA = [[
+ObjC.import('Cocoa');
+function run(argv) {
+	const paths = argv.slice(2);
+	const app = $.NSApplication.sharedApplication;
+	let view;
+	app.setActivationPolicy($.NSApplicationActivationPolicyAccessory);
+
+	ObjC.registerSubclass({
+		name: 'YaziDragView',
+		superclass: 'NSTextField',
+		protocols: ['NSDraggingSource'],
+		methods: {
+			'mouseDown:': {
+				types: ['void', ['id']],
+				implementation: function (event) {
+					const items = paths.map(p => {
+						const item = $.NSDraggingItem.alloc.initWithPasteboardWriter($.NSURL.fileURLWithPath(p));
+						item.setDraggingFrameContents(view.bounds, $.NSWorkspace.sharedWorkspace.iconForFile(p));
+						return item;
+					});
+					view.beginDraggingSessionWithItemsEventSource($(items), event, view);
+				},
+			},
+			'draggingSession:sourceOperationMaskForDraggingContext:': {
+				types: ['unsigned long', ['id', 'long']],
+				implementation: () => $.NSDragOperationCopy | $.NSDragOperationLink,
+			},
+		},
+	});
+	ObjC.registerSubclass({
+		name: 'YaziDragDelegate',
+		protocols: ['NSWindowDelegate'],
+		methods: {
+			'windowWillClose:': { types: ['void', ['id']], implementation: () => app.terminate(null) },
+		},
+	});
+
+	const style = $.NSWindowStyleMaskTitled | $.NSWindowStyleMaskClosable;
+	const win = $.NSWindow.alloc.initWithContentRectStyleMaskBackingDefer(
+		$.NSMakeRect(0, 0, 240, 48), style, $.NSBackingStoreBuffered, false);
+	view = $.YaziDragView.alloc.initWithFrame($.NSMakeRect(0, 0, 240, 48));
+	view.stringValue = argv[1];
+	view.editable = false;
+	view.bezeled = false;
+	view.drawsBackground = false;
+	view.alignment = $.NSTextAlignmentCenter;
+
+	win.title = 'Drag';
+	win.level = $.NSFloatingWindowLevel;
+	win.contentView = view;
+	win.delegate = $.YaziDragDelegate.alloc.init;
+	win.center;
+	win.makeKeyAndOrderFront(null);
+	app.activateIgnoringOtherApps(true);
+	app.run;
+}
+]]
+
+-- Windows Forms of PowerShell, with the files filled in as `%s`
+local FORMS = [[
+Add-Type -AssemblyName System.Windows.Forms
+$keep = %s
+$files = New-Object System.Collections.Specialized.StringCollection
+$files.AddRange([string[]]@(%s))
+
+$form = New-Object System.Windows.Forms.Form
+$form.Text = 'Drag'
+$form.TopMost = $true
+$form.AutoSize = $true
+$form.AutoSizeMode = 'GrowAndShrink'
+$form.StartPosition = 'Manual'
+$form.Location = [System.Windows.Forms.Cursor]::Position
+$form.KeyPreview = $true
+$form.Add_KeyDown({ if ($_.KeyCode -eq 'Escape') { $form.Close() } })
+
+$label = New-Object System.Windows.Forms.Label
+$label.Text = %s
+$label.AutoSize = $true
+$label.Padding = New-Object System.Windows.Forms.Padding(24)
+$label.Add_MouseDown({
+	$data = New-Object System.Windows.Forms.DataObject
+	$data.SetFileDropList($files)
+	$effect = $label.DoDragDrop($data, 'Copy, Link')
+	if ($effect -ne 'None' -and -not $keep) { $form.Close() }
+})
+
+$form.Controls.Add($label)
+[void]$form.ShowDialog()
+]]
+
+local function fail(s, ...) ya.notify { title = "Drag", content = s:format(...), timeout = 5, level = "error" } end
+
+local selected = ya.sync(function()
+	local urls = {}
+	for _, url in pairs(cx.active.selected) do
+		urls[#urls + 1] = url
+	end
+	if #urls == 0 and cx.active.current.hovered then
+		urls[1] = cx.active.current.hovered.url
+	end
+	return urls
+end)
+
+local function quote(s) return "'" .. s:gsub("'", "''") .. "'" end
+
+local function command(paths, label, keep)
+	if ya.target_family() == "windows" then
+		local quoted = {}
+		for i, p in ipairs(paths) do
+			quoted[i] = quote(p)
+		end
+		local script = FORMS:format(keep and "$true" or "$false", table.concat(quoted, ", "), quote(label))
+		return Command("powershell"):arg { "-NoProfile", "-NonInteractive", "-STA", "-Command", script }, "powershell"
+	end
+
+	local k = keep and "1" or "0"
+	if ya.target_os() == "macos" then
+		return Command("osascript"):arg({ "-l", "JavaScript", "-e", COCOA, k, label }):arg(paths), "osascript"
+	else
+		return Command("python3"):arg({ "-c", GTK, k, label }):arg(paths), "python3"
+	end
+end
+
+local M = {}
+
+function M:entry(job)
+	ya.emit("escape", { visual = true })
+
+	local paths = {}
+	for _, url in ipairs(selected()) do
+		if LOCAL[url.scheme] then
+			paths[#paths + 1] = tostring(url)
+		end
+	end
+	if #paths == 0 then
+		return fail("No local files to drag")
+	end
+
+	local label = #paths == 1 and Url(paths[1]).name or string.format("%d files", #paths)
+	local cmd, bin = command(paths, label, job.args.keep)
+	local output, err = cmd:stdin(Command.NULL):stdout(Command.NULL):stderr(Command.PIPED):output()
+	if not output then
+		fail("Failed to start `%s`, error: %s", bin, err)
+	elseif not output.status.success then
+		fail("The drag window exited with error: %s", output.stderr:match("[^\n]*\n?$") or "")
+	end
+end
+
+return M
@@ -3,12 +3,8 @@ local function fail(s, ...) error(string.format(s, ...)) end
 local M = {}
 
 function M:setup()
-	ps.sub_remote("extract", function(args)
-		local noisy = #args == 1 and ' "" --noisy' or ' ""'
-		for _, arg in ipairs(args) do
-			ya.emit("plugin", { self._id, ya.quote(arg, true) .. noisy })
-		end
-	end)
+	-- The opener is handled by the built-in `extract`, which runs as a task with progress
+	ps.sub_remote("extract", function(args) ya.emit("extract", args) end)
 end
 
 function M:entry(job)
@@ -65,6 +61,8 @@ function M:try_with(from, pwd, to)
 	self:tidy(from, to, tmp)
 	if not output then
 		fail("7zip failed to output when extracting '%s', error: %s", from, err)
+	elseif output.status.code ~= 0 and archive.is_corrupt(output.stderr) then
+		fail("'%s' is corrupt or truncated, only the recoverable entries were extracted", from)
 	elseif output.status.code ~= 0 then
 		fail("7zip exited when extracting '%s', error code %s", from, output.status.code)
 	end
@@ -1,4 +1,10 @@
-local M = {}
+local M = {
+	XATTR_NAMES = {
+		["system.posix_acl_access"] = "ACL",
+		["system.posix_acl_default"] = "Default ACL",
+		["security.selinux"] = "SELinux",
+	},
+}
 
 function M:peek(job)
 	local cmd = os.getenv("YAZI_FILE_ONE") or "file"
@@ -43,7 +49,7 @@ function M:spot_base(job)
 		preloaders[i] = v.cmd
 	end
 
-	return {
+	local rows = {
 		ui.Row({ "Base" }):style(ui.Style():fg("green")),
 		ui.Row { "  Created:", cha.btime and os.date("%y/%m/%d %H:%M", math.floor(cha.btime)) or "-" },
 		ui.Row { "  Modified:", cha.mtime and os.date("%y/%m/%d %H:%M", math.floor(cha.mtime)) or "-" },
@@ -56,6 +62,17 @@ function M:spot_base(job)
 		ui.Row { "  Fetchers:", #fetchers ~= 0 and fetchers or "-" },
 		ui.Row { "  Preloaders:", #preloaders ~= 0 and preloaders or "-" },
 	}
+
+	local attrs = fs.xattrs(url) or {}
+	if #attrs ~= 0 then
+		rows[#rows + 1] = ui.Row {}
+		rows[#rows + 1] = ui.Row({ "Attributes" }):style(ui.Style():fg("green"))
+	end
+	for _, a in ipairs(attrs) do
+		local name = M.XATTR_NAMES[a.name] or a.name
+		rows[#rows + 1] = ui.Row { string.format("  %s:", name), a.value }
+	end
+	return rows
 end
 
 return M
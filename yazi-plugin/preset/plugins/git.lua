@@ -0,0 +1,28 @@
+-- Fetches the Git status of the worktrees the files are in, once for each worktree,
+-- which is kept until the watcher sees changes in it
+local M = {}
+
+local render = ya.sync(function() ui.render() end)
+
+function M:fetch(job)
+	local dirs = {}
+	for _, file in ipairs(job.files) do
+		local dir = file.url.parent
+		if dir then
+			dirs[tostring(dir)] = dir
+		end
+	end
+
+	local found, last_err = false, nil
+	for _, dir in pairs(dirs) do
+		local ok, err = fs.git_refresh(dir)
+		found, last_err = found or ok, err or last_err
+	end
+
+	if found then
+		render()
+	end
+	return true, last_err
+end
+
+return M
@@ -0,0 +1,221 @@
+--- @timeout 30
+
+-- Aggregates the metadata of the hovered file into the preview while `info` is on,
+-- where the slow parts from other programs are fetched once and cached by the file
+
+local M = {}
+
+local function title(s) return ui.Row({ s }):style(ui.Style():fg("green")) end
+
+local function date(t) return t and os.date("%y/%m/%d %H:%M", math.floor(t)) or "-" end
+
+local function duration(secs)
+	secs = math.floor(tonumber(secs) or 0)
+	if secs >= 3600 then
+		return string.format("%d:%02d:%02d", secs // 3600, secs % 3600 // 60, secs % 60)
+	end
+	return string.format("%d:%02d", secs // 60, secs % 60)
+end
+
+function M:peek(job)
+	local rows = ya.list_merge(self:base(job), require("file"):spot_base(job))
+	for _, section in ipairs(self:sections(job)) do
+		rows[#rows + 1] = ui.Row {}
+		rows[#rows + 1] = title(section.title)
+		for _, r in ipairs(section.rows) do
+			rows[#rows + 1] = ui.Row { string.format("  %s:", r[1]), r[2] }
+		end
+	end
+
+	local bound = math.max(0, #rows - job.area.h)
+	if job.skip > bound then
+		return ya.emit("peek", { bound, only_if = job.file.url, upper_bound = true })
+	end
+
+	ya.preview_widget(
+		job,
+		ui.Table({ table.unpack(rows, job.skip + 1) })
+			:area(job.area)
+			:col_style(th.spot.tbl_col)
+			:cell_style(th.spot.tbl_cell)
+			:widths { ui.Constraint.Length(14), ui.Constraint.Fill(1) }
+	)
+end
+
+function M:seek(job)
+	local h = cx.active.current.hovered
+	if h and h.url == job.file.url then
+		ya.emit("peek", { math.max(0, cx.active.preview.skip + job.units), only_if = job.file.url })
+	end
+end
+
+function M:base(job)
+	local cha = job.file.cha
+	local owner = "-"
+	if cha.uid and cha.gid then
+		owner = string.format("%s:%s", ya.user_name(cha.uid) or cha.uid, ya.group_name(cha.gid) or cha.gid)
+	end
+
+	return {
+		title("File"),
+		ui.Row { "  Size:", cha.is_dir and "-" or ya.readable_size(cha.len) },
+		ui.Row { "  Permissions:", cha:perm() or "-" },
+		ui.Row { "  Owner:", owner },
+		ui.Row { "  Accessed:", date(cha.atime) },
+		ui.Row {},
+	}
+end
+
+---The sections of the fetchers matching the file, read from the cache if they were fetched before
+---@param job table
+---@return table sections A list of `{ title = string, rows = { { key, value }, ... } }`
+function M:sections(job)
+	local cache = ya.file_cache { file = job.file, skip = 0, kind = "info" }
+	local f = cache and io.open(tostring(cache), "r")
+	if f then
+		local sections = ya.json_decode(f:read("a") or "")
+		f:close()
+		if type(sections) == "table" then
+			return sections
+		end
+	end
+
+	local sections = {}
+	for _, fetch in ipairs { M.image, M.media, M.exif, M.archive } do
+		local ok, section = pcall(fetch, job)
+		if not ok then
+			ya.err(string.format("Failed to fetch the info of %s: %s", job.file.url, section))
+		elseif section and #section.rows ~= 0 then
+			sections[#sections + 1] = section
+		end
+	end
+
+	if cache then
+		fs.write(cache, ya.json_encode(sections) or "[]")
+	end
+	return sections
+end
+
+function M.image(job)
+	if not job.mime:find("^image/") then
+		return
+	end
+
+	local info = ya.image_info(job.file.url)
+	if info then
+		return {
+			title = "Image",
+			rows = {
+				{ "Format", tostring(info.format) },
+				{ "Dimensions", string.format("%dx%d", info.w, info.h) },
+				{ "Color", tostring(info.color) },
+			},
+		}
+	end
+end
+
+function M.media(job)
+	if not job.mime:find("^video/") and not job.mime:find("^audio/") then
+		return
+	end
+
+	-- stylua: ignore
+	local output = Command("ffprobe"):arg({
+		"-v", "quiet",
+		"-show_entries", "format=duration,bit_rate:stream=codec_type,codec_name,width,height,sample_rate,channels",
+		"-of", "json=c=1",
+		tostring(job.file.url),
+	}):output()
+
+	local meta = output and ya.json_decode(output.stdout)
+	if type(meta) ~= "table" then
+		return
+	end
+
+	local format, rows = meta.format or {}, {}
+	if format.duration then
+		rows[#rows + 1] = { "Duration", duration(format.duration) }
+	end
+	if tonumber(format.bit_rate) then
+		rows[#rows + 1] = { "Bitrate", string.format("%d kb/s", tonumber(format.bit_rate) // 1000) }
+	end
+
+	for i, s in ipairs(meta.streams or {}) do
+		local desc
+		if s.codec_type == "video" and s.width then
+			desc = string.format("%s, %dx%d", s.codec_name, s.width, s.height)
+		elseif s.codec_type == "audio" then
+			desc = string.format("%s, %d Hz, %s ch", s.codec_name, tonumber(s.sample_rate) or 0, s.channels or "?")
+		elseif s.codec_type then
+			desc = string.format("%s, %s", s.codec_name or "?", s.codec_type)
+		end
+		rows[#rows + 1] = desc and { string.format("Stream %d", i), desc }
+	end
+	return { title = "Media", rows = rows }
+end
+
+function M.exif(job)
+	if not job.mime:find("^image/") and not job.mime:find("^video/") then
+		return
+	end
+
+	-- stylua: ignore
+	local output = Command("exiftool"):arg({
+		"-json", "-n",
+		"-DateTimeOriginal", "-CreateDate", "-Make", "-Model", "-GPSLatitude", "-GPSLongitude",
+		"--", tostring(job.file.url),
+	}):output()
+
+	local t = output and ya.json_decode(output.stdout)
+	if type(t) ~= "table" or type(t[1]) ~= "table" then
+		return
+	end
+
+	local e, rows = t[1], {}
+	local taken = e.DateTimeOriginal or e.CreateDate
+	if taken and taken ~= "0000:00:00 00:00:00" then
+		rows[#rows + 1] = { "Taken", tostring(taken) }
+	end
+	if e.Make or e.Model then
+		rows[#rows + 1] = { "Camera", table.concat({ e.Make, e.Model }, " ") }
+	end
+	if tonumber(e.GPSLatitude) and tonumber(e.GPSLongitude) then
+		rows[#rows + 1] = { "GPS", string.format("%.5f, %.5f", e.GPSLatitude, e.GPSLongitude) }
+	end
+	return { title = "EXIF", rows = rows }
+end
+
+function M.archive(job)
+	local previewer = rt.plugin.previewer(job.file.url, job.mime)
+	if not previewer or previewer.cmd ~= "archive" then
+		return
+	end
+
+	local files, _, code = require("archive").list_url(job, job.file.url, 0, math.huge)
+	if code == 1 then
+		return
+	elseif code == 2 then
+		return { title = "Archive", rows = { { "Encrypted", "yes" } } }
+	end
+
+	local nfiles, ndirs, size = 0, 0, 0
+	for _, f in ipairs(files) do
+		if f.attr:sub(1, 1) == "D" then
+			ndirs = ndirs + 1
+		else
+			nfiles, size = nfiles + 1, size + f.size
+		end
+	end
+
+	local rows = {
+		{ "Files", tostring(nfiles) },
+		{ "Folders", tostring(ndirs) },
+		{ "Unpacked", ya.readable_size(size) },
+	}
+	if code == 4 then
+		rows[#rows + 1] = { "Corrupt", "yes, some entries are not listed" }
+	end
+	return { title = "Archive", rows = rows }
+end
+
+return M
@@ -0,0 +1,157 @@
+local KEYS = "1234567890abcdefghijklmnopqrstuvwxyz"
+
+local cwd = ya.sync(function() return tostring(cx.active.current.cwd) end)
+
+local function fail(s, ...) ya.notify { title = "Mount", content = s:format(...), timeout = 5, level = "error" } end
+
+local function under(path, dir)
+	local sep = ya.target_family() == "windows" and "\\" or "/"
+	return path == dir or path:sub(1, #dir) == dir and (dir:sub(-1) == sep or path:sub(#dir + 1, #dir + 1) == sep)
+end
+
+local function find(src)
+	for _, p in ipairs(fs.partitions()) do
+		if p.src == src then
+			return p
+		end
+	end
+end
+
+-- Block devices on Unix, and drive letters on Windows, which can be acted on
+local function drives()
+	local t = {}
+	for _, p in ipairs(fs.partitions()) do
+		if ya.target_family() == "windows" or p.src:sub(1, 5) == "/dev/" then
+			t[#t + 1] = p
+		end
+	end
+	return t
+end
+
+-- Phones and cameras that GVfs knows of, browsed through `mtp://` URLs
+local function devices()
+	if ya.target_os() ~= "linux" then
+		return {}
+	end
+
+	local output = Command("gio"):arg({ "mount", "-li" }):stdout(Command.PIPED):output()
+	if not output then
+		return {}
+	end
+
+	local t, seen = {}, {}
+	for scheme, host in output.stdout:gmatch("activation_root=(%w+)://([^/\n]+)/") do
+		if (scheme == "mtp" or scheme == "gphoto2") and not seen[host] then
+			seen[host] = true
+			t[#t + 1] = { src = string.format("mtp://%s//", host), label = host:gsub("_", " "), mtp = true }
+		end
+	end
+	return t
+end
+
+local function describe(p)
+	local s = p.label and string.format("%s (%s)", p.label, p.src) or p.src
+	if p.fstype then
+		s = string.format("%s [%s]", s, p.fstype)
+	end
+	return p.dist and string.format("%s  →  %s", s, p.dist) or s
+end
+
+local function command(action, p)
+	local os = ya.target_os()
+	if os == "linux" then
+		local verb = action == "eject" and "power-off" or action
+		return "udisksctl", { verb, "--no-user-interaction", "-b", p.src }
+	elseif os == "macos" then
+		return "diskutil", { action, p.src }
+	elseif os == "windows" and action == "eject" then
+		local script = "(New-Object -ComObject Shell.Application).NameSpace(17).ParseName('%s').InvokeVerb('Eject')"
+		return "powershell", { "-NoProfile", "-Command", script:format(p.src) }
+	end
+end
+
+local function run(action, p)
+	local cmd, args = command(action, p)
+	if not cmd then
+		return fail("Cannot %s `%s` on this platform", action, p.src)
+	end
+
+	local output, err = Command(cmd):arg(args):stdout(Command.PIPED):stderr(Command.PIPED):output()
+	if not output then
+		return fail("Failed to start `%s`, error: %s", cmd, err)
+	elseif not output.status.success then
+		return fail("Failed to %s `%s`: %s", action, p.src, output.stderr:gsub("%s+$", ""))
+	end
+	return true
+end
+
+local function mount(p)
+	if not run("mount", p) then
+		return
+	end
+
+	-- The mount point shows up once the partitions are updated
+	for _ = 1, 25 do
+		local new = find(p.src)
+		if new and new.dist then
+			return ya.emit("cd", { new.dist, raw = true })
+		end
+		ya.sleep(0.2)
+	end
+end
+
+local function unmount(p, eject)
+	if p.dist and under(cwd(), p.dist) then
+		ya.emit("cd", { Url(p.dist).parent or "~", raw = true })
+	end
+
+	-- Linux powers off the whole drive, which has to be unmounted first
+	if p.dist and (not eject or ya.target_os() == "linux") and not run("unmount", p) then
+		return
+	end
+	if eject then
+		run("eject", p)
+	end
+end
+
+local function entry()
+	local list = drives()
+	for _, d in ipairs(devices()) do
+		list[#list + 1] = d
+	end
+	if #list == 0 then
+		return fail("No drives found")
+	end
+
+	local cands = {}
+	for i, p in ipairs(list) do
+		if i > #KEYS then
+			break
+		end
+		cands[i] = { on = KEYS:sub(i, i), desc = describe(p) }
+	end
+
+	local p = list[ya.which { cands = cands }]
+	if not p then
+		return
+	elseif p.mtp then
+		return ya.emit("cd", { p.src, raw = true })
+	elseif not p.dist then
+		return mount(p)
+	end
+
+	local action = ya.which {
+		cands = {
+			{ on = "<Enter>", desc = "Enter the mount point" },
+			{ on = "u", desc = "Unmount" },
+			{ on = "e", desc = "Eject" },
+		},
+	}
+	if action == 1 then
+		ya.emit("cd", { p.dist, raw = true })
+	elseif action then
+		unmount(p, action == 3)
+	end
+end
+
+return { entry = entry }
@@ -1,6 +1,13 @@
 local M = {}
 
+-- Without a graphics protocol, the text of the page reads better than chafa's blocks
+local function textual() return rt.preview.text_fallback and ya.host.protocol == "chafa" end
+
 function M:peek(job)
+	if textual() then
+		return self:peek_text(job)
+	end
+
 	local start, cache = os.clock(), ya.file_cache(job)
 	if not cache then
 		return
@@ -17,6 +24,27 @@ function M:peek(job)
 	ya.preview_widget(job, err)
 end
 
+function M:peek_text(job)
+	local output, err = Command("pdftotext")
+		:arg({ "-f", job.skip + 1, "-l", job.skip + 1, "-layout", tostring(job.file.url), "-" })
+		:stdout(Command.PIPED)
+		:stderr(Command.PIPED)
+		:output()
+
+	if not output then
+		return ya.preview_widget(job, Err("Failed to start `pdftotext`, error: %s", err))
+	elseif not output.status.success then
+		local pages = tonumber(output.stderr:match("the last page %((%d+)%)")) or 0
+		if job.skip > 0 and pages > 0 then
+			return ya.emit("peek", { math.max(0, pages - 1), only_if = job.file.url, upper_bound = true })
+		end
+		return ya.preview_widget(job, Err("Failed to extract text from PDF, stderr: %s", output.stderr))
+	end
+
+	local text = output.stdout:gsub("\f", "")
+	ya.preview_widget(job, ui.Text(text):area(job.area))
+end
+
 function M:seek(job)
 	local h = cx.active.current.hovered
 	if h and h.url == job.file.url then
@@ -27,7 +55,7 @@ end
 
 function M:preload(job)
 	local cache = ya.file_cache(job)
-	if not cache or fs.cha(cache) then
+	if not cache or textual() or fs.cha(cache) then
 		return true
 	end
 
@@ -1,7 +1,5 @@
-local function setup(_, opts)
-	if opts.sync_yanked then
-		ps.sub_remote("@yank", function(opt) ya.emit("update_yanked", { opt = opt }) end)
-	end
-end
+-- The yanked files are synced between instances by Yazi itself, see `sync_yanked` under `[mgr]`,
+-- so the `sync_yanked` option here is no longer needed, and is kept only for compatibility
+local function setup() end
 
 return { setup = setup }
@@ -0,0 +1,9 @@
+-- Runs the keypress handler of a status line segment, e.g. `plugin status -- git`
+
+local press = ya.sync(function(_, name) Status:press(name) end)
+
+local M = {}
+
+function M:entry(job) press(job.args[1]) end
+
+return M
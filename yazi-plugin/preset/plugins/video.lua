@@ -1,12 +1,17 @@
+--- @timeout 30
+
 local M = {}
 
+-- Frames are cached by content, so the filmstrip of a file moved elsewhere is reused
+local function frame_cache(job, i) return ya.file_cache { file = job.file, skip = i, content = true } end
+
 function M:peek(job)
-	local start, cache = os.clock(), ya.file_cache(job)
+	local start, cache = os.clock(), frame_cache(job, job.skip)
 	if not cache then
 		return
 	end
 
-	local ok, err = self:preload(job)
+	local ok, err = self:frame(job, job.skip)
 	if not ok or err then
 		return ya.preview_widget(job, err)
 	end
@@ -20,38 +25,57 @@ end
 function M:seek(job)
 	local h = cx.active.current.hovered
 	if h and h.url == job.file.url then
-		ya.emit("peek", {
-			math.max(0, cx.active.preview.skip + job.units),
-			only_if = job.file.url,
-		})
+		local step = ya.clamp(-1, job.units, 1)
+		ya.emit("peek", { math.max(0, cx.active.preview.skip + step), only_if = job.file.url })
 	end
 end
 
+-- Generates the whole filmstrip ahead, so seeking through it is instant
 function M:preload(job)
-	local cache = ya.file_cache(job)
+	local meta
+	for i = 0, rt.preview.video_frames - 1 do
+		local ok, err
+		ok, err, meta = self:frame(job, i, meta)
+		if not ok or err then
+			return ok, err
+		end
+	end
+	return true
+end
+
+-- Generates the `i`-th of the frames spread across the duration, where the first
+-- is the cover instead if the video has one
+function M:frame(job, i, meta)
+	if i >= rt.preview.video_frames then
+		ya.emit("peek", { rt.preview.video_frames - 1, only_if = job.file.url, upper_bound = true })
+		return false
+	end
+
+	local cache = frame_cache(job, i)
 	if not cache then
 		return true
 	end
 
 	local cha = fs.cha(cache)
 	if cha and cha.len > 0 then
-		return true
+		return true, nil, meta
 	end
 
-	local meta, err = self.list_meta(job.file.url, "format=duration:stream_disposition=attached_pic")
 	if not meta then
-		return true, err
-	elseif not meta.format.duration then
-		return true, Err("Failed to get video duration")
+		local err
+		meta, err = self.list_meta(job.file.url, "format=duration:stream_disposition=attached_pic")
+		if not meta then
+			return true, err
+		end
 	end
 
-	local pic = M.has_pic(meta)
-	local percent = (pic and 0 or 5) + job.skip
-	if percent > 95 then
-		ya.emit("peek", { pic and 95 or 90, only_if = job.file.url, upper_bound = true })
-		return false
+	if not meta.format.duration then
+		return true, Err("Failed to get video duration")
 	end
 
+	local pic = i == 0 and M.has_pic(meta)
+	local percent = 5 + 90 * i / math.max(1, rt.preview.video_frames - 1)
+
 	-- stylua: ignore
 	local cmd = Command("ffmpeg"):arg({
 		"-v", "quiet", "-threads", 1, "-hwaccel", "auto",
@@ -59,11 +83,11 @@ function M:preload(job)
 		"-an", "-sn", "-dn",
 	})
 
-	if percent ~= 0 then
+	if not pic then
 		cmd:arg { "-ss", math.floor(meta.format.duration * percent / 100) }
 	end
 	cmd:arg { "-i", tostring(job.file.url) }
-	if percent == 0 then
+	if pic then
 		cmd:arg { "-map", "disp:attached_pic" }
 	end
 
@@ -81,7 +105,7 @@ function M:preload(job)
 	elseif not status.success then
 		return false, Err("`ffmpeg` exited with error code: %s", status.code)
 	else
-		return true
+		return true, nil, meta
 	end
 end
 
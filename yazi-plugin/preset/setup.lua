@@ -1,4 +1,3 @@
 os.setlocale("")
 
-require("dds"):setup()
 require("extract"):setup()
@@ -0,0 +1,76 @@
+use std::path::Path;
+
+use anyhow::{Result, bail};
+use regex::bytes::{Regex, RegexBuilder};
+use tokio::{fs, io::AsyncReadExt, sync::mpsc::{self, Receiver}};
+use yazi_config::search::SearchMode;
+use yazi_fs::{File, Filter, FilterCase};
+
+use super::{BUFFER, SearchOpt};
+
+// Only the start of a file is searched, instead of holding the whole of a large one
+const CONTENT_LIMIT: u64 = 16 << 20;
+
+// Files with a NUL in their first bytes are binary, the same heuristic as `grep -I`
+const BINARY_PROBE: u64 = 8192;
+
+/// The pure-Rust search engine, for when no external backend is installed.
+pub(super) fn builtin(mode: SearchMode, opt: SearchOpt) -> Result<Receiver<File>> {
+	let (filter, re) = match mode {
+		SearchMode::Name => (Some(Filter::new(&opt.subject, FilterCase::Smart)?), None),
+		SearchMode::Content => {
+			let re = RegexBuilder::new(&opt.subject)
+				.case_insensitive(!opt.subject.chars().any(char::is_uppercase))
+				.build()?;
+			(None, Some(re))
+		}
+	};
+
+	let Some(root) = opt.cwd.as_path().map(ToOwned::to_owned) else {
+		bail!("search can only be performed on the local filesystem");
+	};
+
+	let (tx, rx) = mpsc::channel(BUFFER);
+
+	tokio::spawn(async move {
+		let mut dirs = vec![root.clone()];
+		while let Some(dir) = dirs.pop() {
+			let Ok(mut it) = fs::read_dir(&dir).await else { continue };
+			while let Ok(Some(entry)) = it.next_entry().await {
+				let Ok(meta) = entry.metadata().await else { continue };
+				let (path, is_dir) = (entry.path(), meta.is_dir());
+
+				// Joined as relative, so the URL stays in the search
+				let rel = path.strip_prefix(&root).unwrap_or(&path);
+				let file = File::from_follow(opt.cwd.join(rel), meta).await;
+				if !opt.hidden && file.cha.is_hidden() {
+					continue;
+				} else if is_dir {
+					dirs.push(path.clone());
+				}
+
+				let matched = match (&filter, &re) {
+					(Some(filter), _) => filter.matches_with(file.name(), &file.cha),
+					(_, Some(re)) => !is_dir && content_matches(&path, re).await,
+					(None, None) => false,
+				};
+				if matched && tx.send(file).await.is_err() {
+					return;
+				}
+			}
+		}
+	});
+	Ok(rx)
+}
+
+async fn content_matches(path: &Path, re: &Regex) -> bool {
+	let Ok(file) = fs::File::open(path).await else { return false };
+	let mut file = file.take(CONTENT_LIMIT);
+
+	let mut buf = Vec::with_capacity(BINARY_PROBE as usize);
+	if (&mut file).take(BINARY_PROBE).read_to_end(&mut buf).await.is_err() || buf.contains(&0) {
+		return false;
+	}
+
+	file.read_to_end(&mut buf).await.is_ok() && re.is_match(&buf)
+}
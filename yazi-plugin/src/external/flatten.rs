@@ -0,0 +1,43 @@
+use anyhow::{Result, bail};
+use tokio::{fs, sync::mpsc::{self, UnboundedReceiver}};
+use yazi_fs::File;
+use yazi_shared::url::Url;
+
+pub struct FlattenOpt {
+	pub cwd:    Url,
+	pub hidden: bool,
+	pub depth:  usize,
+}
+
+/// Streams the files and directories under `cwd`, up to `depth` levels deep.
+pub fn flatten(opt: FlattenOpt) -> Result<UnboundedReceiver<File>> {
+	let Some(root) = opt.cwd.as_path().map(ToOwned::to_owned) else {
+		bail!("flatten can only be performed on the local filesystem");
+	};
+
+	let (tx, rx) = mpsc::unbounded_channel();
+	tokio::spawn(async move {
+		let mut dirs = vec![(root.clone(), 1)];
+		while let Some((dir, depth)) = dirs.pop() {
+			let Ok(mut it) = fs::read_dir(&dir).await else { continue };
+			while let Ok(Some(entry)) = it.next_entry().await {
+				let Ok(meta) = entry.metadata().await else { continue };
+				let (path, is_dir) = (entry.path(), meta.is_dir());
+
+				// Joined as relative, so the URL stays in the search
+				let rel = path.strip_prefix(&root).unwrap_or(&path);
+				let file = File::from_follow(opt.cwd.join(rel), meta).await;
+				if !opt.hidden && file.cha.is_hidden() {
+					continue;
+				} else if is_dir && depth < opt.depth {
+					dirs.push((path, depth + 1));
+				}
+
+				if tx.send(file).is_err() {
+					return;
+				}
+			}
+		}
+	});
+	Ok(rx)
+}
@@ -0,0 +1,72 @@
+use std::{path::PathBuf, process::Stdio};
+
+use anyhow::{Context, Result, bail};
+use tokio::{io::{AsyncBufReadExt, BufReader}, process::Command, sync::mpsc::{self, UnboundedReceiver}};
+use yazi_fs::File;
+use yazi_shared::{IntoOsStr, url::Url};
+
+pub struct GrepOpt {
+	pub cwd:     Url,
+	pub hidden:  bool,
+	pub subject: String,
+	pub args:    Vec<String>,
+}
+
+/// Streams the lines matching the subject in the files under `cwd` via ripgrep.
+pub fn grep(opt: GrepOpt) -> Result<UnboundedReceiver<File>> {
+	let Some(path) = opt.cwd.as_path() else {
+		bail!("grep can only be performed on the local filesystem");
+	};
+
+	// Without a path, ripgrep prints the files relative to its working directory
+	let mut child = Command::new("rg")
+		.args(["--color=never", "--line-number", "--no-heading", "--with-filename", "--null"])
+		.args(["--smart-case", if opt.hidden { "--hidden" } else { "--no-hidden" }])
+		.args(&opt.args)
+		.arg("-e")
+		.arg(&opt.subject)
+		.current_dir(path)
+		.kill_on_drop(true)
+		.stdout(Stdio::piped())
+		.stderr(Stdio::null())
+		.spawn()
+		.context("Failed to run `rg`, make sure ripgrep is installed")?;
+
+	let mut it = BufReader::new(child.stdout.take().unwrap());
+	let (tx, rx) = mpsc::unbounded_channel();
+
+	tokio::spawn(async move {
+		let mut buf = Vec::new();
+		let mut last: Option<(PathBuf, File)> = None;
+		while matches!(it.read_until(b'\n', &mut buf).await, Ok(n) if n > 0) {
+			let hit = parse_line(&buf);
+			buf.clear();
+
+			let Some((path, line)) = hit else { continue };
+			// The matches of a file come in a row, so it's only read once
+			if last.as_ref().is_none_or(|(p, _)| *p != path) {
+				let Ok(file) = File::new(opt.cwd.join(&path)).await else { continue };
+				last = Some((path, file));
+			}
+
+			let (_, file) = last.as_ref().unwrap();
+			let url = file.url.clone().with_frag(Some(line));
+			if tx.send(File { url, ..file.clone() }).is_err() {
+				break;
+			}
+		}
+		child.wait().await.ok();
+	});
+	Ok(rx)
+}
+
+/// Splits a line of the output, `<path>\0<line>:<text>`, into the path and the line number.
+fn parse_line(line: &[u8]) -> Option<(PathBuf, u32)> {
+	let nul = line.iter().position(|&b| b == b'\0')?;
+	let (path, rest) = (&line[..nul], &line[nul + 1..]);
+
+	let colon = rest.iter().position(|&b| b == b':')?;
+	let n = str::from_utf8(&rest[..colon]).ok()?.parse().ok()?;
+
+	if path.is_empty() { None } else { Some((path.into_os_str().ok()?.into_owned().into(), n)) }
+}
@@ -1,37 +1,46 @@
-use std::{borrow::Cow, io::Cursor, mem, path::{Path, PathBuf}, sync::OnceLock};
+use std::{borrow::Cow, io::Cursor, mem, path::PathBuf, sync::{Arc, OnceLock}};
 
 use anyhow::{Result, anyhow};
+use parking_lot::Mutex;
 use ratatui::{layout::Size, text::{Line, Span, Text}};
 use syntect::{LoadingError, dumps, easy::HighlightLines, highlighting::{self, Theme, ThemeSet}, parsing::{SyntaxReference, SyntaxSet}};
 use tokio::io::AsyncBufReadExt;
 use yazi_config::{THEME, YAZI, preview::PreviewWrap};
-use yazi_fs::provider::local::Local;
-use yazi_shared::{Ids, errors::PeekError, replace_to_printable};
+use yazi_fs::provider;
+use yazi_shared::{Ids, errors::PeekError, replace_to_printable, url::Url};
 
 static INCR: Ids = Ids::new();
-static SYNTECT: OnceLock<(Theme, SyntaxSet)> = OnceLock::new();
+static SYNTAXES: OnceLock<SyntaxSet> = OnceLock::new();
+static TMTHEME: Mutex<Option<(PathBuf, Arc<Theme>)>> = Mutex::new(None);
 
 pub struct Highlighter {
-	path: PathBuf,
+	url: Url,
 }
 
 impl Highlighter {
 	#[inline]
-	pub fn new(path: &Path) -> Self { Self { path: path.to_owned() } }
-
-	pub fn init() -> (&'static Theme, &'static SyntaxSet) {
-		let f = || {
-			let theme = std::fs::File::open(&THEME.mgr.syntect_theme)
-				.map_err(LoadingError::Io)
-				.and_then(|f| ThemeSet::load_from_reader(&mut std::io::BufReader::new(f)))
-				.or_else(|_| ThemeSet::load_from_reader(&mut Cursor::new(yazi_prebuilt::ansi_theme())));
-
-			let syntaxes = dumps::from_uncompressed_data(yazi_prebuilt::syntaxes());
+	pub fn new(url: &Url) -> Self { Self { url: url.clone() } }
+
+	/// The theme of the current flavor, loaded again once the flavor is switched, and the syntaxes.
+	pub fn init() -> (Arc<Theme>, &'static SyntaxSet) {
+		let syntaxes =
+			SYNTAXES.get_or_init(|| dumps::from_uncompressed_data(yazi_prebuilt::syntaxes()).unwrap());
+
+		let path = &THEME.mgr.syntect_theme;
+		let mut tmtheme = TMTHEME.lock();
+		if let Some((p, theme)) = &*tmtheme
+			&& p == path
+		{
+			return (theme.clone(), syntaxes);
+		}
 
-			(theme.unwrap(), syntaxes.unwrap())
-		};
+		let theme = std::fs::File::open(path)
+			.map_err(LoadingError::Io)
+			.and_then(|f| ThemeSet::load_from_reader(&mut std::io::BufReader::new(f)))
+			.or_else(|_| ThemeSet::load_from_reader(&mut Cursor::new(yazi_prebuilt::ansi_theme())));
 
-		let (theme, syntaxes) = SYNTECT.get_or_init(f);
+		let theme = Arc::new(theme.unwrap());
+		*tmtheme = Some((path.clone(), theme.clone()));
 		(theme, syntaxes)
 	}
 
@@ -39,9 +48,9 @@ impl Highlighter {
 	pub fn abort() { INCR.next(); }
 
 	pub async fn highlight(&self, skip: usize, size: Size) -> Result<Text<'static>, PeekError> {
-		let mut reader = Local::open(&self.path).await?.reader();
+		let mut reader = provider::open(&self.url).await?.reader();
 
-		let syntax = Self::find_syntax(&self.path).await;
+		let syntax = Self::find_syntax(&self.url).await;
 		let mut plain = syntax.is_err();
 
 		let mut before = Vec::with_capacity(if plain { 0 } else { skip });
@@ -105,7 +114,7 @@ impl Highlighter {
 
 		tokio::task::spawn_blocking(move || {
 			let (theme, syntaxes) = Self::init();
-			let mut h = HighlightLines::new(syntax, theme);
+			let mut h = HighlightLines::new(syntax, &theme);
 
 			for line in before {
 				if ticket != INCR.current() {
@@ -130,21 +139,21 @@ impl Highlighter {
 		.await?
 	}
 
-	async fn find_syntax(path: &Path) -> Result<&'static SyntaxReference> {
+	async fn find_syntax(url: &Url) -> Result<&'static SyntaxReference> {
 		let (_, syntaxes) = Self::init();
-		let name = path.file_name().map(|n| n.to_string_lossy()).unwrap_or_default();
+		let name = url.file_name().map(|n| n.to_string_lossy()).unwrap_or_default();
 		if let Some(s) = syntaxes.find_syntax_by_extension(&name) {
 			return Ok(s);
 		}
 
-		let ext = path.extension().map(|e| e.to_string_lossy()).unwrap_or_default();
+		let ext = url.extension().map(|e| e.to_string_lossy()).unwrap_or_default();
 		if let Some(s) = syntaxes.find_syntax_by_extension(&ext) {
 			return Ok(s);
 		}
 
-		let mut line = String::new();
-		let mut reader = Local::open(&path).await?.reader();
-		reader.read_line(&mut line).await?;
+		// Shebangs and modelines are short, so don't read more than needed
+		let head = provider::read_range(url, 0, 1024).await?;
+		let line = String::from_utf8_lossy(head.split(|&b| b == b'\n').next().unwrap_or_default());
 		syntaxes.find_syntax_by_first_line(&line).ok_or_else(|| anyhow!("No syntax found"))
 	}
 
@@ -0,0 +1,34 @@
+use anyhow::Result;
+use regex::bytes::RegexBuilder;
+use tokio::sync::mpsc::{self, Receiver};
+use yazi_config::search::SearchMode;
+use yazi_fs::{File, INDEX};
+
+use super::{BUFFER, SearchOpt, builtin};
+
+/// Searches the file names in the index, falling back to the builtin engine.
+pub(super) fn index(opt: SearchOpt) -> Result<Receiver<File>> {
+	let re = RegexBuilder::new(&opt.subject)
+		.case_insensitive(!opt.subject.chars().any(char::is_uppercase))
+		.build()?;
+
+	let found = opt
+		.cwd
+		.as_path()
+		.and_then(|dir| INDEX.search(dir, opt.hidden, |name| re.is_match(name.as_encoded_bytes())));
+	let Some(found) = found else {
+		return builtin(SearchMode::Name, opt);
+	};
+
+	let (tx, rx) = mpsc::channel(BUFFER);
+	tokio::spawn(async move {
+		for path in found {
+			// The index can be behind the file system, where the file is gone
+			let Ok(file) = File::new(opt.cwd.join(path)).await else { continue };
+			if tx.send(file).await.is_err() {
+				break;
+			}
+		}
+	});
+	Ok(rx)
+}
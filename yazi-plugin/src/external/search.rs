@@ -0,0 +1,102 @@
+use std::{collections::HashSet, path::PathBuf, process::Stdio};
+
+use anyhow::{Result, bail};
+use tokio::{io::{AsyncBufReadExt, BufReader}, process::Command, sync::mpsc::{self, Receiver}};
+use yazi_config::{YAZI, search::{SearchBackend, SearchMode}};
+use yazi_fs::{File, Filter, FilterCase};
+use yazi_shared::{IntoOsStr, url::Url};
+
+use super::{builtin, index};
+
+// The found files waiting for the receiver, which holds up the search once reached
+pub(super) const BUFFER: usize = 5000;
+
+pub struct SearchOpt {
+	pub via:     String,
+	pub cwd:     Url,
+	pub hidden:  bool,
+	pub subject: String,
+	pub args:    Vec<String>,
+}
+
+pub fn search(opt: SearchOpt) -> Result<Receiver<File>> {
+	let Some(path) = opt.cwd.as_path() else {
+		bail!("search can only be performed on the local filesystem");
+	};
+
+	// Queries of the filter language are only understood by the builtin engine
+	let (mode, name) = YAZI.search.pick(&opt.via);
+	if mode == SearchMode::Name
+		&& Filter::new(&opt.subject, FilterCase::Smart).is_ok_and(|f| !f.is_plain())
+	{
+		return builtin(mode, opt);
+	} else if name == SearchBackend::INDEX {
+		return index(opt);
+	}
+	let Some(backend) = YAZI.search.backends.get(name) else {
+		return builtin(mode, opt);
+	};
+
+	let mut child = Command::new(&backend.run[0])
+		.args(backend.args(&opt.subject, path, opt.hidden, &opt.args))
+		.kill_on_drop(true)
+		.stdout(Stdio::piped())
+		.stderr(Stdio::null())
+		.spawn()?;
+
+	let mut it = BufReader::new(child.stdout.take().unwrap());
+	let (tx, rx) = mpsc::channel(BUFFER);
+	let (sep, lines) = (if backend.nul { b'\0' } else { b'\n' }, backend.lines);
+	let root = path.to_owned();
+
+	tokio::spawn(async move {
+		let mut buf = Vec::new();
+		let mut seen = HashSet::new();
+		while matches!(it.read_until(sep, &mut buf).await, Ok(n) if n > 0) {
+			let path = parse_line(&buf, sep, lines);
+			buf.clear();
+
+			let Some(path) = path else { continue };
+			if lines && !seen.insert(path.clone()) {
+				continue;
+			}
+			// Joined as relative, so the URL stays in the search
+			let rel = path.strip_prefix(&root).unwrap_or(&path);
+			if let Ok(file) = File::new(opt.cwd.join(rel)).await
+				&& tx.send(file).await.is_err()
+			{
+				break;
+			}
+		}
+		child.wait().await.ok();
+	});
+	Ok(rx)
+}
+
+/// Extracts the path from a line of the backend output.
+fn parse_line(line: &[u8], sep: u8, lines: bool) -> Option<PathBuf> {
+	let mut line = line.strip_suffix(&[sep]).unwrap_or(line);
+	if sep == b'\n' {
+		line = line.strip_suffix(b"\r").unwrap_or(line);
+	}
+
+	if lines {
+		line = line_number_at(line).map_or(line, |i| &line[..i]);
+	}
+
+	if line.is_empty() { None } else { line.into_os_str().ok().map(|s| s.into_owned().into()) }
+}
+
+/// Finds the first `:<digits>:` in the line, and returns the index of its leading colon.
+fn line_number_at(line: &[u8]) -> Option<usize> {
+	let mut start = None;
+	for (i, &b) in line.iter().enumerate() {
+		match (start, b) {
+			(Some(s), b':') if i > s + 1 => return Some(s),
+			(_, b':') => start = Some(i),
+			(Some(_), b'0'..=b'9') => {}
+			_ => start = None,
+		}
+	}
+	None
+}
@@ -3,7 +3,7 @@ use std::{borrow::Cow, str::FromStr};
 use mlua::{ExternalError, Function, IntoLua, IntoLuaMulti, Lua, Table, Value};
 use yazi_binding::{Cha, Composer, ComposerGet, ComposerSet, Error, File, Url, UrlRef};
 use yazi_config::Pattern;
-use yazi_fs::{mounts::PARTITIONS, provider, remove_dir_clean};
+use yazi_fs::{Cloud, GIT, TRASH_USAGE, Xattr, mounts::PARTITIONS, provider, remove_dir_clean};
 
 use crate::bindings::SizeCalculator;
 
@@ -21,6 +21,11 @@ pub fn compose() -> Composer<ComposerGet, ComposerSet> {
 			b"expand_url" => expand_url(lua)?,
 			b"unique_name" => unique_name(lua)?,
 			b"partitions" => partitions(lua)?,
+			b"trash_usage" => trash_usage(lua)?,
+			b"xattrs" => xattrs(lua)?,
+			b"cloud" => cloud(lua)?,
+			b"git_refresh" => git_refresh(lua)?,
+			b"provide" => provide(lua)?,
 			_ => return Ok(Value::Nil),
 		}
 		.into_lua(lua)
@@ -177,21 +182,73 @@ fn unique_name(lua: &Lua) -> mlua::Result<Function> {
 }
 
 fn partitions(lua: &Lua) -> mlua::Result<Function> {
-	lua.create_async_function(|lua, ()| async move {
+	lua.create_function(|lua, ()| {
 		PARTITIONS
 			.read()
 			.iter()
 			.filter(|&p| !p.systemic())
 			.map(|p| {
 				lua.create_table_from([
-					("src", p.src.clone().into_lua(&lua)?),
-					("dist", p.dist.clone().into_lua(&lua)?),
-					("label", p.label.clone().into_lua(&lua)?),
-					("fstype", p.fstype.clone().into_lua(&lua)?),
-					("external", p.external.into_lua(&lua)?),
-					("removable", p.removable.into_lua(&lua)?),
+					("src", p.src.clone().into_lua(lua)?),
+					("dist", p.dist.clone().into_lua(lua)?),
+					("label", p.label.clone().into_lua(lua)?),
+					("fstype", p.fstype.clone().into_lua(lua)?),
+					("external", p.external.into_lua(lua)?),
+					("removable", p.removable.into_lua(lua)?),
 				])
 			})
 			.collect::<mlua::Result<Vec<Table>>>()
 	})
 }
+
+fn trash_usage(lua: &Lua) -> mlua::Result<Function> {
+	lua.create_function(|lua, ()| {
+		let Some(usage) = *TRASH_USAGE.read() else { return Ok(None) };
+		lua.create_table_from([("items", usage.items as u64), ("size", usage.size)]).map(Some)
+	})
+}
+
+fn xattrs(lua: &Lua) -> mlua::Result<Function> {
+	lua.create_async_function(|lua, url: UrlRef| async move {
+		let attrs = match Xattr::list(&url).await {
+			Ok(v) => v,
+			Err(e) => return (Value::Nil, Error::Io(e)).into_lua_multi(&lua),
+		};
+
+		let attrs = attrs.into_iter().map(|(name, value)| {
+			lua.create_table_from([
+				("name", lua.create_string(name.as_encoded_bytes())?),
+				("value", lua.create_string(Xattr::display(&name, &value))?),
+				("raw", lua.create_string(value)?),
+			])
+		});
+		lua.create_sequence_from(attrs.collect::<mlua::Result<Vec<_>>>()?)?.into_lua_multi(&lua)
+	})
+}
+
+fn cloud(lua: &Lua) -> mlua::Result<Function> {
+	lua.create_async_function(|lua, url: UrlRef| async move {
+		match Cloud::status(&url).await {
+			Ok(Some(Cloud::Online)) => "online".into_lua_multi(&lua),
+			Ok(Some(Cloud::Local)) => "local".into_lua_multi(&lua),
+			Ok(None) => Value::Nil.into_lua_multi(&lua),
+			Err(e) => (Value::Nil, Error::Io(e)).into_lua_multi(&lua),
+		}
+	})
+}
+
+fn provide(lua: &Lua) -> mlua::Result<Function> {
+	lua.create_function(|_, name: mlua::String| {
+		yazi_fs::provider::vfs::Vfs::register(&name.to_str()?);
+		Ok(())
+	})
+}
+
+fn git_refresh(lua: &Lua) -> mlua::Result<Function> {
+	lua.create_async_function(|lua, dir: UrlRef| async move {
+		match GIT.refresh(&dir).await {
+			Ok(b) => b.into_lua_multi(&lua),
+			Err(e) => (false, Error::Io(e)).into_lua_multi(&lua),
+		}
+	})
+}
@@ -0,0 +1,89 @@
+use std::{collections::BTreeMap, time::{Duration, Instant}};
+
+use parking_lot::Mutex;
+
+pub static BREAKER: Breaker = Breaker::new();
+
+/// Timeouts in a row after which a previewer is skipped.
+const TRIPS: u8 = 3;
+
+/// How long a previewer is skipped for once it has tripped the breaker.
+const COOLDOWN: Duration = Duration::from_secs(60);
+
+/// Skips a previewer that keeps timing out for a while.
+pub struct Breaker {
+	trips: Mutex<BTreeMap<String, Trip>>,
+}
+
+#[derive(Default)]
+struct Trip {
+	count: u8,
+	until: Option<Instant>,
+}
+
+impl Breaker {
+	const fn new() -> Self { Self { trips: Mutex::new(BTreeMap::new()) } }
+
+	/// The remaining cooldown of the previewer, if it's being skipped.
+	#[inline]
+	pub fn cooldown(&self, name: &str) -> Option<Duration> { self.cooldown_at(name, Instant::now()) }
+
+	/// Records a timeout of the previewer, returns `true` if it has just tripped the breaker.
+	#[inline]
+	pub fn trip(&self, name: &str) -> bool { self.trip_at(name, Instant::now()) }
+
+	#[inline]
+	pub fn reset(&self, name: &str) { self.trips.lock().remove(name); }
+
+	fn cooldown_at(&self, name: &str, now: Instant) -> Option<Duration> {
+		let mut trips = self.trips.lock();
+		let left = trips.get(name)?.until?.saturating_duration_since(now);
+		if left.is_zero() {
+			trips.remove(name);
+			return None;
+		}
+		Some(left)
+	}
+
+	fn trip_at(&self, name: &str, now: Instant) -> bool {
+		let mut trips = self.trips.lock();
+		let trip = trips.entry(name.to_owned()).or_default();
+
+		trip.count += 1;
+		if trip.count < TRIPS {
+			return false;
+		}
+
+		*trip = Trip { count: 0, until: Some(now + COOLDOWN) };
+		true
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_breaker() {
+		let breaker = Breaker::new();
+		let now = Instant::now();
+
+		assert!(!breaker.trip_at("pdf", now));
+		assert!(!breaker.trip_at("pdf", now));
+		assert_eq!(breaker.cooldown_at("pdf", now), None);
+
+		// A success in between starts the count over
+		breaker.reset("pdf");
+		assert!(!breaker.trip_at("pdf", now));
+		assert!(!breaker.trip_at("pdf", now));
+		assert!(breaker.trip_at("pdf", now));
+
+		assert_eq!(breaker.cooldown_at("pdf", now), Some(COOLDOWN));
+		assert_eq!(breaker.cooldown_at("video", now), None);
+
+		// Half of the cooldown has passed, then all of it
+		assert_eq!(breaker.cooldown_at("pdf", now + COOLDOWN / 2), Some(COOLDOWN / 2));
+		assert_eq!(breaker.cooldown_at("pdf", now + COOLDOWN), None);
+		assert!(!breaker.trip_at("pdf", now + COOLDOWN));
+	}
+}
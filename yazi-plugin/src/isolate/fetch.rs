@@ -1,14 +1,16 @@
 use mlua::{ExternalResult, FromLua, IntoLua, Lua, ObjectLike, Value};
 use tokio::runtime::Handle;
 use yazi_binding::{Error, File};
+use yazi_config::plugin::Hint;
 use yazi_dds::Sendable;
 use yazi_shared::event::CmdCow;
 
-use super::slim_lua;
-use crate::loader::LOADER;
+use super::{GATE, slim_lua};
+use crate::{loader::LOADER, process::Group};
 
 pub async fn fetch(
 	cmd: CmdCow,
+	hint: &Hint,
 	files: Vec<yazi_fs::File>,
 ) -> mlua::Result<(FetchState, Option<Error>)> {
 	if files.is_empty() {
@@ -16,8 +18,11 @@ pub async fn fetch(
 	}
 	LOADER.ensure(&cmd.name, |_| ()).await.into_lua_err()?;
 
+	let _permit = GATE.acquire(&cmd.name, hint).await;
+
 	tokio::task::spawn_blocking(move || {
 		let lua = slim_lua(&cmd.name)?;
+		lua.set_app_data(Group::limited());
 		let plugin = LOADER.load_once(&lua, &cmd.name)?;
 
 		Handle::current().block_on(plugin.call_async_method(
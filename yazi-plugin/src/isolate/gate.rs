@@ -0,0 +1,218 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use parking_lot::Mutex;
+use tokio::sync::Notify;
+use yazi_config::{YAZI, plugin::Hint};
+
+pub static GATE: Gate = Gate::new();
+
+/// Holds back plugin runs until their declared [`Hint`]s allow them to start.
+pub struct Gate {
+	state:  Mutex<GateState>,
+	notify: Notify,
+}
+
+#[derive(Default)]
+struct GateState {
+	plugins: BTreeMap<String, GateStat>,
+	groups:  BTreeSet<String>,
+	memory:  u64,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GateStat {
+	pub running: usize,
+	pub queued:  usize,
+}
+
+impl Gate {
+	const fn new() -> Self {
+		Self {
+			state:  Mutex::new(GateState {
+				plugins: BTreeMap::new(),
+				groups:  BTreeSet::new(),
+				memory:  0,
+			}),
+			notify: Notify::const_new(),
+		}
+	}
+
+	/// Waits until the plugin is allowed to run.
+	#[inline]
+	pub async fn acquire(&'static self, name: &str, hint: &Hint) -> GatePermit {
+		self.acquire_with(name, hint, YAZI.tasks.plugin_memory).await
+	}
+
+	async fn acquire_with(&'static self, name: &str, hint: &Hint, cap: u64) -> GatePermit {
+		if hint.is_empty() {
+			return GatePermit { gate: self, name: None, group: None, memory: 0 };
+		}
+
+		let mut queued = QueuedGuard { gate: self, name: None };
+		loop {
+			let notified = self.notify.notified();
+			tokio::pin!(notified);
+			notified.as_mut().enable();
+
+			{
+				let mut state = self.state.lock();
+				if state.admits(name, hint, cap) {
+					let stat = state.plugins.entry(name.to_owned()).or_default();
+					stat.running += 1;
+					if queued.name.take().is_some() {
+						stat.queued -= 1;
+					}
+
+					if let Some(g) = &hint.group {
+						state.groups.insert(g.clone());
+					}
+					state.memory += hint.memory;
+
+					return GatePermit {
+						gate:   self,
+						name:   Some(name.to_owned()),
+						group:  hint.group.clone(),
+						memory: hint.memory,
+					};
+				} else if queued.name.is_none() {
+					state.plugins.entry(name.to_owned()).or_default().queued += 1;
+					queued.name = Some(name.to_owned());
+				}
+			}
+
+			notified.await;
+		}
+	}
+
+	/// Running and queued counts of the plugins that have declared hints.
+	pub fn stats(&self) -> Vec<(String, GateStat)> {
+		self.state.lock().plugins.iter().map(|(k, &v)| (k.clone(), v)).collect()
+	}
+
+	/// Total estimated memory usage of the running plugins, in bytes.
+	#[inline]
+	pub fn memory(&self) -> u64 { self.state.lock().memory }
+}
+
+impl GateState {
+	fn admits(&self, name: &str, hint: &Hint, cap: u64) -> bool {
+		let running = self.plugins.get(name).map_or(0, |s| s.running);
+
+		// A single run exceeding the cap is still allowed when nothing else is
+		// running, otherwise it would be queued forever.
+		hint.concurrency.is_none_or(|n| running < n.get())
+			&& hint.group.as_ref().is_none_or(|g| !self.groups.contains(g))
+			&& (hint.memory == 0 || cap == 0 || self.memory == 0 || self.memory + hint.memory <= cap)
+	}
+
+	fn tidy(&mut self, name: &str) {
+		if self.plugins.get(name).is_some_and(|s| s.running == 0 && s.queued == 0) {
+			self.plugins.remove(name);
+		}
+	}
+}
+
+// --- Permit
+pub struct GatePermit {
+	gate:   &'static Gate,
+	name:   Option<String>,
+	group:  Option<String>,
+	memory: u64,
+}
+
+impl Drop for GatePermit {
+	fn drop(&mut self) {
+		let Some(name) = self.name.take() else { return };
+		{
+			let mut state = self.gate.state.lock();
+			if let Some(stat) = state.plugins.get_mut(&name) {
+				stat.running -= 1;
+			}
+			if let Some(g) = &self.group {
+				state.groups.remove(g);
+			}
+			state.memory -= self.memory;
+			state.tidy(&name);
+		}
+		self.gate.notify.notify_waiters();
+	}
+}
+
+// --- Queued
+struct QueuedGuard {
+	gate: &'static Gate,
+	name: Option<String>,
+}
+
+impl Drop for QueuedGuard {
+	fn drop(&mut self) {
+		let Some(name) = self.name.take() else { return };
+		let mut state = self.gate.state.lock();
+		if let Some(stat) = state.plugins.get_mut(&name) {
+			stat.queued -= 1;
+		}
+		state.tidy(&name);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::{num::NonZeroUsize, time::Duration};
+
+	use tokio::time::timeout;
+
+	use super::*;
+
+	fn hint(concurrency: usize, group: Option<&str>, memory: u64) -> Hint {
+		Hint {
+			concurrency: NonZeroUsize::new(concurrency),
+			group: group.map(ToOwned::to_owned),
+			memory,
+		}
+	}
+
+	async fn blocked(gate: &'static Gate, name: &str, hint: &Hint, cap: u64) -> bool {
+		timeout(Duration::from_millis(50), gate.acquire_with(name, hint, cap)).await.is_err()
+	}
+
+	#[tokio::test]
+	async fn test_gate() {
+		let gate: &'static Gate = Box::leak(Box::new(Gate::new()));
+
+		// Concurrency
+		let h = hint(2, None, 0);
+		let a = gate.acquire_with("a", &h, 0).await;
+		let _b = gate.acquire_with("a", &h, 0).await;
+		assert!(blocked(gate, "a", &h, 0).await);
+		assert_eq!(gate.stats(), [("a".to_owned(), GateStat { running: 2, queued: 0 })]);
+
+		// Queued rather than rejected, and resumed once a permit is released
+		let waiter = tokio::spawn(async move { gate.acquire_with("a", &h, 0).await });
+		tokio::time::sleep(Duration::from_millis(20)).await;
+		assert_eq!(gate.stats()[0].1, GateStat { running: 2, queued: 1 });
+		drop(a);
+		let _c = waiter.await.unwrap();
+		assert_eq!(gate.stats()[0].1, GateStat { running: 2, queued: 0 });
+
+		// Groups
+		let (pdf, djvu) = (hint(0, Some("doc"), 0), hint(0, Some("doc"), 0));
+		let p = gate.acquire_with("pdf", &pdf, 0).await;
+		assert!(blocked(gate, "djvu", &djvu, 0).await);
+		drop(p);
+		assert!(!blocked(gate, "djvu", &djvu, 0).await);
+
+		// Memory, where an oversized run alone is still allowed
+		let m = gate.acquire_with("big", &hint(0, None, 300), 200).await;
+		assert!(blocked(gate, "small", &hint(0, None, 10), 200).await);
+		drop(m);
+		let _m1 = gate.acquire_with("small", &hint(0, None, 150), 200).await;
+		assert!(blocked(gate, "small", &hint(0, None, 100), 200).await);
+		assert!(!blocked(gate, "small", &hint(0, None, 50), 200).await);
+
+		// No hints, no limits and no stats
+		for _ in 0..10 {
+			std::mem::forget(gate.acquire_with("free", &Hint::default(), 0).await);
+		}
+		assert!(gate.stats().iter().all(|(k, _)| k != "free"));
+	}
+}
@@ -11,6 +11,7 @@ pub fn slim_lua(name: &str) -> mlua::Result<Lua> {
 	globals.raw_set("ui", crate::elements::compose())?;
 	globals.raw_set("ya", crate::utils::compose(true))?;
 	globals.raw_set("fs", crate::fs::compose())?;
+	globals.raw_set("ps", crate::pubsub::compose(true))?;
 	globals.raw_set("rt", crate::runtime::compose())?;
 	globals.raw_set("th", crate::theme::compose().into_lua(&lua)?)?;
 
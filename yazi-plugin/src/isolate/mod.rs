@@ -1,3 +1,3 @@
 #![allow(clippy::module_inception)]
 
-yazi_macro::mod_flat!(entry fetch isolate peek preload seek spot);
+yazi_macro::mod_flat!(breaker entry fetch gate isolate peek preload run seek serve spot vfs);
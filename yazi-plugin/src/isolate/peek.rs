@@ -1,29 +1,43 @@
+use std::time::{Duration, Instant};
+
 use mlua::{ExternalError, HookTriggers, IntoLua, ObjectLike, VmState};
 use tokio::{runtime::Handle, select};
 use tokio_util::sync::CancellationToken;
 use tracing::error;
-use yazi_binding::{File, elements::Rect};
-use yazi_config::LAYOUT;
+use yazi_binding::{Error, File, elements::Rect};
+use yazi_config::{LAYOUT, YAZI, plugin::Previewer};
 use yazi_dds::Sendable;
-use yazi_parser::app::{PluginCallback, PluginOpt};
-use yazi_proxy::AppProxy;
+use yazi_parser::{app::{PluginCallback, PluginOpt}, mgr::{PreviewLock, UpdatePeekedOpt}};
+use yazi_proxy::{AppProxy, MgrProxy};
 use yazi_shared::{SStr, event::Cmd};
 
-use super::slim_lua;
-use crate::loader::LOADER;
+use super::{BREAKER, GATE, slim_lua};
+use crate::{loader::LOADER, process::Group};
 
 pub fn peek(
-	cmd: &'static Cmd,
+	previewer: &'static Previewer,
 	file: yazi_fs::File,
 	mime: SStr,
 	skip: usize,
 ) -> Option<CancellationToken> {
+	let cmd = &previewer.run;
+	if let Some(left) = BREAKER.cooldown(&cmd.name) {
+		let secs = left.as_secs() + 1;
+		notice(
+			&file,
+			mime,
+			skip,
+			format!("`{}` previewer skipped for {secs}s after timeouts", cmd.name),
+		);
+		return None;
+	}
+
 	let ct = CancellationToken::new();
 	if let Some(c) = LOADER.read().get(cmd.name.as_ref()) {
 		if c.sync_peek {
 			peek_sync(cmd, file, mime, skip);
 		} else {
-			peek_async(cmd, file, mime, skip, ct.clone());
+			peek_async(previewer, file, mime, skip, ct.clone());
 		}
 		return Some(ct).filter(|_| !c.sync_peek);
 	}
@@ -34,9 +48,9 @@ pub fn peek(
 			_ = ct_.cancelled() => {},
 			Ok(b) = LOADER.ensure(&cmd.name, |c| c.sync_peek) => {
 				if b {
-					peek_sync( cmd, file, mime, skip);
+					peek_sync(cmd, file, mime, skip);
 				} else {
-					peek_async( cmd, file, mime, skip, ct_);
+					peek_async(previewer, file, mime, skip, ct_);
 				}
 			},
 			else => {}
@@ -63,21 +77,31 @@ fn peek_sync(cmd: &'static Cmd, file: yazi_fs::File, mime: SStr, skip: usize) {
 }
 
 fn peek_async(
-	cmd: &'static Cmd,
+	previewer: &'static Previewer,
 	file: yazi_fs::File,
 	mime: SStr,
 	skip: usize,
 	ct: CancellationToken,
 ) {
-	let ct_ = ct.clone();
+	let (cmd, ct_) = (&previewer.run, ct.clone());
 	tokio::task::spawn_blocking(move || {
+		let (group, limit, file_, mime_) =
+			(Group::limited(), timeout(previewer), file.clone(), mime.clone());
 		let future = async {
+			let _permit = GATE.acquire(&cmd.name, &previewer.hint).await;
+			let deadline = limit.map(|d| Instant::now() + d);
+
 			let lua = slim_lua(&cmd.name)?;
+			lua.set_app_data(group.clone());
 			lua.set_hook(
 				HookTriggers::new().on_calls().on_returns().every_nth_instruction(2000),
 				move |_, dbg| {
-					if ct.is_cancelled() && dbg.source().what != "C" {
+					if dbg.source().what == "C" {
+						Ok(VmState::Continue)
+					} else if ct.is_cancelled() {
 						Err("Peek task cancelled".into_lua_err())
+					} else if deadline.is_some_and(|d| Instant::now() >= d) {
+						Err("Peek task timed out".into_lua_err())
 					} else {
 						Ok(VmState::Continue)
 					}
@@ -93,7 +117,17 @@ fn peek_async(
 				("skip", skip.into_lua(&lua)?),
 			])?;
 
-			if ct_.is_cancelled() { Ok(()) } else { plugin.call_async_method("peek", job).await }
+			if ct_.is_cancelled() {
+				return Ok(());
+			}
+
+			let run = plugin.call_async_method("peek", job);
+			match deadline {
+				Some(d) => tokio::time::timeout_at(d.into(), run)
+					.await
+					.unwrap_or_else(|_| Err("Peek task timed out".into_lua_err())),
+				None => run.await,
+			}
 		};
 
 		let result = Handle::current().block_on(async {
@@ -103,10 +137,57 @@ fn peek_async(
 			}
 		});
 
-		if let Err(e) = result
-			&& !e.to_string().contains("Peek task cancelled")
-		{
+		// The hovered file has changed, so what's still running is of no use
+		if ct_.is_cancelled() {
+			return group.kill();
+		}
+
+		let Err(e) = result else { return BREAKER.reset(&cmd.name) };
+
+		let e = e.to_string();
+		if e.contains("Peek task timed out") {
+			group.kill();
+			timed_out(cmd, limit.unwrap_or_default(), &file_, mime_, skip);
+		} else if !e.contains("Peek task cancelled") {
 			error!("{e}");
 		}
 	});
 }
+
+/// The time limit of the previewer.
+fn timeout(previewer: &Previewer) -> Option<Duration> {
+	let secs = previewer
+		.timeout
+		.or_else(|| LOADER.read().get(previewer.run.name.as_ref()).and_then(|c| c.timeout))
+		.unwrap_or(YAZI.preview.timeout);
+
+	if secs == 0 { None } else { Some(Duration::from_secs(secs as u64)) }
+}
+
+fn timed_out(cmd: &Cmd, limit: Duration, file: &yazi_fs::File, mime: SStr, skip: usize) {
+	notice(file, mime, skip, format!("`{}` previewer timed out ({}s)", cmd.name, limit.as_secs()));
+
+	if BREAKER.trip(&cmd.name) {
+		AppProxy::notify_warn(
+			"Previewer timed out",
+			format!(
+				"`{}` timed out repeatedly, and is skipped for a while. Consider raising its `timeout`.",
+				cmd.name
+			),
+		);
+	}
+}
+
+fn notice(file: &yazi_fs::File, mime: SStr, skip: usize, msg: String) {
+	let area = Rect::from(LAYOUT.get().preview);
+	MgrProxy::update_peeked(UpdatePeekedOpt {
+		lock: PreviewLock {
+			url: file.url_owned(),
+			cha: file.cha,
+			mime: mime.into_owned(),
+			skip,
+			area,
+			data: vec![(area, Error::Custom(msg.into())).into()],
+		},
+	});
+}
@@ -2,24 +2,27 @@ use mlua::{ExternalError, ExternalResult, HookTriggers, IntoLua, ObjectLike, VmS
 use tokio::{runtime::Handle, select};
 use tokio_util::sync::CancellationToken;
 use yazi_binding::{Error, File, elements::Rect};
-use yazi_config::LAYOUT;
+use yazi_config::{LAYOUT, plugin::Hint};
 use yazi_dds::Sendable;
 use yazi_shared::event::Cmd;
 
-use super::slim_lua;
-use crate::loader::LOADER;
+use super::{GATE, slim_lua};
+use crate::{loader::LOADER, process::Group};
 
 pub async fn preload(
 	cmd: &'static Cmd,
+	hint: &'static Hint,
 	file: yazi_fs::File,
 	ct: CancellationToken,
 ) -> mlua::Result<(bool, Option<Error>)> {
-	let ct_ = ct.clone();
+	let (ct_, group) = (ct.clone(), Group::limited());
 	tokio::task::spawn_blocking(move || {
 		let future = async {
+			let _permit = GATE.acquire(&cmd.name, hint).await;
 			LOADER.ensure(&cmd.name, |_| ()).await.into_lua_err()?;
 
 			let lua = slim_lua(&cmd.name)?;
+			lua.set_app_data(group.clone());
 			lua.set_hook(
 				HookTriggers::new().on_calls().on_returns().every_nth_instruction(2000),
 				move |_, dbg| {
@@ -46,7 +49,7 @@ pub async fn preload(
 			}
 		};
 
-		Handle::current().block_on(async {
+		let result = Handle::current().block_on(async {
 			select! {
 				_ = ct_.cancelled() => Ok((false, None)),
 				r = future => match r {
@@ -54,7 +57,12 @@ pub async fn preload(
 					Ok(_) | Err(_) => r,
 				},
 			}
-		})
+		});
+
+		if ct_.is_cancelled() {
+			group.kill();
+		}
+		result
 	})
 	.await
 	.into_lua_err()?
@@ -0,0 +1,41 @@
+use mlua::{ExternalResult, MultiValue, Value, Variadic};
+use serde_json::json;
+use tokio::runtime::Handle;
+
+use super::slim_lua;
+
+/// Runs the chunks of Lua one after another in the same isolate, without the TUI.
+pub async fn run(chunks: Vec<(String, Vec<u8>)>, args: Vec<String>) -> mlua::Result<bool> {
+	tokio::task::spawn_blocking(move || {
+		let lua = slim_lua("run")?;
+		let mut succeeded = true;
+
+		for (name, code) in chunks {
+			let chunk = lua.load(code).set_name(&name);
+			let result = Handle::current()
+				.block_on(chunk.call_async::<MultiValue>(args.iter().cloned().collect::<Variadic<_>>()));
+
+			let line = match result {
+				Ok(values) => {
+					let values: Vec<_> = values.iter().map(to_json).collect();
+					json!({ "chunk": name, "ok": true, "result": values })
+				}
+				Err(e) => {
+					succeeded = false;
+					json!({ "chunk": name, "ok": false, "error": e.to_string() })
+				}
+			};
+			println!("{line}");
+		}
+		Ok(succeeded)
+	})
+	.await
+	.into_lua_err()?
+}
+
+// Userdata, such as URLs, are given in the form they're printed in
+fn to_json(value: &Value) -> serde_json::Value {
+	serde_json::to_value(value)
+		.or_else(|_| value.to_string().map(serde_json::Value::String))
+		.unwrap_or(serde_json::Value::Null)
+}
@@ -0,0 +1,22 @@
+use mlua::{ExternalResult, ObjectLike};
+use tokio::runtime::Handle;
+use yazi_dds::Sendable;
+use yazi_shared::event::CmdCow;
+
+use super::slim_lua;
+use crate::loader::LOADER;
+
+/// Runs the `serve` method of the plugin of a service.
+pub async fn serve(cmd: CmdCow) -> mlua::Result<()> {
+	LOADER.ensure(&cmd.name, |_| ()).await.into_lua_err()?;
+
+	tokio::task::spawn_blocking(move || {
+		let lua = slim_lua(&cmd.name)?;
+		let plugin = LOADER.load_once(&lua, &cmd.name)?;
+
+		let job = lua.create_table_from([("args", Sendable::args_to_table_ref(&lua, &cmd.args)?)])?;
+		Handle::current().block_on(plugin.call_async_method("serve", job))
+	})
+	.await
+	.into_lua_err()?
+}
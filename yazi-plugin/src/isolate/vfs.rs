@@ -0,0 +1,75 @@
+use std::{io, path::Path};
+
+use mlua::{FromLua, IntoLua, Lua, ObjectLike, Table};
+use tokio::runtime::Handle;
+use yazi_binding::{Cha, Error, File, Url};
+use yazi_fs::provider::vfs::Driver;
+
+use super::slim_lua;
+use crate::loader::LOADER;
+
+/// Runs the callbacks of plugin filesystems.
+pub struct Vfs;
+
+impl Driver for Vfs {
+	fn list(&self, dir: &yazi_shared::url::Url) -> io::Result<Vec<yazi_fs::File>> {
+		let files: Vec<File> = Self::call(dir, "list", |lua| {
+			lua.create_table_from([("url", Url::new(dir.clone()).into_lua(lua)?)])
+		})?;
+		Ok(files.into_iter().map(Into::into).collect())
+	}
+
+	fn stat(&self, url: &yazi_shared::url::Url) -> io::Result<yazi_fs::cha::Cha> {
+		let cha: Cha = Self::call(url, "stat", |lua| {
+			lua.create_table_from([("url", Url::new(url.clone()).into_lua(lua)?)])
+		})?;
+		Ok(*cha)
+	}
+
+	fn read(&self, url: &yazi_shared::url::Url, to: &Path) -> io::Result<()> {
+		Self::call::<bool>(url, "read", |lua| {
+			lua.create_table_from([
+				("url", Url::new(url.clone()).into_lua(lua)?),
+				("to", Url::new(to).into_lua(lua)?),
+			])
+		})
+		.map(|_| ())
+	}
+
+	fn write(&self, url: &yazi_shared::url::Url, from: &Path) -> io::Result<()> {
+		Self::call::<bool>(url, "write", |lua| {
+			lua.create_table_from([
+				("url", Url::new(url.clone()).into_lua(lua)?),
+				("from", Url::new(from).into_lua(lua)?),
+			])
+		})
+		.map(|_| ())
+	}
+}
+
+impl Vfs {
+	// Each method returns the result, or `nil` and an `Error` on failure
+	fn call<T: FromLua>(
+		url: &yazi_shared::url::Url,
+		method: &str,
+		job: impl FnOnce(&Lua) -> mlua::Result<Table>,
+	) -> io::Result<T> {
+		let name = url.scheme.domain().unwrap_or_default();
+		Handle::current().block_on(LOADER.ensure(name, |_| ())).map_err(io::Error::other)?;
+
+		let result: mlua::Result<(Option<T>, Option<Error>)> = (|| {
+			let lua = slim_lua(name)?;
+			let plugin = LOADER.load_once(&lua, name)?;
+			Handle::current().block_on(plugin.call_async_method(method, job(&lua)?))
+		})();
+
+		match result {
+			Ok((Some(value), None)) => Ok(value),
+			Ok((_, Some(Error::Io(e)))) => Err(e),
+			Ok((_, Some(Error::IoKind(kind)))) => Err(kind.into()),
+			Ok((_, Some(e))) => Err(io::Error::other(e.to_string())),
+			Ok((None, None)) => Err(io::ErrorKind::NotFound.into()),
+			Err(e) => Err(io::Error::other(format!("`{method}` of the `{name}` plugin failed: {e}"))),
+		}
+	}
+}
@@ -7,5 +7,7 @@ yazi_macro::mod_flat!(lua twox);
 pub fn init() -> anyhow::Result<()> {
 	crate::loader::init();
 	crate::init_lua()?;
+	yazi_fs::provider::vfs::Vfs::init(crate::isolate::Vfs);
+	yazi_fs::FilesSorter::hook(crate::utils::custom_order);
 	Ok(())
 }
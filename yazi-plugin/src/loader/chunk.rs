@@ -9,6 +9,7 @@ pub struct Chunk {
 	pub since:      String,
 	pub sync_peek:  bool,
 	pub sync_entry: bool,
+	pub timeout:    Option<u16>,
 }
 
 impl Chunk {
@@ -35,6 +36,10 @@ impl Chunk {
 				(b"@since", b"") => continue,
 				(b"@since", b) => self.since = String::from_utf8_lossy(b).to_string(),
 
+				(b"@timeout", b) => {
+					self.timeout = std::str::from_utf8(b).ok().and_then(|s| s.parse().ok());
+				}
+
 				(_, []) => break,
 				(b, _) if b.strip_prefix(b"@").unwrap_or(b"").is_empty() => break,
 				_ => continue,
@@ -51,6 +56,7 @@ impl From<Cow<'static, [u8]>> for Chunk {
 			since:      String::new(),
 			sync_entry: false,
 			sync_peek:  false,
+			timeout:    None,
 		};
 		chunk.analyze();
 		chunk
@@ -75,3 +81,21 @@ impl AsChunk for &Chunk {
 		Ok(Cow::Borrowed(&self.bytes))
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_analyze() {
+		let chunk = Chunk::from(&b"--- @sync peek\n--- @timeout 30\n\nlocal M = {}"[..]);
+		assert!(chunk.sync_peek);
+		assert_eq!(chunk.timeout, Some(30));
+
+		let chunk = Chunk::from(&b"local M = {}\n--- @timeout 30"[..]);
+		assert_eq!(chunk.timeout, None);
+
+		let chunk = Chunk::from(&b"--- @timeout soon"[..]);
+		assert_eq!(chunk.timeout, None);
+	}
+}
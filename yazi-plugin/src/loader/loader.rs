@@ -27,21 +27,28 @@ impl Default for Loader {
 	fn default() -> Self {
 		let cache = HashMap::from_iter([
 			("archive".to_owned(), preset!("plugins/archive").into()),
+			("checksum".to_owned(), preset!("plugins/checksum").into()),
+			("cloud".to_owned(), preset!("plugins/cloud").into()),
 			("code".to_owned(), preset!("plugins/code").into()),
-			("dds".to_owned(), preset!("plugins/dds").into()),
+			("compress".to_owned(), preset!("plugins/compress").into()),
+			("drag".to_owned(), preset!("plugins/drag").into()),
 			("empty".to_owned(), preset!("plugins/empty").into()),
 			("extract".to_owned(), preset!("plugins/extract").into()),
 			("file".to_owned(), preset!("plugins/file").into()),
 			("folder".to_owned(), preset!("plugins/folder").into()),
 			("font".to_owned(), preset!("plugins/font").into()),
 			("fzf".to_owned(), preset!("plugins/fzf").into()),
+			("git".to_owned(), preset!("plugins/git").into()),
 			("image".to_owned(), preset!("plugins/image").into()),
+			("info".to_owned(), preset!("plugins/info").into()),
 			("json".to_owned(), preset!("plugins/json").into()),
 			("magick".to_owned(), preset!("plugins/magick").into()),
 			("mime".to_owned(), preset!("plugins/mime").into()),
+			("mount".to_owned(), preset!("plugins/mount").into()),
 			("noop".to_owned(), preset!("plugins/noop").into()),
 			("pdf".to_owned(), preset!("plugins/pdf").into()),
 			("session".to_owned(), preset!("plugins/session").into()),
+			("status".to_owned(), preset!("plugins/status").into()),
 			("svg".to_owned(), preset!("plugins/svg").into()),
 			("video".to_owned(), preset!("plugins/video").into()),
 			("zoxide".to_owned(), preset!("plugins/zoxide").into()),
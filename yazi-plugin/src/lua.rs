@@ -23,7 +23,7 @@ fn stage_1(lua: &'static Lua) -> Result<()> {
 	globals.raw_set("ui", crate::elements::compose())?;
 	globals.raw_set("ya", crate::utils::compose(false))?;
 	globals.raw_set("fs", crate::fs::compose())?;
-	globals.raw_set("ps", crate::pubsub::compose())?;
+	globals.raw_set("ps", crate::pubsub::compose(false))?;
 	globals.raw_set("rt", crate::runtime::compose())?;
 	globals.raw_set("th", crate::theme::compose())?;
 
@@ -38,6 +38,7 @@ fn stage_1(lua: &'static Lua) -> Result<()> {
 
 	// Components
 	lua.load(preset!("components/current")).set_name("current.lua").exec()?;
+	lua.load(preset!("components/dual")).set_name("dual.lua").exec()?;
 	lua.load(preset!("components/entity")).set_name("entity.lua").exec()?;
 	lua.load(preset!("components/header")).set_name("header.lua").exec()?;
 	lua.load(preset!("components/linemode")).set_name("linemode.lua").exec()?;
@@ -3,9 +3,10 @@ use std::{any::TypeId, io, process::Stdio};
 use mlua::{AnyUserData, ExternalError, IntoLuaMulti, Lua, MetaMethod, Table, UserData, Value};
 use tokio::process::{ChildStderr, ChildStdin, ChildStdout};
 use yazi_binding::Error;
+use yazi_config::YAZI;
 use yazi_shared::IntoOsStr;
 
-use super::{Child, output::Output};
+use super::{Child, Group, output::Output};
 use crate::process::Status;
 
 pub struct Command {
@@ -19,8 +20,15 @@ const INHERIT: u8 = 2;
 
 impl Command {
 	pub fn install(lua: &Lua) -> mlua::Result<()> {
-		let new = lua.create_function(|_, (_, program): (Table, String)| {
-			let mut inner = tokio::process::Command::new(program);
+		let new = lua.create_function(|lua, (_, program): (Table, String)| {
+			let mut inner = match &YAZI.preview.sandbox[..] {
+				[bin, args @ ..] if Group::of(lua).is_some_and(|g| g.is_limited()) => {
+					let mut inner = tokio::process::Command::new(bin);
+					inner.args(args).arg(program);
+					inner
+				}
+				_ => tokio::process::Command::new(program),
+			};
 			inner.kill_on_drop(true).stdin(Stdio::null()).stdout(Stdio::null()).stderr(Stdio::null());
 
 			Ok(Self { inner, memory: None })
@@ -38,27 +46,61 @@ impl Command {
 		lua.globals().raw_set("Command", command)
 	}
 
+	/// The memory and CPU time limits of the process.
+	fn limits(&self, group: Option<&Group>) -> (Option<usize>, Option<u64>) {
+		if !group.is_some_and(|g| g.is_limited()) {
+			return (self.memory, None);
+		}
+
+		let memory = Some(YAZI.preview.memory_limit as usize).filter(|&n| n != 0);
+		let memory = match (self.memory, memory) {
+			(Some(a), Some(b)) => Some(a.min(b)),
+			(a, b) => a.or(b),
+		};
+		(memory, Some(YAZI.preview.cpu_limit as u64).filter(|&n| n != 0))
+	}
+
 	#[cfg(unix)]
-	fn spawn(&mut self) -> io::Result<Child> {
-		if let Some(max) = self.memory {
+	fn spawn(&mut self, group: Option<&Group>) -> io::Result<Child> {
+		let (memory, cpu) = self.limits(group);
+		if memory.is_some() || cpu.is_some() {
 			unsafe {
 				self.inner.pre_exec(move || {
-					let rlp = libc::rlimit { rlim_cur: max as _, rlim_max: max as _ };
-					libc::setrlimit(libc::RLIMIT_AS, &rlp);
+					if let Some(max) = memory {
+						let rlp = libc::rlimit { rlim_cur: max as _, rlim_max: max as _ };
+						libc::setrlimit(libc::RLIMIT_AS, &rlp);
+					}
+					// Sent `SIGXCPU` once it's used up, and `SIGKILL` if it's still running later
+					if let Some(secs) = cpu {
+						let rlp = libc::rlimit { rlim_cur: secs as _, rlim_max: (secs + 5) as _ };
+						libc::setrlimit(libc::RLIMIT_CPU, &rlp);
+					}
 					Ok(())
 				});
 			}
 		}
-		self.inner.spawn().map(Child::new)
+		if let Some(g) = group {
+			g.prepare(&mut self.inner);
+		}
+
+		let child = self.inner.spawn()?;
+		if let Some(g) = group {
+			g.add(&child);
+		}
+		Ok(Child::new(child))
 	}
 
 	#[cfg(windows)]
-	fn spawn(&mut self) -> io::Result<Child> {
+	fn spawn(&mut self, group: Option<&Group>) -> io::Result<Child> {
 		use std::os::windows::io::RawHandle;
 
-		use windows_sys::Win32::System::JobObjects::{AssignProcessToJobObject, CreateJobObjectW, JOB_OBJECT_LIMIT_PROCESS_MEMORY, JOBOBJECT_EXTENDED_LIMIT_INFORMATION, JobObjectExtendedLimitInformation, SetInformationJobObject};
+		use windows_sys::Win32::System::JobObjects::{AssignProcessToJobObject, CreateJobObjectW, JOB_OBJECT_LIMIT_PROCESS_MEMORY, JOB_OBJECT_LIMIT_PROCESS_TIME, JOBOBJECT_EXTENDED_LIMIT_INFORMATION, JobObjectExtendedLimitInformation, SetInformationJobObject};
 
-		fn assign_job(max: usize, handle: RawHandle) -> io::Result<RawHandle> {
+		fn assign_job(
+			memory: Option<usize>,
+			cpu: Option<u64>,
+			handle: RawHandle,
+		) -> io::Result<RawHandle> {
 			unsafe {
 				let job = CreateJobObjectW(std::ptr::null_mut(), std::ptr::null());
 				if job.is_null() {
@@ -66,8 +108,15 @@ impl Command {
 				}
 
 				let mut info: JOBOBJECT_EXTENDED_LIMIT_INFORMATION = std::mem::zeroed();
-				info.BasicLimitInformation.LimitFlags = JOB_OBJECT_LIMIT_PROCESS_MEMORY;
-				info.ProcessMemoryLimit = max;
+				if let Some(max) = memory {
+					info.BasicLimitInformation.LimitFlags |= JOB_OBJECT_LIMIT_PROCESS_MEMORY;
+					info.ProcessMemoryLimit = max;
+				}
+				// In 100-nanosecond ticks of user-mode time
+				if let Some(secs) = cpu {
+					info.BasicLimitInformation.LimitFlags |= JOB_OBJECT_LIMIT_PROCESS_TIME;
+					info.BasicLimitInformation.PerProcessUserTimeLimit = secs as i64 * 10_000_000;
+				}
 
 				let result = SetInformationJobObject(
 					job,
@@ -87,23 +136,29 @@ impl Command {
 		}
 
 		let child = self.inner.spawn()?;
-		if let (Some(max), Some(handle)) = (self.memory, child.raw_handle()) {
-			if let Ok(job) = assign_job(max, handle) {
-				return Ok(Child::new(child, Some(job)));
-			}
+		if let Some(g) = group {
+			g.add(&child);
+		}
+
+		let (memory, cpu) = self.limits(group);
+		if (memory.is_some() || cpu.is_some())
+			&& let Some(handle) = child.raw_handle()
+			&& let Ok(job) = assign_job(memory, cpu, handle)
+		{
+			return Ok(Child::new(child, Some(job)));
 		}
 
 		Ok(Child::new(child, None))
 	}
 
-	async fn output(&mut self) -> io::Result<std::process::Output> {
+	async fn output(&mut self, group: Option<&Group>) -> io::Result<std::process::Output> {
 		self.inner.stdin(Stdio::piped());
 		self.inner.stdout(Stdio::piped());
-		self.spawn()?.wait_with_output().await
+		self.spawn(group)?.wait_with_output().await
 	}
 
-	async fn status(&mut self) -> io::Result<std::process::ExitStatus> {
-		self.spawn()?.status().await
+	async fn status(&mut self, group: Option<&Group>) -> io::Result<std::process::ExitStatus> {
+		self.spawn(group)?.status().await
 	}
 }
 
@@ -185,18 +240,18 @@ impl UserData for Command {
 			ud.borrow_mut::<Self>()?.memory = Some(max);
 			Ok(ud)
 		});
-		methods.add_method_mut("spawn", |lua, me, ()| match me.spawn() {
+		methods.add_method_mut("spawn", |lua, me, ()| match me.spawn(Group::of(lua).as_ref()) {
 			Ok(child) => child.into_lua_multi(lua),
 			Err(e) => (Value::Nil, Error::Io(e)).into_lua_multi(lua),
 		});
 		methods.add_async_method_mut("output", |lua, mut me, ()| async move {
-			match me.output().await {
+			match me.output(Group::of(&lua).as_ref()).await {
 				Ok(output) => Output::new(output).into_lua_multi(&lua),
 				Err(e) => (Value::Nil, Error::Io(e)).into_lua_multi(&lua),
 			}
 		});
 		methods.add_async_method_mut("status", |lua, mut me, ()| async move {
-			match me.status().await {
+			match me.status(Group::of(&lua).as_ref()).await {
 				Ok(status) => Status::new(status).into_lua_multi(&lua),
 				Err(e) => (Value::Nil, Error::Io(e)).into_lua_multi(&lua),
 			}
@@ -0,0 +1,98 @@
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+
+/// Processes spawned during a single plugin run, to be killed together.
+#[derive(Clone, Default)]
+pub struct Group(Arc<Mutex<GroupInner>>);
+
+#[derive(Default)]
+struct GroupInner {
+	limited: bool,
+	#[cfg(unix)]
+	pgids: Vec<u32>,
+	#[cfg(windows)]
+	job:   Option<std::os::windows::io::RawHandle>,
+}
+
+#[cfg(windows)]
+impl Drop for GroupInner {
+	fn drop(&mut self) {
+		if let Some(h) = self.job.take() {
+			unsafe { windows_sys::Win32::Foundation::CloseHandle(h) };
+		}
+	}
+}
+
+impl Group {
+	/// A group of the previewers, preloaders or fetchers, with the limits of `[preview]`.
+	pub fn limited() -> Self {
+		Self(Arc::new(Mutex::new(GroupInner { limited: true, ..Default::default() })))
+	}
+
+	/// The group of the plugin run that owns the Lua state, if any.
+	#[inline]
+	pub(super) fn of(lua: &mlua::Lua) -> Option<Self> { lua.app_data_ref::<Self>().map(|g| g.clone()) }
+
+	#[inline]
+	pub(super) fn is_limited(&self) -> bool { self.0.lock().limited }
+
+	/// Prepares the command to be spawned as the leader of a new process group.
+	#[cfg(unix)]
+	pub(super) fn prepare(&self, cmd: &mut tokio::process::Command) { cmd.process_group(0); }
+
+	#[cfg(unix)]
+	pub(super) fn add(&self, child: &tokio::process::Child) {
+		if let Some(id) = child.id() {
+			self.0.lock().pgids.push(id);
+		}
+	}
+
+	/// Assigns the child to the job object of the group.
+	#[cfg(windows)]
+	pub(super) fn add(&self, child: &tokio::process::Child) {
+		use windows_sys::Win32::System::JobObjects::{AssignProcessToJobObject, CreateJobObjectW, JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE, JOBOBJECT_EXTENDED_LIMIT_INFORMATION, JobObjectExtendedLimitInformation, SetInformationJobObject};
+
+		let Some(handle) = child.raw_handle() else { return };
+		let mut inner = self.0.lock();
+
+		unsafe {
+			if inner.job.is_none() {
+				let job = CreateJobObjectW(std::ptr::null_mut(), std::ptr::null());
+				if job.is_null() {
+					return;
+				}
+
+				let mut info: JOBOBJECT_EXTENDED_LIMIT_INFORMATION = std::mem::zeroed();
+				info.BasicLimitInformation.LimitFlags = JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE;
+				SetInformationJobObject(
+					job,
+					JobObjectExtendedLimitInformation,
+					&mut info as *mut _ as *mut _,
+					std::mem::size_of_val(&info) as u32,
+				);
+				inner.job = Some(job);
+			}
+
+			if let Some(job) = inner.job {
+				AssignProcessToJobObject(job, handle);
+			}
+		}
+	}
+
+	#[cfg(unix)]
+	pub fn kill(&self) {
+		for pgid in self.0.lock().pgids.drain(..) {
+			unsafe { libc::kill(-(pgid as libc::pid_t), libc::SIGKILL) };
+		}
+	}
+
+	#[cfg(windows)]
+	pub fn kill(&self) {
+		use windows_sys::Win32::System::JobObjects::TerminateJobObject;
+
+		if let Some(job) = self.0.lock().job {
+			unsafe { TerminateJobObject(job, 1) };
+		}
+	}
+}
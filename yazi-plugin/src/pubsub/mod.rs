@@ -1,19 +1,24 @@
 #![allow(clippy::module_inception)]
 
 use mlua::{IntoLua, Lua, Value};
-use yazi_binding::{Composer, ComposerGet, ComposerSet};
+use yazi_binding::{Composer, ComposerSet};
 
 yazi_macro::mod_flat!(pubsub);
 
-pub(super) fn compose() -> Composer<ComposerGet, ComposerSet> {
-	fn get(lua: &Lua, key: &[u8]) -> mlua::Result<Value> {
-		match key {
-			b"pub" => Pubsub::r#pub(lua)?,
-			b"pub_to" => Pubsub::pub_to(lua)?,
-			b"sub" => Pubsub::sub(lua)?,
-			b"sub_remote" => Pubsub::sub_remote(lua)?,
-			b"unsub" => Pubsub::unsub(lua)?,
-			b"unsub_remote" => Pubsub::unsub_remote(lua)?,
+// Isolates can only publish, and receive remote messages with `recv_remote()`,
+// as the callbacks of subscriptions are run on the main Lua
+pub(super) fn compose(
+	isolate: bool,
+) -> Composer<impl Fn(&Lua, &[u8]) -> mlua::Result<Value>, ComposerSet> {
+	fn get(lua: &Lua, key: &[u8], isolate: bool) -> mlua::Result<Value> {
+		match (key, isolate) {
+			(b"pub", _) => Pubsub::r#pub(lua)?,
+			(b"pub_to", _) => Pubsub::pub_to(lua)?,
+			(b"sub", false) => Pubsub::sub(lua)?,
+			(b"sub_remote", false) => Pubsub::sub_remote(lua)?,
+			(b"unsub", false) => Pubsub::unsub(lua)?,
+			(b"unsub_remote", false) => Pubsub::unsub_remote(lua)?,
+			(b"recv_remote", true) => Pubsub::recv_remote(lua)?,
 			_ => return Ok(Value::Nil),
 		}
 		.into_lua(lua)
@@ -21,5 +26,5 @@ pub(super) fn compose() -> Composer<ComposerGet, ComposerSet> {
 
 	fn set(_: &Lua, _: &[u8], value: Value) -> mlua::Result<Value> { Ok(value) }
 
-	Composer::new(get, set)
+	Composer::new(move |lua, key| get(lua, key, isolate), set)
 }
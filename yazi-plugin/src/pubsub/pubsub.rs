@@ -1,6 +1,7 @@
-use mlua::{ExternalResult, Function, Lua, Value};
+use mlua::{ExternalResult, Function, IntoLuaMulti, Lua, UserData, UserDataMethods, Value};
+use tokio::sync::mpsc;
 use yazi_binding::{Id, runtime};
-use yazi_dds::ember::Ember;
+use yazi_dds::{Payload, ember::Ember};
 
 pub struct Pubsub;
 
@@ -63,4 +64,24 @@ impl Pubsub {
 			}
 		})
 	}
+
+	pub(super) fn recv_remote(lua: &Lua) -> mlua::Result<Function> {
+		lua.create_function(|_, kind: mlua::String| {
+			Ok(RemoteRx(yazi_dds::Pubsub::sub_service(&kind.to_str()?)))
+		})
+	}
+}
+
+// --- RemoteRx
+pub struct RemoteRx(mpsc::UnboundedReceiver<Payload<'static>>);
+
+impl UserData for RemoteRx {
+	fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+		methods.add_async_method_mut("recv", |lua, mut me, ()| async move {
+			match me.0.recv().await {
+				Some(p) => (p.body, Id(p.sender)).into_lua_multi(&lua),
+				None => ().into_lua_multi(&lua),
+			}
+		});
+	}
 }
@@ -1,6 +1,6 @@
-use mlua::{IntoLua, Lua, LuaSerdeExt, SerializeOptions, Value};
-use yazi_binding::{Composer, ComposerGet, ComposerSet, Url};
-use yazi_boot::ARGS;
+use mlua::{Function, IntoLua, Lua, LuaSerdeExt, SerializeOptions, UserData, Value};
+use yazi_binding::{Composer, ComposerGet, ComposerSet, FileRef, Url, cached_field};
+use yazi_boot::{ARGS, BOOT};
 use yazi_config::YAZI;
 
 pub const OPTS: SerializeOptions =
@@ -15,6 +15,7 @@ pub fn compose() -> Composer<ComposerGet, ComposerSet> {
 			b"plugin" => super::plugin().into_lua(lua)?,
 			b"preview" => preview().into_lua(lua)?,
 			b"tasks" => tasks().into_lua(lua)?,
+			b"trash" => trash().into_lua(lua)?,
 			_ => return Ok(Value::Nil),
 		}
 		.into_lua(lua)
@@ -58,6 +59,15 @@ fn mgr() -> Composer<ComposerGet, ComposerSet> {
 			b"scrolloff" => lua.to_value_with(&m.scrolloff, OPTS)?,
 			b"mouse_events" => lua.to_value_with(&m.mouse_events, OPTS)?,
 			b"title_format" => lua.to_value_with(&m.title_format, OPTS)?,
+			b"columns" => columns(lua)?.into_lua(lua)?,
+			// Whether it's on by `--accessible` or the config
+			b"accessible" => BOOT.accessible.into_lua(lua)?,
+
+			b"yank_threshold" => lua.to_value_with(&m.yank_threshold, OPTS)?,
+			b"sync_yanked" => lua.to_value_with(&m.sync_yanked, OPTS)?,
+
+			b"save_session" => lua.to_value_with(&m.save_session, OPTS)?,
+			b"remember_views" => lua.to_value_with(&m.remember_views, OPTS)?,
 			_ => return Ok(Value::Nil),
 		}
 		.into_lua(lua)
@@ -77,6 +87,12 @@ fn mgr() -> Composer<ComposerGet, ComposerSet> {
 	Composer::new(get, set)
 }
 
+fn columns(lua: &Lua) -> mlua::Result<Function> {
+	lua.create_function(|lua, (file, mime): (FileRef, mlua::String)| {
+		lua.create_sequence_from(YAZI.mgr.columns(&file.url, &mime.to_str()?).map(Column::new))
+	})
+}
+
 fn preview() -> Composer<ComposerGet, ComposerSet> {
 	fn get(lua: &Lua, key: &[u8]) -> mlua::Result<Value> {
 		let p = &YAZI.preview;
@@ -87,13 +103,19 @@ fn preview() -> Composer<ComposerGet, ComposerSet> {
 			b"max_height" => lua.to_value_with(&p.max_height, OPTS)?,
 
 			b"cache_dir" => lua.to_value_with(&p.cache_dir, OPTS)?,
+			b"timeout" => lua.to_value_with(&p.timeout, OPTS)?,
 
 			b"image_delay" => lua.to_value_with(&p.image_delay, OPTS)?,
 			b"image_filter" => lua.to_value_with(&p.image_filter, OPTS)?,
 			b"image_quality" => lua.to_value_with(&p.image_quality, OPTS)?,
+			b"image_memory" => lua.to_value_with(&p.image_memory, OPTS)?,
+
+			b"video_frames" => lua.to_value_with(&p.video_frames, OPTS)?,
 
 			b"ueberzug_scale" => lua.to_value_with(&p.ueberzug_scale, OPTS)?,
 			b"ueberzug_offset" => lua.to_value_with(&p.ueberzug_offset, OPTS)?,
+
+			b"text_fallback" => lua.to_value_with(&p.text_fallback, OPTS)?,
 			_ => return Ok(Value::Nil),
 		}
 		.into_lua(lua)
@@ -115,7 +137,18 @@ fn tasks() -> Composer<ComposerGet, ComposerSet> {
 			b"image_alloc" => lua.to_value_with(&t.image_alloc, OPTS)?,
 			b"image_bound" => lua.to_value_with(&t.image_bound, OPTS)?,
 
+			b"plugin_memory" => lua.to_value_with(&t.plugin_memory, OPTS)?,
+
 			b"suppress_preload" => lua.to_value_with(&t.suppress_preload, OPTS)?,
+			b"verify_paste" => lua.to_value_with(&t.verify_paste, OPTS)?,
+			b"queue_paste" => lua.to_value_with(&t.queue_paste, OPTS)?,
+			b"paste_limit" => lua.to_value_with(&t.paste_limit, OPTS)?,
+			b"preserve_xattr" => lua.to_value_with(&t.preserve_xattr, OPTS)?,
+
+			b"copy_streams" => lua.to_value_with(&t.copy_streams, OPTS)?,
+			b"copy_chunk" => lua.to_value_with(&t.copy_chunk, OPTS)?,
+
+			b"audit_log" => lua.to_value_with(&t.audit_log, OPTS)?,
 			_ => return Ok(Value::Nil),
 		}
 		.into_lua(lua)
@@ -125,3 +158,39 @@ fn tasks() -> Composer<ComposerGet, ComposerSet> {
 
 	Composer::new(get, set)
 }
+
+fn trash() -> Composer<ComposerGet, ComposerSet> {
+	fn get(lua: &Lua, key: &[u8]) -> mlua::Result<Value> {
+		let t = &YAZI.trash;
+		match key {
+			b"usage" => lua.to_value_with(&t.usage, OPTS)?,
+			b"max_age" => lua.to_value_with(&t.max_age, OPTS)?,
+			b"max_size" => lua.to_value_with(&t.max_size, OPTS)?,
+			_ => return Ok(Value::Nil),
+		}
+		.into_lua(lua)
+	}
+
+	fn set(_: &Lua, _: &[u8], value: Value) -> mlua::Result<Value> { Ok(value) }
+
+	Composer::new(get, set)
+}
+
+// --- Column
+struct Column {
+	inner: &'static yazi_config::mgr::Column,
+
+	v_name: Option<Value>,
+}
+
+impl Column {
+	fn new(inner: &'static yazi_config::mgr::Column) -> Self { Self { inner, v_name: None } }
+}
+
+impl UserData for Column {
+	fn add_fields<F: mlua::UserDataFields<Self>>(fields: &mut F) {
+		cached_field!(fields, name, |lua, me| lua.create_string(&me.inner.name));
+		fields.add_field_method_get("width", |_, me| Ok(me.inner.width));
+		fields.add_field_method_get("align", |_, me| Ok(me.inner.align.as_str()));
+	}
+}
@@ -1,10 +1,11 @@
 use mlua::{IntoLua, Lua, Value};
 use yazi_binding::{Composer, ComposerGet, ComposerSet, Style, Url};
-use yazi_config::THEME;
+use yazi_config::{LIGHT, THEME};
 
 pub fn compose() -> Composer<ComposerGet, ComposerSet> {
 	fn get(lua: &Lua, key: &[u8]) -> mlua::Result<Value> {
 		match key {
+			b"light" => return LIGHT.get().into_lua(lua),
 			b"mgr" => mgr(),
 			b"tabs" => tabs(),
 			b"mode" => mode(),
@@ -42,6 +43,17 @@ fn mgr() -> Composer<ComposerGet, ComposerSet> {
 
 			b"symlink_target" => Style::from(m.symlink_target).into_lua(lua),
 
+			b"compare_only" => Style::from(m.compare_only).into_lua(lua),
+			b"compare_differ" => Style::from(m.compare_differ).into_lua(lua),
+
+			b"tag" => Style::from(m.tag).into_lua(lua),
+
+			b"git_modified" => Style::from(m.git_modified).into_lua(lua),
+			b"git_added" => Style::from(m.git_added).into_lua(lua),
+			b"git_untracked" => Style::from(m.git_untracked).into_lua(lua),
+			b"git_ignored" => Style::from(m.git_ignored).into_lua(lua),
+			b"git_conflicted" => Style::from(m.git_conflicted).into_lua(lua),
+
 			b"marker_copied" => Style::from(m.marker_copied).into_lua(lua),
 			b"marker_cut" => Style::from(m.marker_cut).into_lua(lua),
 			b"marker_marked" => Style::from(m.marker_marked).into_lua(lua),
@@ -133,6 +145,9 @@ fn status() -> Composer<ComposerGet, ComposerSet> {
 				])?
 				.into_lua(lua),
 
+			b"left" => lua.create_sequence_from(t.left.iter().map(String::as_str))?.into_lua(lua),
+			b"right" => lua.create_sequence_from(t.right.iter().map(String::as_str))?.into_lua(lua),
+
 			b"perm_sep" => Style::from(t.perm_sep).into_lua(lua),
 			b"perm_type" => Style::from(t.perm_type).into_lua(lua),
 			b"perm_read" => Style::from(t.perm_read).into_lua(lua),
@@ -232,6 +247,10 @@ fn notify() -> Composer<ComposerGet, ComposerSet> {
 			b"icon_warn" => lua.create_string(&t.icon_warn)?.into_lua(lua),
 			b"icon_error" => lua.create_string(&t.icon_error)?.into_lua(lua),
 
+			b"border" => Style::from(t.border).into_lua(lua),
+			b"title" => Style::from(t.title).into_lua(lua),
+			b"hovered" => Style::from(t.hovered).into_lua(lua),
+
 			_ => Ok(Value::Nil),
 		}
 	}
@@ -15,10 +15,21 @@ impl Utils {
 				return Ok(None);
 			}
 
+			// By content, the cache outlives renames and copies, at the cost of a few reads
+			let content = t.raw_get("content").unwrap_or(false);
+			let fingerprint = content.then(|| yazi_fs::fingerprint(&file.url).ok()).flatten();
+
 			let hex = {
 				let mut h = Twox128::default();
-				file.hash(&mut h);
+				match fingerprint {
+					Some(fp) => fp.hash(&mut h),
+					None => file.hash(&mut h),
+				}
 				t.raw_get("skip").unwrap_or(0usize).hash(&mut h);
+				// Keeps the caches of the same file apart, for the plugins other than previewers
+				if let Ok(kind) = t.raw_get::<String>("kind") {
+					kind.hash(&mut h);
+				}
 				format!("{:x}", h.finish_128())
 			};
 
@@ -0,0 +1,42 @@
+use mlua::{AnyUserData, ExternalError, Lua, UserData, UserDataFields, UserDataMethods};
+use yazi_adapter::{ADAPTOR, EMULATOR, HOST};
+
+use super::Utils;
+
+impl Utils {
+	pub(super) fn host(lua: &Lua) -> mlua::Result<AnyUserData> { lua.create_userdata(Host) }
+}
+
+// --- Host
+struct Host;
+
+impl UserData for Host {
+	fn add_fields<F: UserDataFields<Self>>(fields: &mut F) {
+		fields.add_field_method_get("os", |_, _| Ok(std::env::consts::OS));
+		fields.add_field_method_get("arch", |_, _| Ok(std::env::consts::ARCH));
+
+		fields.add_field_method_get("terminal", |_, _| {
+			Ok(EMULATOR.get().kind.left().map(|b| b.to_string()))
+		});
+		fields
+			.add_field_method_get("terminal_version", |_, _| Ok(HOST.read().terminal_version.clone()));
+
+		fields.add_field_method_get("mux", |_, _| Ok(HOST.read().mux));
+		fields.add_field_method_get("mux_version", |_, _| Ok(HOST.read().mux_version.clone()));
+
+		fields.add_field_method_get("protocol", |_, _| Ok(ADAPTOR.get().to_string()));
+		fields.add_field_method_get("session", |_, _| Ok(HOST.read().session.to_string()));
+	}
+
+	fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+		methods.add_function("which", |lua, name: mlua::String| {
+			yazi_adapter::Host::which(&name.to_str()?)
+				.map(|p| lua.create_string(p.as_os_str().as_encoded_bytes()))
+				.transpose()
+		});
+
+		methods.add_async_function("refresh", |_, ()| async move {
+			tokio::task::spawn_blocking(yazi_adapter::Host::refresh).await.map_err(|e| e.into_lua_err())
+		});
+	}
+}
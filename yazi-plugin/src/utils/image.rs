@@ -1,6 +1,7 @@
 use mlua::{Function, IntoLuaMulti, Lua, Value};
 use yazi_adapter::{ADAPTOR, Image};
 use yazi_binding::{Error, UrlRef, elements::Rect};
+use yazi_boot::BOOT;
 
 use super::Utils;
 use crate::bindings::ImageInfo;
@@ -17,6 +18,11 @@ impl Utils {
 
 	pub(super) fn image_show(lua: &Lua) -> mlua::Result<Function> {
 		lua.create_async_function(|lua, (url, rect): (UrlRef, Rect)| async move {
+			// Screen readers can't see images, so none are shown in the accessible mode
+			if BOOT.accessible {
+				return Rect::default().into_lua_multi(&lua);
+			}
+
 			match ADAPTOR.get().image_show(&url, *rect).await {
 				Ok(area) => Rect::from(area).into_lua_multi(&lua),
 				Err(e) => (Value::Nil, Error::Custom(e.to_string().into())).into_lua_multi(&lua),
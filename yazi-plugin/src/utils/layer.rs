@@ -1,13 +1,14 @@
 use std::{str::FromStr, time::Duration};
 
-use mlua::{ExternalError, ExternalResult, Function, IntoLuaMulti, Lua, Table, Value};
+use mlua::{ExternalError, ExternalResult, FromLua, Function, IntoLuaMulti, Lua, Table, Value};
 use tokio::sync::mpsc;
 use tokio_stream::wrappers::UnboundedReceiverStream;
 use yazi_binding::{deprecate, elements::{Line, Pos, Text}};
-use yazi_config::{keymap::{Chord, Key}, popup::{ConfirmCfg, InputCfg}};
+use unicode_width::UnicodeWidthStr;
+use yazi_config::{YAZI, keymap::{Chord, Key}, popup::{ConfirmCfg, InputCfg, PickCfg, Position}};
 use yazi_macro::relay;
 use yazi_parser::which::ShowOpt;
-use yazi_proxy::{AppProxy, ConfirmProxy, InputProxy, WhichProxy};
+use yazi_proxy::{AppProxy, ConfirmProxy, InputProxy, PickProxy, WhichProxy};
 use yazi_shared::Debounce;
 
 use super::Utils;
@@ -72,9 +73,61 @@ impl Utils {
 		})
 	}
 
+	pub(super) fn form(lua: &Lua) -> mlua::Result<Function> {
+		lua.create_async_function(|lua, t: Table| async move {
+			let fields: Vec<Table> =
+				t.raw_get::<Table>("fields")?.sequence_values().collect::<mlua::Result<_>>()?;
+			let pos = Pos::new_input(t.raw_get("pos")?)?;
+
+			let values = lua.create_table_with_capacity(0, fields.len())?;
+			for (i, field) in fields.iter().enumerate() {
+				let title: String = field.raw_get("title")?;
+				let rx = InputProxy::show(InputCfg {
+					title: format!("{title} ({}/{})", i + 1, fields.len()),
+					value: field.raw_get("value").unwrap_or_default(),
+					obscure: field.raw_get("obscure").unwrap_or_default(),
+					position: pos.into(),
+					..Default::default()
+				});
+
+				// Canceling any of the fields cancels the whole form
+				match InputRx::consume(UnboundedReceiverStream::new(rx)).await {
+					(Some(value), 1) => values.raw_set(field.raw_get::<Value>("name")?, value)?,
+					_ => return Ok(None),
+				}
+			}
+			Ok(Some(values))
+		})
+	}
+
+	pub(super) fn pick(lua: &Lua) -> mlua::Result<Function> {
+		lua.create_async_function(|lua, t: Table| async move {
+			let mut rows = vec![];
+			for item in t.raw_get::<Table>("items")?.sequence_values::<Value>() {
+				rows.push(match item? {
+					Value::Table(cols) => cols.sequence_values().collect::<mlua::Result<_>>()?,
+					v => vec![String::from_lua(v, &lua)?],
+				});
+			}
+
+			let position = match t.raw_get::<Value>("pos")? {
+				Value::Nil => Position::new(YAZI.pick.open_origin, YAZI.pick.open_offset),
+				v => Pos::try_from(v)?.into(),
+			};
+
+			let result = PickProxy::show(PickCfg {
+				title: t.raw_get("title").unwrap_or_default(),
+				items: Self::align_columns(rows),
+				position,
+			});
+
+			Ok(result.await.ok().map(|idx| idx + 1))
+		})
+	}
+
 	pub(super) fn confirm(lua: &Lua) -> mlua::Result<Function> {
-		fn body(t: &Table) -> mlua::Result<ratatui::widgets::Paragraph<'static>> {
-			Ok(match t.raw_get::<Value>("body")? {
+		fn paragraph(t: &Table, key: &str) -> mlua::Result<ratatui::widgets::Paragraph<'static>> {
+			Ok(match t.raw_get::<Value>(key)? {
 				Value::Nil => Default::default(),
 				v => Text::try_from(v)?.into(),
 			})
@@ -84,8 +137,9 @@ impl Utils {
 			let result = ConfirmProxy::show(ConfirmCfg {
 				position: Pos::try_from(t.raw_get::<Value>("pos")?)?.into(),
 				title:    Line::try_from(t.raw_get::<Value>("title")?)?.into(),
-				body:     body(&t)?,
-				list:     Default::default(), // TODO
+				body:     paragraph(&t, "body")?,
+				list:     paragraph(&t, "list")?,
+				answer:   None,
 			});
 
 			Ok(result.await)
@@ -99,6 +153,32 @@ impl Utils {
 		})
 	}
 
+	// Pads each column but the last to the widest cell of it, so the rows of a
+	// table line up in the picker
+	fn align_columns(rows: Vec<Vec<String>>) -> Vec<String> {
+		let mut widths = vec![];
+		for row in &rows {
+			widths.resize(widths.len().max(row.len()), 0);
+			for (w, col) in widths.iter_mut().zip(row) {
+				*w = col.width().max(*w);
+			}
+		}
+
+		rows
+			.into_iter()
+			.map(|row| {
+				let last = row.len().saturating_sub(1);
+				let mut s = String::new();
+				for (i, col) in row.into_iter().enumerate() {
+					let pad = if i == last { 0 } else { widths[i] - col.width() + 2 };
+					s.push_str(&col);
+					s.extend(std::iter::repeat_n(' ', pad));
+				}
+				s
+			})
+			.collect()
+	}
+
 	fn parse_keys(value: Value) -> mlua::Result<Vec<Key>> {
 		Ok(match value {
 			Value::String(s) => {
@@ -115,3 +195,18 @@ impl Utils {
 		})
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_align_columns() {
+		let rows = vec![
+			vec!["a".to_owned(), "first".to_owned(), "x".to_owned()],
+			vec!["日本".to_owned(), "2nd".to_owned()],
+			vec!["only".to_owned()],
+		];
+		assert_eq!(Utils::align_columns(rows), ["a     first  x", "日本  2nd", "only"]);
+	}
+}
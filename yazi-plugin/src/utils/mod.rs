@@ -1,5 +1,5 @@
 #![allow(clippy::module_inception)]
 
 yazi_macro::mod_flat!(
-	app cache call image json layer log preview process spot sync target text time user utils
+	app cache call host image json layer log preview process sort spot sync target text time user utils
 );
@@ -0,0 +1,33 @@
+use mlua::{Function, Lua, Table, Value};
+use tracing::error;
+use yazi_binding::File;
+
+use super::Utils;
+use crate::LUA;
+
+impl Utils {
+	// Sets the comparator `sort custom` is done with, which takes two files and
+	// returns whether the first one goes first, as the one of `table.sort()`
+	pub(super) fn sorter(lua: &Lua) -> mlua::Result<Function> {
+		lua.create_function(|lua, f: Option<Function>| lua.set_named_registry_value("ya_sorter", f))
+	}
+}
+
+/// The indices of the files in the order given by the comparator set with `ya.sorter()`, if any.
+pub fn custom_order(files: &[yazi_fs::File]) -> Option<Vec<usize>> {
+	let less: Function = LUA.named_registry_value("ya_sorter").ok()?;
+
+	let result = (|| {
+		let list = LUA.create_sequence_from(files.iter().map(|f| File::new(f.clone())))?;
+		let order = LUA.create_sequence_from(1..=files.len())?;
+		let cmp = LUA.create_function(move |_, (a, b): (usize, usize)| {
+			less.call::<bool>((list.raw_get::<Value>(a)?, list.raw_get::<Value>(b)?))
+		})?;
+
+		let sort: Function = LUA.globals().raw_get::<Table>("table")?.raw_get("sort")?;
+		sort.call::<()>((&order, cmp))?;
+		order.sequence_values::<usize>().map(|i| Ok(i? - 1)).collect::<mlua::Result<_>>()
+	})();
+
+	result.inspect_err(|e| error!("Sorting with the comparator of `ya.sorter()` failed: {e}")).ok()
+}
@@ -1,6 +1,7 @@
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-use mlua::{ExternalError, Function, Lua};
+use mlua::{ExternalError, Function, Lua, Value};
+use tokio::time::MissedTickBehavior;
 
 use super::Utils;
 
@@ -21,4 +22,22 @@ impl Utils {
 			Ok(())
 		})
 	}
+
+	// Calls `f` right away, then every `secs` seconds, until it returns `false`
+	pub(super) fn interval(lua: &Lua) -> mlua::Result<Function> {
+		lua.create_async_function(|_, (secs, f): (f64, Function)| async move {
+			if secs <= 0.0 {
+				return Err("non-positive interval duration".into_lua_err());
+			}
+
+			let mut interval = tokio::time::interval(Duration::from_secs_f64(secs));
+			interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+			loop {
+				interval.tick().await;
+				if let Value::Boolean(false) = f.call_async::<Value>(()).await? {
+					return Ok(());
+				}
+			}
+		})
+	}
 }
@@ -21,6 +21,9 @@ pub fn compose(
 			b"mgr_emit" => Utils::mgr_emit(lua)?,
 			b"manager_emit" => Utils::mgr_emit(lua)?, // TODO: remove this in the future
 
+			// Host
+			b"host" => return Utils::host(lua)?.into_lua(lua),
+
 			// Image
 			b"image_info" => Utils::image_info(lua)?,
 			b"image_show" => Utils::image_show(lua)?,
@@ -34,6 +37,8 @@ pub fn compose(
 			b"which" => Utils::which(lua)?,
 			b"input" => Utils::input(lua)?,
 			b"confirm" => Utils::confirm(lua)?,
+			b"form" => Utils::form(lua)?,
+			b"pick" => Utils::pick(lua)?,
 			b"notify" => Utils::notify(lua)?,
 
 			// Log
@@ -47,6 +52,9 @@ pub fn compose(
 			// Process
 			b"proc_info" => Utils::proc_info(lua)?,
 
+			// Sort
+			b"sorter" => Utils::sorter(lua)?,
+
 			// Spot
 			b"spot_table" => Utils::spot_table(lua)?,
 			b"spot_widgets" => Utils::spot_widgets(lua)?,
@@ -70,6 +78,7 @@ pub fn compose(
 			// Time
 			b"time" => Utils::time(lua)?,
 			b"sleep" => Utils::sleep(lua)?,
+			b"interval" => Utils::interval(lua)?,
 
 			// User
 			#[cfg(unix)]
@@ -31,6 +31,7 @@ impl AppProxy {
 			content: content.to_string(),
 			level:   NotifyLevel::Warn,
 			timeout: Duration::from_secs(5),
+			action:  None,
 		});
 	}
 
@@ -40,6 +41,7 @@ impl AppProxy {
 			content: content.to_string(),
 			level:   NotifyLevel::Error,
 			timeout: Duration::from_secs(10),
+			action:  None,
 		});
 	}
 
@@ -1,8 +1,6 @@
-use std::borrow::Cow;
-
 use yazi_macro::{emit, relay};
-use yazi_parser::mgr::{FilterOpt, FindDoOpt, OpenDoOpt, SearchOpt, UpdatePeekedOpt, UpdateSpottedOpt};
-use yazi_shared::{SStr, url::Url};
+use yazi_parser::mgr::{FilterOpt, FindDoOpt, GrepOpt, OpenDoOpt, PasteOpt, SearchOpt, UpdatePeekedOpt, UpdateSpottedOpt};
+use yazi_shared::{SStr, event::Data, url::Url};
 
 pub struct MgrProxy;
 
@@ -23,6 +21,14 @@ impl MgrProxy {
 		emit!(Call(relay!(mgr:open_do).with_any("option", opt)));
 	}
 
+	pub fn paste(opt: PasteOpt) {
+		emit!(Call(relay!(mgr:paste).with_any("option", opt)));
+	}
+
+	pub fn download(url: String) {
+		emit!(Call(relay!(mgr:download, [url])));
+	}
+
 	pub fn remove_do(targets: Vec<Url>, permanently: bool) {
 		emit!(Call(
 			relay!(mgr:remove_do).with("permanently", permanently).with_any("targets", targets)
@@ -41,11 +47,15 @@ impl MgrProxy {
 		emit!(Call(
 			// TODO: use second positional argument instead of `args` parameter
 			relay!(mgr:search_do, [opt.subject])
-				.with("via", Cow::Borrowed(opt.via.into_str()))
+				.with("via", opt.via)
 				.with("args", opt.args_raw.into_owned())
 		));
 	}
 
+	pub fn grep_do(opt: GrepOpt) {
+		emit!(Call(relay!(mgr:grep_do, [opt.subject]).with("args", opt.args_raw.into_owned())));
+	}
+
 	pub fn update_peeked(opt: UpdatePeekedOpt) {
 		emit!(Call(relay!(mgr:update_peeked).with_any("opt", opt)));
 	}
@@ -58,6 +68,10 @@ impl MgrProxy {
 		emit!(Call(relay!(mgr:update_tasks).with_any("urls", vec![url.clone()])));
 	}
 
+	pub fn compare_resolve(url: &Url) {
+		emit!(Call(relay!(mgr:compare, [Data::from("resolve"), Data::from(url)])));
+	}
+
 	pub fn update_paged_by(page: usize, only_if: &Url) {
 		emit!(Call(relay!(mgr:update_paged, [page]).with_any("only-if", only_if.clone())));
 	}
@@ -3,7 +3,7 @@ use std::{borrow::Cow, ffi::OsString};
 use tokio::sync::oneshot;
 use yazi_config::opener::OpenerRule;
 use yazi_macro::{emit, relay};
-use yazi_parser::{mgr::OpenWithOpt, tasks::ProcessExecOpt};
+use yazi_parser::{mgr::OpenWithOpt, tasks::{ProcessExecOpt, PurgeTrashOpt}};
 use yazi_shared::url::Url;
 
 pub struct TasksProxy;
@@ -15,12 +15,14 @@ impl TasksProxy {
 
 	pub async fn process_exec(opener: Cow<'static, OpenerRule>, cwd: Url, args: Vec<OsString>) {
 		let (tx, rx) = oneshot::channel();
-		emit!(Call(relay!(tasks:process_exec).with_any("option", ProcessExecOpt {
-			cwd,
-			opener,
-			args,
-			done: Some(tx)
-		})));
+		emit!(Call(
+			relay!(tasks:process_exec)
+				.with_any("option", ProcessExecOpt { cwd, opener, args, line: None, done: Some(tx) })
+		));
 		rx.await.ok();
 	}
+
+	pub fn purge_trash(ids: Vec<String>, size: u64) {
+		emit!(Call(relay!(tasks:purge_trash).with_any("option", PurgeTrashOpt { ids, size })));
+	}
 }
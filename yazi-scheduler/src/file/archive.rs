@@ -0,0 +1,245 @@
+use std::{ffi::OsString, io, path::Path, process::Stdio};
+
+use anyhow::{Result, bail};
+use tokio::{io::{AsyncBufReadExt, AsyncReadExt, BufReader}, process::{Child, Command}};
+
+/// An entry of an archive, as listed by 7-Zip.
+#[derive(Debug, Default, Eq, PartialEq)]
+pub(crate) struct ArchiveEntry {
+	pub(crate) path:      String,
+	pub(crate) size:      u64,
+	pub(crate) dir:       bool,
+	pub(crate) encrypted: bool,
+}
+
+/// Archives handled with 7-Zip, either `7zz` or `7z`.
+pub struct Archive;
+
+impl Archive {
+	/// Lists the entries, or `None` if the password is wrong, or is needed for the encrypted headers.
+	pub(crate) async fn list(path: &Path, password: &str) -> Result<Option<Vec<ArchiveEntry>>> {
+		let args = Self::args(["l", "-ba", "-slt", "-sccUTF-8"], password, path);
+		let output = Self::spawn(args)?.wait_with_output().await?;
+
+		let stderr = String::from_utf8_lossy(&output.stderr);
+		if Self::wrong_password(&stderr) {
+			return Ok(None);
+		} else if !output.status.success() {
+			bail!("7-Zip failed to list the archive: {}", stderr.trim());
+		}
+		Ok(Some(Self::parse_list(&String::from_utf8_lossy(&output.stdout))))
+	}
+
+	/// Whether the password opens the entry, tested without writing it out.
+	pub(crate) async fn test(path: &Path, password: &str, entry: &str) -> Result<bool> {
+		let mut args = Self::args(["t", "-sccUTF-8"], password, path);
+		args.push(entry.into());
+
+		let output = Self::spawn(args)?.wait_with_output().await?;
+		let stderr = String::from_utf8_lossy(&output.stderr);
+		if Self::wrong_password(&stderr) {
+			return Ok(false);
+		} else if !output.status.success() {
+			bail!("7-Zip failed to test the archive: {}", stderr.trim());
+		}
+		Ok(true)
+	}
+
+	/// Extracts all the entries into `dest`, calling `done` with each file.
+	pub(crate) async fn extract(
+		path: &Path,
+		dest: &Path,
+		password: &str,
+		mut done: impl FnMut(&str),
+	) -> Result<()> {
+		let mut out = OsString::from("-o");
+		out.push(dest);
+
+		let mut args = Self::args(["x", "-bb1", "-bsp0", "-y", "-sccUTF-8"], password, path);
+		args.insert(args.len() - 2, out);
+
+		let mut child = Self::spawn(args)?;
+		let mut stderr = child.stderr.take().unwrap();
+		let stderr = tokio::spawn(async move {
+			let mut s = String::new();
+			stderr.read_to_string(&mut s).await.ok();
+			s
+		});
+
+		let mut lines = BufReader::new(child.stdout.take().unwrap()).lines();
+		while let Some(line) = lines.next_line().await? {
+			if let Some(p) = line.strip_prefix("- ") {
+				done(p.trim_end_matches('\r'));
+			}
+		}
+
+		let status = child.wait().await?;
+		let stderr = stderr.await.unwrap_or_default();
+		if Self::wrong_password(&stderr) {
+			bail!("Wrong password");
+		} else if !status.success() {
+			bail!("7-Zip exited with {status}: {}", stderr.trim());
+		}
+		Ok(())
+	}
+
+	/// The first volume of the multi-volume archive that `name` is a part of.
+	pub fn first_volume(name: &str) -> Option<String> {
+		let (stem, ext) = name.rsplit_once('.')?;
+		let lower = ext.to_ascii_lowercase();
+
+		// foo.7z.002, foo.zip.002
+		if ext.len() >= 3 && Self::digits(ext) {
+			return Self::not_first(ext).then(|| format!("{stem}.{:0>1$}", 1, ext.len()));
+		}
+
+		// foo.z01, the split zip whose last volume `foo.zip` is the one opened
+		if lower.len() >= 3 && lower.starts_with('z') && Self::digits(&lower[1..]) {
+			return Some(format!("{stem}.zip"));
+		}
+
+		// foo.part2.rar
+		let (stem, part) = stem.rsplit_once('.')?;
+		let n = part.get(4..).filter(|_| part[..4].eq_ignore_ascii_case("part"))?;
+		if lower == "rar" && Self::digits(n) {
+			return Self::not_first(n).then(|| format!("{stem}.{}{:0>2$}.{ext}", &part[..4], 1, n.len()));
+		}
+		None
+	}
+
+	/// The name without the extensions of the archive, e.g. `foo` for `foo.tar.gz`.
+	pub fn stem(name: &str) -> &str {
+		const EXTS: [&str; 22] = [
+			"7z", "apk", "bz2", "bzip2", "cbr", "cbz", "exe", "gz", "gzip", "iso", "jar", "lz4", "rar",
+			"tar", "tbz", "tbz2", "tgz", "txz", "tzst", "xz", "zip", "zst",
+		];
+
+		let mut stem = name;
+		while let Some((s, ext)) = stem.rsplit_once('.')
+			&& !s.is_empty()
+		{
+			let volume = match ext.get(..4) {
+				Some(p) if p.eq_ignore_ascii_case("part") => Self::digits(&ext[4..]),
+				_ => ext.len() >= 3 && Self::digits(ext),
+			};
+			if !volume && !EXTS.iter().any(|e| e.eq_ignore_ascii_case(ext)) {
+				break;
+			}
+			stem = s;
+		}
+		stem
+	}
+
+	/// Whether it's a compressed tarball, which is extracted twice.
+	pub fn tarball(name: &str) -> bool {
+		let Some((stem, ext)) = name.rsplit_once('.') else { return false };
+		["tbz", "tbz2", "tgz", "txz", "tzst"].iter().any(|e| e.eq_ignore_ascii_case(ext))
+			|| stem.get(stem.len().saturating_sub(4)..).is_some_and(|s| s.eq_ignore_ascii_case(".tar"))
+	}
+
+	fn parse_list(s: &str) -> Vec<ArchiveEntry> {
+		let mut entries: Vec<ArchiveEntry> = vec![];
+		for line in s.lines() {
+			let Some((key, value)) = line.split_once(" = ") else { continue };
+			if key == "Path" {
+				entries.push(ArchiveEntry { path: value.to_owned(), ..Default::default() });
+				continue;
+			}
+
+			let Some(entry) = entries.last_mut() else { continue };
+			match key {
+				"Size" => entry.size = value.parse().unwrap_or(0),
+				"Folder" => entry.dir |= value == "+",
+				"Attributes" => entry.dir |= value.starts_with('D'),
+				"Encrypted" => entry.encrypted = value == "+",
+				_ => {}
+			}
+		}
+		entries
+	}
+
+	fn args<const N: usize>(args: [&str; N], password: &str, path: &Path) -> Vec<OsString> {
+		// Always given, even if empty, so 7-Zip never waits for it on the stdin
+		let password = format!("-p{password}");
+		let args = args.into_iter().chain([password.as_str(), "--"]).map(OsString::from);
+		args.chain([path.into()]).collect()
+	}
+
+	fn spawn(args: Vec<OsString>) -> io::Result<Child> {
+		let spawn = |bin: &str| {
+			Command::new(bin)
+				.args(&args)
+				.stdin(Stdio::null())
+				.stdout(Stdio::piped())
+				.stderr(Stdio::piped())
+				.kill_on_drop(true)
+				.spawn()
+		};
+
+		match spawn("7zz") {
+			Err(e) if e.kind() == io::ErrorKind::NotFound => spawn("7z"),
+			r => r,
+		}
+	}
+
+	#[inline]
+	fn digits(s: &str) -> bool { !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit()) }
+
+	#[inline]
+	fn not_first(n: &str) -> bool { n.trim_start_matches('0') != "1" }
+
+	#[inline]
+	fn wrong_password(stderr: &str) -> bool { stderr.contains("Wrong password") }
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_parse_list() {
+		let s = "Path = foo\nFolder = +\nSize = 0\n\n\
+		         Path = foo/bar.txt\nFolder = -\nSize = 12\nEncrypted = +\n\n\
+		         Path = baz\nSize = 0\nAttributes = D drwxr-xr-x\n";
+		let entries = Archive::parse_list(s);
+
+		assert_eq!(entries.len(), 3);
+		assert!(entries[0].dir && !entries[0].encrypted);
+		assert_eq!((entries[1].path.as_str(), entries[1].size), ("foo/bar.txt", 12));
+		assert!(!entries[1].dir && entries[1].encrypted);
+		assert!(entries[2].dir);
+	}
+
+	#[test]
+	fn test_first_volume() {
+		assert_eq!(Archive::first_volume("foo.7z.003").as_deref(), Some("foo.7z.001"));
+		assert_eq!(Archive::first_volume("foo.zip.0010").as_deref(), Some("foo.zip.0001"));
+		assert_eq!(Archive::first_volume("foo.part2.rar").as_deref(), Some("foo.part1.rar"));
+		assert_eq!(Archive::first_volume("foo.Part02.RAR").as_deref(), Some("foo.Part01.RAR"));
+		assert_eq!(Archive::first_volume("foo.z01").as_deref(), Some("foo.zip"));
+
+		for name in ["foo.7z.001", "foo.part1.rar", "foo.part01.rar", "foo.zip", "foo.2", "foo"] {
+			assert_eq!(Archive::first_volume(name), None, "{name}");
+		}
+	}
+
+	#[test]
+	fn test_stem() {
+		assert_eq!(Archive::stem("foo.tar.gz"), "foo");
+		assert_eq!(Archive::stem("foo.7z.001"), "foo");
+		assert_eq!(Archive::stem("foo.part1.rar"), "foo");
+		assert_eq!(Archive::stem("foo.bar.ZIP"), "foo.bar");
+		assert_eq!(Archive::stem(".zip"), ".zip");
+		assert_eq!(Archive::stem("v1.2.tgz"), "v1.2");
+	}
+
+	#[test]
+	fn test_tarball() {
+		assert!(Archive::tarball("foo.tar.gz"));
+		assert!(Archive::tarball("foo.TAR.zst"));
+		assert!(Archive::tarball("foo.tgz"));
+		assert!(!Archive::tarball("foo.tar"));
+		assert!(!Archive::tarball("foo.gz"));
+		assert!(!Archive::tarball("tar"));
+	}
+}
@@ -0,0 +1,127 @@
+use std::{io, sync::Arc, time::SystemTime};
+
+use anyhow::Result;
+use parking_lot::Mutex;
+use yazi_config::popup::PickCfg;
+use yazi_fs::{cha::Cha, must_be_dir, path::unique_name, provider};
+use yazi_parser::mgr::PasteOptConflict;
+use yazi_proxy::PickProxy;
+use yazi_shared::url::Url;
+
+use crate::readable_size;
+
+/// Resolves the conflicts with the existing files before a paste starts.
+#[derive(Default)]
+pub struct Conflict {
+	mode:   PasteOptConflict,
+	sticky: Mutex<Option<PasteOptConflict>>,
+	asking: tokio::sync::Mutex<()>,
+}
+
+impl Conflict {
+	const CHOICES: [PasteOptConflict; 4] = [
+		PasteOptConflict::Overwrite,
+		PasteOptConflict::Skip,
+		PasteOptConflict::Rename,
+		PasteOptConflict::Newer,
+	];
+
+	pub fn new(mode: PasteOptConflict) -> Arc<Self> { Arc::new(Self { mode, ..Default::default() }) }
+
+	#[inline]
+	pub fn overwrites(&self) -> bool { self.mode == PasteOptConflict::Overwrite }
+
+	/// The destination to paste `from` into, or `None` if it should be skipped.
+	pub(crate) async fn resolve(&self, from: &Url, to: Url) -> Result<Option<Url>> {
+		match self.mode {
+			PasteOptConflict::Rename => return Ok(Some(Self::rename(from, to).await?)),
+			PasteOptConflict::Overwrite => return Ok(Some(to)),
+			_ if *from == to => return Ok(Some(Self::rename(from, to).await?)),
+			_ => {}
+		}
+
+		let dst = match provider::symlink_metadata(&to).await {
+			Ok(meta) => Cha::new(&to, meta),
+			Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Some(to)),
+			Err(e) => return Err(e.into()),
+		};
+		let src = Cha::new(from, provider::symlink_metadata(from).await?);
+
+		let rule = match self.mode {
+			PasteOptConflict::Ask => match self.ask(&src, &dst, &to).await {
+				Some(rule) => rule,
+				None => return Ok(None),
+			},
+			mode => mode,
+		};
+
+		Ok(match rule {
+			PasteOptConflict::Rename => Some(Self::rename(from, to).await?),
+			PasteOptConflict::Overwrite => Some(to),
+			PasteOptConflict::Skip => None,
+			PasteOptConflict::Newer => Self::is_newer(&src, &dst).then_some(to),
+			PasteOptConflict::Ask => unreachable!(),
+		})
+	}
+
+	async fn ask(&self, src: &Cha, dst: &Cha, to: &Url) -> Option<PasteOptConflict> {
+		// One at a time, as the answer may apply to the ones waiting
+		let _guard = self.asking.lock().await;
+		if let Some(rule) = *self.sticky.lock() {
+			return Some(rule);
+		}
+
+		let newer = if Self::is_newer(src, dst) { "the pasted one" } else { "the existing one" };
+		let describe = |rule: PasteOptConflict| match rule {
+			PasteOptConflict::Overwrite => format!("with the pasted {}", Self::describe(src)),
+			PasteOptConflict::Skip => format!("keep the existing {}", Self::describe(dst)),
+			PasteOptConflict::Rename => "paste it with a suffix".to_owned(),
+			_ => format!("keep {newer}"),
+		};
+
+		let once = Self::CHOICES.map(|rule| format!("{:<16}{}", Self::label(rule), describe(rule)));
+		let all = Self::CHOICES.map(|rule| {
+			format!("{:<16}for the remaining conflicts", format!("{} all", Self::label(rule)))
+		});
+		let items = once.into_iter().chain(all).collect();
+
+		let name = to.name().to_string_lossy();
+		// Canceling the pick skips this one only
+		let choice = PickProxy::show(PickCfg::conflict(&name, items)).await.ok()?;
+		let rule = Self::CHOICES[choice % Self::CHOICES.len()];
+		if choice >= Self::CHOICES.len() {
+			*self.sticky.lock() = Some(rule);
+		}
+		Some(rule)
+	}
+
+	async fn rename(from: &Url, to: Url) -> io::Result<Url> {
+		unique_name(to, must_be_dir(from)).await
+	}
+
+	fn is_newer(src: &Cha, dst: &Cha) -> bool {
+		src.mtime.unwrap_or(SystemTime::UNIX_EPOCH) > dst.mtime.unwrap_or(SystemTime::UNIX_EPOCH)
+	}
+
+	fn label(rule: PasteOptConflict) -> &'static str {
+		match rule {
+			PasteOptConflict::Overwrite => "Overwrite",
+			PasteOptConflict::Skip => "Skip",
+			PasteOptConflict::Rename => "Rename",
+			PasteOptConflict::Newer => "Keep newer",
+			PasteOptConflict::Ask => "Ask",
+		}
+	}
+
+	fn describe(cha: &Cha) -> String {
+		let what = if cha.is_dir() { "directory".to_owned() } else { readable_size(cha.len) };
+		let Some(elapsed) = cha.mtime.and_then(|t| t.elapsed().ok()) else { return what };
+		let ago = match elapsed.as_secs() {
+			s @ 0..60 => format!("{s}s ago"),
+			s @ 60..3600 => format!("{}m ago", s / 60),
+			s @ 3600..86400 => format!("{}h ago", s / 3600),
+			s => format!("{}d ago", s / 86400),
+		};
+		format!("{what}, modified {ago}")
+	}
+}
@@ -0,0 +1,215 @@
+use std::{path::Path, process::Stdio};
+
+use anyhow::{Result, bail};
+use tokio::{fs::OpenOptions, io::{AsyncReadExt, AsyncWriteExt}, process::Command};
+use yazi_fs::{SanitizeTarget, Sanitizer};
+
+/// What the response headers tell about a download.
+#[derive(Debug, Default, Eq, PartialEq)]
+pub(crate) struct DownloadHead {
+	pub(crate) name:   String,
+	pub(crate) length: Option<u64>,
+	pub(crate) ranges: bool,
+}
+
+/// Downloads over HTTP(S) with `curl`, resuming from the `.part` file.
+pub struct Download;
+
+impl Download {
+	/// Whether it's a URL that can be downloaded.
+	pub fn valid(url: &str) -> bool {
+		["http://", "https://"]
+			.iter()
+			.any(|p| url.get(..p.len()).is_some_and(|s| s.eq_ignore_ascii_case(p)) && url.len() > p.len())
+	}
+
+	/// Asks for the headers of the final response, after the redirects.
+	pub(crate) async fn head(url: &str) -> Result<DownloadHead> {
+		let output = Self::command(url).args(["-I", "-w", "\n%{url_effective}"]).output().await?;
+		Ok(if output.status.success() {
+			Self::parse_head(&String::from_utf8_lossy(&output.stdout), url)
+		} else {
+			DownloadHead { name: Self::name(None, url), ..Default::default() }
+		})
+	}
+
+	/// Downloads into `part`, appending to what's already there from `offset`.
+	pub(crate) async fn fetch(
+		url: &str,
+		part: &Path,
+		offset: u64,
+		mut adv: impl FnMut(u64),
+	) -> Result<()> {
+		let mut file = OpenOptions::new()
+			.create(true)
+			.write(true)
+			.append(offset > 0)
+			.truncate(offset == 0)
+			.open(part)
+			.await?;
+
+		let mut cmd = Self::command(url);
+		if offset > 0 {
+			cmd.args(["-C", &offset.to_string()]);
+		}
+
+		let mut child = cmd.stdout(Stdio::piped()).stderr(Stdio::piped()).spawn()?;
+		let mut stdout = child.stdout.take().unwrap();
+
+		let mut buf = vec![0; 64 * 1024];
+		loop {
+			let n = stdout.read(&mut buf).await?;
+			if n == 0 {
+				break;
+			}
+			file.write_all(&buf[..n]).await?;
+			adv(n as u64);
+		}
+		file.flush().await?;
+
+		let output = child.wait_with_output().await?;
+		if !output.status.success() {
+			let stderr = String::from_utf8_lossy(&output.stderr);
+			bail!("curl exited with {}: {}", output.status, stderr.trim());
+		}
+		Ok(())
+	}
+
+	fn command(url: &str) -> Command {
+		let mut cmd = Command::new("curl");
+		cmd
+			.args(["-fsSL", "--proto", "=http,https", "--proto-redir", "=http,https", "--url", url])
+			.stdin(Stdio::null())
+			.kill_on_drop(true);
+		cmd
+	}
+
+	// The headers of each response come first, the last block being the final
+	// one, then the URL after the redirects on the last line
+	fn parse_head(s: &str, url: &str) -> DownloadHead {
+		let (headers, effective) = match s.trim_end().rsplit_once('\n') {
+			Some((h, u)) if u.contains("://") => (h, u.trim()),
+			_ => (s, url),
+		};
+
+		let (mut disposition, mut head) = (None, DownloadHead::default());
+		for line in headers.lines() {
+			if line.starts_with("HTTP/") {
+				(disposition, head) = Default::default();
+				continue;
+			}
+
+			let Some((key, value)) = line.split_once(':') else { continue };
+			let value = value.trim();
+			match key.trim().to_ascii_lowercase().as_str() {
+				"content-disposition" => disposition = Some(value),
+				"content-length" => head.length = value.parse().ok(),
+				"accept-ranges" => head.ranges = value.eq_ignore_ascii_case("bytes"),
+				_ => {}
+			}
+		}
+
+		head.name = Self::name(disposition, effective);
+		head
+	}
+
+	/// The name to save it as, from the `Content-Disposition` or the URL.
+	fn name(disposition: Option<&str>, url: &str) -> String {
+		let from_url = || {
+			let (_, rest) = url.split(['?', '#']).next()?.split_once("://")?;
+			Some(Self::decode(rest.split_once('/')?.1.rsplit('/').next()?))
+		};
+
+		let name = disposition.and_then(Self::disposition_name).or_else(from_url);
+		let target = if cfg!(windows) { SanitizeTarget::Windows } else { SanitizeTarget::Posix };
+		match name.as_deref().map(str::trim) {
+			Some(s) if !s.is_empty() => Sanitizer::new(target).fix(s).into_owned(),
+			_ => "download".to_owned(),
+		}
+	}
+
+	fn disposition_name(value: &str) -> Option<String> {
+		let mut plain = None;
+		for param in value.split(';') {
+			let Some((key, value)) = param.split_once('=') else { continue };
+			let value = value.trim().trim_matches('"');
+			match key.trim().to_ascii_lowercase().as_str() {
+				// RFC 5987, e.g. `filename*=UTF-8''na%C3%AFve.txt`, which is preferred
+				"filename*" => {
+					if let Some((_, encoded)) = value.split_once("''") {
+						return Some(Self::decode(encoded));
+					}
+				}
+				"filename" => plain = Some(value.replace("\\\"", "\"")),
+				_ => {}
+			}
+		}
+		plain
+	}
+
+	fn decode(s: &str) -> String {
+		let (b, mut out) = (s.as_bytes(), Vec::with_capacity(s.len()));
+		let mut i = 0;
+		while i < b.len() {
+			let hex = b
+				.get(i + 1..i + 3)
+				.filter(|h| b[i] == b'%' && h.iter().all(u8::is_ascii_hexdigit))
+				.and_then(|h| u8::from_str_radix(std::str::from_utf8(h).ok()?, 16).ok());
+
+			if let Some(h) = hex {
+				out.push(h);
+				i += 3;
+			} else {
+				out.push(b[i]);
+				i += 1;
+			}
+		}
+		String::from_utf8_lossy(&out).into_owned()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_valid() {
+		assert!(Download::valid("https://example.com/a.zip"));
+		assert!(Download::valid("HTTP://example.com"));
+		assert!(!Download::valid("https://"));
+		assert!(!Download::valid("ftp://example.com/a.zip"));
+		assert!(!Download::valid("-o /etc/passwd"));
+	}
+
+	#[test]
+	fn test_parse_head() {
+		let s = "HTTP/2 302\r\nlocation: https://cdn.example.com/x?sig=1\r\ncontent-length: 0\r\n\r\n\
+		         HTTP/2 200\r\nContent-Length: 1024\r\nAccept-Ranges: bytes\r\n\
+		         Content-Disposition: attachment; filename=\"yazi-x86_64.zip\"\r\n\r\n\
+		         https://cdn.example.com/x?sig=1";
+		let head = Download::parse_head(s, "https://example.com/latest");
+		assert_eq!(head, DownloadHead {
+			name:   "yazi-x86_64.zip".to_owned(),
+			length: Some(1024),
+			ranges: true,
+		});
+
+		let url = "https://example.com/dir/a%20b.txt?x=1";
+		let s = format!("HTTP/1.1 200 OK\r\nContent-Type: text/plain\r\n\r\n{url}");
+		let head = Download::parse_head(&s, url);
+		assert_eq!(head, DownloadHead { name: "a b.txt".to_owned(), length: None, ranges: false });
+	}
+
+	#[test]
+	fn test_name() {
+		let name = Download::name;
+		let rfc5987 = "attachment; filename*=UTF-8''na%C3%AFve.txt; filename=x";
+		assert_eq!(name(Some(rfc5987), ""), "naïve.txt");
+		assert_eq!(name(Some("attachment; filename=\"My File.zip\""), ""), "My File.zip");
+		assert_eq!(name(Some("inline"), "https://example.com/a/b.tar.gz#top"), "b.tar.gz");
+		assert_eq!(name(Some("attachment; filename=\"../../.bashrc\""), ""), ".._.._.bashrc");
+		assert_eq!(name(None, "https://example.com/"), "download");
+		assert_eq!(name(None, "https://example.com"), "download");
+		assert_eq!(name(None, "https://example.com/100%"), "100%");
+	}
+}
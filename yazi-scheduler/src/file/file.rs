@@ -1,43 +1,56 @@
-use std::{borrow::Cow, collections::VecDeque};
+use std::{borrow::Cow, collections::VecDeque, path::Path, sync::Arc};
 
-use anyhow::{Result, anyhow};
+use anyhow::{Result, bail};
 use tokio::{io::{self, ErrorKind::{AlreadyExists, NotFound}}, sync::mpsc};
 use tracing::warn;
-use yazi_config::YAZI;
-use yazi_fs::{SizeCalculator, cha::Cha, copy_with_progress, maybe_exists, ok_or_not_found, path::{skip_url, url_relative_to}, provider::{self, DirEntry}};
+use yazi_config::{YAZI, popup::InputCfg};
+use yazi_fs::{CopyProg, DUPES, DupeFile, Dupes, Pause, SizeCalculator, Xattr, cha::Cha, checksum, copy_with_progress, hard_link_over, maybe_exists, must_be_dir, ok_or_not_found, path::{skip_url, unique_name, url_relative_to}, provider::{self, DirEntry}};
+use yazi_proxy::InputProxy;
 use yazi_shared::{Id, url::Url};
 
-use super::{FileIn, FileInDelete, FileInHardlink, FileInLink, FileInPaste, FileInTrash};
-use crate::{LOW, NORMAL, TaskOp, TaskProg};
+use super::{Archive, ArchiveEntry, Download, FileIn, FileInDelete, FileInDownload, FileInDupes, FileInExtract, FileInHardlink, FileInLink, FileInPaste, FileInPurge, FileInTrash, Recycle};
+use crate::{LOW, NORMAL, Queue, SUMMARY, SummaryKind, TaskOp, TaskProg};
 
 pub struct File {
-	r#macro: async_priority_channel::Sender<TaskOp, u8>,
+	r#macro: Arc<Queue>,
 	prog:    mpsc::UnboundedSender<TaskProg>,
 }
 
 impl File {
-	pub fn new(
-		r#macro: async_priority_channel::Sender<TaskOp, u8>,
-		prog: mpsc::UnboundedSender<TaskProg>,
-	) -> Self {
+	pub fn new(r#macro: Arc<Queue>, prog: mpsc::UnboundedSender<TaskProg>) -> Self {
 		Self { r#macro, prog }
 	}
 
-	pub async fn work(&self, r#in: FileIn) -> Result<()> {
+	pub async fn work(&self, r#in: FileIn, pause: Pause) -> Result<()> {
 		match r#in {
 			FileIn::Paste(mut task) => {
 				ok_or_not_found(provider::remove_file(&task.to).await)?;
-				let mut it = copy_with_progress(&task.from, &task.to, task.cha.unwrap());
+				let mut it = copy_with_progress(&task.from, &task.to, task.cha.unwrap(), pause);
 
 				while let Some(res) = it.recv().await {
 					match res {
-						Ok(0) => {
+						Ok(CopyProg::Done(method)) => {
+							self.prog.send(TaskProg::Copied(task.id, method))?;
+							// The source of a cut is only removed once the copy is verified
+							if task.verify {
+								Self::verify(&task.from, &task.to).await?;
+							}
+							if YAZI.tasks.preserve_xattr
+								&& let Err(e) = Xattr::copy(&task.from, &task.to).await
+							{
+								self.log(task.id, format!("Failed to copy extended attributes: {e}"))?;
+							}
+
+							let len = task.cha.map_or(0, |c| c.len);
 							if task.cut {
 								provider::remove_file(&task.from).await.ok();
+								SUMMARY.record(SummaryKind::Moved, len);
+							} else {
+								SUMMARY.record(SummaryKind::Copied, len);
 							}
 							break;
 						}
-						Ok(n) => self.prog.send(TaskProg::Adv(task.id, 0, n))?,
+						Ok(CopyProg::Adv(n)) => self.prog.send(TaskProg::Adv(task.id, 0, n))?,
 						Err(e) if e.kind() == NotFound => {
 							warn!("Paste task partially done: {task:?}");
 							break;
@@ -50,7 +63,7 @@ impl File {
 						{
 							task.retry += 1;
 							self.log(task.id, format!("Paste task retry: {task:?}"))?;
-							self.queue(FileIn::Paste(task), LOW).await?;
+							self.queue(FileIn::Paste(task), LOW);
 							return Ok(());
 						}
 						Err(e) => Err(e)?,
@@ -102,8 +115,7 @@ impl File {
 					Cow::Borrowed(&task.from)
 				};
 
-				ok_or_not_found(provider::remove_file(&task.to).await)?;
-				match provider::hard_link(src, &task.to).await {
+				match hard_link_over(src, &task.to).await {
 					Err(e) if e.kind() == NotFound => {
 						warn!("Hardlink task partially done: {task:?}");
 					}
@@ -120,6 +132,7 @@ impl File {
 					self.fail(task.id, format!("Delete task failed: {task:?}, {e}"))?;
 					Err(e)?
 				}
+				SUMMARY.record(SummaryKind::Deleted, task.length);
 				self.prog.send(TaskProg::Adv(task.id, 1, task.length))?
 			}
 			FileIn::Trash(task) => {
@@ -138,14 +151,85 @@ impl File {
 					Ok::<_, anyhow::Error>(())
 				})
 				.await??;
+				SUMMARY.record(SummaryKind::Trashed, task.length);
+				self.prog.send(TaskProg::Adv(task.id, 1, task.length))?;
+			}
+			FileIn::Purge(task) => {
+				Recycle::purge(task.ids).await?;
 				self.prog.send(TaskProg::Adv(task.id, 1, task.length))?;
 			}
+			FileIn::Extract(mut task) => {
+				let tmp = unique_name(task.to.join(".extracting"), async { true }).await?;
+				provider::create_dir_all(&tmp).await?;
+
+				let result = Archive::extract(
+					task.from.as_path().unwrap(),
+					tmp.as_path().unwrap(),
+					&task.password,
+					|path| {
+						if let Some(size) = task.entries.remove(path) {
+							self.prog.send(TaskProg::Adv(task.id, 1, size)).ok();
+						}
+					},
+				)
+				.await;
+
+				if let Err(e) = result {
+					provider::remove_dir_all(&tmp).await.ok();
+					for path in task.entries.keys() {
+						self.fail(task.id, format!("Not extracted: {path}"))?;
+					}
+					Err(e)?
+				}
+
+				// Not every file is reported as it's extracted, e.g. the empty ones
+				for size in task.entries.into_values() {
+					self.prog.send(TaskProg::Adv(task.id, 1, size))?;
+				}
+
+				let result = Self::settle(&tmp, &task.from, &task.to).await;
+				provider::remove_dir_all(&tmp).await.ok();
+				self.log(task.id, format!("Extracted to {}", result?.display()))?;
+				self.prog.send(TaskProg::Adv(task.id, 1, 0))?;
+			}
+			FileIn::Download(task) => {
+				// What's downloaded so far is kept in the `.part` file if it fails,
+				// so the next download of it resumes from there
+				let part = task.to.join(format!("{}.part", task.name));
+				if task.offset > 0 {
+					self.prog.send(TaskProg::Adv(task.id, 0, task.offset))?;
+				}
+
+				Download::fetch(&task.url, part.as_path().unwrap(), task.offset, |n| {
+					self.prog.send(TaskProg::Adv(task.id, 0, n)).ok();
+				})
+				.await?;
+
+				let dest = unique_name(task.to.join(&task.name), async { false }).await?;
+				provider::rename(&part, &dest).await?;
+				self.log(task.id, format!("Downloaded to {}", dest.display()))?;
+				self.prog.send(TaskProg::Adv(task.id, 1, 0))?;
+			}
+			FileIn::Dupes(task) => {
+				let FileInDupes { id, files, found } = task;
+				let prog = self.prog.clone();
+
+				let groups = tokio::task::spawn_blocking(move || {
+					DUPES.split(files, |n| _ = prog.send(TaskProg::Adv(id, 0, n)))
+				})
+				.await?;
+
+				found.lock().extend(groups);
+				self.prog.send(TaskProg::Adv(id, 1, 0))?;
+			}
 		}
 		Ok(())
 	}
 
-	pub async fn paste(&self, mut task: FileInPaste) -> Result<()> {
+	/// Pastes a file, or walks a directory to queue the pastes of the files in it.
+	pub async fn paste(&self, mut task: FileInPaste, alive: impl Fn() -> bool) -> Result<()> {
 		if task.cut && ok_or_not_found(provider::rename(&task.from, &task.to).await).is_ok() {
+			SUMMARY.record(SummaryKind::Moved, task.cha.map_or(0, |c| c.len));
 			return self.succ(task.id);
 		}
 
@@ -159,13 +243,17 @@ impl File {
 			self.prog.send(TaskProg::New(id, cha.len))?;
 
 			if cha.is_orphan() || (cha.is_link() && !task.follow) {
-				self.queue(FileIn::Link(task.into()), NORMAL).await?;
+				self.queue(FileIn::Link(task.into()), NORMAL);
 			} else {
-				self.queue(FileIn::Paste(task), LOW).await?;
+				self.queue(FileIn::Paste(task), LOW);
 			}
 			return self.succ(id);
 		}
 
+		if !self.prescan(task.id, &task.from, alive).await? {
+			return Ok(());
+		}
+
 		macro_rules! continue_unless_ok {
 			($result:expr) => {
 				match $result {
@@ -189,6 +277,9 @@ impl File {
 				Err(e) if e.kind() != AlreadyExists => Err(e),
 				_ => Ok(()),
 			});
+			if YAZI.tasks.preserve_xattr {
+				Xattr::copy(&src, &dest).await.ok();
+			}
 
 			let mut it = continue_unless_ok!(provider::read_dir(&src).await);
 			while let Ok(Some(entry)) = it.next_entry().await {
@@ -200,13 +291,13 @@ impl File {
 					continue;
 				}
 
-				let to = dest.join(from.file_name().unwrap());
+				let to = from.rebase(&dest);
 				self.prog.send(TaskProg::New(task.id, cha.len))?;
 
 				if cha.is_orphan() || (cha.is_link() && !task.follow) {
-					self.queue(FileIn::Link(task.spawn(from, to, cha).into()), NORMAL).await?;
+					self.queue(FileIn::Link(task.spawn(from, to, cha).into()), NORMAL);
 				} else {
-					self.queue(FileIn::Paste(task.spawn(from, to, cha)), LOW).await?;
+					self.queue(FileIn::Paste(task.spawn(from, to, cha)), LOW);
 				}
 			}
 		}
@@ -220,7 +311,7 @@ impl File {
 		}
 
 		self.prog.send(TaskProg::New(id, task.cha.unwrap().len))?;
-		self.queue(FileIn::Link(task), NORMAL).await?;
+		self.queue(FileIn::Link(task), NORMAL);
 		self.succ(id)
 	}
 
@@ -233,7 +324,7 @@ impl File {
 		if !cha.is_dir() {
 			let id = task.id;
 			self.prog.send(TaskProg::New(id, cha.len))?;
-			self.queue(FileIn::Hardlink(task), NORMAL).await?;
+			self.queue(FileIn::Hardlink(task), NORMAL);
 			return self.succ(id);
 		}
 
@@ -271,9 +362,9 @@ impl File {
 					continue;
 				}
 
-				let to = dest.join(from.file_name().unwrap());
+				let to = from.rebase(&dest);
 				self.prog.send(TaskProg::New(task.id, cha.len))?;
-				self.queue(FileIn::Hardlink(task.spawn(from, to, cha)), NORMAL).await?;
+				self.queue(FileIn::Hardlink(task.spawn(from, to, cha)), NORMAL);
 			}
 		}
 		self.succ(task.id)
@@ -285,7 +376,7 @@ impl File {
 			let id = task.id;
 			task.length = meta.len();
 			self.prog.send(TaskProg::New(id, meta.len()))?;
-			self.queue(FileIn::Delete(task), NORMAL).await?;
+			self.queue(FileIn::Delete(task), NORMAL);
 			return self.succ(id);
 		}
 
@@ -304,7 +395,7 @@ impl File {
 				task.target = entry.url();
 				task.length = meta.len();
 				self.prog.send(TaskProg::New(task.id, meta.len()))?;
-				self.queue(FileIn::Delete(task.clone()), NORMAL).await?;
+				self.queue(FileIn::Delete(task.clone()), NORMAL);
 			}
 		}
 		self.succ(task.id)
@@ -315,10 +406,179 @@ impl File {
 		task.length = SizeCalculator::total(&task.target).await?;
 
 		self.prog.send(TaskProg::New(id, task.length))?;
-		self.queue(FileIn::Trash(task), LOW).await?;
+		self.queue(FileIn::Trash(task), LOW);
+		self.succ(id)
+	}
+
+	pub async fn purge(&self, task: FileInPurge) -> Result<()> {
+		let id = task.id;
+
+		self.prog.send(TaskProg::New(id, task.length))?;
+		self.queue(FileIn::Purge(task), LOW);
+		self.succ(id)
+	}
+
+	pub async fn extract(&self, mut task: FileInExtract) -> Result<()> {
+		let Some(path) = task.from.as_path() else { bail!("Only local archives can be extracted") };
+		let name = task.from.name().to_string_lossy().into_owned();
+
+		let entries = loop {
+			if let Some(entries) = Archive::list(path, &task.password).await?
+				&& Self::unlocked(path, &task.password, &entries).await?
+			{
+				break entries;
+			}
+
+			let mut rx = InputProxy::show(InputCfg::password(&name));
+			match rx.recv().await {
+				Some(Ok(password)) => task.password = password,
+				_ => bail!("The archive is encrypted, but no password was given"),
+			}
+		};
+
+		let id = task.id;
+		for entry in entries.into_iter().filter(|e| !e.dir) {
+			self.prog.send(TaskProg::New(id, entry.size))?;
+			task.entries.insert(entry.path, entry.size);
+		}
+
+		// Moving the extracted files to their destination is the last step
+		self.prog.send(TaskProg::New(id, 0))?;
+		self.queue(FileIn::Extract(task), LOW);
+		self.succ(id)
+	}
+
+	pub async fn download(&self, mut task: FileInDownload) -> Result<()> {
+		if task.to.as_path().is_none() {
+			bail!("Can only download into a local directory");
+		}
+
+		let head = Download::head(&task.url).await?;
+		let part = task.to.join(format!("{}.part", head.name));
+
+		// A `.part` file at least as large as the whole is not the same download
+		task.offset = match provider::symlink_metadata(&part).await {
+			Ok(m) if head.ranges && m.is_file() && head.length.is_none_or(|n| m.len() < n) => m.len(),
+			_ => 0,
+		};
+		task.name = head.name;
+
+		let id = task.id;
+		self.prog.send(TaskProg::New(id, head.length.unwrap_or(0)))?;
+		self.queue(FileIn::Download(task), LOW);
 		self.succ(id)
 	}
 
+	pub async fn dupes(&self, task: FileInDupes, targets: Vec<Url>) -> Result<()> {
+		let (mut files, mut dirs) = (vec![], VecDeque::new());
+		for target in targets {
+			let Some(path) = target.as_path() else {
+				bail!("Can only find duplicates among local files");
+			};
+
+			let meta = provider::symlink_metadata(&target).await?;
+			if meta.is_dir() {
+				dirs.push_back(Url::from(path));
+			} else if meta.is_file() {
+				let mtime = meta.modified().ok();
+				files.push(DupeFile { path: path.to_owned(), len: meta.len(), mtime });
+			}
+		}
+
+		// Symlinks are left out, as they're neither files nor directories here
+		while let Some(dir) = dirs.pop_front() {
+			let Ok(mut it) = provider::read_dir(&dir).await else { continue };
+			while let Ok(Some(entry)) = it.next_entry().await {
+				let Ok(meta) = entry.metadata().await else { continue };
+				let url = entry.url();
+				if meta.is_dir() {
+					dirs.push_back(url);
+				} else if meta.is_file()
+					&& let Some(path) = url.as_path()
+				{
+					let mtime = meta.modified().ok();
+					files.push(DupeFile { path: path.to_owned(), len: meta.len(), mtime });
+				}
+			}
+		}
+
+		// Each group of the same size is told apart on its own
+		for files in Dupes::by_size(files) {
+			self.prog.send(TaskProg::New(task.id, files.iter().map(|f| f.len).sum()))?;
+			self.queue(FileIn::Dupes(FileInDupes { files, ..task.clone() }), LOW);
+		}
+		self.succ(task.id)
+	}
+
+	async fn verify(from: &Url, to: &Url) -> Result<()> {
+		let (a, b) = tokio::try_join!(checksum(from), checksum(to))?;
+		if a != b {
+			bail!(
+				"Checksum mismatch, `{}` differs from its source `{}` after pasting",
+				to.display(),
+				from.display()
+			);
+		}
+		Ok(())
+	}
+
+	/// Whether the password opens the archive.
+	async fn unlocked(path: &Path, password: &str, entries: &[ArchiveEntry]) -> Result<bool> {
+		match entries.iter().find(|e| e.encrypted && !e.dir) {
+			Some(e) => Archive::test(path, password, &e.path).await,
+			None => Ok(true),
+		}
+	}
+
+	/// Moves what's extracted to `tmp` into `to`, in a directory unless it's a single root.
+	async fn settle(tmp: &Url, from: &Url, to: &Url) -> Result<Url> {
+		let name = from.name().to_string_lossy();
+		let (mut dir, mut root) = (Cow::Borrowed(tmp), Self::single_root(tmp).await?);
+
+		// A compressed tarball, e.g. `foo.tar.gz`, comes out as a single `foo.tar` at first
+		if Archive::tarball(&name)
+			&& let Some(tar) = root.take_if(|r| r.name().as_encoded_bytes().ends_with(b".tar"))
+		{
+			let inner = tmp.join(".tar");
+			provider::create_dir(&inner).await?;
+			Archive::extract(tar.as_path().unwrap(), inner.as_path().unwrap(), "", |_| ()).await?;
+
+			provider::remove_file(&tar).await?;
+			root = Self::single_root(&inner).await?;
+			dir = Cow::Owned(inner);
+		}
+
+		let (src, dest) = match root {
+			Some(root) => {
+				let dest = to.join(root.name());
+				(root, dest)
+			}
+			None => (dir.into_owned(), to.join(Archive::stem(&name))),
+		};
+
+		let dest = unique_name(dest, must_be_dir(&src)).await?;
+		provider::rename(&src, &dest).await?;
+		Ok(dest)
+	}
+
+	async fn single_root(dir: &Url) -> io::Result<Option<Url>> {
+		let mut it = provider::read_dir(dir).await?;
+		let Some(first) = it.next_entry().await? else { return Ok(None) };
+		Ok(if it.next_entry().await?.is_none() { Some(first.url()) } else { None })
+	}
+
+	/// Reports the size of the directory as it's pre-scanned, returning whether it's alive.
+	async fn prescan(&self, id: Id, dir: &Url, alive: impl Fn() -> bool) -> Result<bool> {
+		let Ok(mut it) = SizeCalculator::new(dir).await else { return Ok(alive()) };
+		while alive() {
+			match it.next().await {
+				Ok(Some(n)) => self.prog.send(TaskProg::Expect(id, n))?,
+				_ => return Ok(true),
+			}
+		}
+		Ok(false)
+	}
+
 	#[inline]
 	async fn cha(url: &Url, follow: bool) -> io::Result<Cha> {
 		let meta = provider::symlink_metadata(url).await?;
@@ -336,6 +596,13 @@ impl File {
 }
 
 impl File {
+	/// Finishes the task without doing anything, as `to` is skipped.
+	pub(crate) fn skip(&self, id: Id, to: &Url) -> Result<()> {
+		self.prog.send(TaskProg::New(id, 0))?;
+		self.log(id, format!("Skipped, as {} already exists", to.display()))?;
+		self.succ(id)
+	}
+
 	#[inline]
 	fn succ(&self, id: Id) -> Result<()> { Ok(self.prog.send(TaskProg::Succ(id))?) }
 
@@ -348,7 +615,7 @@ impl File {
 	fn log(&self, id: Id, line: String) -> Result<()> { Ok(self.prog.send(TaskProg::Log(id, line))?) }
 
 	#[inline]
-	async fn queue(&self, r#in: impl Into<TaskOp>, priority: u8) -> Result<()> {
-		self.r#macro.send(r#in.into(), priority).await.map_err(|_| anyhow!("Failed to send task"))
+	fn queue(&self, r#in: impl Into<TaskOp>, priority: u8) {
+		self.r#macro.send(r#in.into(), priority);
 	}
 }
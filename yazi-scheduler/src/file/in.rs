@@ -1,4 +1,7 @@
-use yazi_fs::cha::Cha;
+use std::{collections::HashMap, sync::Arc};
+
+use parking_lot::Mutex;
+use yazi_fs::{DupeFile, cha::Cha};
 use yazi_shared::{Id, url::Url};
 
 #[derive(Debug)]
@@ -8,6 +11,10 @@ pub enum FileIn {
 	Hardlink(FileInHardlink),
 	Delete(FileInDelete),
 	Trash(FileInTrash),
+	Purge(FileInPurge),
+	Extract(FileInExtract),
+	Download(FileInDownload),
+	Dupes(FileInDupes),
 }
 
 impl FileIn {
@@ -18,6 +25,10 @@ impl FileIn {
 			Self::Hardlink(r#in) => r#in.id,
 			Self::Delete(r#in) => r#in.id,
 			Self::Trash(r#in) => r#in.id,
+			Self::Purge(r#in) => r#in.id,
+			Self::Extract(r#in) => r#in.id,
+			Self::Download(r#in) => r#in.id,
+			Self::Dupes(r#in) => r#in.id,
 		}
 	}
 }
@@ -31,6 +42,7 @@ pub struct FileInPaste {
 	pub cha:    Option<Cha>,
 	pub cut:    bool,
 	pub follow: bool,
+	pub verify: bool,
 	pub retry:  u8,
 }
 
@@ -43,6 +55,7 @@ impl FileInPaste {
 			cha: Some(cha),
 			cut: self.cut,
 			follow: self.follow,
+			verify: self.verify,
 			retry: self.retry,
 		}
 	}
@@ -105,3 +118,39 @@ pub struct FileInTrash {
 	pub target: Url,
 	pub length: u64,
 }
+
+// --- Purge
+#[derive(Clone, Debug)]
+pub struct FileInPurge {
+	pub id:     Id,
+	pub ids:    Vec<String>,
+	pub length: u64,
+}
+
+// --- Extract
+#[derive(Clone, Debug)]
+pub struct FileInExtract {
+	pub id:       Id,
+	pub from:     Url,
+	pub to:       Url,
+	pub password: String,
+	pub entries:  HashMap<String, u64>, // Path of the files in the archive -> size
+}
+
+// --- Download
+#[derive(Clone, Debug)]
+pub struct FileInDownload {
+	pub id:     Id,
+	pub url:    String,
+	pub to:     Url,
+	pub name:   String,
+	pub offset: u64, // Size of the `.part` file to resume from
+}
+
+// --- Dupes
+#[derive(Clone, Debug)]
+pub struct FileInDupes {
+	pub id:    Id,
+	pub files: Vec<DupeFile>, // Of the same size, to be told apart by their content
+	pub found: Arc<Mutex<Vec<Vec<DupeFile>>>>,
+}
@@ -1,3 +1,3 @@
 #![allow(clippy::module_inception)]
 
-yazi_macro::mod_flat!(file out r#in);
+yazi_macro::mod_flat!(archive conflict download file out r#in recycle);
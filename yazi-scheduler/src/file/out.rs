@@ -5,6 +5,10 @@ pub enum FileOut {
 	Hardlink(FileOutHardlink),
 	Delete(FileOutDelete),
 	Trash(FileOutTrash),
+	Purge(FileOutPurge),
+	Extract(FileOutExtract),
+	Download(FileOutDownload),
+	Dupes(FileOutDupes),
 }
 
 #[derive(Debug)]
@@ -21,3 +25,15 @@ pub struct FileOutDelete;
 
 #[derive(Debug)]
 pub struct FileOutTrash;
+
+#[derive(Debug)]
+pub struct FileOutPurge;
+
+#[derive(Debug)]
+pub struct FileOutExtract;
+
+#[derive(Debug)]
+pub struct FileOutDownload;
+
+#[derive(Debug)]
+pub struct FileOutDupes;
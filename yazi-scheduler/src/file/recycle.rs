@@ -0,0 +1,399 @@
+use std::{collections::{HashMap, HashSet}, path::{Path, PathBuf}, sync::LazyLock, time::{Duration, SystemTime, UNIX_EPOCH}};
+
+use anyhow::Result;
+use tokio::sync::mpsc;
+use tracing::error;
+use yazi_config::{YAZI, popup::ConfirmCfg};
+use yazi_dds::{ID, Trashed, ember::BodyTrashedItem};
+use yazi_fs::{File, TRASH_USAGE, TrashUsage};
+use yazi_macro::{emit, relay};
+use yazi_proxy::{ConfirmProxy, TasksProxy};
+use yazi_shared::url::Url;
+
+static TX: LazyLock<mpsc::UnboundedSender<PathBuf>> = LazyLock::new(|| {
+	let (tx, mut rx) = mpsc::unbounded_channel();
+	tokio::spawn(async move {
+		while let Some(first) = rx.recv().await {
+			// Look them up in batches, as listing the trash isn't cheap
+			tokio::time::sleep(Duration::from_millis(500)).await;
+			let mut paths = HashSet::from([first]);
+			while let Ok(path) = rx.try_recv() {
+				paths.insert(path);
+			}
+
+			match tokio::task::spawn_blocking(move || Recycle::lookup(paths)).await {
+				Ok(Ok(items)) if items.is_empty() => {}
+				Ok(Ok(items)) => Trashed::record(items).unwrap_or_else(|e| error!("{e}")),
+				Ok(Err(e)) => error!("Could not look up the trashed files: {e}"),
+				Err(e) => error!("{e}"),
+			}
+		}
+	});
+	tx
+});
+
+static REFRESH: LazyLock<mpsc::UnboundedSender<()>> = LazyLock::new(|| {
+	let (tx, mut rx) = mpsc::unbounded_channel();
+	tokio::spawn(async move {
+		// The items already asked about, so declining isn't asked again
+		let mut asked = HashSet::new();
+		while rx.recv().await.is_some() {
+			// Walking the whole trash isn't cheap, so wait for things to settle down
+			tokio::time::sleep(Duration::from_secs(1)).await;
+			while rx.try_recv().is_ok() {}
+
+			match tokio::task::spawn_blocking(Recycle::scan).await {
+				Ok(Ok(items)) => Recycle::enforce(items, &mut asked).await,
+				Ok(Err(e)) => error!("Could not measure the trash: {e}"),
+				Err(e) => error!("{e}"),
+			}
+		}
+	});
+	tx
+});
+
+/// Keeps track of the files trashed by Yazi, so they can be restored later.
+pub struct Recycle;
+
+impl Recycle {
+	/// Whether the trash backend of the platform can list and restore items.
+	pub const SUPPORTED: bool = cfg!(any(
+		windows,
+		all(unix, not(target_os = "macos"), not(target_os = "ios"), not(target_os = "android"))
+	));
+
+	/// Adds a file that has just been trashed to the index.
+	pub(crate) fn record(target: &Url) {
+		if !Self::SUPPORTED {
+			return;
+		}
+
+		if let Some(path) = Self::original(target) {
+			TX.send(path).ok();
+		}
+	}
+
+	/// The most recently trashed item in the index for a file, if any.
+	pub fn find(target: &Url) -> Option<BodyTrashedItem> {
+		let path = Self::original(target)?;
+		Trashed::list()
+			.into_iter()
+			.filter(|t| t.url.as_path() == Some(path.as_path()))
+			.max_by_key(|t| t.at)
+	}
+
+	/// Lists the trash for `trash://`, each file linked to where it was, the latest first.
+	pub async fn read(url: &Url) -> Vec<File> {
+		let Ok(Ok(mut items)) = tokio::task::spawn_blocking(Self::list).await else { return vec![] };
+		items.sort_unstable_by(|a, b| b.at.cmp(&a.at));
+
+		let mut files = Vec::with_capacity(items.len());
+		for item in items {
+			let Some(stored) = Self::stored(&item.id) else { continue };
+			let Ok(rel) = stored.strip_prefix(url.loc.base()) else { continue };
+			let Ok(mut file) = File::new(url.join(rel)).await else { continue };
+
+			// Shown as where it was, which `follow` reveals
+			file.link_to = Some(Url::from(item.path));
+			files.push(file);
+		}
+		files
+	}
+
+	/// The item in the trash a `trash://` URL lists, to restore it to where it was.
+	pub async fn locate(url: &Url) -> Option<BodyTrashedItem> {
+		let id = Self::id_at(url)?;
+		let items = tokio::task::spawn_blocking(Self::list).await.ok()?.ok()?;
+
+		let item = items.into_iter().find(|i| i.id == id)?;
+		Some(BodyTrashedItem { url: Url::from(item.path), id: item.id, at: item.at, sender: *ID })
+	}
+
+	/// The ID of the item in the trash a `trash://` URL lists, to purge it.
+	pub fn id_at(url: &Url) -> Option<String> {
+		Self::id_of(url.as_path().filter(|_| url.is_trash())?)
+	}
+
+	/// Forgets the files that are no longer in the trash.
+	pub async fn prune() -> Result<()> {
+		let items = tokio::task::spawn_blocking(Self::list).await??;
+		let ids: HashSet<_> = items.into_iter().map(|i| i.id).collect();
+
+		Trashed::retain(|t| ids.contains(&t.id))
+	}
+
+	/// Restores a file to where it was, creating the missing parent directories.
+	pub async fn restore(item: BodyTrashedItem, overwrite: bool) -> Result<()> {
+		tokio::task::spawn_blocking(move || Self::restore_sync(item, overwrite)).await??;
+		Self::refresh();
+		Ok(())
+	}
+
+	/// Measures the trash again, and asks to purge the items that exceed `[trash]`.
+	pub(crate) fn refresh() {
+		// Relist `trash://` if it's open
+		emit!(Call(relay!(mgr:refresh)));
+		if Self::SUPPORTED && YAZI.trash.measured() {
+			REFRESH.send(()).ok();
+		}
+	}
+
+	/// Permanently deletes the items from the trash.
+	pub(crate) async fn purge(ids: Vec<String>) -> Result<()> {
+		tokio::task::spawn_blocking(move || Self::purge_sync(ids.into_iter().collect())).await?
+	}
+
+	async fn enforce(items: Vec<Item>, asked: &mut HashSet<String>) {
+		let usage = TrashUsage { items: items.len(), size: items.iter().map(|i| i.size).sum() };
+		if TRASH_USAGE.write().replace(usage) != Some(usage) {
+			emit!(Render);
+		}
+
+		let (cfg, now) = (&YAZI.trash, SystemTime::now().duration_since(UNIX_EPOCH));
+		let now = now.map_or(0, |d| d.as_secs() as i64);
+		let expired = Self::expired(items, now, cfg.max_age as i64 * 86400, cfg.max_size);
+		if expired.iter().all(|i| asked.contains(&i.id)) {
+			return;
+		}
+
+		asked.extend(expired.iter().map(|i| i.id.clone()));
+		let names = expired.iter().map(|i| i.path.to_string_lossy().into_owned()).collect();
+		if ConfirmProxy::show(ConfirmCfg::purge(names)).await {
+			let size = expired.iter().map(|i| i.size).sum();
+			TasksProxy::purge_trash(expired.into_iter().map(|i| i.id).collect(), size);
+		}
+	}
+
+	/// The items exceeding the retention policy, the oldest first.
+	fn expired(mut items: Vec<Item>, now: i64, max_age: i64, max_size: u64) -> Vec<Item> {
+		items.sort_unstable_by(|a, b| a.at.cmp(&b.at).then_with(|| a.id.cmp(&b.id)));
+
+		let mut total: u64 = items.iter().map(|i| i.size).sum();
+		let n = items
+			.iter()
+			.take_while(|i| {
+				let old = max_age > 0 && now - i.at > max_age;
+				let over = max_size > 0 && total > max_size;
+				total -= i.size;
+				old || over
+			})
+			.count();
+
+		items.truncate(n);
+		items
+	}
+
+	#[cfg(any(
+		windows,
+		all(unix, not(target_os = "macos"), not(target_os = "ios"), not(target_os = "android"))
+	))]
+	fn restore_sync(item: BodyTrashedItem, overwrite: bool) -> Result<()> {
+		let found =
+			trash::os_limited::list()?.into_iter().find(|i| i.id.to_string_lossy() == item.id.as_str());
+
+		let Some(found) = found else {
+			Trashed::retain(|t| t.id != item.id)?;
+			anyhow::bail!("`{}` is no longer in the trash", item.url.display());
+		};
+
+		let path = found.original_path();
+		if std::fs::symlink_metadata(&path).is_ok() {
+			if !overwrite {
+				anyhow::bail!("`{}` already exists", path.display());
+			}
+			trash::delete(&path)?;
+			Trashed::record(Self::lookup(HashSet::from([path]))?)?;
+		}
+
+		std::fs::create_dir_all(&found.original_parent)?;
+		trash::os_limited::restore_all([found])?;
+		Trashed::retain(|t| t.id != item.id)
+	}
+
+	#[cfg(not(any(
+		windows,
+		all(unix, not(target_os = "macos"), not(target_os = "ios"), not(target_os = "android"))
+	)))]
+	fn restore_sync(_: BodyTrashedItem, _: bool) -> Result<()> {
+		anyhow::bail!("Restoring from the trash is not supported on this platform")
+	}
+
+	#[cfg(any(
+		windows,
+		all(unix, not(target_os = "macos"), not(target_os = "ios"), not(target_os = "android"))
+	))]
+	fn purge_sync(ids: HashSet<String>) -> Result<()> {
+		let found: Vec<_> = trash::os_limited::list()?
+			.into_iter()
+			.filter(|i| ids.contains(&*i.id.to_string_lossy()))
+			.collect();
+
+		trash::os_limited::purge_all(found)?;
+		Trashed::retain(|t| !ids.contains(&t.id))
+	}
+
+	#[cfg(not(any(
+		windows,
+		all(unix, not(target_os = "macos"), not(target_os = "ios"), not(target_os = "android"))
+	)))]
+	fn purge_sync(_: HashSet<String>) -> Result<()> {
+		anyhow::bail!("Purging the trash is not supported on this platform")
+	}
+
+	/// The path the trash backend remembers for a file that's been trashed.
+	fn original(target: &Url) -> Option<PathBuf> {
+		// The trash backend remembers the canonical path, but since the file is gone,
+		// only its parent can be canonicalized
+		let path = target.as_path()?;
+		let (parent, name) = (path.parent()?, path.file_name()?);
+		let parent = std::fs::canonicalize(parent).unwrap_or_else(|_| parent.to_owned());
+
+		Some(parent.join(name))
+	}
+
+	/// Finds the items in the trash for the paths, the latest one of each.
+	fn lookup(paths: HashSet<PathBuf>) -> Result<Vec<BodyTrashedItem>> {
+		let mut found: HashMap<PathBuf, Item> = HashMap::new();
+		for item in Self::list()? {
+			if !paths.contains(&item.path) {
+				continue;
+			}
+			match found.get(&item.path) {
+				Some(old) if old.at >= item.at => {}
+				_ => _ = found.insert(item.path.clone(), item),
+			}
+		}
+
+		Ok(
+			found
+				.into_values()
+				.map(|i| BodyTrashedItem { url: Url::from(i.path), id: i.id, at: i.at, sender: *ID })
+				.collect(),
+		)
+	}
+
+	#[cfg(any(
+		windows,
+		all(unix, not(target_os = "macos"), not(target_os = "ios"), not(target_os = "android"))
+	))]
+	fn list() -> Result<Vec<Item>> {
+		Ok(
+			trash::os_limited::list()?
+				.into_iter()
+				.map(|i| Item {
+					id:   i.id.to_string_lossy().into_owned(),
+					path: i.original_path(),
+					at:   i.time_deleted,
+					size: 0,
+				})
+				.collect(),
+		)
+	}
+
+	#[cfg(not(any(
+		windows,
+		all(unix, not(target_os = "macos"), not(target_os = "ios"), not(target_os = "android"))
+	)))]
+	fn list() -> Result<Vec<Item>> { Ok(vec![]) }
+
+	/// Lists the items in the trash along with their sizes.
+	fn scan() -> Result<Vec<Item>> {
+		let mut items = Self::list()?;
+		for item in &mut items {
+			item.size = Self::stored(&item.id).map_or(0, |p| Self::measure(&p));
+		}
+		Ok(items)
+	}
+
+	/// Where the trash backend stores the item, told by its ID.
+	#[cfg(all(unix, not(target_os = "macos"), not(target_os = "ios"), not(target_os = "android")))]
+	fn stored(id: &str) -> Option<PathBuf> {
+		// The ID is the path of `$trash/info/<name>.trashinfo`, for `$trash/files/<name>`
+		let info = Path::new(id);
+		Some(info.parent()?.parent()?.join("files").join(info.file_stem()?))
+	}
+
+	/// Where the trash backend stores the item, told by its ID.
+	#[cfg(windows)]
+	fn stored(id: &str) -> Option<PathBuf> {
+		// The ID is the parsing name, which is the path in `$Recycle.Bin`
+		Some(PathBuf::from(id))
+	}
+
+	#[cfg(not(any(
+		windows,
+		all(unix, not(target_os = "macos"), not(target_os = "ios"), not(target_os = "android"))
+	)))]
+	fn stored(_: &str) -> Option<PathBuf> { None }
+
+	/// The ID of the item the trash backend stores at the path, see [`Self::stored`].
+	#[cfg(all(unix, not(target_os = "macos"), not(target_os = "ios"), not(target_os = "android")))]
+	fn id_of(stored: &Path) -> Option<String> {
+		let (files, name) = (stored.parent()?, stored.file_name()?);
+		let mut info = files.parent()?.join("info").join(name).into_os_string();
+		info.push(".trashinfo");
+		Some(info.to_string_lossy().into_owned())
+	}
+
+	/// The ID of the item the trash backend stores at the path, see [`Self::stored`].
+	#[cfg(windows)]
+	fn id_of(stored: &Path) -> Option<String> { Some(stored.to_string_lossy().into_owned()) }
+
+	#[cfg(not(any(
+		windows,
+		all(unix, not(target_os = "macos"), not(target_os = "ios"), not(target_os = "android"))
+	)))]
+	fn id_of(_: &Path) -> Option<String> { None }
+
+	fn measure(path: &Path) -> u64 {
+		let Ok(meta) = std::fs::symlink_metadata(path) else { return 0 };
+		if !meta.is_dir() {
+			return meta.len();
+		}
+
+		let Ok(it) = std::fs::read_dir(path) else { return 0 };
+		it.flatten().map(|e| Self::measure(&e.path())).sum()
+	}
+}
+
+// --- Item
+struct Item {
+	id:   String,
+	path: PathBuf,
+	at:   i64,
+	size: u64,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_expired() {
+		let item = |id: &str, at: i64, size: u64| Item { id: id.to_owned(), path: id.into(), at, size };
+		let ids = |items: Vec<Item>| items.into_iter().map(|i| i.id).collect::<Vec<_>>();
+		let items = || vec![item("c", 300, 10), item("a", 100, 30), item("b", 200, 20)];
+
+		assert!(Recycle::expired(items(), 400, 0, 0).is_empty());
+
+		// Trashed more than 150 seconds ago
+		assert_eq!(ids(Recycle::expired(items(), 400, 150, 0)), ["a", "b"]);
+
+		// The oldest ones until the rest fit in 25 bytes
+		assert_eq!(ids(Recycle::expired(items(), 400, 0, 25)), ["a", "b"]);
+		assert_eq!(ids(Recycle::expired(items(), 400, 0, 30)), ["a"]);
+
+		// Either of them
+		assert_eq!(ids(Recycle::expired(items(), 400, 250, 60)), ["a"]);
+		assert_eq!(ids(Recycle::expired(items(), 400, 250, 5)), ["a", "b", "c"]);
+	}
+
+	#[test]
+	#[cfg(all(unix, not(target_os = "macos"), not(target_os = "ios"), not(target_os = "android")))]
+	fn test_id_of() {
+		let id = "/home/a/.local/share/Trash/info/b.txt.trashinfo";
+		let stored = Recycle::stored(id).unwrap();
+		assert_eq!(stored, Path::new("/home/a/.local/share/Trash/files/b.txt"));
+		assert_eq!(Recycle::id_of(&stored).as_deref(), Some(id));
+	}
+}
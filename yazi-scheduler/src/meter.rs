@@ -0,0 +1,61 @@
+use std::{collections::VecDeque, time::{Duration, Instant}};
+
+/// How many speeds are kept for the graph, one per sample.
+const SPEEDS: usize = 32;
+
+/// Measures the speed of the tasks, averaged over the last few seconds.
+#[derive(Default)]
+pub(super) struct Meter {
+	samples: VecDeque<(Instant, u64)>,
+	speeds:  [u64; SPEEDS],
+}
+
+impl Meter {
+	const WINDOW: Duration = Duration::from_secs(3);
+
+	/// Takes a sample of the bytes processed so far, returning the speed in bytes per second.
+	pub(super) fn sample(&mut self, now: Instant, processed: u64) -> u64 {
+		self.samples.push_back((now, processed));
+		while self.samples.len() > 2 && now - self.samples[1].0 >= Self::WINDOW {
+			self.samples.pop_front();
+		}
+
+		let (since, base) = self.samples[0];
+		let secs = (now - since).as_secs_f64();
+		let speed = if secs > 0.0 { (processed.saturating_sub(base) as f64 / secs) as u64 } else { 0 };
+
+		self.speeds.rotate_left(1);
+		self.speeds[SPEEDS - 1] = speed;
+		speed
+	}
+
+	/// The speeds of the samples taken, the oldest first.
+	#[inline]
+	pub(super) fn speeds(&self) -> [u64; SPEEDS] { self.speeds }
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_sample() {
+		let (mut meter, start) = (Meter::default(), Instant::now());
+		let at = |ms: u64| start + Duration::from_millis(ms);
+
+		assert_eq!(meter.sample(at(0), 0), 0);
+		assert_eq!(meter.sample(at(500), 1000), 2000);
+		assert_eq!(meter.sample(at(1000), 2000), 2000);
+
+		// Only the last few seconds count
+		for i in 3..=8 {
+			meter.sample(at(i * 500), 2000);
+		}
+		assert_eq!(meter.sample(at(4500), 2000), 0);
+		assert_eq!(meter.sample(at(5000), 5000), 1000);
+
+		let speeds = meter.speeds();
+		assert_eq!(speeds[SPEEDS - 3..], [0, 0, 1000]);
+		assert_eq!(speeds[SPEEDS - 11..SPEEDS - 8], [0, 2000, 2000]);
+	}
+}
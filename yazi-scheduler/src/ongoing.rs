@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, time::Instant};
 
 use futures::future::BoxFuture;
 use yazi_config::YAZI;
@@ -6,12 +6,17 @@ use yazi_parser::app::TasksProgress;
 use yazi_shared::{Id, Ids};
 
 use super::{Task, TaskStage};
-use crate::{Hooks, TaskKind};
+use crate::{Hooks, Meter, TaskKind};
 
 #[derive(Default)]
 pub struct Ongoing {
 	pub(super) hooks: Hooks,
 	pub(super) all:   HashMap<Id, Task>,
+
+	// The bytes processed by all the tasks so far, including the finished ones,
+	// to measure the speed with
+	pub(super) processed: u64,
+	meter:                Meter,
 }
 
 impl Ongoing {
@@ -44,18 +49,52 @@ impl Ongoing {
 	#[inline]
 	pub fn exists(&self, id: Id) -> bool { self.all.contains_key(&id) }
 
-	#[inline]
-	pub fn values(&self) -> Box<dyn Iterator<Item = &Task> + '_> {
-		if YAZI.tasks.suppress_preload {
-			Box::new(self.all.values().filter(|t| t.kind != TaskKind::Preload))
-		} else {
-			Box::new(self.all.values())
-		}
+	/// The tasks in the order they're listed, without the preloads if they're suppressed.
+	pub fn values(&self) -> impl Iterator<Item = &Task> {
+		let suppress = YAZI.tasks.suppress_preload;
+		let mut tasks: Vec<_> =
+			self.all.values().filter(|t| !suppress || t.kind != TaskKind::Preload).collect();
+
+		tasks.sort_unstable_by_key(|t| t.rank);
+		tasks.into_iter()
 	}
 
 	#[inline]
 	pub fn is_empty(&self) -> bool { self.len() == 0 }
 
+	/// Moves the task `offset` places up or down the list.
+	pub fn reorder(&mut self, id: Id, offset: isize) -> bool {
+		let mut ids: Vec<_> = self.values().map(|t| t.id).collect();
+		let ranks: Vec<_> = self.values().map(|t| t.rank).collect();
+
+		let Some(from) = ids.iter().position(|&i| i == id) else { return false };
+		let to = from.saturating_add_signed(offset).min(ids.len() - 1);
+		if from == to {
+			return false;
+		}
+
+		// The ranks are handed out again, so the ones in between keep their order
+		let id = ids.remove(from);
+		ids.insert(to, id);
+		for (id, rank) in ids.into_iter().zip(ranks) {
+			if let Some(task) = self.all.get_mut(&id) {
+				task.rank = rank;
+			}
+		}
+		true
+	}
+
+	/// Whether the operations of the task are held back.
+	pub(super) fn held(&self, id: Id) -> bool {
+		let Some(task) = self.all.get(&id) else { return false };
+		if task.pause.paused() || !task.due() {
+			return true;
+		}
+
+		task.schedule.is_some_and(|s| s.queue)
+			&& self.all.values().any(|t| t.schedule.is_some() && t.rank < task.rank && t.busy())
+	}
+
 	pub fn try_remove(&mut self, id: Id, stage: TaskStage) -> Option<BoxFuture<'static, ()>> {
 		if let Some(task) = self.get_mut(id) {
 			if stage > task.stage {
@@ -75,14 +114,18 @@ impl Ongoing {
 				TaskStage::Hooked => {}
 			}
 
-			self.all.remove(&id);
+			if let Some(task) = self.all.remove(&id) {
+				task.feed();
+			}
 		}
 		None
 	}
 
-	pub fn progress(&self) -> TasksProgress {
+	/// The progress of all the tasks, which also takes a sample of the speed.
+	pub fn progress(&mut self) -> TasksProgress {
 		let mut progress = TasksProgress::default();
 		if self.is_empty() {
+			self.meter = Meter::default();
 			return progress;
 		}
 
@@ -91,9 +134,15 @@ impl Ongoing {
 			progress.succ += task.succ;
 			progress.fail += task.fail;
 
-			progress.found += task.found;
+			progress.found += task.size();
 			progress.processed += task.processed;
 		}
+
+		progress.speed = self.meter.sample(Instant::now(), self.processed);
+		progress.speeds = self.meter.speeds();
+
+		let left = progress.found.saturating_sub(progress.processed);
+		progress.eta = (progress.speed > 0 && left > 0).then(|| left.div_ceil(progress.speed));
 		progress
 	}
 }
@@ -1,23 +1,20 @@
-use std::fmt::Display;
+use std::{fmt::Display, sync::Arc};
 
-use anyhow::{Result, anyhow};
+use anyhow::Result;
 use tokio::sync::mpsc;
 use yazi_plugin::isolate;
 use yazi_shared::Id;
 
 use super::{PluginIn, PluginInEntry};
-use crate::{HIGH, TaskOp, TaskProg};
+use crate::{HIGH, Queue, TaskOp, TaskProg};
 
 pub struct Plugin {
-	r#macro: async_priority_channel::Sender<TaskOp, u8>,
+	r#macro: Arc<Queue>,
 	prog:    mpsc::UnboundedSender<TaskProg>,
 }
 
 impl Plugin {
-	pub fn new(
-		r#macro: async_priority_channel::Sender<TaskOp, u8>,
-		prog: mpsc::UnboundedSender<TaskProg>,
-	) -> Self {
+	pub fn new(r#macro: Arc<Queue>, prog: mpsc::UnboundedSender<TaskProg>) -> Self {
 		Self { r#macro, prog }
 	}
 
@@ -46,7 +43,7 @@ impl Plugin {
 		let id = task.id;
 
 		self.prog.send(TaskProg::New(id, 0))?;
-		self.queue(PluginIn::Entry(task), HIGH)?;
+		self.queue(PluginIn::Entry(task), HIGH);
 		self.succ(id)
 	}
 }
@@ -61,7 +58,7 @@ impl Plugin {
 	}
 
 	#[inline]
-	fn queue(&self, r#in: impl Into<TaskOp>, priority: u8) -> Result<()> {
-		self.r#macro.try_send(r#in.into(), priority).map_err(|_| anyhow!("Failed to send task"))
+	fn queue(&self, r#in: impl Into<TaskOp>, priority: u8) {
+		self.r#macro.send(r#in.into(), priority);
 	}
 }
@@ -1,5 +1,6 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::SystemTime};
 
+use tokio_util::sync::CancellationToken;
 use yazi_config::plugin::{Fetcher, Preloader};
 use yazi_shared::{Id, Throttle, url::Url};
 
@@ -38,5 +39,7 @@ pub struct PreworkInLoad {
 pub struct PreworkInSize {
 	pub id:       Id,
 	pub target:   Url,
-	pub throttle: Arc<Throttle<(Url, u64)>>,
+	pub mtime:    Option<SystemTime>,
+	pub throttle: Arc<Throttle<(Url, Option<u64>)>>,
+	pub ct:       CancellationToken,
 }
@@ -1,21 +1,21 @@
-use std::{collections::{HashMap, HashSet}, num::NonZeroUsize};
+use std::{collections::{HashMap, HashSet}, num::NonZeroUsize, sync::Arc};
 
-use anyhow::{Result, anyhow};
+use anyhow::Result;
 use lru::LruCache;
 use parking_lot::{Mutex, RwLock};
-use tokio::sync::mpsc;
+use tokio::{select, sync::mpsc};
 use tokio_util::sync::CancellationToken;
 use tracing::error;
 use yazi_config::Priority;
-use yazi_fs::{FilesOp, SizeCalculator};
+use yazi_fs::{FilesOp, SIZES, SizeCalculator};
 use yazi_plugin::isolate;
 use yazi_shared::{Id, event::CmdCow, url::Url};
 
 use super::{PreworkIn, PreworkInFetch, PreworkInLoad, PreworkInSize};
-use crate::{HIGH, NORMAL, TaskOp, TaskProg};
+use crate::{HIGH, NORMAL, Queue, TaskOp, TaskProg};
 
 pub struct Prework {
-	r#macro: async_priority_channel::Sender<TaskOp, u8>,
+	r#macro: Arc<Queue>,
 	prog:    mpsc::UnboundedSender<TaskProg>,
 
 	pub loaded:  Mutex<LruCache<u64, u32>>,
@@ -24,10 +24,7 @@ pub struct Prework {
 }
 
 impl Prework {
-	pub fn new(
-		r#macro: async_priority_channel::Sender<TaskOp, u8>,
-		prog: mpsc::UnboundedSender<TaskProg>,
-	) -> Self {
+	pub fn new(r#macro: Arc<Queue>, prog: mpsc::UnboundedSender<TaskProg>) -> Self {
 		Self {
 			r#macro,
 			prog,
@@ -42,7 +39,8 @@ impl Prework {
 		match r#in {
 			PreworkIn::Fetch(task) => {
 				let hashes: Vec<_> = task.targets.iter().map(|f| f.hash_u64()).collect();
-				let result = isolate::fetch(CmdCow::from(&task.plugin.run), task.targets).await;
+				let result =
+					isolate::fetch(CmdCow::from(&task.plugin.run), &task.plugin.hint, task.targets).await;
 				if let Err(e) = result {
 					self.fail(task.id, format!("Failed to run fetcher `{}`:\n{e}", task.plugin.run.name))?;
 					return Err(e.into());
@@ -64,7 +62,7 @@ impl Prework {
 				}
 
 				let hash = task.target.hash_u64();
-				let result = isolate::preload(&task.plugin.run, task.target, ct).await;
+				let result = isolate::preload(&task.plugin.run, &task.plugin.hint, task.target, ct).await;
 				if let Err(e) = result {
 					self
 						.fail(task.id, format!("Failed to run preloader `{}`:\n{e}", task.plugin.run.name))?;
@@ -80,7 +78,11 @@ impl Prework {
 				}
 			}
 			PreworkIn::Size(task) => {
-				let length = SizeCalculator::total(&task.target).await.unwrap_or(0);
+				let length = self.calculate(&task).await;
+				if let Some(n) = length {
+					SIZES.set(task.target.clone(), task.mtime, n);
+				}
+
 				task.throttle.done((task.target, length), |buf| {
 					{
 						let mut loading = self.sizing.write();
@@ -90,11 +92,12 @@ impl Prework {
 					}
 
 					let parent = buf[0].0.parent_url().unwrap();
-					FilesOp::Size(
-						parent,
-						HashMap::from_iter(buf.into_iter().map(|(u, s)| (u.urn_owned(), s))),
-					)
-					.emit();
+					let sizes: HashMap<_, _> =
+						buf.into_iter().filter_map(|(u, s)| Some((u.urn_owned(), s?))).collect();
+					if !sizes.is_empty() {
+						FilesOp::Size(parent, sizes).emit();
+						tokio::task::spawn_blocking(|| SIZES.save().ok());
+					}
 				});
 			}
 		}
@@ -106,8 +109,8 @@ impl Prework {
 		self.prog.send(TaskProg::New(id, 0))?;
 
 		match task.plugin.prio {
-			Priority::Low => self.queue(PreworkIn::Fetch(task), NORMAL).await?,
-			Priority::Normal => self.queue(PreworkIn::Fetch(task), HIGH).await?,
+			Priority::Low => self.queue(PreworkIn::Fetch(task), NORMAL),
+			Priority::Normal => self.queue(PreworkIn::Fetch(task), HIGH),
 			Priority::High => self.work(PreworkIn::Fetch(task)).await?,
 		}
 		self.succ(id)
@@ -118,8 +121,8 @@ impl Prework {
 		self.prog.send(TaskProg::New(id, 0))?;
 
 		match task.plugin.prio {
-			Priority::Low => self.queue(PreworkIn::Load(task), NORMAL).await?,
-			Priority::Normal => self.queue(PreworkIn::Load(task), HIGH).await?,
+			Priority::Low => self.queue(PreworkIn::Load(task), NORMAL),
+			Priority::Normal => self.queue(PreworkIn::Load(task), HIGH),
 			Priority::High => self.work(PreworkIn::Load(task)).await?,
 		}
 		self.succ(id)
@@ -132,6 +135,26 @@ impl Prework {
 		self.work(PreworkIn::Size(task)).await?;
 		self.succ(id)
 	}
+
+	/// Calculates the size chunk by chunk, or `None` if the task is canceled.
+	async fn calculate(&self, task: &PreworkInSize) -> Option<u64> {
+		let Ok(mut it) = SizeCalculator::new(&task.target).await else { return Some(0) };
+
+		let mut total = 0;
+		loop {
+			select! {
+				_ = task.ct.cancelled() => return None,
+				r = it.next() => match r {
+					Ok(Some(n)) => {
+						total += n;
+						self.prog.send(TaskProg::Scan(task.id, n)).ok();
+					}
+					Ok(None) => return Some(total),
+					Err(_) => return Some(0),
+				},
+			}
+		}
+	}
 }
 
 impl Prework {
@@ -144,7 +167,7 @@ impl Prework {
 	}
 
 	#[inline]
-	async fn queue(&self, r#in: impl Into<TaskOp>, priority: u8) -> Result<()> {
-		self.r#macro.send(r#in.into(), priority).await.map_err(|_| anyhow!("Failed to send task"))
+	fn queue(&self, r#in: impl Into<TaskOp>, priority: u8) {
+		self.r#macro.send(r#in.into(), priority);
 	}
 }
@@ -12,11 +12,19 @@ pub struct ProcessInBlock {
 	pub cwd:  Url,
 	pub cmd:  OsString,
 	pub args: Vec<OsString>,
+	pub line: Option<u32>,
 }
 
 impl From<ProcessInBlock> for ShellOpt {
 	fn from(r#in: ProcessInBlock) -> Self {
-		Self { cwd: r#in.cwd, cmd: r#in.cmd, args: r#in.args, piped: false, orphan: false }
+		Self {
+			cwd:    r#in.cwd,
+			cmd:    r#in.cmd,
+			args:   r#in.args,
+			line:   r#in.line,
+			piped:  false,
+			orphan: false,
+		}
 	}
 }
 
@@ -27,11 +35,19 @@ pub struct ProcessInOrphan {
 	pub cwd:  Url,
 	pub cmd:  OsString,
 	pub args: Vec<OsString>,
+	pub line: Option<u32>,
 }
 
 impl From<ProcessInOrphan> for ShellOpt {
 	fn from(r#in: ProcessInOrphan) -> Self {
-		Self { cwd: r#in.cwd, cmd: r#in.cmd, args: r#in.args, piped: false, orphan: true }
+		Self {
+			cwd:    r#in.cwd,
+			cmd:    r#in.cmd,
+			args:   r#in.args,
+			line:   r#in.line,
+			piped:  false,
+			orphan: true,
+		}
 	}
 }
 
@@ -42,11 +58,19 @@ pub struct ProcessInBg {
 	pub cwd:    Url,
 	pub cmd:    OsString,
 	pub args:   Vec<OsString>,
+	pub line:   Option<u32>,
 	pub cancel: mpsc::Receiver<()>,
 }
 
 impl From<ProcessInBg> for ShellOpt {
 	fn from(r#in: ProcessInBg) -> Self {
-		Self { cwd: r#in.cwd, cmd: r#in.cmd, args: r#in.args, piped: true, orphan: false }
+		Self {
+			cwd:    r#in.cwd,
+			cmd:    r#in.cmd,
+			args:   r#in.args,
+			line:   r#in.line,
+			piped:  true,
+			orphan: false,
+		}
 	}
 }
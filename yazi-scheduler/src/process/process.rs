@@ -58,6 +58,7 @@ impl Process {
 			cwd:    task.cwd,
 			cmd:    task.cmd,
 			args:   task.args,
+			line:   task.line,
 			piped:  true,
 			orphan: false,
 		})?;
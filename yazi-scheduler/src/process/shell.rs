@@ -8,6 +8,7 @@ pub struct ShellOpt {
 	pub cwd:    Url,
 	pub cmd:    OsString,
 	pub args:   Vec<OsString>,
+	pub line:   Option<u32>,
 	pub piped:  bool,
 	pub orphan: bool,
 }
@@ -35,6 +36,7 @@ pub fn shell(opt: ShellOpt) -> Result<Child> {
 			.stderr(opt.stdio())
 			.arg(opt.cmd)
 			.args(opt.args)
+			.envs(opt.line.map(|n| ("YAZI_LINE", n.to_string())))
 			.current_dir(opt.cwd)
 			.kill_on_drop(!opt.orphan)
 			.pre_exec(move || {
@@ -52,6 +54,7 @@ pub fn shell(opt: ShellOpt) -> Result<Child> {
 			Command::new("cmd.exe")
 				.raw_arg("/C")
 				.raw_arg(parser::parse(&opt.cmd, &opt.args))
+				.envs(opt.line.map(|n| ("YAZI_LINE", n.to_string())))
 				.stdin(opt.stdio())
 				.stdout(opt.stdio())
 				.stderr(opt.stdio())
@@ -0,0 +1,81 @@
+use std::{collections::{BTreeMap, HashMap, VecDeque}, sync::{Arc, atomic::{AtomicBool, Ordering}}};
+
+use parking_lot::Mutex;
+use tokio::sync::Notify;
+use yazi_shared::Id;
+
+use crate::{Ongoing, TaskOp};
+
+/// The queue of the macro tasks, by priority and then the order of the task list.
+pub struct Queue {
+	ops:     Mutex<BTreeMap<u8, HashMap<Id, VecDeque<TaskOp>>>>,
+	notify:  Notify,
+	held:    AtomicBool,
+	ongoing: Arc<Mutex<Ongoing>>,
+}
+
+impl Queue {
+	pub(super) fn new(ongoing: Arc<Mutex<Ongoing>>) -> Self {
+		Self { ops: Default::default(), notify: Notify::new(), held: AtomicBool::new(false), ongoing }
+	}
+
+	pub(super) fn send(&self, op: TaskOp, priority: u8) {
+		self.ops.lock().entry(priority).or_default().entry(op.id()).or_default().push_back(op);
+		self.notify.notify_one();
+	}
+
+	pub(super) async fn recv(&self) -> TaskOp {
+		loop {
+			let notified = self.notify.notified();
+			tokio::pin!(notified);
+			notified.as_mut().enable();
+
+			if let Some(op) = self.pop() {
+				return op;
+			}
+			notified.await;
+		}
+	}
+
+	/// Wakes up all the workers waiting, when a task is resumed or canceled.
+	#[inline]
+	pub(super) fn wake(&self) { self.notify.notify_waiters(); }
+
+	/// Wakes up the workers if any operations were held back the last time.
+	#[inline]
+	pub(super) fn kick(&self) {
+		if self.held.load(Ordering::Relaxed) {
+			self.wake();
+		}
+	}
+
+	fn pop(&self) -> Option<TaskOp> {
+		let ongoing = self.ongoing.lock();
+		let mut ops = self.ops.lock();
+
+		let mut held = false;
+		for tasks in ops.values_mut().rev() {
+			// The ones of the tasks gone have no rank, and are handed out first to be dropped
+			let id = tasks
+				.keys()
+				.filter(|&&id| {
+					let h = ongoing.held(id);
+					held |= h;
+					!h
+				})
+				.min_by_key(|&&id| ongoing.get(id).map(|t| t.rank))
+				.copied();
+
+			let Some(id) = id else { continue };
+			let queue = tasks.get_mut(&id)?;
+			let op = queue.pop_front();
+			if queue.is_empty() {
+				tasks.remove(&id);
+			}
+			return op;
+		}
+
+		self.held.store(held, Ordering::Relaxed);
+		None
+	}
+}
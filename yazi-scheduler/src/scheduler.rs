@@ -1,18 +1,19 @@
-use std::{ffi::OsString, future::Future, sync::Arc, time::Duration};
+use std::{ffi::OsString, future::Future, mem, sync::{Arc, Weak}, time::Duration};
 
 use anyhow::Result;
 use futures::{FutureExt, future::BoxFuture};
 use parking_lot::Mutex;
 use tokio::{select, sync::mpsc::{self, UnboundedReceiver}, task::JoinHandle};
+use tokio_util::sync::CancellationToken;
 use yazi_config::{YAZI, plugin::{Fetcher, Preloader}};
 use yazi_dds::Pump;
-use yazi_fs::{must_be_dir, path::unique_name, provider, remove_dir_clean};
-use yazi_parser::{app::PluginOpt, tasks::ProcessExecOpt};
-use yazi_proxy::MgrProxy;
+use yazi_fs::{AUDIT, AuditEntry, AuditOp, BANDWIDTH, CHUNKED, DUPES, DupeFile, Dupes, FilesOp, JournalOp, SIZES, Selection, cha::Cha, must_be_dir, path::unique_name, paths_on_same_device, provider, remove_dir_clean};
+use yazi_parser::{app::{NotifyLevel, NotifyOpt, PluginOpt}, mgr::{LinkOptRelative, PasteOptConflict}, tasks::ProcessExecOpt};
+use yazi_proxy::{AppProxy, MgrProxy};
 use yazi_shared::{Id, Throttle, url::Url};
 
-use super::{Ongoing, TaskProg, TaskStage};
-use crate::{HIGH, LOW, NORMAL, TaskKind, TaskOp, file::{File, FileInDelete, FileInHardlink, FileInLink, FileInPaste, FileInTrash}, plugin::{Plugin, PluginInEntry}, prework::{Prework, PreworkInFetch, PreworkInLoad, PreworkInSize}, process::{Process, ProcessInBg, ProcessInBlock, ProcessInOrphan}};
+use super::{Ongoing, Queue, Schedule, TaskProg, TaskStage};
+use crate::{HIGH, LOW, NORMAL, TaskKind, TaskOp, file::{Conflict, File, FileInDelete, FileInDownload, FileInDupes, FileInExtract, FileInHardlink, FileInLink, FileInPaste, FileInPurge, FileInTrash, Recycle}, plugin::{Plugin, PluginInEntry}, prework::{Prework, PreworkInFetch, PreworkInLoad, PreworkInSize}, process::{Process, ProcessInBg, ProcessInBlock, ProcessInOrphan}};
 
 pub struct Scheduler {
 	pub file:    Arc<File>,
@@ -21,6 +22,7 @@ pub struct Scheduler {
 	pub process: Arc<Process>,
 
 	micro:       async_priority_channel::Sender<BoxFuture<'static, ()>, u8>,
+	r#macro:     Arc<Queue>,
 	prog:        mpsc::UnboundedSender<TaskProg>,
 	handles:     Vec<JoinHandle<()>>,
 	pub ongoing: Arc<Mutex<Ongoing>>,
@@ -29,28 +31,36 @@ pub struct Scheduler {
 impl Scheduler {
 	pub fn serve() -> Self {
 		let (micro_tx, micro_rx) = async_priority_channel::unbounded();
-		let (macro_tx, macro_rx) = async_priority_channel::unbounded();
 		let (prog_tx, prog_rx) = mpsc::unbounded_channel();
+		let ongoing: Arc<Mutex<Ongoing>> = Default::default();
+		let r#macro = Arc::new(Queue::new(ongoing.clone()));
+		BANDWIDTH.set_limit(YAZI.tasks.paste_limit);
+		CHUNKED.set(YAZI.tasks.copy_chunk, YAZI.tasks.copy_streams);
+		AUDIT.enable(YAZI.tasks.audit_log);
+		tokio::task::spawn_blocking(|| SIZES.load().ok());
+		tokio::task::spawn_blocking(|| DUPES.load().ok());
+		Recycle::refresh();
 
 		let mut scheduler = Self {
-			file:    Arc::new(File::new(macro_tx.clone(), prog_tx.clone())),
-			plugin:  Arc::new(Plugin::new(macro_tx.clone(), prog_tx.clone())),
-			prework: Arc::new(Prework::new(macro_tx.clone(), prog_tx.clone())),
+			file:    Arc::new(File::new(r#macro.clone(), prog_tx.clone())),
+			plugin:  Arc::new(Plugin::new(r#macro.clone(), prog_tx.clone())),
+			prework: Arc::new(Prework::new(r#macro.clone(), prog_tx.clone())),
 			process: Arc::new(Process::new(prog_tx.clone())),
 
 			micro:   micro_tx,
+			r#macro,
 			prog:    prog_tx,
 			handles: Vec::with_capacity(
 				YAZI.tasks.micro_workers as usize + YAZI.tasks.macro_workers as usize + 1,
 			),
-			ongoing: Default::default(),
+			ongoing,
 		};
 
 		for _ in 0..YAZI.tasks.micro_workers {
 			scheduler.handles.push(scheduler.schedule_micro(micro_rx.clone()));
 		}
 		for _ in 0..YAZI.tasks.macro_workers {
-			scheduler.handles.push(scheduler.schedule_macro(micro_rx.clone(), macro_rx.clone()));
+			scheduler.handles.push(scheduler.schedule_macro(micro_rx.clone()));
 		}
 		scheduler.progress(prog_rx);
 		scheduler
@@ -59,12 +69,42 @@ impl Scheduler {
 	pub fn cancel(&self, id: Id) -> bool {
 		let mut ongoing = self.ongoing.lock();
 
+		// Let go of the copies waiting on it, and of its operations held back,
+		// before the hook that waits for them
+		if let Some(task) = ongoing.get(id) {
+			task.pause.set(false);
+			self.r#macro.wake();
+		}
+
 		if let Some(fut) = ongoing.hooks.run_or_pop(id, true) {
 			self.micro.try_send(fut, HIGH).ok();
 			return false;
 		}
 
-		ongoing.all.remove(&id).is_some()
+		let Some(task) = ongoing.all.remove(&id) else { return false };
+		task.feed();
+		true
+	}
+
+	/// Pauses the task, or resumes it if it's paused, returning whether it's paused now.
+	pub fn pause(&self, id: Id) -> Option<bool> {
+		let ongoing = self.ongoing.lock();
+		let task = ongoing.get(id)?;
+
+		let paused = !task.pause.paused();
+		task.pause.set(paused);
+		if !paused {
+			self.r#macro.wake();
+		}
+		Some(paused)
+	}
+
+	pub fn reorder(&self, id: Id, offset: isize) -> bool {
+		let moved = self.ongoing.lock().reorder(id, offset);
+		if moved {
+			self.r#macro.kick();
+		}
+		moved
 	}
 
 	pub fn shutdown(&self) {
@@ -73,23 +113,36 @@ impl Scheduler {
 		}
 	}
 
-	pub fn file_cut(&self, from: Url, mut to: Url, force: bool) {
+	pub fn file_cut(
+		&self,
+		from: Url,
+		to: Url,
+		conflict: Arc<Conflict>,
+		verify: bool,
+		schedule: Schedule,
+	) -> Id {
 		let mut ongoing = self.ongoing.lock();
 		let id = ongoing.add(TaskKind::User, format!("Cut {} to {}", from.display(), to.display()));
+		self.schedule(&mut ongoing, id, schedule);
 
 		if to.starts_with(&from) && to != from {
 			self.new_and_fail(id, "Cannot cut directory into itself").ok();
-			return;
+			return id;
 		}
 
+		// The destination is only known for sure once the conflict has been resolved,
+		// and there's none if it was skipped
+		let dest = Arc::new(Mutex::new(Some(to.clone())));
 		ongoing.hooks.add_async(id, {
 			let ongoing = self.ongoing.clone();
-			let (from, to) = (from.clone(), to.clone());
+			let (from, dest) = (from.clone(), dest.clone());
 
 			move |canceled: bool| {
 				async move {
-					if !canceled {
+					let to = dest.lock().clone();
+					if let Some(to) = to.filter(|_| !canceled) {
 						remove_dir_clean(&from).await;
+						JournalOp::Move { from: from.clone(), to: to.clone() }.emit_by(id);
 						Pump::push_move(from, to);
 					}
 					ongoing.lock().try_remove(id, TaskStage::Hooked);
@@ -98,36 +151,100 @@ impl Scheduler {
 			}
 		});
 
-		let file = self.file.clone();
+		if AUDIT.enabled()
+			&& let Some(task) = ongoing.get_mut(id)
+		{
+			task.audit = Some(AuditEntry::new(AuditOp::Move, Some(from.clone()), to.clone()));
+		}
+
+		let (file, verify) = (self.file.clone(), verify || YAZI.tasks.verify_paste);
+		let (alive, ongoing) = (self.alive(id), self.ongoing.clone());
 		self.send_micro(id, LOW, async move {
-			if !force {
-				to = unique_name(to, must_be_dir(&from)).await?;
+			let resolved = conflict.resolve(&from, to.clone()).await?;
+			*dest.lock() = resolved.clone();
+			if let Some(task) = ongoing.lock().get_mut(id) {
+				let audit = task.audit.take().zip(resolved.clone());
+				task.audit = audit.map(|(a, to)| AuditEntry { target: to, ..a });
 			}
-			file.paste(FileInPaste { id, from, to, cha: None, cut: true, follow: false, retry: 0 }).await
+
+			let Some(to) = resolved else { return file.skip(id, &to) };
+			file
+				.paste(
+					FileInPaste { id, from, to, cha: None, cut: true, follow: false, verify, retry: 0 },
+					alive,
+				)
+				.await
 		});
+		id
 	}
 
-	pub fn file_copy(&self, from: Url, mut to: Url, force: bool, follow: bool) {
-		let id = self
-			.ongoing
-			.lock()
-			.add(TaskKind::User, format!("Copy {} to {}", from.display(), to.display()));
+	pub fn file_copy(
+		&self,
+		from: Url,
+		to: Url,
+		conflict: Arc<Conflict>,
+		follow: bool,
+		verify: bool,
+		schedule: Schedule,
+	) {
+		self.copy(&mut self.ongoing.lock(), from, to, conflict, follow, verify, schedule);
+	}
+
+	/// Copies a compared file over its counterpart, resolving their difference once it succeeds.
+	pub fn file_sync(&self, from: Url, to: Url) {
+		let mut ongoing = self.ongoing.lock();
+		let conflict = Arc::new(Conflict::new(PasteOptConflict::Overwrite));
+		let id = self.copy(&mut ongoing, from.clone(), to, conflict, false, false, Default::default());
+
+		// Added while the lock is still held, so the copy can't finish before it
+		ongoing.hooks.add_async(id, {
+			let ongoing = self.ongoing.clone();
+
+			move |canceled: bool| {
+				async move {
+					if !canceled {
+						MgrProxy::compare_resolve(&from);
+					}
+					ongoing.lock().try_remove(id, TaskStage::Hooked);
+				}
+				.boxed()
+			}
+		});
+	}
+
+	#[allow(clippy::too_many_arguments)]
+	fn copy(
+		&self,
+		ongoing: &mut Ongoing,
+		from: Url,
+		to: Url,
+		conflict: Arc<Conflict>,
+		follow: bool,
+		verify: bool,
+		schedule: Schedule,
+	) -> Id {
+		let id = ongoing.add(TaskKind::User, format!("Copy {} to {}", from.display(), to.display()));
+		self.schedule(ongoing, id, schedule);
 
 		if to.starts_with(&from) && to != from {
 			self.new_and_fail(id, "Cannot copy directory into itself").ok();
-			return;
+			return id;
 		}
 
-		let file = self.file.clone();
+		let (file, verify) = (self.file.clone(), verify || YAZI.tasks.verify_paste);
+		let alive = self.alive(id);
 		self.send_micro(id, LOW, async move {
-			if !force {
-				to = unique_name(to, must_be_dir(&from)).await?;
-			}
-			file.paste(FileInPaste { id, from, to, cha: None, cut: false, follow, retry: 0 }).await
+			let Some(to) = conflict.resolve(&from, to.clone()).await? else {
+				return file.skip(id, &to);
+			};
+			file
+				.paste(FileInPaste { id, from, to, cha: None, cut: false, follow, verify, retry: 0 }, alive)
+				.await
 		});
+		id
 	}
 
-	pub fn file_link(&self, from: Url, mut to: Url, relative: bool, force: bool) {
+	pub fn file_link(&self, from: Url, mut to: Url, relative: LinkOptRelative, force: bool) {
 		let id = self
 			.ongoing
 			.lock()
@@ -138,6 +255,11 @@ impl Scheduler {
 			if !force {
 				to = unique_name(to, must_be_dir(&from)).await?;
 			}
+			let relative = match relative {
+				LinkOptRelative::Never => false,
+				LinkOptRelative::Always => true,
+				LinkOptRelative::Auto => paths_on_same_device(&from, to.parent_url().unwrap()).await,
+			};
 			file
 				.link(FileInLink { id, from, to, cha: None, resolve: false, relative, delete: false })
 				.await
@@ -164,9 +286,37 @@ impl Scheduler {
 		});
 	}
 
+	pub fn file_dedupe(&self, keep: Url, dupe: Url) {
+		let id = self
+			.ongoing
+			.lock()
+			.add(TaskKind::User, format!("Hardlink {} to {}", keep.display(), dupe.display()));
+
+		let prog = self.prog.clone();
+		self.send_micro(id, LOW, async move {
+			let (Some(k), Some(d)) = (keep.as_path(), dupe.as_path()) else {
+				anyhow::bail!("Only local files can be hardlinked");
+			};
+
+			prog.send(TaskProg::New(id, 0))?;
+			if let Err(e) = DUPES.link(k, d).await {
+				prog.send(TaskProg::Fail(id, format!("Dedupe task failed: {}, {e}", d.display())))?;
+			} else {
+				prog.send(TaskProg::Adv(id, 1, 0))?;
+				prog.send(TaskProg::Succ(id))?;
+			}
+			Ok(())
+		});
+	}
+
 	pub fn file_delete(&self, target: Url) {
 		let mut ongoing = self.ongoing.lock();
 		let id = ongoing.add(TaskKind::User, format!("Delete {}", target.display()));
+		if AUDIT.enabled()
+			&& let Some(task) = ongoing.get_mut(id)
+		{
+			task.audit = Some(AuditEntry::new(AuditOp::Delete, None, target.clone()));
+		}
 
 		ongoing.hooks.add_async(id, {
 			let target = target.clone();
@@ -196,6 +346,11 @@ impl Scheduler {
 	pub fn file_trash(&self, target: Url) {
 		let mut ongoing = self.ongoing.lock();
 		let id = ongoing.add(TaskKind::User, format!("Trash {}", target.display()));
+		if AUDIT.enabled()
+			&& let Some(task) = ongoing.get_mut(id)
+		{
+			task.audit = Some(AuditEntry::new(AuditOp::Trash, None, target.clone()));
+		}
 
 		ongoing.hooks.add_async(id, {
 			let target = target.clone();
@@ -205,6 +360,9 @@ impl Scheduler {
 				async move {
 					if !canceled {
 						MgrProxy::update_tasks(&target);
+						Recycle::record(&target);
+						Recycle::refresh();
+						JournalOp::Trash { target: target.clone() }.emit();
 						Pump::push_trash(target);
 					}
 					ongoing.lock().try_remove(id, TaskStage::Hooked);
@@ -219,6 +377,107 @@ impl Scheduler {
 		})
 	}
 
+	pub fn file_purge(&self, ids: Vec<String>, size: u64) {
+		let mut ongoing = self.ongoing.lock();
+		let id = ongoing.add(TaskKind::User, format!("Purge {} item(s) from the trash", ids.len()));
+
+		ongoing.hooks.add_async(id, {
+			let ongoing = self.ongoing.clone();
+			move |canceled: bool| {
+				async move {
+					if !canceled {
+						Recycle::refresh();
+					}
+					ongoing.lock().try_remove(id, TaskStage::Hooked);
+				}
+				.boxed()
+			}
+		});
+
+		let file = self.file.clone();
+		self.send_micro(
+			id,
+			LOW,
+			async move { file.purge(FileInPurge { id, ids, length: size }).await },
+		);
+	}
+
+	pub fn file_extract(&self, from: Url, to: Url) {
+		let id = self
+			.ongoing
+			.lock()
+			.add(TaskKind::User, format!("Extract {} to {}", from.display(), to.display()));
+
+		let file = self.file.clone();
+		self.send_micro(id, LOW, async move {
+			let password = String::new();
+			file.extract(FileInExtract { id, from, to, password, entries: Default::default() }).await
+		});
+	}
+
+	pub fn file_download(&self, url: String, to: Url) {
+		let id = self
+			.ongoing
+			.lock()
+			.add(TaskKind::User, format!("Download {url} to {}", to.display()));
+
+		let file = self.file.clone();
+		self.send_micro(id, LOW, async move {
+			let task = FileInDownload { id, url, to, name: String::new(), offset: 0 };
+			file.download(task).await
+		});
+	}
+
+	pub fn file_dupes(&self, targets: Vec<Url>) {
+		let mut ongoing = self.ongoing.lock();
+		let id = ongoing.add(TaskKind::User, match &targets[..] {
+			[u] => format!("Find duplicates in {}", u.display()),
+			_ => format!("Find duplicates among {} item(s)", targets.len()),
+		});
+
+		let found: Arc<Mutex<Vec<Vec<DupeFile>>>> = Default::default();
+		ongoing.hooks.add_async(id, {
+			let ongoing = self.ongoing.clone();
+			let found = found.clone();
+
+			move |canceled: bool| {
+				async move {
+					if !canceled {
+						let groups = mem::take(&mut *found.lock());
+						Self::dupes_found(groups).await;
+					}
+					ongoing.lock().try_remove(id, TaskStage::Hooked);
+				}
+				.boxed()
+			}
+		});
+
+		let file = self.file.clone();
+		self.send_micro(id, LOW, async move {
+			file.dupes(FileInDupes { id, files: vec![], found }, targets).await
+		});
+	}
+
+	pub fn file_expand(&self, selection: Selection, into: Weak<Mutex<Vec<Url>>>) {
+		let id = self.ongoing.lock().add(TaskKind::User, match &selection.roots[..] {
+			[u] => format!("Expand {} yanked item(s) in {}", selection.len(), u.display()),
+			_ => format!("Expand {} yanked item(s)", selection.len()),
+		});
+
+		let (prog, alive) = (self.prog.clone(), self.alive(id));
+		self.send_micro(id, LOW, async move {
+			let mut rx = selection.expand();
+			while let Some(url) = rx.recv().await {
+				// Stop once it's cancelled, or the yank has been replaced
+				let Some(into) = into.upgrade().filter(|_| alive()) else { break };
+				into.lock().push(url);
+				prog.send(TaskProg::New(id, 0))?;
+				prog.send(TaskProg::Adv(id, 1, 0))?;
+			}
+			Ok(prog.send(TaskProg::Succ(id))?)
+		});
+	}
+
 	pub fn plugin_micro(&self, opt: PluginOpt) {
 		let id = self.ongoing.lock().add(TaskKind::User, format!("Run micro plugin `{}`", opt.id));
 
@@ -255,24 +514,35 @@ impl Scheduler {
 		});
 	}
 
-	pub fn prework_size(&self, targets: Vec<&Url>) {
+	pub fn prework_size(&self, targets: Vec<&yazi_fs::File>) {
 		let throttle = Arc::new(Throttle::new(targets.len(), Duration::from_millis(300)));
 		let mut ongoing = self.ongoing.lock();
 
 		for target in targets {
 			let id =
-				ongoing.add(TaskKind::Preload, format!("Calculate the size of {}", target.display()));
-			let target = target.clone();
+				ongoing.add(TaskKind::Preload, format!("Calculate the size of {}", target.url.display()));
+
+			let ct = CancellationToken::new();
+			ongoing.hooks.add_sync(id, {
+				let ct = ct.clone();
+				move |canceled| {
+					if canceled {
+						ct.cancel();
+					}
+				}
+			});
+
+			let (target, mtime) = (target.url.clone(), target.cha.mtime);
 			let throttle = throttle.clone();
 
 			let prework = self.prework.clone();
 			self.send_micro(id, NORMAL, async move {
-				prework.size(PreworkInSize { id, target, throttle }).await
+				prework.size(PreworkInSize { id, target, mtime, throttle, ct }).await
 			});
 		}
 	}
 
-	pub fn process_open(&self, ProcessExecOpt { cwd, opener, args, done }: ProcessExecOpt) {
+	pub fn process_open(&self, ProcessExecOpt { cwd, opener, args, line, done }: ProcessExecOpt) {
 		let name = {
 			let args = args.iter().map(|a| a.to_string_lossy()).collect::<Vec<_>>().join(" ");
 			if args.is_empty() {
@@ -307,11 +577,11 @@ impl Scheduler {
 		let process = self.process.clone();
 		self.send_micro(id, NORMAL, async move {
 			if opener.block {
-				process.block(ProcessInBlock { id, cwd, cmd, args }).await
+				process.block(ProcessInBlock { id, cwd, cmd, args, line }).await
 			} else if opener.orphan {
-				process.orphan(ProcessInOrphan { id, cwd, cmd, args }).await
+				process.orphan(ProcessInOrphan { id, cwd, cmd, args, line }).await
 			} else {
-				process.bg(ProcessInBg { id, cwd, cmd, args, cancel: cancel_rx }).await
+				process.bg(ProcessInBg { id, cwd, cmd, args, line, cancel: cancel_rx }).await
 			}
 		});
 	}
@@ -332,8 +602,8 @@ impl Scheduler {
 	fn schedule_macro(
 		&self,
 		micro: async_priority_channel::Receiver<BoxFuture<'static, ()>, u8>,
-		r#macro: async_priority_channel::Receiver<TaskOp, u8>,
 	) -> JoinHandle<()> {
+		let r#macro = self.r#macro.clone();
 		let file = self.file.clone();
 		let plugin = self.plugin.clone();
 		let prework = self.prework.clone();
@@ -347,14 +617,14 @@ impl Scheduler {
 					Ok((fut, _)) = micro.recv() => {
 						fut.await;
 					}
-					Ok((r#in, _)) = r#macro.recv() => {
+					r#in = r#macro.recv() => {
 						let id = r#in.id();
-						if !ongoing.lock().exists(id) {
+						let Some(pause) = ongoing.lock().get(id).map(|t| t.pause.clone()) else {
 							continue;
-						}
+						};
 
 						let result = match r#in {
-							TaskOp::File(r#in) => file.work(*r#in).await,
+							TaskOp::File(r#in) => file.work(*r#in, pause).await,
 							TaskOp::Plugin(r#in) => plugin.work(*r#in).await,
 							TaskOp::Prework(r#in) => prework.work(*r#in).await,
 						};
@@ -368,8 +638,22 @@ impl Scheduler {
 		})
 	}
 
+	fn schedule(&self, ongoing: &mut Ongoing, id: Id, schedule: Schedule) {
+		if let Some(task) = ongoing.get_mut(id) {
+			task.schedule = Some(schedule);
+		}
+
+		let Some(start) = schedule.start else { return };
+		let r#macro = self.r#macro.clone();
+		tokio::spawn(async move {
+			tokio::time::sleep_until(start.into()).await;
+			r#macro.wake();
+		});
+	}
+
 	fn progress(&self, mut rx: UnboundedReceiver<TaskProg>) -> JoinHandle<()> {
 		let micro = self.micro.clone();
+		let r#macro = self.r#macro.clone();
 		let ongoing = self.ongoing.clone();
 
 		tokio::spawn(async move {
@@ -383,6 +667,7 @@ impl Scheduler {
 					}
 					TaskProg::Adv(id, succ, processed) => {
 						let mut ongoing = ongoing.lock();
+						ongoing.processed += processed;
 						if let Some(task) = ongoing.get_mut(id) {
 							task.succ += succ;
 							task.processed += processed;
@@ -393,6 +678,17 @@ impl Scheduler {
 							micro.try_send(fut, LOW).ok();
 						}
 					}
+					TaskProg::Scan(id, size) => {
+						if let Some(task) = ongoing.lock().get_mut(id) {
+							task.found += size;
+							task.processed += size;
+						}
+					}
+					TaskProg::Expect(id, size) => {
+						if let Some(task) = ongoing.lock().get_mut(id) {
+							task.expected += size;
+						}
+					}
 					TaskProg::Succ(id) => {
 						if let Some(fut) = ongoing.lock().try_remove(id, TaskStage::Dispatched) {
 							micro.try_send(fut, LOW).ok();
@@ -401,25 +697,25 @@ impl Scheduler {
 					TaskProg::Fail(id, reason) => {
 						if let Some(task) = ongoing.lock().get_mut(id) {
 							task.fail += 1;
-							task.logs.push_str(&reason);
-							task.logs.push('\n');
-
-							if let Some(logger) = &task.logger {
-								logger.send(reason).ok();
-							}
+							task.log(reason);
 						}
 					}
 					TaskProg::Log(id, line) => {
 						if let Some(task) = ongoing.lock().get_mut(id) {
-							task.logs.push_str(&line);
-							task.logs.push('\n');
-
-							if let Some(logger) = &task.logger {
-								logger.send(line).ok();
-							}
+							task.log(line);
+						}
+					}
+					TaskProg::Copied(id, method) => {
+						if let Some(task) = ongoing.lock().get_mut(id)
+							&& !task.copied.contains(&method)
+						{
+							task.copied.push(method);
+							task.log(format!("Copied with {method}"));
 						}
 					}
 				}
+				// The pastes queued may be waiting for this one to finish
+				r#macro.kick();
 			}
 		})
 	}
@@ -441,9 +737,77 @@ impl Scheduler {
 		);
 	}
 
+	/// Whether the task is still there, as opposed to having been cancelled.
+	fn alive(&self, id: Id) -> impl Fn() -> bool + Send + 'static {
+		let ongoing = self.ongoing.clone();
+		move || ongoing.lock().get(id).is_some()
+	}
+
+	async fn dupes_found(groups: Vec<Vec<DupeFile>>) {
+		let found = DUPES.publish(groups);
+		tokio::task::spawn_blocking(|| DUPES.save().ok());
+
+		if found == 0 {
+			return AppProxy::notify(NotifyOpt {
+				title:   "Find duplicates".to_owned(),
+				content: "No duplicates found".to_owned(),
+				level:   NotifyLevel::Info,
+				timeout: Duration::from_secs(3),
+				action:  None,
+			});
+		}
+
+		// Already in the listing, it's not entered again but replaced
+		let root = Dupes::root();
+		FilesOp::Full(root.clone(), DUPES.read(&root).await, Cha::default()).emit();
+		MgrProxy::cd(&root);
+	}
+
 	fn new_and_fail(&self, id: Id, reason: &str) -> Result<()> {
 		self.prog.send(TaskProg::New(id, 0))?;
 		self.prog.send(TaskProg::Fail(id, reason.to_owned()))?;
 		Ok(())
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn scheduler() -> Scheduler {
+		let (micro, _) = async_priority_channel::unbounded();
+		let (prog, _) = mpsc::unbounded_channel();
+		let ongoing: Arc<Mutex<Ongoing>> = Default::default();
+		let r#macro = Arc::new(Queue::new(ongoing.clone()));
+
+		Scheduler {
+			file: Arc::new(File::new(r#macro.clone(), prog.clone())),
+			plugin: Arc::new(Plugin::new(r#macro.clone(), prog.clone())),
+			prework: Arc::new(Prework::new(r#macro.clone(), prog.clone())),
+			process: Arc::new(Process::new(prog.clone())),
+
+			micro,
+			r#macro,
+			prog,
+			handles: vec![],
+			ongoing,
+		}
+	}
+
+	#[test]
+	fn test_cancel_paused() {
+		let scheduler = scheduler();
+		let (id, pause) = {
+			let mut ongoing = scheduler.ongoing.lock();
+			let id = ongoing.add(TaskKind::User, "Cut a to b".to_owned());
+			ongoing.hooks.add_async(id, |_| async {}.boxed());
+			(id, ongoing.get(id).unwrap().pause.clone())
+		};
+		assert_eq!(scheduler.pause(id), Some(true));
+
+		// The hook is left to remove the task, but the copies waiting are let go
+		assert!(!scheduler.cancel(id));
+		assert!(!pause.paused());
+		assert!(scheduler.ongoing.lock().exists(id));
+	}
+}
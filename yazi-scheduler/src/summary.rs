@@ -0,0 +1,144 @@
+use std::{fmt::{self, Display}, path::PathBuf, sync::atomic::{AtomicU64, Ordering}};
+
+use serde::Serialize;
+
+pub static SUMMARY: Summary = Summary::new();
+
+/// What the file operations have done during this session.
+pub struct Summary {
+	moved:   [AtomicU64; 2],
+	copied:  [AtomicU64; 2],
+	trashed: [AtomicU64; 2],
+	deleted: [AtomicU64; 2],
+	renamed: AtomicU64,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum SummaryKind {
+	Moved,
+	Copied,
+	Trashed,
+	Deleted,
+	Renamed,
+}
+
+impl Summary {
+	const fn new() -> Self {
+		const fn zero() -> [AtomicU64; 2] { [AtomicU64::new(0), AtomicU64::new(0)] }
+
+		Self {
+			moved:   zero(),
+			copied:  zero(),
+			trashed: zero(),
+			deleted: zero(),
+			renamed: AtomicU64::new(0),
+		}
+	}
+
+	pub fn record(&self, kind: SummaryKind, bytes: u64) {
+		let [count, size] = match kind {
+			SummaryKind::Moved => &self.moved,
+			SummaryKind::Copied => &self.copied,
+			SummaryKind::Trashed => &self.trashed,
+			SummaryKind::Deleted => &self.deleted,
+			SummaryKind::Renamed => {
+				self.renamed.fetch_add(1, Ordering::Relaxed);
+				return;
+			}
+		};
+
+		count.fetch_add(1, Ordering::Relaxed);
+		size.fetch_add(bytes, Ordering::Relaxed);
+	}
+
+	pub fn report(&self, incomplete: usize, written: Vec<PathBuf>) -> SummaryReport {
+		let load = |[count, size]: &[AtomicU64; 2]| SummaryCount {
+			count: count.load(Ordering::Relaxed),
+			bytes: size.load(Ordering::Relaxed),
+		};
+
+		SummaryReport {
+			moved: load(&self.moved),
+			copied: load(&self.copied),
+			trashed: load(&self.trashed),
+			deleted: load(&self.deleted),
+			renamed: self.renamed.load(Ordering::Relaxed),
+			incomplete,
+			written,
+		}
+	}
+}
+
+// --- Report
+#[derive(Debug, Serialize)]
+pub struct SummaryReport {
+	pub moved:      SummaryCount,
+	pub copied:     SummaryCount,
+	pub trashed:    SummaryCount,
+	pub deleted:    SummaryCount,
+	pub renamed:    u64,
+	pub incomplete: usize,
+	pub written:    Vec<PathBuf>,
+}
+
+#[derive(Clone, Copy, Debug, Default, Serialize)]
+pub struct SummaryCount {
+	pub count: u64,
+	pub bytes: u64,
+}
+
+impl SummaryReport {
+	pub fn is_empty(&self) -> bool {
+		self.moved.count == 0
+			&& self.copied.count == 0
+			&& self.trashed.count == 0
+			&& self.deleted.count == 0
+			&& self.renamed == 0
+			&& self.incomplete == 0
+			&& self.written.is_empty()
+	}
+
+	#[inline]
+	pub fn to_json(&self) -> serde_json::Result<String> { serde_json::to_string_pretty(self) }
+}
+
+impl Display for SummaryReport {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		fn line(f: &mut fmt::Formatter<'_>, c: SummaryCount, what: &str) -> fmt::Result {
+			if c.count == 0 {
+				return Ok(());
+			}
+			let s = if c.count == 1 { "" } else { "s" };
+			writeln!(f, "  {} file{s} {what} ({})", c.count, readable_size(c.bytes))
+		}
+
+		writeln!(f, "Yazi session summary:")?;
+		line(f, self.moved, "moved")?;
+		line(f, self.copied, "copied")?;
+		line(f, self.trashed, "moved to trash")?;
+		line(f, self.deleted, "deleted permanently")?;
+		if self.renamed > 0 {
+			writeln!(f, "  {} renamed", self.renamed)?;
+		}
+		if self.incomplete > 0 {
+			let s = if self.incomplete == 1 { "" } else { "s" };
+			writeln!(f, "  {} task{s} still incomplete", self.incomplete)?;
+		}
+		for p in &self.written {
+			writeln!(f, "  Wrote {}", p.display())?;
+		}
+		Ok(())
+	}
+}
+
+pub fn readable_size(bytes: u64) -> String {
+	const UNITS: [&str; 6] = ["B", "KB", "MB", "GB", "TB", "PB"];
+
+	let (mut size, mut i) = (bytes as f64, 0);
+	while size >= 1024.0 && i < UNITS.len() - 1 {
+		size /= 1024.0;
+		i += 1;
+	}
+
+	if i == 0 { format!("{bytes} B") } else { format!("{size:.1} {}", UNITS[i]) }
+}
@@ -1,4 +1,9 @@
+use std::time::Instant;
+
 use tokio::sync::mpsc;
+use yazi_config::YAZI;
+use yazi_dds::Feed;
+use yazi_fs::{AUDIT, AuditEntry, AuditOutcome, CopyMethod, Pause};
 use yazi_shared::Id;
 
 #[derive(Debug, Default)]
@@ -8,21 +13,95 @@ pub struct Task {
 	pub name:  String,
 	pub stage: TaskStage,
 
+	/// Where it's listed, the lower the earlier, and so are its operations run.
+	pub rank:     u64,
+	pub pause:    Pause,
+	/// When its operations are run, for the pastes.
+	pub schedule: Option<Schedule>,
+
 	pub total: u32,
 	pub succ:  u32,
 	pub fail:  u32,
 
 	pub found:     u64,
 	pub processed: u64,
+	/// The bytes pre-scanned before the files are found one by one, so the total is known early.
+	pub expected:  u64,
 
 	pub logs:   String,
 	pub logger: Option<mpsc::UnboundedSender<String>>,
+
+	/// How the files were copied, each is logged the first time it's used.
+	pub copied: Vec<CopyMethod>,
+
+	/// The entry of the audit log written once it's finished, if it's enabled.
+	pub audit: Option<AuditEntry>,
 }
 
 impl Task {
 	pub fn new(id: Id, kind: TaskKind, name: String) -> Self {
-		Self { id, kind, name, ..Default::default() }
+		Self { id, kind, name, rank: id.get(), ..Default::default() }
+	}
+
+	/// Whether it's still got operations to run.
+	pub(super) fn busy(&self) -> bool {
+		self.stage == TaskStage::Pending || self.succ + self.fail < self.total
 	}
+
+	/// The bytes to process in all, as far as it's known.
+	#[inline]
+	pub fn size(&self) -> u64 { self.found.max(self.expected) }
+
+	/// Whether it can start now, as it's not scheduled to start later, or that time has come.
+	pub(super) fn due(&self) -> bool {
+		self.schedule.and_then(|s| s.start).is_none_or(|t| t <= Instant::now())
+	}
+
+	pub(super) fn log(&mut self, line: String) {
+		self.logs.push_str(&line);
+		self.logs.push('\n');
+
+		if let Some(logger) = &self.logger {
+			logger.send(line).ok();
+		}
+	}
+
+	/// Reports it as finished to the feed of `--events`, and to the audit log.
+	pub(super) fn feed(&self) {
+		if self.kind != TaskKind::User {
+			return;
+		}
+
+		let outcome = if self.fail > 0 {
+			AuditOutcome::Failed
+		} else if self.succ < self.total {
+			AuditOutcome::Cancelled
+		} else {
+			AuditOutcome::Succeeded
+		};
+
+		if let Some(mut entry) = self.audit.clone() {
+			entry.outcome = outcome;
+			if outcome == AuditOutcome::Failed {
+				entry.error = self.logs.lines().rfind(|l| !l.is_empty()).map(ToOwned::to_owned);
+			}
+			AUDIT.record(entry);
+		}
+		Feed::task(self.id, &self.name, &outcome.to_string(), self.succ, self.fail);
+	}
+}
+
+/// When the operations of a paste are run.
+#[derive(Clone, Copy, Debug)]
+pub struct Schedule {
+	/// Wait until the pastes listed before it finish.
+	pub queue: bool,
+	/// Don't start before this.
+	pub start: Option<Instant>,
+}
+
+impl Default for Schedule {
+	fn default() -> Self { Self { queue: YAZI.tasks.queue_paste, start: None } }
 }
 
 #[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
@@ -34,7 +113,9 @@ pub enum TaskKind {
 
 #[derive(Debug, PartialEq, Eq)]
 pub struct TaskSummary {
-	pub name: String,
+	pub name:      String,
+	pub paused:    bool,
+	pub scheduled: bool,
 
 	pub total: u32,
 	pub succ:  u32,
@@ -47,13 +128,15 @@ pub struct TaskSummary {
 impl From<&Task> for TaskSummary {
 	fn from(task: &Task) -> Self {
 		TaskSummary {
-			name: task.name.clone(),
+			name:      task.name.clone(),
+			paused:    task.pause.paused(),
+			scheduled: !task.due(),
 
 			total: task.total,
 			succ:  task.succ,
 			fail:  task.fail,
 
-			found:     task.found,
+			found:     task.size(),
 			processed: task.processed,
 		}
 	}
@@ -65,12 +148,18 @@ pub enum TaskProg {
 	New(Id, u64),
 	// id, processed, size
 	Adv(Id, u32, u64),
+	// id, size, for the tasks that only scan what they found
+	Scan(Id, u64),
+	// id, size, as pre-scanned
+	Expect(Id, u64),
 	// id
 	Succ(Id),
 	// id
 	Fail(Id, String),
 	// id, line
 	Log(Id, String),
+	// id, how a file was copied
+	Copied(Id, CopyMethod),
 }
 
 #[derive(Clone, Debug, Default, Eq, PartialEq, Ord, PartialOrd)]
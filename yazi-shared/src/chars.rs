@@ -120,8 +120,13 @@ pub fn replace_to_printable(s: &[String], tab_size: u8) -> String {
 	unsafe { String::from_utf8_unchecked(buf) }
 }
 
-pub fn osstr_contains(s: impl AsRef<OsStr>, needle: impl AsRef<OsStr>) -> bool {
-	memchr::memmem::find(s.as_ref().as_encoded_bytes(), needle.as_ref().as_encoded_bytes()).is_some()
+pub fn osstr_contains(s: impl AsRef<OsStr>, needle: impl AsRef<OsStr>, insensitive: bool) -> bool {
+	let (s, needle) = (s.as_ref().as_encoded_bytes(), needle.as_ref().as_encoded_bytes());
+	if insensitive {
+		memchr::memmem::find(&s.to_ascii_lowercase(), &needle.to_ascii_lowercase()).is_some()
+	} else {
+		memchr::memmem::find(s, needle).is_some()
+	}
 }
 
 pub fn osstr_starts_with(
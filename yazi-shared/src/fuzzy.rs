@@ -0,0 +1,39 @@
+/// Scores how well `pattern` matches `s` as a subsequence, ignoring case.
+pub fn fuzzy(pattern: &str, s: &str) -> Option<u32> {
+	let s: Vec<char> = s.chars().collect();
+	let (mut score, mut next, mut last) = (0, 0, None);
+
+	for p in pattern.chars().filter(|c| !c.is_whitespace()) {
+		let i = (next..s.len()).find(|&i| eq_ignore_case(s[i], p))?;
+		score += 1;
+		if last.is_some_and(|l| l + 1 == i) {
+			score += 4;
+		}
+		if i == 0 || !s[i - 1].is_alphanumeric() || (s[i - 1].is_lowercase() && s[i].is_uppercase()) {
+			score += 3;
+		}
+		(next, last) = (i + 1, Some(i));
+	}
+	Some(score)
+}
+
+#[inline]
+fn eq_ignore_case(a: char, b: char) -> bool { a == b || a.to_lowercase().eq(b.to_lowercase()) }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_fuzzy() {
+		assert_eq!(fuzzy("", "anything"), Some(0));
+		assert_eq!(fuzzy("xyz", "tab_create"), None);
+		assert_eq!(fuzzy("tc", "tab_create"), Some(8));
+		assert_eq!(fuzzy("TAB", "tab_create"), Some(14));
+		assert_eq!(fuzzy("t c", "TabCreate"), Some(8));
+
+		// Consecutive ones and the start of words score higher than the scattered ones
+		assert!(fuzzy("tab", "tab_switch") > fuzzy("tab", "toggle_all_bookmarks"));
+		assert!(fuzzy("ts", "tab_switch") > fuzzy("ts", "toggle_hidden_files_list"));
+	}
+}
@@ -9,6 +9,7 @@ pub enum Layer {
 	App,
 	Mgr,
 	Tasks,
+	Notify,
 	Spot,
 	Pick,
 	Input,
@@ -24,6 +25,7 @@ impl Display for Layer {
 			Self::App => "app",
 			Self::Mgr => "mgr",
 			Self::Tasks => "tasks",
+			Self::Notify => "notify",
 			Self::Spot => "spot",
 			Self::Pick => "pick",
 			Self::Input => "input",
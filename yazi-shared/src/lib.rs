@@ -2,7 +2,7 @@
 
 yazi_macro::mod_pub!(errors event shell translit url);
 
-yazi_macro::mod_flat!(alias bytes chars condition debounce either env id layer natsort os osstr rand ro_cell source string sync_cell terminal throttle time utf8);
+yazi_macro::mod_flat!(alias bytes chars condition debounce either env fuzzy id layer natsort os osstr rand ro_cell source string swap_cell sync_cell terminal throttle time utf8 vercmp);
 
 pub fn init() {
 	LOG_LEVEL.replace(<_>::from(std::env::var("YAZI_LOG").unwrap_or_default()));
@@ -0,0 +1,53 @@
+use std::{marker::PhantomData, ops::Deref, ptr, sync::atomic::{AtomicPtr, Ordering}};
+
+/// A cell whose value can be swapped at runtime, leaking the previous ones.
+pub struct SwapCell<T> {
+	inner:   AtomicPtr<T>,
+	_marker: PhantomData<T>,
+}
+
+impl<T> SwapCell<T> {
+	#[inline]
+	pub const fn new() -> Self {
+		Self { inner: AtomicPtr::new(ptr::null_mut()), _marker: PhantomData }
+	}
+
+	#[inline]
+	pub fn init(&self, value: T) {
+		let old = self.inner.swap(Box::into_raw(Box::new(value)), Ordering::AcqRel);
+		debug_assert!(old.is_null());
+	}
+
+	#[inline]
+	pub fn set(&self, value: T) {
+		self.inner.store(Box::into_raw(Box::new(value)), Ordering::Release);
+	}
+}
+
+impl<T> Default for SwapCell<T> {
+	fn default() -> Self { Self::new() }
+}
+
+impl<T> Deref for SwapCell<T> {
+	type Target = T;
+
+	fn deref(&self) -> &Self::Target {
+		// SAFETY: the pointer is either null or from a leaked box, which is never freed
+		unsafe { self.inner.load(Ordering::Acquire).as_ref() }.expect("SwapCell is not initialized")
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_swap_cell() {
+		static CELL: SwapCell<String> = SwapCell::new();
+		CELL.init("foo".to_owned());
+		let old: &'static str = &CELL;
+
+		CELL.set("bar".to_owned());
+		assert_eq!((old, CELL.as_str()), ("foo", "bar"));
+	}
+}
@@ -1,6 +1,78 @@
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 #[inline]
 pub fn timestamp_us() -> u64 {
 	SystemTime::now().duration_since(UNIX_EPOCH).expect("Time went backwards").as_micros() as _
 }
+
+/// The UTC time of the Unix timestamp in RFC 3339, e.g. `2025-07-01T08:30:00Z`.
+pub fn rfc3339(secs: u64) -> String {
+	// The days since 0000-03-01, in eras of 400 years, so leap days come last
+	let z = (secs / 86400) as i64 + 719468;
+	let (era, doe) = (z.div_euclid(146097), z.rem_euclid(146097));
+	let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+	let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+	let mp = (5 * doy + 2) / 153;
+
+	let d = doy - (153 * mp + 2) / 5 + 1;
+	let m = if mp < 10 { mp + 3 } else { mp - 9 };
+	let y = yoe + era * 400 + (m <= 2) as i64;
+
+	let secs = secs % 86400;
+	format!("{y:04}-{m:02}-{d:02}T{:02}:{:02}:{:02}Z", secs / 3600, secs / 60 % 60, secs % 60)
+}
+
+/// How long until the next `HH:MM` of the local time, e.g. `23:30`.
+pub fn until_clock(s: &str) -> Option<Duration> {
+	let (h, m) = s.trim().split_once(':')?;
+	let (h, m): (u32, u32) = (h.parse().ok()?, m.parse().ok()?);
+	if h > 23 || m > 59 || s.trim().len() > 5 {
+		return None;
+	}
+
+	let secs = clock_delay(h * 3600 + m * 60, local_secs());
+	Some(Duration::from_secs(secs as u64))
+}
+
+fn clock_delay(target: u32, now: u32) -> u32 { (target + 86400 - now % 86400) % 86400 }
+
+/// Seconds since the midnight of the local time.
+#[cfg(unix)]
+fn local_secs() -> u32 {
+	let mut tm = unsafe { std::mem::zeroed::<libc::tm>() };
+	unsafe { libc::localtime_r(&libc::time(std::ptr::null_mut()), &mut tm) };
+	(tm.tm_hour * 3600 + tm.tm_min * 60 + tm.tm_sec) as u32
+}
+
+/// Seconds since the midnight of the local time.
+#[cfg(windows)]
+fn local_secs() -> u32 {
+	let t = unsafe { windows_sys::Win32::System::SystemInformation::GetLocalTime() };
+	t.wHour as u32 * 3600 + t.wMinute as u32 * 60 + t.wSecond as u32
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_rfc3339() {
+		assert_eq!(rfc3339(0), "1970-01-01T00:00:00Z");
+		assert_eq!(rfc3339(951_782_400), "2000-02-29T00:00:00Z");
+		assert_eq!(rfc3339(1_700_000_000), "2023-11-14T22:13:20Z");
+		assert_eq!(rfc3339(4_102_444_799), "2099-12-31T23:59:59Z");
+	}
+
+	#[test]
+	fn test_until_clock() {
+		assert_eq!(clock_delay(3600, 0), 3600);
+		assert_eq!(clock_delay(0, 3600), 82800);
+		assert_eq!(clock_delay(7200, 7200), 0);
+
+		assert!(until_clock("23:30").is_some_and(|d| d.as_secs() < 86400));
+		assert!(until_clock(" 7:05 ").is_some());
+		for s in ["24:00", "12:60", "12", "ab:cd", "12:30:00", "-1:30", "01:005"] {
+			assert_eq!(until_clock(s), None, "{s}");
+		}
+	}
+}
@@ -0,0 +1,31 @@
+/// What the files of a scheme support.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SchemeCaps {
+	pub(super) write:  bool,
+	pub(super) remote: bool,
+	pub(super) watch:  bool,
+	pub(super) trash:  bool,
+	pub(super) poll:   bool,
+}
+
+impl SchemeCaps {
+	/// Whether files can be created, changed or removed.
+	#[inline]
+	pub const fn can_write(self) -> bool { self.write }
+
+	/// Whether the files live on another machine, and each access is costly.
+	#[inline]
+	pub const fn is_remote(self) -> bool { self.remote }
+
+	/// Whether changes to the files can be watched.
+	#[inline]
+	pub const fn supports_watch(self) -> bool { self.watch }
+
+	/// Whether the files can be moved to the trash.
+	#[inline]
+	pub const fn supports_trash(self) -> bool { self.trash }
+
+	/// Whether changes to the files can be found by listing them again at an interval.
+	#[inline]
+	pub const fn supports_poll(self) -> bool { self.poll }
+}
@@ -19,28 +19,23 @@ impl<'a> Encode<'a> {
 
 	#[inline]
 	fn domain<'s>(s: &'s str) -> PercentEncode<'s> {
-		const SET: &AsciiSet = &CONTROLS.add(b'/').add(b':');
+		const SET: &AsciiSet = &CONTROLS.add(b'%').add(b'/').add(b':');
 		percent_encode(s.as_bytes(), SET)
 	}
 
 	#[inline]
-	fn urn(loc: &'a Loc) -> impl Display {
-		struct D(usize, usize);
-
-		impl Display for D {
-			fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-				let (uri, urn) = (self.0, self.1);
-				match (uri != 0, urn != 0) {
-					(true, true) => write!(f, ":{uri}:{urn}"),
-					(true, false) => write!(f, ":{uri}"),
-					(false, true) => write!(f, "::{urn}"),
-					(false, false) => Ok(()),
-				}
-			}
-		}
+	fn urn(loc: &'a Loc) -> impl Display { Port(loc.uri().count(), loc.urn().count(), false) }
 
-		D(loc.uri().count(), loc.urn().count())
+	/// Same as [`Self::urn`], but an empty port is written out if the path has a `#`.
+	#[inline]
+	fn archive_urn(loc: &'a Loc) -> impl Display {
+		let hash = loc.as_os_str().as_encoded_bytes().contains(&b'#');
+		Port(loc.uri().count(), loc.urn().count(), hash)
 	}
+
+	/// The line a grep result points at, written after the path.
+	#[inline]
+	pub(super) fn frag(loc: &'a Loc) -> impl Display { Frag(loc.frag()) }
 }
 
 impl Display for Encode<'_> {
@@ -48,12 +43,46 @@ impl Display for Encode<'_> {
 		match self.scheme {
 			Scheme::Regular => write!(f, "regular://"),
 			Scheme::Search(d) => write!(f, "search://{}{}/", Self::domain(d), Self::urn(self.loc)),
-			Scheme::Archive(d) => write!(f, "archive://{}{}/", Self::domain(d), Self::urn(self.loc)),
+			Scheme::Grep(d) => write!(f, "grep://{}{}/", Self::domain(d), Self::urn(self.loc)),
+			Scheme::Recent => write!(f, "recent://{}/", Self::urn(self.loc)),
+			Scheme::Frecency => write!(f, "frecency://{}/", Self::urn(self.loc)),
+			Scheme::Dupes => write!(f, "dupes://{}/", Self::urn(self.loc)),
+			Scheme::Trash => write!(f, "trash://{}/", Self::urn(self.loc)),
+			Scheme::Archive(d) => {
+				write!(f, "archive://{}{}/", Self::domain(d), Self::archive_urn(self.loc))
+			}
 			Scheme::Sftp(d) => write!(f, "sftp://{}{}/", Self::domain(d), Self::urn(self.loc)),
+			Scheme::Mtp(d) => write!(f, "mtp://{}{}/", Self::domain(d), Self::urn(self.loc)),
+			Scheme::Vfs(d) => write!(f, "vfs://{}{}/", Self::domain(d), Self::urn(self.loc)),
 		}
 	}
 }
 
+// --- Port
+struct Port(usize, usize, bool);
+
+impl Display for Port {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let (uri, urn) = (self.0, self.1);
+		match (uri != 0, urn != 0) {
+			(true, true) => write!(f, ":{uri}:{urn}"),
+			(true, false) => write!(f, ":{uri}"),
+			(false, true) => write!(f, "::{urn}"),
+			(false, false) if self.2 => write!(f, ":"),
+			(false, false) => Ok(()),
+		}
+	}
+}
+
+// --- Frag
+struct Frag(Option<u32>);
+
+impl Display for Frag {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		if let Some(line) = self.0 { write!(f, "#{line}") } else { Ok(()) }
+	}
+}
+
 // --- Tilded
 pub struct EncodeTilded<'a> {
 	loc:    &'a Loc,
@@ -68,12 +97,49 @@ impl Display for EncodeTilded<'_> {
 	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 		use Encode as E;
 
-		let loc = percent_encode(self.loc.as_os_str().as_encoded_bytes(), CONTROLS);
+		// `#` is escaped as well, so it's never mistaken for the archive boundary
+		const SET: &AsciiSet = &CONTROLS.add(b'%').add(b'#');
+
+		let loc = percent_encode(self.loc.as_os_str().as_encoded_bytes(), SET);
 		match self.scheme {
 			Scheme::Regular => write!(f, "regular~://{loc}"),
 			Scheme::Search(d) => write!(f, "search~://{}{}/{loc}", E::domain(d), E::urn(self.loc)),
-			Scheme::Archive(d) => write!(f, "archive~://{}{}/{loc}", E::domain(d), E::urn(self.loc)),
+			Scheme::Grep(d) => {
+				write!(f, "grep~://{}{}/{loc}{}", E::domain(d), E::urn(self.loc), E::frag(self.loc))
+			}
+			Scheme::Recent => write!(f, "recent~://{}/{loc}", E::urn(self.loc)),
+			Scheme::Frecency => write!(f, "frecency~://{}/{loc}", E::urn(self.loc)),
+			Scheme::Dupes => write!(f, "dupes~://{}/{loc}", E::urn(self.loc)),
+			Scheme::Trash => write!(f, "trash~://{}/{loc}", E::urn(self.loc)),
+			Scheme::Archive(d) => {
+				write!(f, "archive~://{}{}/{loc}", E::domain(d), E::archive_urn(self.loc))
+			}
 			Scheme::Sftp(d) => write!(f, "sftp~://{}{}/{loc}", E::domain(d), E::urn(self.loc)),
+			Scheme::Mtp(d) => write!(f, "mtp~://{}{}/{loc}", E::domain(d), E::urn(self.loc)),
+			Scheme::Vfs(d) => write!(f, "vfs~://{}{}/{loc}", E::domain(d), E::urn(self.loc)),
+		}
+	}
+}
+
+// --- Serde
+/// The string form of a URL in serialization, which can always be parsed back into the same URL.
+pub struct EncodeSerde<'a> {
+	url: &'a Url,
+}
+
+impl<'a> From<&'a Url> for EncodeSerde<'a> {
+	fn from(url: &'a Url) -> Self { Self { url } }
+}
+
+impl Display for EncodeSerde<'_> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match (&self.url.scheme, self.url.loc.to_str()) {
+			// A plain path for regular files, unless it could be mistaken for a URL
+			(Scheme::Regular, Some(s)) if !s.contains("://") => f.write_str(s),
+			// A `#` in the path of a grep result could be mistaken for its line
+			(Scheme::Grep(_), Some(s)) if s.contains('#') => EncodeTilded::from(self.url).fmt(f),
+			(_, Some(s)) => write!(f, "{}{s}{}", Encode::from(self.url), Encode::frag(&self.url.loc)),
+			(_, None) => EncodeTilded::from(self.url).fmt(f),
 		}
 	}
 }
@@ -0,0 +1,188 @@
+use std::{borrow::Cow, ffi::OsStr, hash::{Hash, Hasher}, ops::Deref, path::Path, sync::atomic::{AtomicBool, Ordering}};
+
+use serde::{Deserialize, Serialize};
+
+use crate::url::{Scheme, Url, UrnPattern};
+
+static INSENSITIVE: AtomicBool = AtomicBool::new(!UrnPattern::NATIVE_SENSITIVE);
+
+/// A URL as the key of a map or set, ignoring the case of its local part if set.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[repr(transparent)]
+pub struct UrlKey(pub Url);
+
+impl Deref for UrlKey {
+	type Target = Url;
+
+	fn deref(&self) -> &Self::Target { &self.0 }
+}
+
+impl AsRef<Url> for UrlKey {
+	fn as_ref(&self) -> &Url { &self.0 }
+}
+
+impl From<Url> for UrlKey {
+	fn from(value: Url) -> Self { Self(value) }
+}
+
+impl From<UrlKey> for Url {
+	fn from(value: UrlKey) -> Self { value.0 }
+}
+
+impl Hash for UrlKey {
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		if !Self::insensitive() {
+			return self.0.hash(state);
+		}
+
+		let (local, rest) = Self::split(&self.0);
+		self.scheme.hash(state);
+		Path::new(&*fold(local)).hash(state);
+		Path::new(rest).hash(state);
+	}
+}
+
+impl PartialEq for UrlKey {
+	fn eq(&self, other: &Self) -> bool {
+		if Self::insensitive() { self.0.eq_case_insensitive(&other.0) } else { self.0 == other.0 }
+	}
+}
+
+impl Eq for UrlKey {}
+
+impl UrlKey {
+	#[inline]
+	pub fn new<T: AsRef<Url>>(u: &T) -> &Self {
+		unsafe { &*(u.as_ref() as *const Url as *const Self) }
+	}
+
+	/// Whether the keys ignore the case of local paths.
+	#[inline]
+	pub fn insensitive() -> bool { INSENSITIVE.load(Ordering::Relaxed) }
+
+	/// Sets the policy of [`Self::insensitive`].
+	#[inline]
+	pub fn set_insensitive(b: bool) { INSENSITIVE.store(b, Ordering::Relaxed) }
+
+	/// Splits the path of the URL into the local part and the rest.
+	pub(super) fn split(url: &Url) -> (&OsStr, &OsStr) {
+		match url.scheme {
+			Scheme::Regular
+			| Scheme::Search(_)
+			| Scheme::Grep(_)
+			| Scheme::Recent
+			| Scheme::Frecency
+			| Scheme::Dupes
+			| Scheme::Trash => (url.loc.as_os_str(), OsStr::new("")),
+			Scheme::Archive(_) => (url.loc.base().as_os_str(), url.loc.uri().as_os_str()),
+			Scheme::Sftp(_) | Scheme::Mtp(_) | Scheme::Vfs(_) => {
+				(OsStr::new(""), url.loc.as_os_str())
+			}
+		}
+	}
+}
+
+/// Folds the case of the string with the simple one-to-one mappings of Unicode.
+pub(super) fn fold(s: &OsStr) -> Cow<'_, OsStr> {
+	let bytes = s.as_encoded_bytes();
+	if !bytes.iter().any(|&b| b.is_ascii_uppercase() || !b.is_ascii()) {
+		return Cow::Borrowed(s);
+	}
+
+	let mut buf = Vec::with_capacity(bytes.len());
+	for chunk in bytes.utf8_chunks() {
+		for c in chunk.valid().chars() {
+			let mut lower = c.to_lowercase();
+			let c = if lower.len() == 1 { lower.next().unwrap() } else { c };
+			buf.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes());
+		}
+		buf.extend_from_slice(chunk.invalid());
+	}
+
+	// SAFETY: valid UTF-8 is only replaced with valid UTF-8, and the rest is kept
+	Cow::Owned(unsafe { std::ffi::OsString::from_encoded_bytes_unchecked(buf) })
+}
+
+#[cfg(test)]
+mod tests {
+	use std::collections::HashSet;
+
+	use anyhow::Result;
+
+	use super::*;
+
+	// Sets the sensitivity of the keys, restoring the previous one once dropped
+	struct Insensitive(bool);
+
+	impl Insensitive {
+		fn set(b: bool) -> Self {
+			let old = UrlKey::insensitive();
+			UrlKey::set_insensitive(b);
+			Self(old)
+		}
+	}
+
+	impl Drop for Insensitive {
+		fn drop(&mut self) { UrlKey::set_insensitive(self.0) }
+	}
+
+	fn eq(a: &str, b: &str) -> Result<bool> {
+		Ok(a.parse::<Url>()?.eq_case_insensitive(&b.parse()?))
+	}
+
+	#[test]
+	fn test_eq_case_insensitive() -> Result<()> {
+		assert!(eq("/Users/me/Foo", "/users/ME/foo")?);
+		assert!(eq("/tmp/Été.txt", "/tmp/été.TXT")?);
+		assert!(eq("search://kw:1:1//Tmp/A", "search://kw:1:1//tmp/a")?);
+		assert!(!eq("/tmp/a", "/tmp/b")?);
+		assert!(!eq("/tmp/a", "search://kw//tmp/a")?);
+
+		// Entries in archives and paths on remotes are case-sensitive
+		assert!(eq("archive://:1:1//Tmp/A.zip/b", "archive://:1:1//tmp/a.zip/b")?);
+		assert!(!eq("archive://:1:1//tmp/a.zip/B", "archive://:1:1//tmp/a.zip/b")?);
+		assert!(!eq("sftp://remote//Tmp", "sftp://remote//tmp")?);
+
+		// Neither does it change the default behavior
+		assert_ne!("/tmp/A".parse::<Url>()?, "/tmp/a".parse::<Url>()?);
+		Ok(())
+	}
+
+	#[cfg(windows)]
+	#[test]
+	fn test_drive_letter() -> Result<()> {
+		assert!(eq(r"C:\Users\Me", r"c:\users\me")?);
+		assert!(!eq(r"C:\Users\Me", r"D:\Users\Me")?);
+		Ok(())
+	}
+
+	#[cfg(unix)]
+	#[test]
+	fn test_non_utf8() {
+		use std::os::unix::ffi::OsStrExt;
+
+		let a = Url::from(Path::new(OsStr::from_bytes(b"/tmp/A\xff")));
+		let b = Url::from(Path::new(OsStr::from_bytes(b"/tmp/a\xff")));
+		let c = Url::from(Path::new(OsStr::from_bytes(b"/tmp/a\xfe")));
+		assert!(a.eq_case_insensitive(&b));
+		assert!(!a.eq_case_insensitive(&c));
+	}
+
+	#[test]
+	fn test_key() -> Result<()> {
+		let urls =
+			["/tmp/Foo", "/tmp/foo", "/tmp/FOO", "/tmp/Été", "/tmp/été", "sftp://r//A", "sftp://r//a"];
+		let set = || -> Result<HashSet<UrlKey>> {
+			urls.iter().map(|s| s.parse::<Url>().map(UrlKey)).collect()
+		};
+
+		let _guard = Insensitive::set(true);
+		let insensitive = set()?;
+		assert_eq!(insensitive.len(), 4);
+		assert!(insensitive.contains(UrlKey::new(&"/TMP/FOO".parse::<Url>()?)));
+
+		let _guard = Insensitive::set(false);
+		assert_eq!(set()?.len(), urls.len());
+		Ok(())
+	}
+}
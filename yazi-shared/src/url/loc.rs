@@ -9,6 +9,7 @@ pub struct Loc {
 	inner: PathBuf,
 	uri:   usize,
 	urn:   usize,
+	frag:  Option<u32>,
 }
 
 impl Deref for Loc {
@@ -22,13 +23,15 @@ impl AsRef<Path> for Loc {
 }
 
 impl PartialEq for Loc {
-	fn eq(&self, other: &Self) -> bool { self.inner == other.inner }
+	fn eq(&self, other: &Self) -> bool { self.inner == other.inner && self.frag == other.frag }
 }
 
 impl Eq for Loc {}
 
 impl Ord for Loc {
-	fn cmp(&self, other: &Self) -> cmp::Ordering { self.inner.cmp(&other.inner) }
+	fn cmp(&self, other: &Self) -> cmp::Ordering {
+		self.inner.cmp(&other.inner).then_with(|| self.frag.cmp(&other.frag))
+	}
 }
 
 impl PartialOrd for Loc {
@@ -36,7 +39,10 @@ impl PartialOrd for Loc {
 }
 
 impl Hash for Loc {
-	fn hash<H: Hasher>(&self, state: &mut H) { self.inner.hash(state) }
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		self.inner.hash(state);
+		self.frag.hash(state);
+	}
 }
 
 impl Debug for Loc {
@@ -45,6 +51,7 @@ impl Debug for Loc {
 			.field("path", &self.inner)
 			.field("uri", &self.uri())
 			.field("urn", &self.urn())
+			.field("frag", &self.frag)
 			.finish()
 	}
 }
@@ -61,7 +68,7 @@ impl From<PathBuf> for Loc {
 	fn from(path: PathBuf) -> Self {
 		let Some(name) = path.file_name() else {
 			let uri = path.as_os_str().len();
-			return Self { inner: path, uri, urn: 0 };
+			return Self { inner: path, uri, urn: 0, frag: None };
 		};
 
 		let name_len = name.len();
@@ -78,6 +85,7 @@ impl From<PathBuf> for Loc {
 			inner: PathBuf::from(unsafe { OsString::from_encoded_bytes_unchecked(bytes) }),
 			uri:   name_len,
 			urn:   name_len,
+			frag:  None,
 		}
 	}
 }
@@ -182,6 +190,13 @@ impl Loc {
 		self.inner.set_file_name(new);
 	}
 
+	/// The line number the location points at in the file.
+	#[inline]
+	pub fn frag(&self) -> Option<u32> { self.frag }
+
+	#[inline]
+	pub fn set_frag(&mut self, frag: Option<u32>) { self.frag = frag; }
+
 	#[inline]
 	pub fn base(&self) -> &Urn {
 		Urn::new(unsafe {
@@ -212,7 +227,7 @@ impl Loc {
 		let path = parent.join(self.name());
 
 		debug_assert!(path.file_name().is_some_and(|s| s.len() == self.name().len()));
-		Self { inner: path, uri: self.uri, urn: self.uri }
+		Self { inner: path, uri: self.uri, urn: self.uri, frag: None }
 	}
 
 	#[inline]
@@ -1 +1 @@
-yazi_macro::mod_flat!(component cov display encode loc scheme url urn);
+yazi_macro::mod_flat!(caps component cov display encode key loc pattern scheme uri_list url urn);
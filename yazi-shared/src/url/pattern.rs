@@ -0,0 +1,301 @@
+use std::{path::Component, str::FromStr};
+
+use anyhow::{Result, bail};
+
+use crate::url::Urn;
+
+/// A glob matched against the components of a [`Urn`].
+#[derive(Clone, Debug)]
+pub struct UrnPattern {
+	segments:  Vec<Segment>,
+	dir_only:  bool,
+	sensitive: bool,
+}
+
+#[derive(Clone, Debug)]
+enum Segment {
+	Globstar,
+	Tokens(Vec<Token>),
+}
+
+#[derive(Clone, Debug)]
+enum Token {
+	Char(char),
+	Any,
+	Star,
+	Class { negated: bool, ranges: Vec<(char, char)> },
+}
+
+impl FromStr for UrnPattern {
+	type Err = anyhow::Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> { Self::new(s, Self::NATIVE_SENSITIVE) }
+}
+
+impl UrnPattern {
+	/// Whether the file systems of the platform are case-sensitive by default.
+	pub const NATIVE_SENSITIVE: bool = !cfg!(any(windows, target_os = "macos"));
+
+	pub fn new(glob: &str, sensitive: bool) -> Result<Self> {
+		let (glob, dir_only) = match glob.strip_suffix('/') {
+			Some(s) => (s, true),
+			None => (glob, false),
+		};
+		if glob.is_empty() {
+			bail!("Empty glob pattern");
+		}
+
+		let segments = glob
+			.split('/')
+			.map(|s| Self::segment(s).map_err(|e| e.context(format!("Invalid glob pattern `{glob}`"))))
+			.collect::<Result<_>>()?;
+
+		Ok(Self { segments, dir_only, sensitive })
+	}
+
+	/// Whether the pattern only matches directories, i.e. it ends with `/`.
+	#[inline]
+	pub fn dir_only(&self) -> bool { self.dir_only }
+
+	/// Matches the URN of a file that isn't a directory.
+	#[inline]
+	pub fn matches(&self, urn: &Urn) -> bool { !self.dir_only && self.matches_urn(urn) }
+
+	/// Matches the URN of a directory.
+	#[inline]
+	pub fn matches_dir(&self, urn: &Urn) -> bool { self.matches_urn(urn) }
+
+	fn matches_urn(&self, urn: &Urn) -> bool {
+		let components: Vec<_> = urn
+			.components()
+			.filter_map(|c| match c {
+				Component::Normal(s) => Some(s.as_encoded_bytes()),
+				_ => None,
+			})
+			.collect();
+
+		self.match_segments(&self.segments, &components)
+	}
+
+	fn match_segments(&self, segments: &[Segment], components: &[&[u8]]) -> bool {
+		match segments.split_first() {
+			None => components.is_empty(),
+			Some((Segment::Globstar, rest)) => {
+				(0..=components.len()).any(|i| self.match_segments(rest, &components[i..]))
+			}
+			Some((Segment::Tokens(tokens), rest)) => match components.split_first() {
+				Some((first, others)) => {
+					self.match_tokens(tokens, first) && self.match_segments(rest, others)
+				}
+				None => false,
+			},
+		}
+	}
+
+	/// Wildcard matching with backtracking to the last `*`.
+	fn match_tokens(&self, tokens: &[Token], name: &[u8]) -> bool {
+		let (mut t, mut n) = (0, 0);
+		let mut star: Option<(usize, usize)> = None;
+
+		while n < name.len() {
+			let (c, len) = next_char(&name[n..]);
+			match tokens.get(t) {
+				Some(Token::Star) => {
+					star = Some((t, n));
+					t += 1;
+					continue;
+				}
+				Some(token) if self.match_token(token, c) => {
+					(t, n) = (t + 1, n + len);
+					continue;
+				}
+				_ => {}
+			}
+
+			let Some((st, sn)) = star else { return false };
+			let skip = next_char(&name[sn..]).1;
+			star = Some((st, sn + skip));
+			(t, n) = (st + 1, sn + skip);
+		}
+
+		tokens[t..].iter().all(|t| matches!(t, Token::Star))
+	}
+
+	fn match_token(&self, token: &Token, c: Option<char>) -> bool {
+		let Some(c) = c else {
+			// Bytes that aren't valid UTF-8 can only be matched by wildcards
+			return matches!(token, Token::Any);
+		};
+
+		match token {
+			Token::Char(p) => *p == c || (!self.sensitive && eq_ignore_case(*p, c)),
+			Token::Any => true,
+			Token::Star => false,
+			Token::Class { negated, ranges } => {
+				let within = |c: char| ranges.iter().any(|&(lo, hi)| (lo..=hi).contains(&c));
+				let hit = within(c)
+					|| (!self.sensitive && c.to_lowercase().chain(c.to_uppercase()).any(within));
+				hit != *negated
+			}
+		}
+	}
+
+	fn segment(s: &str) -> Result<Segment> {
+		if s == "**" {
+			return Ok(Segment::Globstar);
+		} else if s.is_empty() {
+			bail!("empty path component");
+		} else if s.contains("**") {
+			bail!("`**` must be a whole path component, found in `{s}`");
+		}
+
+		let mut tokens = vec![];
+		let mut it = s.chars().peekable();
+		while let Some(c) = it.next() {
+			tokens.push(match c {
+				'*' => Token::Star,
+				'?' => Token::Any,
+				'[' => Self::class(&mut it)?,
+				c => Token::Char(c),
+			});
+		}
+		Ok(Segment::Tokens(tokens))
+	}
+
+	fn class(it: &mut std::iter::Peekable<std::str::Chars>) -> Result<Token> {
+		let negated = it.next_if(|&c| c == '!' || c == '^').is_some();
+
+		let mut ranges = vec![];
+		let mut first = true;
+		loop {
+			let Some(c) = it.next() else { bail!("unclosed character class, missing `]`") };
+			if c == ']' && !first {
+				break;
+			}
+			first = false;
+
+			if it.peek() == Some(&'-') {
+				let mut ahead = it.clone();
+				ahead.next();
+				if let Some(hi) = ahead.next().filter(|&c| c != ']') {
+					if hi < c {
+						bail!("invalid range `{c}-{hi}` in character class");
+					}
+					*it = ahead;
+					ranges.push((c, hi));
+					continue;
+				}
+			}
+			ranges.push((c, c));
+		}
+
+		Ok(Token::Class { negated, ranges })
+	}
+}
+
+/// Decodes the next character, or a single byte if it isn't valid UTF-8.
+fn next_char(b: &[u8]) -> (Option<char>, usize) {
+	let len = match b[0] {
+		0x00..=0x7f => 1,
+		0xc0..=0xdf => 2,
+		0xe0..=0xef => 3,
+		0xf0..=0xf7 => 4,
+		_ => return (None, 1),
+	};
+
+	match b.get(..len).and_then(|s| str::from_utf8(s).ok()) {
+		Some(s) => (s.chars().next(), len),
+		None => (None, 1),
+	}
+}
+
+#[inline]
+fn eq_ignore_case(a: char, b: char) -> bool { a.to_lowercase().eq(b.to_lowercase()) }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn matches(glob: &str, urn: &str) -> bool { UrnPattern::new(glob, true).unwrap().matches(Urn::new(urn)) }
+
+	#[test]
+	fn test_wildcards() {
+		assert!(matches("*.rs", "main.rs"));
+		assert!(matches("*.rs", ".rs"));
+		assert!(!matches("*.rs", "main.rsx"));
+		assert!(matches("?.txt", "a.txt"));
+		assert!(!matches("?.txt", "ab.txt"));
+		assert!(matches("a*b*c", "aXbYbZc"));
+		assert!(matches("*", "anything"));
+	}
+
+	#[test]
+	fn test_unicode() {
+		assert!(matches("?.txt", "日.txt"));
+		assert!(matches("日本*", "日本語.md"));
+		assert!(matches("[あ-お]*", "いろは"));
+		assert!(!matches("??.txt", "日.txt"));
+
+		assert!(UrnPattern::new("ÉTÉ*", false).unwrap().matches(Urn::new("été.jpg")));
+		assert!(!UrnPattern::new("ÉTÉ*", true).unwrap().matches(Urn::new("été.jpg")));
+		assert!(UrnPattern::new("[A-Z]*", false).unwrap().matches(Urn::new("readme")));
+	}
+
+	#[test]
+	fn test_class() {
+		assert!(matches("a[[]1].txt", "a[1].txt"));
+		assert!(matches("[]]", "]"));
+		assert!(matches("[!a-c]", "d"));
+		assert!(!matches("[^a-c]", "b"));
+		assert!(matches("[a-]", "-"));
+		assert!(matches("[*?]", "?"));
+		assert!(!matches("[*?]", "x"));
+	}
+
+	#[test]
+	fn test_globstar() {
+		// `**` is anchored at the root of the URN
+		assert!(matches("**/*.rs", "main.rs"));
+		assert!(matches("**/*.rs", "src/a/b/main.rs"));
+		assert!(!matches("*.rs", "src/main.rs"));
+		assert!(matches("src/**", "src/a/b"));
+		assert!(matches("src/**", "src"));
+		assert!(!matches("src/**", "lib/a"));
+		assert!(matches("a/**/b", "a/b"));
+		assert!(matches("a/**/b", "a/x/y/b"));
+		assert!(!matches("a/**/b", "x/a/b"));
+	}
+
+	#[test]
+	fn test_dir_only() {
+		let p = UrnPattern::new("node_modules/", true).unwrap();
+		assert!(p.dir_only());
+		assert!(p.matches_dir(Urn::new("node_modules")));
+		assert!(!p.matches(Urn::new("node_modules")));
+	}
+
+	#[cfg(unix)]
+	#[test]
+	fn test_non_utf8() {
+		use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+
+		let urn = Urn::new(OsStr::from_bytes(b"a\xffb.txt"));
+		assert!(UrnPattern::new("a?b.txt", true).unwrap().matches(urn));
+		assert!(UrnPattern::new("a*.txt", true).unwrap().matches(urn));
+		assert!(!UrnPattern::new("a[x]b.txt", true).unwrap().matches(urn));
+	}
+
+	#[test]
+	fn test_invalid() {
+		for (glob, err) in [
+			("", "Empty glob pattern"),
+			("a[bc", "unclosed character class"),
+			("[z-a]", "invalid range `z-a`"),
+			("a**b", "`**` must be a whole path component"),
+			("a//b", "empty path component"),
+		] {
+			let e = format!("{:#}", UrnPattern::new(glob, true).unwrap_err());
+			assert!(e.contains(err), "{glob}: {e}");
+		}
+	}
+}
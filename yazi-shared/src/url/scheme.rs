@@ -3,7 +3,7 @@ use std::borrow::Cow;
 use anyhow::{Result, bail};
 use percent_encoding::percent_decode;
 
-use crate::BytesExt;
+use crate::{BytesExt, url::SchemeCaps};
 
 #[derive(Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub enum Scheme {
@@ -12,9 +12,29 @@ pub enum Scheme {
 
 	Search(String),
 
+	Grep(String),
+
+	/// The files opened lately, see [`Url::is_history`](crate::url::Url::is_history).
+	Recent,
+
+	/// The directories visited most frequently and recently.
+	Frecency,
+
+	/// The files found to have the same content, one group after another.
+	Dupes,
+
+	/// The files in the trash, by where they're stored.
+	Trash,
+
 	Archive(String),
 
 	Sftp(String),
+
+	/// Phones and cameras over MTP, with the domain naming the device.
+	Mtp(String),
+
+	/// Filesystems provided by Lua plugins, with the domain naming the plugin.
+	Vfs(String),
 }
 
 impl Scheme {
@@ -23,16 +43,28 @@ impl Scheme {
 		match self {
 			Self::Regular => "regular",
 			Self::Search(_) => "search",
+			Self::Grep(_) => "grep",
+			Self::Recent => "recent",
+			Self::Frecency => "frecency",
+			Self::Dupes => "dupes",
+			Self::Trash => "trash",
 			Self::Archive(_) => "archive",
 			Self::Sftp(_) => "sftp",
+			Self::Mtp(_) => "mtp",
+			Self::Vfs(_) => "vfs",
 		}
 	}
 
 	#[inline]
 	pub fn domain(&self) -> Option<&str> {
 		match self {
-			Self::Regular => None,
-			Self::Search(s) | Self::Archive(s) | Self::Sftp(s) => Some(s),
+			Self::Regular | Self::Recent | Self::Frecency | Self::Dupes | Self::Trash => None,
+			Self::Search(s)
+			| Self::Grep(s)
+			| Self::Archive(s)
+			| Self::Sftp(s)
+			| Self::Mtp(s)
+			| Self::Vfs(s) => Some(s),
 		}
 	}
 
@@ -56,16 +88,51 @@ impl Scheme {
 		let (scheme, port) = match protocol {
 			b"regular" => (Self::Regular, None),
 			b"search" => {
-				let (domain, uri, urn) = Self::decode_param(rest, skip)?;
-				(Self::Search(domain), Some((uri, urn)))
+				let (domain, port) = Self::decode_param(rest, skip)?;
+				(Self::Search(domain), Some(port.unwrap_or_default()))
+			}
+			b"grep" => {
+				let (domain, port) = Self::decode_param(rest, skip)?;
+				(Self::Grep(domain), Some(port.unwrap_or_default()))
+			}
+			b"recent" | b"frecency" => {
+				let (domain, port) = Self::decode_param(rest, skip)?;
+				if !domain.is_empty() {
+					bail!("History URLs cannot have a domain: {}", String::from_utf8_lossy(bytes));
+				}
+				let scheme = if protocol == b"recent" { Self::Recent } else { Self::Frecency };
+				(scheme, Some(port.unwrap_or_default()))
+			}
+			b"dupes" => {
+				let (domain, port) = Self::decode_param(rest, skip)?;
+				if !domain.is_empty() {
+					bail!("Dupes URLs cannot have a domain: {}", String::from_utf8_lossy(bytes));
+				}
+				(Self::Dupes, Some(port.unwrap_or_default()))
+			}
+			b"trash" => {
+				let (domain, port) = Self::decode_param(rest, skip)?;
+				if !domain.is_empty() {
+					bail!("Trash URLs cannot have a domain: {}", String::from_utf8_lossy(bytes));
+				}
+				(Self::Trash, Some(port.unwrap_or_default()))
 			}
 			b"archive" => {
-				let (domain, uri, urn) = Self::decode_param(rest, skip)?;
-				(Self::Archive(domain), Some((uri, urn)))
+				// Without a port, the boundary is given by the fragment, see `Url::try_from`
+				let (domain, port) = Self::decode_param(rest, skip)?;
+				(Self::Archive(domain), port)
 			}
 			b"sftp" => {
-				let (domain, uri, urn) = Self::decode_param(rest, skip)?;
-				(Self::Sftp(domain), Some((uri, urn)))
+				let (domain, port) = Self::decode_param(rest, skip)?;
+				(Self::Sftp(domain), Some(port.unwrap_or_default()))
+			}
+			b"mtp" => {
+				let (domain, port) = Self::decode_param(rest, skip)?;
+				(Self::Mtp(domain), Some(port.unwrap_or_default()))
+			}
+			b"vfs" => {
+				let (domain, port) = Self::decode_param(rest, skip)?;
+				(Self::Vfs(domain), Some(port.unwrap_or_default()))
 			}
 			_ => bail!("Could not parse protocol from URL: {}", String::from_utf8_lossy(bytes)),
 		};
@@ -78,8 +145,15 @@ impl Scheme {
 		match bytes {
 			b"regular" => Ok("regular"),
 			b"search" => Ok("search"),
+			b"grep" => Ok("grep"),
+			b"recent" => Ok("recent"),
+			b"frecency" => Ok("frecency"),
+			b"dupes" => Ok("dupes"),
+			b"trash" => Ok("trash"),
 			b"archive" => Ok("archive"),
 			b"sftp" => Ok("sftp"),
+			b"mtp" => Ok("mtp"),
+			b"vfs" => Ok("vfs"),
 			_ => bail!("Could not parse protocol from URL: {}", String::from_utf8_lossy(bytes)),
 		}
 	}
@@ -89,26 +163,66 @@ impl Scheme {
 		if self.is_virtual() || other.is_virtual() { self == other } else { true }
 	}
 
+	pub const fn caps(&self) -> SchemeCaps {
+		const fn caps(write: bool, remote: bool, watch: bool, trash: bool) -> SchemeCaps {
+			// Remote files can't be watched, so they're polled instead
+			SchemeCaps { write, remote, watch, trash, poll: remote && !watch }
+		}
+
+		match self {
+			Self::Regular => caps(true, false, true, true),
+			// Search results are a snapshot and not watched for changes
+			Self::Search(_) | Self::Grep(_) => caps(true, false, false, true),
+			Self::Recent | Self::Frecency | Self::Dupes => caps(true, false, false, true),
+			// Trashed files can only be restored or purged, not written to or trashed again
+			Self::Trash => caps(false, false, false, false),
+			Self::Archive(_) => caps(false, false, false, false),
+			Self::Sftp(_) | Self::Mtp(_) | Self::Vfs(_) => {
+				caps(true, true, false, false)
+			}
+		}
+	}
+
 	#[inline]
 	pub fn is_virtual(&self) -> bool {
 		match self {
-			Self::Regular | Self::Search(_) => false,
-			Self::Archive(_) | Self::Sftp(_) => true,
+			Self::Regular
+			| Self::Search(_)
+			| Self::Grep(_)
+			| Self::Recent
+			| Self::Frecency
+			| Self::Dupes
+			| Self::Trash => false,
+			Self::Archive(_)
+			| Self::Sftp(_)
+			| Self::Mtp(_)
+			| Self::Vfs(_) => true,
 		}
 	}
 
-	fn decode_param(bytes: &[u8], skip: &mut usize) -> Result<(String, usize, usize)> {
+	/// Whether the files are listed in place of the regular ones they stand for.
+	#[inline]
+	pub fn is_listing(&self) -> bool {
+		matches!(
+			self,
+			Self::Search(_) | Self::Grep(_) | Self::Recent | Self::Frecency | Self::Dupes | Self::Trash
+		)
+	}
+
+	fn decode_param(bytes: &[u8], skip: &mut usize) -> Result<(String, Option<(usize, usize)>)> {
 		let mut len = bytes.iter().copied().take_while(|&b| b != b'/').count();
 		let slash = bytes.get(len).is_some_and(|&b| b == b'/');
 		*skip += len + slash as usize;
 
-		let (uri, urn) = Self::decode_port(&bytes[..len], &mut len)?;
+		// A `:` in the domain is always escaped, so an unescaped one starts the port
+		let explicit = bytes[..len].contains(&b':');
+		let port = Self::decode_port(&bytes[..len], &mut len)?;
 		let domain = match Cow::from(percent_decode(&bytes[..len])) {
 			Cow::Borrowed(b) => str::from_utf8(b)?.to_owned(),
 			Cow::Owned(b) => String::from_utf8(b)?,
 		};
 
-		Ok((domain, uri, urn))
+		Ok((domain, Some(port).filter(|_| explicit)))
 	}
 
 	fn decode_port(bytes: &[u8], skip: &mut usize) -> anyhow::Result<(usize, usize)> {
@@ -155,4 +269,18 @@ mod tests {
 		assert(":22:11", 22, 11, 6)?;
 		Ok(())
 	}
+
+	#[test]
+	fn test_caps() {
+		assert!(Scheme::Regular.caps().supports_watch());
+		assert!(!Scheme::Search("kw".to_owned()).caps().supports_watch());
+		assert!(Scheme::Mtp("phone".to_owned()).caps().supports_poll());
+		assert!(Scheme::Vfs("gdrive".to_owned()).caps().supports_poll());
+		assert!(!Scheme::Regular.caps().supports_poll());
+		assert!(!Scheme::Archive(String::new()).caps().can_write());
+		assert!(Scheme::Sftp("remote".to_owned()).caps().is_remote());
+		assert!(!Scheme::Sftp("remote".to_owned()).caps().supports_trash());
+		assert!(!Scheme::Trash.caps().supports_trash());
+		assert!(Scheme::Dupes.is_listing() && !Scheme::Archive(String::new()).is_listing());
+	}
 }
@@ -0,0 +1,85 @@
+use std::path::PathBuf;
+
+use percent_encoding::{AsciiSet, NON_ALPHANUMERIC, percent_decode_str, percent_encode};
+
+use crate::IntoOsStr;
+
+/// Local files as a list of `file://` URIs.
+#[derive(Debug, Default, Eq, PartialEq)]
+pub struct UriList {
+	pub cut:   bool,
+	pub paths: Vec<PathBuf>,
+}
+
+impl UriList {
+	/// The list of RFC 2483, which has no idea of cutting.
+	pub const MIME: &str = "text/uri-list";
+	/// The list with a leading `cut` or `copy` line, used by GNOME, Xfce, etc.
+	pub const MIME_GNOME: &str = "x-special/gnome-copied-files";
+
+	const SET: &AsciiSet =
+		&NON_ALPHANUMERIC.remove(b'/').remove(b'-').remove(b'_').remove(b'.').remove(b'~');
+
+	pub fn new(cut: bool, paths: Vec<PathBuf>) -> Self { Self { cut, paths } }
+
+	/// Parses both forms, where the URIs of other hosts or schemes are ignored.
+	pub fn parse(s: &str) -> Self {
+		let mut me = Self::default();
+		for line in s.lines().map(str::trim) {
+			match line {
+				"cut" if me.paths.is_empty() => me.cut = true,
+				"copy" if me.paths.is_empty() => {}
+				_ if line.is_empty() || line.starts_with('#') => {}
+				_ => me.paths.extend(Self::path(line)),
+			}
+		}
+		me
+	}
+
+	pub fn encode(&self, gnome: bool) -> String {
+		let uris: Vec<_> = self
+			.paths
+			.iter()
+			.map(|p| format!("file://{}", percent_encode(p.as_os_str().as_encoded_bytes(), Self::SET)))
+			.collect();
+
+		if gnome {
+			format!("{}\n{}", if self.cut { "cut" } else { "copy" }, uris.join("\n"))
+		} else {
+			uris.into_iter().map(|u| u + "\r\n").collect()
+		}
+	}
+
+	fn path(uri: &str) -> Option<PathBuf> {
+		let rest = uri.strip_prefix("file://")?;
+		let path = match rest.find('/')? {
+			0 => rest,
+			i if rest[..i].eq_ignore_ascii_case("localhost") => &rest[i..],
+			_ => return None,
+		};
+
+		let bytes = percent_decode_str(path).collect::<Vec<_>>();
+		Some(bytes.as_slice().into_os_str().ok()?.into_owned().into())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_uri_list() {
+		let list = UriList::new(false, vec!["/a/b c".into(), "/日本/100%.txt".into()]);
+		let s = list.encode(false);
+		assert_eq!(s, "file:///a/b%20c\r\nfile:///%E6%97%A5%E6%9C%AC/100%25.txt\r\n");
+		assert_eq!(UriList::parse(&s), list);
+
+		let list = UriList::new(true, vec!["/a".into(), "/b".into()]);
+		assert_eq!(list.encode(true), "cut\nfile:///a\nfile:///b");
+		assert_eq!(UriList::parse(&list.encode(true)), list);
+
+		let s = "# comment\nfile://localhost/x\nfile://remote/y\nhttps://example.com/z\n\nfile:///w";
+		assert_eq!(UriList::parse(s), UriList::new(false, vec!["/x".into(), "/w".into()]));
+		assert_eq!(UriList::parse("copy\nfile:///a"), UriList::new(false, vec!["/a".into()]));
+	}
+}
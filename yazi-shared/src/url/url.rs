@@ -1,18 +1,48 @@
-use std::{borrow::Cow, ffi::OsStr, fmt::{Debug, Formatter}, hash::BuildHasher, ops::Deref, path::{Path, PathBuf}, str::FromStr};
+use std::{borrow::Cow, ffi::OsStr, fmt::{Debug, Formatter}, hash::{BuildHasher, Hash, Hasher}, ops::Deref, path::{MAIN_SEPARATOR_STR, Path, PathBuf}, str::FromStr};
 
-use anyhow::Result;
+use anyhow::{Result, bail};
 use percent_encoding::percent_decode;
 use serde::{Deserialize, Serialize};
 
 use super::UrnBuf;
-use crate::{IntoOsStr, url::{Components, Display, Encode, EncodeTilded, Loc, Scheme, Urn}};
+use crate::{IntoOsStr, url::{Components, Display, Encode, EncodeSerde, Loc, Scheme, SchemeCaps, UrlKey, Urn, UrnPattern, fold}};
 
-#[derive(Clone, Default, Eq, Ord, PartialOrd, PartialEq, Hash)]
+#[derive(Clone, Default)]
 pub struct Url {
 	pub loc:    Loc,
 	pub scheme: Scheme,
 }
 
+impl PartialEq for Url {
+	fn eq(&self, other: &Self) -> bool {
+		self.loc == other.loc && self.scheme == other.scheme && self.boundary() == other.boundary()
+	}
+}
+
+impl Eq for Url {}
+
+impl Ord for Url {
+	fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+		self
+			.loc
+			.cmp(&other.loc)
+			.then_with(|| self.scheme.cmp(&other.scheme))
+			.then_with(|| self.boundary().cmp(&other.boundary()))
+	}
+}
+
+impl PartialOrd for Url {
+	fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> { Some(self.cmp(other)) }
+}
+
+impl Hash for Url {
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		self.loc.hash(state);
+		self.scheme.hash(state);
+		self.boundary().hash(state);
+	}
+}
+
 impl Deref for Url {
 	type Target = Loc;
 
@@ -43,11 +73,12 @@ impl TryFrom<&[u8]> for Url {
 	type Error = anyhow::Error;
 
 	fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
-		let (scheme, path, port) = Self::parse(bytes)?;
+		let (scheme, path, port, frag) = Self::parse(bytes)?;
 
-		let loc =
+		let mut loc =
 			if let Some((uri, urn)) = port { Loc::with(path, uri, urn)? } else { Loc::from(path) };
 
+		loc.set_frag(frag);
 		Ok(Self { loc, scheme })
 	}
 }
@@ -94,26 +125,79 @@ impl Url {
 		match self.scheme {
 			S::Regular => Self { loc, scheme: S::Regular },
 			S::Search(_) => Self { loc, scheme: self.scheme.clone() },
+			S::Grep(_) => Self { loc, scheme: self.scheme.clone() },
+			S::Recent | S::Frecency | S::Dupes | S::Trash => Self { loc, scheme: self.scheme.clone() },
 			S::Archive(_) => Self { loc, scheme: self.scheme.clone() },
 			S::Sftp(_) => Self { loc, scheme: self.scheme.clone() },
+			S::Mtp(_) => Self { loc, scheme: self.scheme.clone() },
+			S::Vfs(_) => Self { loc, scheme: self.scheme.clone() },
 		}
 	}
 
+	/// Joins a path to the URL.
 	pub fn join(&self, path: impl AsRef<Path>) -> Self {
 		use Scheme as S;
 
-		let join = self.loc.join(path);
+		let path = path.as_ref();
+		if let Some(url) = self.try_join(path) {
+			return url;
+		}
+
+		let loc: Loc = self.loc.join(path).into();
+		match self.scheme {
+			S::Regular
+			| S::Search(_)
+			| S::Grep(_)
+			| S::Recent
+			| S::Frecency
+			| S::Dupes
+			| S::Trash
+			| S::Archive(_) => Self { loc, scheme: S::Regular },
+			S::Sftp(_) | S::Mtp(_) | S::Vfs(_) => {
+				Self { loc, scheme: self.scheme.clone() }
+			}
+		}
+	}
+
+	/// Same as [`Url::join`], but a search URL keeps its keyword.
+	pub fn join_keeping_frag(&self, path: impl AsRef<Path>) -> Self {
+		let path = path.as_ref();
+		match self.scheme {
+			Scheme::Search(_) | Scheme::Grep(_) if !Self::joinable(path) => {
+				Self { loc: Loc::zeroed(self.loc.join(path)), scheme: self.scheme.clone() }
+			}
+			_ => self.join(path),
+		}
+	}
 
+	/// Joins a relative path to the URL, or returns `None` if it would escape it.
+	pub fn try_join(&self, path: impl AsRef<Path>) -> Option<Self> {
+		use Scheme as S;
+
+		let path = path.as_ref();
+		if !Self::joinable(path) {
+			return None;
+		}
+
+		let join = self.loc.join(path);
 		let loc = match self.scheme {
 			S::Regular => join.into(),
-			S::Search(_) => Loc::new(join, self.loc.base(), self.loc.base()),
+			S::Search(_) | S::Grep(_) => Loc::new(join, self.loc.base(), self.loc.base()),
+			S::Recent | S::Frecency | S::Dupes | S::Trash => {
+				Loc::new(join, self.loc.base(), self.loc.base())
+			}
 			S::Archive(_) => Loc::floated(join, self.loc.base()),
 			S::Sftp(_) => join.into(),
+			S::Mtp(_) => join.into(),
+			S::Vfs(_) => join.into(),
 		};
 
-		Self { loc, scheme: self.scheme.clone() }
+		Some(Self { loc, scheme: self.scheme.clone() })
 	}
 
+	#[inline]
+	pub fn caps(&self) -> SchemeCaps { self.scheme.caps() }
+
 	#[inline]
 	pub fn components(&self) -> Components<'_> { Components::new(self) }
 
@@ -122,6 +206,14 @@ impl Url {
 		self.scheme.covariant(&other.scheme) && self.loc == other.loc
 	}
 
+	/// Whether the two URLs are the same when the case of their local part is ignored.
+	pub fn eq_case_insensitive(&self, other: &Self) -> bool {
+		let ((a, a_rest), (b, b_rest)) = (UrlKey::split(self), UrlKey::split(other));
+		self.scheme == other.scheme
+			&& Path::new(&*fold(a)) == Path::new(&*fold(b))
+			&& Path::new(a_rest) == Path::new(b_rest)
+	}
+
 	#[inline]
 	pub fn display(&self) -> Display<'_> { Display::new(self) }
 
@@ -139,8 +231,16 @@ impl Url {
 			S::Regular => Self { loc: parent.into(), scheme: S::Regular },
 
 			// Search
-			S::Search(_) if uri.is_empty() => Self { loc: parent.into(), scheme: S::Regular },
-			S::Search(_) => Self {
+			S::Search(_) | S::Grep(_) if uri.is_empty() => {
+				Self { loc: parent.into(), scheme: S::Regular }
+			}
+			S::Search(_) | S::Grep(_) => Self {
+				loc:    Loc::new(parent, self.loc.base(), self.loc.base()),
+				scheme: self.scheme.clone(),
+			},
+
+			// History, duplicates and the trash
+			S::Recent | S::Frecency | S::Dupes | S::Trash => Self {
 				loc:    Loc::new(parent, self.loc.base(), self.loc.base()),
 				scheme: self.scheme.clone(),
 			},
@@ -156,6 +256,12 @@ impl Url {
 
 			// SFTP
 			S::Sftp(_) => Self { loc: parent.into(), scheme: self.scheme.clone() },
+
+			// MTP
+			S::Mtp(_) => Self { loc: parent.into(), scheme: self.scheme.clone() },
+
+			// Lua providers
+			S::Vfs(_) => Self { loc: parent.into(), scheme: self.scheme.clone() },
 		})
 	}
 
@@ -165,30 +271,15 @@ impl Url {
 		let base = base.as_ref();
 		let prefix = self.loc.strip_prefix(&base.loc).ok()?;
 
+		let plain = |s: &Scheme| matches!(s, S::Regular | S::Search(_) | S::Grep(_));
 		Some(Urn::new(match (&self.scheme, &base.scheme) {
-			// Same scheme
-			(S::Regular, S::Regular) => Some(prefix),
-			(S::Search(_), S::Search(_)) => Some(prefix),
-			(S::Archive(a), S::Archive(b)) => Some(prefix).filter(|_| a == b),
-			(S::Sftp(a), S::Sftp(b)) => Some(prefix).filter(|_| a == b),
-
-			// Both are local files
-			(S::Regular, S::Search(_)) => Some(prefix),
-			(S::Search(_), S::Regular) => Some(prefix),
-
 			// Only the entry of archives is a local file
-			(S::Regular, S::Archive(_)) => Some(prefix).filter(|_| base.uri().is_empty()),
-			(S::Search(_), S::Archive(_)) => Some(prefix).filter(|_| base.uri().is_empty()),
-			(S::Archive(_), S::Regular) => Some(prefix).filter(|_| self.uri().is_empty()),
-			(S::Archive(_), S::Search(_)) => Some(prefix).filter(|_| self.uri().is_empty()),
-
-			// Independent virtual file space
-			(S::Regular, S::Sftp(_)) => None,
-			(S::Search(_), S::Sftp(_)) => None,
-			(S::Archive(_), S::Sftp(_)) => None,
-			(S::Sftp(_), S::Regular) => None,
-			(S::Sftp(_), S::Search(_)) => None,
-			(S::Sftp(_), S::Archive(_)) => None,
+			(S::Archive(_), b) if plain(b) => Some(prefix).filter(|_| self.uri().is_empty()),
+			(a, S::Archive(_)) if plain(a) => Some(prefix).filter(|_| base.uri().is_empty()),
+
+			// History entries and duplicates are local files as well, but not each other's
+			(a, b) if plain(a) || plain(b) => Some(prefix).filter(|_| a.covariant(b)),
+			(a, b) => Some(prefix).filter(|_| a == b),
 		}?))
 	}
 
@@ -197,37 +288,117 @@ impl Url {
 		Some(self.loc.as_path()).filter(|_| !self.scheme.is_virtual())
 	}
 
+	/// Whether the URN of the URL matches the pattern, as a file that isn't a directory.
 	#[inline]
-	pub fn set_name(&mut self, name: impl AsRef<OsStr>) { self.loc.set_name(name); }
+	pub fn matches(&self, pattern: &UrnPattern) -> bool { pattern.matches(self.loc.urn()) }
 
 	#[inline]
-	pub fn pair(&self) -> Option<(Self, UrnBuf)> { Some((self.parent_url()?, self.loc.urn_owned())) }
+	pub fn set_name(&mut self, name: impl AsRef<OsStr>) { self.loc.set_name(name); }
+
+	pub fn pair(&self) -> Option<(Self, UrnBuf)> {
+		let urn = match self.scheme {
+			// Always the name of the entry, or of the archive itself for its root
+			Scheme::Archive(_) => Urn::new(self.loc.name()).to_owned(),
+			_ => self.loc.urn_owned(),
+		};
+		Some((self.parent_url()?, urn))
+	}
 
 	#[inline]
 	pub fn hash_u64(&self) -> u64 { foldhash::fast::FixedState::default().hash_one(self) }
 
+	/// Moves the URL under `parent`, keeping only its name.
+	pub fn rebase(&self, parent: &Url) -> Self {
+		match self.scheme {
+			Scheme::Regular if parent.is_regular() => self.loc.rebase(parent).into(),
+			_ => parent.join(self.name()),
+		}
+	}
+
+	/// Same as [`Url::rebase`], but for a regular `parent` given as a path.
 	#[inline]
-	pub fn rebase(&self, parent: &Path) -> Self {
-		debug_assert!(self.is_regular());
-		self.loc.rebase(parent).into()
+	pub fn rebase_path(&self, parent: &Path) -> Self { self.rebase(&parent.into()) }
+
+	/// Moves the URL from under `root` to under `parent`, keeping its relative path.
+	pub fn rebase_keeping_structure(&self, root: &Url, parent: &Url) -> Option<Self> {
+		let rel = self.strip_prefix(root)?;
+		if rel.as_os_str().is_empty() { Some(parent.clone()) } else { parent.try_join(rel) }
 	}
 
-	pub fn parse(bytes: &[u8]) -> Result<(Scheme, PathBuf, Option<(usize, usize)>)> {
+	pub fn parse(bytes: &[u8]) -> Result<(Scheme, PathBuf, Option<(usize, usize)>, Option<u32>)> {
 		let mut skip = 0;
 		let (scheme, tilde, port) = Scheme::parse(bytes, &mut skip)?;
 
-		let rest = if tilde {
-			Cow::from(percent_decode(&bytes[skip..])).into_os_str()?
-		} else {
-			bytes[skip..].into_os_str()?
+		let mut rest = &bytes[skip..];
+		if let Scheme::Grep(_) = scheme {
+			// A trailing `#<line>` is the line of a grep result, a `#` in the path
+			// itself is escaped in the tilded form, see `EncodeSerde`
+			let frag = Self::decode_frag(rest);
+			if frag.is_some() {
+				rest = &rest[..rest.iter().rposition(|&b| b == b'#').unwrap()];
+			}
+			return Ok((scheme, Self::decode_path(rest, tilde)?, port, frag));
+		} else if matches!(scheme, Scheme::Recent | Scheme::Frecency | Scheme::Dupes | Scheme::Trash)
+			&& rest.is_empty()
+		{
+			// A bare `recent://` is the listing itself, which is rooted at the root
+			return Ok((scheme, PathBuf::from(MAIN_SEPARATOR_STR), port, None));
+		} else if port.is_some() || !matches!(scheme, Scheme::Archive(_)) {
+			return Ok((scheme, Self::decode_path(rest, tilde)?, port, None));
+		}
+
+		// Without a port, an unescaped `#` splits `/a/b.zip#c/d` into the archive
+		// `/a/b.zip` and the entry `c/d` in it
+		let Some(i) = rest.iter().position(|&b| b == b'#') else {
+			return Ok((scheme, Self::decode_path(rest, tilde)?, Some((0, 0)), None));
 		};
 
-		let path = match rest {
+		let mut path = Self::decode_path(&rest[..i], tilde)?;
+		let entry = Self::decode_path(&rest[i + 1..], tilde)?;
+		if entry.as_os_str().is_empty() {
+			return Ok((scheme, path, Some((0, 0)), None));
+		} else if !entry.components().all(|c| matches!(c, std::path::Component::Normal(_))) {
+			bail!("Invalid archive entry in URL: {}", String::from_utf8_lossy(bytes));
+		}
+
+		let uri = entry.components().count();
+		path.push(entry);
+		Ok((scheme, path, Some((uri, 1)), None))
+	}
+
+	fn decode_frag(bytes: &[u8]) -> Option<u32> {
+		let i = bytes.iter().rposition(|&b| b == b'#')?;
+		let digits = &bytes[i + 1..];
+		if digits.is_empty() || !digits.iter().all(u8::is_ascii_digit) {
+			return None;
+		}
+		str::from_utf8(digits).ok()?.parse().ok()
+	}
+
+	fn decode_path(bytes: &[u8], tilde: bool) -> Result<PathBuf> {
+		let s =
+			if tilde { Cow::from(percent_decode(bytes)).into_os_str()? } else { bytes.into_os_str()? };
+
+		Ok(match s {
 			Cow::Borrowed(s) => Path::new(s).to_owned(),
 			Cow::Owned(s) => PathBuf::from(s),
-		};
+		})
+	}
 
-		Ok((scheme, path, port))
+	/// Where the archive ends in the location.
+	#[inline]
+	fn boundary(&self) -> usize {
+		match self.scheme {
+			Scheme::Archive(_) => self.loc.uri().as_os_str().len(),
+			_ => 0,
+		}
+	}
+
+	/// Whether the path only consists of normal components, `.` and `..`.
+	fn joinable(path: &Path) -> bool {
+		use std::path::Component as C;
+
+		path.components().all(|c| matches!(c, C::Normal(_) | C::CurDir | C::ParentDir))
 	}
 }
 
@@ -249,8 +420,55 @@ impl Url {
 	}
 
 	// --- Search
+	/// Whether the URL is in the results of a search, by name or by content.
+	#[inline]
+	pub fn is_search(&self) -> bool {
+		matches!(self.scheme, Scheme::Search(_) | Scheme::Grep(_))
+	}
+
+	#[inline]
+	pub fn is_grep(&self) -> bool { matches!(self.scheme, Scheme::Grep(_)) }
+
+	// --- History
+	/// Whether the URL is in the listing of `recent://` or `frecency://`.
 	#[inline]
-	pub fn is_search(&self) -> bool { matches!(self.scheme, Scheme::Search(_)) }
+	pub fn is_history(&self) -> bool { matches!(self.scheme, Scheme::Recent | Scheme::Frecency) }
+
+	/// Whether the URL is in the listing of the duplicates found.
+	#[inline]
+	pub fn is_dupes(&self) -> bool { self.scheme == Scheme::Dupes }
+
+	/// Whether the URL is in the listing of the trash, by where the files are stored.
+	#[inline]
+	pub fn is_trash(&self) -> bool { self.scheme == Scheme::Trash }
+
+	/// Whether the URL is in a listing, standing for the regular file it turns back into.
+	#[inline]
+	pub fn is_listing(&self) -> bool { self.scheme.is_listing() }
+
+	/// Whether the URL is the root of a listing not under any directory, unlike the search.
+	#[inline]
+	pub fn is_listing_root(&self) -> bool {
+		self.is_listing() && !self.is_search() && self.urn().is_empty()
+	}
+
+	/// The keyword of the search, which is carried by the results as well, however deep they are.
+	#[inline]
+	pub fn keyword(&self) -> Option<Cow<'_, str>> {
+		match &self.scheme {
+			Scheme::Search(kw) | Scheme::Grep(kw) => Some(Cow::Borrowed(kw)),
+			_ => None,
+		}
+	}
+
+	/// Replaces the keyword of a search URL, other URLs are left as is.
+	#[inline]
+	pub fn with_keyword(mut self, keyword: impl AsRef<str>) -> Self {
+		if let Scheme::Search(kw) | Scheme::Grep(kw) = &mut self.scheme {
+			keyword.as_ref().clone_into(kw);
+		}
+		self
+	}
 
 	#[inline]
 	pub fn to_search(&self, domain: impl AsRef<str>) -> Self {
@@ -267,6 +485,18 @@ impl Url {
 		self
 	}
 
+	#[inline]
+	pub fn to_grep(&self, domain: impl AsRef<str>) -> Self {
+		Self { loc: Loc::zeroed(self.loc.to_path()), scheme: Scheme::Grep(domain.as_ref().to_owned()) }
+	}
+
+	/// Points the URL at a line of the file, as a result of a grep.
+	#[inline]
+	pub fn with_frag(mut self, frag: Option<u32>) -> Self {
+		self.loc.set_frag(frag);
+		self
+	}
+
 	// --- Archive
 	#[inline]
 	pub fn is_archive(&self) -> bool { matches!(self.scheme, Scheme::Archive(_)) }
@@ -278,18 +508,13 @@ impl Url {
 
 impl Debug for Url {
 	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-		write!(f, "{}{}", Encode::from(self), self.loc.display())
+		write!(f, "{}{}{}", Encode::from(self), self.loc.display(), Encode::frag(&self.loc))
 	}
 }
 
 impl Serialize for Url {
 	fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
-		let Url { scheme, loc } = self;
-		match (scheme.is_virtual(), loc.to_str()) {
-			(false, Some(s)) => serializer.serialize_str(s),
-			(true, Some(s)) => serializer.serialize_str(&format!("{}{s}", Encode::from(self))),
-			(_, None) => serializer.collect_str(&EncodeTilded::from(self)),
-		}
+		serializer.collect_str(&EncodeSerde::from(self))
 	}
 }
 
@@ -328,6 +553,11 @@ mod tests {
 			// Relative
 			("search://kw", "b/c", "search://kw:2:2/b/c"),
 			("search://kw/", "b/c", "search://kw:2:2/b/c"),
+			// Absolute
+			("/a", "/b", "regular:///b"),
+			("search://kw:2:2//a/b/c", "/d", "regular:///d"),
+			("archive://:2:1//a/b.zip/c/d", "/e", "regular:///e"),
+			("sftp://remote:1:1//a/b", "/c", "sftp://remote:1:1//c"),
 		];
 
 		for (base, path, expected) in cases {
@@ -341,6 +571,387 @@ mod tests {
 		Ok(())
 	}
 
+	#[test]
+	fn test_join_keeping_frag() -> Result<()> {
+		let cases = [
+			("search://kw:2:2//a/b/c", "d", "search://kw:3:3//a/b/c/d"),
+			("search://kw:2:2//a/b/c", "/d", "search://kw//d"),
+			("archive://:2:1//a/b.zip/c/d", "/e", "regular:///e"),
+		];
+
+		for (base, path, expected) in cases {
+			let base: Url = base.parse()?;
+			assert_eq!(format!("{:?}", base.join_keeping_frag(path)).replace(r"\", "/"), expected);
+		}
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_try_join() -> Result<()> {
+		let url: Url = "search://kw:1:1//a/b".parse()?;
+		assert!(url.try_join("c/../d").is_some());
+		assert!(url.try_join("/c").is_none());
+
+		#[cfg(windows)]
+		{
+			let url: Url = r"search://kw:1:1/C:\a\b".parse()?;
+			assert!(url.try_join(r"\c").is_none());
+			assert!(url.try_join("C:c").is_none());
+			assert!(url.try_join("D:c").is_none());
+
+			// Rooted paths stay on the same drive, drive-relative ones replace it
+			assert_eq!(format!("{:?}", url.join(r"\c")), r"regular://C:\c");
+			assert_eq!(format!("{:?}", url.join("D:c")), "regular://D:c");
+		}
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_join_parent() -> Result<()> {
+		let urls = [
+			"/a",
+			"search://kw//a",
+			"search://kw:1:1//a/b",
+			"archive:////a/b.zip",
+			"archive://:1:1//a/b.zip/c",
+			"sftp://remote//a",
+			"sftp://remote:1:1//a/b",
+		];
+
+		for url in urls {
+			let url: Url = url.parse()?;
+			for path in ["b", "b/c", "./b", "b/../c"] {
+				let mut u = url.join(path);
+				assert_eq!(u.scheme, url.scheme, "{url:?} + {path}");
+				assert!(u.strip_prefix(&url).is_some(), "{url:?} + {path} = {u:?}");
+
+				while u != url {
+					u = u.parent_url().unwrap_or_else(|| panic!("{url:?} + {path} never reaches back"));
+				}
+			}
+		}
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_strip_prefix() -> Result<()> {
+		let cases = [
+			// Local
+			("/a/b", "/a", Some("b")),
+			("search://kw:1:1//a/b", "/a", Some("b")),
+			("/a/b", "grep://kw//a", Some("b")),
+			("recent://:2:2//a/b", "search://kw//a", Some("b")),
+			("recent://:2:2//a/b", "recent:////a", Some("b")),
+			("recent://:2:2//a/b", "frecency:////a", None),
+			("/a/b", "dupes:////a", Some("b")),
+			("dupes://:2:2//a/b", "recent:////a", None),
+			// Archive
+			("archive://:1:1//a/b.zip/c", "/a", None),
+			("archive://:1:1//a/b.zip/c", "archive://:1//a/b.zip", Some("c")),
+			("archive://:1:1//a/b.zip/c", "archive://x:1//a/b.zip", None),
+			("archive://:1:1//a/b.zip/c", "recent:////a", None),
+			// Remote
+			("sftp://remote:1:1//a/b", "sftp://remote//a", Some("b")),
+			("sftp://remote:1:1//a/b", "sftp://work//a", None),
+			("sftp://remote:1:1//a/b", "/a", None),
+			("/a/b", "mtp://phone//a", None),
+		];
+
+		for (url, base, expected) in cases {
+			let (url, base): (Url, Url) = (url.parse()?, base.parse()?);
+			let urn = url.strip_prefix(&base).map(|u| u.as_os_str());
+			assert_eq!(urn, expected.map(OsStr::new), "{url:?} without {base:?}");
+		}
+		Ok(())
+	}
+
+	#[test]
+	fn test_rebase() -> Result<()> {
+		let cases = [
+			// Regular
+			("/a/b", "/c", "regular:///c/b"),
+			("/a/b", "search://kw:1:1//c/d", "search://kw:2:2//c/d/b"),
+			("/a/b", "sftp://work//uploads", "sftp://work:1:1//uploads/b"),
+			// Search
+			("search://kw:2:2//a/b/c", "/d", "regular:///d/c"),
+			("search://kw:2:2//a/b/c", "search://kw//a", "search://kw:1:1//a/c"),
+			("search://kw:2:2//a/b/c", "search://new:1:1//d/e", "search://new:2:2//d/e/c"),
+			("search://kw:2:2//a/b/c", "sftp://work//uploads", "sftp://work:1:1//uploads/c"),
+			// Archive
+			("archive://:2:1//a/b.zip/c/d", "/e", "regular:///e/d"),
+			("archive://:2:1//a/b.zip/c/d", "sftp://work//uploads", "sftp://work:1:1//uploads/d"),
+			// SFTP
+			("sftp://remote:1:1//a/b", "/c", "regular:///c/b"),
+			("sftp://remote:1:1//a/b", "search://kw//c", "search://kw:1:1//c/b"),
+			("sftp://remote:1:1//a/b", "sftp://remote:1:1//c/d", "sftp://remote:1:1//c/d/b"),
+			("sftp://remote:1:1//a/b", "sftp://work//uploads", "sftp://work:1:1//uploads/b"),
+		];
+
+		for (url, parent, expected) in cases {
+			let (url, parent): (Url, Url) = (url.parse()?, parent.parse()?);
+			let rebased = url.rebase(&parent);
+			assert_eq!(format!("{rebased:?}").replace(r"\", "/"), expected, "{url:?} onto {parent:?}");
+			assert_eq!(rebased.scheme, parent.scheme);
+			assert_eq!(rebased.parent_url().as_ref(), Some(&parent));
+		}
+
+		assert_eq!(
+			Url::from(Path::new("/a/b")).rebase_path(Path::new("/c")),
+			Url::from(Path::new("/c/b"))
+		);
+		Ok(())
+	}
+
+	#[test]
+	fn test_rebase_keeping_structure() -> Result<()> {
+		let cases = [
+			("/a/b/c", "/a", "/d", Some("regular:///d/b/c")),
+			("/a/b/c", "/a", "sftp://work//uploads", Some("sftp://work:1:1//uploads/b/c")),
+			("search://kw:2:2//a/b/c", "/a", "/d", Some("regular:///d/b/c")),
+			("search://kw:2:2//a/b/c", "search://kw//a", "/d", Some("regular:///d/b/c")),
+			("sftp://remote:1:1//a/b/c", "sftp://remote//a", "/d", Some("regular:///d/b/c")),
+			("archive://:2:1//a/b.zip/c/d", "archive:////a/b.zip", "/e", Some("regular:///e/c/d")),
+			// The root itself
+			("/a", "/a", "/d", Some("regular:///d")),
+			// Not under the root
+			("/a/b", "/c", "/d", None),
+			("sftp://remote:1:1//a/b", "/a", "/d", None),
+			("sftp://remote:1:1//a/b", "sftp://other//a", "/d", None),
+		];
+
+		for (url, root, parent, expected) in cases {
+			let (url, root, parent): (Url, Url, Url) = (url.parse()?, root.parse()?, parent.parse()?);
+			let rebased = url.rebase_keeping_structure(&root, &parent);
+			assert_eq!(
+				rebased.map(|u| format!("{u:?}").replace(r"\", "/")).as_deref(),
+				expected,
+				"{url:?} from {root:?} onto {parent:?}"
+			);
+		}
+
+		// Rebasing onto the parent of the URL is the same as `rebase`
+		let url: Url = "search://kw:2:2//a/b/c".parse()?;
+		let parent: Url = "sftp://work//uploads".parse()?;
+		assert_eq!(
+			url.rebase_keeping_structure(&url.parent_url().unwrap(), &parent),
+			Some(url.rebase(&parent))
+		);
+		Ok(())
+	}
+
+	#[cfg(unix)]
+	#[test]
+	fn test_rebase_non_utf8() -> Result<()> {
+		use std::os::unix::ffi::OsStrExt;
+
+		let name = OsStr::from_bytes(b"a\xffb");
+		for url in ["/a", "search://kw:1:1//a/b", "sftp://remote//a"] {
+			let url = url.parse::<Url>()?.join(name);
+			for parent in ["/c", "search://kw//c", "sftp://work//c"] {
+				let rebased = url.rebase(&parent.parse()?);
+				assert_eq!(rebased.name(), name, "{url:?} onto {parent}");
+				assert_eq!(rebased.urn(), Urn::new(name), "{url:?} onto {parent}");
+			}
+		}
+		Ok(())
+	}
+
+	#[test]
+	fn test_archive() -> Result<()> {
+		// The fragment form is the same as the port form
+		let u: Url = "archive:////tmp/foo.zip#docs/readme.md".parse()?;
+		assert_eq!(u, "archive://:2:1//tmp/foo.zip/docs/readme.md".parse()?);
+		assert_eq!(format!("{u:?}").replace(r"\", "/"), "archive://:2:1//tmp/foo.zip/docs/readme.md");
+		assert_eq!(u.base().as_os_str(), Path::new("/tmp/foo.zip").as_os_str());
+		assert_eq!("archive:////tmp/foo.zip#".parse::<Url>()?, "archive:////tmp/foo.zip".parse()?);
+
+		// Round trip
+		for s in [format!("{u:?}"), format!("{}", u.display())] {
+			assert_eq!(s.parse::<Url>()?, u, "{s}");
+		}
+
+		// The inner path is joined to and walked first
+		let u = u.join("a");
+		assert_eq!(format!("{u:?}").replace(r"\", "/"), "archive://:3:1//tmp/foo.zip/docs/readme.md/a");
+		let mut parents = vec![];
+		let mut p = Some(u);
+		while let Some(u) = p {
+			p = u.parent_url();
+			parents.push(format!("{u:?}").replace(r"\", "/"));
+		}
+		assert_eq!(
+			parents,
+			[
+				"archive://:3:1//tmp/foo.zip/docs/readme.md/a",
+				"archive://:2:1//tmp/foo.zip/docs/readme.md",
+				"archive://:1:1//tmp/foo.zip/docs",
+				"archive:////tmp/foo.zip",
+				"regular:///tmp",
+				"regular:///",
+			]
+		);
+
+		// The same path in different archives
+		let a: Url = "archive://:2:1//a/b.zip/c/d".parse()?;
+		let b: Url = "archive://:1:1//a/b.zip/c/d".parse()?;
+		assert_ne!(a, b);
+		assert_ne!(a.hash_u64(), b.hash_u64());
+
+		// The pair ends with the name of the entry
+		let u: Url = "archive://:2:2//a/b.zip/c/d".parse()?;
+		assert_eq!(
+			u.pair().map(|(p, n)| (format!("{p:?}"), n)),
+			Some(("archive://:1:1//a/b.zip/c".to_owned(), UrnBuf::from("d")))
+		);
+		let u: Url = "archive:////a/b.zip".parse()?;
+		assert_eq!(
+			u.pair().map(|(p, n)| (format!("{p:?}"), n)),
+			Some(("regular:///a".to_owned(), UrnBuf::from("b.zip")))
+		);
+
+		// A `#` in the path of the archive itself
+		let u = Url { loc: Loc::zeroed("/a/b#1.zip"), scheme: Scheme::Archive(String::new()) };
+		assert_eq!(format!("{u:?}"), "archive://://a/b#1.zip");
+		assert_eq!(format!("{u:?}").parse::<Url>()?, u);
+
+		assert!("archive:////a.zip#../b".parse::<Url>().is_err());
+		assert!("archive:////a.zip#/b".parse::<Url>().is_err());
+		Ok(())
+	}
+
+	#[test]
+	fn test_grep() -> Result<()> {
+		let u: Url = "grep://kw:1:1//tmp/a.rs#12".parse()?;
+		assert_eq!((u.is_grep(), u.frag()), (true, Some(12)));
+		assert_eq!(u.loc.as_os_str(), OsStr::new("/tmp/a.rs"));
+		assert_eq!(u.keyword().as_deref(), Some("kw"));
+
+		// Each line is a result on its own
+		let v: Url = "grep://kw:1:1//tmp/a.rs#13".parse()?;
+		assert_ne!(u, v);
+		assert_eq!(u.clone().with_frag(None), v.clone().with_frag(None));
+
+		// The line isn't carried over to other files
+		assert_eq!(format!("{:?}", u.parent_url().unwrap()), "grep://kw//tmp");
+		assert_eq!(format!("{:?}", u.join("b")), "grep://kw:2:2//tmp/a.rs/b");
+
+		// Only digits at the end make a line
+		let u: Url = "grep://kw:1:1//tmp/a#b".parse()?;
+		assert_eq!((u.frag(), u.loc.as_os_str()), (None, OsStr::new("/tmp/a#b")));
+		let u: Url = "grep~://kw:1:1//tmp/a%2312#3".parse()?;
+		assert_eq!((u.frag(), u.loc.as_os_str()), (Some(3), OsStr::new("/tmp/a#12")));
+		Ok(())
+	}
+
+	#[test]
+	fn test_history() -> Result<()> {
+		for s in ["recent://", "recent:///", "recent:////"] {
+			let u: Url = s.parse()?;
+			assert_eq!((u.is_history(), u.loc.as_os_str()), (true, OsStr::new("/")));
+			assert!(u.urn().is_empty() && u.parent_url().is_none());
+			assert!(u.is_listing_root());
+		}
+
+		// Entries are named by their whole path, so files of the same name don't clash
+		let u = "frecency://".parse::<Url>()?.join("tmp/a/b");
+		assert_eq!(format!("{u:?}"), "frecency://:3:3//tmp/a/b");
+		assert_eq!(u.urn().count(), 3);
+		assert_eq!(u.as_path(), Some(Path::new("/tmp/a/b")));
+		assert_eq!(format!("{:?}", u.parent_url().unwrap()), "frecency://:2:2//tmp/a");
+
+		assert!("recent://x//tmp".parse::<Url>().is_err());
+
+		// Duplicates are listed the same way
+		let u = "dupes://".parse::<Url>()?.join("tmp/a.jpg");
+		assert_eq!((u.is_dupes(), u.is_history()), (true, false));
+		assert!(u.is_listing() && !u.is_listing_root());
+		assert_eq!(u.as_path(), Some(Path::new("/tmp/a.jpg")));
+		assert!("dupes://x//tmp".parse::<Url>().is_err());
+
+		// And so are the files in the trash
+		let u = "trash://".parse::<Url>()?.join("home/a/.local/share/Trash/files/b");
+		assert_eq!(format!("{u:?}"), "trash://:7:7//home/a/.local/share/Trash/files/b");
+		assert!(u.is_trash() && !u.caps().can_write());
+		assert!("trash://x//tmp".parse::<Url>().is_err());
+		Ok(())
+	}
+
+	fn round_trip(urls: impl IntoIterator<Item = Url>, debug: bool) -> Result<()> {
+		for u in urls {
+			let mut forms = vec![EncodeSerde::from(&u).to_string()];
+			if debug {
+				forms.push(format!("{u:?}"));
+			}
+
+			for s in forms {
+				let v = Url::try_from(s.as_bytes())?;
+				assert_eq!(v, u, "{s:?}");
+				assert_eq!((v.uri(), v.urn()), (u.uri(), u.urn()), "{s:?}");
+			}
+		}
+		Ok(())
+	}
+
+	fn all_schemes(name: &OsStr) -> Vec<Url> {
+		let dir = PathBuf::from("/tmp").join(name);
+		let regular = Url::from(dir.join(name));
+		let archive =
+			Url { loc: Loc::zeroed(dir.join("a.zip")), scheme: Scheme::Archive("d #%".to_owned()) };
+		let sftp = Url { loc: Loc::from(dir.clone()), scheme: Scheme::Sftp("host #%:/".to_owned()) };
+
+		vec![
+			regular.clone(),
+			Url::from(Path::new(name)),
+			Url::from(&dir).into_search("kw #%").join(name),
+			Url::from(&dir).into_search(name.to_string_lossy()).join("a").join(name),
+			Url::from(&dir).to_grep("kw #%").join(name),
+			Url::from(&dir).to_grep("kw").join(name).with_frag(Some(12)),
+			archive.clone(),
+			archive.join(name).join("b"),
+			Url { loc: Loc::zeroed(dir.join(name)), scheme: Scheme::Archive(String::new()) },
+			sftp.clone(),
+			sftp.join(name),
+			"recent://".parse::<Url>().unwrap().join(dir.strip_prefix("/").unwrap().join(name)),
+			"frecency://".parse::<Url>().unwrap().join(dir.strip_prefix("/").unwrap()),
+			"dupes://".parse::<Url>().unwrap().join(dir.strip_prefix("/").unwrap().join(name)),
+			Url { loc: Loc::from(dir.join(name)), scheme: Scheme::Mtp("Pixel #%:1".to_owned()) },
+			Url { loc: Loc::from(dir.join(name)), scheme: Scheme::Vfs("gdrive".to_owned()) },
+		]
+	}
+
+	#[test]
+	fn test_round_trip() -> Result<()> {
+		let names = ["a#b", "#", "100%", "a%20b", "%23", "with space", "new\nline", "a://b", "日本語"];
+		for name in names {
+			round_trip(all_schemes(OsStr::new(name)), true)?;
+		}
+
+		// The fragment form
+		let u: Url = "archive:////tmp/a%23b.zip#c%23d/e".parse()?;
+		assert_eq!(u.base().as_os_str(), Path::new("/tmp/a%23b.zip").as_os_str());
+		round_trip([u], true)?;
+		let u: Url = "archive~:////tmp/a%23b.zip#c%23d/e".parse()?;
+		assert_eq!(u.base().as_os_str(), Path::new("/tmp/a#b.zip").as_os_str());
+		assert_eq!(u.urn().as_os_str(), OsStr::new("e"));
+		round_trip([u], true)?;
+		Ok(())
+	}
+
+	#[cfg(unix)]
+	#[test]
+	fn test_round_trip_non_utf8() -> Result<()> {
+		use std::os::unix::ffi::OsStrExt;
+
+		for name in [&b"a\xffb"[..], b"#\xfe%", b"\xc3 %23"] {
+			let urls = all_schemes(OsStr::from_bytes(name));
+			round_trip(urls, false)?;
+		}
+		Ok(())
+	}
+
 	#[test]
 	fn test_parent_url() -> anyhow::Result<()> {
 		let cases = [
@@ -374,6 +985,42 @@ mod tests {
 		Ok(())
 	}
 
+	#[test]
+	fn test_keyword() -> Result<()> {
+		for kw in ["foo bar", "#tag", "100%", "a%2Fb", "a:b/c", "日本語 ✓"] {
+			let u = Url::from(PathBuf::from("/root")).into_search(kw).join("a").join("b");
+			assert_eq!(u.keyword().as_deref(), Some(kw));
+			assert_eq!(u.parent_url().unwrap().keyword().as_deref(), Some(kw));
+
+			// Round trip through the string form
+			let s = format!("{u:?}");
+			let v: Url = s.parse()?;
+			assert_eq!(v, u, "{s}");
+			assert_eq!(v.keyword().as_deref(), Some(kw), "{s}");
+			assert_eq!(v.urn(), u.urn(), "{s}");
+		}
+
+		let u: Url = "search://kw:1:1//root/a".parse()?;
+		assert_eq!(u.clone().with_keyword("new").keyword().as_deref(), Some("new"));
+		assert_ne!(u.clone().with_keyword("new"), u);
+		assert_eq!(u.to_regular().with_keyword("new").keyword(), None);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_matches() -> Result<()> {
+		let u: Url = "search://kw:2:2//root/b/c.rs".parse()?;
+		assert!(u.matches(&UrnPattern::new("b/*.rs", true)?));
+		assert!(u.matches(&UrnPattern::new("**/c.rs", true)?));
+		assert!(!u.matches(&UrnPattern::new("c.rs", true)?));
+
+		let u: Url = "/root/b/c.rs".parse()?;
+		assert!(u.matches(&UrnPattern::new("c.rs", true)?));
+		assert!(!u.matches(&UrnPattern::new("b/*.rs", true)?));
+		Ok(())
+	}
+
 	#[test]
 	fn test_into_search() -> Result<()> {
 		const S: char = std::path::MAIN_SEPARATOR;
@@ -0,0 +1,118 @@
+// A version sort implementation in Rust.
+//
+// This is a port of `filevercmp()` of gnulib, which `ls -v` and `sort -V` of
+// GNU coreutils are based on, where numbers are compared by their values, `~`
+// sorts before anything, even the end, and suffixes such as `.tar.gz` are only
+// compared when the rest is equal.
+
+use std::cmp::Ordering;
+
+pub fn vercmp(left: &[u8], right: &[u8], insensitive: bool) -> Ordering {
+	// Hidden files come first
+	match (left.first() == Some(&b'.'), right.first() == Some(&b'.')) {
+		(true, false) => return Ordering::Less,
+		(false, true) => return Ordering::Greater,
+		_ => {}
+	}
+
+	let (l, r) = (&left[..prefix_len(left)], &right[..prefix_len(right)]);
+	match verrevcmp(l, r, insensitive) {
+		Ordering::Equal => verrevcmp(left, right, insensitive),
+		ord => ord,
+	}
+}
+
+// The length without the suffix matching `(\.[A-Za-z~][A-Za-z0-9~]*)*$`,
+// where the first byte is never a part of it
+fn prefix_len(s: &[u8]) -> usize {
+	let (mut i, mut len) = (0, 0);
+	while i < s.len() {
+		i += 1;
+		len = i;
+		while i + 1 < s.len() && s[i] == b'.' && (s[i + 1].is_ascii_alphabetic() || s[i + 1] == b'~') {
+			i += 2;
+			while i < s.len() && (s[i].is_ascii_alphanumeric() || s[i] == b'~') {
+				i += 1;
+			}
+		}
+	}
+	len
+}
+
+#[inline(always)]
+fn order(c: Option<&u8>, insensitive: bool) -> i32 {
+	match c {
+		None => -1,
+		Some(c) if c.is_ascii_digit() => 0,
+		Some(c) if c.is_ascii_alphabetic() && insensitive => c.to_ascii_lowercase() as i32,
+		Some(c) if c.is_ascii_alphabetic() => *c as i32,
+		Some(b'~') => -2,
+		Some(&c) => c as i32 + 256,
+	}
+}
+
+fn verrevcmp(left: &[u8], right: &[u8], insensitive: bool) -> Ordering {
+	let is_digit = |s: &[u8], i: usize| s.get(i).is_some_and(u8::is_ascii_digit);
+	let (mut li, mut ri) = (0, 0);
+
+	while li < left.len() || ri < right.len() {
+		// The non-digit parts, where letters sort before the other bytes
+		while (li < left.len() && !is_digit(left, li)) || (ri < right.len() && !is_digit(right, ri)) {
+			let (l, r) = (order(left.get(li), insensitive), order(right.get(ri), insensitive));
+			if l != r {
+				return l.cmp(&r);
+			}
+			li += 1;
+			ri += 1;
+		}
+
+		// The digit parts, by their values regardless of the leading zeros
+		while left.get(li) == Some(&b'0') {
+			li += 1;
+		}
+		while right.get(ri) == Some(&b'0') {
+			ri += 1;
+		}
+
+		let mut first_diff = Ordering::Equal;
+		while is_digit(left, li) && is_digit(right, ri) {
+			if first_diff == Ordering::Equal {
+				first_diff = left[li].cmp(&right[ri]);
+			}
+			li += 1;
+			ri += 1;
+		}
+
+		if is_digit(left, li) {
+			return Ordering::Greater;
+		} else if is_digit(right, ri) {
+			return Ordering::Less;
+		} else if first_diff != Ordering::Equal {
+			return first_diff;
+		}
+	}
+	Ordering::Equal
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn cmp(left: &[&str]) {
+		let mut right = left.to_vec();
+		right.reverse();
+		right.sort_by(|a, b| vercmp(a.as_bytes(), b.as_bytes(), false));
+		assert_eq!(left, right);
+	}
+
+	#[test]
+	fn test_vercmp() {
+		cmp(&[".hidden", "1.0~rc1", "1.0", "1.0.1", "1.2", "1.10", "v1.2", "v1.10", "v1.10a"]);
+		cmp(&["foo-1.2.tar.gz", "foo-1.10.tar.gz", "foo-1.10.tar.xz", "foo-1.10a.tar.gz"]);
+		cmp(&["a", "a.txt", "a1", "a2.txt", "a10", "b"]);
+
+		assert_eq!(vercmp(b"img007.png", b"img7.png", false), Ordering::Equal);
+		assert_eq!(vercmp(b"A2", b"a10", true), Ordering::Less);
+		assert_eq!(vercmp(b"a2", b"B10", false), Ordering::Greater);
+	}
+}
@@ -0,0 +1,82 @@
+use std::{fmt::Write, path::{Path, PathBuf}, sync::OnceLock};
+
+static ORIGIN: OnceLock<PathBuf> = OnceLock::new();
+
+/// Tells the terminal the directory we're in, with OSC 7, or OSC 9;9 on Windows.
+pub struct ReportCwd<'a>(pub &'a Path);
+
+impl ReportCwd<'_> {
+	#[cfg(unix)]
+	fn encode(path: &Path, f: &mut impl Write) -> std::fmt::Result {
+		use std::os::unix::ffi::OsStrExt;
+
+		for &b in path.as_os_str().as_bytes() {
+			if b.is_ascii_alphanumeric() || b"/-._~".contains(&b) {
+				f.write_char(b as char)?;
+			} else {
+				write!(f, "%{b:02X}")?;
+			}
+		}
+		Ok(())
+	}
+}
+
+impl crossterm::Command for ReportCwd<'_> {
+	fn write_ansi(&self, f: &mut impl Write) -> std::fmt::Result {
+		#[cfg(unix)]
+		{
+			write!(f, "\x1b]7;file://{}", yazi_shared::hostname().unwrap_or_default())?;
+			Self::encode(self.0, f)?;
+			f.write_str("\x1b\\")
+		}
+		#[cfg(windows)]
+		{
+			write!(f, "\x1b]9;9;{}\x1b\\", self.0.display())
+		}
+	}
+
+	#[cfg(windows)]
+	fn execute_winapi(&self) -> std::io::Result<()> { Ok(()) }
+}
+
+/// Reports the directory Yazi was started from, which is where the shell is, once we've left.
+pub struct RestoreCwd;
+
+impl RestoreCwd {
+	pub fn store() {
+		if let Ok(p) = std::env::current_dir() {
+			ORIGIN.set(p).ok();
+		}
+	}
+}
+
+impl crossterm::Command for RestoreCwd {
+	fn write_ansi(&self, f: &mut impl Write) -> std::fmt::Result {
+		match ORIGIN.get() {
+			Some(p) => ReportCwd(p).write_ansi(f),
+			None => Ok(()),
+		}
+	}
+
+	#[cfg(windows)]
+	fn execute_winapi(&self) -> std::io::Result<()> { Ok(()) }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_encode() {
+		let encode = |s: &str| {
+			let mut out = String::new();
+			ReportCwd::encode(Path::new(s), &mut out).unwrap();
+			out
+		};
+
+		assert_eq!(encode("/home/u/my-proj_1.0~"), "/home/u/my-proj_1.0~");
+		assert_eq!(encode("/tmp/a b/100%"), "/tmp/a%20b/100%25");
+		assert_eq!(encode("/tmp/naïve"), "/tmp/na%C3%AFve");
+		assert_eq!(encode("/tmp/\x1b]"), "/tmp/%1B%5D");
+	}
+}
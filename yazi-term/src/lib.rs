@@ -2,6 +2,6 @@
 
 yazi_macro::mod_pub!(tty);
 
-yazi_macro::mod_flat!(cursor r#if);
+yazi_macro::mod_flat!(cursor cwd r#if);
 
 pub fn init() { tty::init(); }
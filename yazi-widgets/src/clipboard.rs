@@ -1,7 +1,9 @@
 use std::ffi::OsString;
 
 use parking_lot::Mutex;
-use yazi_shared::RoCell;
+#[cfg(unix)]
+use yazi_shared::in_ssh_connection;
+use yazi_shared::{RoCell, url::UriList};
 
 pub static CLIPBOARD: RoCell<Clipboard> = RoCell::new();
 
@@ -16,7 +18,6 @@ impl Clipboard {
 		use std::os::unix::prelude::OsStringExt;
 
 		use tokio::process::Command;
-		use yazi_shared::in_ssh_connection;
 
 		if in_ssh_connection() {
 			return self.content.lock().clone();
@@ -55,10 +56,7 @@ impl Clipboard {
 
 	#[cfg(unix)]
 	pub async fn set(&self, s: impl AsRef<std::ffi::OsStr>) {
-		use std::process::Stdio;
-
 		use crossterm::execute;
-		use tokio::{io::AsyncWriteExt, process::Command};
 		use yazi_term::tty::TTY;
 
 		s.as_ref().clone_into(&mut self.content.lock());
@@ -73,23 +71,7 @@ impl Clipboard {
 		];
 
 		for (bin, args) in all {
-			let cmd = Command::new(bin)
-				.args(args)
-				.stdin(Stdio::piped())
-				.stdout(Stdio::null())
-				.stderr(Stdio::null())
-				.kill_on_drop(true)
-				.spawn();
-
-			let Ok(mut child) = cmd else { continue };
-
-			let mut stdin = child.stdin.take().unwrap();
-			if stdin.write_all(s.as_ref().as_encoded_bytes()).await.is_err() {
-				continue;
-			}
-			drop(stdin);
-
-			if child.wait().await.map(|s| s.success()).unwrap_or_default() {
+			if Self::pipe(bin, args, s.as_ref().as_encoded_bytes()).await {
 				break;
 			}
 		}
@@ -108,6 +90,176 @@ impl Clipboard {
 	}
 }
 
+// --- Files
+impl Clipboard {
+	/// The files copied or cut to the system clipboard by a file manager.
+	#[cfg(all(unix, not(target_os = "macos")))]
+	pub async fn get_files(&self) -> Option<UriList> {
+		use tokio::process::Command;
+
+		if in_ssh_connection() {
+			return None;
+		}
+
+		for mime in [UriList::MIME_GNOME, UriList::MIME] {
+			let all = [
+				("wl-paste", &["--no-newline", "--type", mime][..]),
+				("xclip", &["-o", "-selection", "clipboard", "-t", mime]),
+			];
+
+			for (bin, args) in all {
+				let Ok(output) = Command::new(bin).args(args).kill_on_drop(true).output().await else {
+					continue;
+				};
+				let list = UriList::parse(&String::from_utf8_lossy(&output.stdout));
+				if output.status.success() && !list.paths.is_empty() {
+					return Some(list);
+				}
+			}
+		}
+		None
+	}
+
+	#[cfg(target_os = "macos")]
+	pub async fn get_files(&self) -> Option<UriList> {
+		use tokio::process::Command;
+
+		const SCRIPT: &str = "ObjC.import('AppKit');
+const urls = $.NSPasteboard.generalPasteboard.readObjectsForClassesOptions(
+	$([$.NSURL]), $({ NSPasteboardURLReadingFileURLsOnlyKey: true }));
+urls.isNil() ? '' : urls.js.map(u => u.path.js).join('\\n')";
+
+		if in_ssh_connection() {
+			return None;
+		}
+
+		let output = Command::new("osascript")
+			.args(["-l", "JavaScript", "-e", SCRIPT])
+			.kill_on_drop(true)
+			.output()
+			.await
+			.ok()?;
+
+		let s = String::from_utf8_lossy(&output.stdout);
+		let paths: Vec<_> = s.lines().filter(|l| !l.is_empty()).map(Into::into).collect();
+		(output.status.success() && !paths.is_empty()).then(|| UriList::new(false, paths))
+	}
+
+	#[cfg(windows)]
+	pub async fn get_files(&self) -> Option<UriList> {
+		use clipboard_win::{Clipboard, raw};
+
+		tokio::task::spawn_blocking(|| {
+			let _clip = Clipboard::new_attempts(10).ok()?;
+			let mut paths = vec![];
+			raw::get_file_list_path(&mut paths).ok()?;
+
+			// `DROPEFFECT_MOVE` without `DROPEFFECT_COPY` means they were cut
+			let mut effect = vec![];
+			let cut = raw::register_format("Preferred DropEffect")
+				.is_some_and(|f| raw::get_vec(f.get(), &mut effect).is_ok())
+				&& effect.first().is_some_and(|&b| b & 3 == 2);
+
+			Some(UriList::new(cut, paths))
+		})
+		.await
+		.ok()
+		.flatten()
+	}
+
+	/// Puts the files into the system clipboard.
+	#[cfg(all(unix, not(target_os = "macos")))]
+	pub async fn set_files(&self, list: UriList) -> bool {
+		// `text/uri-list` has no idea of cutting, so the GNOME one is used instead
+		let (mime, s) = if list.cut {
+			(UriList::MIME_GNOME, list.encode(true))
+		} else {
+			(UriList::MIME, list.encode(false))
+		};
+
+		if in_ssh_connection() {
+			return false;
+		}
+
+		let all =
+			[("wl-copy", &["--type", mime][..]), ("xclip", &["-selection", "clipboard", "-t", mime])];
+		for (bin, args) in all {
+			if Self::pipe(bin, args, s.as_bytes()).await {
+				return true;
+			}
+		}
+		false
+	}
+
+	#[cfg(target_os = "macos")]
+	pub async fn set_files(&self, list: UriList) -> bool {
+		use tokio::process::Command;
+
+		const SCRIPT: &str = "ObjC.import('AppKit');
+function run(argv) {
+	const pb = $.NSPasteboard.generalPasteboard;
+	pb.clearContents;
+	pb.writeObjects($(argv.map(p => $.NSURL.fileURLWithPath(p))));
+}";
+
+		if in_ssh_connection() {
+			return false;
+		}
+
+		Command::new("osascript")
+			.args(["-l", "JavaScript", "-e", SCRIPT])
+			.args(&list.paths)
+			.kill_on_drop(true)
+			.output()
+			.await
+			.is_ok_and(|o| o.status.success())
+	}
+
+	#[cfg(windows)]
+	pub async fn set_files(&self, list: UriList) -> bool {
+		use clipboard_win::{Clipboard, raw};
+
+		let paths: Vec<_> = list.paths.iter().map(|p| p.to_string_lossy().into_owned()).collect();
+		let result = tokio::task::spawn_blocking(move || -> clipboard_win::SysResult<()> {
+			let _clip = Clipboard::new_attempts(10)?;
+			raw::set_file_list(&paths)?;
+
+			// `DROPEFFECT_MOVE` if cut, otherwise `DROPEFFECT_COPY | DROPEFFECT_LINK` as Explorer does
+			if let Some(f) = raw::register_format("Preferred DropEffect") {
+				raw::set_without_clear(f.get(), &(if list.cut { 2u32 } else { 5 }).to_le_bytes())?;
+			}
+			Ok(())
+		});
+
+		result.await.is_ok_and(|r| r.is_ok())
+	}
+
+	#[cfg(unix)]
+	async fn pipe(bin: &str, args: &[&str], data: &[u8]) -> bool {
+		use std::process::Stdio;
+
+		use tokio::{io::AsyncWriteExt, process::Command};
+
+		let cmd = Command::new(bin)
+			.args(args)
+			.stdin(Stdio::piped())
+			.stdout(Stdio::null())
+			.stderr(Stdio::null())
+			.kill_on_drop(true)
+			.spawn();
+
+		let Ok(mut child) = cmd else { return false };
+
+		let mut stdin = child.stdin.take().unwrap();
+		if stdin.write_all(data).await.is_err() {
+			return false;
+		}
+		drop(stdin);
+
+		child.wait().await.map(|s| s.success()).unwrap_or_default()
+	}
+}
+
 #[cfg(unix)]
 mod osc52 {
 	use std::ffi::OsStr;
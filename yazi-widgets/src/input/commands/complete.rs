@@ -16,11 +16,13 @@ const SEPARATOR: char = std::path::MAIN_SEPARATOR;
 impl Input {
 	pub fn complete(&mut self, opt: CompleteOpt) -> Result<Data> {
 		let (before, after) = self.partition();
-		let new = if let Some((prefix, _)) = before.rsplit_once(SEPARATOR) {
-			format!("{prefix}/{}{after}", opt.item.completable()).replace(SEPARATOR, MAIN_SEPARATOR_STR)
-		} else {
-			format!("{}{after}", opt.item.completable()).replace(SEPARATOR, MAIN_SEPARATOR_STR)
-		};
+		let item = opt.item.completable();
+		let new = match (opt.parent, before.rsplit_once(SEPARATOR)) {
+			(Some(parent), _) => format!("{parent}{item}{after}"),
+			(None, Some((prefix, _))) => format!("{prefix}/{item}{after}"),
+			(None, None) => format!("{item}{after}"),
+		}
+		.replace(SEPARATOR, MAIN_SEPARATOR_STR);
 
 		let snap = self.snap_mut();
 		if new == snap.value {